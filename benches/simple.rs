@@ -9,6 +9,15 @@ fn every_day(p: &HyperbolicParameters<AverageDaysTime>) {
     }
 }
 
+fn every_day_batch(
+    p: &HyperbolicParameters<AverageDaysTime>,
+    times: &[AverageDaysTime],
+    out: &mut [f64],
+) {
+    p.incremental_volumes_at_times(times, out);
+    black_box(out);
+}
+
 fn hyperbolic(c: &mut Criterion) {
     let mut group = c.benchmark_group("Hyperbolic");
 
@@ -30,6 +39,16 @@ fn hyperbolic(c: &mut Criterion) {
         |b, p| b.iter(|| black_box(every_day(p))),
     );
 
+    let times: Vec<_> = (0..parameters.incremental_duration().days as u64)
+        .map(|d| AverageDaysTime { days: d as f64 })
+        .collect();
+    let mut out = vec![0.; times.len()];
+    group.bench_with_input(
+        BenchmarkId::new("Daily Batch", "Incremental Volume"),
+        &parameters,
+        |b, p| b.iter(|| every_day_batch(p, &times, &mut out)),
+    );
+
     group.finish();
 }
 
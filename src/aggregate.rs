@@ -0,0 +1,229 @@
+use crate::{
+    CalendarDate, CalendarTime, DeclineCurveAnalysisError, DeclineTimeUnit, Forecast,
+    StreamForecast, TimeGrid, validate_finite,
+};
+use alloc::{vec, vec::Vec};
+
+/// How the forecasts of a group of wells are positioned on a common time grid.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProfileAlignment<Time: DeclineTimeUnit> {
+    /// Every forecast starts at time zero of the grid, e.g., for comparing wells from their first
+    /// production.
+    TimeZero,
+    /// Each forecast starts at the given time on the grid, with one time for each forecast.
+    StartTimes(Vec<Time>),
+}
+
+impl<Time: DeclineTimeUnit> ProfileAlignment<Time> {
+    /// Aligns forecasts by calendar date, where time zero of the grid is `grid_start` and each
+    /// forecast starts on its own date.
+    pub fn from_start_dates(grid_start: CalendarDate, start_dates: &[CalendarDate]) -> Self {
+        Self::StartTimes(
+            start_dates
+                .iter()
+                .map(|start_date| CalendarTime::between(grid_start, *start_date).to_unit())
+                .collect(),
+        )
+    }
+
+    fn start_time(&self, index: usize) -> f64 {
+        match self {
+            Self::TimeZero => 0.,
+            Self::StartTimes(start_times) => start_times[index].value(),
+        }
+    }
+}
+
+/// The combined production of a group of wells on a common time grid, which is net of injection
+/// for [`aggregate_streams`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct AggregateProfile<Time: DeclineTimeUnit> {
+    grid: TimeGrid<Time>,
+    rates: Vec<f64>,
+    volumes: Vec<f64>,
+}
+
+impl<Time: DeclineTimeUnit> AggregateProfile<Time> {
+    pub fn grid(&self) -> &TimeGrid<Time> {
+        &self.grid
+    }
+
+    /// The combined rate at each boundary of the grid. Each forecast only contributes while it's
+    /// producing, from its start (inclusive) to its end (exclusive).
+    pub fn rates(&self) -> &[f64] {
+        &self.rates
+    }
+
+    /// The combined volume in each period of the grid.
+    pub fn volumes(&self) -> &[f64] {
+        &self.volumes
+    }
+
+    /// The combined cumulative volume at the end of each period of the grid.
+    pub fn cumulative_volumes(&self) -> Vec<f64> {
+        self.volumes
+            .iter()
+            .scan(0., |total, volume| {
+                *total += volume;
+                Some(*total)
+            })
+            .collect()
+    }
+}
+
+/// Sums the rates and volumes of many forecasts on a common time grid, e.g., to build a
+/// field-level production profile.
+///
+/// This fails if the alignment doesn't have one finite start time for each forecast.
+pub fn aggregate<Time: DeclineTimeUnit>(
+    forecasts: &[Forecast<Time>],
+    grid: &TimeGrid<Time>,
+    alignment: &ProfileAlignment<Time>,
+) -> Result<AggregateProfile<Time>, DeclineCurveAnalysisError> {
+    aggregate_signed(
+        forecasts.iter().map(|forecast| (forecast, 1.)),
+        forecasts.len(),
+        grid,
+        alignment,
+    )
+}
+
+/// Sums the rates and volumes of production and injection streams on a common time grid, where
+/// injection is subtracted, e.g., to build a net water profile of a waterflood.
+///
+/// This fails if the alignment doesn't have one finite start time for each stream.
+pub fn aggregate_streams<Time: DeclineTimeUnit>(
+    streams: &[StreamForecast<Time>],
+    grid: &TimeGrid<Time>,
+    alignment: &ProfileAlignment<Time>,
+) -> Result<AggregateProfile<Time>, DeclineCurveAnalysisError> {
+    aggregate_signed(
+        streams
+            .iter()
+            .map(|stream| (stream.forecast(), stream.kind().sign())),
+        streams.len(),
+        grid,
+        alignment,
+    )
+}
+
+fn aggregate_signed<'a, Time: DeclineTimeUnit + 'a>(
+    forecasts: impl Iterator<Item = (&'a Forecast<Time>, f64)>,
+    count: usize,
+    grid: &TimeGrid<Time>,
+    alignment: &ProfileAlignment<Time>,
+) -> Result<AggregateProfile<Time>, DeclineCurveAnalysisError> {
+    if let ProfileAlignment::StartTimes(start_times) = alignment {
+        if start_times.len() != count {
+            return Err(DeclineCurveAnalysisError::InputLengthMismatch {
+                field: "start times",
+                count: start_times.len(),
+                expected: count,
+            });
+        }
+
+        for start_time in start_times {
+            validate_finite(start_time.value(), "start time")?;
+        }
+    }
+
+    let mut rates = vec![0.; grid.boundaries().len()];
+    let mut volumes = vec![0.; grid.period_count()];
+    let mut forecast_volumes = vec![0.; grid.period_count()];
+
+    for (index, (forecast, sign)) in forecasts.enumerate() {
+        add_to_profile(
+            forecast,
+            grid,
+            alignment.start_time(index),
+            sign,
+            (&mut rates, &mut volumes),
+            &mut forecast_volumes,
+        );
+    }
+
+    Ok(AggregateProfile {
+        grid: grid.clone(),
+        rates,
+        volumes,
+    })
+}
+
+/// Adds the rates and volumes of a forecast that starts at `start_time` on the grid, multiplied by
+/// `sign`, where `forecast_volumes` is scratch space for the volumes of the forecast.
+fn add_to_profile<Time: DeclineTimeUnit>(
+    forecast: &Forecast<Time>,
+    grid: &TimeGrid<Time>,
+    start_time: f64,
+    sign: f64,
+    (rates, volumes): (&mut [f64], &mut [f64]),
+    forecast_volumes: &mut [f64],
+) {
+    let incremental_duration = forecast.incremental_duration().value();
+
+    for (rate, boundary) in rates.iter_mut().zip(grid.boundaries()) {
+        let time = boundary.value() - start_time;
+        if time >= 0. && time < incremental_duration {
+            *rate += sign * forecast.rate_at_time(Time::from(time)).value();
+        }
+    }
+
+    forecast.volumes_on_grid(&grid.shifted(start_time), forecast_volumes);
+    for (volume, forecast_volume) in volumes.iter_mut().zip(forecast_volumes.iter()) {
+        *volume += sign * forecast_volume;
+    }
+}
+
+/// The difference in production between two forecasts of the same well, e.g., with and without a
+/// workover.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IncrementalProfile<Time: DeclineTimeUnit> {
+    profile: AggregateProfile<Time>,
+    incremental_eur: f64,
+}
+
+impl<Time: DeclineTimeUnit> IncrementalProfile<Time> {
+    /// The incremental rates and volumes on the grid, which are negative where the first forecast
+    /// produces less than the second.
+    pub fn profile(&self) -> &AggregateProfile<Time> {
+        &self.profile
+    }
+
+    /// The difference in EUR over the whole life of the forecasts, including any production past
+    /// the end of the grid.
+    pub fn incremental_eur(&self) -> f64 {
+        self.incremental_eur
+    }
+}
+
+/// Calculates the incremental production of `forecast` over `baseline` on a common time grid,
+/// where both forecasts start at time zero of the grid.
+pub fn difference<Time: DeclineTimeUnit>(
+    forecast: &Forecast<Time>,
+    baseline: &Forecast<Time>,
+    grid: &TimeGrid<Time>,
+) -> IncrementalProfile<Time> {
+    let mut rates = vec![0.; grid.boundaries().len()];
+    let mut volumes = vec![0.; grid.period_count()];
+    let mut forecast_volumes = vec![0.; grid.period_count()];
+
+    for (forecast, sign) in [(forecast, 1.), (baseline, -1.)] {
+        add_to_profile(
+            forecast,
+            grid,
+            0.,
+            sign,
+            (&mut rates, &mut volumes),
+            &mut forecast_volumes,
+        );
+    }
+
+    IncrementalProfile {
+        profile: AggregateProfile {
+            grid: grid.clone(),
+            rates,
+            volumes,
+        },
+        incremental_eur: forecast.cumulative_volume() - baseline.cumulative_volume(),
+    }
+}
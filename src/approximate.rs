@@ -0,0 +1,63 @@
+//! Implementations of the approximate equality traits of the `approx` and `float-cmp` crates,
+//! which compare each parameter in the same way as the `approx_eq` methods.
+
+use crate::{DeclineTimeUnit, Forecast, Segment};
+
+macro_rules! impl_approximate_equality {
+    ($($ty:ident),*) => {
+        $(
+            #[cfg(feature = "approx")]
+            impl<Time: DeclineTimeUnit> approx::AbsDiffEq for $ty<Time> {
+                type Epsilon = f64;
+
+                fn default_epsilon() -> f64 {
+                    f64::default_epsilon()
+                }
+
+                fn abs_diff_eq(&self, other: &Self, epsilon: f64) -> bool {
+                    self.parameters_match(other, |a, b| a.abs_diff_eq(&b, epsilon))
+                }
+            }
+
+            #[cfg(feature = "approx")]
+            impl<Time: DeclineTimeUnit> approx::RelativeEq for $ty<Time> {
+                fn default_max_relative() -> f64 {
+                    f64::default_max_relative()
+                }
+
+                fn relative_eq(&self, other: &Self, epsilon: f64, max_relative: f64) -> bool {
+                    self.parameters_match(other, |a, b| a.relative_eq(&b, epsilon, max_relative))
+                }
+            }
+
+            #[cfg(feature = "approx")]
+            impl<Time: DeclineTimeUnit> approx::UlpsEq for $ty<Time> {
+                fn default_max_ulps() -> u32 {
+                    f64::default_max_ulps()
+                }
+
+                fn ulps_eq(&self, other: &Self, epsilon: f64, max_ulps: u32) -> bool {
+                    self.parameters_match(other, |a, b| a.ulps_eq(&b, epsilon, max_ulps))
+                }
+            }
+
+            #[cfg(feature = "float-cmp")]
+            impl<Time: DeclineTimeUnit> float_cmp::ApproxEq for &$ty<Time> {
+                type Margin = float_cmp::F64Margin;
+
+                fn approx_eq<Margin: Into<float_cmp::F64Margin>>(
+                    self,
+                    other: Self,
+                    margin: Margin,
+                ) -> bool {
+                    let margin = margin.into();
+                    self.parameters_match(other, |a, b| {
+                        float_cmp::ApproxEq::approx_eq(a, b, margin)
+                    })
+                }
+            }
+        )*
+    };
+}
+
+impl_approximate_equality!(Forecast, Segment);
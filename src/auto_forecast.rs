@@ -0,0 +1,180 @@
+use crate::{
+    AverageYearsTime, BestFitResult, BindingConstraint, DeclineCurveAnalysisError, DeclineTimeUnit,
+    FitLoss, FitOptions, Forecast, HistoryTimeAxis, LinearParameters, ModelSelectionCriterion,
+    ProductionHistory, ProductionRate, QualityGates, Segment, SegmentationOptions,
+    TerminalDeclinePolicy, ValidationReport, detect_changepoints, fit_best,
+    validate_non_zero_positive_rate,
+};
+use alloc::vec::Vec;
+
+/// Options for [`auto_forecast`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct AutoForecastOptions<Time: DeclineTimeUnit> {
+    pub time_axis: HistoryTimeAxis,
+    /// The options for detecting changepoints, where the analysis window starts at the last
+    /// changepoint. `None` uses all of the history.
+    pub segmentation: Option<SegmentationOptions>,
+    pub criterion: ModelSelectionCriterion,
+    /// The options for fitting the analysis window, where weights are given for all samples of
+    /// the history.
+    pub fit: FitOptions,
+    /// When the fit switches to a terminal exponential decline.
+    pub terminal_decline: TerminalDeclinePolicy<Time>,
+    pub economic_limit_rate: ProductionRate<Time>,
+    /// The longest that the forecast continues after the last sample.
+    pub horizon: Time,
+    /// The requirements for the analysis window and the best fit, so that wells that fail can be
+    /// reviewed manually.
+    pub quality_gates: QualityGates<Time>,
+}
+
+impl<Time: DeclineTimeUnit> AutoForecastOptions<Time> {
+    /// Options for a forecast of up to 50 years after the history that ends at the economic limit,
+    /// which use a Huber loss so that outliers have less influence on the fit.
+    pub fn new(economic_limit_rate: ProductionRate<Time>) -> Self {
+        Self {
+            time_axis: HistoryTimeAxis::Producing,
+            segmentation: Some(SegmentationOptions::default()),
+            criterion: ModelSelectionCriterion::default(),
+            fit: FitOptions {
+                loss: FitLoss::Huber { threshold: 1.345 },
+                ..FitOptions::default()
+            },
+            terminal_decline: TerminalDeclinePolicy::default(),
+            economic_limit_rate,
+            horizon: AverageYearsTime { years: 50. }.to_unit(),
+            quality_gates: QualityGates::default(),
+        }
+    }
+}
+
+/// How an automatic forecast was built from the history.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AutoForecastReport<Time: DeclineTimeUnit> {
+    /// The number of samples with production in the history.
+    pub sample_count: usize,
+    /// The index of the first sample of each regime after the first (see
+    /// [`detect_changepoints`]).
+    pub changepoints: Vec<usize>,
+    /// The index of the first sample that was fitted.
+    pub window_start_index: usize,
+    /// The time of the first sample that was fitted, which is when the forecast starts.
+    pub start: Time,
+    /// The best fit to the analysis window, where times are relative to `start`.
+    pub fit: BestFitResult<Time>,
+    /// The limit that ended the forecast.
+    pub binding_constraint: BindingConstraint,
+    /// Quality control issues in the forecast.
+    pub validation: ValidationReport<Time>,
+}
+
+/// A forecast built from history by [`auto_forecast`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct AutoForecast<Time: DeclineTimeUnit> {
+    /// The forecast, which starts at the first sample that was fitted.
+    pub forecast: Forecast<Time>,
+    pub report: AutoForecastReport<Time>,
+}
+
+/// Extends a fitted segment to `duration`.
+pub(crate) fn extend_segment<Time: DeclineTimeUnit>(
+    segment: &Segment<Time>,
+    duration: Time,
+    economic_limit_rate: ProductionRate<Time>,
+) -> Result<Segment<Time>, DeclineCurveAnalysisError> {
+    // A linear decline reaches zero, so it's only extended to the economic limit if that comes
+    // first.
+    if let Segment::Linear(parameters) = segment
+        && let Ok(limited) = LinearParameters::from_final_rate(
+            parameters.initial_rate(),
+            parameters.decline_rate(),
+            economic_limit_rate,
+        )
+        && limited.incremental_duration().value() < duration.value()
+    {
+        return Ok(limited.into());
+    }
+
+    segment.with_incremental_duration(duration)
+}
+
+/// Builds a forecast from production history in one call, which is the usual workflow when
+/// forecasting many wells:
+///
+/// 1. Samples are taken from the history, skipping shut-ins (see [`ProductionHistory::samples`]).
+/// 2. The analysis window starts at the last changepoint (e.g., a refrac), then at the peak rate
+///    in the first half of the window so that ramp-up isn't fitted.
+/// 3. The best model is fitted to the window with [`fit_best`], after the window and before the
+///    fit are checked against the quality gates.
+/// 4. The fit is extended past the history with the terminal decline policy, then ends at the
+///    economic limit or the horizon.
+///
+/// The report records each step, along with quality control issues in the forecast.
+pub fn auto_forecast<Time: DeclineTimeUnit>(
+    history: &ProductionHistory,
+    options: &AutoForecastOptions<Time>,
+) -> Result<AutoForecast<Time>, DeclineCurveAnalysisError> {
+    validate_non_zero_positive_rate(options.economic_limit_rate.value(), "economic limit rate")?;
+    validate_non_zero_positive_rate(options.horizon.value(), "horizon")?;
+    options.terminal_decline.validate()?;
+
+    let samples = history.samples::<Time>(options.time_axis);
+    let Some((last_time, _)) = samples.last() else {
+        return Err(DeclineCurveAnalysisError::TooFewInputs {
+            field: "production record count",
+            count: 0,
+            minimum: 1,
+        });
+    };
+    options.fit.validate(samples.len())?;
+
+    let changepoints = match &options.segmentation {
+        Some(segmentation) => detect_changepoints(&samples, segmentation)?,
+        None => Vec::new(),
+    };
+
+    let regime_start = changepoints.last().copied().unwrap_or(0);
+    let ramp_up_end = regime_start + (samples.len() - regime_start).div_ceil(2);
+    let window_start_index = (regime_start..ramp_up_end)
+        .max_by(|a, b| samples[*a].1.value().total_cmp(&samples[*b].1.value()))
+        .unwrap_or(regime_start);
+
+    let start = samples[window_start_index].0;
+    let window: Vec<_> = samples[window_start_index..]
+        .iter()
+        .map(|(time, rate)| (Time::from(time.value() - start.value()), *rate))
+        .collect();
+    let fit_options = FitOptions {
+        weights: options
+            .fit
+            .weights
+            .as_ref()
+            .map(|weights| weights[window_start_index..].to_vec()),
+        ..options.fit.clone()
+    };
+    options.quality_gates.check_samples(&window)?;
+    let fit = fit_best(&window, options.criterion, &fit_options)?;
+    options.quality_gates.check_fit(&fit.best)?;
+
+    let extended = extend_segment(
+        &fit.best.parameters,
+        Time::from(last_time.value() - start.value() + options.horizon.value()),
+        options.economic_limit_rate,
+    )?;
+    let truncation = Forecast::with_terminal_decline(extended, options.terminal_decline)?
+        .truncate_at_limits(options.economic_limit_rate, None)?;
+    let validation = truncation.forecast.validate();
+
+    Ok(AutoForecast {
+        forecast: truncation.forecast,
+        report: AutoForecastReport {
+            sample_count: samples.len(),
+            changepoints,
+            window_start_index,
+            start,
+            fit,
+            binding_constraint: truncation.binding_constraint,
+            validation,
+        },
+    })
+}
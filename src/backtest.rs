@@ -0,0 +1,152 @@
+use crate::{
+    AutoForecast, AutoForecastOptions, CalendarDate, CalendarTime, DeclineCurveAnalysisError,
+    DeclineTimeUnit, ProductionHistory, ProductionRate, auto_forecast,
+};
+use alloc::vec::Vec;
+use core::ops::Bound;
+
+/// Error metrics of a forecast over production that it wasn't fitted to, where positive errors
+/// mean that the forecast is too high.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BacktestMetrics {
+    /// The mean absolute percentage error (MAPE) of the forecast rates, in percent.
+    pub mean_absolute_percentage_error: f64,
+    /// The mean percentage error of the forecast rates, in percent.
+    pub bias: f64,
+    /// The volume produced over the holdout.
+    pub actual_volume: f64,
+    /// The volume that the forecast predicted over the holdout.
+    pub forecast_volume: f64,
+    /// The error of the forecast volume relative to the actual volume, in percent.
+    pub cumulative_error: f64,
+}
+
+/// A sample of the holdout with the rate that the forecast predicted.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HoldoutSample<Time: DeclineTimeUnit> {
+    /// The time of the sample, on the time axis of the history.
+    pub time: Time,
+    pub actual_rate: ProductionRate<Time>,
+    /// The rate of the forecast, which is zero after the forecast ends.
+    pub forecast_rate: ProductionRate<Time>,
+}
+
+/// The result of fitting the early portion of a history and comparing the forecast to the rest.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Backtest<Time: DeclineTimeUnit> {
+    /// The forecast of the history before the holdout.
+    pub fit: AutoForecast<Time>,
+    /// The first month of the holdout.
+    pub holdout_start: CalendarDate,
+    /// The samples of the holdout, in the order of the history.
+    pub holdout: Vec<HoldoutSample<Time>>,
+    pub metrics: BacktestMetrics,
+}
+
+/// Hindcasts the last `holdout_months` calendar months of a history, so that forecasting options
+/// can be evaluated against known production (e.g., across a set of wells).
+///
+/// The history before the holdout is forecast with [`auto_forecast`], where weights in the fit
+/// options are given for its samples only. Shut-ins in the holdout are skipped, the same as for
+/// [`ProductionHistory::samples`].
+pub fn backtest<Time: DeclineTimeUnit>(
+    history: &ProductionHistory,
+    holdout_months: u32,
+    options: &AutoForecastOptions<Time>,
+) -> Result<Backtest<Time>, DeclineCurveAnalysisError> {
+    if holdout_months == 0 {
+        return Err(DeclineCurveAnalysisError::TooFewInputs {
+            field: "holdout months",
+            count: 0,
+            minimum: 1,
+        });
+    }
+    let Some(last_record) = history.records().last() else {
+        return Err(DeclineCurveAnalysisError::TooFewInputs {
+            field: "production record count",
+            count: 0,
+            minimum: 1,
+        });
+    };
+
+    let offset = i32::try_from(holdout_months)
+        .ok()
+        .and_then(|months| 1_i32.checked_sub(months))
+        .ok_or(DeclineCurveAnalysisError::InputOutOfRange {
+            field: "holdout months",
+            value: holdout_months.into(),
+            minimum: Bound::Included(1.),
+            maximum: Bound::Included(i32::MAX.into()),
+        })?;
+    let holdout_start = last_record.date.start_of_month().add_months(offset)?;
+    let training = ProductionHistory::new(
+        history.kind(),
+        history
+            .records()
+            .iter()
+            .filter(|record| record.date.start_of_month() < holdout_start)
+            .copied()
+            .collect(),
+    )?;
+    let fit = auto_forecast(&training, options)?;
+
+    // Samples and producing periods are in the same order, so the holdout is the samples of the
+    // periods that start in the holdout. The forecast predicts no production after it ends.
+    let start = fit.report.start;
+    let duration = fit.forecast.incremental_duration();
+    let since_start = |time: Time| Time::from(time.value() - start.value());
+    let to_time = |days: f64| since_start(CalendarTime { days }.to_unit::<Time>());
+    let mut holdout = Vec::new();
+    let mut actual_volume = 0.;
+    let mut forecast_volume = 0.;
+
+    for ((time, actual_rate), period) in history
+        .samples::<Time>(options.time_axis)
+        .into_iter()
+        .zip(history.producing_periods(options.time_axis))
+        .filter(|(_, period)| period.date >= holdout_start)
+    {
+        holdout.push(HoldoutSample {
+            time,
+            actual_rate,
+            forecast_rate: if since_start(time).value() > duration.value() {
+                ProductionRate::new(0.)
+            } else {
+                fit.forecast.rate_at_time(since_start(time))
+            },
+        });
+        actual_volume += period.rate_per_day * period.days;
+        forecast_volume += fit.forecast.incremental_volume_between(
+            to_time(period.start_days),
+            to_time(period.start_days + period.days),
+        );
+    }
+    if holdout.is_empty() {
+        return Err(DeclineCurveAnalysisError::TooFewInputs {
+            field: "holdout record count",
+            count: 0,
+            minimum: 1,
+        });
+    }
+
+    let count = holdout.len() as f64;
+    let relative_errors = || {
+        holdout.iter().map(|sample| {
+            (sample.forecast_rate.value() - sample.actual_rate.value()) / sample.actual_rate.value()
+        })
+    };
+    let metrics = BacktestMetrics {
+        mean_absolute_percentage_error: 100. * relative_errors().map(f64::abs).sum::<f64>() / count,
+        bias: 100. * relative_errors().sum::<f64>() / count,
+        actual_volume,
+        forecast_volume,
+        cumulative_error: 100. * (forecast_volume - actual_volume) / actual_volume,
+    };
+
+    Ok(Backtest {
+        fit,
+        holdout_start,
+        holdout,
+        metrics,
+    })
+}
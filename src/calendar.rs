@@ -0,0 +1,212 @@
+use crate::{
+    AverageDaysTime, DeclineCurveAnalysisError, DeclineTimeUnit, decline_rate::impl_time_unit_ops,
+};
+use core::ops::Bound;
+
+/// A proleptic Gregorian calendar date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CalendarDate {
+    year: i32,
+    month: u32,
+    day: u32,
+}
+
+/// Returns true if the year is a leap year in the Gregorian calendar, otherwise false.
+pub const fn is_leap_year(year: i32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+/// Returns the number of days in the month, where the month is between 1 and 12.
+pub const fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+impl CalendarDate {
+    pub fn new(year: i32, month: u32, day: u32) -> Result<Self, DeclineCurveAnalysisError> {
+        if !(1..=12).contains(&month) {
+            return Err(DeclineCurveAnalysisError::InputOutOfRange {
+                field: "month",
+                value: month as f64,
+                minimum: Bound::Included(1.),
+                maximum: Bound::Included(12.),
+            });
+        }
+
+        let max_day = days_in_month(year, month);
+        if !(1..=max_day).contains(&day) {
+            return Err(DeclineCurveAnalysisError::InputOutOfRange {
+                field: "day",
+                value: day as f64,
+                minimum: Bound::Included(1.),
+                maximum: Bound::Included(max_day as f64),
+            });
+        }
+
+        Ok(Self { year, month, day })
+    }
+
+    pub const fn year(&self) -> i32 {
+        self.year
+    }
+
+    pub const fn month(&self) -> u32 {
+        self.month
+    }
+
+    pub const fn day(&self) -> u32 {
+        self.day
+    }
+
+    /// The number of days since 1970-01-01.
+    ///
+    /// This uses the `days_from_civil` algorithm from Howard Hinnant.
+    pub const fn days_since_epoch(&self) -> i64 {
+        let year = if self.month <= 2 {
+            self.year as i64 - 1
+        } else {
+            self.year as i64
+        };
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let month = self.month as i64;
+        let day_of_year =
+            (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + self.day as i64 - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    /// Creates a date from the number of days since 1970-01-01.
+    ///
+    /// This uses the `civil_from_days` algorithm from Howard Hinnant. Fails if the year doesn't fit
+    /// in an `i32`.
+    pub fn from_days_since_epoch(days: i64) -> Result<Self, DeclineCurveAnalysisError> {
+        let out_of_range = || DeclineCurveAnalysisError::DateOutOfRange {
+            field: "days since epoch",
+            value: days,
+        };
+        let days = days.checked_add(719_468).ok_or_else(out_of_range)?;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days - era * 146_097;
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month_index = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+        let month = if month_index < 10 {
+            month_index + 3
+        } else {
+            month_index - 9
+        } as u32;
+        let year = i32::try_from(year_of_era + era * 400 + if month <= 2 { 1 } else { 0 })
+            .map_err(|_| out_of_range())?;
+
+        Ok(Self { year, month, day })
+    }
+
+    /// Adds a whole number of days to the date.
+    ///
+    /// Fails if the resulting year doesn't fit in an `i32`.
+    pub fn add_days(&self, days: i64) -> Result<Self, DeclineCurveAnalysisError> {
+        let days_since_epoch = self.days_since_epoch().checked_add(days).ok_or(
+            DeclineCurveAnalysisError::DateOutOfRange {
+                field: "days",
+                value: days,
+            },
+        )?;
+        Self::from_days_since_epoch(days_since_epoch)
+    }
+
+    /// Adds a whole number of months to the date. If the day doesn't exist in the resulting month,
+    /// it's clamped to the last day of that month (e.g., January 31 plus one month is February 28
+    /// or 29).
+    ///
+    /// Fails if the resulting year doesn't fit in an `i32`.
+    pub fn add_months(&self, months: i32) -> Result<Self, DeclineCurveAnalysisError> {
+        let month_index = self.year as i64 * 12 + (self.month as i64 - 1) + months as i64;
+        let year = i32::try_from(month_index.div_euclid(12)).map_err(|_| {
+            DeclineCurveAnalysisError::DateOutOfRange {
+                field: "months",
+                value: months.into(),
+            }
+        })?;
+        let month = (month_index.rem_euclid(12) + 1) as u32;
+        let day = self.day.min(days_in_month(year, month));
+
+        Ok(Self { year, month, day })
+    }
+
+    /// The first day of the month for this date.
+    pub const fn start_of_month(&self) -> Self {
+        Self {
+            year: self.year,
+            month: self.month,
+            day: 1,
+        }
+    }
+}
+
+/// A time unit of actual calendar days, which is used alongside [`CalendarDate`] so that periods
+/// land on real month and year boundaries instead of average approximations.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct CalendarTime {
+    pub days: f64,
+}
+
+impl From<f64> for CalendarTime {
+    fn from(days: f64) -> Self {
+        Self { days }
+    }
+}
+
+impl_time_unit_ops!(CalendarTime, days);
+
+impl DeclineTimeUnit for CalendarTime {
+    const LENGTH: f64 = 1.;
+
+    fn value(&self) -> f64 {
+        self.days
+    }
+}
+
+impl CalendarTime {
+    /// The elapsed time between two dates, which is negative if `end` is before `start`.
+    pub const fn between(start: CalendarDate, end: CalendarDate) -> Self {
+        Self {
+            days: (end.days_since_epoch() - start.days_since_epoch()) as f64,
+        }
+    }
+
+    /// Returns the elapsed time from `start` to the start of each following calendar month.
+    ///
+    /// The first boundary is the start of the month after `start`, so a partial first month is
+    /// handled correctly. The boundaries end when the year no longer fits in an `i32`.
+    pub fn month_boundaries(start: CalendarDate) -> impl Iterator<Item = Self> {
+        let first_month = start.start_of_month();
+        (1..).map_while(move |months| {
+            first_month
+                .add_months(months)
+                .ok()
+                .map(|boundary| Self::between(start, boundary))
+        })
+    }
+}
+
+impl From<CalendarTime> for AverageDaysTime {
+    fn from(value: CalendarTime) -> Self {
+        Self { days: value.days }
+    }
+}
+
+impl From<AverageDaysTime> for CalendarTime {
+    fn from(value: AverageDaysTime) -> Self {
+        Self { days: value.days }
+    }
+}
@@ -0,0 +1,113 @@
+use std::sync::Arc;
+
+use arrow::{
+    array::{Date32Array, Float64Array, RecordBatch, StringArray},
+    datatypes::{DataType, Field, Schema, SchemaRef},
+};
+use parquet::arrow::ArrowWriter;
+
+use crate::{
+    CalendarDate, CalendarTime, DeclineCurveAnalysisError, DeclineTimeUnit, WellForecast,
+    periods::period_volumes_between,
+};
+
+/// A well to include in a forecast schedule.
+#[derive(Debug, Clone, Copy)]
+pub struct ScheduleWell<'a, Time: DeclineTimeUnit> {
+    /// The name of the well, which is written to the `well` column.
+    pub name: &'a str,
+    /// The date that the forecast starts.
+    pub start_date: CalendarDate,
+    pub forecast: &'a WellForecast<Time>,
+}
+
+/// The schema of monthly forecast schedules, with one row per well, phase, and calendar month.
+///
+/// The `date` is the start of the period, which is the start date of the well for its first
+/// month. The `rate` is the average rate over the period in the time unit of the forecast.
+pub fn monthly_schedule_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new("well", DataType::Utf8, false),
+        Field::new("phase", DataType::Utf8, false),
+        Field::new("date", DataType::Date32, false),
+        Field::new("rate", DataType::Float64, false),
+        Field::new("volume", DataType::Float64, false),
+    ]))
+}
+
+fn write_failed(error: impl std::fmt::Display) -> DeclineCurveAnalysisError {
+    DeclineCurveAnalysisError::WriteFailed {
+        reason: error.to_string(),
+    }
+}
+
+/// Evaluates the volume of every phase of each well in each calendar month, as a record batch
+/// with the [`monthly_schedule_schema`].
+pub fn monthly_schedule_record_batch<Time: DeclineTimeUnit>(
+    wells: &[ScheduleWell<'_, Time>],
+) -> Result<RecordBatch, DeclineCurveAnalysisError> {
+    let mut well_names = Vec::new();
+    let mut phases = Vec::new();
+    let mut dates = Vec::new();
+    let mut rates = Vec::new();
+    let mut volumes = Vec::new();
+
+    for well in wells {
+        let start_date = well.start_date;
+        let first_month = start_date.start_of_month();
+        for phase in well.forecast.phases() {
+            let periods = period_volumes_between(
+                well.forecast.incremental_duration().value(),
+                CalendarTime::month_boundaries(start_date)
+                    .map(|boundary| boundary.to_unit::<Time>().value()),
+                |start, end| well.forecast.incremental_volume_between(phase, start, end),
+            );
+
+            for (index, period) in periods.enumerate() {
+                let date = if index == 0 {
+                    start_date
+                } else {
+                    i32::try_from(index)
+                        .ok()
+                        .and_then(|index| first_month.add_months(index).ok())
+                        .ok_or_else(|| write_failed("date is out of range for Arrow"))?
+                };
+                let date = i32::try_from(date.days_since_epoch())
+                    .map_err(|_| write_failed("date is out of range for Arrow"))?;
+
+                well_names.push(well.name);
+                phases.push(phase.name());
+                dates.push(date);
+                rates.push(period.volume / (period.end.value() - period.start.value()));
+                volumes.push(period.volume);
+            }
+        }
+    }
+
+    RecordBatch::try_new(
+        monthly_schedule_schema(),
+        vec![
+            Arc::new(StringArray::from(well_names)),
+            Arc::new(StringArray::from(phases)),
+            Arc::new(Date32Array::from(dates)),
+            Arc::new(Float64Array::from(rates)),
+            Arc::new(Float64Array::from(volumes)),
+        ],
+    )
+    .map_err(write_failed)
+}
+
+/// Writes the monthly schedule of each well (see [`monthly_schedule_record_batch`]) as a Parquet
+/// file.
+pub fn write_monthly_schedule_parquet<Time: DeclineTimeUnit, W: std::io::Write + Send>(
+    wells: &[ScheduleWell<'_, Time>],
+    writer: W,
+) -> Result<(), DeclineCurveAnalysisError> {
+    let batch = monthly_schedule_record_batch(wells)?;
+
+    let mut writer = ArrowWriter::try_new(writer, batch.schema(), None).map_err(write_failed)?;
+    writer.write(&batch).map_err(write_failed)?;
+    writer.close().map_err(write_failed)?;
+
+    Ok(())
+}
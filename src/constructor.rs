@@ -0,0 +1,486 @@
+use crate::{
+    DeclineCurveAnalysisError, DeclineTimeUnit, DelayParameters, ExponentialParameters,
+    FlatParameters, HarmonicParameters, HyperbolicParameters, LinearParameters,
+    LogisticGrowthParameters, NominalDeclineRate, PowerLawExponentialParameters, ProductionRate,
+    Segment,
+};
+
+/// How the end of a segment is specified when it's constructed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SegmentEnd<Time: DeclineTimeUnit> {
+    IncrementalDuration(Time),
+    IncrementalVolume(f64),
+    FinalRate(ProductionRate<Time>),
+    FinalDeclineRate(NominalDeclineRate<Time>),
+}
+
+/// The kinds of [`SegmentEnd`], which describe what a type of segment can be solved from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SegmentEndKind {
+    IncrementalDuration,
+    IncrementalVolume,
+    FinalRate,
+    FinalDeclineRate,
+}
+
+impl SegmentEndKind {
+    const ALL: &'static [Self] = &[
+        Self::IncrementalDuration,
+        Self::IncrementalVolume,
+        Self::FinalRate,
+        Self::FinalDeclineRate,
+    ];
+
+    fn description(self) -> &'static str {
+        match self {
+            Self::IncrementalDuration => "an incremental duration",
+            Self::IncrementalVolume => "an incremental volume",
+            Self::FinalRate => "a final rate",
+            Self::FinalDeclineRate => "a final decline rate",
+        }
+    }
+
+    fn unsupported<T>(self, segment: &'static str) -> Result<T, DeclineCurveAnalysisError> {
+        Err(DeclineCurveAnalysisError::UnsupportedSegmentEnd {
+            segment,
+            end: self.description(),
+        })
+    }
+}
+
+impl<Time: DeclineTimeUnit> SegmentEnd<Time> {
+    pub fn kind(&self) -> SegmentEndKind {
+        match self {
+            Self::IncrementalDuration(_) => SegmentEndKind::IncrementalDuration,
+            Self::IncrementalVolume(_) => SegmentEndKind::IncrementalVolume,
+            Self::FinalRate(_) => SegmentEndKind::FinalRate,
+            Self::FinalDeclineRate(_) => SegmentEndKind::FinalDeclineRate,
+        }
+    }
+
+    fn unsupported<T>(&self, segment: &'static str) -> Result<T, DeclineCurveAnalysisError> {
+        self.kind().unsupported(segment)
+    }
+}
+
+/// A uniform way to construct any type of segment, so that generic code can construct segments
+/// without knowing which constructors each type has.
+///
+/// Ends that a type of segment can't be constructed from return
+/// [`DeclineCurveAnalysisError::UnsupportedSegmentEnd`], e.g., a flat segment from a final rate.
+pub trait SegmentConstructor<Time: DeclineTimeUnit>: Sized {
+    /// The parameters of the segment other than its end, in the same order as the arguments of
+    /// its constructors, e.g., the initial rate, initial decline rate, and exponent of a
+    /// hyperbolic segment.
+    type Shape;
+
+    /// The kinds of ends that [`SegmentConstructor::from_end`] can solve for this type of
+    /// segment.
+    const SUPPORTED_ENDS: &'static [SegmentEndKind];
+
+    fn from_end(
+        shape: Self::Shape,
+        end: SegmentEnd<Time>,
+    ) -> Result<Self, DeclineCurveAnalysisError>;
+
+    /// The shape of this segment, which reconstructs it with [`SegmentConstructor::from_end`]
+    /// along with any of its supported ends.
+    fn shape(&self) -> Self::Shape;
+
+    /// Re-expresses this segment as its shape and an end of the given kind, e.g., so that a UI
+    /// can switch which parameter is solved for without re-deriving the others.
+    fn to_spec(
+        &self,
+        kind: SegmentEndKind,
+    ) -> Result<(Self::Shape, SegmentEnd<Time>), DeclineCurveAnalysisError>
+    where
+        Self: Clone + Into<Segment<Time>>,
+    {
+        let segment: Segment<Time> = self.clone().into();
+        Ok((self.shape(), segment.to_end(kind)?))
+    }
+
+    fn to_incremental_duration_spec(&self) -> (Self::Shape, Time)
+    where
+        Self: Clone + Into<Segment<Time>>,
+    {
+        let segment: Segment<Time> = self.clone().into();
+        (self.shape(), segment.incremental_duration())
+    }
+
+    fn to_incremental_volume_spec(&self) -> Result<(Self::Shape, f64), DeclineCurveAnalysisError>
+    where
+        Self: Clone + Into<Segment<Time>>,
+    {
+        let segment: Segment<Time> = self.clone().into();
+        segment.validate_supported_end(SegmentEndKind::IncrementalVolume)?;
+        Ok((self.shape(), segment.incremental_volume()))
+    }
+
+    fn to_final_rate_spec(
+        &self,
+    ) -> Result<(Self::Shape, ProductionRate<Time>), DeclineCurveAnalysisError>
+    where
+        Self: Clone + Into<Segment<Time>>,
+    {
+        let segment: Segment<Time> = self.clone().into();
+        segment.validate_supported_end(SegmentEndKind::FinalRate)?;
+        Ok((self.shape(), segment.final_rate()))
+    }
+
+    /// The final decline rate only reconstructs an exponential segment if its duration is zero,
+    /// because its decline rate never changes.
+    fn to_final_decline_rate_spec(
+        &self,
+    ) -> Result<(Self::Shape, NominalDeclineRate<Time>), DeclineCurveAnalysisError>
+    where
+        Self: Clone + Into<Segment<Time>>,
+    {
+        let segment: Segment<Time> = self.clone().into();
+        segment.validate_supported_end(SegmentEndKind::FinalDeclineRate)?;
+        Ok((self.shape(), segment.final_decline_rate()))
+    }
+}
+
+impl<Time: DeclineTimeUnit> SegmentConstructor<Time> for DelayParameters<Time> {
+    type Shape = ();
+    const SUPPORTED_ENDS: &'static [SegmentEndKind] = &[SegmentEndKind::IncrementalDuration];
+
+    fn shape(&self) {}
+
+    fn from_end((): (), end: SegmentEnd<Time>) -> Result<Self, DeclineCurveAnalysisError> {
+        match end {
+            SegmentEnd::IncrementalDuration(duration) => Self::from_incremental_duration(duration),
+            _ => end.unsupported("delay"),
+        }
+    }
+}
+
+impl<Time: DeclineTimeUnit> SegmentConstructor<Time> for FlatParameters<Time> {
+    type Shape = ProductionRate<Time>;
+    const SUPPORTED_ENDS: &'static [SegmentEndKind] = &[
+        SegmentEndKind::IncrementalDuration,
+        SegmentEndKind::IncrementalVolume,
+    ];
+
+    fn shape(&self) -> Self::Shape {
+        self.rate()
+    }
+
+    fn from_end(
+        rate: ProductionRate<Time>,
+        end: SegmentEnd<Time>,
+    ) -> Result<Self, DeclineCurveAnalysisError> {
+        match end {
+            SegmentEnd::IncrementalDuration(duration) => {
+                Self::from_incremental_duration(rate, duration)
+            }
+            SegmentEnd::IncrementalVolume(volume) => Self::from_incremental_volume(rate, volume),
+            _ => end.unsupported("flat"),
+        }
+    }
+}
+
+impl<Time: DeclineTimeUnit> SegmentConstructor<Time> for ExponentialParameters<Time> {
+    type Shape = (ProductionRate<Time>, NominalDeclineRate<Time>);
+    const SUPPORTED_ENDS: &'static [SegmentEndKind] = SegmentEndKind::ALL;
+
+    fn shape(&self) -> Self::Shape {
+        (self.initial_rate(), self.decline_rate())
+    }
+
+    fn from_end(
+        (initial_rate, decline_rate): Self::Shape,
+        end: SegmentEnd<Time>,
+    ) -> Result<Self, DeclineCurveAnalysisError> {
+        match end {
+            SegmentEnd::IncrementalDuration(duration) => {
+                Self::from_incremental_duration(initial_rate, decline_rate, duration)
+            }
+            SegmentEnd::IncrementalVolume(volume) => {
+                Self::from_incremental_volume(initial_rate, decline_rate, volume)
+            }
+            SegmentEnd::FinalRate(final_rate) => {
+                Self::from_final_rate(initial_rate, decline_rate, final_rate)
+            }
+            SegmentEnd::FinalDeclineRate(final_decline_rate) => {
+                Self::from_final_decline_rate(initial_rate, decline_rate, final_decline_rate)
+            }
+        }
+    }
+}
+
+impl<Time: DeclineTimeUnit> SegmentConstructor<Time> for HarmonicParameters<Time> {
+    type Shape = (ProductionRate<Time>, NominalDeclineRate<Time>);
+    const SUPPORTED_ENDS: &'static [SegmentEndKind] = SegmentEndKind::ALL;
+
+    fn shape(&self) -> Self::Shape {
+        (self.initial_rate(), self.initial_decline_rate())
+    }
+
+    fn from_end(
+        (initial_rate, initial_decline_rate): Self::Shape,
+        end: SegmentEnd<Time>,
+    ) -> Result<Self, DeclineCurveAnalysisError> {
+        match end {
+            SegmentEnd::IncrementalDuration(duration) => {
+                Self::from_incremental_duration(initial_rate, initial_decline_rate, duration)
+            }
+            SegmentEnd::IncrementalVolume(volume) => {
+                Self::from_incremental_volume(initial_rate, initial_decline_rate, volume)
+            }
+            SegmentEnd::FinalRate(final_rate) => {
+                Self::from_final_rate(initial_rate, initial_decline_rate, final_rate)
+            }
+            SegmentEnd::FinalDeclineRate(final_decline_rate) => Self::from_final_decline_rate(
+                initial_rate,
+                initial_decline_rate,
+                final_decline_rate,
+            ),
+        }
+    }
+}
+
+impl<Time: DeclineTimeUnit> SegmentConstructor<Time> for HyperbolicParameters<Time> {
+    type Shape = (ProductionRate<Time>, NominalDeclineRate<Time>, f64);
+    const SUPPORTED_ENDS: &'static [SegmentEndKind] = SegmentEndKind::ALL;
+
+    fn shape(&self) -> Self::Shape {
+        (
+            self.initial_rate(),
+            self.initial_decline_rate(),
+            self.exponent(),
+        )
+    }
+
+    fn from_end(
+        (initial_rate, initial_decline_rate, exponent): Self::Shape,
+        end: SegmentEnd<Time>,
+    ) -> Result<Self, DeclineCurveAnalysisError> {
+        match end {
+            SegmentEnd::IncrementalDuration(duration) => Self::from_incremental_duration(
+                initial_rate,
+                initial_decline_rate,
+                duration,
+                exponent,
+            ),
+            SegmentEnd::IncrementalVolume(volume) => {
+                Self::from_incremental_volume(initial_rate, initial_decline_rate, volume, exponent)
+            }
+            SegmentEnd::FinalRate(final_rate) => {
+                Self::from_final_rate(initial_rate, initial_decline_rate, final_rate, exponent)
+            }
+            SegmentEnd::FinalDeclineRate(final_decline_rate) => Self::from_final_decline_rate(
+                initial_rate,
+                initial_decline_rate,
+                final_decline_rate,
+                exponent,
+            ),
+        }
+    }
+}
+
+impl<Time: DeclineTimeUnit> SegmentConstructor<Time> for LinearParameters<Time> {
+    type Shape = (ProductionRate<Time>, NominalDeclineRate<Time>);
+    const SUPPORTED_ENDS: &'static [SegmentEndKind] = SegmentEndKind::ALL;
+
+    fn shape(&self) -> Self::Shape {
+        (self.initial_rate(), self.decline_rate())
+    }
+
+    fn from_end(
+        (initial_rate, decline_rate): Self::Shape,
+        end: SegmentEnd<Time>,
+    ) -> Result<Self, DeclineCurveAnalysisError> {
+        match end {
+            SegmentEnd::IncrementalDuration(duration) => {
+                Self::from_incremental_duration(initial_rate, decline_rate, duration)
+            }
+            SegmentEnd::IncrementalVolume(volume) => {
+                Self::from_incremental_volume(initial_rate, decline_rate, volume)
+            }
+            SegmentEnd::FinalRate(final_rate) => {
+                Self::from_final_rate(initial_rate, decline_rate, final_rate)
+            }
+            SegmentEnd::FinalDeclineRate(final_decline_rate) => {
+                Self::from_final_decline_rate(initial_rate, decline_rate, final_decline_rate)
+            }
+        }
+    }
+}
+
+impl<Time: DeclineTimeUnit> SegmentConstructor<Time> for LogisticGrowthParameters<Time> {
+    /// The carrying capacity, `a`, and the exponent.
+    type Shape = (f64, f64, f64);
+    const SUPPORTED_ENDS: &'static [SegmentEndKind] = &[
+        SegmentEndKind::IncrementalDuration,
+        SegmentEndKind::IncrementalVolume,
+        SegmentEndKind::FinalRate,
+    ];
+
+    fn shape(&self) -> Self::Shape {
+        (self.carrying_capacity(), self.a(), self.exponent())
+    }
+
+    fn from_end(
+        (carrying_capacity, a, exponent): Self::Shape,
+        end: SegmentEnd<Time>,
+    ) -> Result<Self, DeclineCurveAnalysisError> {
+        match end {
+            SegmentEnd::IncrementalDuration(duration) => {
+                Self::from_incremental_duration(carrying_capacity, a, duration, exponent)
+            }
+            SegmentEnd::IncrementalVolume(volume) => {
+                Self::from_incremental_volume(carrying_capacity, a, volume, exponent)
+            }
+            SegmentEnd::FinalRate(final_rate) => {
+                Self::from_final_rate(carrying_capacity, a, final_rate, exponent)
+            }
+            SegmentEnd::FinalDeclineRate(_) => end.unsupported("logistic growth"),
+        }
+    }
+}
+
+impl<Time: DeclineTimeUnit> SegmentConstructor<Time> for PowerLawExponentialParameters<Time> {
+    /// The initial rate, initial decline rate, infinite decline rate, and exponent.
+    type Shape = (ProductionRate<Time>, f64, NominalDeclineRate<Time>, f64);
+    const SUPPORTED_ENDS: &'static [SegmentEndKind] = SegmentEndKind::ALL;
+
+    fn shape(&self) -> Self::Shape {
+        (
+            self.initial_rate(),
+            self.initial_decline_rate(),
+            self.infinite_decline_rate(),
+            self.exponent(),
+        )
+    }
+
+    fn from_end(
+        (initial_rate, initial_decline_rate, infinite_decline_rate, exponent): Self::Shape,
+        end: SegmentEnd<Time>,
+    ) -> Result<Self, DeclineCurveAnalysisError> {
+        match end {
+            SegmentEnd::IncrementalDuration(duration) => Self::from_incremental_duration(
+                initial_rate,
+                initial_decline_rate,
+                infinite_decline_rate,
+                duration,
+                exponent,
+            ),
+            SegmentEnd::IncrementalVolume(volume) => Self::from_incremental_volume(
+                initial_rate,
+                initial_decline_rate,
+                infinite_decline_rate,
+                volume,
+                exponent,
+            ),
+            SegmentEnd::FinalRate(final_rate) => Self::from_final_rate(
+                initial_rate,
+                initial_decline_rate,
+                infinite_decline_rate,
+                final_rate,
+                exponent,
+            ),
+            SegmentEnd::FinalDeclineRate(final_decline_rate) => Self::from_final_decline_rate(
+                initial_rate,
+                initial_decline_rate,
+                infinite_decline_rate,
+                final_decline_rate,
+                exponent,
+            ),
+        }
+    }
+}
+
+impl<Time: DeclineTimeUnit> Segment<Time> {
+    /// The kinds of ends that this type of segment can be solved from with
+    /// [`SegmentConstructor::from_end`].
+    pub fn supported_solves(&self) -> &'static [SegmentEndKind] {
+        match self {
+            Self::Delay(_) => DelayParameters::<Time>::SUPPORTED_ENDS,
+            Self::Exponential(_) => ExponentialParameters::<Time>::SUPPORTED_ENDS,
+            Self::Flat(_) => FlatParameters::<Time>::SUPPORTED_ENDS,
+            Self::Harmonic(_) => HarmonicParameters::<Time>::SUPPORTED_ENDS,
+            Self::Hyperbolic(_) => HyperbolicParameters::<Time>::SUPPORTED_ENDS,
+            Self::Linear(_) => LinearParameters::<Time>::SUPPORTED_ENDS,
+            Self::LogisticGrowth(_) => LogisticGrowthParameters::<Time>::SUPPORTED_ENDS,
+            Self::PowerLawExponential(_) => PowerLawExponentialParameters::<Time>::SUPPORTED_ENDS,
+        }
+    }
+
+    /// Re-expresses the end of the segment as the given kind, which reconstructs the segment with
+    /// [`SegmentConstructor::from_end`] along with the shape of its parameters.
+    pub fn to_end(
+        &self,
+        kind: SegmentEndKind,
+    ) -> Result<SegmentEnd<Time>, DeclineCurveAnalysisError> {
+        self.validate_supported_end(kind)?;
+
+        Ok(match kind {
+            SegmentEndKind::IncrementalDuration => {
+                SegmentEnd::IncrementalDuration(self.incremental_duration())
+            }
+            SegmentEndKind::IncrementalVolume => {
+                SegmentEnd::IncrementalVolume(self.incremental_volume())
+            }
+            SegmentEndKind::FinalRate => SegmentEnd::FinalRate(self.final_rate()),
+            SegmentEndKind::FinalDeclineRate => {
+                SegmentEnd::FinalDeclineRate(self.final_decline_rate())
+            }
+        })
+    }
+
+    /// Reconstructs the segment with the same shape and a different duration, e.g., to extend a
+    /// fitted segment past the last sample.
+    pub(crate) fn with_incremental_duration(
+        &self,
+        duration: Time,
+    ) -> Result<Self, DeclineCurveAnalysisError> {
+        let end = SegmentEnd::IncrementalDuration(duration);
+
+        Ok(match self {
+            Self::Delay(parameters) => DelayParameters::from_end(parameters.shape(), end)?.into(),
+            Self::Exponential(parameters) => {
+                ExponentialParameters::from_end(parameters.shape(), end)?.into()
+            }
+            Self::Flat(parameters) => FlatParameters::from_end(parameters.shape(), end)?.into(),
+            Self::Harmonic(parameters) => {
+                HarmonicParameters::from_end(parameters.shape(), end)?.into()
+            }
+            Self::Hyperbolic(parameters) => {
+                HyperbolicParameters::from_end(parameters.shape(), end)?.into()
+            }
+            Self::Linear(parameters) => LinearParameters::from_end(parameters.shape(), end)?.into(),
+            Self::LogisticGrowth(parameters) => {
+                LogisticGrowthParameters::from_end(parameters.shape(), end)?.into()
+            }
+            Self::PowerLawExponential(parameters) => {
+                PowerLawExponentialParameters::from_end(parameters.shape(), end)?.into()
+            }
+        })
+    }
+
+    fn final_decline_rate(&self) -> NominalDeclineRate<Time> {
+        self.decline_rate_at_time(self.incremental_duration())
+    }
+
+    fn validate_supported_end(
+        &self,
+        kind: SegmentEndKind,
+    ) -> Result<(), DeclineCurveAnalysisError> {
+        if self.supported_solves().contains(&kind) {
+            return Ok(());
+        }
+
+        kind.unsupported(match self {
+            Self::Delay(_) => "delay",
+            Self::Exponential(_) => "exponential",
+            Self::Flat(_) => "flat",
+            Self::Harmonic(_) => "harmonic",
+            Self::Hyperbolic(_) => "hyperbolic",
+            Self::Linear(_) => "linear",
+            Self::LogisticGrowth(_) => "logistic growth",
+            Self::PowerLawExponential(_) => "power law exponential",
+        })
+    }
+}
@@ -0,0 +1,168 @@
+use crate::{
+    DeclineCurveAnalysisError, DeclineTimeUnit, DelayParameters, ExponentialParameters,
+    FlatParameters, HarmonicParameters, HyperbolicParameters, LinearParameters,
+    LogisticGrowthParameters, NominalDeclineRate, PowerLawExponentialParameters, ProductionRate,
+    RateTruncation, Segment, validate_non_zero_positive_rate,
+};
+
+/// A segment whose rates and volumes are scaled by a constant availability factor, e.g., 0.92 for
+/// a well that's producing 92% of the time.
+///
+/// The underlying decline parameters are unchanged, so decline rates and durations are the same as
+/// the uncurtailed segment.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CurtailedSegment<S> {
+    segment: S,
+    availability_factor: f64,
+}
+
+impl<S> CurtailedSegment<S> {
+    /// Wraps a segment with an availability factor, which must be greater than zero and at most
+    /// one.
+    pub fn new(segment: S, availability_factor: f64) -> Result<Self, DeclineCurveAnalysisError> {
+        validate_non_zero_positive_rate(availability_factor, "availability factor")?;
+        if availability_factor > 1. {
+            return Err(DeclineCurveAnalysisError::InputGreaterThanOne {
+                field: "availability factor",
+                value: availability_factor,
+            });
+        }
+
+        Ok(Self {
+            segment,
+            availability_factor,
+        })
+    }
+
+    /// The underlying segment without the availability factor applied.
+    pub fn segment(&self) -> &S {
+        &self.segment
+    }
+
+    pub fn into_segment(self) -> S {
+        self.segment
+    }
+
+    pub fn availability_factor(&self) -> f64 {
+        self.availability_factor
+    }
+
+    fn scale_rate<Time: DeclineTimeUnit>(
+        &self,
+        rate: ProductionRate<Time>,
+    ) -> ProductionRate<Time> {
+        ProductionRate::new(rate.value() * self.availability_factor)
+    }
+
+    fn unscale_rate<Time: DeclineTimeUnit>(
+        &self,
+        rate: ProductionRate<Time>,
+    ) -> ProductionRate<Time> {
+        ProductionRate::new(rate.value() / self.availability_factor)
+    }
+}
+
+macro_rules! impl_curtailed_segment {
+    ($($segment:ident),* $(,)?) => {
+        $(
+            impl<Time: DeclineTimeUnit> CurtailedSegment<$segment<Time>> {
+                pub fn incremental_duration(&self) -> Time {
+                    self.segment.incremental_duration()
+                }
+
+                pub fn rate_at_time(&self, time: Time) -> ProductionRate<Time> {
+                    self.scale_rate(self.segment.rate_at_time(time))
+                }
+
+                pub fn final_rate(&self) -> ProductionRate<Time> {
+                    self.scale_rate(self.segment.final_rate())
+                }
+
+                pub fn rates_at_times(&self, times: &[Time], out: &mut [f64]) {
+                    self.segment.rates_at_times(times, out);
+                    out.iter_mut().for_each(|rate| *rate *= self.availability_factor);
+                }
+
+                pub fn decline_rate_at_time(&self, time: Time) -> NominalDeclineRate<Time> {
+                    self.segment.decline_rate_at_time(time)
+                }
+
+                pub fn incremental_volume_at_time(&self, time: Time) -> f64 {
+                    self.segment.incremental_volume_at_time(time) * self.availability_factor
+                }
+
+                pub fn incremental_volume_between(&self, start: Time, end: Time) -> f64 {
+                    self.segment.incremental_volume_between(start, end) * self.availability_factor
+                }
+
+                pub fn incremental_volume(&self) -> f64 {
+                    self.segment.incremental_volume() * self.availability_factor
+                }
+
+                pub fn incremental_volumes_at_times(&self, times: &[Time], out: &mut [f64]) {
+                    self.segment.incremental_volumes_at_times(times, out);
+                    out.iter_mut().for_each(|volume| *volume *= self.availability_factor);
+                }
+
+                pub fn rates_at_times_f32(&self, times: &[f32], out: &mut [f32]) {
+                    self.segment.rates_at_times_f32(times, out);
+                    let availability_factor = self.availability_factor as f32;
+                    out.iter_mut().for_each(|rate| *rate *= availability_factor);
+                }
+
+                pub fn incremental_volumes_at_times_f32(&self, times: &[f32], out: &mut [f32]) {
+                    self.segment.incremental_volumes_at_times_f32(times, out);
+                    let availability_factor = self.availability_factor as f32;
+                    out.iter_mut().for_each(|volume| *volume *= availability_factor);
+                }
+
+                pub fn time_at_rate(
+                    &self,
+                    rate: ProductionRate<Time>,
+                ) -> Result<Time, DeclineCurveAnalysisError> {
+                    self.segment.time_at_rate(self.unscale_rate(rate))
+                }
+
+                pub fn time_at_incremental_volume(
+                    &self,
+                    incremental_volume: f64,
+                ) -> Result<Time, DeclineCurveAnalysisError> {
+                    self.segment
+                        .time_at_incremental_volume(incremental_volume / self.availability_factor)
+                }
+
+                /// Returns a copy that ends at the first time the curtailed rate falls to the
+                /// economic limit, along with the curtailed volume and duration that were removed.
+                pub fn truncate_at_rate(
+                    &self,
+                    economic_limit_rate: ProductionRate<Time>,
+                ) -> Result<RateTruncation<Self, Time>, DeclineCurveAnalysisError> {
+                    let truncation = self
+                        .segment
+                        .truncate_at_rate(self.unscale_rate(economic_limit_rate))?;
+
+                    Ok(RateTruncation {
+                        parameters: Self {
+                            segment: truncation.parameters,
+                            availability_factor: self.availability_factor,
+                        },
+                        truncated_volume: truncation.truncated_volume * self.availability_factor,
+                        truncated_duration: truncation.truncated_duration,
+                    })
+                }
+            }
+        )*
+    };
+}
+
+impl_curtailed_segment!(
+    Segment,
+    DelayParameters,
+    ExponentialParameters,
+    FlatParameters,
+    HarmonicParameters,
+    HyperbolicParameters,
+    LinearParameters,
+    LogisticGrowthParameters,
+    PowerLawExponentialParameters,
+);
@@ -0,0 +1,82 @@
+use chrono::{Datelike, NaiveDate};
+
+use crate::{
+    CalendarDate, CalendarTime, DeclineTimeUnit, Forecast, PeriodVolume, ProductionRate,
+    periods::period_volumes,
+};
+
+/// A forecast anchored to a start date, so it can be evaluated directly with calendar dates.
+///
+/// Dates are converted to elapsed time using the actual number of days between them, so leap
+/// years and varying month lengths are handled exactly.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DatedForecast<Time: DeclineTimeUnit> {
+    start_date: NaiveDate,
+    forecast: Forecast<Time>,
+}
+
+impl<Time: DeclineTimeUnit> DatedForecast<Time> {
+    pub fn new(start_date: NaiveDate, forecast: Forecast<Time>) -> Self {
+        Self {
+            start_date,
+            forecast,
+        }
+    }
+
+    pub fn start_date(&self) -> NaiveDate {
+        self.start_date
+    }
+
+    pub fn forecast(&self) -> &Forecast<Time> {
+        &self.forecast
+    }
+
+    /// The elapsed time from the start date to the given date, which is negative for dates
+    /// before the start date.
+    pub fn time_at_date(&self, date: NaiveDate) -> Time {
+        let days = date.signed_duration_since(self.start_date).num_days() as f64;
+        Time::from(days / Time::LENGTH)
+    }
+
+    /// The rate at the start of the given date.
+    pub fn rate_at_date(&self, date: NaiveDate) -> ProductionRate<Time> {
+        self.forecast.rate_at_time(self.time_at_date(date))
+    }
+
+    /// The cumulative volume from the start date to the start of the given date.
+    pub fn volume_to_date(&self, date: NaiveDate) -> f64 {
+        self.forecast
+            .incremental_volume_at_time(self.time_at_date(date))
+    }
+
+    /// Returns the volume produced in each calendar month over the whole forecast. The first period
+    /// is shortened if the start date isn't the first day of a month.
+    pub fn monthly_volumes(&self) -> impl Iterator<Item = PeriodVolume<Time>> + '_ {
+        let boundaries = CalendarTime::month_boundaries(CalendarDate::from(self.start_date))
+            .map(|boundary| boundary.days / Time::LENGTH);
+
+        period_volumes(self.forecast.segments(), boundaries)
+    }
+
+    /// The volume produced from the start of `start` to the start of `end`. This is negative if
+    /// `end` is before `start`.
+    pub fn volume_between_dates(&self, start: NaiveDate, end: NaiveDate) -> f64 {
+        self.forecast
+            .incremental_volume_between(self.time_at_date(start), self.time_at_date(end))
+    }
+}
+
+impl From<NaiveDate> for CalendarDate {
+    fn from(date: NaiveDate) -> Self {
+        CalendarDate::new(date.year(), date.month(), date.day())
+            .expect("chrono dates are always valid calendar dates")
+    }
+}
+
+impl From<CalendarDate> for NaiveDate {
+    fn from(date: CalendarDate) -> Self {
+        NaiveDate::from_ymd_opt(date.year(), date.month(), date.day())
+            .expect("calendar dates are always valid chrono dates")
+    }
+}
@@ -0,0 +1,68 @@
+use crate::{
+    DeclineCurveAnalysisError, DeclineTimeUnit, NominalDeclineRate, SecantEffectiveDeclineRate,
+    TangentEffectiveDeclineRate, validate_finite,
+};
+use alloc::vec::Vec;
+
+/// Equivalent decline rates for one nominal decline rate.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeclinationTableRow<Time: DeclineTimeUnit> {
+    pub nominal: NominalDeclineRate<Time>,
+    pub tangent_effective: TangentEffectiveDeclineRate<Time>,
+    /// The secant effective decline rate for each exponent, in the order of the table's exponents.
+    pub secant_effective: Vec<SecantEffectiveDeclineRate<Time>>,
+}
+
+/// A table of equivalent nominal, tangent effective, and secant effective decline rates, like the
+/// conversion tables in the SPEE monographs.
+///
+/// Secant effective decline rates depend on the hyperbolic exponent, so each row has one for each
+/// exponent of the table.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeclinationTable<Time: DeclineTimeUnit> {
+    exponents: Vec<f64>,
+    rows: Vec<DeclinationTableRow<Time>>,
+}
+
+impl<Time: DeclineTimeUnit> DeclinationTable<Time> {
+    /// Creates a table with a row for each nominal decline rate and a secant effective column for
+    /// each exponent.
+    pub fn new(
+        nominal_rates: impl IntoIterator<Item = NominalDeclineRate<Time>>,
+        exponents: &[f64],
+    ) -> Result<Self, DeclineCurveAnalysisError> {
+        for exponent in exponents {
+            validate_finite(*exponent, "exponent")?;
+        }
+
+        let rows = nominal_rates
+            .into_iter()
+            .map(|nominal| {
+                Ok(DeclinationTableRow {
+                    nominal,
+                    tangent_effective: nominal.to_tangent_effective()?,
+                    secant_effective: exponents
+                        .iter()
+                        .map(|exponent| nominal.to_secant_effective(*exponent))
+                        .collect::<Result<_, _>>()?,
+                })
+            })
+            .collect::<Result<_, DeclineCurveAnalysisError>>()?;
+
+        Ok(Self {
+            exponents: exponents.to_vec(),
+            rows,
+        })
+    }
+
+    /// The exponents of the secant effective columns.
+    pub fn exponents(&self) -> &[f64] {
+        &self.exponents
+    }
+
+    pub fn rows(&self) -> &[DeclinationTableRow<Time>] {
+        &self.rows
+    }
+}
@@ -1,10 +1,16 @@
-use crate::DeclineCurveAnalysisError;
-use std::marker::PhantomData;
+#[cfg(not(feature = "std"))]
+use crate::math::FloatMath;
+use crate::{DeclineCurveAnalysisError, ProductionRate, VolumeUnit, math, validate_finite};
+use core::{
+    cmp::Ordering,
+    marker::PhantomData,
+    ops::{Add, Div, Mul, Neg, Sub},
+};
 
 /// A time unit for decline parameters. The base unit is defined in terms of average days, where an
 /// average year is 365.25 days. This allows for conversions between different time units, even
 /// with different average year lengths (e.g., 365 days in some software).
-pub trait DeclineTimeUnit: Copy + Clone + std::fmt::Debug + PartialEq + From<f64> {
+pub trait DeclineTimeUnit: Copy + Clone + core::fmt::Debug + PartialEq + From<f64> {
     const LENGTH: f64;
 
     fn value(&self) -> f64;
@@ -18,8 +24,63 @@ pub trait DeclineTimeUnit: Copy + Clone + std::fmt::Debug + PartialEq + From<f64
     }
 }
 
+/// Implements the arithmetic that all time units share, which operates on the values of the times.
+macro_rules! impl_time_unit_ops {
+    ($time:ident $(<const $length:ident: u64>)?, $field:ident) => {
+        impl$(<const $length: u64>)? core::ops::Add for $time$(<$length>)? {
+            type Output = Self;
+
+            fn add(self, other: Self) -> Self {
+                Self {
+                    $field: self.$field + other.$field,
+                }
+            }
+        }
+
+        impl$(<const $length: u64>)? core::ops::Sub for $time$(<$length>)? {
+            type Output = Self;
+
+            fn sub(self, other: Self) -> Self {
+                Self {
+                    $field: self.$field - other.$field,
+                }
+            }
+        }
+
+        impl$(<const $length: u64>)? core::ops::Mul<f64> for $time$(<$length>)? {
+            type Output = Self;
+
+            fn mul(self, factor: f64) -> Self {
+                Self {
+                    $field: self.$field * factor,
+                }
+            }
+        }
+
+        impl$(<const $length: u64>)? core::ops::Div<f64> for $time$(<$length>)? {
+            type Output = Self;
+
+            fn div(self, divisor: f64) -> Self {
+                Self {
+                    $field: self.$field / divisor,
+                }
+            }
+        }
+    };
+}
+
+pub(crate) use impl_time_unit_ops;
+
+impl_time_unit_ops!(AverageYearsTime, years);
+impl_time_unit_ops!(AverageDaysTime, days);
+impl_time_unit_ops!(AverageMonthsTime, months);
+impl_time_unit_ops!(AverageQuartersTime, quarters);
+impl_time_unit_ops!(CustomTime<const HUNDREDTHS_OF_A_DAY: u64>, value);
+
 /// Average year length of 365.25 days.
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct AverageYearsTime {
     pub years: f64,
 }
@@ -39,7 +100,9 @@ impl DeclineTimeUnit for AverageYearsTime {
 }
 
 /// Average day length of 1 day.
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct AverageDaysTime {
     pub days: f64,
 }
@@ -58,6 +121,132 @@ impl DeclineTimeUnit for AverageDaysTime {
     }
 }
 
+/// Average month length of 30.4375 days (an average year divided by 12).
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct AverageMonthsTime {
+    pub months: f64,
+}
+
+impl From<f64> for AverageMonthsTime {
+    fn from(months: f64) -> Self {
+        Self { months }
+    }
+}
+
+impl DeclineTimeUnit for AverageMonthsTime {
+    const LENGTH: f64 = 30.4375;
+
+    fn value(&self) -> f64 {
+        self.months
+    }
+}
+
+/// Average quarter length of 91.3125 days (an average year divided by 4).
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct AverageQuartersTime {
+    pub quarters: f64,
+}
+
+impl From<f64> for AverageQuartersTime {
+    fn from(quarters: f64) -> Self {
+        Self { quarters }
+    }
+}
+
+impl DeclineTimeUnit for AverageQuartersTime {
+    const LENGTH: f64 = 91.3125;
+
+    fn value(&self) -> f64 {
+        self.quarters
+    }
+}
+
+/// A time unit with a custom length, given in hundredths of a day, for interoperating with
+/// software that uses other conventions (e.g., 365-day years or 30-day months).
+///
+/// The length must be greater than zero.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct CustomTime<const HUNDREDTHS_OF_A_DAY: u64> {
+    pub value: f64,
+}
+
+/// Year length of 365 days.
+pub type Years365Time = CustomTime<36_500>;
+
+/// Month length of 30 days.
+pub type Months30Time = CustomTime<3_000>;
+
+impl<const HUNDREDTHS_OF_A_DAY: u64> From<f64> for CustomTime<HUNDREDTHS_OF_A_DAY> {
+    fn from(value: f64) -> Self {
+        Self { value }
+    }
+}
+
+impl<const HUNDREDTHS_OF_A_DAY: u64> DeclineTimeUnit for CustomTime<HUNDREDTHS_OF_A_DAY> {
+    const LENGTH: f64 = {
+        assert!(
+            HUNDREDTHS_OF_A_DAY > 0,
+            "custom time units must have a length greater than zero"
+        );
+        HUNDREDTHS_OF_A_DAY as f64 / 100.
+    };
+
+    fn value(&self) -> f64 {
+        self.value
+    }
+}
+
+/// A fraction expressed as a percentage, e.g., 25% is a fraction of 0.25.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Percent(pub f64);
+
+impl Percent {
+    pub fn from_fraction(fraction: f64) -> Self {
+        Self(fraction * 100.)
+    }
+
+    pub fn to_fraction(self) -> f64 {
+        self.0 / 100.
+    }
+}
+
+/// Implements the comparisons and percent conversions that all decline rate types share.
+macro_rules! impl_decline_rate {
+    ($($rate:ident),* $(,)?) => {
+        $(
+            impl<Time: DeclineTimeUnit> $rate<Time> {
+                pub fn from_percent(percent: Percent) -> Self {
+                    Self::new(percent.to_fraction())
+                }
+
+                pub fn to_percent(self) -> Percent {
+                    Percent::from_fraction(self.value)
+                }
+            }
+
+            /// Decline rates are compared by value, so a NaN decline rate isn't ordered.
+            impl<Time: DeclineTimeUnit> PartialOrd for $rate<Time> {
+                fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                    self.value.partial_cmp(&other.value)
+                }
+            }
+        )*
+    };
+}
+
+impl_decline_rate!(
+    NominalDeclineRate,
+    SecantEffectiveDeclineRate,
+    TangentEffectiveDeclineRate,
+);
+
 /// The nominal decline rate as a fraction.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct NominalDeclineRate<Time: DeclineTimeUnit> {
@@ -79,8 +268,12 @@ impl<Time: DeclineTimeUnit> NominalDeclineRate<Time> {
 
     pub fn to_secant_effective(
         self,
-        exponent: f64,
+        exponent: impl Into<f64>,
     ) -> Result<SecantEffectiveDeclineRate<Time>, DeclineCurveAnalysisError> {
+        let exponent = exponent.into();
+        validate_finite(self.value, "decline rate")?;
+        validate_finite(exponent, "exponent")?;
+
         if exponent == 0. {
             // Handle as an exponential segment, so use the tangent effective conversion.
             let tangent_effective = self.to_tangent_effective()?;
@@ -88,21 +281,39 @@ impl<Time: DeclineTimeUnit> NominalDeclineRate<Time> {
             // Then just call it a secant effective.
             Ok(SecantEffectiveDeclineRate::new(tangent_effective.value))
         } else {
-            let secant_effective = 1. - (self.value.mul_add(exponent, 1.)).powf(-1. / exponent);
+            let secant_effective =
+                1. - math::powf(self.value.mul_add(exponent, 1.), -1. / exponent);
 
-            Ok(SecantEffectiveDeclineRate::new(secant_effective))
+            Ok(SecantEffectiveDeclineRate::new(validate_converted(
+                secant_effective,
+            )?))
         }
     }
 
     pub fn to_tangent_effective(
         self,
     ) -> Result<TangentEffectiveDeclineRate<Time>, DeclineCurveAnalysisError> {
-        let tangent_effective = 1. - (-self.value).exp();
+        validate_finite(self.value, "decline rate")?;
+        let tangent_effective = 1. - math::exp(-self.value);
 
-        Ok(TangentEffectiveDeclineRate::new(tangent_effective))
+        Ok(TangentEffectiveDeclineRate::new(validate_converted(
+            tangent_effective,
+        )?))
     }
 
-    fn to_time<ToTimeUnit: DeclineTimeUnit>(self) -> NominalDeclineRate<ToTimeUnit> {
+    /// Converts the decline rate to a tangent effective decline rate that compounds over a
+    /// different period, e.g., the monthly effective decline of an annual nominal decline rate.
+    ///
+    /// This is the same as converting the decline rate to the time unit of the period, then to a
+    /// tangent effective decline rate.
+    pub fn to_effective_for_period<Period: DeclineTimeUnit>(
+        self,
+    ) -> Result<TangentEffectiveDeclineRate<Period>, DeclineCurveAnalysisError> {
+        self.to_time_unit::<Period>().to_tangent_effective()
+    }
+
+    /// Converts the decline rate to a different time unit.
+    pub fn to_time_unit<ToTimeUnit: DeclineTimeUnit>(self) -> NominalDeclineRate<ToTimeUnit> {
         NominalDeclineRate {
             value: (self.value * ToTimeUnit::LENGTH) / Time::LENGTH,
             _time: PhantomData,
@@ -110,18 +321,100 @@ impl<Time: DeclineTimeUnit> NominalDeclineRate<Time> {
     }
 }
 
-impl From<NominalDeclineRate<AverageDaysTime>> for NominalDeclineRate<AverageYearsTime> {
-    fn from(value: NominalDeclineRate<AverageDaysTime>) -> Self {
-        value.to_time()
+/// Nominal decline rates are instantaneous, so they can be added and scaled, e.g., to combine two
+/// exponential declines. Effective decline rates compound over the time unit, so they can't.
+impl<Time: DeclineTimeUnit> Add for NominalDeclineRate<Time> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.value + other.value)
+    }
+}
+
+impl<Time: DeclineTimeUnit> Sub for NominalDeclineRate<Time> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.value - other.value)
+    }
+}
+
+impl<Time: DeclineTimeUnit> Mul<f64> for NominalDeclineRate<Time> {
+    type Output = Self;
+
+    fn mul(self, factor: f64) -> Self {
+        Self::new(self.value * factor)
+    }
+}
+
+impl<Time: DeclineTimeUnit> Div<f64> for NominalDeclineRate<Time> {
+    type Output = Self;
+
+    fn div(self, divisor: f64) -> Self {
+        Self::new(self.value / divisor)
     }
 }
 
-impl From<NominalDeclineRate<AverageYearsTime>> for NominalDeclineRate<AverageDaysTime> {
-    fn from(value: NominalDeclineRate<AverageYearsTime>) -> Self {
-        value.to_time()
+/// A negative decline rate is an incline.
+impl<Time: DeclineTimeUnit> Neg for NominalDeclineRate<Time> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.value)
     }
 }
 
+/// Implements `From` between two time units, and between production rates and nominal decline
+/// rates in those units, in both directions. The generic parameters of the impls are in brackets.
+macro_rules! impl_time_unit_conversion {
+    ([$($generics:tt)*] $a:ty, $b:ty) => {
+        impl_time_unit_conversion!(@one [$($generics)*] $a, $b);
+        impl_time_unit_conversion!(@one [$($generics)*] $b, $a);
+    };
+    (@one [$($generics:tt)*] $from:ty, $to:ty) => {
+        impl<$($generics)*> From<$from> for $to {
+            fn from(value: $from) -> Self {
+                value.to_unit()
+            }
+        }
+
+        impl<$($generics)* Volume: VolumeUnit> From<ProductionRate<$from, Volume>>
+            for ProductionRate<$to, Volume>
+        {
+            fn from(value: ProductionRate<$from, Volume>) -> Self {
+                value.to_time_unit()
+            }
+        }
+
+        impl<$($generics)*> From<NominalDeclineRate<$from>> for NominalDeclineRate<$to> {
+            fn from(value: NominalDeclineRate<$from>) -> Self {
+                value.to_time_unit()
+            }
+        }
+    };
+}
+
+/// Implements the conversions between every pair of the given average time units, and between
+/// each of them and every custom time unit. Conversions between two custom time units would
+/// conflict with the identity conversion, so they use [`DeclineTimeUnit::to_unit`] instead.
+macro_rules! impl_time_unit_conversions {
+    ($first:ty $(, $rest:ty)* $(,)?) => {
+        $(impl_time_unit_conversion!([] $first, $rest);)*
+        impl_time_unit_conversion!(
+            [const HUNDREDTHS_OF_A_DAY: u64,] $first, CustomTime<HUNDREDTHS_OF_A_DAY>
+        );
+        impl_time_unit_conversions!($($rest),*);
+    };
+    () => {};
+}
+
+impl_time_unit_conversions!(
+    AverageDaysTime,
+    AverageMonthsTime,
+    AverageQuartersTime,
+    AverageYearsTime,
+);
+
 /// The secant effective decline rate as a fraction.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct SecantEffectiveDeclineRate<Time: DeclineTimeUnit> {
@@ -145,19 +438,23 @@ impl<Time: DeclineTimeUnit> SecantEffectiveDeclineRate<Time> {
         self,
         exponent: f64,
     ) -> Result<NominalDeclineRate<Time>, DeclineCurveAnalysisError> {
+        validate_finite(self.value, "decline rate")?;
         if self.value >= 1. {
             return Err(DeclineCurveAnalysisError::DeclineRateTooHigh);
         }
 
-        Ok(NominalDeclineRate::new(
-            (((1. - self.value).powf(-exponent)) - 1.) / exponent,
-        ))
+        Ok(NominalDeclineRate::new(validate_converted(
+            (math::powf(1. - self.value, -exponent) - 1.) / exponent,
+        )?))
     }
 
     pub fn to_nominal(
         self,
-        exponent: f64,
+        exponent: impl Into<f64>,
     ) -> Result<NominalDeclineRate<Time>, DeclineCurveAnalysisError> {
+        let exponent = exponent.into();
+        validate_finite(exponent, "exponent")?;
+
         if exponent == 0. {
             // Handle as an exponential segment, so treat the decline rate as a tangent effective
             // conversion.
@@ -169,8 +466,12 @@ impl<Time: DeclineTimeUnit> SecantEffectiveDeclineRate<Time> {
 
     pub fn to_tangent_effective(
         self,
-        exponent: f64,
+        exponent: impl Into<f64>,
     ) -> Result<TangentEffectiveDeclineRate<Time>, DeclineCurveAnalysisError> {
+        let exponent = exponent.into();
+        validate_finite(self.value, "decline rate")?;
+        validate_finite(exponent, "exponent")?;
+
         if exponent == 0. {
             // It's an exponential, so secant effective and tangent effective are the same.
             Ok(TangentEffectiveDeclineRate::new(self.value))
@@ -201,11 +502,14 @@ impl<Time: DeclineTimeUnit> TangentEffectiveDeclineRate<Time> {
     }
 
     fn to_nominal_inner(self) -> Result<NominalDeclineRate<Time>, DeclineCurveAnalysisError> {
+        validate_finite(self.value, "decline rate")?;
         if self.value >= 1. {
             return Err(DeclineCurveAnalysisError::DeclineRateTooHigh);
         }
 
-        Ok(NominalDeclineRate::new(-(-self.value).ln_1p()))
+        Ok(NominalDeclineRate::new(validate_converted(-math::ln_1p(
+            -self.value,
+        ))?))
     }
 
     pub fn to_nominal(self) -> Result<NominalDeclineRate<Time>, DeclineCurveAnalysisError> {
@@ -214,8 +518,12 @@ impl<Time: DeclineTimeUnit> TangentEffectiveDeclineRate<Time> {
 
     pub fn to_secant_effective(
         self,
-        exponent: f64,
+        exponent: impl Into<f64>,
     ) -> Result<SecantEffectiveDeclineRate<Time>, DeclineCurveAnalysisError> {
+        let exponent = exponent.into();
+        validate_finite(self.value, "decline rate")?;
+        validate_finite(exponent, "exponent")?;
+
         if exponent == 0. {
             // It's an exponential, so secant effective and tangent effective are the same.
             Ok(SecantEffectiveDeclineRate::new(self.value))
@@ -225,3 +533,13 @@ impl<Time: DeclineTimeUnit> TangentEffectiveDeclineRate<Time> {
         }
     }
 }
+
+/// Validates that a converted decline rate is finite, which fails if there's no equivalent decline
+/// rate for the exponent (e.g., a steep incline).
+fn validate_converted(value: f64) -> Result<f64, DeclineCurveAnalysisError> {
+    if value.is_finite() {
+        Ok(value)
+    } else {
+        Err(DeclineCurveAnalysisError::CannotSolveDecline)
+    }
+}
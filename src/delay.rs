@@ -1,4 +1,9 @@
-use crate::{DeclineCurveAnalysisError, DeclineTimeUnit, ProductionRate, validate_duration};
+use crate::{
+    DeclineCurveAnalysisError, DeclineTimeUnit, NominalDeclineRate, ProductionRate, RateTruncation,
+    evaluate_at_times, evaluate_at_times_f32, is_effectively_zero, split_duration,
+    validate_duration, validate_economic_limit_rate, validate_incremental_volume,
+    validate_positive,
+};
 
 /// A no-op delay segment that represents a delay with no volume. It can be useful to represent an
 /// arbitrary delay in forecasts.
@@ -28,14 +33,87 @@ impl<Time: DeclineTimeUnit> DelayParameters<Time> {
         })
     }
 
+    /// Splits the segment at `time` into two delays whose durations add up to the original.
+    pub fn split_at(&self, time: Time) -> (Self, Self) {
+        let (first_duration, second_duration) = split_duration(time, self.incremental_duration);
+        (
+            Self {
+                incremental_duration: first_duration,
+            },
+            Self {
+                incremental_duration: second_duration,
+            },
+        )
+    }
+
+    /// Delays have no production, so scaling returns an unchanged copy.
+    pub(crate) fn scale_rates(&self, _factor: f64) -> Self {
+        self.clone()
+    }
+
+    /// Delays have no production, so they're never truncated by an economic limit.
+    pub fn truncate_at_rate(
+        &self,
+        economic_limit_rate: ProductionRate<Time>,
+    ) -> Result<RateTruncation<Self, Time>, DeclineCurveAnalysisError> {
+        validate_economic_limit_rate(economic_limit_rate)?;
+
+        Ok(RateTruncation {
+            parameters: self.clone(),
+            truncated_volume: 0.,
+            truncated_duration: Time::from(0.),
+        })
+    }
+
     pub const fn incremental_volume_at_time(&self, _time: Time) -> f64 {
         0.
     }
 
+    /// Delays have no production, so only a zero volume can be reached, which happens immediately.
+    pub fn time_at_incremental_volume(
+        &self,
+        incremental_volume: f64,
+    ) -> Result<Time, DeclineCurveAnalysisError> {
+        validate_incremental_volume(incremental_volume)?;
+
+        if is_effectively_zero(incremental_volume) {
+            Ok(Time::from(0.))
+        } else {
+            Err(DeclineCurveAnalysisError::CannotSolveDecline)
+        }
+    }
+
+    /// Delays have no production, so only a zero volume can be reached, where the rate is zero.
+    pub fn rate_at_incremental_volume(
+        &self,
+        incremental_volume: f64,
+    ) -> Result<ProductionRate<Time>, DeclineCurveAnalysisError> {
+        self.time_at_incremental_volume(incremental_volume)?;
+        Ok(Self::ZERO_PRODUCTION_RATE)
+    }
+
+    pub const fn incremental_volume_between(&self, _start: Time, _end: Time) -> f64 {
+        0.
+    }
+
     pub const fn incremental_volume(&self) -> f64 {
         0.
     }
 
+    /// Delays have no production, so only a zero rate can be reached, which happens immediately.
+    pub fn time_at_rate(
+        &self,
+        rate: ProductionRate<Time>,
+    ) -> Result<Time, DeclineCurveAnalysisError> {
+        validate_positive(rate.value(), "rate")?;
+
+        if is_effectively_zero(rate.value()) {
+            Ok(Time::from(0.))
+        } else {
+            Err(DeclineCurveAnalysisError::CannotSolveDecline)
+        }
+    }
+
     pub const fn final_rate(&self) -> ProductionRate<Time> {
         Self::ZERO_PRODUCTION_RATE
     }
@@ -43,4 +121,52 @@ impl<Time: DeclineTimeUnit> DelayParameters<Time> {
     pub const fn rate_at_time(&self, _time: Time) -> ProductionRate<Time> {
         Self::ZERO_PRODUCTION_RATE
     }
+
+    /// Writes a zero rate to `out` for each time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `times` and `out` have different lengths.
+    pub fn rates_at_times(&self, times: &[Time], out: &mut [f64]) {
+        evaluate_at_times(times, out, self.incremental_duration, |_| 0.);
+    }
+
+    /// Writes a zero volume to `out` for each time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `times` and `out` have different lengths.
+    pub fn incremental_volumes_at_times(&self, times: &[Time], out: &mut [f64]) {
+        evaluate_at_times(times, out, self.incremental_duration, |_| 0.);
+    }
+
+    /// Like `rates_at_times`, but with `f32` times and rates, where the times are in the segment's
+    /// time unit.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `times` and `out` have different lengths.
+    pub fn rates_at_times_f32(&self, times: &[f32], out: &mut [f32]) {
+        evaluate_at_times_f32(times, out, self.incremental_duration, |_| 0.);
+    }
+
+    /// Like `incremental_volumes_at_times`, but with `f32` times and volumes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `times` and `out` have different lengths.
+    pub fn incremental_volumes_at_times_f32(&self, times: &[f32], out: &mut [f32]) {
+        evaluate_at_times_f32(times, out, self.incremental_duration, |_| 0.);
+    }
+
+    /// The instantaneous nominal decline rate, which is zero for delays since there's no
+    /// production.
+    pub fn decline_rate_at_time(&self, _time: Time) -> NominalDeclineRate<Time> {
+        NominalDeclineRate::new(0.)
+    }
+
+    /// The derivative of the rate with respect to time, which is zero for delays.
+    pub fn rate_derivative_at_time(&self, _time: Time) -> f64 {
+        0.
+    }
 }
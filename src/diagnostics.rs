@@ -0,0 +1,175 @@
+//! Series for the standard diagnostic plots of production data, as `(x, y)` pairs that can be
+//! passed to any plotting library.
+//!
+//! Logarithmic axes use base 10 logarithms, and samples that can't be shown on a logarithmic axis
+//! (i.e., zero rates or times) are left out of those series. Times, rates, and volumes are in the
+//! time unit of the samples.
+
+use crate::{
+    DeclineCurveAnalysisError, DeclineTimeUnit, NominalDeclineRate, ProductionRate, math,
+    validate_finite, validate_non_zero_positive_rate, validate_positive,
+};
+use alloc::vec::Vec;
+
+/// Validates `(time, rate)` samples and converts them to `(time, rate, cumulative volume)`.
+///
+/// The cumulative volume integrates the rates with the trapezoidal rule, where the rate of the
+/// first sample is held from time zero.
+pub(crate) fn cumulative_samples<Time: DeclineTimeUnit>(
+    samples: &[(Time, ProductionRate<Time>)],
+) -> Result<Vec<(f64, f64, f64)>, DeclineCurveAnalysisError> {
+    let mut cumulative_samples = Vec::with_capacity(samples.len());
+    let mut previous = (0., None);
+    let mut cumulative_volume = 0.;
+
+    for (time, rate) in samples {
+        let (time, rate) = (time.value(), rate.value());
+        validate_positive(time, "sample time")?;
+        validate_positive(rate, "sample rate")?;
+
+        let (previous_time, previous_rate) = previous;
+        if previous_rate.is_some() && time <= previous_time {
+            return Err(DeclineCurveAnalysisError::NotStrictlyIncreasing {
+                field: "sample times",
+            });
+        }
+
+        let previous_rate = previous_rate.unwrap_or(rate);
+        cumulative_volume += 0.5 * (previous_rate + rate) * (time - previous_time);
+        cumulative_samples.push((time, rate, cumulative_volume));
+        previous = (time, Some(rate));
+    }
+
+    Ok(cumulative_samples)
+}
+
+/// Returns `(time, log(rate))` pairs, where an exponential decline is a straight line.
+pub fn log_rate_vs_time<Time: DeclineTimeUnit>(
+    samples: &[(Time, ProductionRate<Time>)],
+) -> Result<Vec<(f64, f64)>, DeclineCurveAnalysisError> {
+    Ok(cumulative_samples(samples)?
+        .into_iter()
+        .filter(|&(_, rate, _)| rate > 0.)
+        .map(|(time, rate, _)| (time, math::log10(rate)))
+        .collect())
+}
+
+/// Returns `(cumulative volume, rate)` pairs, where an exponential decline is a straight line
+/// that reaches zero rate at its ultimate recovery.
+pub fn rate_vs_cumulative_volume<Time: DeclineTimeUnit>(
+    samples: &[(Time, ProductionRate<Time>)],
+) -> Result<Vec<(f64, f64)>, DeclineCurveAnalysisError> {
+    Ok(cumulative_samples(samples)?
+        .into_iter()
+        .map(|(_, rate, cumulative_volume)| (cumulative_volume, rate))
+        .collect())
+}
+
+/// Returns `(log(time), log(rate))` pairs, where the slope identifies flow regimes, e.g., a slope
+/// of -1/2 for linear flow.
+pub fn log_rate_vs_log_time<Time: DeclineTimeUnit>(
+    samples: &[(Time, ProductionRate<Time>)],
+) -> Result<Vec<(f64, f64)>, DeclineCurveAnalysisError> {
+    Ok(cumulative_samples(samples)?
+        .into_iter()
+        .filter(|&(time, rate, _)| time > 0. && rate > 0.)
+        .map(|(time, rate, _)| (math::log10(time), math::log10(rate)))
+        .collect())
+}
+
+/// Returns `(log(material balance time), log(rate))` pairs in the style of a Blasingame plot,
+/// where the material balance time is the cumulative volume divided by the rate.
+///
+/// Boundary-dominated flow approaches a slope of -1, and the changing rates of the samples are
+/// accounted for by the material balance time.
+pub fn log_rate_vs_log_material_balance_time<Time: DeclineTimeUnit>(
+    samples: &[(Time, ProductionRate<Time>)],
+) -> Result<Vec<(f64, f64)>, DeclineCurveAnalysisError> {
+    Ok(cumulative_samples(samples)?
+        .into_iter()
+        .filter(|&(_, rate, cumulative_volume)| rate > 0. && cumulative_volume > 0.)
+        .map(|(_, rate, cumulative_volume)| {
+            (math::log10(cumulative_volume / rate), math::log10(rate))
+        })
+        .collect())
+}
+
+/// Returns `(log(Di * time), log(rate / qi))` pairs in the style of a Fetkovich type curve, where
+/// the time and rate are normalized by an initial rate and decline rate, e.g., from a fit.
+///
+/// Arps declines with the same exponent overlay each other on this plot, so the samples can be
+/// compared against the Fetkovich decline stems.
+pub fn fetkovich_normalized<Time: DeclineTimeUnit>(
+    samples: &[(Time, ProductionRate<Time>)],
+    initial_rate: ProductionRate<Time>,
+    initial_decline_rate: NominalDeclineRate<Time>,
+) -> Result<Vec<(f64, f64)>, DeclineCurveAnalysisError> {
+    validate_non_zero_positive_rate(initial_rate.value(), "initial rate")?;
+    validate_non_zero_positive_rate(initial_decline_rate.value(), "initial decline rate")?;
+
+    Ok(cumulative_samples(samples)?
+        .into_iter()
+        .filter(|&(time, rate, _)| time > 0. && rate > 0.)
+        .map(|(time, rate, _)| {
+            (
+                math::log10(initial_decline_rate.value() * time),
+                math::log10(rate / initial_rate.value()),
+            )
+        })
+        .collect())
+}
+
+/// Returns `(x, dy/d ln(x))` pairs, which is the smoothed logarithmic derivative of Bourdet et al.
+/// (1989), e.g., for rates against time or normalized rates against material balance time.
+///
+/// On a log-log plot, the derivative identifies flow regimes before a decline model is chosen,
+/// e.g., the derivative of `1 / q` has a slope of 1/2 in linear flow and 1 in boundary-dominated
+/// flow.
+///
+/// The derivative at each point uses the nearest points on either side that are at least
+/// `smoothing` apart in `ln(x)` (typically 0.1 to 0.5), or the first and last points if there
+/// aren't any. Zero smoothing uses the adjacent points. Points where `x` is zero are left out, and
+/// the first and last points have no derivative.
+pub fn bourdet_derivative(
+    points: &[(f64, f64)],
+    smoothing: f64,
+) -> Result<Vec<(f64, f64)>, DeclineCurveAnalysisError> {
+    validate_positive(smoothing, "smoothing")?;
+
+    let mut log_points = Vec::with_capacity(points.len());
+    for (index, &(x, y)) in points.iter().enumerate() {
+        validate_positive(x, "x")?;
+        validate_finite(y, "y")?;
+        if index > 0 && x <= points[index - 1].0 {
+            return Err(DeclineCurveAnalysisError::NotStrictlyIncreasing { field: "x values" });
+        }
+        if x > 0. {
+            log_points.push((x, math::ln(x), y));
+        }
+    }
+
+    let last = log_points.len().saturating_sub(1);
+    Ok((1..last)
+        .map(|index| {
+            let (x, log_x, y) = log_points[index];
+            let (_, left_log_x, left_y) = *log_points[..index]
+                .iter()
+                .rev()
+                .find(|(_, left_log_x, _)| log_x - left_log_x >= smoothing)
+                .unwrap_or(&log_points[0]);
+            let (_, right_log_x, right_y) = *log_points[index + 1..]
+                .iter()
+                .find(|(_, right_log_x, _)| right_log_x - log_x >= smoothing)
+                .unwrap_or(&log_points[last]);
+
+            let (left_step, right_step) = (log_x - left_log_x, right_log_x - log_x);
+            let left_slope = (y - left_y) / left_step;
+            let right_slope = (right_y - y) / right_step;
+
+            (
+                x,
+                (left_slope * right_step + right_slope * left_step) / (left_step + right_step),
+            )
+        })
+        .collect())
+}
@@ -0,0 +1,364 @@
+use crate::{
+    AverageYearsTime, DeclineTimeUnit, DelayParameters, ExponentialParameters, FlatParameters,
+    HarmonicParameters, HyperbolicParameters, LinearParameters, LogisticGrowthParameters,
+    NominalDeclineRate, PowerLawExponentialParameters, ProductionRate, SecantEffectiveDeclineRate,
+    Segment, TangentEffectiveDeclineRate,
+};
+use alloc::format;
+use core::fmt;
+
+/// The number of significant digits shown for displayed values.
+const SIGNIFICANT_DIGITS: usize = 4;
+
+/// The convention used to show the decline rates of segments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DeclineRateConvention {
+    #[default]
+    Nominal,
+    SecantEffective,
+    TangentEffective,
+}
+
+impl DeclineRateConvention {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Nominal => "nominal",
+            Self::SecantEffective => "secant effective",
+            Self::TangentEffective => "tangent effective",
+        }
+    }
+}
+
+/// Options that control how segments are displayed.
+///
+/// Segments are always shown with rates per day, decline rates per year, and durations in days,
+/// regardless of their time unit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DisplayOptions<'a> {
+    pub decline_rate_convention: DeclineRateConvention,
+    /// The volume unit shown with rates and volumes (e.g., "bbl" or "Mcf"). If it's empty, rates
+    /// are shown without a volume unit.
+    pub volume_unit: &'a str,
+}
+
+/// Segment parameters that are displayed with the given options, which is returned by
+/// `display_with`.
+#[derive(Debug, Clone, Copy)]
+pub struct DisplayParameters<'a, Parameters> {
+    parameters: &'a Parameters,
+    options: DisplayOptions<'a>,
+}
+
+pub(crate) trait FormatParameters {
+    fn format(&self, f: &mut fmt::Formatter<'_>, options: &DisplayOptions<'_>) -> fmt::Result;
+}
+
+impl<Parameters: FormatParameters> fmt::Display for DisplayParameters<'_, Parameters> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.parameters.format(f, &self.options)
+    }
+}
+
+/// Writes a value rounded to a few significant digits, without trailing zeros.
+fn write_number(f: &mut fmt::Formatter<'_>, value: f64) -> fmt::Result {
+    if !value.is_finite() || value == 0. {
+        return write!(f, "{}", value.abs());
+    }
+
+    let mut magnitude = value.abs();
+    let precision = if magnitude >= 1. {
+        let mut integer_digits = 1;
+        while magnitude >= 10. {
+            magnitude /= 10.;
+            integer_digits += 1;
+        }
+        SIGNIFICANT_DIGITS.saturating_sub(integer_digits)
+    } else {
+        let mut leading_zeros = 0;
+        while magnitude < 0.1 && leading_zeros < 12 {
+            magnitude *= 10.;
+            leading_zeros += 1;
+        }
+        SIGNIFICANT_DIGITS + leading_zeros
+    };
+
+    let formatted = format!("{value:.precision$}");
+    let formatted = if formatted.contains('.') {
+        formatted.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        &formatted
+    };
+
+    // Avoid showing "-0" for small negative values that round to zero.
+    if formatted == "-0" {
+        f.write_str("0")
+    } else {
+        f.write_str(formatted)
+    }
+}
+
+fn write_percentage(f: &mut fmt::Formatter<'_>, fraction: f64) -> fmt::Result {
+    write_number(f, fraction * 100.)?;
+    f.write_str("%")
+}
+
+/// Writes the time unit that a value is per, e.g., "/yr".
+fn write_per_time_unit<Time: DeclineTimeUnit>(f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match Time::LENGTH {
+        365.25 => f.write_str("/yr"),
+        30.4375 => f.write_str("/mo"),
+        1. => f.write_str("/d"),
+        length => {
+            f.write_str("/(")?;
+            write_number(f, length)?;
+            f.write_str(" d)")
+        }
+    }
+}
+
+fn write_rate<Time: DeclineTimeUnit>(
+    f: &mut fmt::Formatter<'_>,
+    name: &str,
+    rate: ProductionRate<Time>,
+    options: &DisplayOptions<'_>,
+) -> fmt::Result {
+    write!(f, "{name}=")?;
+    write_number(f, rate.value() / Time::LENGTH)?;
+    if options.volume_unit.is_empty() {
+        f.write_str("/d")
+    } else {
+        write!(f, " {}/d", options.volume_unit)
+    }
+}
+
+fn write_duration<Time: DeclineTimeUnit>(
+    f: &mut fmt::Formatter<'_>,
+    duration: Time,
+) -> fmt::Result {
+    f.write_str("t=")?;
+    write_number(f, duration.value() * Time::LENGTH)?;
+    f.write_str(" d")
+}
+
+/// Writes a decline rate per year in the convention from the options, where the exponent is used
+/// to convert to a secant effective decline rate.
+fn write_decline_rate<Time: DeclineTimeUnit>(
+    f: &mut fmt::Formatter<'_>,
+    name: &str,
+    decline_rate: NominalDeclineRate<Time>,
+    exponent: f64,
+    options: &DisplayOptions<'_>,
+) -> fmt::Result {
+    let decline_rate = decline_rate.to_time_unit::<AverageYearsTime>();
+    let value = match options.decline_rate_convention {
+        DeclineRateConvention::Nominal => Ok(decline_rate.value()),
+        DeclineRateConvention::SecantEffective => decline_rate
+            .to_secant_effective(exponent)
+            .map(|decline_rate| decline_rate.value()),
+        DeclineRateConvention::TangentEffective => decline_rate
+            .to_tangent_effective()
+            .map(|decline_rate| decline_rate.value()),
+    }
+    .unwrap_or(f64::NAN);
+
+    write!(f, "{name}=")?;
+    write_percentage(f, value)?;
+    write!(f, "/yr {}", options.decline_rate_convention.label())
+}
+
+impl<Time: DeclineTimeUnit> FormatParameters for DelayParameters<Time> {
+    fn format(&self, f: &mut fmt::Formatter<'_>, _options: &DisplayOptions<'_>) -> fmt::Result {
+        f.write_str("Delay: ")?;
+        write_duration(f, self.incremental_duration())
+    }
+}
+
+impl<Time: DeclineTimeUnit> FormatParameters for FlatParameters<Time> {
+    fn format(&self, f: &mut fmt::Formatter<'_>, options: &DisplayOptions<'_>) -> fmt::Result {
+        f.write_str("Flat: ")?;
+        write_rate(f, "q", self.rate(), options)?;
+        f.write_str(", ")?;
+        write_duration(f, self.incremental_duration())
+    }
+}
+
+impl<Time: DeclineTimeUnit> FormatParameters for ExponentialParameters<Time> {
+    fn format(&self, f: &mut fmt::Formatter<'_>, options: &DisplayOptions<'_>) -> fmt::Result {
+        f.write_str("Exponential: ")?;
+        write_rate(f, "qi", self.initial_rate(), options)?;
+        f.write_str(", ")?;
+        write_decline_rate(f, "D", self.decline_rate(), 0., options)?;
+        f.write_str(", ")?;
+        write_duration(f, self.incremental_duration())?;
+        f.write_str(", ")?;
+        write_rate(f, "qf", self.final_rate(), options)
+    }
+}
+
+impl<Time: DeclineTimeUnit> FormatParameters for HarmonicParameters<Time> {
+    fn format(&self, f: &mut fmt::Formatter<'_>, options: &DisplayOptions<'_>) -> fmt::Result {
+        f.write_str("Harmonic: ")?;
+        write_rate(f, "qi", self.initial_rate(), options)?;
+        f.write_str(", ")?;
+        write_decline_rate(f, "Di", self.initial_decline_rate(), 1., options)?;
+        f.write_str(", ")?;
+        write_duration(f, self.incremental_duration())?;
+        f.write_str(", ")?;
+        write_rate(f, "qf", self.final_rate(), options)
+    }
+}
+
+impl<Time: DeclineTimeUnit> FormatParameters for HyperbolicParameters<Time> {
+    fn format(&self, f: &mut fmt::Formatter<'_>, options: &DisplayOptions<'_>) -> fmt::Result {
+        f.write_str("Hyperbolic: ")?;
+        write_rate(f, "qi", self.initial_rate(), options)?;
+        f.write_str(", ")?;
+        write_decline_rate(
+            f,
+            "Di",
+            self.initial_decline_rate(),
+            self.exponent(),
+            options,
+        )?;
+        f.write_str(", b=")?;
+        write_number(f, self.exponent())?;
+        f.write_str(", ")?;
+        write_duration(f, self.incremental_duration())?;
+        f.write_str(", ")?;
+        write_rate(f, "qf", self.final_rate(), options)
+    }
+}
+
+/// Linear decline rates are a fraction of the initial rate, so they're always shown as they're
+/// defined, regardless of the decline rate convention.
+impl<Time: DeclineTimeUnit> FormatParameters for LinearParameters<Time> {
+    fn format(&self, f: &mut fmt::Formatter<'_>, options: &DisplayOptions<'_>) -> fmt::Result {
+        f.write_str("Linear: ")?;
+        write_rate(f, "qi", self.initial_rate(), options)?;
+        f.write_str(", D=")?;
+        write_percentage(
+            f,
+            self.decline_rate()
+                .to_time_unit::<AverageYearsTime>()
+                .value(),
+        )?;
+        f.write_str("/yr, ")?;
+        write_duration(f, self.incremental_duration())?;
+        f.write_str(", ")?;
+        write_rate(f, "qf", self.final_rate(), options)
+    }
+}
+
+/// The `a` constant has units of `time^n` in the segment's time unit, so it's shown as a raw
+/// value.
+impl<Time: DeclineTimeUnit> FormatParameters for LogisticGrowthParameters<Time> {
+    fn format(&self, f: &mut fmt::Formatter<'_>, options: &DisplayOptions<'_>) -> fmt::Result {
+        f.write_str("Logistic growth: K=")?;
+        write_number(f, self.carrying_capacity())?;
+        if !options.volume_unit.is_empty() {
+            write!(f, " {}", options.volume_unit)?;
+        }
+        f.write_str(", a=")?;
+        write_number(f, self.a())?;
+        f.write_str(", n=")?;
+        write_number(f, self.exponent())?;
+        f.write_str(", ")?;
+        write_duration(f, self.incremental_duration())?;
+        f.write_str(", ")?;
+        write_rate(f, "qf", self.final_rate(), options)
+    }
+}
+
+/// The `Di` constant has units of `1 / time^n` in the segment's time unit, so it's shown as a raw
+/// value.
+impl<Time: DeclineTimeUnit> FormatParameters for PowerLawExponentialParameters<Time> {
+    fn format(&self, f: &mut fmt::Formatter<'_>, options: &DisplayOptions<'_>) -> fmt::Result {
+        f.write_str("Power law exponential: ")?;
+        write_rate(f, "qi", self.initial_rate(), options)?;
+        f.write_str(", Di=")?;
+        write_number(f, self.initial_decline_rate())?;
+        f.write_str(", ")?;
+        write_decline_rate(f, "Dinf", self.infinite_decline_rate(), 0., options)?;
+        f.write_str(", n=")?;
+        write_number(f, self.exponent())?;
+        f.write_str(", ")?;
+        write_duration(f, self.incremental_duration())?;
+        f.write_str(", ")?;
+        write_rate(f, "qf", self.final_rate(), options)
+    }
+}
+
+impl<Time: DeclineTimeUnit> FormatParameters for Segment<Time> {
+    fn format(&self, f: &mut fmt::Formatter<'_>, options: &DisplayOptions<'_>) -> fmt::Result {
+        match self {
+            Segment::Delay(parameters) => parameters.format(f, options),
+            Segment::Exponential(parameters) => parameters.format(f, options),
+            Segment::Flat(parameters) => parameters.format(f, options),
+            Segment::Harmonic(parameters) => parameters.format(f, options),
+            Segment::Hyperbolic(parameters) => parameters.format(f, options),
+            Segment::Linear(parameters) => parameters.format(f, options),
+            Segment::LogisticGrowth(parameters) => parameters.format(f, options),
+            Segment::PowerLawExponential(parameters) => parameters.format(f, options),
+        }
+    }
+}
+
+macro_rules! impl_display {
+    ($($parameters:ident),* $(,)?) => {
+        $(
+            impl<Time: DeclineTimeUnit> $parameters<Time> {
+                /// Returns a value that displays the parameters with the given options.
+                pub fn display_with<'a>(
+                    &'a self,
+                    options: DisplayOptions<'a>,
+                ) -> DisplayParameters<'a, Self> {
+                    DisplayParameters {
+                        parameters: self,
+                        options,
+                    }
+                }
+            }
+
+            /// Displays the parameters with the default options, where decline rates are
+            /// nominal.
+            impl<Time: DeclineTimeUnit> fmt::Display for $parameters<Time> {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    self.format(f, &DisplayOptions::default())
+                }
+            }
+        )*
+    };
+}
+
+impl_display!(
+    DelayParameters,
+    ExponentialParameters,
+    FlatParameters,
+    HarmonicParameters,
+    HyperbolicParameters,
+    LinearParameters,
+    LogisticGrowthParameters,
+    PowerLawExponentialParameters,
+    Segment,
+);
+
+macro_rules! impl_decline_rate_display {
+    ($($decline_rate:ident => $label:literal),* $(,)?) => {
+        $(
+            /// Displays the decline rate as a percentage per its time unit.
+            impl<Time: DeclineTimeUnit> fmt::Display for $decline_rate<Time> {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    write_percentage(f, self.value())?;
+                    write_per_time_unit::<Time>(f)?;
+                    f.write_str(concat!(" ", $label))
+                }
+            }
+        )*
+    };
+}
+
+impl_decline_rate_display!(
+    NominalDeclineRate => "nominal",
+    SecantEffectiveDeclineRate => "secant effective",
+    TangentEffectiveDeclineRate => "tangent effective",
+);
@@ -0,0 +1,263 @@
+#[cfg(not(feature = "std"))]
+use crate::math::FloatMath;
+use crate::{
+    DeclineCurveAnalysisError, DeclineTimeUnit, Forecast, ProductionRate, Segment,
+    validate_non_zero_positive_rate, validate_positive,
+};
+use alloc::{vec, vec::Vec};
+
+/// A period where production is scaled by an uptime factor, e.g., zero for a shut-in. The period
+/// can optionally repeat, e.g., for a two day workover every month.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DowntimeInterval<Time: DeclineTimeUnit> {
+    start: Time,
+    end: Time,
+    uptime_factor: f64,
+    repeat_every: Option<Time>,
+}
+
+impl<Time: DeclineTimeUnit> DowntimeInterval<Time> {
+    /// Creates a single period from `start` to `end`, relative to the start of the segment or
+    /// forecast.
+    pub fn new(
+        start: Time,
+        end: Time,
+        uptime_factor: f64,
+    ) -> Result<Self, DeclineCurveAnalysisError> {
+        validate_positive(start.value(), "downtime start")?;
+        validate_positive(end.value(), "downtime end")?;
+        validate_uptime_factor(uptime_factor)?;
+
+        if end.value() <= start.value() {
+            return Err(DeclineCurveAnalysisError::InputNotLessThan {
+                field: "downtime start",
+                value: start.value(),
+                other: "downtime end",
+                limit: end.value(),
+            });
+        }
+
+        Ok(Self {
+            start,
+            end,
+            uptime_factor,
+            repeat_every: None,
+        })
+    }
+
+    /// Creates a period from `start` to `end` that repeats every `period`, forever. The period must
+    /// be longer than the downtime, otherwise the well would never be up.
+    pub fn recurring(
+        start: Time,
+        end: Time,
+        uptime_factor: f64,
+        period: Time,
+    ) -> Result<Self, DeclineCurveAnalysisError> {
+        let interval = Self::new(start, end, uptime_factor)?;
+        validate_non_zero_positive_rate(period.value(), "downtime period")?;
+
+        if period.value() <= end.value() - start.value() {
+            return Err(DeclineCurveAnalysisError::InputNotLessThan {
+                field: "downtime duration",
+                value: end.value() - start.value(),
+                other: "downtime period",
+                limit: period.value(),
+            });
+        }
+
+        Ok(Self {
+            repeat_every: Some(period),
+            ..interval
+        })
+    }
+
+    pub fn start(&self) -> Time {
+        self.start
+    }
+
+    pub fn end(&self) -> Time {
+        self.end
+    }
+
+    pub fn uptime_factor(&self) -> f64 {
+        self.uptime_factor
+    }
+
+    pub fn repeat_every(&self) -> Option<Time> {
+        self.repeat_every
+    }
+
+    fn contains(&self, time: f64) -> bool {
+        let start = self.start.value();
+        let length = self.end.value() - start;
+
+        match self.repeat_every {
+            Some(period) if time >= start => (time - start).rem_euclid(period.value()) < length,
+            _ => (start..self.end.value()).contains(&time),
+        }
+    }
+
+    /// Adds the start and end of each occurrence that falls strictly between `start` and `end`.
+    fn push_boundaries(&self, start: f64, end: f64, boundaries: &mut Vec<f64>) {
+        let mut push = |boundary: f64| {
+            if boundary > start && boundary < end {
+                boundaries.push(boundary);
+            }
+        };
+
+        let Some(period) = self.repeat_every else {
+            push(self.start.value());
+            push(self.end.value());
+            return;
+        };
+
+        let period = period.value();
+        let length = self.end.value() - self.start.value();
+        let first_occurrence = ((start - self.start.value()) / period).floor().max(0.);
+        let mut occurrence_start = first_occurrence.mul_add(period, self.start.value());
+
+        while occurrence_start < end {
+            push(occurrence_start);
+            push(occurrence_start + length);
+            occurrence_start += period;
+        }
+    }
+}
+
+/// A schedule of uptime factors that scales the rate of a segment or forecast, e.g., a 95% uptime
+/// factor with occasional shut-ins.
+///
+/// The rate at each time is multiplied by the uptime factor, and the curve itself isn't delayed by
+/// downtime. Downtime intervals replace the base uptime factor while they're active, and the lowest
+/// factor is used where intervals overlap.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DowntimeSchedule<Time: DeclineTimeUnit> {
+    uptime_factor: f64,
+    intervals: Vec<DowntimeInterval<Time>>,
+}
+
+impl<Time: DeclineTimeUnit> DowntimeSchedule<Time> {
+    /// Creates a schedule with the given base uptime factor, which must be between zero and one.
+    pub fn new(
+        uptime_factor: f64,
+        intervals: Vec<DowntimeInterval<Time>>,
+    ) -> Result<Self, DeclineCurveAnalysisError> {
+        validate_uptime_factor(uptime_factor)?;
+
+        Ok(Self {
+            uptime_factor,
+            intervals,
+        })
+    }
+
+    pub fn uptime_factor(&self) -> f64 {
+        self.uptime_factor
+    }
+
+    pub fn intervals(&self) -> &[DowntimeInterval<Time>] {
+        &self.intervals
+    }
+
+    /// The uptime factor at the given time, relative to the start of the segment or forecast.
+    pub fn uptime_factor_at_time(&self, time: Time) -> f64 {
+        let time = time.value();
+
+        self.intervals
+            .iter()
+            .filter(|interval| interval.contains(time))
+            .map(|interval| interval.uptime_factor)
+            .reduce(f64::min)
+            .unwrap_or(self.uptime_factor)
+    }
+
+    /// Scales a volume between `start` and `end` by the uptime factors, where `volume_between`
+    /// returns the volume without downtime. The uptime factor is constant between the interval
+    /// boundaries, so the result is exact.
+    pub(crate) fn scale_volume_between(
+        &self,
+        start: Time,
+        end: Time,
+        mut volume_between: impl FnMut(Time, Time) -> f64,
+    ) -> f64 {
+        let (start, end) = (start.value(), end.value());
+        if end <= start {
+            return 0.;
+        }
+
+        let mut boundaries = vec![start, end];
+        for interval in &self.intervals {
+            interval.push_boundaries(start, end, &mut boundaries);
+        }
+        boundaries.sort_by(f64::total_cmp);
+        boundaries.dedup();
+
+        boundaries
+            .windows(2)
+            .map(|window| {
+                let midpoint = Time::from(0.5 * (window[0] + window[1]));
+                let uptime_factor = self.uptime_factor_at_time(midpoint);
+                if uptime_factor == 0. {
+                    0.
+                } else {
+                    uptime_factor * volume_between(Time::from(window[0]), Time::from(window[1]))
+                }
+            })
+            .sum()
+    }
+}
+
+fn validate_uptime_factor(uptime_factor: f64) -> Result<(), DeclineCurveAnalysisError> {
+    validate_positive(uptime_factor, "uptime factor")?;
+    if uptime_factor > 1. {
+        return Err(DeclineCurveAnalysisError::InputGreaterThanOne {
+            field: "uptime factor",
+            value: uptime_factor,
+        });
+    }
+    Ok(())
+}
+
+macro_rules! impl_downtime {
+    ($($ty:ident),* $(,)?) => {
+        $(
+            impl<Time: DeclineTimeUnit> $ty<Time> {
+                /// The rate at the given time, scaled by the uptime factor of the schedule.
+                pub fn rate_at_time_with_downtime(
+                    &self,
+                    time: Time,
+                    schedule: &DowntimeSchedule<Time>,
+                ) -> ProductionRate<Time> {
+                    ProductionRate::new(
+                        self.rate_at_time(time).value() * schedule.uptime_factor_at_time(time),
+                    )
+                }
+
+                /// The volume between two times, scaled by the uptime factors of the schedule.
+                pub fn incremental_volume_between_with_downtime(
+                    &self,
+                    start: Time,
+                    end: Time,
+                    schedule: &DowntimeSchedule<Time>,
+                ) -> f64 {
+                    schedule.scale_volume_between(start, end, |start, end| {
+                        self.incremental_volume_between(start, end)
+                    })
+                }
+
+                /// The total volume, scaled by the uptime factors of the schedule.
+                pub fn incremental_volume_with_downtime(
+                    &self,
+                    schedule: &DowntimeSchedule<Time>,
+                ) -> f64 {
+                    self.incremental_volume_between_with_downtime(
+                        Time::from(0.),
+                        self.incremental_duration(),
+                        schedule,
+                    )
+                }
+            }
+        )*
+    };
+}
+
+impl_downtime!(Segment, Forecast);
@@ -0,0 +1,228 @@
+use alloc::string::ToString;
+use core::{
+    ops::{Add, Div, Mul, Sub},
+    str::FromStr,
+};
+
+use crate::{
+    AverageDaysTime, AverageMonthsTime, AverageYearsTime, DeclineCurveAnalysisError,
+    DeclineTimeUnit, NominalDeclineRate, ProductionRate, validate_finite,
+    validate_non_zero_positive_rate,
+};
+
+/// A time unit that's chosen at runtime, for applications that read units from files and can't
+/// choose a [`DeclineTimeUnit`] at compile time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DynTimeUnit {
+    length_days: f64,
+}
+
+impl DynTimeUnit {
+    /// Creates a time unit with the given length in days, which must be positive.
+    pub fn new(length_days: f64) -> Result<Self, DeclineCurveAnalysisError> {
+        validate_non_zero_positive_rate(length_days, "time unit length")?;
+        Ok(Self { length_days })
+    }
+
+    /// The runtime equivalent of a compile-time time unit.
+    pub fn of<Time: DeclineTimeUnit>() -> Self {
+        Self {
+            length_days: Time::LENGTH,
+        }
+    }
+
+    pub fn length_days(&self) -> f64 {
+        self.length_days
+    }
+
+    /// The factor that converts a time in this unit to a compile-time time unit. Rates use the
+    /// reciprocal factor.
+    fn factor_to<Time: DeclineTimeUnit>(self) -> f64 {
+        self.length_days / Time::LENGTH
+    }
+}
+
+impl FromStr for DynTimeUnit {
+    type Err = DeclineCurveAnalysisError;
+
+    /// Parses the name of an average time unit: `days`, `months`, or `years`.
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "day" | "days" => Ok(Self::of::<AverageDaysTime>()),
+            "month" | "months" => Ok(Self::of::<AverageMonthsTime>()),
+            "year" | "years" => Ok(Self::of::<AverageYearsTime>()),
+            _ => Err(DeclineCurveAnalysisError::UnknownInput {
+                field: "time unit",
+                value: name.to_string(),
+                expected: "days, months, or years",
+            }),
+        }
+    }
+}
+
+/// A time in a runtime time unit.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DynTime {
+    value: f64,
+    unit: DynTimeUnit,
+}
+
+impl DynTime {
+    pub fn new(value: f64, unit: DynTimeUnit) -> Self {
+        Self { value, unit }
+    }
+
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    pub fn unit(&self) -> DynTimeUnit {
+        self.unit
+    }
+
+    /// Converts the time to a compile-time time unit, which fails if the time isn't finite.
+    pub fn to_typed<Time: DeclineTimeUnit>(self) -> Result<Time, DeclineCurveAnalysisError> {
+        validate_finite(self.value, "time")?;
+        Ok(Time::from(self.value * self.unit.factor_to::<Time>()))
+    }
+
+    /// Converts the time to a different runtime time unit.
+    pub fn to_unit(self, unit: DynTimeUnit) -> Self {
+        Self {
+            value: self.value * self.unit.length_days / unit.length_days,
+            unit,
+        }
+    }
+
+    pub fn from_typed<Time: DeclineTimeUnit>(time: Time) -> Self {
+        Self::new(time.value(), DynTimeUnit::of::<Time>())
+    }
+}
+
+/// Times in different units are added in the unit of the left-hand side.
+impl Add for DynTime {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.value + other.to_unit(self.unit).value, self.unit)
+    }
+}
+
+/// Times in different units are subtracted in the unit of the left-hand side.
+impl Sub for DynTime {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.value - other.to_unit(self.unit).value, self.unit)
+    }
+}
+
+impl Mul<f64> for DynTime {
+    type Output = Self;
+
+    fn mul(self, factor: f64) -> Self {
+        Self::new(self.value * factor, self.unit)
+    }
+}
+
+impl Div<f64> for DynTime {
+    type Output = Self;
+
+    fn div(self, divisor: f64) -> Self {
+        Self::new(self.value / divisor, self.unit)
+    }
+}
+
+/// A production rate in a runtime time unit.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DynProductionRate {
+    value: f64,
+    unit: DynTimeUnit,
+}
+
+impl DynProductionRate {
+    pub fn new(value: f64, unit: DynTimeUnit) -> Self {
+        Self { value, unit }
+    }
+
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    pub fn unit(&self) -> DynTimeUnit {
+        self.unit
+    }
+
+    /// Converts the rate to a different runtime time unit.
+    pub fn to_unit(self, unit: DynTimeUnit) -> Self {
+        Self {
+            value: self.value * unit.length_days / self.unit.length_days,
+            unit,
+        }
+    }
+}
+
+/// A nominal decline rate in a runtime time unit.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DynNominalDeclineRate {
+    value: f64,
+    unit: DynTimeUnit,
+}
+
+impl DynNominalDeclineRate {
+    pub fn new(value: f64, unit: DynTimeUnit) -> Self {
+        Self { value, unit }
+    }
+
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    pub fn unit(&self) -> DynTimeUnit {
+        self.unit
+    }
+
+    /// Converts the decline rate to a different runtime time unit.
+    pub fn to_unit(self, unit: DynTimeUnit) -> Self {
+        Self {
+            value: self.value * unit.length_days / self.unit.length_days,
+            unit,
+        }
+    }
+}
+
+impl<Time: DeclineTimeUnit> From<ProductionRate<Time>> for DynProductionRate {
+    fn from(rate: ProductionRate<Time>) -> Self {
+        Self::new(rate.value(), DynTimeUnit::of::<Time>())
+    }
+}
+
+/// Converts to a compile-time time unit, which fails if the rate isn't finite.
+impl<Time: DeclineTimeUnit> TryFrom<DynProductionRate> for ProductionRate<Time> {
+    type Error = DeclineCurveAnalysisError;
+
+    fn try_from(rate: DynProductionRate) -> Result<Self, Self::Error> {
+        validate_finite(rate.value, "rate")?;
+        Ok(ProductionRate::new(
+            rate.value / rate.unit.factor_to::<Time>(),
+        ))
+    }
+}
+
+impl<Time: DeclineTimeUnit> From<NominalDeclineRate<Time>> for DynNominalDeclineRate {
+    fn from(decline_rate: NominalDeclineRate<Time>) -> Self {
+        Self::new(decline_rate.value(), DynTimeUnit::of::<Time>())
+    }
+}
+
+/// Converts to a compile-time time unit, which fails if the decline rate isn't finite.
+impl<Time: DeclineTimeUnit> TryFrom<DynNominalDeclineRate> for NominalDeclineRate<Time> {
+    type Error = DeclineCurveAnalysisError;
+
+    fn try_from(decline_rate: DynNominalDeclineRate) -> Result<Self, Self::Error> {
+        validate_finite(decline_rate.value, "decline rate")?;
+        Ok(NominalDeclineRate::new(
+            decline_rate.value / decline_rate.unit.factor_to::<Time>(),
+        ))
+    }
+}
@@ -1,7 +1,13 @@
+#[cfg(not(feature = "std"))]
+use crate::math::FloatMath;
 use crate::{
     DeclineCurveAnalysisError, DeclineRateSignValidation, DeclineTimeUnit, NominalDeclineRate,
-    ProductionRate, approx_gte, validate_decline_rate_sign, validate_duration,
-    validate_incremental_volume, validate_non_zero_decline_rate, validate_non_zero_positive_rate,
+    ProductionRate, RateTruncation, SecantEffectiveDeclineRate, TangentEffectiveDeclineRate,
+    approx_eq, approx_gte, clamp_to_duration, evaluate_at_times, evaluate_at_times_f32,
+    inverse::{rate_at_incremental_volume, time_at_incremental_volume, time_at_rate},
+    kernels, math, split_duration, truncated_duration, validate_decline_rate_sign,
+    validate_duration, validate_incremental_volume, validate_non_zero_decline_rate,
+    validate_non_zero_positive_rate,
 };
 
 /// An exponential decline segment that represents a decline with a constant nominal decline rate.
@@ -23,6 +29,14 @@ impl<Time: DeclineTimeUnit> ExponentialParameters<Time> {
         self.decline_rate
     }
 
+    /// The decline rate as a tangent effective decline rate, which is the same for the whole
+    /// segment.
+    pub fn tangent_effective(
+        &self,
+    ) -> Result<TangentEffectiveDeclineRate<Time>, DeclineCurveAnalysisError> {
+        self.decline_rate.to_tangent_effective()
+    }
+
     pub fn incremental_duration(&self) -> Time {
         self.incremental_duration
     }
@@ -68,7 +82,7 @@ impl<Time: DeclineTimeUnit> ExponentialParameters<Time> {
         }
 
         let incremental_duration = Time::from(
-            -((-incremental_volume * decline_rate.value()) / initial_rate.value).ln_1p()
+            -math::ln_1p((-incremental_volume * decline_rate.value()) / initial_rate.value)
                 / decline_rate.value(),
         );
         validate_duration(incremental_duration)?;
@@ -105,7 +119,7 @@ impl<Time: DeclineTimeUnit> ExponentialParameters<Time> {
         }
 
         let incremental_duration =
-            Time::from((initial_rate.value / final_rate.value).ln() / decline_rate.value());
+            Time::from(math::ln(initial_rate.value / final_rate.value) / decline_rate.value());
         validate_duration(incremental_duration)?;
 
         Ok(Self {
@@ -115,9 +129,81 @@ impl<Time: DeclineTimeUnit> ExponentialParameters<Time> {
         })
     }
 
+    /// The decline rate of an exponential decline never changes, so this only succeeds if the
+    /// final decline rate is the same as the decline rate, which gives a segment with zero
+    /// duration. This matches the constructors of the other Arps segments.
+    pub fn from_final_decline_rate(
+        initial_rate: ProductionRate<Time>,
+        decline_rate: NominalDeclineRate<Time>,
+        final_decline_rate: NominalDeclineRate<Time>,
+    ) -> Result<Self, DeclineCurveAnalysisError> {
+        validate_non_zero_positive_rate(initial_rate.value, "initial rate")?;
+        validate_non_zero_decline_rate(decline_rate.value(), "decline rate")?;
+        validate_non_zero_decline_rate(final_decline_rate.value(), "final decline rate")?;
+
+        if !approx_eq(decline_rate.value(), final_decline_rate.value()) {
+            return Err(DeclineCurveAnalysisError::CannotSolveDecline);
+        }
+
+        Ok(Self {
+            initial_rate,
+            decline_rate,
+            incremental_duration: Time::from(0.),
+        })
+    }
+
+    /// Like the nominal constructor, but the decline rate is a secant effective decline rate. With
+    /// an exponent of zero, this is the same as a tangent effective decline rate.
+    pub fn from_secant_effective_incremental_duration(
+        initial_rate: ProductionRate<Time>,
+        decline_rate: SecantEffectiveDeclineRate<Time>,
+        incremental_duration: Time,
+    ) -> Result<Self, DeclineCurveAnalysisError> {
+        Self::from_incremental_duration(
+            initial_rate,
+            decline_rate.to_nominal(0.)?,
+            incremental_duration,
+        )
+    }
+
+    /// Like the nominal constructor, but the decline rate is a secant effective decline rate. With
+    /// an exponent of zero, this is the same as a tangent effective decline rate.
+    pub fn from_secant_effective_final_rate(
+        initial_rate: ProductionRate<Time>,
+        decline_rate: SecantEffectiveDeclineRate<Time>,
+        final_rate: ProductionRate<Time>,
+    ) -> Result<Self, DeclineCurveAnalysisError> {
+        Self::from_final_rate(initial_rate, decline_rate.to_nominal(0.)?, final_rate)
+    }
+
+    /// Like the nominal constructor, but the decline rate is a tangent effective decline rate.
+    pub fn from_tangent_effective_incremental_duration(
+        initial_rate: ProductionRate<Time>,
+        decline_rate: TangentEffectiveDeclineRate<Time>,
+        incremental_duration: Time,
+    ) -> Result<Self, DeclineCurveAnalysisError> {
+        Self::from_incremental_duration(
+            initial_rate,
+            decline_rate.to_nominal()?,
+            incremental_duration,
+        )
+    }
+
+    /// Like the nominal constructor, but the decline rate is a tangent effective decline rate.
+    pub fn from_tangent_effective_final_rate(
+        initial_rate: ProductionRate<Time>,
+        decline_rate: TangentEffectiveDeclineRate<Time>,
+        final_rate: ProductionRate<Time>,
+    ) -> Result<Self, DeclineCurveAnalysisError> {
+        Self::from_final_rate(initial_rate, decline_rate.to_nominal()?, final_rate)
+    }
+
     fn incremental_volume_at_time_without_clamping(&self, time: Time) -> f64 {
-        let exp_part = -(-self.decline_rate.value() * time.value()).exp_m1();
-        (exp_part * self.initial_rate.value) / self.decline_rate.value()
+        kernels::exponential_incremental_volume(
+            self.initial_rate.value,
+            self.decline_rate.value(),
+            time.value(),
+        )
     }
 
     pub fn incremental_volume_at_time(&self, time: Time) -> f64 {
@@ -128,20 +214,152 @@ impl<Time: DeclineTimeUnit> ExponentialParameters<Time> {
         }
     }
 
+    /// The volume produced between two times, which are clamped to be within the segment. This is
+    /// negative if `end` is before `start`.
+    pub fn incremental_volume_between(&self, start: Time, end: Time) -> f64 {
+        let start = clamp_to_duration(start, self.incremental_duration);
+        let end = clamp_to_duration(end, self.incremental_duration);
+
+        self.incremental_volume_at_time_without_clamping(end)
+            - self.incremental_volume_at_time_without_clamping(start)
+    }
+
+    /// Returns the time at which the cumulative volume reaches the given volume, which is the
+    /// inverse of `incremental_volume_at_time`.
+    ///
+    /// This fails if the volume isn't reached before the end of the segment.
+    pub fn time_at_incremental_volume(
+        &self,
+        incremental_volume: f64,
+    ) -> Result<Time, DeclineCurveAnalysisError> {
+        time_at_incremental_volume(
+            self.incremental_volume(),
+            self.incremental_duration,
+            incremental_volume,
+            || {
+                Ok(Self::from_incremental_volume(
+                    self.initial_rate,
+                    self.decline_rate,
+                    incremental_volume,
+                )?
+                .incremental_duration)
+            },
+        )
+    }
+
+    /// Returns the rate at the time the cumulative volume reaches the given volume, for
+    /// rate-cumulative analysis.
+    ///
+    /// This fails if the volume isn't reached before the end of the segment.
+    pub fn rate_at_incremental_volume(
+        &self,
+        incremental_volume: f64,
+    ) -> Result<ProductionRate<Time>, DeclineCurveAnalysisError> {
+        // The volume is `(q_i - q) / D`.
+        rate_at_incremental_volume(self.incremental_volume(), incremental_volume, |volume| {
+            Ok((-self.decline_rate.value()).mul_add(volume, self.initial_rate.value))
+        })
+    }
+
     pub fn incremental_volume(&self) -> f64 {
         self.incremental_volume_at_time_without_clamping(self.incremental_duration)
     }
 
     fn rate_at_time_without_clamping(&self, time: Time) -> ProductionRate<Time> {
-        ProductionRate::new(
-            self.initial_rate.value * (-self.decline_rate.value() * time.value()).exp(),
+        ProductionRate::new(kernels::exponential_rate(
+            self.initial_rate.value,
+            self.decline_rate.value(),
+            time.value(),
+        ))
+    }
+
+    /// Splits the segment at `time` into two segments that together reproduce the original. The
+    /// second segment starts at the rate at the split, with the same decline rate.
+    pub fn split_at(&self, time: Time) -> (Self, Self) {
+        let (first_duration, second_duration) = split_duration(time, self.incremental_duration);
+        (
+            Self {
+                initial_rate: self.initial_rate,
+                decline_rate: self.decline_rate,
+                incremental_duration: first_duration,
+            },
+            Self {
+                initial_rate: self.rate_at_time_without_clamping(first_duration),
+                decline_rate: self.decline_rate,
+                incremental_duration: second_duration,
+            },
         )
     }
 
+    /// Returns a copy where every rate is multiplied by `factor`, keeping the same decline.
+    pub(crate) fn scale_rates(&self, factor: f64) -> Self {
+        Self {
+            initial_rate: ProductionRate::new(self.initial_rate.value * factor),
+            ..self.clone()
+        }
+    }
+
+    /// Returns a copy of the parameters that ends at the first time the rate falls to the economic
+    /// limit, along with the volume and duration that were removed.
+    pub fn truncate_at_rate(
+        &self,
+        economic_limit_rate: ProductionRate<Time>,
+    ) -> Result<RateTruncation<Self, Time>, DeclineCurveAnalysisError> {
+        let incremental_duration = truncated_duration(
+            self.initial_rate,
+            self.final_rate(),
+            self.incremental_duration,
+            economic_limit_rate,
+            || {
+                Ok(Self::from_final_rate(
+                    self.initial_rate,
+                    self.decline_rate,
+                    economic_limit_rate,
+                )?
+                .incremental_duration)
+            },
+        )?;
+
+        let parameters = Self::from_incremental_duration(
+            self.initial_rate,
+            self.decline_rate,
+            incremental_duration,
+        )?;
+
+        Ok(RateTruncation {
+            truncated_volume: self.incremental_volume() - parameters.incremental_volume(),
+            truncated_duration: Time::from(
+                self.incremental_duration.value() - parameters.incremental_duration.value(),
+            ),
+            parameters,
+        })
+    }
+
     pub fn final_rate(&self) -> ProductionRate<Time> {
         self.rate_at_time_without_clamping(self.incremental_duration)
     }
 
+    /// Returns the first time at which the rate reaches the given rate.
+    ///
+    /// This fails if the rate isn't reached before the end of the segment.
+    pub fn time_at_rate(
+        &self,
+        rate: ProductionRate<Time>,
+    ) -> Result<Time, DeclineCurveAnalysisError> {
+        time_at_rate(
+            self.initial_rate,
+            self.final_rate(),
+            self.incremental_duration,
+            rate,
+            || {
+                Ok(
+                    Self::from_final_rate(self.initial_rate, self.decline_rate, rate)?
+                        .incremental_duration,
+                )
+            },
+        )
+    }
+
     pub fn rate_at_time(&self, time: Time) -> ProductionRate<Time> {
         if time.value() > self.incremental_duration.value() {
             self.final_rate()
@@ -149,4 +367,68 @@ impl<Time: DeclineTimeUnit> ExponentialParameters<Time> {
             self.rate_at_time_without_clamping(time)
         }
     }
+
+    /// Evaluates `rate_at_time` for each time, writing the rates to `out`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `times` and `out` have different lengths.
+    pub fn rates_at_times(&self, times: &[Time], out: &mut [f64]) {
+        evaluate_at_times(times, out, self.incremental_duration, |time| {
+            self.rate_at_time_without_clamping(time).value()
+        });
+    }
+
+    /// Evaluates `incremental_volume_at_time` for each time, writing the volumes to `out`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `times` and `out` have different lengths.
+    pub fn incremental_volumes_at_times(&self, times: &[Time], out: &mut [f64]) {
+        evaluate_at_times(times, out, self.incremental_duration, |time| {
+            self.incremental_volume_at_time_without_clamping(time)
+        });
+    }
+
+    /// Like `rates_at_times`, but with `f32` times and rates, where the times are in the segment's
+    /// time unit. See [`Segment::rates_at_times_f32`](crate::Segment::rates_at_times_f32) for the
+    /// precision.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `times` and `out` have different lengths.
+    pub fn rates_at_times_f32(&self, times: &[f32], out: &mut [f32]) {
+        let initial_rate = self.initial_rate.value as f32;
+        let decline_rate = self.decline_rate.value() as f32;
+        evaluate_at_times_f32(times, out, self.incremental_duration, |time| {
+            kernels::exponential_rate(initial_rate, decline_rate, time)
+        });
+    }
+
+    /// Like `incremental_volumes_at_times`, but with `f32` times and volumes. See
+    /// [`Segment::rates_at_times_f32`](crate::Segment::rates_at_times_f32) for the precision.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `times` and `out` have different lengths.
+    pub fn incremental_volumes_at_times_f32(&self, times: &[f32], out: &mut [f32]) {
+        let initial_rate = self.initial_rate.value as f32;
+        let decline_rate = self.decline_rate.value() as f32;
+        evaluate_at_times_f32(times, out, self.incremental_duration, |time| {
+            kernels::exponential_incremental_volume(initial_rate, decline_rate, time)
+        });
+    }
+
+    /// The instantaneous nominal decline rate at the given time, which is constant for exponential
+    /// declines.
+    pub fn decline_rate_at_time(&self, _time: Time) -> NominalDeclineRate<Time> {
+        self.decline_rate
+    }
+
+    /// The derivative of the rate with respect to time, which is `-D * q(t)`. Times past the end of
+    /// the segment are clamped to the end, like `decline_rate_at_time`.
+    pub fn rate_derivative_at_time(&self, time: Time) -> f64 {
+        let time = Time::from(time.value().min(self.incremental_duration.value()));
+        -self.decline_rate.value() * self.rate_at_time_without_clamping(time).value
+    }
 }
@@ -0,0 +1,120 @@
+//! Flat tables of forecasts for spreadsheets and BI tools (e.g., Excel or Spotfire), with one row
+//! per reporting period.
+
+use alloc::{string::String, vec::Vec};
+use core::fmt::Write;
+
+use crate::{
+    CalendarDate, CalendarTime, DeclineCurveAnalysisError, DeclineTimeUnit, Forecast, Phase,
+    ProductionRate, ReportFrequency, ScheduleBuilder,
+};
+
+/// Options for [`flat_table`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FlatTableOptions {
+    /// The date that the forecast starts.
+    pub start_date: CalendarDate,
+    /// The phase of the forecast, which is written to every row.
+    pub phase: Phase,
+    pub frequency: ReportFrequency,
+    /// Ends the table at the start of this date if the forecast hasn't already ended.
+    pub end_date: Option<CalendarDate>,
+}
+
+impl FlatTableOptions {
+    /// Options for a monthly table of the whole forecast.
+    pub fn new(start_date: CalendarDate, phase: Phase) -> Self {
+        Self {
+            start_date,
+            phase,
+            frequency: ReportFrequency::default(),
+            end_date: None,
+        }
+    }
+}
+
+/// A row of a flat table, which is one reporting period of a forecast.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FlatTableRow<Time: DeclineTimeUnit> {
+    /// The first day of the period, which is the start date of the forecast for the first period.
+    pub date: CalendarDate,
+    /// The length of the period in days, which is shorter for partial first and last periods.
+    pub days: f64,
+    pub phase: Phase,
+    /// The average rate over the period.
+    pub rate: ProductionRate<Time>,
+    pub volume: f64,
+    /// The cumulative volume at the end of the period, including the initial cumulative volume of
+    /// the forecast.
+    pub cumulative_volume: f64,
+    /// The index of the segment that's active at the start of the period.
+    pub segment_index: usize,
+}
+
+/// The header of the CSV written by [`to_csv`].
+pub const FLAT_TABLE_CSV_HEADER: &str =
+    "date,days,phase,rate,volume,cumulative_volume,segment_index";
+
+/// Builds a flat table of the forecast with one row per reporting period, where periods are
+/// dated as for [`ScheduleBuilder`].
+pub fn flat_table<Time: DeclineTimeUnit>(
+    forecast: &Forecast<Time>,
+    options: &FlatTableOptions,
+) -> Result<Vec<FlatTableRow<Time>>, DeclineCurveAnalysisError> {
+    let mut builder =
+        ScheduleBuilder::new(forecast, options.start_date).frequency(options.frequency);
+    if let Some(end_date) = options.end_date {
+        builder = builder.end_date(end_date);
+    }
+
+    let mut cumulative_volume = forecast.initial_cumulative_volume();
+    Ok(builder
+        .build()?
+        .into_iter()
+        .map(|row| {
+            cumulative_volume += row.volume;
+            FlatTableRow {
+                date: row.date,
+                days: Time::from(row.end.value() - row.start.value())
+                    .to_unit::<CalendarTime>()
+                    .days,
+                phase: options.phase,
+                rate: row.rate,
+                volume: row.volume,
+                cumulative_volume,
+                segment_index: forecast
+                    .segment_index_at_time(row.start.value())
+                    .map_or(0, |(index, _)| index),
+            }
+        })
+        .collect())
+}
+
+/// Writes a flat table as CSV, with a [header](FLAT_TABLE_CSV_HEADER) and dates formatted as
+/// `YYYY-MM-DD`.
+///
+/// Numbers are written with full precision so that they can be read back exactly.
+pub fn to_csv<Time: DeclineTimeUnit>(rows: &[FlatTableRow<Time>]) -> String {
+    let mut csv = String::new();
+    csv.push_str(FLAT_TABLE_CSV_HEADER);
+    csv.push('\n');
+
+    for row in rows {
+        // Writing to a string can't fail.
+        let _ = writeln!(
+            csv,
+            "{:04}-{:02}-{:02},{},{},{},{},{},{}",
+            row.date.year(),
+            row.date.month(),
+            row.date.day(),
+            row.days,
+            row.phase.name(),
+            row.rate.value(),
+            row.volume,
+            row.cumulative_volume,
+            row.segment_index,
+        );
+    }
+
+    csv
+}
@@ -0,0 +1,98 @@
+//! Power functions for hyperbolic segments, which use fast approximations when the `fast-math`
+//! feature is enabled.
+//!
+//! The approximations reduce `ln` to a mantissa near one and `exp` to a remainder within
+//! `ln(2) / 2` of zero, then evaluate short polynomials, so they skip the extra work that `libm`
+//! does to be correctly rounded. Their relative error is at most 1e-7 (and typically closer to
+//! 1e-9), which is far below the uncertainty of a forecast, but results won't match the exact
+//! functions to the last digit.
+//!
+//! The gain depends on the target: the approximation is about three times as fast as `powf` from
+//! the `libm` crate, which `no_std` and some `wasm` builds use, but about as fast as glibc's `pow`.
+
+use crate::math;
+/// Returns `base ^ exponent`.
+#[cfg(not(feature = "fast-math"))]
+#[inline]
+pub(crate) fn powf(base: f64, exponent: f64) -> f64 {
+    math::powf(base, exponent)
+}
+
+/// Returns `base ^ exponent` as `exp(exponent * ln(base))`. Bases that aren't positive, finite
+/// numbers, large exponents, and results that would overflow or underflow fall back to the exact
+/// `powf`.
+#[cfg(feature = "fast-math")]
+#[inline]
+pub(crate) fn powf(base: f64, exponent: f64) -> f64 {
+    if (f64::MIN_POSITIVE..=f64::MAX).contains(&base) && exponent.abs() <= 1000. {
+        let power = exponent * ln(base);
+        if power.abs() < 700. {
+            return exp(power);
+        }
+    }
+
+    exact_powf(base, exponent)
+}
+
+#[cfg(feature = "fast-math")]
+#[cold]
+#[inline(never)]
+fn exact_powf(base: f64, exponent: f64) -> f64 {
+    math::powf(base, exponent)
+}
+
+/// Returns the natural logarithm of a positive, normal number, with an absolute error of about
+/// 2e-11.
+#[cfg(feature = "fast-math")]
+#[inline]
+fn ln(value: f64) -> f64 {
+    use core::f64::consts::{LN_2, SQRT_2};
+
+    // Split the value into `2 ^ exponent * mantissa`, where the mantissa is within
+    // `[sqrt(2) / 2, sqrt(2)]`.
+    let bits = value.to_bits();
+    let mut exponent = ((bits >> 52) & 0x7ff) as i64 - 1023;
+    let mut mantissa = f64::from_bits((bits & 0x000f_ffff_ffff_ffff) | 0x3ff0_0000_0000_0000);
+    if mantissa > SQRT_2 {
+        mantissa *= 0.5;
+        exponent += 1;
+    }
+
+    // `ln(m) = 2 * atanh(s)`, where `s = (m - 1) / (m + 1)` is at most 0.172, so the series
+    // `2 * (s + s^3 / 3 + ... + s^11 / 11)` converges quickly.
+    let s = (mantissa - 1.) / (mantissa + 1.);
+    let s2 = s * s;
+    let series =
+        ((((s2 * (1. / 11.) + 1. / 9.) * s2 + 1. / 7.) * s2 + 1. / 5.) * s2 + 1. / 3.) * s2 + 1.;
+
+    2. * s * series + exponent as f64 * LN_2
+}
+
+/// Returns `e ^ value`, where `value` is within `(-700, 700)`, with a relative error of about
+/// 5e-9.
+#[cfg(feature = "fast-math")]
+#[inline]
+fn exp(value: f64) -> f64 {
+    use core::f64::consts::{LN_2, LOG2_E};
+
+    // Adding and subtracting `1.5 * 2 ^ 52` rounds to the nearest integer without a call to
+    // `round`, which isn't inlined on all targets.
+    const ROUNDING_SHIFT: f64 = 6_755_399_441_055_744.;
+
+    // Split the value into `k * ln(2) + r`, where `r` is within `ln(2) / 2` of zero, and
+    // evaluate the Taylor series of `e ^ r` to the seventh power.
+    let k = (value * LOG2_E + ROUNDING_SHIFT) - ROUNDING_SHIFT;
+    let r = value - k * LN_2;
+    let series =
+        ((((((r * (1. / 5040.) + 1. / 720.) * r + 1. / 120.) * r + 1. / 24.) * r + 1. / 6.) * r
+            + 0.5)
+            * r
+            + 1.)
+            * r
+            + 1.;
+
+    // `2 ^ k`, which is a normal number because `k` is within `[-1010, 1010]`.
+    let scale = f64::from_bits(((k as i64 + 1023) as u64) << 52);
+
+    series * scale
+}
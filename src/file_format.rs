@@ -0,0 +1,147 @@
+//! A versioned JSON file format for forecasts, which is enabled by the `json` feature.
+//!
+//! Forecast files are pretty-printed so that they can be reviewed and diffed as text. A file is an
+//! object with these fields:
+//!
+//! - `format`: always `"decline-curve-analysis/forecast"`.
+//! - `version`: the version of the format, which is [`FORECAST_FILE_VERSION`] when written.
+//! - `time_unit_days`: the length of the time unit of the forecast in days (e.g., 365.25 for
+//!   [`AverageYearsTime`](crate::AverageYearsTime)), which must match the time unit that the file
+//!   is read as.
+//! - `forecast`: the forecast, in the same representation as its serde implementation.
+//!
+//! Unknown fields are ignored, so fields can be added without changing the version. Files from
+//! older versions are migrated when they're read, and files from newer versions are rejected.
+//!
+//! Version 0 is a bare forecast without the other fields, as written by serializing a
+//! [`Forecast`] directly. Its time unit isn't recorded, so it's assumed to be the time unit that
+//! the file is read as.
+
+use alloc::{format, string::String};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use serde_json::{Value, json};
+use std::path::Path;
+
+use crate::{DeclineCurveAnalysisError, DeclineTimeUnit, Forecast};
+
+/// The version of the forecast file format that's written.
+pub const FORECAST_FILE_VERSION: u64 = 1;
+
+const FORMAT: &str = "decline-curve-analysis/forecast";
+
+#[derive(Serialize)]
+struct ForecastFile<'a, Time: DeclineTimeUnit + Serialize> {
+    format: &'static str,
+    version: u64,
+    time_unit_days: f64,
+    forecast: &'a Forecast<Time>,
+}
+
+#[derive(Deserialize)]
+#[serde(bound(deserialize = "Time: DeserializeOwned"))]
+struct OwnedForecastFile<Time: DeclineTimeUnit> {
+    format: String,
+    time_unit_days: f64,
+    forecast: Forecast<Time>,
+}
+
+fn read_failed(error: impl core::fmt::Display) -> DeclineCurveAnalysisError {
+    DeclineCurveAnalysisError::ReadFailed {
+        reason: format!("{error}"),
+    }
+}
+
+fn write_failed(error: impl core::fmt::Display) -> DeclineCurveAnalysisError {
+    DeclineCurveAnalysisError::WriteFailed {
+        reason: format!("{error}"),
+    }
+}
+
+/// The version of a parsed file, where a file without a format is version 0.
+fn file_version(file: &Value) -> Result<u64, DeclineCurveAnalysisError> {
+    if file.get("format").is_none() {
+        return Ok(0);
+    }
+
+    file.get("version")
+        .and_then(Value::as_u64)
+        .ok_or_else(|| read_failed("forecast file has no version"))
+}
+
+/// Migrates a file from `version` to the next version.
+fn migrate<Time: DeclineTimeUnit>(
+    version: u64,
+    file: Value,
+) -> Result<Value, DeclineCurveAnalysisError> {
+    match version {
+        0 => Ok(json!({
+            "format": FORMAT,
+            "version": 1,
+            "time_unit_days": Time::LENGTH,
+            "forecast": file,
+        })),
+        _ => Err(read_failed(format!(
+            "forecast file version {version} can't be migrated"
+        ))),
+    }
+}
+
+impl<Time: DeclineTimeUnit + Serialize> Forecast<Time> {
+    /// Writes the forecast in the current version of the file format (see the
+    /// [module documentation](crate::file_format)).
+    pub fn to_file_string(&self) -> Result<String, DeclineCurveAnalysisError> {
+        serde_json::to_string_pretty(&ForecastFile {
+            format: FORMAT,
+            version: FORECAST_FILE_VERSION,
+            time_unit_days: Time::LENGTH,
+            forecast: self,
+        })
+        .map_err(write_failed)
+    }
+
+    /// Writes the forecast to a file at `path`, replacing any existing file.
+    pub fn to_file(&self, path: impl AsRef<Path>) -> Result<(), DeclineCurveAnalysisError> {
+        std::fs::write(path, self.to_file_string()?).map_err(write_failed)
+    }
+}
+
+impl<Time: DeclineTimeUnit + DeserializeOwned> Forecast<Time> {
+    /// Reads a forecast from any version of the file format up to the current version, which
+    /// fails if the file was written in a different time unit.
+    pub fn from_file_str(contents: &str) -> Result<Self, DeclineCurveAnalysisError> {
+        let mut file: Value = serde_json::from_str(contents).map_err(read_failed)?;
+
+        let mut version = file_version(&file)?;
+        if version > FORECAST_FILE_VERSION {
+            return Err(read_failed(format!(
+                "forecast file version {version} is newer than the supported version \
+                 {FORECAST_FILE_VERSION}"
+            )));
+        }
+        while version < FORECAST_FILE_VERSION {
+            file = migrate::<Time>(version, file)?;
+            version += 1;
+        }
+
+        let file: OwnedForecastFile<Time> = serde_json::from_value(file).map_err(read_failed)?;
+        if file.format != FORMAT {
+            return Err(read_failed(format!(
+                "expected the format to be \"{FORMAT}\", but got \"{}\"",
+                file.format
+            )));
+        }
+        if file.time_unit_days != Time::LENGTH {
+            return Err(DeclineCurveAnalysisError::TimeUnitMismatch {
+                days: file.time_unit_days,
+                expected_days: Time::LENGTH,
+            });
+        }
+
+        Ok(file.forecast)
+    }
+
+    /// Reads a forecast from a file at `path` (see [`Forecast::from_file_str`]).
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, DeclineCurveAnalysisError> {
+        Self::from_file_str(&std::fs::read_to_string(path).map_err(read_failed)?)
+    }
+}
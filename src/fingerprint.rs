@@ -0,0 +1,70 @@
+use crate::{DeclineTimeUnit, Segment};
+
+/// The number of low mantissa bits that are rounded away before hashing, which keeps 32 of the 52
+/// mantissa bits (about 9 significant digits).
+const ROUNDED_MANTISSA_BITS: u32 = 20;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+/// A 64-bit FNV-1a hash of quantized values, which is the same on every platform and in every
+/// version of Rust (unlike [`core::hash::Hasher`] implementations such as `DefaultHasher`).
+pub(crate) struct Fingerprinter {
+    hash: u64,
+}
+
+impl Fingerprinter {
+    pub(crate) fn new<Time: DeclineTimeUnit>() -> Self {
+        let mut fingerprinter = Self {
+            hash: FNV_OFFSET_BASIS,
+        };
+        // The same values describe a different forecast in another time unit.
+        fingerprinter.write_value(Time::LENGTH);
+        fingerprinter
+    }
+
+    pub(crate) fn write_segment<Time: DeclineTimeUnit>(&mut self, segment: &Segment<Time>) {
+        // The tags are fixed so that adding or reordering segment types doesn't change the
+        // fingerprints of existing forecasts.
+        let tag = match segment {
+            Segment::Delay(_) => 1,
+            Segment::Exponential(_) => 2,
+            Segment::Flat(_) => 3,
+            Segment::Harmonic(_) => 4,
+            Segment::Hyperbolic(_) => 5,
+            Segment::Linear(_) => 6,
+            Segment::LogisticGrowth(_) => 7,
+            Segment::PowerLawExponential(_) => 8,
+        };
+        self.write_u64(tag);
+        for value in segment.parameter_values() {
+            self.write_value(value);
+        }
+    }
+
+    /// Writes a value rounded to the nearest multiple of its retained mantissa bits, so that
+    /// round-off between code paths usually doesn't change the hash. Negative zero is written as
+    /// zero, and every NaN is written the same.
+    pub(crate) fn write_value(&mut self, value: f64) {
+        let bits = if value == 0. {
+            0
+        } else if value.is_nan() {
+            u64::MAX
+        } else {
+            // Adding half of the rounded bits rounds the magnitude, which carries into the exponent
+            // when the mantissa overflows.
+            (value.to_bits() + (1 << (ROUNDED_MANTISSA_BITS - 1))) >> ROUNDED_MANTISSA_BITS
+        };
+        self.write_u64(bits);
+    }
+
+    pub(crate) fn write_u64(&mut self, value: u64) {
+        for byte in value.to_le_bytes() {
+            self.hash = (self.hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    pub(crate) fn finish(&self) -> u64 {
+        self.hash
+    }
+}
@@ -0,0 +1,743 @@
+#[cfg(not(feature = "std"))]
+use crate::math::FloatMath;
+use crate::{
+    DeclineCurveAnalysisError, DeclineTimeUnit, ExponentialParameters, HarmonicParameters,
+    HyperbolicParameters, LinearParameters, NominalDeclineRate, ProductionRate, Segment, kernels,
+    math,
+    numerical::{
+        LeastSquaresSolution, least_squares_covariance, levenberg_marquardt_with_jacobian,
+    },
+    validate_finite, validate_positive,
+};
+use alloc::{string::ToString, vec, vec::Vec};
+
+mod bootstrap;
+mod exponent;
+mod options;
+mod quality;
+mod robust;
+mod segmentation;
+mod transient;
+mod uncertainty;
+
+pub use bootstrap::{BootstrapOptions, BootstrapResult, bootstrap_eur, bootstrap_eur_with_rng};
+pub use exponent::{ExponentEstimate, estimate_exponent};
+pub use options::{FitLoss, FitOptions, FitSpace, LowessSmoothing, SigmaClipping};
+pub use quality::{QualityGate, QualityGates, fit_best_with_gates};
+use robust::{huber_weights, lowess, standard_deviation};
+pub use segmentation::{
+    Regime, SegmentationOptions, SegmentedFit, detect_changepoints, fit_segmented,
+};
+pub use transient::{TransientFit, TransientFitOptions, fit_transient_to_boundary};
+pub use uncertainty::{ConfidenceBand, ParameterCovariance};
+
+/// Maximum number of reweighting iterations for robust losses.
+const MAX_REWEIGHTING_ITERATIONS: usize = 50;
+
+/// Minimum sigma-clipping cutoff, relative to the maximum sample rate.
+const SIGMA_CLIPPING_RELATIVE_FLOOR: f64 = 1e-9;
+
+/// Bounds for the exponent when fitting hyperbolic segments.
+const MIN_FIT_EXPONENT: f64 = 1e-3;
+const MAX_FIT_EXPONENT: f64 = 2.;
+
+/// Goodness-of-fit statistics for a fitted segment.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FitStatistics {
+    /// The number of samples used in the fit.
+    pub sample_count: usize,
+    /// The sum of squared residuals between the sample rates and fitted rates.
+    pub sum_of_squared_residuals: f64,
+    /// The root mean squared error between the sample rates and fitted rates.
+    pub root_mean_squared_error: f64,
+    /// The coefficient of determination.
+    pub r_squared: f64,
+    /// The number of solver iterations.
+    pub iterations: usize,
+}
+
+/// The result of fitting a segment to production samples.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FitResult<Parameters> {
+    /// The fitted segment, with a duration that spans the samples.
+    pub parameters: Parameters,
+    /// The residual (sample rate minus fitted rate) for each sample.
+    pub residuals: Vec<f64>,
+    /// The indices of samples that were rejected as outliers and excluded from the fit.
+    pub rejected_samples: Vec<usize>,
+    pub statistics: FitStatistics,
+    /// The estimated covariance of the fitted parameters, or `None` if the samples don't
+    /// constrain every parameter (e.g., the Jacobian is singular).
+    pub covariance: Option<ParameterCovariance>,
+}
+
+impl<Parameters> FitResult<Parameters> {
+    /// Returns the confidence interval of the fitted rate at a time, where the level is the
+    /// two-sided coverage probability (e.g., 0.95).
+    ///
+    /// See [`ParameterCovariance::confidence_band`] for details.
+    pub fn confidence_band<Time: DeclineTimeUnit>(
+        &self,
+        time: Time,
+        level: f64,
+    ) -> Result<ConfidenceBand<Time>, DeclineCurveAnalysisError> {
+        self.covariance
+            .as_ref()
+            .ok_or_else(|| DeclineCurveAnalysisError::InvalidInput {
+                reason: "the parameter covariance couldn't be estimated from the samples"
+                    .to_string(),
+            })?
+            .confidence_band(time, level)
+    }
+}
+
+/// A model that can be fitted to production samples.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FitModel {
+    Exponential,
+    Harmonic,
+    Hyperbolic,
+    Linear,
+}
+
+impl FitModel {
+    /// All models that can be fitted.
+    pub const ALL: [Self; 4] = [
+        Self::Exponential,
+        Self::Harmonic,
+        Self::Hyperbolic,
+        Self::Linear,
+    ];
+
+    /// The number of fitted parameters for the model.
+    pub fn parameter_count(self) -> usize {
+        match self {
+            Self::Exponential | Self::Harmonic | Self::Linear => 2,
+            Self::Hyperbolic => 3,
+        }
+    }
+
+    /// Evaluates the rate for the model parameters `[q_i, D_i, b]` without any validation, where
+    /// the exponent is only used by hyperbolic models.
+    pub fn rate(self, parameters: &[f64], time: f64) -> f64 {
+        let initial_rate = parameters[0];
+        let decline_rate = parameters[1];
+
+        match self {
+            Self::Exponential => kernels::exponential_rate(initial_rate, decline_rate, time),
+            Self::Harmonic => kernels::harmonic_rate(initial_rate, decline_rate, time),
+            Self::Hyperbolic => {
+                kernels::hyperbolic_rate(initial_rate, decline_rate, parameters[2], time)
+            }
+            Self::Linear => kernels::linear_rate(initial_rate, decline_rate, time),
+        }
+    }
+
+    /// Evaluates the partial derivatives of the rate with respect to each of the model parameters
+    /// `[q_i, D_i, b]` without any validation, which is a row of the Jacobian for least-squares
+    /// fitting.
+    ///
+    /// The returned vector has one derivative per parameter, so it doesn't include the exponent
+    /// for models other than hyperbolic.
+    pub fn rate_jacobian(self, parameters: &[f64], time: f64) -> Vec<f64> {
+        let initial_rate = parameters[0];
+        let decline_rate = parameters[1];
+
+        match self {
+            Self::Exponential => {
+                let relative_rate = math::exp(-decline_rate * time);
+                vec![relative_rate, -time * initial_rate * relative_rate]
+            }
+            Self::Harmonic => {
+                let denominator = time.mul_add(decline_rate, 1.);
+                vec![
+                    1. / denominator,
+                    -time * initial_rate / (denominator * denominator),
+                ]
+            }
+            Self::Hyperbolic => {
+                let exponent = parameters[2];
+                let denominator = time.mul_add(exponent * decline_rate, 1.);
+                let relative_rate = math::powf(denominator, -1. / exponent);
+                let rate = initial_rate * relative_rate;
+                vec![
+                    relative_rate,
+                    -time * rate / denominator,
+                    rate / exponent
+                        * (math::ln_1p(exponent * decline_rate * time) / exponent
+                            - decline_rate * time / denominator),
+                ]
+            }
+            Self::Linear => vec![(-decline_rate).mul_add(time, 1.), -time * initial_rate],
+        }
+    }
+
+    /// The Jacobian of the weighted residuals in the fit space with respect to the parameters.
+    fn residual_jacobian(
+        self,
+        space: FitSpace,
+        samples: &[(f64, f64)],
+        weights: &[f64],
+        parameters: &[f64],
+    ) -> Vec<Vec<f64>> {
+        samples
+            .iter()
+            .zip(weights)
+            .map(|((time, _), weight)| {
+                let fitted_rate = self.rate(parameters, *time);
+                self.rate_jacobian(parameters, *time)
+                    .into_iter()
+                    .map(|derivative| {
+                        -weight.sqrt() * space.fitted_rate_derivative(fitted_rate, derivative)
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn bounds(self, initial_rate_guess: f64) -> (Vec<f64>, Vec<f64>) {
+        // Allow the initial rate to vary widely, but keep it positive.
+        let min_rate = initial_rate_guess * 1e-6;
+        let max_rate = initial_rate_guess * 1e6;
+
+        match self {
+            Self::Exponential | Self::Linear => {
+                (vec![min_rate, f64::MIN], vec![max_rate, f64::MAX])
+            }
+            // Keep the harmonic and hyperbolic denominators positive over the samples.
+            Self::Harmonic => (vec![min_rate, 0.], vec![max_rate, f64::MAX]),
+            Self::Hyperbolic => (
+                vec![min_rate, 0., MIN_FIT_EXPONENT],
+                vec![max_rate, f64::MAX, MAX_FIT_EXPONENT],
+            ),
+        }
+    }
+
+    fn initial_guess(self, samples: &[(f64, f64)], options: &FitOptions) -> Vec<f64> {
+        match self {
+            Self::Linear => {
+                let (slope, intercept) = linear_regression(samples.iter().copied());
+                let initial_rate = if intercept > 0. {
+                    intercept
+                } else {
+                    max_rate(samples)
+                };
+                vec![initial_rate, -slope / initial_rate]
+            }
+            _ => {
+                // Regress `ln(q)` against time, which is exact for an exponential.
+                let (slope, intercept) = linear_regression(
+                    samples
+                        .iter()
+                        .filter(|(_, rate)| *rate > 0.)
+                        .map(|(time, rate)| (*time, math::ln(*rate))),
+                );
+                let initial_rate = if intercept.is_finite() {
+                    math::exp(intercept)
+                } else {
+                    max_rate(samples)
+                };
+                let decline_rate = if slope.is_finite() { -slope } else { 0. };
+
+                match self {
+                    Self::Hyperbolic => vec![
+                        initial_rate,
+                        decline_rate.max(0.),
+                        options.initial_exponent.unwrap_or(0.5),
+                    ],
+                    Self::Harmonic => vec![initial_rate, decline_rate.max(0.)],
+                    _ => vec![initial_rate, decline_rate],
+                }
+            }
+        }
+    }
+}
+
+fn max_rate(samples: &[(f64, f64)]) -> f64 {
+    samples
+        .iter()
+        .map(|(_, rate)| *rate)
+        .fold(0., f64::max)
+        .max(f64::MIN_POSITIVE)
+}
+
+/// Computes the ordinary least-squares slope and intercept for a set of points.
+pub(crate) fn linear_regression(points: impl Iterator<Item = (f64, f64)>) -> (f64, f64) {
+    let (mut count, mut sum_x, mut sum_y, mut sum_xx, mut sum_xy) = (0., 0., 0., 0., 0.);
+    for (x, y) in points {
+        count += 1.;
+        sum_x += x;
+        sum_y += y;
+        sum_xx += x * x;
+        sum_xy += x * y;
+    }
+
+    let slope = (count * sum_xy - sum_x * sum_y) / (count * sum_xx - sum_x * sum_x);
+    let intercept = (sum_y - slope * sum_x) / count;
+    (slope, intercept)
+}
+
+/// Validates the samples and converts them to raw values.
+fn validate_samples<Time: DeclineTimeUnit>(
+    samples: &[(Time, ProductionRate<Time>)],
+    parameter_count: usize,
+) -> Result<Vec<(f64, f64)>, DeclineCurveAnalysisError> {
+    if samples.len() <= parameter_count {
+        return Err(DeclineCurveAnalysisError::TooFewInputs {
+            field: "sample count",
+            count: samples.len(),
+            minimum: parameter_count + 1,
+        });
+    }
+
+    samples
+        .iter()
+        .map(|(time, rate)| {
+            validate_positive(time.value(), "sample time")?;
+            validate_positive(rate.value(), "sample rate")?;
+            Ok((time.value(), rate.value()))
+        })
+        .collect()
+}
+
+struct RawFit {
+    parameters: Vec<f64>,
+    duration: f64,
+    residuals: Vec<f64>,
+    rejected_samples: Vec<usize>,
+    statistics: FitStatistics,
+    covariance: Option<ParameterCovariance>,
+}
+
+/// Solves for the model parameters that minimize the weighted loss over the samples.
+fn solve(
+    model: FitModel,
+    samples: &[(f64, f64)],
+    sample_weights: &[f64],
+    options: &FitOptions,
+    initial: &[f64],
+) -> Result<LeastSquaresSolution, DeclineCurveAnalysisError> {
+    let space = options.space;
+    let (lower, upper) = model.bounds(initial[0]);
+    let solve_weighted = |weights: &[f64], initial: &[f64]| {
+        levenberg_marquardt_with_jacobian(
+            |parameters| {
+                samples
+                    .iter()
+                    .zip(weights)
+                    .map(|((time, rate), weight)| {
+                        weight.sqrt() * space.residual(*rate, model.rate(parameters, *time))
+                    })
+                    .collect()
+            },
+            |parameters, _| model.residual_jacobian(space, samples, weights, parameters),
+            initial,
+            &lower,
+            &upper,
+        )
+        .ok_or(DeclineCurveAnalysisError::FitDidNotConverge)
+    };
+
+    let mut solution = solve_weighted(sample_weights, initial)?;
+
+    // Use iteratively reweighted least squares for robust losses.
+    if let FitLoss::Huber { threshold } = options.loss {
+        for _ in 0..MAX_REWEIGHTING_ITERATIONS {
+            let residuals: Vec<f64> = samples
+                .iter()
+                .map(|(time, rate)| space.residual(*rate, model.rate(&solution.parameters, *time)))
+                .collect();
+            let weights: Vec<f64> = huber_weights(&residuals, threshold)
+                .iter()
+                .zip(sample_weights)
+                .map(|(huber_weight, sample_weight)| huber_weight * sample_weight)
+                .collect();
+
+            let next = solve_weighted(&weights, &solution.parameters)?;
+            let converged = next
+                .parameters
+                .iter()
+                .zip(&solution.parameters)
+                .all(|(a, b)| (a - b).abs() <= 1e-10 * b.abs().max(1e-10));
+            solution = LeastSquaresSolution {
+                parameters: next.parameters,
+                iterations: solution.iterations + next.iterations,
+            };
+            if converged {
+                break;
+            }
+        }
+    }
+
+    Ok(solution)
+}
+
+fn fit_model<Time: DeclineTimeUnit>(
+    model: FitModel,
+    samples: &[(Time, ProductionRate<Time>)],
+    options: &FitOptions,
+) -> Result<RawFit, DeclineCurveAnalysisError> {
+    options.validate(samples.len())?;
+    let samples = validate_samples(samples, model.parameter_count())?;
+
+    // Fit against smoothed rates if requested, but report residuals against the original rates.
+    let targets = match options.lowess {
+        Some(smoothing) => lowess(&samples, smoothing.fraction),
+        None => samples.clone(),
+    };
+    let weights = match &options.weights {
+        Some(weights) => weights.clone(),
+        None => vec![1.; samples.len()],
+    };
+
+    // Samples with zero weight are excluded entirely, as are samples that can't be represented in
+    // the regression space.
+    let usable: Vec<bool> = targets
+        .iter()
+        .zip(&weights)
+        .map(|((_, rate), weight)| *weight > 0. && options.space.is_usable(*rate))
+        .collect();
+    let mut active = usable.clone();
+    let mut iterations = 0;
+    let mut clipping_iterations = 0;
+
+    let parameters = loop {
+        let (active_targets, active_weights): (Vec<(f64, f64)>, Vec<f64>) = targets
+            .iter()
+            .zip(&weights)
+            .zip(&active)
+            .filter(|(_, active)| **active)
+            .map(|((target, weight), _)| (*target, *weight))
+            .unzip();
+        if active_targets.len() <= model.parameter_count() {
+            return Err(DeclineCurveAnalysisError::TooFewInputs {
+                field: "active sample count",
+                count: active_targets.len(),
+                minimum: model.parameter_count() + 1,
+            });
+        }
+
+        let initial = model.initial_guess(&active_targets, options);
+        for value in &initial {
+            validate_finite(*value, "initial guess")?;
+        }
+
+        let solution = solve(model, &active_targets, &active_weights, options, &initial)?;
+        iterations += solution.iterations;
+
+        let Some(clipping) = options.sigma_clipping else {
+            break solution.parameters;
+        };
+        if clipping_iterations >= clipping.max_iterations {
+            break solution.parameters;
+        }
+        clipping_iterations += 1;
+
+        let residual = |(time, rate): &(f64, f64)| {
+            options
+                .space
+                .residual(*rate, model.rate(&solution.parameters, *time))
+        };
+        let active_residuals: Vec<f64> = active_targets.iter().map(residual).collect();
+        // Don't reject samples based on round-off when the fit is essentially exact.
+        let scale = match options.space {
+            FitSpace::Rate => max_rate(&active_targets),
+            FitSpace::LogRate => 1.,
+        };
+        let cutoff = (clipping.sigma * standard_deviation(&active_residuals))
+            .max(SIGMA_CLIPPING_RELATIVE_FLOOR * scale);
+
+        let mut rejected_any = false;
+        for (target, active) in targets.iter().zip(active.iter_mut()) {
+            if *active && residual(target).abs() > cutoff {
+                *active = false;
+                rejected_any = true;
+            }
+        }
+        if !rejected_any {
+            break solution.parameters;
+        }
+    };
+
+    let covariance = {
+        let (active_targets, active_weights): (Vec<(f64, f64)>, Vec<f64>) = targets
+            .iter()
+            .zip(&weights)
+            .zip(&active)
+            .filter(|(_, active)| **active)
+            .map(|((target, weight), _)| (*target, *weight))
+            .unzip();
+
+        least_squares_covariance(
+            |parameters| {
+                active_targets
+                    .iter()
+                    .zip(&active_weights)
+                    .map(|((time, rate), weight)| {
+                        weight.sqrt() * options.space.residual(*rate, model.rate(parameters, *time))
+                    })
+                    .collect()
+            },
+            |parameters, _| {
+                model.residual_jacobian(options.space, &active_targets, &active_weights, parameters)
+            },
+            &parameters,
+        )
+        .map(|matrix| ParameterCovariance::new(model, options.space, parameters.clone(), matrix))
+    };
+
+    let residuals: Vec<f64> = samples
+        .iter()
+        .map(|(time, rate)| rate - model.rate(&parameters, *time))
+        .collect();
+    let rejected_samples = (0..samples.len())
+        .filter(|&i| usable[i] && !active[i])
+        .collect();
+
+    // Only include the samples that weren't rejected in the statistics.
+    let active_samples: Vec<(f64, f64)> = samples
+        .iter()
+        .zip(&active)
+        .filter(|(_, active)| **active)
+        .map(|(sample, _)| *sample)
+        .collect();
+    let active_residuals: Vec<f64> = residuals
+        .iter()
+        .zip(&active)
+        .filter(|(_, active)| **active)
+        .map(|(residual, _)| *residual)
+        .collect();
+
+    let sample_count = active_samples.len();
+    let sum_of_squared_residuals: f64 = active_residuals
+        .iter()
+        .map(|residual| residual * residual)
+        .sum();
+    let mean_rate = active_samples.iter().map(|(_, rate)| rate).sum::<f64>() / sample_count as f64;
+    let total_sum_of_squares: f64 = active_samples
+        .iter()
+        .map(|(_, rate)| math::powi(rate - mean_rate, 2))
+        .sum();
+    let r_squared = if total_sum_of_squares > 0. {
+        1. - sum_of_squared_residuals / total_sum_of_squares
+    } else {
+        1.
+    };
+
+    let duration = samples.iter().map(|(time, _)| *time).fold(0., f64::max);
+
+    Ok(RawFit {
+        parameters,
+        duration,
+        residuals,
+        rejected_samples,
+        statistics: FitStatistics {
+            sample_count,
+            sum_of_squared_residuals,
+            root_mean_squared_error: (sum_of_squared_residuals / sample_count as f64).sqrt(),
+            r_squared,
+            iterations,
+        },
+        covariance,
+    })
+}
+
+/// Fits an exponential segment to `(time, rate)` samples using nonlinear least squares.
+pub fn fit_exponential<Time: DeclineTimeUnit>(
+    samples: &[(Time, ProductionRate<Time>)],
+    options: &FitOptions,
+) -> Result<FitResult<ExponentialParameters<Time>>, DeclineCurveAnalysisError> {
+    let fit = fit_model(FitModel::Exponential, samples, options)?;
+
+    Ok(FitResult {
+        parameters: ExponentialParameters::from_incremental_duration(
+            ProductionRate::new(fit.parameters[0]),
+            NominalDeclineRate::new(fit.parameters[1]),
+            Time::from(fit.duration),
+        )?,
+        residuals: fit.residuals,
+        rejected_samples: fit.rejected_samples,
+        statistics: fit.statistics,
+        covariance: fit.covariance,
+    })
+}
+
+/// Fits a harmonic segment to `(time, rate)` samples using nonlinear least squares.
+pub fn fit_harmonic<Time: DeclineTimeUnit>(
+    samples: &[(Time, ProductionRate<Time>)],
+    options: &FitOptions,
+) -> Result<FitResult<HarmonicParameters<Time>>, DeclineCurveAnalysisError> {
+    let fit = fit_model(FitModel::Harmonic, samples, options)?;
+
+    Ok(FitResult {
+        parameters: HarmonicParameters::from_incremental_duration(
+            ProductionRate::new(fit.parameters[0]),
+            NominalDeclineRate::new(fit.parameters[1]),
+            Time::from(fit.duration),
+        )?,
+        residuals: fit.residuals,
+        rejected_samples: fit.rejected_samples,
+        statistics: fit.statistics,
+        covariance: fit.covariance,
+    })
+}
+
+/// Fits a hyperbolic segment to `(time, rate)` samples using nonlinear least squares.
+///
+/// The exponent is constrained to be between 0.001 and 2.
+pub fn fit_hyperbolic<Time: DeclineTimeUnit>(
+    samples: &[(Time, ProductionRate<Time>)],
+    options: &FitOptions,
+) -> Result<FitResult<HyperbolicParameters<Time>>, DeclineCurveAnalysisError> {
+    let fit = fit_model(FitModel::Hyperbolic, samples, options)?;
+
+    Ok(FitResult {
+        parameters: HyperbolicParameters::from_incremental_duration(
+            ProductionRate::new(fit.parameters[0]),
+            NominalDeclineRate::new(fit.parameters[1]),
+            Time::from(fit.duration),
+            fit.parameters[2],
+        )?,
+        residuals: fit.residuals,
+        rejected_samples: fit.rejected_samples,
+        statistics: fit.statistics,
+        covariance: fit.covariance,
+    })
+}
+
+/// Fits a linear segment to `(time, rate)` samples using nonlinear least squares.
+pub fn fit_linear<Time: DeclineTimeUnit>(
+    samples: &[(Time, ProductionRate<Time>)],
+    options: &FitOptions,
+) -> Result<FitResult<LinearParameters<Time>>, DeclineCurveAnalysisError> {
+    let fit = fit_model(FitModel::Linear, samples, options)?;
+
+    Ok(FitResult {
+        parameters: LinearParameters::from_incremental_duration(
+            ProductionRate::new(fit.parameters[0]),
+            NominalDeclineRate::new(fit.parameters[1]),
+            Time::from(fit.duration),
+        )?,
+        residuals: fit.residuals,
+        rejected_samples: fit.rejected_samples,
+        statistics: fit.statistics,
+        covariance: fit.covariance,
+    })
+}
+
+/// The criterion used to rank models when selecting the best fit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ModelSelectionCriterion {
+    /// Akaike information criterion, which penalizes additional parameters.
+    #[default]
+    Aic,
+    /// Bayesian information criterion, which penalizes additional parameters more strongly than
+    /// AIC as the number of samples grows.
+    Bic,
+    /// Root mean squared error, which doesn't penalize additional parameters.
+    Rmse,
+}
+
+/// The scores for a single fitted model.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ModelScore {
+    pub model: FitModel,
+    pub aic: f64,
+    pub bic: f64,
+    pub statistics: FitStatistics,
+}
+
+impl ModelScore {
+    fn new(model: FitModel, statistics: FitStatistics) -> Self {
+        let sample_count = statistics.sample_count as f64;
+        let parameter_count = model.parameter_count() as f64;
+
+        // Floor the mean squared error so that exact fits don't produce infinite scores.
+        let log_likelihood_term = sample_count
+            * math::ln((statistics.sum_of_squared_residuals / sample_count).max(f64::MIN_POSITIVE));
+
+        Self {
+            model,
+            aic: 2. * parameter_count + log_likelihood_term,
+            bic: parameter_count * math::ln(sample_count) + log_likelihood_term,
+            statistics,
+        }
+    }
+
+    /// The score for the given criterion, where lower is better.
+    pub fn score(&self, criterion: ModelSelectionCriterion) -> f64 {
+        match criterion {
+            ModelSelectionCriterion::Aic => self.aic,
+            ModelSelectionCriterion::Bic => self.bic,
+            ModelSelectionCriterion::Rmse => self.statistics.root_mean_squared_error,
+        }
+    }
+}
+
+/// The result of fitting all supported models and selecting the best one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BestFitResult<Time: DeclineTimeUnit> {
+    /// The fit for the best-scoring model.
+    pub best: FitResult<Segment<Time>>,
+    /// The scores for every model that could be fitted, ordered from best to worst.
+    pub ranking: Vec<ModelScore>,
+}
+
+fn fit_segment<Time: DeclineTimeUnit>(
+    model: FitModel,
+    samples: &[(Time, ProductionRate<Time>)],
+    options: &FitOptions,
+) -> Result<FitResult<Segment<Time>>, DeclineCurveAnalysisError> {
+    fn into_segment<Parameters: Into<Segment<Time>>, Time: DeclineTimeUnit>(
+        fit: FitResult<Parameters>,
+    ) -> FitResult<Segment<Time>> {
+        FitResult {
+            parameters: fit.parameters.into(),
+            residuals: fit.residuals,
+            rejected_samples: fit.rejected_samples,
+            statistics: fit.statistics,
+            covariance: fit.covariance,
+        }
+    }
+
+    Ok(match model {
+        FitModel::Exponential => into_segment(fit_exponential(samples, options)?),
+        FitModel::Harmonic => into_segment(fit_harmonic(samples, options)?),
+        FitModel::Hyperbolic => into_segment(fit_hyperbolic(samples, options)?),
+        FitModel::Linear => into_segment(fit_linear(samples, options)?),
+    })
+}
+
+/// Fits every supported model to `(time, rate)` samples, and returns the best fit according to
+/// the criterion along with a ranking of all models.
+///
+/// Models that can't be fitted are excluded from the ranking. If no model can be fitted, the
+/// first error is returned.
+pub fn fit_best<Time: DeclineTimeUnit>(
+    samples: &[(Time, ProductionRate<Time>)],
+    criterion: ModelSelectionCriterion,
+    options: &FitOptions,
+) -> Result<BestFitResult<Time>, DeclineCurveAnalysisError> {
+    let mut first_error = None;
+    let mut fits = Vec::new();
+
+    for model in FitModel::ALL {
+        match fit_segment(model, samples, options) {
+            Ok(fit) => {
+                let score = ModelScore::new(model, fit.statistics);
+                fits.push((score, fit));
+            }
+            Err(error) => {
+                first_error.get_or_insert(error);
+            }
+        }
+    }
+
+    fits.sort_by(|(a, _), (b, _)| a.score(criterion).total_cmp(&b.score(criterion)));
+
+    let ranking = fits.iter().map(|(score, _)| *score).collect();
+    match fits.into_iter().next() {
+        Some((_, best)) => Ok(BestFitResult { best, ranking }),
+        None => Err(first_error.unwrap_or(DeclineCurveAnalysisError::FitDidNotConverge)),
+    }
+}
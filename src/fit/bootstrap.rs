@@ -0,0 +1,170 @@
+use super::{FitModel, FitOptions, FitResult, fit_segment};
+use crate::{
+    AverageYearsTime, DeclineCurveAnalysisError, DeclineTimeUnit, Forecast, Percentiles,
+    ProductionRate, RandomNumberGenerator, SeededRng, Segment, TerminalDeclinePolicy,
+    auto_forecast::extend_segment, validate_non_zero_positive_rate,
+};
+use alloc::vec::Vec;
+
+/// Options for [`bootstrap_eur`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct BootstrapOptions<Time: DeclineTimeUnit> {
+    pub model: FitModel,
+    pub fit: FitOptions,
+    /// The number of resampled histories to fit.
+    pub resamples: usize,
+    /// The seed for a [`SeededRng`], which is ignored by [`bootstrap_eur_with_rng`].
+    pub seed: u64,
+    /// When each fit switches to a terminal exponential decline.
+    pub terminal_decline: TerminalDeclinePolicy<Time>,
+    pub economic_limit_rate: ProductionRate<Time>,
+    /// The longest that each forecast continues after time zero.
+    pub duration: Time,
+}
+
+impl<Time: DeclineTimeUnit> BootstrapOptions<Time> {
+    /// Options for 1000 resamples of forecasts of up to 50 years that end at the economic limit.
+    pub fn new(model: FitModel, economic_limit_rate: ProductionRate<Time>) -> Self {
+        Self {
+            model,
+            fit: FitOptions::default(),
+            resamples: 1000,
+            seed: 0,
+            terminal_decline: TerminalDeclinePolicy::default(),
+            economic_limit_rate,
+            duration: AverageYearsTime { years: 50. }.to_unit(),
+        }
+    }
+}
+
+/// The EUR distribution from refitting resampled histories.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BootstrapResult<Time: DeclineTimeUnit> {
+    /// The fit to the original samples.
+    pub fit: FitResult<Segment<Time>>,
+    /// The EUR of the fit to the original samples.
+    pub fitted_eur: f64,
+    /// The EUR percentiles across the resamples.
+    pub estimated_ultimate_recovery: Percentiles,
+    /// The EUR of each resample that could be fitted, in the order they were sampled.
+    pub realization_eurs: Vec<f64>,
+    /// The number of resamples that couldn't be fitted.
+    pub failed_resamples: usize,
+}
+
+/// Estimates the EUR distribution of a single well with a residual bootstrap.
+///
+/// The model is fitted to the samples, then each resample adds residuals drawn with replacement
+/// from the fit to the fitted rates, and is refitted. Residuals are drawn in the space of the fit
+/// options, so they're multiplicative for [`FitSpace::LogRate`](crate::FitSpace::LogRate) fits,
+/// and resampled rates are floored at zero. Samples that were excluded from the fit (e.g., with
+/// zero weight) keep their rates.
+///
+/// Each EUR is the volume of the fit from time zero to the economic limit or the duration, after
+/// the terminal decline policy is applied.
+pub fn bootstrap_eur<Time: DeclineTimeUnit>(
+    samples: &[(Time, ProductionRate<Time>)],
+    options: &BootstrapOptions<Time>,
+) -> Result<BootstrapResult<Time>, DeclineCurveAnalysisError> {
+    bootstrap_eur_with_rng(samples, options, &mut SeededRng::new(options.seed))
+}
+
+/// Like [`bootstrap_eur`], but draws from the given generator instead of seeding one from the
+/// options.
+pub fn bootstrap_eur_with_rng<Time: DeclineTimeUnit, R: RandomNumberGenerator + ?Sized>(
+    samples: &[(Time, ProductionRate<Time>)],
+    options: &BootstrapOptions<Time>,
+    rng: &mut R,
+) -> Result<BootstrapResult<Time>, DeclineCurveAnalysisError> {
+    if options.resamples == 0 {
+        return Err(DeclineCurveAnalysisError::TooFewInputs {
+            field: "resamples",
+            count: 0,
+            minimum: 1,
+        });
+    }
+    validate_non_zero_positive_rate(options.economic_limit_rate.value(), "economic limit rate")?;
+    validate_non_zero_positive_rate(options.duration.value(), "duration")?;
+    options.terminal_decline.validate()?;
+
+    let eur = |segment: &Segment<Time>| -> Result<f64, DeclineCurveAnalysisError> {
+        let extended = extend_segment(segment, options.duration, options.economic_limit_rate)?;
+        Ok(
+            Forecast::with_terminal_decline(extended, options.terminal_decline)?
+                .truncate_at_limits(options.economic_limit_rate, None)?
+                .forecast
+                .incremental_volume(),
+        )
+    };
+
+    let fit = fit_segment(options.model, samples, &options.fit)?;
+    let fitted_eur = eur(&fit.parameters)?;
+
+    // The residuals of the samples that the fit used, in the space of the fit.
+    let space = options.fit.space;
+    let fitted_rates: Vec<f64> = samples
+        .iter()
+        .map(|(time, _)| fit.parameters.rate_at_time(*time).value())
+        .collect();
+    let used: Vec<bool> = samples
+        .iter()
+        .enumerate()
+        .map(|(i, (_, rate))| {
+            let weight = options
+                .fit
+                .weights
+                .as_ref()
+                .map_or(1., |weights| weights[i]);
+            weight > 0.
+                && space.is_usable(rate.value())
+                && !fit.rejected_samples.contains(&i)
+                && space.residual(rate.value(), fitted_rates[i]).is_finite()
+        })
+        .collect();
+    let residuals: Vec<f64> = samples
+        .iter()
+        .zip(&fitted_rates)
+        .zip(&used)
+        .filter(|(_, used)| **used)
+        .map(|(((_, rate), fitted_rate), _)| space.residual(rate.value(), *fitted_rate))
+        .collect();
+
+    let mut realization_eurs = Vec::with_capacity(options.resamples);
+    let mut failed_resamples = 0;
+    let mut resampled = samples.to_vec();
+
+    for _ in 0..options.resamples {
+        for (((sample, original), fitted_rate), used) in resampled
+            .iter_mut()
+            .zip(samples)
+            .zip(&fitted_rates)
+            .zip(&used)
+        {
+            sample.1 = if *used {
+                let index =
+                    ((rng.next_f64() * residuals.len() as f64) as usize).min(residuals.len() - 1);
+                ProductionRate::new(space.apply_residual(*fitted_rate, residuals[index]).max(0.))
+            } else {
+                original.1
+            };
+        }
+
+        match fit_segment(options.model, &resampled, &options.fit)
+            .and_then(|resample_fit| eur(&resample_fit.parameters))
+        {
+            Ok(eur) => realization_eurs.push(eur),
+            Err(_) => failed_resamples += 1,
+        }
+    }
+    if realization_eurs.is_empty() {
+        return Err(DeclineCurveAnalysisError::FitDidNotConverge);
+    }
+
+    Ok(BootstrapResult {
+        fit,
+        fitted_eur,
+        estimated_ultimate_recovery: Percentiles::from_values(&mut realization_eurs.clone()),
+        realization_eurs,
+        failed_resamples,
+    })
+}
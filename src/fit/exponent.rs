@@ -0,0 +1,140 @@
+use crate::{DeclineCurveAnalysisError, DeclineTimeUnit, ProductionRate, math, validate_positive};
+use alloc::vec::Vec;
+use core::ops::Bound;
+
+use super::{LowessSmoothing, MAX_FIT_EXPONENT, MIN_FIT_EXPONENT, linear_regression, lowess};
+#[cfg(not(feature = "std"))]
+use crate::math::FloatMath;
+
+/// The minimum number of samples with production needed to estimate an exponent.
+const MIN_EXPONENT_SAMPLES: usize = 4;
+
+/// An estimate of the Arps exponent from production samples.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExponentEstimate<Time: DeclineTimeUnit> {
+    /// The evolving exponent at each sample time, which is the derivative of the loss ratio
+    /// (`1 / D`) with respect to time.
+    pub exponents: Vec<(Time, f64)>,
+    /// The recommended constant exponent, which is the slope of a linear regression of the loss
+    /// ratio against time. This is limited to the exponents that hyperbolic fits allow, so it can
+    /// be used as [`FitOptions::initial_exponent`](super::FitOptions::initial_exponent).
+    pub exponent: f64,
+    /// The coefficient of determination of the loss ratio regression, which is close to one when
+    /// a constant exponent describes the samples well.
+    pub r_squared: f64,
+    /// The standard deviation of the evolving exponents, where a large spread suggests that the
+    /// exponent changes over time (e.g., a transition between flow regimes).
+    pub standard_deviation: f64,
+    /// The number of samples used in the estimate.
+    pub sample_count: usize,
+}
+
+/// Returns the derivative of `y` with respect to `x` at each point, using central differences
+/// for interior points and one-sided differences at the ends.
+fn derivatives(points: &[(f64, f64)]) -> Vec<f64> {
+    let last = points.len() - 1;
+
+    (0..points.len())
+        .map(|index| {
+            let (before, after) = (
+                points[index.saturating_sub(1)],
+                points[(index + 1).min(last)],
+            );
+            (after.1 - before.1) / (after.0 - before.0)
+        })
+        .collect()
+}
+
+/// Estimates the Arps exponent from `(time, rate)` samples, e.g., to seed a hyperbolic fit.
+///
+/// The log rates are smoothed with LOWESS, then differentiated to get the decline rate, and the
+/// loss ratio (`1 / D`) is differentiated to get the exponent. An Arps hyperbolic decline has a
+/// loss ratio that increases linearly with a slope of `b`. Samples with zero rates are excluded,
+/// as are samples where the smoothed rate doesn't decline.
+pub fn estimate_exponent<Time: DeclineTimeUnit>(
+    samples: &[(Time, ProductionRate<Time>)],
+    smoothing: LowessSmoothing,
+) -> Result<ExponentEstimate<Time>, DeclineCurveAnalysisError> {
+    if !(smoothing.fraction > 0. && smoothing.fraction <= 1.) {
+        return Err(DeclineCurveAnalysisError::InputOutOfRange {
+            field: "LOWESS fraction",
+            value: smoothing.fraction,
+            minimum: Bound::Excluded(0.),
+            maximum: Bound::Included(1.),
+        });
+    }
+
+    let mut log_rates = Vec::with_capacity(samples.len());
+    for (time, rate) in samples {
+        validate_positive(time.value(), "sample time")?;
+        validate_positive(rate.value(), "sample rate")?;
+        if rate.value() > 0. {
+            log_rates.push((time.value(), math::ln(rate.value())));
+        }
+    }
+    if !log_rates.windows(2).all(|window| window[0].0 < window[1].0) {
+        return Err(DeclineCurveAnalysisError::NotStrictlyIncreasing {
+            field: "sample times",
+        });
+    }
+
+    let smoothed = lowess(&log_rates, smoothing.fraction);
+    let loss_ratios: Vec<(f64, f64)> = smoothed
+        .iter()
+        .zip(derivatives(&smoothed))
+        .filter(|(_, slope)| *slope < 0.)
+        .map(|((time, _), slope)| (*time, -slope.recip()))
+        .filter(|(_, loss_ratio)| loss_ratio.is_finite())
+        .collect();
+    if loss_ratios.len() < MIN_EXPONENT_SAMPLES {
+        return Err(DeclineCurveAnalysisError::TooFewInputs {
+            field: "declining sample count",
+            count: loss_ratios.len(),
+            minimum: MIN_EXPONENT_SAMPLES,
+        });
+    }
+
+    let exponents: Vec<(Time, f64)> = loss_ratios
+        .iter()
+        .zip(derivatives(&loss_ratios))
+        .map(|((time, _), exponent)| (Time::from(*time), exponent))
+        .collect();
+
+    let (slope, intercept) = linear_regression(loss_ratios.iter().copied());
+    let count = loss_ratios.len() as f64;
+    let mean = loss_ratios
+        .iter()
+        .map(|(_, loss_ratio)| loss_ratio)
+        .sum::<f64>()
+        / count;
+    let (residual_sum, total_sum) =
+        loss_ratios
+            .iter()
+            .fold((0., 0.), |(residual_sum, total_sum), (time, loss_ratio)| {
+                (
+                    residual_sum + math::powi(loss_ratio - slope.mul_add(*time, intercept), 2),
+                    total_sum + math::powi(loss_ratio - mean, 2),
+                )
+            });
+    let r_squared = if total_sum > 0. {
+        1. - residual_sum / total_sum
+    } else {
+        1.
+    };
+
+    let mean_exponent = exponents.iter().map(|(_, exponent)| exponent).sum::<f64>() / count;
+    let standard_deviation = (exponents
+        .iter()
+        .map(|(_, exponent)| math::powi(exponent - mean_exponent, 2))
+        .sum::<f64>()
+        / count)
+        .sqrt();
+
+    Ok(ExponentEstimate {
+        exponents,
+        exponent: slope.clamp(MIN_FIT_EXPONENT, MAX_FIT_EXPONENT),
+        r_squared,
+        standard_deviation,
+        sample_count: loss_ratios.len(),
+    })
+}
@@ -0,0 +1,150 @@
+use super::{MAX_FIT_EXPONENT, MIN_FIT_EXPONENT};
+use crate::{
+    DeclineCurveAnalysisError, ExponentRange, math, validate_non_zero_positive_rate,
+    validate_positive,
+};
+use alloc::vec::Vec;
+use core::ops::Bound;
+
+/// The loss function used to weight residuals when fitting.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum FitLoss {
+    /// Ordinary least squares.
+    #[default]
+    SquaredError,
+    /// Huber loss, which is quadratic for small residuals and linear for large residuals, so that
+    /// outliers have less influence on the fit.
+    ///
+    /// The threshold is a multiple of the robust standard deviation of the residuals (estimated
+    /// with the median absolute deviation). A threshold of 1.345 is a common choice.
+    Huber { threshold: f64 },
+}
+
+/// Iterative sigma-clipping, which repeatedly fits, then rejects samples with residuals larger
+/// than `sigma` standard deviations.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SigmaClipping {
+    pub sigma: f64,
+    pub max_iterations: usize,
+}
+
+/// LOWESS (locally weighted scatterplot smoothing) applied to the rates before fitting.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LowessSmoothing {
+    /// The fraction of samples used for each local regression, between zero and one.
+    pub fraction: f64,
+}
+
+/// The space in which residuals are minimized.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FitSpace {
+    /// Minimize residuals between rates, which favors the high early rates.
+    #[default]
+    Rate,
+    /// Minimize residuals between the logarithms of rates, which weights all rates equally in a
+    /// relative sense. Samples with zero rates are excluded.
+    LogRate,
+}
+
+impl FitSpace {
+    pub(crate) fn residual(self, rate: f64, fitted_rate: f64) -> f64 {
+        match self {
+            Self::Rate => rate - fitted_rate,
+            Self::LogRate => math::ln(rate) - math::ln(fitted_rate),
+        }
+    }
+
+    /// The rate with the given residual from the fitted rate, which is the inverse of
+    /// [`Self::residual`].
+    pub(crate) fn apply_residual(self, fitted_rate: f64, residual: f64) -> f64 {
+        match self {
+            Self::Rate => fitted_rate + residual,
+            Self::LogRate => fitted_rate * math::exp(residual),
+        }
+    }
+
+    /// Converts a derivative of the fitted rate into a derivative of the fitted rate in this
+    /// space.
+    pub(crate) fn fitted_rate_derivative(self, fitted_rate: f64, derivative: f64) -> f64 {
+        match self {
+            Self::Rate => derivative,
+            Self::LogRate => derivative / fitted_rate,
+        }
+    }
+
+    pub(crate) fn is_usable(self, rate: f64) -> bool {
+        match self {
+            Self::Rate => true,
+            Self::LogRate => rate > 0.,
+        }
+    }
+}
+
+/// Options that control how segments are fitted.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct FitOptions {
+    pub loss: FitLoss,
+    pub sigma_clipping: Option<SigmaClipping>,
+    pub lowess: Option<LowessSmoothing>,
+    /// The weight for each sample, which must have the same length as the samples. Samples with
+    /// zero weight (e.g., shut-in months) are excluded from the fit.
+    pub weights: Option<Vec<f64>>,
+    pub space: FitSpace,
+    /// The initial guess for the exponent of hyperbolic fits, e.g., from [`estimate_exponent`],
+    /// which defaults to 0.5. A good initial guess helps the fit converge to the right solution
+    /// when the samples don't constrain the exponent well.
+    ///
+    /// [`estimate_exponent`]: crate::estimate_exponent
+    pub initial_exponent: Option<f64>,
+}
+
+impl FitOptions {
+    pub(crate) fn validate(&self, sample_count: usize) -> Result<(), DeclineCurveAnalysisError> {
+        if let Some(weights) = &self.weights {
+            if weights.len() != sample_count {
+                return Err(DeclineCurveAnalysisError::InputLengthMismatch {
+                    field: "weights",
+                    count: weights.len(),
+                    expected: sample_count,
+                });
+            }
+            for weight in weights {
+                validate_positive(*weight, "weight")?;
+            }
+        }
+
+        if let FitLoss::Huber { threshold } = self.loss {
+            validate_non_zero_positive_rate(threshold, "Huber threshold")?;
+        }
+
+        if let Some(clipping) = self.sigma_clipping {
+            validate_non_zero_positive_rate(clipping.sigma, "sigma-clipping sigma")?;
+        }
+
+        if let Some(lowess) = self.lowess
+            && !(lowess.fraction > 0. && lowess.fraction <= 1.)
+        {
+            return Err(DeclineCurveAnalysisError::InputOutOfRange {
+                field: "LOWESS fraction",
+                value: lowess.fraction,
+                minimum: Bound::Excluded(0.),
+                maximum: Bound::Included(1.),
+            });
+        }
+
+        if let Some(exponent) = self.initial_exponent
+            && !(MIN_FIT_EXPONENT..=MAX_FIT_EXPONENT).contains(&exponent)
+        {
+            return Err(DeclineCurveAnalysisError::ExponentOutOfRange {
+                value: exponent,
+                allowed: ExponentRange {
+                    minimum: MIN_FIT_EXPONENT,
+                    maximum: MAX_FIT_EXPONENT,
+                    excluded: &[],
+                },
+            });
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,211 @@
+use super::{BestFitResult, FitOptions, FitResult, ModelSelectionCriterion, fit_best};
+use crate::{
+    DeclineCurveAnalysisError, DeclineTimeUnit, NominalDeclineRate, ProductionRate, Segment,
+};
+use core::fmt;
+
+/// A requirement checked by [`QualityGates`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum QualityGate {
+    /// The span of the sample times.
+    Duration,
+    SampleCount,
+    /// The relative drop in rate observed in the samples.
+    ObservedDecline,
+    /// The initial decline rate of the fit.
+    InitialDeclineRate,
+    /// The exponent of the fit, which is zero for exponential and one for harmonic fits.
+    Exponent,
+    /// The coefficient of determination of the fit.
+    RSquared,
+}
+
+impl QualityGate {
+    /// Whether the gate is an upper limit rather than a lower limit.
+    fn is_maximum(self) -> bool {
+        matches!(self, Self::InitialDeclineRate | Self::Exponent)
+    }
+
+    /// Describes whether values must be at least or at most the limit.
+    pub(crate) fn comparison(self) -> &'static str {
+        if self.is_maximum() {
+            "at most"
+        } else {
+            "at least"
+        }
+    }
+}
+
+impl fmt::Display for QualityGate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Duration => "duration",
+            Self::SampleCount => "sample count",
+            Self::ObservedDecline => "observed decline",
+            Self::InitialDeclineRate => "initial decline rate",
+            Self::Exponent => "exponent",
+            Self::RSquared => "R-squared",
+        })
+    }
+}
+
+/// Requirements that samples and fits must meet to be forecast automatically, so that pipelines
+/// can route wells that fail to manual review.
+///
+/// Samples that don't meet a gate return [`DeclineCurveAnalysisError::InsufficientData`], and fits
+/// that don't meet a gate return [`DeclineCurveAnalysisError::QualityGateFailed`]. Gates that
+/// aren't set always pass.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QualityGates<Time: DeclineTimeUnit> {
+    /// The shortest span of the sample times (e.g., six months).
+    pub min_duration: Option<Time>,
+    pub min_samples: Option<usize>,
+    /// The smallest relative drop from the mean rate of the first quarter of the samples to the
+    /// mean rate of the last quarter (e.g., 0.1), so that wells that haven't started declining
+    /// aren't forecast.
+    pub min_observed_decline: Option<f64>,
+    pub max_initial_decline_rate: Option<NominalDeclineRate<Time>>,
+    pub max_exponent: Option<f64>,
+    pub min_r_squared: Option<f64>,
+}
+
+impl<Time: DeclineTimeUnit> Default for QualityGates<Time> {
+    fn default() -> Self {
+        Self {
+            min_duration: None,
+            min_samples: None,
+            min_observed_decline: None,
+            max_initial_decline_rate: None,
+            max_exponent: None,
+            min_r_squared: None,
+        }
+    }
+}
+
+impl<Time: DeclineTimeUnit> QualityGates<Time> {
+    /// Checks that the samples are sufficient to fit.
+    pub fn check_samples(
+        &self,
+        samples: &[(Time, ProductionRate<Time>)],
+    ) -> Result<(), DeclineCurveAnalysisError> {
+        let insufficient = |gate, value: f64, minimum: f64| {
+            if value < minimum {
+                Err(DeclineCurveAnalysisError::InsufficientData {
+                    gate,
+                    value,
+                    minimum,
+                })
+            } else {
+                Ok(())
+            }
+        };
+
+        if let Some(min_samples) = self.min_samples {
+            insufficient(
+                QualityGate::SampleCount,
+                samples.len() as f64,
+                min_samples as f64,
+            )?;
+        }
+        if let Some(min_duration) = self.min_duration {
+            let (start, end) = samples.iter().fold(
+                (f64::INFINITY, f64::NEG_INFINITY),
+                |(start, end), (time, _)| (start.min(time.value()), end.max(time.value())),
+            );
+            insufficient(
+                QualityGate::Duration,
+                (end - start).max(0.),
+                min_duration.value(),
+            )?;
+        }
+        if let Some(min_observed_decline) = self.min_observed_decline {
+            insufficient(
+                QualityGate::ObservedDecline,
+                observed_decline(samples),
+                min_observed_decline,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Checks that the parameters and statistics of a fit are acceptable.
+    pub fn check_fit(
+        &self,
+        fit: &FitResult<Segment<Time>>,
+    ) -> Result<(), DeclineCurveAnalysisError> {
+        let failed = |gate: QualityGate, value: f64, limit: f64| {
+            let passes = if gate.is_maximum() {
+                value <= limit
+            } else {
+                value >= limit
+            };
+            if passes {
+                Ok(())
+            } else {
+                Err(DeclineCurveAnalysisError::QualityGateFailed { gate, value, limit })
+            }
+        };
+
+        if let Some(max_initial_decline_rate) = self.max_initial_decline_rate {
+            failed(
+                QualityGate::InitialDeclineRate,
+                fit.parameters.decline_rate_at_time(Time::from(0.)).value(),
+                max_initial_decline_rate.value(),
+            )?;
+        }
+        if let Some(max_exponent) = self.max_exponent {
+            let exponent = match &fit.parameters {
+                Segment::Exponential(_) => Some(0.),
+                Segment::Harmonic(_) => Some(1.),
+                Segment::Hyperbolic(parameters) => Some(parameters.exponent()),
+                _ => None,
+            };
+            if let Some(exponent) = exponent {
+                failed(QualityGate::Exponent, exponent, max_exponent)?;
+            }
+        }
+        if let Some(min_r_squared) = self.min_r_squared {
+            failed(
+                QualityGate::RSquared,
+                fit.statistics.r_squared,
+                min_r_squared,
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The relative drop from the mean rate of the first quarter of the samples to the mean rate of
+/// the last quarter, which is zero if the rate didn't drop.
+fn observed_decline<Time: DeclineTimeUnit>(samples: &[(Time, ProductionRate<Time>)]) -> f64 {
+    let quarter = (samples.len() / 4).max(1);
+    if samples.len() < 2 {
+        return 0.;
+    }
+    let mean_rate = |samples: &[(Time, ProductionRate<Time>)]| {
+        samples.iter().map(|(_, rate)| rate.value()).sum::<f64>() / samples.len() as f64
+    };
+
+    let first = mean_rate(&samples[..quarter]);
+    let last = mean_rate(&samples[samples.len() - quarter..]);
+    if first > 0. {
+        (1. - last / first).max(0.)
+    } else {
+        0.
+    }
+}
+
+/// Like [`fit_best`], but checks the samples and the best fit against the quality gates.
+pub fn fit_best_with_gates<Time: DeclineTimeUnit>(
+    samples: &[(Time, ProductionRate<Time>)],
+    criterion: ModelSelectionCriterion,
+    options: &FitOptions,
+    gates: &QualityGates<Time>,
+) -> Result<BestFitResult<Time>, DeclineCurveAnalysisError> {
+    gates.check_samples(samples)?;
+    let fit = fit_best(samples, criterion, options)?;
+    gates.check_fit(&fit.best)?;
+    Ok(fit)
+}
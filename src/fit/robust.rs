@@ -0,0 +1,136 @@
+use crate::math;
+#[cfg(not(feature = "std"))]
+use crate::math::FloatMath;
+use alloc::vec::Vec;
+
+/// Scale factor that makes the median absolute deviation a consistent estimator of the standard
+/// deviation for normally distributed residuals.
+const MAD_SCALE: f64 = 1.4826;
+
+fn median(values: &mut [f64]) -> f64 {
+    values.sort_by(f64::total_cmp);
+    let middle = values.len() / 2;
+    if values.is_empty() {
+        0.
+    } else if values.len().is_multiple_of(2) {
+        0.5 * (values[middle - 1] + values[middle])
+    } else {
+        values[middle]
+    }
+}
+
+/// Estimates the standard deviation of residuals using the median absolute deviation.
+pub(crate) fn robust_scale(residuals: &[f64]) -> f64 {
+    let mut values = residuals.to_vec();
+    let center = median(&mut values);
+    let mut deviations: Vec<f64> = residuals
+        .iter()
+        .map(|residual| (residual - center).abs())
+        .collect();
+    MAD_SCALE * median(&mut deviations)
+}
+
+/// Computes the standard deviation of residuals.
+pub(crate) fn standard_deviation(residuals: &[f64]) -> f64 {
+    let count = residuals.len() as f64;
+    let mean = residuals.iter().sum::<f64>() / count;
+    (residuals
+        .iter()
+        .map(|residual| math::powi(residual - mean, 2))
+        .sum::<f64>()
+        / count)
+        .sqrt()
+}
+
+/// Computes Huber weights for residuals, where residuals within the threshold have a weight of
+/// one.
+pub(crate) fn huber_weights(residuals: &[f64], threshold: f64) -> Vec<f64> {
+    let cutoff = threshold * robust_scale(residuals);
+    residuals
+        .iter()
+        .map(|residual| {
+            if cutoff <= 0. || residual.abs() <= cutoff {
+                1.
+            } else {
+                cutoff / residual.abs()
+            }
+        })
+        .collect()
+}
+
+/// Smooths `(x, y)` points with LOWESS using a tricube kernel and local linear
+/// regression.
+pub(crate) fn lowess(points: &[(f64, f64)], fraction: f64) -> Vec<(f64, f64)> {
+    let count = points.len();
+    let window = ((fraction * count as f64).ceil() as usize).clamp(2.min(count), count);
+
+    points
+        .iter()
+        .map(|&(x, y)| {
+            // Find the distance to the furthest of the nearest `window` points.
+            let mut distances: Vec<f64> = points.iter().map(|(xi, _)| (xi - x).abs()).collect();
+            distances.sort_by(f64::total_cmp);
+            let bandwidth = distances[window - 1];
+            if bandwidth <= 0. {
+                return (x, y);
+            }
+
+            let (mut sum_w, mut sum_wx, mut sum_wy, mut sum_wxx, mut sum_wxy) =
+                (0., 0., 0., 0., 0.);
+            for &(xi, yi) in points {
+                let distance = (xi - x).abs() / bandwidth;
+                if distance >= 1. {
+                    continue;
+                }
+                let weight = math::powi(1. - math::powi(distance, 3), 3);
+                sum_w += weight;
+                sum_wx += weight * xi;
+                sum_wy += weight * yi;
+                sum_wxx += weight * xi * xi;
+                sum_wxy += weight * xi * yi;
+            }
+
+            let denominator = sum_w * sum_wxx - sum_wx * sum_wx;
+            if denominator.abs() <= f64::EPSILON * sum_w * sum_wxx {
+                return (x, sum_wy / sum_w);
+            }
+
+            let slope = (sum_w * sum_wxy - sum_wx * sum_wy) / denominator;
+            let intercept = (sum_wy - slope * sum_wx) / sum_w;
+            (x, intercept + slope * x)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn robust_scale_ignores_outlier() {
+        let residuals = [-1., 1., -1., 1., 100.];
+        insta::assert_snapshot!(robust_scale(&residuals), @"2.9652");
+    }
+
+    #[test]
+    fn huber_weights_downweight_outlier() {
+        let residuals = [-1., 1., -1., 1., 100.];
+        insta::assert_debug_snapshot!(huber_weights(&residuals, 1.345), @"
+        [
+            1.0,
+            1.0,
+            1.0,
+            1.0,
+            0.03988194,
+        ]
+        ");
+    }
+
+    #[test]
+    fn lowess_preserves_line() {
+        let points: Vec<_> = (0..10).map(|x| (x as f64, 2. * x as f64 + 1.)).collect();
+        for ((_, smoothed), (_, original)) in lowess(&points, 0.5).iter().zip(&points) {
+            assert!((smoothed - original).abs() < 1e-9);
+        }
+    }
+}
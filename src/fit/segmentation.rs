@@ -0,0 +1,329 @@
+use crate::{
+    BestFitResult, DeclineCurveAnalysisError, DeclineTimeUnit, FitOptions, Forecast,
+    ModelSelectionCriterion, ProductionRate, fit_best, math, numerical::solve_linear_system,
+    validate_finite, validate_non_zero_positive_rate, validate_positive,
+};
+use alloc::{vec, vec::Vec};
+
+use super::robust::robust_scale;
+
+/// The smallest number of samples in a regime, which is enough to fit a hyperbolic segment.
+const MIN_REGIME_SAMPLES: usize = 4;
+
+/// The number of parameters that each additional regime adds: the three coefficients of the
+/// quadratic cost and the changepoint itself.
+const PARAMETERS_PER_REGIME: f64 = 4.;
+
+/// Options for splitting production history into regimes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SegmentationOptions {
+    /// The penalty for starting a new regime, in units of squared log rate. A larger penalty
+    /// detects fewer changepoints.
+    ///
+    /// Defaults to a BIC-style penalty that's scaled by the noise in the log rates, which is
+    /// estimated from the differences between consecutive samples.
+    pub penalty: Option<f64>,
+    /// The minimum number of samples in each regime, which must be at least 4.
+    pub min_regime_samples: usize,
+    /// The criterion used to select the model of each regime.
+    pub criterion: ModelSelectionCriterion,
+    /// The options used to fit each regime. Weights are given for all samples, and are split
+    /// between the regimes.
+    pub fit: FitOptions,
+}
+
+impl Default for SegmentationOptions {
+    fn default() -> Self {
+        Self {
+            penalty: None,
+            min_regime_samples: 6,
+            criterion: ModelSelectionCriterion::default(),
+            fit: FitOptions::default(),
+        }
+    }
+}
+
+/// A regime of production history with consistent decline behavior.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Regime<Time: DeclineTimeUnit> {
+    /// The index of the first sample of the regime.
+    pub start_index: usize,
+    /// The index after the last sample of the regime.
+    pub end_index: usize,
+    /// The time that the segment of the regime starts in the forecast.
+    pub start: Time,
+    /// The best fit to the samples of the regime, where times are relative to `start`.
+    pub fit: BestFitResult<Time>,
+}
+
+/// The result of splitting production history into regimes and fitting a segment to each.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SegmentedFit<Time: DeclineTimeUnit> {
+    /// The forecast with one segment for each regime.
+    pub forecast: Forecast<Time>,
+    pub regimes: Vec<Regime<Time>>,
+}
+
+/// Prefix sums of the powers of the scaled sample times and their products with the log rates,
+/// so that the cost of any range of samples can be computed in constant time.
+struct QuadraticCost {
+    /// The sums of `u^k` for `k` from 0 to 4, where `u` is the scaled time.
+    time_powers: Vec<[f64; 5]>,
+    /// The sums of `y * u^k` for `k` from 0 to 2, where `y` is the log rate.
+    log_rate_moments: Vec<[f64; 3]>,
+    /// The sums of `y^2`.
+    log_rate_squares: Vec<f64>,
+}
+
+impl QuadraticCost {
+    fn new(samples: &[(f64, f64)]) -> Self {
+        // Scale the times to [-1, 1] so that the normal equations are well conditioned.
+        let first = samples.first().map_or(0., |(time, _)| *time);
+        let last = samples.last().map_or(0., |(time, _)| *time);
+        let center = 0.5 * (first + last);
+        let scale = if last > first {
+            0.5 * (last - first)
+        } else {
+            1.
+        };
+
+        let mut time_powers = vec![[0.; 5]];
+        let mut log_rate_moments = vec![[0.; 3]];
+        let mut log_rate_squares = vec![0.];
+        for (time, log_rate) in samples {
+            let u = (time - center) / scale;
+            let mut powers = *time_powers.last().unwrap();
+            let mut moments = *log_rate_moments.last().unwrap();
+            let mut u_power = 1.;
+            for k in 0..5 {
+                powers[k] += u_power;
+                if k < 3 {
+                    moments[k] += log_rate * u_power;
+                }
+                u_power *= u;
+            }
+            time_powers.push(powers);
+            log_rate_moments.push(moments);
+            log_rate_squares.push(log_rate_squares.last().unwrap() + log_rate * log_rate);
+        }
+
+        Self {
+            time_powers,
+            log_rate_moments,
+            log_rate_squares,
+        }
+    }
+
+    /// The residual sum of squares of a quadratic fitted to the log rates of the samples from
+    /// `start` (inclusive) to `end` (exclusive).
+    fn cost(&self, start: usize, end: usize) -> f64 {
+        let s: [f64; 5] =
+            core::array::from_fn(|k| self.time_powers[end][k] - self.time_powers[start][k]);
+        let t: [f64; 3] = core::array::from_fn(|k| {
+            self.log_rate_moments[end][k] - self.log_rate_moments[start][k]
+        });
+        let squares = self.log_rate_squares[end] - self.log_rate_squares[start];
+
+        let explained = match solve_linear_system(
+            vec![
+                vec![s[0], s[1], s[2]],
+                vec![s[1], s[2], s[3]],
+                vec![s[2], s[3], s[4]],
+            ],
+            t.to_vec(),
+        ) {
+            Some(coefficients) => coefficients.iter().zip(&t).map(|(c, t)| c * t).sum(),
+            // Fall back to a constant if the times don't determine a quadratic.
+            None => t[0] * t[0] / s[0],
+        };
+
+        (squares - explained).max(0.)
+    }
+}
+
+/// Validates the samples and options, and converts the samples to times and log rates.
+fn validate_segmentation<Time: DeclineTimeUnit>(
+    samples: &[(Time, ProductionRate<Time>)],
+    options: &SegmentationOptions,
+) -> Result<Vec<(f64, f64)>, DeclineCurveAnalysisError> {
+    if options.min_regime_samples < MIN_REGIME_SAMPLES {
+        return Err(DeclineCurveAnalysisError::TooFewInputs {
+            field: "minimum regime samples",
+            count: options.min_regime_samples,
+            minimum: MIN_REGIME_SAMPLES,
+        });
+    }
+    if let Some(penalty) = options.penalty {
+        validate_positive(penalty, "penalty")?;
+    }
+    options.fit.validate(samples.len())?;
+
+    let mut previous_time = f64::NEG_INFINITY;
+    samples
+        .iter()
+        .map(|(time, rate)| {
+            validate_finite(time.value(), "sample time")?;
+            validate_non_zero_positive_rate(rate.value(), "sample rate")?;
+            if time.value() <= previous_time {
+                return Err(DeclineCurveAnalysisError::NotStrictlyIncreasing {
+                    field: "sample times",
+                });
+            }
+            previous_time = time.value();
+            Ok((time.value(), math::ln(rate.value())))
+        })
+        .collect()
+}
+
+/// The default penalty, which is the BIC penalty for the parameters of a regime scaled by the
+/// variance of the log rates.
+fn default_penalty(log_rates: &[(f64, f64)]) -> f64 {
+    // The differences of independent noise have twice the variance of the noise, and the robust
+    // scale ignores the few large differences at changepoints.
+    let differences: Vec<f64> = log_rates
+        .windows(2)
+        .map(|pair| pair[1].1 - pair[0].1)
+        .collect();
+    let variance = math::powi(robust_scale(&differences), 2) / 2.;
+
+    PARAMETERS_PER_REGIME * variance * math::ln(log_rates.len() as f64)
+}
+
+/// Finds the start indices of the regimes after the first using the pruned exact linear time
+/// (PELT) method.
+fn changepoints(log_rates: &[(f64, f64)], penalty: f64, min_regime_samples: usize) -> Vec<usize> {
+    let count = log_rates.len();
+    let cost = QuadraticCost::new(log_rates);
+    // Ties are broken toward fewer regimes, so noise-free samples aren't split by round-off.
+    let penalty = penalty.max(f64::EPSILON);
+
+    // The optimal cost of the samples before each index, and the start of the last regime.
+    let mut optimal = vec![f64::INFINITY; count + 1];
+    let mut last_start = vec![0; count + 1];
+    optimal[0] = -penalty;
+    let mut candidates = vec![0];
+
+    for end in min_regime_samples..=count {
+        for &start in &candidates {
+            if end - start < min_regime_samples {
+                continue;
+            }
+            let total = optimal[start] + cost.cost(start, end) + penalty;
+            if total < optimal[end] {
+                optimal[end] = total;
+                last_start[end] = start;
+            }
+        }
+
+        // Candidates that can't start the last regime of a later optimal solution are pruned.
+        let optimal_end = optimal[end];
+        candidates.retain(|&start| {
+            end - start < min_regime_samples
+                || optimal[start] + cost.cost(start, end) <= optimal_end
+        });
+        if optimal_end.is_finite() {
+            candidates.push(end);
+        }
+    }
+
+    if !optimal[count].is_finite() {
+        return Vec::new();
+    }
+
+    let mut starts = Vec::new();
+    let mut end = count;
+    while last_start[end] > 0 {
+        end = last_start[end];
+        starts.push(end);
+    }
+    starts.reverse();
+    starts
+}
+
+/// Detects changepoints in production history where the decline behavior changes abruptly (e.g.,
+/// after a refrac or an artificial lift install), and returns the index of the first sample of
+/// each regime after the first.
+///
+/// The cost of a regime is the residual sum of squares of a quadratic in time fitted to the log
+/// rates, which follows gradual changes of the decline rate within a regime (e.g., a hyperbolic
+/// decline). The samples must have positive rates and increasing times.
+pub fn detect_changepoints<Time: DeclineTimeUnit>(
+    samples: &[(Time, ProductionRate<Time>)],
+    options: &SegmentationOptions,
+) -> Result<Vec<usize>, DeclineCurveAnalysisError> {
+    let log_rates = validate_segmentation(samples, options)?;
+    let penalty = options
+        .penalty
+        .unwrap_or_else(|| default_penalty(&log_rates));
+
+    Ok(changepoints(
+        &log_rates,
+        penalty,
+        options.min_regime_samples,
+    ))
+}
+
+/// Splits production history into regimes at the changepoints found by [`detect_changepoints`],
+/// then fits the best segment to each regime, producing a forecast with one segment per regime.
+///
+/// The first regime starts at time zero, and each later regime starts at the time of its first
+/// sample. Each segment lasts until the next regime starts, and the last segment ends at the last
+/// sample.
+pub fn fit_segmented<Time: DeclineTimeUnit>(
+    samples: &[(Time, ProductionRate<Time>)],
+    options: &SegmentationOptions,
+) -> Result<SegmentedFit<Time>, DeclineCurveAnalysisError> {
+    let changepoints = detect_changepoints(samples, options)?;
+
+    let mut boundaries = vec![0];
+    boundaries.extend(changepoints);
+    boundaries.push(samples.len());
+
+    let mut regimes: Vec<Regime<Time>> = Vec::new();
+    for bounds in boundaries.windows(2) {
+        let (start_index, end_index) = (bounds[0], bounds[1]);
+        let start = if start_index == 0 {
+            0.
+        } else {
+            samples[start_index].0.value()
+        };
+        let regime_samples: Vec<_> = samples[start_index..end_index]
+            .iter()
+            .map(|(time, rate)| (Time::from(time.value() - start), *rate))
+            .collect();
+        let fit_options = FitOptions {
+            weights: options
+                .fit
+                .weights
+                .as_ref()
+                .map(|weights| weights[start_index..end_index].to_vec()),
+            ..options.fit.clone()
+        };
+
+        regimes.push(Regime {
+            start_index,
+            end_index,
+            start: Time::from(start),
+            fit: fit_best(&regime_samples, options.criterion, &fit_options)?,
+        });
+    }
+
+    let segments = regimes
+        .iter()
+        .zip(regimes.iter().skip(1).map(Some).chain([None]))
+        .map(|(regime, next)| {
+            let segment = &regime.fit.best.parameters;
+            match next {
+                Some(next) => segment.with_incremental_duration(Time::from(
+                    next.start.value() - regime.start.value(),
+                )),
+                None => Ok(segment.clone()),
+            }
+        })
+        .collect::<Result<_, DeclineCurveAnalysisError>>()?;
+
+    Ok(SegmentedFit {
+        forecast: Forecast::new(segments),
+        regimes,
+    })
+}
@@ -0,0 +1,348 @@
+use super::{
+    FitModel, FitOptions, FitSpace, FitStatistics, MAX_FIT_EXPONENT, MIN_FIT_EXPONENT,
+    fit_hyperbolic, validate_samples,
+};
+#[cfg(not(feature = "std"))]
+use crate::math::FloatMath;
+use crate::{
+    DeclineCurveAnalysisError, DeclineTimeUnit, ExponentRange, Forecast, HarmonicParameters,
+    HyperbolicParameters, NominalDeclineRate, ProductionRate, Segment, is_effectively_zero, math,
+    numerical::levenberg_marquardt,
+};
+use alloc::{vec, vec::Vec};
+
+/// The number of golden-section iterations when refining the switch time, which narrows the
+/// interval by a factor of about 10^-6.
+const SWITCH_TIME_ITERATIONS: usize = 30;
+
+/// Options for [`fit_transient_to_boundary`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransientFitOptions {
+    pub space: FitSpace,
+    /// The weight for each sample, which must have the same length as the samples. Samples with
+    /// zero weight (e.g., shut-in months) are excluded from the fit.
+    pub weights: Option<Vec<f64>>,
+    /// The fewest samples before and after the switch.
+    pub min_segment_samples: usize,
+    /// The allowed exponents of the transient segment, which are high during linear flow.
+    pub transient_exponent: ExponentRange,
+    /// The allowed exponents of the boundary-dominated segment.
+    pub boundary_exponent: ExponentRange,
+}
+
+impl Default for TransientFitOptions {
+    fn default() -> Self {
+        Self {
+            space: FitSpace::default(),
+            weights: None,
+            min_segment_samples: 4,
+            transient_exponent: ExponentRange {
+                minimum: 1.,
+                maximum: MAX_FIT_EXPONENT,
+                excluded: &[],
+            },
+            boundary_exponent: ExponentRange {
+                minimum: MIN_FIT_EXPONENT,
+                maximum: 1.,
+                excluded: &[],
+            },
+        }
+    }
+}
+
+impl TransientFitOptions {
+    fn validate(&self, sample_count: usize) -> Result<(), DeclineCurveAnalysisError> {
+        FitOptions {
+            weights: self.weights.clone(),
+            ..FitOptions::default()
+        }
+        .validate(sample_count)?;
+
+        if self.min_segment_samples < 2 {
+            return Err(DeclineCurveAnalysisError::TooFewInputs {
+                field: "minimum segment samples",
+                count: self.min_segment_samples,
+                minimum: 2,
+            });
+        }
+        for range in [self.transient_exponent, self.boundary_exponent] {
+            for exponent in [range.minimum, range.maximum] {
+                if !(MIN_FIT_EXPONENT..=MAX_FIT_EXPONENT).contains(&exponent)
+                    || range.minimum > range.maximum
+                {
+                    return Err(DeclineCurveAnalysisError::ExponentOutOfRange {
+                        value: exponent,
+                        allowed: ExponentRange {
+                            minimum: MIN_FIT_EXPONENT,
+                            maximum: MAX_FIT_EXPONENT,
+                            excluded: &[],
+                        },
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The result of fitting a transient segment followed by a boundary-dominated segment.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransientFit<Time: DeclineTimeUnit> {
+    /// The transient segment followed by the boundary-dominated segment, which spans the samples.
+    pub forecast: Forecast<Time>,
+    /// The time that boundary-dominated flow starts.
+    pub switch_time: Time,
+    /// The residual (sample rate minus fitted rate) for each sample.
+    pub residuals: Vec<f64>,
+    pub statistics: FitStatistics,
+}
+
+/// The rate of a hyperbolic decline with parameters `[q_i, D_i, b_transient, b_boundary]` that
+/// switches to the boundary exponent at `switch_time`, keeping the rate and decline rate
+/// continuous.
+fn transient_rate(parameters: &[f64], switch_time: f64, time: f64) -> f64 {
+    let (initial_rate, decline_rate) = (parameters[0], parameters[1]);
+    let (transient_exponent, boundary_exponent) = (parameters[2], parameters[3]);
+    let transient = [initial_rate, decline_rate, transient_exponent];
+
+    if time <= switch_time {
+        return FitModel::Hyperbolic.rate(&transient, time);
+    }
+    let switch_rate = FitModel::Hyperbolic.rate(&transient, switch_time);
+    let switch_decline_rate =
+        decline_rate / switch_time.mul_add(transient_exponent * decline_rate, 1.);
+    FitModel::Hyperbolic.rate(
+        &[switch_rate, switch_decline_rate, boundary_exponent],
+        time - switch_time,
+    )
+}
+
+/// A hyperbolic segment, or a harmonic segment if the exponent is one.
+fn decline_segment<Time: DeclineTimeUnit>(
+    initial_rate: f64,
+    decline_rate: f64,
+    duration: f64,
+    exponent: f64,
+) -> Result<Segment<Time>, DeclineCurveAnalysisError> {
+    let (initial_rate, decline_rate, duration) = (
+        ProductionRate::new(initial_rate),
+        NominalDeclineRate::new(decline_rate),
+        Time::from(duration),
+    );
+
+    Ok(if is_effectively_zero(exponent - 1.) {
+        HarmonicParameters::from_incremental_duration(initial_rate, decline_rate, duration)?.into()
+    } else {
+        HyperbolicParameters::from_incremental_duration(
+            initial_rate,
+            decline_rate,
+            duration,
+            exponent,
+        )?
+        .into()
+    })
+}
+
+/// Fits a transient segment with a high exponent (e.g., during linear flow in an unconventional
+/// well) that switches to a boundary-dominated segment with a lower exponent, which usually
+/// forecasts better than a single hyperbolic with a high exponent.
+///
+/// The rate and decline rate are continuous at the switch. For each candidate switch time, the
+/// initial rate, initial decline rate, and both exponents are fitted with nonlinear least squares.
+/// The switch time is found by searching the sample times, then refined between the neighboring
+/// samples.
+pub fn fit_transient_to_boundary<Time: DeclineTimeUnit>(
+    samples: &[(Time, ProductionRate<Time>)],
+    options: &TransientFitOptions,
+) -> Result<TransientFit<Time>, DeclineCurveAnalysisError> {
+    options.validate(samples.len())?;
+    let raw_samples = validate_samples(samples, 2 * options.min_segment_samples - 1)?;
+
+    // Start from a single hyperbolic fit, which is close to the transient segment.
+    let single = fit_hyperbolic(
+        samples,
+        &FitOptions {
+            weights: options.weights.clone(),
+            space: options.space,
+            ..FitOptions::default()
+        },
+    )?
+    .parameters;
+    let initial = [
+        single.initial_rate().value(),
+        single.initial_decline_rate().value(),
+        single.exponent().clamp(
+            options.transient_exponent.minimum,
+            options.transient_exponent.maximum,
+        ),
+        0.5 * (options.boundary_exponent.minimum + options.boundary_exponent.maximum),
+    ];
+    let lower = [
+        initial[0] * 1e-6,
+        0.,
+        options.transient_exponent.minimum,
+        options.boundary_exponent.minimum,
+    ];
+    let upper = [
+        initial[0] * 1e6,
+        f64::MAX,
+        options.transient_exponent.maximum,
+        options.boundary_exponent.maximum,
+    ];
+
+    let weights = match &options.weights {
+        Some(weights) => weights.clone(),
+        None => vec![1.; raw_samples.len()],
+    };
+    let active: Vec<(f64, f64, f64)> = raw_samples
+        .iter()
+        .zip(&weights)
+        .filter(|((_, rate), weight)| **weight > 0. && options.space.is_usable(*rate))
+        .map(|((time, rate), weight)| (*time, *rate, *weight))
+        .collect();
+    let mut times: Vec<f64> = active.iter().map(|(time, _, _)| *time).collect();
+    times.sort_by(f64::total_cmp);
+    if times.len() < 2 * options.min_segment_samples {
+        return Err(DeclineCurveAnalysisError::TooFewInputs {
+            field: "active sample count",
+            count: times.len(),
+            minimum: 2 * options.min_segment_samples,
+        });
+    }
+
+    let residuals = |switch_time: f64, parameters: &[f64]| -> Vec<f64> {
+        active
+            .iter()
+            .map(|(time, rate, weight)| {
+                weight.sqrt()
+                    * options
+                        .space
+                        .residual(*rate, transient_rate(parameters, switch_time, *time))
+            })
+            .collect()
+    };
+    let mut iterations = 0;
+    let mut solve = |switch_time: f64| {
+        let solution = levenberg_marquardt(
+            |parameters| residuals(switch_time, parameters),
+            &initial,
+            &lower,
+            &upper,
+        )?;
+        iterations += solution.iterations;
+        let sum_of_squares: f64 = residuals(switch_time, &solution.parameters)
+            .iter()
+            .map(|residual| residual * residual)
+            .sum();
+        sum_of_squares
+            .is_finite()
+            .then_some((sum_of_squares, solution.parameters))
+    };
+
+    // Search the sample times, where a switch at a sample time includes it in the transient.
+    let first_index = options.min_segment_samples - 1;
+    let last_index = times.len() - options.min_segment_samples - 1;
+    let mut best: Option<(usize, f64, Vec<f64>)> = None;
+    for (index, time) in times
+        .iter()
+        .enumerate()
+        .take(last_index + 1)
+        .skip(first_index)
+    {
+        if let Some((sum_of_squares, parameters)) = solve(*time)
+            && best
+                .as_ref()
+                .is_none_or(|(_, best_sum, _)| sum_of_squares < *best_sum)
+        {
+            best = Some((index, sum_of_squares, parameters));
+        }
+    }
+    let (best_index, mut best_sum, mut best_parameters) =
+        best.ok_or(DeclineCurveAnalysisError::FitDidNotConverge)?;
+    let mut switch_time = times[best_index];
+
+    // Refine the switch time between the neighboring candidates with a golden-section search.
+    let inverse_golden_ratio = 0.5 * (5f64.sqrt() - 1.);
+    let (mut low, mut high) = (
+        times[best_index.saturating_sub(1).max(first_index)],
+        times[(best_index + 1).min(last_index + 1)],
+    );
+    for _ in 0..SWITCH_TIME_ITERATIONS {
+        let left = high - inverse_golden_ratio * (high - low);
+        let right = low + inverse_golden_ratio * (high - low);
+        let left_fit = solve(left);
+        let right_fit = solve(right);
+        let sum = |fit: &Option<(f64, Vec<f64>)>| fit.as_ref().map_or(f64::INFINITY, |fit| fit.0);
+
+        if sum(&left_fit) <= sum(&right_fit) {
+            high = right;
+            if let Some((sum_of_squares, parameters)) = left_fit
+                && sum_of_squares < best_sum
+            {
+                (best_sum, best_parameters, switch_time) = (sum_of_squares, parameters, left);
+            }
+        } else {
+            low = left;
+            if let Some((sum_of_squares, parameters)) = right_fit
+                && sum_of_squares < best_sum
+            {
+                (best_sum, best_parameters, switch_time) = (sum_of_squares, parameters, right);
+            }
+        }
+    }
+
+    let parameters = best_parameters;
+    let end = times[times.len() - 1];
+    let switch_rate = transient_rate(&parameters, switch_time, switch_time);
+    let switch_decline_rate =
+        parameters[1] / switch_time.mul_add(parameters[2] * parameters[1], 1.);
+    let forecast = Forecast::new(vec![
+        decline_segment(parameters[0], parameters[1], switch_time, parameters[2])?,
+        decline_segment(
+            switch_rate,
+            switch_decline_rate,
+            end - switch_time,
+            parameters[3],
+        )?,
+    ]);
+
+    let residuals: Vec<f64> = raw_samples
+        .iter()
+        .map(|(time, rate)| rate - transient_rate(&parameters, switch_time, *time))
+        .collect();
+    let active_rates: Vec<(f64, f64)> = raw_samples
+        .iter()
+        .zip(&residuals)
+        .zip(&weights)
+        .filter(|(((_, rate), _), weight)| **weight > 0. && options.space.is_usable(*rate))
+        .map(|(((_, rate), residual), _)| (*rate, *residual))
+        .collect();
+    let sample_count = active_rates.len();
+    let sum_of_squared_residuals: f64 = active_rates
+        .iter()
+        .map(|(_, residual)| residual * residual)
+        .sum();
+    let mean_rate = active_rates.iter().map(|(rate, _)| rate).sum::<f64>() / sample_count as f64;
+    let total_sum_of_squares: f64 = active_rates
+        .iter()
+        .map(|(rate, _)| math::powi(rate - mean_rate, 2))
+        .sum();
+
+    Ok(TransientFit {
+        forecast,
+        switch_time: Time::from(switch_time),
+        residuals,
+        statistics: FitStatistics {
+            sample_count,
+            sum_of_squared_residuals,
+            root_mean_squared_error: (sum_of_squared_residuals / sample_count as f64).sqrt(),
+            r_squared: if total_sum_of_squares > 0. {
+                1. - sum_of_squared_residuals / total_sum_of_squares
+            } else {
+                1.
+            },
+            iterations,
+        },
+    })
+}
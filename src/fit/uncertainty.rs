@@ -0,0 +1,126 @@
+use crate::{
+    DeclineCurveAnalysisError, DeclineTimeUnit, ProductionRate, math,
+    numerical::standard_normal_quantile, validate_finite,
+};
+use alloc::vec::Vec;
+use core::ops::Bound;
+
+use super::{FitModel, FitSpace};
+#[cfg(not(feature = "std"))]
+use crate::math::FloatMath;
+
+/// The estimated covariance of fitted parameters, which quantifies how well the samples constrain
+/// the fit.
+///
+/// The parameters are ordered as the initial rate, the (initial) decline rate, and the exponent
+/// for hyperbolic fits. The covariance is in the space the residuals were minimized in, so for
+/// [`FitSpace::LogRate`] fits it describes the uncertainty of the log rates.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParameterCovariance {
+    model: FitModel,
+    space: FitSpace,
+    parameters: Vec<f64>,
+    matrix: Vec<Vec<f64>>,
+}
+
+/// The lower and upper bounds of a confidence interval around a fitted rate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConfidenceBand<Time: DeclineTimeUnit> {
+    pub lower: ProductionRate<Time>,
+    pub rate: ProductionRate<Time>,
+    pub upper: ProductionRate<Time>,
+}
+
+impl ParameterCovariance {
+    pub(crate) fn new(
+        model: FitModel,
+        space: FitSpace,
+        parameters: Vec<f64>,
+        matrix: Vec<Vec<f64>>,
+    ) -> Self {
+        Self {
+            model,
+            space,
+            parameters,
+            matrix,
+        }
+    }
+
+    pub fn model(&self) -> FitModel {
+        self.model
+    }
+
+    pub fn parameters(&self) -> &[f64] {
+        &self.parameters
+    }
+
+    pub fn matrix(&self) -> &[Vec<f64>] {
+        &self.matrix
+    }
+
+    /// The standard error of each parameter, which is the square root of the diagonal.
+    pub fn standard_errors(&self) -> Vec<f64> {
+        self.matrix
+            .iter()
+            .enumerate()
+            .map(|(i, row)| row[i].max(0.).sqrt())
+            .collect()
+    }
+
+    /// Returns the confidence interval of the fitted rate at a time, where the level is the
+    /// two-sided coverage probability (e.g., 0.95).
+    ///
+    /// The interval is calculated with the delta method assuming normally distributed parameter
+    /// errors. The time isn't clamped to the fitted duration, so this can be used for
+    /// extrapolation.
+    pub fn confidence_band<Time: DeclineTimeUnit>(
+        &self,
+        time: Time,
+        level: f64,
+    ) -> Result<ConfidenceBand<Time>, DeclineCurveAnalysisError> {
+        validate_finite(time.value(), "time")?;
+        if !(level > 0. && level < 1.) {
+            return Err(DeclineCurveAnalysisError::InputOutOfRange {
+                field: "confidence level",
+                value: level,
+                minimum: Bound::Excluded(0.),
+                maximum: Bound::Excluded(1.),
+            });
+        }
+
+        let time = time.value();
+        let rate = self.model.rate(&self.parameters, time);
+        let gradient: Vec<f64> = self
+            .model
+            .rate_jacobian(&self.parameters, time)
+            .into_iter()
+            .map(|derivative| self.space.fitted_rate_derivative(rate, derivative))
+            .collect();
+
+        let variance: f64 = self
+            .matrix
+            .iter()
+            .zip(&gradient)
+            .map(|(row, gradient_i)| {
+                gradient_i
+                    * row
+                        .iter()
+                        .zip(&gradient)
+                        .map(|(value, gradient_j)| value * gradient_j)
+                        .sum::<f64>()
+            })
+            .sum();
+        let half_width = standard_normal_quantile(0.5 + 0.5 * level) * variance.max(0.).sqrt();
+
+        let (lower, upper) = match self.space {
+            FitSpace::Rate => ((rate - half_width).max(0.), rate + half_width),
+            FitSpace::LogRate => (rate * math::exp(-half_width), rate * math::exp(half_width)),
+        };
+
+        Ok(ConfidenceBand {
+            lower: ProductionRate::new(lower),
+            rate: ProductionRate::new(rate),
+            upper: ProductionRate::new(upper),
+        })
+    }
+}
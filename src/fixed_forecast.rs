@@ -0,0 +1,82 @@
+use crate::{
+    AverageDaysTime, AverageMonthsTime, AverageYearsTime, DeclineCurveAnalysisError,
+    DeclineTimeUnit, Forecast, NominalDeclineRate, PeriodVolume, ProductionRate,
+    RateCumulativePoint, Segment, SegmentSampler, TimeGrid,
+    forecast::impl_forecast_evaluation,
+    is_effectively_zero,
+    periods::{fixed_period_boundaries, grid_volumes, period_volumes, validate_period_length},
+    validate_finite, validate_incremental_volume, validate_positive,
+};
+
+/// A forecast with a fixed number of segments that are stored inline, so it can be created and
+/// evaluated without allocating, e.g., in Monte Carlo loops or on embedded targets.
+///
+/// It has the same evaluation methods as [`Forecast`], and converts to and from it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FixedForecast<Time: DeclineTimeUnit, const N: usize> {
+    segments: [Segment<Time>; N],
+    initial_cumulative_volume: f64,
+}
+
+impl<Time: DeclineTimeUnit, const N: usize> FixedForecast<Time, N> {
+    pub fn new(segments: [Segment<Time>; N]) -> Self {
+        Self {
+            segments,
+            initial_cumulative_volume: 0.,
+        }
+    }
+
+    /// Sets the cumulative volume produced before the start of the forecast.
+    pub fn with_initial_cumulative_volume(
+        self,
+        initial_cumulative_volume: f64,
+    ) -> Result<Self, DeclineCurveAnalysisError> {
+        validate_positive(initial_cumulative_volume, "initial cumulative volume")?;
+
+        Ok(Self {
+            initial_cumulative_volume,
+            ..self
+        })
+    }
+
+    pub fn into_segments(self) -> [Segment<Time>; N] {
+        self.segments
+    }
+}
+
+impl_forecast_evaluation!(FixedForecast, const N);
+
+impl<Time: DeclineTimeUnit, const N: usize> From<[Segment<Time>; N]> for FixedForecast<Time, N> {
+    fn from(segments: [Segment<Time>; N]) -> Self {
+        Self::new(segments)
+    }
+}
+
+impl<Time: DeclineTimeUnit, const N: usize> From<FixedForecast<Time, N>> for Forecast<Time> {
+    fn from(forecast: FixedForecast<Time, N>) -> Self {
+        Forecast::from_parts(forecast.segments.into(), forecast.initial_cumulative_volume)
+    }
+}
+
+impl<Time: DeclineTimeUnit, const N: usize> TryFrom<Forecast<Time>> for FixedForecast<Time, N> {
+    type Error = DeclineCurveAnalysisError;
+
+    /// Converts a forecast that has exactly `N` segments.
+    fn try_from(forecast: Forecast<Time>) -> Result<Self, Self::Error> {
+        let initial_cumulative_volume = forecast.initial_cumulative_volume();
+        let segment_count = forecast.segments().len();
+
+        let segments = forecast.into_segments().try_into().map_err(|_| {
+            DeclineCurveAnalysisError::InputLengthMismatch {
+                field: "segments",
+                count: segment_count,
+                expected: N,
+            }
+        })?;
+
+        Ok(Self {
+            segments,
+            initial_cumulative_volume,
+        })
+    }
+}
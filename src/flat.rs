@@ -1,6 +1,9 @@
 use crate::{
-    DeclineCurveAnalysisError, DeclineTimeUnit, ProductionRate, is_effectively_zero,
-    validate_duration, validate_incremental_volume, validate_positive,
+    DeclineCurveAnalysisError, DeclineTimeUnit, NominalDeclineRate, ProductionRate, RateTruncation,
+    clamp_to_duration, evaluate_at_times, evaluate_at_times_f32,
+    inverse::{rate_at_incremental_volume, time_at_incremental_volume, time_at_rate},
+    is_effectively_zero, split_duration, truncated_duration, validate_duration,
+    validate_incremental_volume, validate_positive,
 };
 
 /// A flat segment that represents a constant production rate.
@@ -32,6 +35,20 @@ impl<Time: DeclineTimeUnit> FlatParameters<Time> {
         })
     }
 
+    /// Creates a flat segment with a zero rate, e.g., for a shut-in within a forecast.
+    ///
+    /// Unlike a [`DelayParameters`](crate::DelayParameters), which only offsets the start of
+    /// production, a zero-rate flat segment is part of the producing profile, so it's included
+    /// when checking rate continuity and finding initial rates.
+    pub fn zero(incremental_duration: Time) -> Result<Self, DeclineCurveAnalysisError> {
+        Self::from_incremental_duration(ProductionRate::new(0.), incremental_duration)
+    }
+
+    /// Creates a flat segment that produces `incremental_volume` at `rate`.
+    ///
+    /// This fails with [`DeclineCurveAnalysisError::ZeroRateVolume`] if the rate is zero and the
+    /// volume isn't, since a zero rate never produces it. Zero-rate segments can be created from a
+    /// duration instead (see [`FlatParameters::zero`]).
     pub fn from_incremental_volume(
         rate: ProductionRate<Time>,
         incremental_volume: f64,
@@ -47,7 +64,9 @@ impl<Time: DeclineTimeUnit> FlatParameters<Time> {
         }
 
         if is_effectively_zero(rate.value) {
-            return Err(DeclineCurveAnalysisError::CannotSolveDecline);
+            return Err(DeclineCurveAnalysisError::ZeroRateVolume {
+                volume: incremental_volume,
+            });
         }
 
         let incremental_duration = Time::from(incremental_volume / rate.value);
@@ -71,10 +90,117 @@ impl<Time: DeclineTimeUnit> FlatParameters<Time> {
         }
     }
 
+    /// The volume produced between two times, which are clamped to be within the segment. This is
+    /// negative if `end` is before `start`.
+    pub fn incremental_volume_between(&self, start: Time, end: Time) -> f64 {
+        let start = clamp_to_duration(start, self.incremental_duration);
+        let end = clamp_to_duration(end, self.incremental_duration);
+
+        self.incremental_volume_at_time_without_clamping(end)
+            - self.incremental_volume_at_time_without_clamping(start)
+    }
+
+    /// Returns the time at which the cumulative volume reaches the given volume, which is the
+    /// inverse of `incremental_volume_at_time`.
+    ///
+    /// This fails if the volume isn't reached before the end of the segment.
+    pub fn time_at_incremental_volume(
+        &self,
+        incremental_volume: f64,
+    ) -> Result<Time, DeclineCurveAnalysisError> {
+        time_at_incremental_volume(
+            self.incremental_volume(),
+            self.incremental_duration,
+            incremental_volume,
+            || {
+                Ok(
+                    Self::from_incremental_volume(self.rate, incremental_volume)?
+                        .incremental_duration,
+                )
+            },
+        )
+    }
+
+    /// Returns the rate at the time the cumulative volume reaches the given volume, for
+    /// rate-cumulative analysis.
+    ///
+    /// This fails if the volume isn't reached before the end of the segment.
+    pub fn rate_at_incremental_volume(
+        &self,
+        incremental_volume: f64,
+    ) -> Result<ProductionRate<Time>, DeclineCurveAnalysisError> {
+        rate_at_incremental_volume(self.incremental_volume(), incremental_volume, |_| {
+            Ok(self.rate.value)
+        })
+    }
+
     pub fn incremental_volume(&self) -> f64 {
         self.incremental_volume_at_time_without_clamping(self.incremental_duration)
     }
 
+    /// Splits the segment at `time` into two segments that together reproduce the original.
+    pub fn split_at(&self, time: Time) -> (Self, Self) {
+        let (first_duration, second_duration) = split_duration(time, self.incremental_duration);
+        (
+            Self {
+                rate: self.rate,
+                incremental_duration: first_duration,
+            },
+            Self {
+                rate: self.rate,
+                incremental_duration: second_duration,
+            },
+        )
+    }
+
+    /// Returns a copy where every rate is multiplied by `factor`, keeping the same decline.
+    pub(crate) fn scale_rates(&self, factor: f64) -> Self {
+        Self {
+            rate: ProductionRate::new(self.rate.value * factor),
+            ..self.clone()
+        }
+    }
+
+    /// Returns a copy of the parameters that ends at the first time the rate falls to the economic
+    /// limit, along with the volume and duration that were removed.
+    pub fn truncate_at_rate(
+        &self,
+        economic_limit_rate: ProductionRate<Time>,
+    ) -> Result<RateTruncation<Self, Time>, DeclineCurveAnalysisError> {
+        let incremental_duration = truncated_duration(
+            self.rate,
+            self.rate,
+            self.incremental_duration,
+            economic_limit_rate,
+            || Ok(self.incremental_duration),
+        )?;
+
+        let parameters = Self::from_incremental_duration(self.rate, incremental_duration)?;
+
+        Ok(RateTruncation {
+            truncated_volume: self.incremental_volume() - parameters.incremental_volume(),
+            truncated_duration: Time::from(
+                self.incremental_duration.value() - parameters.incremental_duration.value(),
+            ),
+            parameters,
+        })
+    }
+
+    /// Returns the first time at which the rate reaches the given rate, which is the start of the
+    /// segment if the rate matches.
+    pub fn time_at_rate(
+        &self,
+        rate: ProductionRate<Time>,
+    ) -> Result<Time, DeclineCurveAnalysisError> {
+        time_at_rate(
+            self.rate,
+            self.rate,
+            self.incremental_duration,
+            rate,
+            || Err(DeclineCurveAnalysisError::CannotSolveDecline),
+        )
+    }
+
     pub fn final_rate(&self) -> ProductionRate<Time> {
         self.rate
     }
@@ -82,4 +208,55 @@ impl<Time: DeclineTimeUnit> FlatParameters<Time> {
     pub fn rate_at_time(&self, _time: Time) -> ProductionRate<Time> {
         self.rate
     }
+
+    /// Evaluates `rate_at_time` for each time, writing the rates to `out`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `times` and `out` have different lengths.
+    pub fn rates_at_times(&self, times: &[Time], out: &mut [f64]) {
+        evaluate_at_times(times, out, self.incremental_duration, |_| self.rate.value);
+    }
+
+    /// Evaluates `incremental_volume_at_time` for each time, writing the volumes to `out`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `times` and `out` have different lengths.
+    pub fn incremental_volumes_at_times(&self, times: &[Time], out: &mut [f64]) {
+        evaluate_at_times(times, out, self.incremental_duration, |time| {
+            self.incremental_volume_at_time_without_clamping(time)
+        });
+    }
+
+    /// Like `rates_at_times`, but with `f32` times and rates, where the times are in the segment's
+    /// time unit.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `times` and `out` have different lengths.
+    pub fn rates_at_times_f32(&self, times: &[f32], out: &mut [f32]) {
+        let rate = self.rate.value as f32;
+        evaluate_at_times_f32(times, out, self.incremental_duration, |_| rate);
+    }
+
+    /// Like `incremental_volumes_at_times`, but with `f32` times and volumes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `times` and `out` have different lengths.
+    pub fn incremental_volumes_at_times_f32(&self, times: &[f32], out: &mut [f32]) {
+        let rate = self.rate.value as f32;
+        evaluate_at_times_f32(times, out, self.incremental_duration, |time| rate * time);
+    }
+
+    /// The instantaneous nominal decline rate, which is always zero for flat segments.
+    pub fn decline_rate_at_time(&self, _time: Time) -> NominalDeclineRate<Time> {
+        NominalDeclineRate::new(0.)
+    }
+
+    /// The derivative of the rate with respect to time, which is always zero for flat segments.
+    pub fn rate_derivative_at_time(&self, _time: Time) -> f64 {
+        0.
+    }
 }
@@ -0,0 +1,730 @@
+use crate::{
+    AverageDaysTime, AverageMonthsTime, AverageYearsTime, BindingConstraint,
+    DeclineCurveAnalysisError, DeclineTimeUnit, ExponentPolicy, ExponentialParameters,
+    FlatParameters, HyperbolicParameters, LimitTruncation, LinearParameters, NominalDeclineRate,
+    PeriodVolume, ProductionRate, RateTruncation, Segment, SegmentSampler, TimeGrid,
+    is_effectively_zero,
+    periods::{fixed_period_boundaries, grid_volumes, period_volumes, validate_period_length},
+    validate_duration, validate_economic_limit_rate, validate_finite, validate_incremental_volume,
+    validate_non_zero_positive_rate, validate_positive,
+};
+use alloc::{vec, vec::Vec};
+
+/// The rate of a forecast at a point in its life, along with the cumulative volume produced by
+/// then, e.g., for plotting rate against cumulative volume.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateCumulativePoint<Time: DeclineTimeUnit> {
+    pub time: Time,
+    /// The cumulative volume at `time`, including the initial cumulative volume of the forecast.
+    pub cumulative_volume: f64,
+    pub rate: ProductionRate<Time>,
+}
+
+/// A sequence of segments that are evaluated one after another, where each segment starts at the
+/// end of the previous one.
+///
+/// A forecast can carry the cumulative volume produced before it starts (e.g., the historical
+/// production to date), so that cumulative volumes include it. Incremental volumes never include
+/// it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Forecast<Time: DeclineTimeUnit> {
+    segments: Vec<Segment<Time>>,
+    initial_cumulative_volume: f64,
+}
+
+impl<Time: DeclineTimeUnit> Forecast<Time> {
+    pub fn new(segments: Vec<Segment<Time>>) -> Self {
+        Self {
+            segments,
+            initial_cumulative_volume: 0.,
+        }
+    }
+
+    /// Creates a forecast for a new well that builds up linearly from `initial_rate` to a peak
+    /// rate over `ramp_duration`, then declines. The peak rate is the initial rate of `decline`.
+    pub fn ramp_up_then_decline(
+        initial_rate: ProductionRate<Time>,
+        ramp_duration: Time,
+        decline: impl Into<Segment<Time>>,
+    ) -> Result<Self, DeclineCurveAnalysisError> {
+        let decline = decline.into();
+        let peak_rate = decline.rate_at_time(Time::from(0.));
+        validate_non_zero_positive_rate(peak_rate.value(), "peak rate")?;
+        validate_non_zero_positive_rate(initial_rate.value(), "initial rate")?;
+        validate_non_zero_positive_rate(ramp_duration.value(), "ramp duration")?;
+        validate_duration(ramp_duration)?;
+
+        if initial_rate.value() >= peak_rate.value() {
+            return Err(DeclineCurveAnalysisError::InputNotLessThan {
+                field: "initial rate",
+                value: initial_rate.value(),
+                other: "peak rate",
+                limit: peak_rate.value(),
+            });
+        }
+
+        // The linear segment reaches the peak rate with a negative decline rate.
+        let ramp = LinearParameters::from_incremental_duration(
+            initial_rate,
+            NominalDeclineRate::new(
+                (initial_rate.value() - peak_rate.value())
+                    / (initial_rate.value() * ramp_duration.value()),
+            ),
+            ramp_duration,
+        )?;
+
+        Ok(Self::new(vec![ramp.into(), decline]))
+    }
+
+    /// Creates a forecast that holds the initial rate of `decline` for `plateau_duration`, then
+    /// declines, e.g., for a well that's choked back or limited by facility capacity.
+    pub fn plateau_then_decline(
+        plateau_duration: Time,
+        decline: impl Into<Segment<Time>>,
+    ) -> Result<Self, DeclineCurveAnalysisError> {
+        let decline = decline.into();
+        let plateau = FlatParameters::from_incremental_duration(
+            decline.rate_at_time(Time::from(0.)),
+            plateau_duration,
+        )?;
+
+        Ok(Self::new(vec![plateau.into(), decline]))
+    }
+
+    /// Creates a modified hyperbolic forecast, which follows a hyperbolic decline until its nominal
+    /// decline rate reaches `terminal_decline_rate`, then switches to an exponential decline at
+    /// that rate for the rest of `incremental_duration`.
+    ///
+    /// The forecast is exponential throughout if the initial decline rate is already at or below
+    /// the terminal decline rate.
+    pub fn modified_hyperbolic(
+        initial_rate: ProductionRate<Time>,
+        initial_decline_rate: NominalDeclineRate<Time>,
+        exponent: f64,
+        terminal_decline_rate: NominalDeclineRate<Time>,
+        incremental_duration: Time,
+    ) -> Result<Self, DeclineCurveAnalysisError> {
+        validate_non_zero_positive_rate(terminal_decline_rate.value(), "terminal decline rate")?;
+        validate_duration(incremental_duration)?;
+
+        if initial_decline_rate.value() <= terminal_decline_rate.value() {
+            let exponential = ExponentialParameters::from_incremental_duration(
+                initial_rate,
+                initial_decline_rate,
+                incremental_duration,
+            )?;
+            return Ok(Self::new(vec![exponential.into()]));
+        }
+
+        let hyperbolic = HyperbolicParameters::from_final_decline_rate(
+            initial_rate,
+            initial_decline_rate,
+            terminal_decline_rate,
+            exponent,
+        )?;
+        let switch_time = hyperbolic.incremental_duration().value();
+        if switch_time >= incremental_duration.value() {
+            let hyperbolic = HyperbolicParameters::from_incremental_duration(
+                initial_rate,
+                initial_decline_rate,
+                incremental_duration,
+                exponent,
+            )?;
+            return Ok(Self::new(vec![hyperbolic.into()]));
+        }
+
+        let exponential = ExponentialParameters::from_incremental_duration(
+            hyperbolic.final_rate(),
+            terminal_decline_rate,
+            Time::from(incremental_duration.value() - switch_time),
+        )?;
+
+        Ok(Self::new(vec![hyperbolic.into(), exponential.into()]))
+    }
+
+    /// Sets the cumulative volume produced before the start of the forecast.
+    pub fn with_initial_cumulative_volume(
+        self,
+        initial_cumulative_volume: f64,
+    ) -> Result<Self, DeclineCurveAnalysisError> {
+        validate_positive(initial_cumulative_volume, "initial cumulative volume")?;
+
+        Ok(Self {
+            initial_cumulative_volume,
+            ..self
+        })
+    }
+
+    /// Creates a forecast from an already validated initial cumulative volume.
+    pub(crate) fn from_parts(segments: Vec<Segment<Time>>, initial_cumulative_volume: f64) -> Self {
+        Self {
+            segments,
+            initial_cumulative_volume,
+        }
+    }
+
+    pub fn into_segments(self) -> Vec<Segment<Time>> {
+        self.segments
+    }
+
+    /// Returns a copy of the forecast that ends at the first time the rate falls to the economic
+    /// limit, along with the volume and duration that were removed. Segments after the truncated
+    /// segment are dropped.
+    pub fn truncate_at_rate(
+        &self,
+        economic_limit_rate: ProductionRate<Time>,
+    ) -> Result<RateTruncation<Self, Time>, DeclineCurveAnalysisError> {
+        validate_economic_limit_rate(economic_limit_rate)?;
+
+        let mut segments = Vec::with_capacity(self.segments.len());
+        let mut truncated_volume = 0.;
+        let mut truncated_duration = 0.;
+
+        for segment in &self.segments {
+            if truncated_duration > 0. {
+                truncated_volume += segment.incremental_volume();
+                truncated_duration += segment.incremental_duration().value();
+                continue;
+            }
+
+            let truncation = segment.truncate_at_rate(economic_limit_rate)?;
+            truncated_volume += truncation.truncated_volume;
+            truncated_duration += truncation.truncated_duration.value();
+            segments.push(truncation.parameters);
+        }
+
+        Ok(RateTruncation {
+            parameters: Self {
+                segments,
+                initial_cumulative_volume: self.initial_cumulative_volume,
+            },
+            truncated_volume,
+            truncated_duration: Time::from(truncated_duration),
+        })
+    }
+
+    /// Returns a copy of the forecast that ends at the first time the rate falls to the economic
+    /// limit or at the maximum life, whichever comes first, along with the volume and duration
+    /// that were removed and the limit that ended it.
+    ///
+    /// The maximum life caps the duration regardless of the rate (e.g., 50 years for reserves
+    /// reporting). If both limits are reached at the same time, the economic limit is reported.
+    pub fn truncate_at_limits(
+        &self,
+        economic_limit_rate: ProductionRate<Time>,
+        maximum_life: Option<Time>,
+    ) -> Result<LimitTruncation<Time>, DeclineCurveAnalysisError> {
+        if let Some(maximum_life) = maximum_life {
+            validate_non_zero_positive_rate(maximum_life.value(), "maximum life")?;
+        }
+
+        let truncation = self.truncate_at_rate(economic_limit_rate)?;
+        let economic_life = truncation.parameters.incremental_duration().value();
+        let binding_constraint = if truncation.is_truncated() {
+            BindingConstraint::EconomicLimit
+        } else {
+            BindingConstraint::EndOfForecast
+        };
+
+        let Some(maximum_life) = maximum_life.filter(|maximum_life| {
+            maximum_life.value() < economic_life
+                && !is_effectively_zero(economic_life - maximum_life.value())
+        }) else {
+            return Ok(LimitTruncation {
+                forecast: truncation.parameters,
+                truncated_volume: truncation.truncated_volume,
+                truncated_duration: truncation.truncated_duration,
+                binding_constraint,
+            });
+        };
+
+        let forecast = truncation.parameters.truncate_at_time(maximum_life)?;
+        let removed_volume =
+            truncation.parameters.incremental_volume() - forecast.incremental_volume();
+
+        Ok(LimitTruncation {
+            forecast,
+            truncated_volume: truncation.truncated_volume + removed_volume,
+            truncated_duration: Time::from(
+                truncation.truncated_duration.value() + economic_life - maximum_life.value(),
+            ),
+            binding_constraint: BindingConstraint::MaximumLife,
+        })
+    }
+
+    /// Returns a copy of the forecast that ends at `time`, where the segment that's active at
+    /// `time` is shortened to end there. The forecast is unchanged if it ends before `time`.
+    pub fn truncate_at_time(&self, time: Time) -> Result<Self, DeclineCurveAnalysisError> {
+        validate_positive(time.value(), "time")?;
+
+        let mut segments = Vec::with_capacity(self.segments.len());
+        let mut start = 0.;
+
+        for segment in &self.segments {
+            let end = start + segment.incremental_duration().value();
+            if end > time.value() {
+                let elapsed = time.value() - start;
+                if !is_effectively_zero(elapsed) {
+                    segments.push(segment.with_incremental_duration(Time::from(elapsed))?);
+                }
+                break;
+            }
+
+            segments.push(segment.clone());
+            start = end;
+        }
+
+        Ok(Self {
+            segments,
+            initial_cumulative_volume: self.initial_cumulative_volume,
+        })
+    }
+
+    /// Validates that the exponents of the hyperbolic segments are allowed by the policy.
+    pub fn validate_exponents(
+        &self,
+        policy: ExponentPolicy,
+    ) -> Result<(), DeclineCurveAnalysisError> {
+        self.segments
+            .iter()
+            .try_for_each(|segment| segment.validate_exponent(policy))
+    }
+
+    /// Rolls the forecast forward so that it starts at `time`, returning the volume produced up to
+    /// that time. Segments that end at or before `time` are dropped, and the segment that's active
+    /// at `time` is split so that only its remainder is kept. The produced volume is added to the
+    /// initial cumulative volume, so cumulative volumes are unchanged.
+    ///
+    /// Advancing past the end of the forecast leaves it empty. The forecast is left unchanged if
+    /// the active segment can't be split.
+    pub fn advance_to(&mut self, time: Time) -> Result<f64, DeclineCurveAnalysisError> {
+        validate_positive(time.value(), "time")?;
+
+        let mut produced_volume = 0.;
+        let mut start = 0.;
+        let mut produced_segments = 0;
+        let mut remainder = None;
+
+        for segment in &self.segments {
+            let end = start + segment.incremental_duration().value();
+            if end > time.value() {
+                let elapsed = time.value() - start;
+                if !is_effectively_zero(elapsed) {
+                    let (produced, remaining) = segment.split_at(Time::from(elapsed))?;
+                    produced_volume += produced.incremental_volume();
+                    remainder = Some(remaining);
+                }
+                break;
+            }
+
+            produced_volume += segment.incremental_volume();
+            produced_segments += 1;
+            start = end;
+        }
+
+        self.segments.drain(..produced_segments);
+        if let Some(remainder) = remainder {
+            self.segments[0] = remainder;
+        }
+        self.initial_cumulative_volume += produced_volume;
+
+        Ok(produced_volume)
+    }
+}
+
+/// Implements the evaluation methods that are shared by [`Forecast`] and
+/// [`FixedForecast`](crate::FixedForecast), which both store their `segments` in order along with
+/// an `initial_cumulative_volume`.
+macro_rules! impl_forecast_evaluation {
+    ($ty:ident $(, const $count:ident)?) => {
+        impl<Time: DeclineTimeUnit $(, const $count: usize)?> $ty<Time $(, $count)?> {
+            /// The cumulative volume produced before the start of the forecast, which is zero
+            /// unless it was set.
+            pub fn initial_cumulative_volume(&self) -> f64 {
+                self.initial_cumulative_volume
+            }
+
+            pub fn segments(&self) -> &[Segment<Time>] {
+                &self.segments
+            }
+
+            pub fn incremental_duration(&self) -> Time {
+                Time::from(self.segments.iter().fold(0., |total, segment| {
+                    total + segment.incremental_duration().value()
+                }))
+            }
+
+            pub fn incremental_volume(&self) -> f64 {
+                self.segments
+                    .iter()
+                    .fold(0., |total, segment| total + segment.incremental_volume())
+            }
+
+            /// Returns true if the other forecast has the same number and types of segments, and
+            /// its parameters and initial cumulative volume are within `tolerance` as for
+            /// [`Segment::approx_eq`], otherwise false.
+            pub fn approx_eq(&self, other: &Self, tolerance: f64) -> bool {
+                self.parameters_match(other, |a, b| crate::within_tolerance(a, b, tolerance))
+            }
+
+            /// Returns true if the other forecast has the same number and types of segments, and
+            /// `is_match` is true for the initial cumulative volumes and each pair of segment
+            /// parameters, otherwise false.
+            pub(crate) fn parameters_match(
+                &self,
+                other: &Self,
+                is_match: impl Fn(f64, f64) -> bool,
+            ) -> bool {
+                self.segments.len() == other.segments.len()
+                    && is_match(self.initial_cumulative_volume, other.initial_cumulative_volume)
+                    && self
+                        .segments
+                        .iter()
+                        .zip(other.segments.iter())
+                        .all(|(segment, other)| segment.parameters_match(other, &is_match))
+            }
+
+            /// Returns a hash of the segment types, their parameters, and the initial cumulative
+            /// volume, e.g., to detect whether a stored forecast changed between revisions.
+            ///
+            /// Parameters are rounded to about 9 significant digits before hashing, so forecasts
+            /// that only differ by round-off usually have the same fingerprint. The hash is stable
+            /// across platforms and releases, and includes the time unit.
+            pub fn fingerprint(&self) -> u64 {
+                let mut fingerprinter = crate::fingerprint::Fingerprinter::new::<Time>();
+                fingerprinter.write_value(self.initial_cumulative_volume);
+                fingerprinter.write_u64(self.segments.len() as u64);
+                for segment in self.segments.iter() {
+                    fingerprinter.write_segment(segment);
+                }
+                fingerprinter.finish()
+            }
+
+            /// Returns the segment that is active at the given time along with its start time,
+            /// where segments are active from their start (inclusive) to their end (exclusive).
+            ///
+            /// Times past the end of the forecast return the last segment.
+            fn segment_at_time(&self, time: f64) -> Option<(f64, &Segment<Time>)> {
+                self.segment_index_at_time(time)
+                    .map(|(index, start)| (start, &self.segments[index]))
+            }
+
+            /// Like `segment_at_time`, but returns the index of the segment instead of the segment.
+            pub(crate) fn segment_index_at_time(&self, time: f64) -> Option<(usize, f64)> {
+                let mut start = 0.;
+                let last_index = self.segments.len().checked_sub(1)?;
+
+                for (index, segment) in self.segments.iter().enumerate() {
+                    let end = start + segment.incremental_duration().value();
+                    if time < end || index == last_index {
+                        return Some((index, start));
+                    }
+                    start = end;
+                }
+
+                None
+            }
+
+            /// The total cumulative volume at the end of the forecast, including the initial
+            /// cumulative volume.
+            pub fn cumulative_volume(&self) -> f64 {
+                self.initial_cumulative_volume + self.incremental_volume()
+            }
+
+            /// The total cumulative volume at the given time, including the initial cumulative
+            /// volume.
+            pub fn cumulative_volume_at_time(&self, time: Time) -> f64 {
+                self.initial_cumulative_volume + self.incremental_volume_at_time(time)
+            }
+
+            pub fn incremental_volume_at_time(&self, time: Time) -> f64 {
+                let mut start = 0.;
+                let mut volume = 0.;
+
+                for segment in &self.segments {
+                    if time.value() <= start {
+                        break;
+                    }
+
+                    volume += segment.incremental_volume_at_time(Time::from(time.value() - start));
+                    start += segment.incremental_duration().value();
+                }
+
+                volume
+            }
+
+            /// The volume produced between two times, which are clamped to be within the forecast.
+            /// This is negative if `end` is before `start`.
+            pub fn incremental_volume_between(&self, start: Time, end: Time) -> f64 {
+                let (lower, upper, sign) = if start.value() <= end.value() {
+                    (start.value(), end.value(), 1.)
+                } else {
+                    (end.value(), start.value(), -1.)
+                };
+
+                let mut segment_start = 0.;
+                let mut volume = 0.;
+
+                for segment in &self.segments {
+                    let segment_end = segment_start + segment.incremental_duration().value();
+                    if segment_start >= upper {
+                        break;
+                    }
+
+                    if segment_end > lower {
+                        volume += segment.incremental_volume_between(
+                            Time::from(lower - segment_start),
+                            Time::from(upper - segment_start),
+                        );
+                    }
+                    segment_start = segment_end;
+                }
+
+                sign * volume
+            }
+
+            /// Returns the time at which the cumulative volume of the forecast reaches the given
+            /// volume.
+            ///
+            /// This fails if the volume isn't reached before the end of the forecast.
+            pub fn time_at_incremental_volume(
+                &self,
+                incremental_volume: f64,
+            ) -> Result<Time, DeclineCurveAnalysisError> {
+                validate_incremental_volume(incremental_volume)?;
+
+                let mut start = 0.;
+                let mut remaining_volume = incremental_volume;
+
+                for segment in &self.segments {
+                    let segment_volume = segment.incremental_volume();
+                    if remaining_volume <= segment_volume {
+                        let time = segment.time_at_incremental_volume(remaining_volume)?;
+                        return Ok(Time::from(start + time.value()));
+                    }
+
+                    remaining_volume -= segment_volume;
+                    start += segment.incremental_duration().value();
+                }
+
+                if is_effectively_zero(remaining_volume) {
+                    return Ok(Time::from(start));
+                }
+
+                Err(DeclineCurveAnalysisError::CannotSolveDecline)
+            }
+
+            /// Returns the rate at the time the cumulative volume reaches the given volume,
+            /// including the initial cumulative volume.
+            ///
+            /// This fails if the volume is less than the initial cumulative volume, or isn't
+            /// reached before the end of the forecast.
+            pub fn rate_at_cumulative_volume(
+                &self,
+                cumulative_volume: f64,
+            ) -> Result<ProductionRate<Time>, DeclineCurveAnalysisError> {
+                validate_finite(cumulative_volume, "cumulative volume")?;
+
+                let mut remaining_volume = cumulative_volume - self.initial_cumulative_volume;
+                if remaining_volume < 0. && !is_effectively_zero(remaining_volume) {
+                    return Err(DeclineCurveAnalysisError::CannotSolveDecline);
+                }
+                remaining_volume = remaining_volume.max(0.);
+
+                for segment in &self.segments {
+                    let segment_volume = segment.incremental_volume();
+                    if remaining_volume <= segment_volume {
+                        return segment.rate_at_incremental_volume(remaining_volume);
+                    }
+
+                    remaining_volume -= segment_volume;
+                }
+
+                if is_effectively_zero(remaining_volume) {
+                    return Ok(self.final_rate());
+                }
+
+                Err(DeclineCurveAnalysisError::CannotSolveDecline)
+            }
+
+            /// Returns the first time at which the rate of the forecast reaches the given rate.
+            ///
+            /// This fails if the rate isn't reached before the end of the forecast.
+            pub fn time_at_rate(
+                &self,
+                rate: ProductionRate<Time>,
+            ) -> Result<Time, DeclineCurveAnalysisError> {
+                validate_positive(rate.value(), "rate")?;
+
+                let mut start = 0.;
+
+                for segment in &self.segments {
+                    match segment.time_at_rate(rate) {
+                        Ok(time) => return Ok(Time::from(start + time.value())),
+                        Err(DeclineCurveAnalysisError::CannotSolveDecline) => {}
+                        Err(error) => return Err(error),
+                    }
+
+                    start += segment.incremental_duration().value();
+                }
+
+                Err(DeclineCurveAnalysisError::CannotSolveDecline)
+            }
+
+            /// The rate at the given time. Times before the start of the forecast use the initial
+            /// rate of the first segment, and times past the end use the final rate of the last
+            /// segment.
+            ///
+            /// An empty forecast has a rate of zero.
+            pub fn rate_at_time(&self, time: Time) -> ProductionRate<Time> {
+                match self.segment_at_time(time.value()) {
+                    Some((start, segment)) => {
+                        segment.rate_at_time(Time::from((time.value() - start).max(0.)))
+                    }
+                    None => ProductionRate::new(0.),
+                }
+            }
+
+            /// Returns the volume produced in each period of the given length over the whole
+            /// forecast.
+            ///
+            /// This is evaluated in a single pass over the segments.
+            pub fn volumes_by_period(
+                &self,
+                period_length: Time,
+            ) -> Result<impl Iterator<Item = PeriodVolume<Time>> + '_, DeclineCurveAnalysisError> {
+                validate_period_length(period_length)?;
+
+                Ok(period_volumes(
+                    &self.segments,
+                    fixed_period_boundaries(period_length.value()),
+                ))
+            }
+
+            /// Returns the rate and cumulative volume at the start of the forecast and at the end
+            /// of each period of the given length, for rate-cumulative analysis.
+            pub fn rate_cumulative_points(
+                &self,
+                period_length: Time,
+            ) -> Result<
+                impl Iterator<Item = RateCumulativePoint<Time>> + '_,
+                DeclineCurveAnalysisError,
+            > {
+                let mut cumulative_volume = self.initial_cumulative_volume;
+                let start = RateCumulativePoint {
+                    time: Time::from(0.),
+                    cumulative_volume,
+                    rate: self.rate_at_time(Time::from(0.)),
+                };
+
+                Ok(
+                    core::iter::once(start).chain(self.volumes_by_period(period_length)?.map(
+                        move |period| {
+                            cumulative_volume += period.volume;
+
+                            RateCumulativePoint {
+                                time: period.end,
+                                cumulative_volume,
+                                rate: self.rate_at_time(period.end),
+                            }
+                        },
+                    )),
+                )
+            }
+
+            /// Writes the volume produced in each period of the grid to `out`. Periods outside of
+            /// the forecast have zero volume.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `out` doesn't have one value for each period of the grid.
+            pub fn volumes_on_grid(&self, grid: &TimeGrid<Time>, out: &mut [f64]) {
+                grid_volumes(&self.segments, grid, out);
+            }
+
+            /// Returns the volume produced in each day over the whole forecast.
+            pub fn daily_volumes(&self) -> impl Iterator<Item = PeriodVolume<Time>> + '_ {
+                self.volumes_by_average_period(AverageDaysTime { days: 1. })
+            }
+
+            /// Returns the volume produced in each average month (30.4375 days) over the whole
+            /// forecast.
+            pub fn monthly_volumes(&self) -> impl Iterator<Item = PeriodVolume<Time>> + '_ {
+                self.volumes_by_average_period(AverageMonthsTime { months: 1. })
+            }
+
+            /// Returns the volume produced in each average year (365.25 days) over the whole
+            /// forecast.
+            pub fn yearly_volumes(&self) -> impl Iterator<Item = PeriodVolume<Time>> + '_ {
+                self.volumes_by_average_period(AverageYearsTime { years: 1. })
+            }
+
+            fn volumes_by_average_period<Period: DeclineTimeUnit>(
+                &self,
+                period_length: Period,
+            ) -> impl Iterator<Item = PeriodVolume<Time>> + '_ {
+                period_volumes(
+                    &self.segments,
+                    fixed_period_boundaries(period_length.to_unit::<Time>().value()),
+                )
+            }
+
+            /// The instantaneous nominal decline rate at the given time, using the same segment
+            /// selection as `rate_at_time`.
+            ///
+            /// An empty forecast has a decline rate of zero.
+            pub fn decline_rate_at_time(&self, time: Time) -> NominalDeclineRate<Time> {
+                match self.segment_at_time(time.value()) {
+                    Some((start, segment)) => {
+                        segment.decline_rate_at_time(Time::from((time.value() - start).max(0.)))
+                    }
+                    None => NominalDeclineRate::new(0.),
+                }
+            }
+
+            /// The derivative of the rate with respect to time, using the same segment selection as
+            /// `rate_at_time`.
+            ///
+            /// An empty forecast has a derivative of zero.
+            pub fn rate_derivative_at_time(&self, time: Time) -> f64 {
+                match self.segment_at_time(time.value()) {
+                    Some((start, segment)) => {
+                        segment.rate_derivative_at_time(Time::from((time.value() - start).max(0.)))
+                    }
+                    None => 0.,
+                }
+            }
+
+            /// The final rate of the last segment, or zero for an empty forecast.
+            pub fn final_rate(&self) -> ProductionRate<Time> {
+                self.segments
+                    .last()
+                    .map_or(ProductionRate::new(0.), |segment| segment.final_rate())
+            }
+
+            /// Returns a sampler that walks the forecast on a fixed step without allocating.
+            pub fn sampler(
+                &self,
+                step: Time,
+            ) -> Result<SegmentSampler<'_, Time>, DeclineCurveAnalysisError> {
+                SegmentSampler::new(&self.segments, step)
+            }
+        }
+    };
+}
+
+pub(crate) use impl_forecast_evaluation;
+
+impl_forecast_evaluation!(Forecast);
+
+impl<Time: DeclineTimeUnit> From<Vec<Segment<Time>>> for Forecast<Time> {
+    fn from(segments: Vec<Segment<Time>>) -> Self {
+        Self::new(segments)
+    }
+}
+
+impl<Time: DeclineTimeUnit> FromIterator<Segment<Time>> for Forecast<Time> {
+    fn from_iter<I: IntoIterator<Item = Segment<Time>>>(iter: I) -> Self {
+        Self::new(iter.into_iter().collect())
+    }
+}
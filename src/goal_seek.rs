@@ -0,0 +1,126 @@
+use crate::{
+    DeclineCurveAnalysisError, DeclineTimeUnit, Forecast, HyperbolicParameters, NominalDeclineRate,
+    ProductionRate, is_effectively_zero, math, numerical::find_root, validate_duration,
+    validate_non_zero_positive_rate,
+};
+
+/// Maximum number of times the decline rate bracket is widened before giving up.
+const MAX_BRACKET_ITERATIONS: usize = 200;
+
+/// Solves for the nominal decline rate at which `volume` equals `target_volume`, where `volume`
+/// decreases from `max_volume` as the decline rate increases from zero.
+fn solve_decline_for_volume(
+    target_volume: f64,
+    max_volume: f64,
+    incremental_duration: f64,
+    volume: impl Fn(f64) -> f64,
+) -> Result<f64, DeclineCurveAnalysisError> {
+    validate_non_zero_positive_rate(target_volume, "target volume")?;
+    if target_volume >= max_volume || is_effectively_zero(incremental_duration) {
+        return Err(DeclineCurveAnalysisError::CannotSolveDecline);
+    }
+
+    // Widen the bracket geometrically from a decline rate on the scale of the duration, since the
+    // solution can be many orders of magnitude away.
+    let mut lower = incremental_duration.recip();
+    let mut upper = lower;
+    for _ in 0..MAX_BRACKET_ITERATIONS {
+        let (lower_volume, upper_volume) = (volume(lower), volume(upper));
+        if lower_volume >= target_volume && upper_volume <= target_volume {
+            break;
+        }
+        if lower_volume < target_volume {
+            lower *= 0.5;
+        }
+        if upper_volume > target_volume {
+            upper *= 2.;
+        }
+    }
+
+    // Bisect on the logarithm of the decline rate to keep the relative precision.
+    let log_decline_rate = find_root(
+        |log_decline_rate| volume(math::exp(log_decline_rate)) - target_volume,
+        math::ln(lower),
+        math::ln(upper),
+    )
+    .ok_or(DeclineCurveAnalysisError::CannotSolveDecline)?;
+
+    Ok(math::exp(log_decline_rate))
+}
+
+/// Solves for the initial decline rate of a hyperbolic segment that produces `target_volume` over
+/// `incremental_duration`, e.g., to match a target EUR.
+///
+/// This fails if the target volume isn't less than the volume at a flat rate, which is the limit
+/// as the decline rate approaches zero.
+pub fn solve_initial_decline_for_volume<Time: DeclineTimeUnit>(
+    initial_rate: ProductionRate<Time>,
+    target_volume: f64,
+    exponent: f64,
+    incremental_duration: Time,
+) -> Result<HyperbolicParameters<Time>, DeclineCurveAnalysisError> {
+    validate_non_zero_positive_rate(initial_rate.value(), "initial rate")?;
+    validate_duration(incremental_duration)?;
+
+    let segment = |decline_rate| {
+        HyperbolicParameters::from_incremental_duration(
+            initial_rate,
+            NominalDeclineRate::new(decline_rate),
+            incremental_duration,
+            exponent,
+        )
+    };
+    // Validate the exponent before solving.
+    segment(incremental_duration.value().recip())?;
+
+    let decline_rate = solve_decline_for_volume(
+        target_volume,
+        initial_rate.value() * incremental_duration.value(),
+        incremental_duration.value(),
+        |decline_rate| {
+            segment(decline_rate).map_or(f64::NAN, |segment| segment.incremental_volume())
+        },
+    )?;
+
+    segment(decline_rate)
+}
+
+/// Solves for the initial decline rate of a modified hyperbolic forecast (see
+/// [`Forecast::modified_hyperbolic`]) that produces `target_volume` over `incremental_duration`.
+///
+/// This fails if the target volume isn't less than the volume at a flat rate, which is the limit
+/// as the decline rate approaches zero.
+pub fn solve_modified_hyperbolic_initial_decline_for_volume<Time: DeclineTimeUnit>(
+    initial_rate: ProductionRate<Time>,
+    target_volume: f64,
+    exponent: f64,
+    terminal_decline_rate: NominalDeclineRate<Time>,
+    incremental_duration: Time,
+) -> Result<Forecast<Time>, DeclineCurveAnalysisError> {
+    validate_non_zero_positive_rate(initial_rate.value(), "initial rate")?;
+    validate_duration(incremental_duration)?;
+
+    let forecast = |decline_rate| {
+        Forecast::modified_hyperbolic(
+            initial_rate,
+            NominalDeclineRate::new(decline_rate),
+            exponent,
+            terminal_decline_rate,
+            incremental_duration,
+        )
+    };
+    // Validate the exponent and terminal decline rate before solving, using an initial decline
+    // rate above the terminal decline rate so that the hyperbolic part is used.
+    forecast(2. * terminal_decline_rate.value().abs())?;
+
+    let decline_rate = solve_decline_for_volume(
+        target_volume,
+        initial_rate.value() * incremental_duration.value(),
+        incremental_duration.value(),
+        |decline_rate| {
+            forecast(decline_rate).map_or(f64::NAN, |forecast| forecast.incremental_volume())
+        },
+    )?;
+
+    forecast(decline_rate)
+}
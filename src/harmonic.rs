@@ -1,7 +1,16 @@
+#[cfg(not(feature = "std"))]
+use crate::math::FloatMath;
 use crate::{
     DeclineCurveAnalysisError, DeclineRateSignValidation, DeclineTimeUnit, NominalDeclineRate,
-    ProductionRate, validate_decline_rate_sign, validate_duration, validate_incremental_volume,
-    validate_non_zero_decline_rate, validate_non_zero_positive_rate,
+    ProductionRate, RateTruncation, SecantEffectiveDeclineRate, TangentEffectiveDeclineRate,
+    clamp_to_duration, evaluate_at_times, evaluate_at_times_f32,
+    inverse::{
+        rate_at_incremental_volume, time_at_incremental_volume, time_at_nominal_decline,
+        time_at_rate,
+    },
+    kernels, math, split_duration, truncated_duration, validate_decline_rate_sign,
+    validate_duration, validate_incremental_volume, validate_non_zero_decline_rate,
+    validate_non_zero_positive_rate,
 };
 
 /// For harmonic inclines (negative decline rate), validates that the duration
@@ -70,7 +79,7 @@ impl<Time: DeclineTimeUnit> HarmonicParameters<Time> {
         validate_incremental_volume(incremental_volume)?;
 
         let incremental_duration = Time::from(
-            (((incremental_volume * initial_decline_rate.value()) / initial_rate.value).exp_m1())
+            math::exp_m1((incremental_volume * initial_decline_rate.value()) / initial_rate.value)
                 / initial_decline_rate.value(),
         );
         validate_duration(incremental_duration)?;
@@ -154,9 +163,62 @@ impl<Time: DeclineTimeUnit> HarmonicParameters<Time> {
         })
     }
 
+    /// Like the nominal constructor, but the decline rate is a secant effective decline rate that
+    /// is converted using an exponent of one.
+    pub fn from_secant_effective_incremental_duration(
+        initial_rate: ProductionRate<Time>,
+        initial_decline_rate: SecantEffectiveDeclineRate<Time>,
+        incremental_duration: Time,
+    ) -> Result<Self, DeclineCurveAnalysisError> {
+        Self::from_incremental_duration(
+            initial_rate,
+            initial_decline_rate.to_nominal(1.)?,
+            incremental_duration,
+        )
+    }
+
+    /// Like the nominal constructor, but the decline rate is a secant effective decline rate that
+    /// is converted using an exponent of one.
+    pub fn from_secant_effective_final_rate(
+        initial_rate: ProductionRate<Time>,
+        initial_decline_rate: SecantEffectiveDeclineRate<Time>,
+        final_rate: ProductionRate<Time>,
+    ) -> Result<Self, DeclineCurveAnalysisError> {
+        Self::from_final_rate(
+            initial_rate,
+            initial_decline_rate.to_nominal(1.)?,
+            final_rate,
+        )
+    }
+
+    /// Like the nominal constructor, but the decline rate is a tangent effective decline rate.
+    pub fn from_tangent_effective_incremental_duration(
+        initial_rate: ProductionRate<Time>,
+        initial_decline_rate: TangentEffectiveDeclineRate<Time>,
+        incremental_duration: Time,
+    ) -> Result<Self, DeclineCurveAnalysisError> {
+        Self::from_incremental_duration(
+            initial_rate,
+            initial_decline_rate.to_nominal()?,
+            incremental_duration,
+        )
+    }
+
+    /// Like the nominal constructor, but the decline rate is a tangent effective decline rate.
+    pub fn from_tangent_effective_final_rate(
+        initial_rate: ProductionRate<Time>,
+        initial_decline_rate: TangentEffectiveDeclineRate<Time>,
+        final_rate: ProductionRate<Time>,
+    ) -> Result<Self, DeclineCurveAnalysisError> {
+        Self::from_final_rate(initial_rate, initial_decline_rate.to_nominal()?, final_rate)
+    }
+
     fn incremental_volume_at_time_without_clamping(&self, time: Time) -> f64 {
-        (self.initial_rate.value * (time.value() * self.initial_decline_rate.value()).ln_1p())
-            / self.initial_decline_rate.value()
+        kernels::harmonic_incremental_volume(
+            self.initial_rate.value,
+            self.initial_decline_rate.value(),
+            time.value(),
+        )
     }
 
     pub fn incremental_volume_at_time(&self, time: Time) -> f64 {
@@ -167,20 +229,153 @@ impl<Time: DeclineTimeUnit> HarmonicParameters<Time> {
         }
     }
 
+    /// The volume produced between two times, which are clamped to be within the segment. This is
+    /// negative if `end` is before `start`.
+    pub fn incremental_volume_between(&self, start: Time, end: Time) -> f64 {
+        let start = clamp_to_duration(start, self.incremental_duration);
+        let end = clamp_to_duration(end, self.incremental_duration);
+
+        self.incremental_volume_at_time_without_clamping(end)
+            - self.incremental_volume_at_time_without_clamping(start)
+    }
+
+    /// Returns the time at which the cumulative volume reaches the given volume, which is the
+    /// inverse of `incremental_volume_at_time`.
+    ///
+    /// This fails if the volume isn't reached before the end of the segment.
+    pub fn time_at_incremental_volume(
+        &self,
+        incremental_volume: f64,
+    ) -> Result<Time, DeclineCurveAnalysisError> {
+        time_at_incremental_volume(
+            self.incremental_volume(),
+            self.incremental_duration,
+            incremental_volume,
+            || {
+                Ok(Self::from_incremental_volume(
+                    self.initial_rate,
+                    self.initial_decline_rate,
+                    incremental_volume,
+                )?
+                .incremental_duration)
+            },
+        )
+    }
+
+    /// Returns the rate at the time the cumulative volume reaches the given volume, for
+    /// rate-cumulative analysis.
+    ///
+    /// This fails if the volume isn't reached before the end of the segment.
+    pub fn rate_at_incremental_volume(
+        &self,
+        incremental_volume: f64,
+    ) -> Result<ProductionRate<Time>, DeclineCurveAnalysisError> {
+        // The volume is `(q_i / D_i) * ln(q_i / q)`.
+        rate_at_incremental_volume(self.incremental_volume(), incremental_volume, |volume| {
+            Ok(self.initial_rate.value
+                * math::exp(-self.initial_decline_rate.value() * volume / self.initial_rate.value))
+        })
+    }
+
     pub fn incremental_volume(&self) -> f64 {
         self.incremental_volume_at_time_without_clamping(self.incremental_duration)
     }
 
     fn rate_at_time_without_clamping(&self, time: Time) -> ProductionRate<Time> {
-        ProductionRate::new(
-            self.initial_rate.value / (time.value().mul_add(self.initial_decline_rate.value(), 1.)),
+        ProductionRate::new(kernels::harmonic_rate(
+            self.initial_rate.value,
+            self.initial_decline_rate.value(),
+            time.value(),
+        ))
+    }
+
+    /// Splits the segment at `time` into two segments that together reproduce the original. The
+    /// second segment starts at the rate and instantaneous decline rate at the split.
+    pub fn split_at(&self, time: Time) -> (Self, Self) {
+        let (first_duration, second_duration) = split_duration(time, self.incremental_duration);
+        (
+            Self {
+                initial_rate: self.initial_rate,
+                initial_decline_rate: self.initial_decline_rate,
+                incremental_duration: first_duration,
+            },
+            Self {
+                initial_rate: self.rate_at_time_without_clamping(first_duration),
+                initial_decline_rate: self.decline_rate_at_time(first_duration),
+                incremental_duration: second_duration,
+            },
         )
     }
 
+    /// Returns a copy where every rate is multiplied by `factor`, keeping the same decline.
+    pub(crate) fn scale_rates(&self, factor: f64) -> Self {
+        Self {
+            initial_rate: ProductionRate::new(self.initial_rate.value * factor),
+            ..self.clone()
+        }
+    }
+
+    /// Returns a copy of the parameters that ends at the first time the rate falls to the economic
+    /// limit, along with the volume and duration that were removed.
+    pub fn truncate_at_rate(
+        &self,
+        economic_limit_rate: ProductionRate<Time>,
+    ) -> Result<RateTruncation<Self, Time>, DeclineCurveAnalysisError> {
+        let incremental_duration = truncated_duration(
+            self.initial_rate,
+            self.final_rate(),
+            self.incremental_duration,
+            economic_limit_rate,
+            || {
+                Ok(Self::from_final_rate(
+                    self.initial_rate,
+                    self.initial_decline_rate,
+                    economic_limit_rate,
+                )?
+                .incremental_duration)
+            },
+        )?;
+
+        let parameters = Self::from_incremental_duration(
+            self.initial_rate,
+            self.initial_decline_rate,
+            incremental_duration,
+        )?;
+
+        Ok(RateTruncation {
+            truncated_volume: self.incremental_volume() - parameters.incremental_volume(),
+            truncated_duration: Time::from(
+                self.incremental_duration.value() - parameters.incremental_duration.value(),
+            ),
+            parameters,
+        })
+    }
+
     pub fn final_rate(&self) -> ProductionRate<Time> {
         self.rate_at_time_without_clamping(self.incremental_duration)
     }
 
+    /// Returns the first time at which the rate reaches the given rate.
+    ///
+    /// This fails if the rate isn't reached before the end of the segment.
+    pub fn time_at_rate(
+        &self,
+        rate: ProductionRate<Time>,
+    ) -> Result<Time, DeclineCurveAnalysisError> {
+        time_at_rate(
+            self.initial_rate,
+            self.final_rate(),
+            self.incremental_duration,
+            rate,
+            || {
+                Ok(
+                    Self::from_final_rate(self.initial_rate, self.initial_decline_rate, rate)?
+                        .incremental_duration,
+                )
+            },
+        )
+    }
+
     pub fn rate_at_time(&self, time: Time) -> ProductionRate<Time> {
         if time.value() > self.incremental_duration.value() {
             self.final_rate()
@@ -188,4 +383,95 @@ impl<Time: DeclineTimeUnit> HarmonicParameters<Time> {
             self.rate_at_time_without_clamping(time)
         }
     }
+
+    /// Evaluates `rate_at_time` for each time, writing the rates to `out`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `times` and `out` have different lengths.
+    pub fn rates_at_times(&self, times: &[Time], out: &mut [f64]) {
+        evaluate_at_times(times, out, self.incremental_duration, |time| {
+            self.rate_at_time_without_clamping(time).value()
+        });
+    }
+
+    /// Evaluates `incremental_volume_at_time` for each time, writing the volumes to `out`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `times` and `out` have different lengths.
+    pub fn incremental_volumes_at_times(&self, times: &[Time], out: &mut [f64]) {
+        evaluate_at_times(times, out, self.incremental_duration, |time| {
+            self.incremental_volume_at_time_without_clamping(time)
+        });
+    }
+
+    /// Like `rates_at_times`, but with `f32` times and rates, where the times are in the segment's
+    /// time unit. See [`Segment::rates_at_times_f32`](crate::Segment::rates_at_times_f32) for the
+    /// precision.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `times` and `out` have different lengths.
+    pub fn rates_at_times_f32(&self, times: &[f32], out: &mut [f32]) {
+        let initial_rate = self.initial_rate.value as f32;
+        let initial_decline_rate = self.initial_decline_rate.value() as f32;
+        evaluate_at_times_f32(times, out, self.incremental_duration, |time| {
+            kernels::harmonic_rate(initial_rate, initial_decline_rate, time)
+        });
+    }
+
+    /// Like `incremental_volumes_at_times`, but with `f32` times and volumes. See
+    /// [`Segment::rates_at_times_f32`](crate::Segment::rates_at_times_f32) for the precision.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `times` and `out` have different lengths.
+    pub fn incremental_volumes_at_times_f32(&self, times: &[f32], out: &mut [f32]) {
+        let initial_rate = self.initial_rate.value as f32;
+        let initial_decline_rate = self.initial_decline_rate.value() as f32;
+        evaluate_at_times_f32(times, out, self.incremental_duration, |time| {
+            kernels::harmonic_incremental_volume(initial_rate, initial_decline_rate, time)
+        });
+    }
+
+    /// The instantaneous nominal decline rate at the given time, which is `D_i / (1 + D_i * t)`.
+    /// Times past the end of the segment are clamped to the end, like `rate_at_time`.
+    pub fn decline_rate_at_time(&self, time: Time) -> NominalDeclineRate<Time> {
+        let time = time.value().min(self.incremental_duration.value());
+        let initial_decline_rate = self.initial_decline_rate.value();
+        NominalDeclineRate::new(initial_decline_rate / time.mul_add(initial_decline_rate, 1.))
+    }
+
+    /// The derivative of the rate with respect to time, which is `-q_i * D_i / (1 + D_i * t)^2`.
+    /// Times past the end of the segment are clamped to the end, like `decline_rate_at_time`.
+    pub fn rate_derivative_at_time(&self, time: Time) -> f64 {
+        let time = time.value().min(self.incremental_duration.value());
+        let initial_decline_rate = self.initial_decline_rate.value();
+        let denominator = time.mul_add(initial_decline_rate, 1.);
+        -self.initial_rate.value * initial_decline_rate / (denominator * denominator)
+    }
+
+    /// Returns the time at which the instantaneous nominal decline rate reaches the target.
+    ///
+    /// This fails if the target isn't reached within the segment.
+    pub fn time_at_nominal_decline(
+        &self,
+        decline_rate: NominalDeclineRate<Time>,
+    ) -> Result<Time, DeclineCurveAnalysisError> {
+        time_at_nominal_decline(
+            self.initial_decline_rate,
+            self.decline_rate_at_time(self.incremental_duration),
+            self.incremental_duration,
+            decline_rate,
+            || {
+                Ok(Self::from_final_decline_rate(
+                    self.initial_rate,
+                    self.initial_decline_rate,
+                    decline_rate,
+                )?
+                .incremental_duration)
+            },
+        )
+    }
 }
@@ -0,0 +1,230 @@
+use crate::{
+    CalendarDate, CalendarTime, DeclineCurveAnalysisError, DeclineTimeUnit, ProductionRate,
+    days_in_month, validate_positive,
+};
+use alloc::{format, string::String, vec::Vec};
+
+/// What the values of a production history are.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HistoryValue {
+    /// Average daily rates, where each rate holds until the date of the next record.
+    DailyRate,
+    /// Volumes produced in each calendar month, which are converted to average daily rates.
+    MonthlyVolume,
+}
+
+/// How the time of each sample is measured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HistoryTimeAxis {
+    /// The elapsed calendar time since first production, including shut-ins.
+    Calendar,
+    /// The producing time since first production, where periods with zero production are removed
+    /// so that shut-ins don't look like steep declines.
+    Producing,
+}
+
+/// A single dated value of a production history.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProductionRecord {
+    pub date: CalendarDate,
+    pub value: f64,
+}
+
+/// Dated production data that can be converted to samples for fitting.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProductionHistory {
+    kind: HistoryValue,
+    records: Vec<ProductionRecord>,
+}
+
+impl ProductionHistory {
+    /// Creates a history from records in increasing date order, where monthly volumes can only
+    /// have one record for each month. Values must be positive or zero.
+    pub fn new(
+        kind: HistoryValue,
+        records: Vec<ProductionRecord>,
+    ) -> Result<Self, DeclineCurveAnalysisError> {
+        for record in &records {
+            validate_positive(record.value, "production value")?;
+        }
+
+        let is_increasing = records.windows(2).all(|window| match kind {
+            HistoryValue::DailyRate => window[0].date < window[1].date,
+            HistoryValue::MonthlyVolume => {
+                window[0].date.start_of_month() < window[1].date.start_of_month()
+            }
+        });
+        if !is_increasing {
+            return Err(DeclineCurveAnalysisError::NotStrictlyIncreasing {
+                field: "production dates",
+            });
+        }
+
+        Ok(Self { kind, records })
+    }
+
+    /// Parses CSV data with a date column and a value column, e.g., `2024-01-31,3050`.
+    ///
+    /// Dates are `YYYY-MM-DD`, or `YYYY-MM` for monthly volumes. Blank lines are skipped, and the
+    /// first line is skipped as a header if it doesn't start with a date.
+    pub fn parse_csv(input: &str, kind: HistoryValue) -> Result<Self, DeclineCurveAnalysisError> {
+        let mut records = Vec::new();
+        let mut is_first_line = true;
+
+        for (index, line) in input.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let is_header = core::mem::replace(&mut is_first_line, false);
+
+            let invalid = |reason: String| DeclineCurveAnalysisError::ParseFailed {
+                line: index + 1,
+                reason,
+            };
+
+            let fields: Vec<_> = line.split(',').map(str::trim).collect();
+            let &[date, value] = fields.as_slice() else {
+                return Err(invalid(format!(
+                    "expected 2 columns, but got {}",
+                    fields.len()
+                )));
+            };
+
+            let Some(date) = parse_date(date) else {
+                if is_header {
+                    continue;
+                }
+                return Err(invalid(format!("`{date}` is not a date")));
+            };
+            let value = value
+                .parse()
+                .map_err(|_| invalid(format!("`{value}` is not a number")))?;
+
+            records.push(ProductionRecord { date, value });
+        }
+
+        Self::new(kind, records)
+    }
+
+    pub fn kind(&self) -> HistoryValue {
+        self.kind
+    }
+
+    pub fn records(&self) -> &[ProductionRecord] {
+        &self.records
+    }
+
+    /// The date of the first record with production, which is the start of its month for monthly
+    /// volumes.
+    pub fn first_production_date(&self) -> Option<CalendarDate> {
+        self.records
+            .iter()
+            .find(|record| record.value > 0.)
+            .map(|record| self.period_start(record.date))
+    }
+
+    fn period_start(&self, date: CalendarDate) -> CalendarDate {
+        match self.kind {
+            HistoryValue::DailyRate => date,
+            HistoryValue::MonthlyVolume => date.start_of_month(),
+        }
+    }
+
+    /// Converts the history to `(time, rate)` samples for fitting, with time measured from first
+    /// production.
+    ///
+    /// Records with zero production are skipped, since they're shut-ins rather than part of the
+    /// decline, and missing months of monthly volumes are treated the same way. Monthly volumes
+    /// are converted to average daily rates at the middle of each month.
+    pub fn samples<Time: DeclineTimeUnit>(
+        &self,
+        time_axis: HistoryTimeAxis,
+    ) -> Vec<(Time, ProductionRate<Time>)> {
+        self.producing_periods(time_axis)
+            .into_iter()
+            .map(|period| {
+                let time = match self.kind {
+                    HistoryValue::DailyRate => period.start_days,
+                    HistoryValue::MonthlyVolume => period.start_days + 0.5 * period.days,
+                };
+                (CalendarTime { days: time }.to_unit(), period.rate())
+            })
+            .collect()
+    }
+
+    /// The periods with production, in the same order as [`ProductionHistory::samples`].
+    pub(crate) fn producing_periods(&self, time_axis: HistoryTimeAxis) -> Vec<ProducingPeriod> {
+        let Some(first_production_date) = self.first_production_date() else {
+            return Vec::new();
+        };
+
+        let mut periods = Vec::new();
+        let mut producing_days = 0.;
+
+        for (index, record) in self.records.iter().enumerate() {
+            let start = self.period_start(record.date);
+            if start < first_production_date || record.value == 0. {
+                continue;
+            }
+
+            let days = match self.kind {
+                HistoryValue::DailyRate => self.records.get(index + 1).map_or(1., |next| {
+                    CalendarTime::between(record.date, next.date).days
+                }),
+                HistoryValue::MonthlyVolume => days_in_month(start.year(), start.month()).into(),
+            };
+
+            let start_days = match time_axis {
+                HistoryTimeAxis::Calendar => {
+                    CalendarTime::between(first_production_date, start).days
+                }
+                HistoryTimeAxis::Producing => producing_days,
+            };
+            producing_days += days;
+
+            let rate_per_day = match self.kind {
+                HistoryValue::DailyRate => record.value,
+                HistoryValue::MonthlyVolume => record.value / days,
+            };
+
+            periods.push(ProducingPeriod {
+                date: start,
+                start_days,
+                days,
+                rate_per_day,
+            });
+        }
+
+        periods
+    }
+}
+
+/// A period of a history with production, where times are in days since first production.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct ProducingPeriod {
+    pub date: CalendarDate,
+    pub start_days: f64,
+    pub days: f64,
+    pub rate_per_day: f64,
+}
+
+impl ProducingPeriod {
+    pub(crate) fn rate<Time: DeclineTimeUnit>(&self) -> ProductionRate<Time> {
+        ProductionRate::new(self.rate_per_day * Time::LENGTH / CalendarTime::LENGTH)
+    }
+}
+
+/// Parses a `YYYY-MM-DD` or `YYYY-MM` date, where the day defaults to the first of the month.
+fn parse_date(date: &str) -> Option<CalendarDate> {
+    let mut parts = date.split('-');
+    let year = parts.next()?.parse().ok()?;
+    let month = parts.next()?.parse().ok()?;
+    let day = parts.next().map_or(Some(1), |day| day.parse().ok())?;
+
+    if parts.next().is_some() {
+        return None;
+    }
+
+    CalendarDate::new(year, month, day).ok()
+}
@@ -1,6 +1,17 @@
+#[cfg(not(feature = "std"))]
+use crate::math::FloatMath;
 use crate::{
-    DeclineCurveAnalysisError, DeclineRateSignValidation, DeclineTimeUnit, NominalDeclineRate,
-    ProductionRate, approx_gte, is_effectively_zero, validate_decline_rate_sign, validate_duration,
+    DeclineCurveAnalysisError, DeclineRateSignValidation, DeclineTimeUnit, ExponentRange,
+    NominalDeclineRate, ProductionRate, RateTruncation, SecantEffectiveDeclineRate,
+    TangentEffectiveDeclineRate, approx_gte, clamp_to_duration, evaluate_at_times,
+    evaluate_at_times_f32,
+    inverse::{
+        rate_at_incremental_volume, time_at_incremental_volume, time_at_nominal_decline,
+        time_at_rate,
+    },
+    is_effectively_zero,
+    kernels::HyperbolicCoefficients,
+    math, split_duration, truncated_duration, validate_decline_rate_sign, validate_duration,
     validate_finite, validate_incremental_volume, validate_non_zero_decline_rate,
     validate_non_zero_positive_rate,
 };
@@ -11,49 +22,197 @@ use crate::{
 /// errors.
 const MAX_EXPONENT: f64 = 100.;
 
-/// Validates that a hyperbolic exponent is valid.
+/// Validates that a hyperbolic exponent is valid and has the same sign as the initial decline
+/// rate.
 fn validate_hyperbolic_exponent(
-    exponent: f64,
+    exponent: impl IntoHyperbolicExponent,
     initial_decline_rate: f64,
-) -> Result<(), DeclineCurveAnalysisError> {
-    validate_finite(exponent, "exponent")?;
-    if is_effectively_zero(exponent) {
-        return Err(DeclineCurveAnalysisError::InvalidInput {
-            reason: "exponent was approximately zero, so an exponential should be used instead"
-                .to_string(),
-        });
+) -> Result<f64, DeclineCurveAnalysisError> {
+    let exponent = exponent.into_hyperbolic_exponent()?.value();
+
+    if exponent.is_sign_positive() != initial_decline_rate.is_sign_positive() {
+        return Err(DeclineCurveAnalysisError::DeclineRateWrongSign);
     }
 
-    if is_effectively_zero(exponent - 1.) {
-        return Err(DeclineCurveAnalysisError::InvalidInput {
-            reason: "exponent was approximately one, so a harmonic should be used instead"
-                .to_string(),
-        });
+    Ok(exponent)
+}
+
+/// An Arps exponent that a hyperbolic segment can use: a finite number that isn't approximately 0
+/// (an exponential) or 1 (a harmonic), and that's allowed by [`ExponentPolicy::Permissive`].
+///
+/// Hyperbolic constructors accept either this or an `f64`, which is validated the same way, so
+/// an exponent that's validated once can be reused without checking it again.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct HyperbolicExponent(f64);
+
+impl HyperbolicExponent {
+    pub fn new(exponent: f64) -> Result<Self, DeclineCurveAnalysisError> {
+        validate_finite(exponent, "exponent")?;
+        if is_effectively_zero(exponent) || is_effectively_zero(exponent - 1.) {
+            return Err(DeclineCurveAnalysisError::ExponentOutOfRange {
+                value: exponent,
+                allowed: ExponentRange {
+                    minimum: -MAX_EXPONENT,
+                    maximum: MAX_EXPONENT,
+                    excluded: &[0., 1.],
+                },
+            });
+        }
+
+        ExponentPolicy::Permissive.validate(exponent)?;
+
+        Ok(Self(exponent))
     }
 
-    if exponent.abs() > MAX_EXPONENT {
-        return Err(DeclineCurveAnalysisError::ExponentTooLarge);
+    /// Like [`HyperbolicExponent::new`], but the exponent must also be allowed by the policy.
+    pub fn with_policy(
+        exponent: f64,
+        policy: ExponentPolicy,
+    ) -> Result<Self, DeclineCurveAnalysisError> {
+        let exponent = Self::new(exponent)?;
+        policy.validate(exponent.0)?;
+
+        Ok(exponent)
     }
 
-    if exponent.is_sign_positive() != initial_decline_rate.is_sign_positive() {
-        return Err(DeclineCurveAnalysisError::DeclineRateWrongSign);
+    pub fn value(self) -> f64 {
+        self.0
+    }
+}
+
+impl TryFrom<f64> for HyperbolicExponent {
+    type Error = DeclineCurveAnalysisError;
+
+    fn try_from(exponent: f64) -> Result<Self, Self::Error> {
+        Self::new(exponent)
+    }
+}
+
+impl From<HyperbolicExponent> for f64 {
+    fn from(exponent: HyperbolicExponent) -> Self {
+        exponent.0
+    }
+}
+
+/// Values that hyperbolic constructors accept as an exponent.
+pub trait IntoHyperbolicExponent {
+    fn into_hyperbolic_exponent(self) -> Result<HyperbolicExponent, DeclineCurveAnalysisError>;
+}
+
+impl IntoHyperbolicExponent for f64 {
+    fn into_hyperbolic_exponent(self) -> Result<HyperbolicExponent, DeclineCurveAnalysisError> {
+        HyperbolicExponent::new(self)
     }
+}
+
+impl IntoHyperbolicExponent for HyperbolicExponent {
+    fn into_hyperbolic_exponent(self) -> Result<HyperbolicExponent, DeclineCurveAnalysisError> {
+        Ok(self)
+    }
+}
+
+/// A policy for the range of hyperbolic exponents that are accepted.
+///
+/// Hyperbolic segments can always be created with any exponent that the equations support, which
+/// includes exponents greater than one (e.g., for transient flow) and negative exponents. A
+/// stricter policy can be checked with [`HyperbolicParameters::validate_exponent`] or
+/// [`Forecast::validate_exponents`](crate::Forecast::validate_exponents), e.g., to follow SPEE
+/// guidelines.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ExponentPolicy {
+    /// Accepts exponents between -100 and 100.
+    #[default]
+    Permissive,
+    /// Accepts exponents greater than zero and at most `maximum`, which is typically one for
+    /// boundary-dominated flow, or two when early transient flow is included.
+    Strict { maximum: f64 },
+}
+
+impl ExponentPolicy {
+    /// Validates that an exponent is allowed by the policy.
+    pub fn validate(&self, exponent: f64) -> Result<(), DeclineCurveAnalysisError> {
+        validate_finite(exponent, "exponent")?;
+
+        let (allowed, is_allowed) = match *self {
+            Self::Permissive => (
+                ExponentRange {
+                    minimum: -MAX_EXPONENT,
+                    maximum: MAX_EXPONENT,
+                    excluded: &[],
+                },
+                exponent.abs() <= MAX_EXPONENT,
+            ),
+            Self::Strict { maximum } => {
+                validate_non_zero_positive_rate(maximum, "maximum exponent")?;
+                (
+                    ExponentRange {
+                        minimum: 0.,
+                        maximum,
+                        excluded: &[],
+                    },
+                    exponent > 0. && exponent <= maximum,
+                )
+            }
+        };
 
-    Ok(())
+        if !is_allowed {
+            return Err(DeclineCurveAnalysisError::ExponentOutOfRange {
+                value: exponent,
+                allowed,
+            });
+        }
+        Ok(())
+    }
 }
 
 /// A hyperbolic decline segment.
 ///
 /// This is derived from the Arps equation when the exponent is not equal to 0 or 1.
-#[derive(Debug, Clone, PartialEq)]
+///
+/// With the `fast-math` feature, rates and volumes are evaluated with an approximation of `powf`
+/// that has a relative error of at most 1e-7, e.g., for Monte Carlo simulations of large
+/// portfolios.
+#[derive(Clone, PartialEq)]
 pub struct HyperbolicParameters<Time: DeclineTimeUnit> {
     initial_rate: ProductionRate<Time>,
     initial_decline_rate: NominalDeclineRate<Time>,
     incremental_duration: Time,
     exponent: f64,
+    coefficients: HyperbolicCoefficients<f64>,
+}
+
+// The coefficients are derived from the other fields, so they're left out.
+impl<Time: DeclineTimeUnit> core::fmt::Debug for HyperbolicParameters<Time> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("HyperbolicParameters")
+            .field("initial_rate", &self.initial_rate)
+            .field("initial_decline_rate", &self.initial_decline_rate)
+            .field("incremental_duration", &self.incremental_duration)
+            .field("exponent", &self.exponent)
+            .finish()
+    }
 }
 
 impl<Time: DeclineTimeUnit> HyperbolicParameters<Time> {
+    fn new(
+        initial_rate: ProductionRate<Time>,
+        initial_decline_rate: NominalDeclineRate<Time>,
+        incremental_duration: Time,
+        exponent: f64,
+    ) -> Self {
+        Self {
+            initial_rate,
+            initial_decline_rate,
+            incremental_duration,
+            exponent,
+            coefficients: HyperbolicCoefficients::new(
+                initial_rate.value,
+                initial_decline_rate.value(),
+                exponent,
+            ),
+        }
+    }
+
     pub fn initial_rate(&self) -> ProductionRate<Time> {
         self.initial_rate
     }
@@ -70,39 +229,78 @@ impl<Time: DeclineTimeUnit> HyperbolicParameters<Time> {
         self.exponent
     }
 
+    /// The initial decline rate as a secant effective decline rate, converted using the exponent
+    /// of the segment.
+    pub fn initial_secant_effective(
+        &self,
+    ) -> Result<SecantEffectiveDeclineRate<Time>, DeclineCurveAnalysisError> {
+        self.initial_decline_rate.to_secant_effective(self.exponent)
+    }
+
+    /// The decline rate at the end of the segment as a secant effective decline rate, converted
+    /// using the exponent of the segment.
+    pub fn final_secant_effective(
+        &self,
+    ) -> Result<SecantEffectiveDeclineRate<Time>, DeclineCurveAnalysisError> {
+        self.decline_rate_at_time(self.incremental_duration)
+            .to_secant_effective(self.exponent)
+    }
+
+    pub fn initial_tangent_effective(
+        &self,
+    ) -> Result<TangentEffectiveDeclineRate<Time>, DeclineCurveAnalysisError> {
+        self.initial_decline_rate.to_tangent_effective()
+    }
+
+    /// The decline rate at the end of the segment as a tangent effective decline rate.
+    pub fn final_tangent_effective(
+        &self,
+    ) -> Result<TangentEffectiveDeclineRate<Time>, DeclineCurveAnalysisError> {
+        self.decline_rate_at_time(self.incremental_duration)
+            .to_tangent_effective()
+    }
+
+    /// Validates that the exponent is allowed by the policy.
+    pub fn validate_exponent(
+        &self,
+        policy: ExponentPolicy,
+    ) -> Result<(), DeclineCurveAnalysisError> {
+        policy.validate(self.exponent)
+    }
+
     pub fn from_incremental_duration(
         initial_rate: ProductionRate<Time>,
         initial_decline_rate: NominalDeclineRate<Time>,
         incremental_duration: Time,
-        exponent: f64,
+        exponent: impl IntoHyperbolicExponent,
     ) -> Result<Self, DeclineCurveAnalysisError> {
         let initial_decline_rate_value = initial_decline_rate.value();
 
         validate_non_zero_positive_rate(initial_rate.value, "initial rate")?;
         validate_non_zero_decline_rate(initial_decline_rate_value, "initial decline rate")?;
         validate_duration(incremental_duration)?;
-        validate_hyperbolic_exponent(exponent, initial_decline_rate_value)?;
+        let exponent = validate_hyperbolic_exponent(exponent, initial_decline_rate_value)?;
 
-        Ok(Self {
+        Ok(Self::new(
             initial_rate,
             initial_decline_rate,
             incremental_duration,
             exponent,
-        })
+        ))
     }
 
     pub fn from_incremental_volume(
         initial_rate: ProductionRate<Time>,
         initial_decline_rate: NominalDeclineRate<Time>,
         incremental_volume: f64,
-        exponent: f64,
+        exponent: impl IntoHyperbolicExponent,
     ) -> Result<Self, DeclineCurveAnalysisError> {
         let initial_decline_rate_value = initial_decline_rate.value();
 
         validate_non_zero_positive_rate(initial_rate.value, "initial rate")?;
         validate_non_zero_decline_rate(initial_decline_rate_value, "initial decline rate")?;
         validate_incremental_volume(incremental_volume)?;
-        validate_hyperbolic_exponent(exponent, initial_decline_rate_value)?;
+        let exponent = validate_hyperbolic_exponent(exponent, initial_decline_rate_value)?;
 
         let one_minus_exponent = 1. - exponent;
 
@@ -127,22 +325,22 @@ impl<Time: DeclineTimeUnit> HyperbolicParameters<Time> {
                 / initial_rate.value;
         let duration_denom = exponent * initial_decline_rate_value;
         let incremental_duration =
-            Time::from((base.powf(-exponent / one_minus_exponent) - 1.) / duration_denom);
+            Time::from((math::powf(base, -exponent / one_minus_exponent) - 1.) / duration_denom);
         validate_duration(incremental_duration)?;
 
-        Ok(Self {
+        Ok(Self::new(
             initial_rate,
             initial_decline_rate,
             incremental_duration,
             exponent,
-        })
+        ))
     }
 
     pub fn from_final_decline_rate(
         initial_rate: ProductionRate<Time>,
         initial_decline_rate: NominalDeclineRate<Time>,
         final_decline_rate: NominalDeclineRate<Time>,
-        exponent: f64,
+        exponent: impl IntoHyperbolicExponent,
     ) -> Result<Self, DeclineCurveAnalysisError> {
         let initial_decline_rate_value = initial_decline_rate.value();
         let final_decline_rate_value = final_decline_rate.value();
@@ -150,7 +348,7 @@ impl<Time: DeclineTimeUnit> HyperbolicParameters<Time> {
         validate_non_zero_positive_rate(initial_rate.value, "initial rate")?;
         validate_non_zero_decline_rate(initial_decline_rate_value, "initial decline rate")?;
         validate_non_zero_decline_rate(final_decline_rate_value, "final decline rate")?;
-        validate_hyperbolic_exponent(exponent, initial_decline_rate_value)?;
+        let exponent = validate_hyperbolic_exponent(exponent, initial_decline_rate_value)?;
 
         if initial_decline_rate_value.is_sign_positive()
             != final_decline_rate_value.is_sign_positive()
@@ -172,26 +370,26 @@ impl<Time: DeclineTimeUnit> HyperbolicParameters<Time> {
         );
         validate_duration(incremental_duration)?;
 
-        Ok(Self {
+        Ok(Self::new(
             initial_rate,
             initial_decline_rate,
             incremental_duration,
             exponent,
-        })
+        ))
     }
 
     pub fn from_final_rate(
         initial_rate: ProductionRate<Time>,
         initial_decline_rate: NominalDeclineRate<Time>,
         final_rate: ProductionRate<Time>,
-        exponent: f64,
+        exponent: impl IntoHyperbolicExponent,
     ) -> Result<Self, DeclineCurveAnalysisError> {
         let initial_decline_rate_value = initial_decline_rate.value();
 
         validate_non_zero_positive_rate(initial_rate.value, "initial rate")?;
         validate_non_zero_decline_rate(initial_decline_rate_value, "initial decline rate")?;
         validate_non_zero_positive_rate(final_rate.value, "final rate")?;
-        validate_hyperbolic_exponent(exponent, initial_decline_rate_value)?;
+        let exponent = validate_hyperbolic_exponent(exponent, initial_decline_rate_value)?;
 
         match validate_decline_rate_sign(
             initial_decline_rate_value,
@@ -200,51 +398,95 @@ impl<Time: DeclineTimeUnit> HyperbolicParameters<Time> {
         )? {
             DeclineRateSignValidation::Continue => {}
             DeclineRateSignValidation::ZeroDuration => {
-                return Ok(Self {
+                return Ok(Self::new(
                     initial_rate,
                     initial_decline_rate,
-                    incremental_duration: Time::from(0.),
+                    Time::from(0.),
                     exponent,
-                });
+                ));
             }
         }
 
         let incremental_duration = Time::from(
-            ((initial_rate.value / final_rate.value).powf(exponent) - 1.0)
+            (math::powf(initial_rate.value / final_rate.value, exponent) - 1.0)
                 / (exponent * initial_decline_rate_value),
         );
         validate_duration(incremental_duration)?;
 
-        Ok(Self {
+        Ok(Self::new(
             initial_rate,
             initial_decline_rate,
             incremental_duration,
             exponent,
-        })
+        ))
     }
 
-    fn incremental_volume_at_time_without_clamping(&self, time: Time) -> f64 {
-        let initial_decline_rate_value = self.initial_decline_rate.value();
-
-        let factor_denom = self
-            .initial_decline_rate
-            .value()
-            .mul_add(-self.exponent, initial_decline_rate_value);
-
-        // `q_i / (a_i * (1 - b))`
-        let factor = self.initial_rate.value() / factor_denom;
+    /// Like the nominal constructor, but the decline rate is a secant effective decline rate that
+    /// is converted using the exponent of the segment.
+    pub fn from_secant_effective_incremental_duration(
+        initial_rate: ProductionRate<Time>,
+        initial_decline_rate: SecantEffectiveDeclineRate<Time>,
+        incremental_duration: Time,
+        exponent: impl IntoHyperbolicExponent,
+    ) -> Result<Self, DeclineCurveAnalysisError> {
+        let exponent = exponent.into_hyperbolic_exponent()?;
+        Self::from_incremental_duration(
+            initial_rate,
+            initial_decline_rate.to_nominal(exponent)?,
+            incremental_duration,
+            exponent,
+        )
+    }
 
-        // `1 - 1 / b`
-        let power = 1. - 1. / self.exponent;
+    /// Like the nominal constructor, but the decline rate is a secant effective decline rate that
+    /// is converted using the exponent of the segment.
+    pub fn from_secant_effective_final_rate(
+        initial_rate: ProductionRate<Time>,
+        initial_decline_rate: SecantEffectiveDeclineRate<Time>,
+        final_rate: ProductionRate<Time>,
+        exponent: impl IntoHyperbolicExponent,
+    ) -> Result<Self, DeclineCurveAnalysisError> {
+        let exponent = exponent.into_hyperbolic_exponent()?;
+        Self::from_final_rate(
+            initial_rate,
+            initial_decline_rate.to_nominal(exponent)?,
+            final_rate,
+            exponent,
+        )
+    }
 
-        // `b * a_i`
-        let exponent_times_initial_decline_rate = self.exponent * initial_decline_rate_value;
+    /// Like the nominal constructor, but the decline rate is a tangent effective decline rate.
+    pub fn from_tangent_effective_incremental_duration(
+        initial_rate: ProductionRate<Time>,
+        initial_decline_rate: TangentEffectiveDeclineRate<Time>,
+        incremental_duration: Time,
+        exponent: impl IntoHyperbolicExponent,
+    ) -> Result<Self, DeclineCurveAnalysisError> {
+        Self::from_incremental_duration(
+            initial_rate,
+            initial_decline_rate.to_nominal()?,
+            incremental_duration,
+            exponent,
+        )
+    }
 
-        let base = time
-            .value()
-            .mul_add(exponent_times_initial_decline_rate, 1.);
+    /// Like the nominal constructor, but the decline rate is a tangent effective decline rate.
+    pub fn from_tangent_effective_final_rate(
+        initial_rate: ProductionRate<Time>,
+        initial_decline_rate: TangentEffectiveDeclineRate<Time>,
+        final_rate: ProductionRate<Time>,
+        exponent: impl IntoHyperbolicExponent,
+    ) -> Result<Self, DeclineCurveAnalysisError> {
+        Self::from_final_rate(
+            initial_rate,
+            initial_decline_rate.to_nominal()?,
+            final_rate,
+            exponent,
+        )
+    }
 
-        base.powf(power).mul_add(-factor, factor)
+    fn incremental_volume_at_time_without_clamping(&self, time: Time) -> f64 {
+        self.coefficients.incremental_volume(time.value())
     }
 
     pub fn incremental_volume_at_time(&self, time: Time) -> f64 {
@@ -255,24 +497,166 @@ impl<Time: DeclineTimeUnit> HyperbolicParameters<Time> {
         }
     }
 
+    /// The volume produced between two times, which are clamped to be within the segment. This is
+    /// negative if `end` is before `start`.
+    pub fn incremental_volume_between(&self, start: Time, end: Time) -> f64 {
+        let start = clamp_to_duration(start, self.incremental_duration);
+        let end = clamp_to_duration(end, self.incremental_duration);
+
+        self.incremental_volume_at_time_without_clamping(end)
+            - self.incremental_volume_at_time_without_clamping(start)
+    }
+
+    /// Returns the time at which the cumulative volume reaches the given volume, which is the
+    /// inverse of `incremental_volume_at_time`.
+    ///
+    /// This fails if the volume isn't reached before the end of the segment.
+    pub fn time_at_incremental_volume(
+        &self,
+        incremental_volume: f64,
+    ) -> Result<Time, DeclineCurveAnalysisError> {
+        time_at_incremental_volume(
+            self.incremental_volume(),
+            self.incremental_duration,
+            incremental_volume,
+            || {
+                Ok(Self::from_incremental_volume(
+                    self.initial_rate,
+                    self.initial_decline_rate,
+                    incremental_volume,
+                    self.exponent,
+                )?
+                .incremental_duration)
+            },
+        )
+    }
+
+    /// Returns the rate at the time the cumulative volume reaches the given volume, for
+    /// rate-cumulative analysis.
+    ///
+    /// This fails if the volume isn't reached before the end of the segment.
+    pub fn rate_at_incremental_volume(
+        &self,
+        incremental_volume: f64,
+    ) -> Result<ProductionRate<Time>, DeclineCurveAnalysisError> {
+        // The volume is `q_i^b / ((1 - b) * D_i) * (q_i^(1 - b) - q^(1 - b))`.
+        rate_at_incremental_volume(self.incremental_volume(), incremental_volume, |volume| {
+            let complement = 1. - self.exponent;
+            let base = (-complement * self.initial_decline_rate.value() / self.initial_rate.value)
+                .mul_add(volume, 1.)
+                .max(0.);
+            Ok(self.initial_rate.value * math::powf(base, complement.recip()))
+        })
+    }
+
     pub fn incremental_volume(&self) -> f64 {
         self.incremental_volume_at_time_without_clamping(self.incremental_duration)
     }
 
     fn rate_at_time_without_clamping(&self, time: Time) -> ProductionRate<Time> {
         ProductionRate::new(
-            self.initial_rate.value
-                / (time
-                    .value()
-                    .mul_add(self.exponent * self.initial_decline_rate.value(), 1.))
-                .powf(1. / self.exponent),
+            self.coefficients
+                .rate(self.initial_rate.value, time.value()),
+        )
+    }
+
+    /// Splits the segment at `time` into two segments that together reproduce the original. The
+    /// second segment starts at the rate and instantaneous decline rate at the split, with the
+    /// same exponent.
+    pub fn split_at(&self, time: Time) -> (Self, Self) {
+        let (first_duration, second_duration) = split_duration(time, self.incremental_duration);
+        (
+            Self::new(
+                self.initial_rate,
+                self.initial_decline_rate,
+                first_duration,
+                self.exponent,
+            ),
+            Self::new(
+                self.rate_at_time_without_clamping(first_duration),
+                self.decline_rate_at_time(first_duration),
+                second_duration,
+                self.exponent,
+            ),
+        )
+    }
+
+    /// Returns a copy where every rate is multiplied by `factor`, keeping the same decline.
+    pub(crate) fn scale_rates(&self, factor: f64) -> Self {
+        Self::new(
+            ProductionRate::new(self.initial_rate.value * factor),
+            self.initial_decline_rate,
+            self.incremental_duration,
+            self.exponent,
         )
     }
 
+    /// Returns a copy of the parameters that ends at the first time the rate falls to the economic
+    /// limit, along with the volume and duration that were removed.
+    pub fn truncate_at_rate(
+        &self,
+        economic_limit_rate: ProductionRate<Time>,
+    ) -> Result<RateTruncation<Self, Time>, DeclineCurveAnalysisError> {
+        let incremental_duration = truncated_duration(
+            self.initial_rate,
+            self.final_rate(),
+            self.incremental_duration,
+            economic_limit_rate,
+            || {
+                Ok(Self::from_final_rate(
+                    self.initial_rate,
+                    self.initial_decline_rate,
+                    economic_limit_rate,
+                    self.exponent,
+                )?
+                .incremental_duration)
+            },
+        )?;
+
+        let parameters = Self::from_incremental_duration(
+            self.initial_rate,
+            self.initial_decline_rate,
+            incremental_duration,
+            self.exponent,
+        )?;
+
+        Ok(RateTruncation {
+            truncated_volume: self.incremental_volume() - parameters.incremental_volume(),
+            truncated_duration: Time::from(
+                self.incremental_duration.value() - parameters.incremental_duration.value(),
+            ),
+            parameters,
+        })
+    }
+
     pub fn final_rate(&self) -> ProductionRate<Time> {
         self.rate_at_time_without_clamping(self.incremental_duration)
     }
 
+    /// Returns the first time at which the rate reaches the given rate.
+    ///
+    /// This fails if the rate isn't reached before the end of the segment.
+    pub fn time_at_rate(
+        &self,
+        rate: ProductionRate<Time>,
+    ) -> Result<Time, DeclineCurveAnalysisError> {
+        time_at_rate(
+            self.initial_rate,
+            self.final_rate(),
+            self.incremental_duration,
+            rate,
+            || {
+                Ok(Self::from_final_rate(
+                    self.initial_rate,
+                    self.initial_decline_rate,
+                    rate,
+                    self.exponent,
+                )?
+                .incremental_duration)
+            },
+        )
+    }
+
     pub fn rate_at_time(&self, time: Time) -> ProductionRate<Time> {
         if time.value() > self.incremental_duration.value() {
             self.final_rate()
@@ -280,4 +664,105 @@ impl<Time: DeclineTimeUnit> HyperbolicParameters<Time> {
             self.rate_at_time_without_clamping(time)
         }
     }
+
+    /// Evaluates `rate_at_time` for each time, writing the rates to `out`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `times` and `out` have different lengths.
+    pub fn rates_at_times(&self, times: &[Time], out: &mut [f64]) {
+        evaluate_at_times(times, out, self.incremental_duration, |time| {
+            self.rate_at_time_without_clamping(time).value()
+        });
+    }
+
+    /// Evaluates `incremental_volume_at_time` for each time, writing the volumes to `out`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `times` and `out` have different lengths.
+    pub fn incremental_volumes_at_times(&self, times: &[Time], out: &mut [f64]) {
+        evaluate_at_times(times, out, self.incremental_duration, |time| {
+            self.incremental_volume_at_time_without_clamping(time)
+        });
+    }
+
+    /// Like `rates_at_times`, but with `f32` times and rates, where the times are in the segment's
+    /// time unit. See [`Segment::rates_at_times_f32`](crate::Segment::rates_at_times_f32) for the
+    /// precision.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `times` and `out` have different lengths.
+    pub fn rates_at_times_f32(&self, times: &[f32], out: &mut [f32]) {
+        let initial_rate = self.initial_rate.value as f32;
+        let coefficients = self.coefficients.to_f32();
+        evaluate_at_times_f32(times, out, self.incremental_duration, |time| {
+            coefficients.rate(initial_rate, time)
+        });
+    }
+
+    /// Like `incremental_volumes_at_times`, but with `f32` times and volumes. See
+    /// [`Segment::rates_at_times_f32`](crate::Segment::rates_at_times_f32) for the precision.
+    ///
+    /// The volume is the difference of two nearly equal terms early in the segment and when the
+    /// exponent is close to one, which amplifies the error of `f32`. Over 50 years of daily times,
+    /// the relative error is about 1e-7 at the end of the segment for most exponents, but it grows
+    /// to about 5e-6 for an exponent of 0.99, and it can reach 1e-4 in the first few days. Use
+    /// `incremental_volumes_at_times` if the volumes are differenced or summed over many segments.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `times` and `out` have different lengths.
+    pub fn incremental_volumes_at_times_f32(&self, times: &[f32], out: &mut [f32]) {
+        let coefficients = self.coefficients.to_f32();
+        evaluate_at_times_f32(times, out, self.incremental_duration, |time| {
+            coefficients.incremental_volume(time)
+        });
+    }
+
+    /// The instantaneous nominal decline rate at the given time, which is
+    /// `D_i / (1 + b * D_i * t)`. Times past the end of the segment are clamped to the end, like
+    /// `rate_at_time`.
+    pub fn decline_rate_at_time(&self, time: Time) -> NominalDeclineRate<Time> {
+        let time = time.value().min(self.incremental_duration.value());
+        let initial_decline_rate = self.initial_decline_rate.value();
+        NominalDeclineRate::new(
+            initial_decline_rate
+                / time.mul_add(self.coefficients.exponent_times_initial_decline_rate, 1.),
+        )
+    }
+
+    /// The derivative of the rate with respect to time, which is
+    /// `-D_i * q(t) / (1 + b * D_i * t)`. Times past the end of the segment are clamped to the end,
+    /// like `decline_rate_at_time`.
+    pub fn rate_derivative_at_time(&self, time: Time) -> f64 {
+        let time = Time::from(time.value().min(self.incremental_duration.value()));
+        -self.decline_rate_at_time(time).value() * self.rate_at_time_without_clamping(time).value
+    }
+
+    /// Returns the time at which the instantaneous nominal decline rate reaches the target, e.g.,
+    /// to find where a modified hyperbolic switches to its terminal exponential decline.
+    ///
+    /// This fails if the target isn't reached within the segment.
+    pub fn time_at_nominal_decline(
+        &self,
+        decline_rate: NominalDeclineRate<Time>,
+    ) -> Result<Time, DeclineCurveAnalysisError> {
+        time_at_nominal_decline(
+            self.initial_decline_rate,
+            self.decline_rate_at_time(self.incremental_duration),
+            self.incremental_duration,
+            decline_rate,
+            || {
+                Ok(Self::from_final_decline_rate(
+                    self.initial_rate,
+                    self.initial_decline_rate,
+                    decline_rate,
+                    self.exponent,
+                )?
+                .incremental_duration)
+            },
+        )
+    }
 }
@@ -0,0 +1,3 @@
+//! Conversions to and from the formats used by other decline curve software.
+
+pub mod aries;
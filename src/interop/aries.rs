@@ -0,0 +1,372 @@
+//! Import and export of ARIES-style decline keywords.
+//!
+//! Each decline line has the form `PHASE QI QF UNITS LIFE LIFE_UNITS METHOD DECLINE`, e.g.,
+//! `OIL 1000 X B/D 5 YRS B/1.2 70` for a five year hyperbolic with an exponent of 1.2 and a secant
+//! effective decline of 70% per year. The supported values are:
+//!
+//! - `PHASE`: `OIL`, `GAS`, or `WTR`.
+//! - `QI`: the initial rate, or `X` to continue from the final rate of the previous line for the
+//!   same phase.
+//! - `QF`: the final rate, or `X` if the life is given instead.
+//! - `UNITS`: `B/D` or `B/M` for oil and water, and `M/D` or `M/M` for gas, i.e., volumes per day
+//!   or per month.
+//! - `LIFE` and `LIFE_UNITS`: the duration in `YRS`, `MOS`, or `DAYS`, or `X` if the final rate is
+//!   given instead.
+//! - `METHOD`: `EXP`, `HAR`, `FLAT`, or `B/<exponent>` for hyperbolic declines.
+//! - `DECLINE`: the decline in percent per year, which is the effective decline for exponentials
+//!   and the secant effective decline for harmonics and hyperbolics. This is `0` for flat lines.
+//!
+//! A flat line with a zero rate is a delay.
+
+use crate::{
+    AverageDaysTime, AverageMonthsTime, AverageYearsTime, DeclineCurveAnalysisError,
+    DeclineTimeUnit, DelayParameters, ExponentialParameters, FlatParameters, Forecast,
+    HarmonicParameters, HyperbolicParameters, NominalDeclineRate, Phase, ProductionRate,
+    SecantEffectiveDeclineRate, Segment,
+};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// The time unit of the rates in ARIES decline lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AriesRateUnit {
+    /// Volumes per day, e.g., `B/D`.
+    PerDay,
+    /// Volumes per month, e.g., `B/M`.
+    PerMonth,
+}
+
+impl AriesRateUnit {
+    fn keyword(self, phase: Phase) -> &'static str {
+        match (phase, self) {
+            (Phase::Oil | Phase::Water, Self::PerDay) => "B/D",
+            (Phase::Oil | Phase::Water, Self::PerMonth) => "B/M",
+            (Phase::Gas, Self::PerDay) => "M/D",
+            (Phase::Gas, Self::PerMonth) => "M/M",
+        }
+    }
+
+    fn from_keyword(keyword: &str) -> Option<Self> {
+        match keyword {
+            "B/D" | "M/D" => Some(Self::PerDay),
+            "B/M" | "M/M" => Some(Self::PerMonth),
+            _ => None,
+        }
+    }
+
+    /// The number of days in the time unit.
+    fn length(self) -> f64 {
+        match self {
+            Self::PerDay => AverageDaysTime::LENGTH,
+            Self::PerMonth => AverageMonthsTime::LENGTH,
+        }
+    }
+
+    fn rate<Time: DeclineTimeUnit>(self, value: f64) -> ProductionRate<Time> {
+        ProductionRate::new(value * Time::LENGTH / self.length())
+    }
+
+    fn rate_value<Time: DeclineTimeUnit>(self, rate: ProductionRate<Time>) -> f64 {
+        rate.value() * self.length() / Time::LENGTH
+    }
+}
+
+fn phase_keyword(phase: Phase) -> &'static str {
+    match phase {
+        Phase::Oil => "OIL",
+        Phase::Gas => "GAS",
+        Phase::Water => "WTR",
+    }
+}
+
+fn phase_from_keyword(keyword: &str) -> Option<Phase> {
+    match keyword {
+        "OIL" => Some(Phase::Oil),
+        "GAS" => Some(Phase::Gas),
+        "WTR" => Some(Phase::Water),
+        _ => None,
+    }
+}
+
+/// How a line declines, which determines the exponent used for the secant effective decline.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Method {
+    Exponential,
+    Harmonic,
+    Hyperbolic(f64),
+    Flat,
+}
+
+impl Method {
+    fn parse(keyword: &str) -> Option<Self> {
+        match keyword {
+            "EXP" => Some(Self::Exponential),
+            "HAR" => Some(Self::Harmonic),
+            "FLAT" => Some(Self::Flat),
+            _ => keyword
+                .strip_prefix("B/")
+                .and_then(|exponent| exponent.parse().ok())
+                .map(Self::Hyperbolic),
+        }
+    }
+}
+
+/// The end of a line, which is either given by its life or by its final rate.
+enum LineEnd<Time: DeclineTimeUnit> {
+    Life(Time),
+    FinalRate(ProductionRate<Time>),
+}
+
+/// Parses ARIES decline lines into a forecast for each phase, in the order each phase first
+/// appears.
+///
+/// Blank lines and lines that start with other keywords (e.g., `START`) are skipped.
+pub fn parse_forecasts<Time: DeclineTimeUnit>(
+    input: &str,
+) -> Result<Vec<(Phase, Forecast<Time>)>, DeclineCurveAnalysisError> {
+    let mut phases: Vec<(Phase, Vec<Segment<Time>>)> = Vec::new();
+
+    for (index, line) in input.lines().enumerate() {
+        let tokens: Vec<_> = line.split_whitespace().collect();
+        let Some(phase) = tokens.first().copied().and_then(phase_from_keyword) else {
+            continue;
+        };
+
+        let segments = match phases.iter().position(|(existing, _)| *existing == phase) {
+            Some(position) => &mut phases[position].1,
+            None => {
+                phases.push((phase, Vec::new()));
+                &mut phases.last_mut().expect("phase was just added").1
+            }
+        };
+
+        let segment = parse_line(&tokens, segments.last()).map_err(|error| {
+            DeclineCurveAnalysisError::ParseFailed {
+                line: index + 1,
+                reason: error.to_string(),
+            }
+        })?;
+        segments.push(segment);
+    }
+
+    Ok(phases
+        .into_iter()
+        .map(|(phase, segments)| (phase, Forecast::new(segments)))
+        .collect())
+}
+
+fn invalid(reason: String) -> DeclineCurveAnalysisError {
+    DeclineCurveAnalysisError::InvalidInput { reason }
+}
+
+fn parse_number(token: &str, name: &str) -> Result<Option<f64>, DeclineCurveAnalysisError> {
+    if token == "X" {
+        return Ok(None);
+    }
+
+    token
+        .parse()
+        .map(Some)
+        .map_err(|_| invalid(format!("{name} `{token}` is not a number or `X`")))
+}
+
+fn parse_line<Time: DeclineTimeUnit>(
+    tokens: &[&str],
+    previous: Option<&Segment<Time>>,
+) -> Result<Segment<Time>, DeclineCurveAnalysisError> {
+    let &[
+        _,
+        initial_rate,
+        final_rate,
+        rate_unit,
+        life,
+        life_unit,
+        method,
+        decline,
+    ] = tokens
+    else {
+        return Err(invalid(format!(
+            "expected 8 values, but got {}",
+            tokens.len()
+        )));
+    };
+
+    let rate_unit = AriesRateUnit::from_keyword(rate_unit)
+        .ok_or_else(|| invalid(format!("unsupported rate units `{rate_unit}`")))?;
+    let method =
+        Method::parse(method).ok_or_else(|| invalid(format!("unsupported method `{method}`")))?;
+
+    let initial_rate = match parse_number(initial_rate, "initial rate")? {
+        Some(initial_rate) => rate_unit.rate(initial_rate),
+        None => previous
+            .map(Segment::final_rate)
+            .ok_or_else(|| invalid("initial rate is `X`, but there's no previous line".into()))?,
+    };
+
+    let end = match (
+        parse_number(final_rate, "final rate")?,
+        parse_number(life, "life")?,
+    ) {
+        (None, Some(life)) => LineEnd::Life(parse_life(life, life_unit)?),
+        (Some(final_rate), None) => LineEnd::FinalRate(rate_unit.rate(final_rate)),
+        _ => {
+            return Err(invalid(
+                "expected exactly one of the final rate and life to be `X`".into(),
+            ));
+        }
+    };
+
+    let decline = parse_number(decline, "decline")?
+        .ok_or_else(|| invalid("decline is `X`, but expected a number".into()))?;
+
+    match (method, end) {
+        (Method::Flat, LineEnd::Life(life)) if initial_rate.value() == 0. => {
+            Ok(DelayParameters::from_incremental_duration(life)?.into())
+        }
+        (Method::Flat, LineEnd::Life(life)) => {
+            Ok(FlatParameters::from_incremental_duration(initial_rate, life)?.into())
+        }
+        (Method::Flat, LineEnd::FinalRate(_)) => Err(invalid(
+            "flat lines need a life instead of a final rate".into(),
+        )),
+        (Method::Exponential, end) => {
+            let decline_rate = nominal_decline_rate(decline, 0.)?;
+            Ok(match end {
+                LineEnd::Life(life) => ExponentialParameters::from_incremental_duration(
+                    initial_rate,
+                    decline_rate,
+                    life,
+                )?,
+                LineEnd::FinalRate(final_rate) => {
+                    ExponentialParameters::from_final_rate(initial_rate, decline_rate, final_rate)?
+                }
+            }
+            .into())
+        }
+        (Method::Harmonic, end) => {
+            let decline_rate = nominal_decline_rate(decline, 1.)?;
+            Ok(match end {
+                LineEnd::Life(life) => {
+                    HarmonicParameters::from_incremental_duration(initial_rate, decline_rate, life)?
+                }
+                LineEnd::FinalRate(final_rate) => {
+                    HarmonicParameters::from_final_rate(initial_rate, decline_rate, final_rate)?
+                }
+            }
+            .into())
+        }
+        (Method::Hyperbolic(exponent), end) => {
+            let decline_rate = nominal_decline_rate(decline, exponent)?;
+            Ok(match end {
+                LineEnd::Life(life) => HyperbolicParameters::from_incremental_duration(
+                    initial_rate,
+                    decline_rate,
+                    life,
+                    exponent,
+                )?,
+                LineEnd::FinalRate(final_rate) => HyperbolicParameters::from_final_rate(
+                    initial_rate,
+                    decline_rate,
+                    final_rate,
+                    exponent,
+                )?,
+            }
+            .into())
+        }
+    }
+}
+
+fn parse_life<Time: DeclineTimeUnit>(
+    life: f64,
+    life_unit: &str,
+) -> Result<Time, DeclineCurveAnalysisError> {
+    match life_unit {
+        "YRS" => Ok(AverageYearsTime { years: life }.to_unit()),
+        "MOS" => Ok(AverageMonthsTime { months: life }.to_unit()),
+        "DAYS" => Ok(AverageDaysTime { days: life }.to_unit()),
+        _ => Err(invalid(format!("unsupported life units `{life_unit}`"))),
+    }
+}
+
+/// Converts a secant effective decline in percent per year to a nominal decline rate.
+fn nominal_decline_rate<Time: DeclineTimeUnit>(
+    decline: f64,
+    exponent: f64,
+) -> Result<NominalDeclineRate<Time>, DeclineCurveAnalysisError> {
+    Ok(
+        SecantEffectiveDeclineRate::<AverageYearsTime>::new(decline / 100.)
+            .to_nominal(exponent)?
+            .to_time_unit(),
+    )
+}
+
+/// Formats a forecast as ARIES decline lines, with one line for each segment.
+///
+/// Every line is given by its initial rate and its life in years, so the forecast is reproduced
+/// exactly when it's parsed. This fails for segment types that ARIES can't express, e.g., linear
+/// segments.
+pub fn format_forecast<Time: DeclineTimeUnit>(
+    phase: Phase,
+    forecast: &Forecast<Time>,
+    rate_unit: AriesRateUnit,
+) -> Result<String, DeclineCurveAnalysisError> {
+    let mut output = String::new();
+
+    for segment in forecast.segments() {
+        let (initial_rate, method, decline) = match segment {
+            Segment::Delay(_) => (0., "FLAT".to_string(), 0.),
+            Segment::Flat(parameters) => (
+                rate_unit.rate_value(parameters.rate()),
+                "FLAT".to_string(),
+                0.,
+            ),
+            Segment::Exponential(parameters) => (
+                rate_unit.rate_value(parameters.initial_rate()),
+                "EXP".to_string(),
+                secant_effective_decline(parameters.decline_rate(), 0.)?,
+            ),
+            Segment::Harmonic(parameters) => (
+                rate_unit.rate_value(parameters.initial_rate()),
+                "HAR".to_string(),
+                secant_effective_decline(parameters.initial_decline_rate(), 1.)?,
+            ),
+            Segment::Hyperbolic(parameters) => (
+                rate_unit.rate_value(parameters.initial_rate()),
+                format!("B/{}", parameters.exponent()),
+                secant_effective_decline(parameters.initial_decline_rate(), parameters.exponent())?,
+            ),
+            Segment::Linear(_) | Segment::LogisticGrowth(_) | Segment::PowerLawExponential(_) => {
+                return Err(invalid(
+                    "segment type can't be expressed as an ARIES decline".into(),
+                ));
+            }
+        };
+
+        let life = segment
+            .incremental_duration()
+            .to_unit::<AverageYearsTime>()
+            .years;
+        output.push_str(&format!(
+            "{} {initial_rate} X {} {life} YRS {method} {decline}\n",
+            phase_keyword(phase),
+            rate_unit.keyword(phase),
+        ));
+    }
+
+    Ok(output)
+}
+
+/// Converts a nominal decline rate to a secant effective decline in percent per year.
+fn secant_effective_decline<Time: DeclineTimeUnit>(
+    decline_rate: NominalDeclineRate<Time>,
+    exponent: f64,
+) -> Result<f64, DeclineCurveAnalysisError> {
+    Ok(decline_rate
+        .to_time_unit::<AverageYearsTime>()
+        .to_secant_effective(exponent)?
+        .value()
+        * 100.)
+}
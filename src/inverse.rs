@@ -0,0 +1,116 @@
+use crate::{
+    DeclineCurveAnalysisError, DeclineTimeUnit, NominalDeclineRate, ProductionRate, approx_eq,
+    is_effectively_zero, validate_finite, validate_incremental_volume, validate_positive,
+};
+
+/// Returns the time at which the cumulative volume of a segment reaches `incremental_volume`.
+///
+/// The `solve` function is called to find the duration of a segment with the same parameters that
+/// has the given incremental volume.
+pub(crate) fn time_at_incremental_volume<Time: DeclineTimeUnit>(
+    total_volume: f64,
+    incremental_duration: Time,
+    incremental_volume: f64,
+    solve: impl FnOnce() -> Result<Time, DeclineCurveAnalysisError>,
+) -> Result<Time, DeclineCurveAnalysisError> {
+    validate_incremental_volume(incremental_volume)?;
+
+    if is_effectively_zero(incremental_volume) {
+        return Ok(Time::from(0.));
+    }
+
+    // The segment ends before the volume is reached.
+    if incremental_volume > total_volume {
+        return Err(DeclineCurveAnalysisError::CannotSolveDecline);
+    }
+
+    Ok(Time::from(
+        solve()?.value().min(incremental_duration.value()),
+    ))
+}
+
+/// Returns the rate of a segment at the time its cumulative volume reaches `incremental_volume`.
+///
+/// This fails if the volume isn't reached before the end of the segment. The `rate` function is
+/// called with a volume within the segment to calculate the rate.
+pub(crate) fn rate_at_incremental_volume<Time: DeclineTimeUnit>(
+    total_volume: f64,
+    incremental_volume: f64,
+    rate: impl FnOnce(f64) -> Result<f64, DeclineCurveAnalysisError>,
+) -> Result<ProductionRate<Time>, DeclineCurveAnalysisError> {
+    validate_incremental_volume(incremental_volume)?;
+
+    // The segment ends before the volume is reached.
+    if incremental_volume > total_volume && !approx_eq(incremental_volume, total_volume) {
+        return Err(DeclineCurveAnalysisError::CannotSolveDecline);
+    }
+
+    Ok(ProductionRate::new(rate(
+        incremental_volume.min(total_volume),
+    )?))
+}
+
+/// Returns the first time at which the rate of a segment with a monotonic rate reaches `rate`.
+///
+/// Like `rate_at_time`, the rate is only considered within the segment, so this fails if the rate
+/// isn't between the initial and final rates. The `solve` function is called to find the duration
+/// of a segment with the same parameters that has the given final rate.
+pub(crate) fn time_at_rate<Time: DeclineTimeUnit>(
+    initial_rate: ProductionRate<Time>,
+    final_rate: ProductionRate<Time>,
+    incremental_duration: Time,
+    rate: ProductionRate<Time>,
+    solve: impl FnOnce() -> Result<Time, DeclineCurveAnalysisError>,
+) -> Result<Time, DeclineCurveAnalysisError> {
+    validate_positive(rate.value(), "rate")?;
+
+    if approx_eq(rate.value(), initial_rate.value()) {
+        return Ok(Time::from(0.));
+    }
+    if approx_eq(rate.value(), final_rate.value()) {
+        return Ok(incremental_duration);
+    }
+
+    let lowest_rate = initial_rate.value().min(final_rate.value());
+    let highest_rate = initial_rate.value().max(final_rate.value());
+    if rate.value() < lowest_rate || rate.value() > highest_rate {
+        return Err(DeclineCurveAnalysisError::CannotSolveDecline);
+    }
+
+    Ok(Time::from(
+        solve()?.value().min(incremental_duration.value()),
+    ))
+}
+
+/// Returns the time at which the instantaneous nominal decline rate of a segment with a monotonic
+/// decline rate reaches `decline_rate`.
+///
+/// Like `time_at_rate`, this fails if the decline rate isn't between the initial and final decline
+/// rates. The `solve` function is called to find the duration of a segment with the same
+/// parameters that has the given final decline rate.
+pub(crate) fn time_at_nominal_decline<Time: DeclineTimeUnit>(
+    initial_decline_rate: NominalDeclineRate<Time>,
+    final_decline_rate: NominalDeclineRate<Time>,
+    incremental_duration: Time,
+    decline_rate: NominalDeclineRate<Time>,
+    solve: impl FnOnce() -> Result<Time, DeclineCurveAnalysisError>,
+) -> Result<Time, DeclineCurveAnalysisError> {
+    validate_finite(decline_rate.value(), "decline rate")?;
+
+    if approx_eq(decline_rate.value(), initial_decline_rate.value()) {
+        return Ok(Time::from(0.));
+    }
+    if approx_eq(decline_rate.value(), final_decline_rate.value()) {
+        return Ok(incremental_duration);
+    }
+
+    let lowest = initial_decline_rate.value().min(final_decline_rate.value());
+    let highest = initial_decline_rate.value().max(final_decline_rate.value());
+    if decline_rate.value() < lowest || decline_rate.value() > highest {
+        return Err(DeclineCurveAnalysisError::CannotSolveDecline);
+    }
+
+    Ok(Time::from(
+        solve()?.value().min(incremental_duration.value()),
+    ))
+}
@@ -0,0 +1,243 @@
+//! Closed-form rate and volume equations for the Arps models, which are generic over the scalar
+//! type.
+//!
+//! The segment types evaluate these kernels with `f64`, but they can also be evaluated with any
+//! type that implements [`Float`], e.g., dual numbers for automatic differentiation when a decline
+//! curve is embedded in a larger optimization problem. The kernels don't validate their inputs or
+//! clamp times to a duration, which is left to the segment types. The segment types also use them
+//! to evaluate in `f32` (e.g., [`crate::Segment::rates_at_times_f32`]) for memory-bound workloads.
+//!
+//! Times and decline rates must be in the same time unit, e.g., a nominal decline rate per day
+//! with a time in days.
+
+use core::ops::{Add, Div, Mul, Neg, Sub};
+
+use crate::{fast_math, math};
+
+/// The floating-point operations used by the kernels.
+///
+/// This is implemented for `f32` and `f64`. Transcendental functions use the same implementations
+/// as the rest of the crate, so they use `libm` when the standard library isn't available or the
+/// `deterministic` feature is enabled, and `powf` for `f64` uses the approximation from the
+/// `fast-math` feature when it's enabled.
+pub trait Float:
+    Copy
+    + PartialOrd
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+{
+    /// Converts a constant to this type.
+    fn from_f64(value: f64) -> Self;
+    fn exp(self) -> Self;
+    /// Returns `e ^ self - 1`, which is accurate even when `self` is close to zero.
+    fn exp_m1(self) -> Self;
+    fn ln(self) -> Self;
+    /// Returns `ln(1 + self)`, which is accurate even when `self` is close to zero.
+    fn ln_1p(self) -> Self;
+    fn powf(self, exponent: Self) -> Self;
+    /// Returns `self * a + b`.
+    fn mul_add(self, a: Self, b: Self) -> Self;
+}
+
+impl Float for f64 {
+    #[inline]
+    fn from_f64(value: f64) -> Self {
+        value
+    }
+
+    #[inline]
+    fn exp(self) -> Self {
+        math::exp(self)
+    }
+
+    #[inline]
+    fn exp_m1(self) -> Self {
+        math::exp_m1(self)
+    }
+
+    #[inline]
+    fn ln(self) -> Self {
+        math::ln(self)
+    }
+
+    #[inline]
+    fn ln_1p(self) -> Self {
+        math::ln_1p(self)
+    }
+
+    #[inline]
+    fn powf(self, exponent: Self) -> Self {
+        fast_math::powf(self, exponent)
+    }
+
+    #[inline]
+    fn mul_add(self, a: Self, b: Self) -> Self {
+        #[cfg(not(feature = "std"))]
+        return libm::fma(self, a, b);
+        #[cfg(feature = "std")]
+        return f64::mul_add(self, a, b);
+    }
+}
+
+/// Uses `libm` for an `f32` function, or the `f32` method with the same name.
+macro_rules! f32_functions {
+    ($($name:ident($($argument:ident),*) => $libm:ident),* $(,)?) => {
+        $(
+            #[inline]
+            fn $name(self, $($argument: Self),*) -> Self {
+                #[cfg(any(not(feature = "std"), feature = "deterministic"))]
+                return libm::$libm(self, $($argument),*);
+                #[cfg(all(feature = "std", not(feature = "deterministic")))]
+                return f32::$name(self, $($argument),*);
+            }
+        )*
+    };
+}
+
+impl Float for f32 {
+    #[inline]
+    fn from_f64(value: f64) -> Self {
+        value as f32
+    }
+
+    f32_functions!(
+        exp() => expf,
+        exp_m1() => expm1f,
+        ln() => logf,
+        ln_1p() => log1pf,
+        powf(exponent) => powf,
+    );
+
+    #[inline]
+    fn mul_add(self, a: Self, b: Self) -> Self {
+        #[cfg(not(feature = "std"))]
+        return libm::fmaf(self, a, b);
+        #[cfg(feature = "std")]
+        return f32::mul_add(self, a, b);
+    }
+}
+
+/// The rate of an exponential decline, which is `q_i * e^(-D * t)`.
+#[inline]
+pub fn exponential_rate<F: Float>(initial_rate: F, decline_rate: F, time: F) -> F {
+    initial_rate * (-decline_rate * time).exp()
+}
+
+/// The cumulative volume of an exponential decline, which is `q_i * (1 - e^(-D * t)) / D`.
+#[inline]
+pub fn exponential_incremental_volume<F: Float>(initial_rate: F, decline_rate: F, time: F) -> F {
+    (-(-decline_rate * time).exp_m1() * initial_rate) / decline_rate
+}
+
+/// The rate of a harmonic decline, which is `q_i / (1 + D_i * t)`.
+#[inline]
+pub fn harmonic_rate<F: Float>(initial_rate: F, initial_decline_rate: F, time: F) -> F {
+    initial_rate / time.mul_add(initial_decline_rate, F::from_f64(1.))
+}
+
+/// The cumulative volume of a harmonic decline, which is `q_i * ln(1 + D_i * t) / D_i`.
+#[inline]
+pub fn harmonic_incremental_volume<F: Float>(
+    initial_rate: F,
+    initial_decline_rate: F,
+    time: F,
+) -> F {
+    (initial_rate * (time * initial_decline_rate).ln_1p()) / initial_decline_rate
+}
+
+/// The rate of a hyperbolic decline, which is `q_i / (1 + b * D_i * t)^(1 / b)`.
+#[inline]
+pub fn hyperbolic_rate<F: Float>(
+    initial_rate: F,
+    initial_decline_rate: F,
+    exponent: F,
+    time: F,
+) -> F {
+    HyperbolicCoefficients::new(initial_rate, initial_decline_rate, exponent)
+        .rate(initial_rate, time)
+}
+
+/// The cumulative volume of a hyperbolic decline, which is
+/// `q_i * (1 - (1 + b * D_i * t)^(1 - 1 / b)) / (D_i * (1 - b))`.
+#[inline]
+pub fn hyperbolic_incremental_volume<F: Float>(
+    initial_rate: F,
+    initial_decline_rate: F,
+    exponent: F,
+    time: F,
+) -> F {
+    HyperbolicCoefficients::new(initial_rate, initial_decline_rate, exponent)
+        .incremental_volume(time)
+}
+
+/// The rate of a linear decline, which is `q_i * (1 - D * t)`.
+#[inline]
+pub fn linear_rate<F: Float>(initial_rate: F, decline_rate: F, time: F) -> F {
+    initial_rate.mul_add(-decline_rate * time, initial_rate)
+}
+
+/// The cumulative volume of a linear decline, which is `q_i * t - D * q_i * t^2 / 2`.
+#[inline]
+pub fn linear_incremental_volume<F: Float>(initial_rate: F, decline_rate: F, time: F) -> F {
+    initial_rate * time - F::from_f64(0.5) * decline_rate * initial_rate * (time * time)
+}
+
+/// The terms of the hyperbolic rate and volume equations that only depend on the parameters, so
+/// that segments can compute them once when they're created.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct HyperbolicCoefficients<F> {
+    /// `b * a_i`
+    pub(crate) exponent_times_initial_decline_rate: F,
+    /// `1 / b`
+    pub(crate) inverse_exponent: F,
+    /// `q_i / (a_i * (1 - b))`
+    pub(crate) volume_factor: F,
+    /// `1 - 1 / b`
+    pub(crate) volume_power: F,
+}
+
+impl HyperbolicCoefficients<f64> {
+    /// Rounds the coefficients to `f32`, which is more accurate than computing them in `f32`.
+    pub(crate) fn to_f32(self) -> HyperbolicCoefficients<f32> {
+        HyperbolicCoefficients {
+            exponent_times_initial_decline_rate: self.exponent_times_initial_decline_rate as f32,
+            inverse_exponent: self.inverse_exponent as f32,
+            volume_factor: self.volume_factor as f32,
+            volume_power: self.volume_power as f32,
+        }
+    }
+}
+
+impl<F: Float> HyperbolicCoefficients<F> {
+    #[inline]
+    pub(crate) fn new(initial_rate: F, initial_decline_rate: F, exponent: F) -> Self {
+        let one = F::from_f64(1.);
+
+        Self {
+            exponent_times_initial_decline_rate: exponent * initial_decline_rate,
+            inverse_exponent: one / exponent,
+            volume_factor: initial_rate
+                / initial_decline_rate.mul_add(-exponent, initial_decline_rate),
+            volume_power: one - one / exponent,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn rate(&self, initial_rate: F, time: F) -> F {
+        initial_rate
+            / time
+                .mul_add(self.exponent_times_initial_decline_rate, F::from_f64(1.))
+                .powf(self.inverse_exponent)
+    }
+
+    #[inline]
+    pub(crate) fn incremental_volume(&self, time: F) -> F {
+        let base = time.mul_add(self.exponent_times_initial_decline_rate, F::from_f64(1.));
+
+        base.powf(self.volume_power)
+            .mul_add(-self.volume_factor, self.volume_factor)
+    }
+}
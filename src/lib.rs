@@ -1,21 +1,139 @@
-use std::marker::PhantomData;
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[cfg(not(any(feature = "std", feature = "libm")))]
+compile_error!(
+    "either the `std` feature or the `libm` feature must be enabled for floating-point math"
+);
+
+use alloc::string::String;
+use core::{fmt, marker::PhantomData, ops::Bound};
 use thiserror::Error;
 
+mod aggregate;
+#[cfg(any(feature = "approx", feature = "float-cmp"))]
+mod approximate;
+mod auto_forecast;
+mod backtest;
+mod calendar;
+#[cfg(feature = "arrow")]
+mod columnar;
+mod constructor;
+mod curtailed;
+#[cfg(feature = "chrono")]
+mod dated_forecast;
+mod declination_table;
 mod decline_rate;
 mod delay;
+mod diagnostics;
+mod display;
+mod downtime;
+mod dynamic;
 mod exponential;
+pub mod export;
+mod fast_math;
+#[cfg(feature = "json")]
+pub mod file_format;
+mod fingerprint;
+mod fit;
+mod fixed_forecast;
 mod flat;
+mod forecast;
+mod goal_seek;
 mod harmonic;
+mod history;
 mod hyperbolic;
+pub mod interop;
+mod inverse;
+pub mod kernels;
 mod linear;
-
+mod logistic_growth;
+mod math;
+mod metadata;
+mod numerical;
+mod periods;
+#[cfg(feature = "plot")]
+pub mod plot;
+mod portfolio;
+mod power_law_exponential;
+mod probabilistic;
+mod ratio;
+mod reserves;
+mod restimulation;
+mod rta;
+mod sampler;
+mod scenario;
+mod schedule;
+mod segment;
+mod sensitivity;
+#[cfg(feature = "serde")]
+mod serialization;
+mod stream;
+mod superposition;
+mod terminal_decline;
+mod time_shift;
+mod truncation;
+mod type_curve;
+mod validation;
+mod volume;
+#[cfg(feature = "wasm")]
+mod wasm;
+mod well;
+
+pub use aggregate::*;
+pub use auto_forecast::*;
+pub use backtest::*;
+pub use calendar::*;
+#[cfg(feature = "arrow")]
+pub use columnar::*;
+pub use constructor::*;
+pub use curtailed::*;
+#[cfg(feature = "chrono")]
+pub use dated_forecast::*;
+pub use declination_table::*;
 pub use decline_rate::*;
 pub use delay::*;
+pub use diagnostics::*;
+pub use display::*;
+pub use downtime::*;
+pub use dynamic::*;
 pub use exponential::*;
+pub use fit::*;
+pub use fixed_forecast::*;
 pub use flat::*;
+pub use forecast::*;
+pub use goal_seek::*;
 pub use harmonic::*;
+pub use history::*;
 pub use hyperbolic::*;
 pub use linear::*;
+pub use logistic_growth::*;
+pub use metadata::*;
+pub use periods::{PeriodVolume, TimeGrid};
+pub use portfolio::*;
+pub use power_law_exponential::*;
+pub use probabilistic::*;
+pub use ratio::*;
+pub use reserves::*;
+pub use restimulation::*;
+pub use rta::*;
+pub use sampler::*;
+pub use scenario::*;
+pub use schedule::*;
+pub use segment::*;
+pub use sensitivity::*;
+pub use stream::*;
+pub use superposition::*;
+pub use terminal_decline::*;
+pub use time_shift::*;
+pub use truncation::*;
+pub use type_curve::*;
+pub use validation::*;
+pub use volume::*;
+#[cfg(feature = "wasm")]
+pub use wasm::*;
+pub use well::*;
 
 /// Absolute tolerance for floating-point comparisons and "effectively zero" checks.
 pub(crate) const EPSILON: f64 = 1e-12;
@@ -24,6 +142,94 @@ pub(crate) const EPSILON: f64 = 1e-12;
 /// errors that could cause numerical instability.
 pub(crate) const MAX_DURATION_YEARS: f64 = 1000.;
 
+/// Returns the maximum allowed duration in the given time unit. This is useful as an upper bound
+/// for numerical solves.
+pub(crate) fn max_duration<Time: DeclineTimeUnit>() -> f64 {
+    AverageYearsTime {
+        years: MAX_DURATION_YEARS,
+    }
+    .to_unit::<Time>()
+    .value()
+}
+
+/// Splits a duration at `time`, clamped to be within `[0, incremental_duration]`. A time that's
+/// not-a-number splits at the start.
+pub(crate) fn split_duration<Time: DeclineTimeUnit>(
+    time: Time,
+    incremental_duration: Time,
+) -> (Time, Time) {
+    let split_time = if time.value().is_nan() {
+        0.
+    } else {
+        time.value().clamp(0., incremental_duration.value())
+    };
+
+    (
+        Time::from(split_time),
+        Time::from(incremental_duration.value() - split_time),
+    )
+}
+
+/// Clamps a time to be within `[0, incremental_duration]`.
+pub(crate) fn clamp_to_duration<Time: DeclineTimeUnit>(
+    time: Time,
+    incremental_duration: Time,
+) -> Time {
+    Time::from(time.value().clamp(0., incremental_duration.value()))
+}
+
+/// Evaluates `f` at each time after clamping it to the end of the segment, writing the results to
+/// `out`.
+///
+/// The loop is kept free of branches (other than in `f`) so that the compiler can vectorize it.
+///
+/// # Panics
+///
+/// Panics if `times` and `out` have different lengths.
+#[inline]
+pub(crate) fn evaluate_at_times<Time: DeclineTimeUnit>(
+    times: &[Time],
+    out: &mut [f64],
+    incremental_duration: Time,
+    f: impl Fn(Time) -> f64,
+) {
+    assert_eq!(
+        times.len(),
+        out.len(),
+        "expected the times and output to have the same length"
+    );
+
+    let incremental_duration = incremental_duration.value();
+    for (time, out) in times.iter().zip(out) {
+        *out = f(Time::from(time.value().min(incremental_duration)));
+    }
+}
+
+/// Like [`evaluate_at_times`], but with `f32` times and results, where the times are values in the
+/// segment's time unit.
+///
+/// # Panics
+///
+/// Panics if `times` and `out` have different lengths.
+#[inline]
+pub(crate) fn evaluate_at_times_f32<Time: DeclineTimeUnit>(
+    times: &[f32],
+    out: &mut [f32],
+    incremental_duration: Time,
+    f: impl Fn(f32) -> f32,
+) {
+    assert_eq!(
+        times.len(),
+        out.len(),
+        "expected the times and output to have the same length"
+    );
+
+    let incremental_duration = incremental_duration.value() as f32;
+    for (time, out) in times.iter().zip(out) {
+        *out = f(time.min(incremental_duration));
+    }
+}
+
 /// Returns true if `value` is approximately zero, otherwise false.
 pub(crate) fn is_effectively_zero(value: f64) -> bool {
     value.abs() <= EPSILON
@@ -34,6 +240,12 @@ pub(crate) fn approx_eq(a: f64, b: f64) -> bool {
     (a - b).abs() <= EPSILON
 }
 
+/// Returns true if two values differ by at most `tolerance` relative to the larger magnitude, or
+/// by at most `tolerance` when both magnitudes are below one, otherwise false.
+pub(crate) fn within_tolerance(a: f64, b: f64, tolerance: f64) -> bool {
+    a == b || (a - b).abs() <= tolerance * a.abs().max(b.abs()).max(1.)
+}
+
 /// Returns true if `a >= b`, otherwise false.
 pub(crate) fn approx_gte(a: f64, b: f64) -> bool {
     a >= b - EPSILON
@@ -48,13 +260,7 @@ pub(crate) fn validate_finite(
         return Ok(());
     }
 
-    Err(DeclineCurveAnalysisError::InvalidInput {
-        reason: if value.is_nan() {
-            format!("{name} is not-a-number, but expected a finite number")
-        } else {
-            format!("{name} is infinity, but expected a finite number")
-        },
-    })
+    Err(DeclineCurveAnalysisError::NonFiniteInput { field: name, value })
 }
 
 /// Validates that a value is positive and finite.
@@ -64,9 +270,7 @@ pub(crate) fn validate_positive(
 ) -> Result<(), DeclineCurveAnalysisError> {
     validate_finite(value, name)?;
     if value.is_sign_negative() {
-        return Err(DeclineCurveAnalysisError::InvalidInput {
-            reason: format!("{name} is negative, but expected a positive number"),
-        });
+        return Err(DeclineCurveAnalysisError::NegativeInput { field: name, value });
     }
     Ok(())
 }
@@ -78,9 +282,7 @@ pub(crate) fn validate_non_zero_positive_rate(
 ) -> Result<(), DeclineCurveAnalysisError> {
     validate_finite(value, name)?;
     if value.is_sign_negative() || is_effectively_zero(value) {
-        return Err(DeclineCurveAnalysisError::InvalidInput {
-            reason: format!("{name} is negative or zero, but expected a positive number"),
-        });
+        return Err(DeclineCurveAnalysisError::NonPositiveInput { field: name, value });
     }
     Ok(())
 }
@@ -92,9 +294,7 @@ pub(crate) fn validate_non_zero_decline_rate(
 ) -> Result<(), DeclineCurveAnalysisError> {
     validate_finite(value, name)?;
     if is_effectively_zero(value) {
-        return Err(DeclineCurveAnalysisError::InvalidInput {
-            reason: format!("{name} is approximately zero, but expected it to be non-zero"),
-        });
+        return Err(DeclineCurveAnalysisError::ZeroInput { field: name, value });
     }
     Ok(())
 }
@@ -117,17 +317,15 @@ pub(crate) fn validate_duration<Time: DeclineTimeUnit>(
 
 /// Validates that a volume is positive and finite.
 pub(crate) fn validate_incremental_volume(volume: f64) -> Result<(), DeclineCurveAnalysisError> {
-    validate_finite(volume, "incremental volume")?;
-    if volume.is_sign_negative() {
-        return Err(DeclineCurveAnalysisError::InvalidInput {
-            reason: "incremental volume is negative, but expected a positive number".to_string(),
-        });
-    }
-    Ok(())
+    validate_positive(volume, "incremental volume")
 }
 
 /// An error type for invalid parameters.
-#[derive(Clone, Debug, Error, Eq, PartialEq)]
+///
+/// Errors for a specific input carry the name of the input (e.g., "initial rate") and the value
+/// that was rejected, so that the offending input can be highlighted. The error isn't `Eq`, since
+/// the rejected values are floating-point numbers.
+#[derive(Clone, Debug, Error, PartialEq)]
 pub enum DeclineCurveAnalysisError {
     #[error("decline rate too high")]
     DeclineRateTooHigh,
@@ -135,26 +333,202 @@ pub enum DeclineCurveAnalysisError {
     DeclineRateWrongSign,
     #[error("cannot solve decline: no finite solution exists for the given parameters")]
     CannotSolveDecline,
-    #[error("exponent too large")]
-    ExponentTooLarge,
+    #[error("exponent is {value}, but expected it to be {allowed}")]
+    ExponentOutOfRange { value: f64, allowed: ExponentRange },
     #[error("duration too long")]
     DurationTooLong,
+    #[error("fit did not converge")]
+    FitDidNotConverge,
+    #[error("{field} is {}, but expected a finite number", describe_non_finite(*.value))]
+    NonFiniteInput { field: &'static str, value: f64 },
+    #[error("{field} is negative, but expected a positive number")]
+    NegativeInput { field: &'static str, value: f64 },
+    #[error("{field} is negative or zero, but expected a positive number")]
+    NonPositiveInput { field: &'static str, value: f64 },
+    #[error("{field} is approximately zero, but expected it to be non-zero")]
+    ZeroInput { field: &'static str, value: f64 },
+    #[error("{field} is greater than one, but expected a fraction")]
+    InputGreaterThanOne { field: &'static str, value: f64 },
+    #[error("{field} is {value}, but expected it to be {}", RangeDescription(*.minimum, *.maximum))]
+    InputOutOfRange {
+        field: &'static str,
+        value: f64,
+        minimum: Bound<f64>,
+        maximum: Bound<f64>,
+    },
+    #[error("{field} is {value}, but expected it to be less than the {other} of {limit}")]
+    InputNotLessThan {
+        field: &'static str,
+        value: f64,
+        other: &'static str,
+        limit: f64,
+    },
+    #[error("{field} is {value}, but expected it to be at most the {other} of {limit}")]
+    InputGreaterThan {
+        field: &'static str,
+        value: f64,
+        other: &'static str,
+        limit: f64,
+    },
+    #[error("{field} is {count}, but expected at least {minimum}")]
+    TooFewInputs {
+        field: &'static str,
+        count: usize,
+        minimum: usize,
+    },
+    #[error("{field} has a length of {count}, but expected {expected}")]
+    InputLengthMismatch {
+        field: &'static str,
+        count: usize,
+        expected: usize,
+    },
+    #[error("{field} are not strictly increasing")]
+    NotStrictlyIncreasing { field: &'static str },
+    #[error("{field} is {value}, which moves the date out of range")]
+    DateOutOfRange { field: &'static str, value: i64 },
+    #[error("{field} \"{value}\" is unknown, but expected {expected}")]
+    UnknownInput {
+        field: &'static str,
+        value: String,
+        expected: &'static str,
+    },
+    #[error("{name} is forecast more than once")]
+    DuplicateForecast { field: &'static str, name: String },
+    #[error("{field} is missing, but there's no previous segment to continue from")]
+    NoPreviousSegment { field: &'static str },
+    #[error("forecast file has a time unit of {days} days, but expected {expected_days} days")]
+    TimeUnitMismatch { days: f64, expected_days: f64 },
+    #[error("line {line}: {reason}")]
+    ParseFailed { line: usize, reason: String },
     #[error("{reason}")]
     InvalidInput { reason: String },
+    #[error("a zero rate can't produce an incremental volume of {volume}")]
+    ZeroRateVolume { volume: f64 },
+    #[error("{segment} segments can't be constructed from {end}")]
+    UnsupportedSegmentEnd {
+        segment: &'static str,
+        end: &'static str,
+    },
+    #[error(
+        "{segment} segments can't be split because their rates depend on the time since the start \
+         of production"
+    )]
+    UnsplittableSegment { segment: &'static str },
+    #[error("failed to write output: {reason}")]
+    WriteFailed { reason: String },
+    #[error("failed to read input: {reason}")]
+    ReadFailed { reason: String },
+    #[error("insufficient data: {gate} is {value}, but expected at least {minimum}")]
+    InsufficientData {
+        gate: QualityGate,
+        value: f64,
+        minimum: f64,
+    },
+    #[error("quality gate failed: {gate} is {value}, but expected {} {limit}", .gate.comparison())]
+    QualityGateFailed {
+        gate: QualityGate,
+        value: f64,
+        limit: f64,
+    },
+}
+
+impl DeclineCurveAnalysisError {
+    /// The name of the input that was rejected, if the error is for a specific input.
+    pub fn field(&self) -> Option<&'static str> {
+        match self {
+            Self::ExponentOutOfRange { .. } => Some("exponent"),
+            Self::NonFiniteInput { field, .. }
+            | Self::NegativeInput { field, .. }
+            | Self::NonPositiveInput { field, .. }
+            | Self::ZeroInput { field, .. }
+            | Self::InputGreaterThanOne { field, .. }
+            | Self::InputOutOfRange { field, .. }
+            | Self::InputNotLessThan { field, .. }
+            | Self::InputGreaterThan { field, .. }
+            | Self::TooFewInputs { field, .. }
+            | Self::InputLengthMismatch { field, .. }
+            | Self::NotStrictlyIncreasing { field }
+            | Self::DateOutOfRange { field, .. }
+            | Self::UnknownInput { field, .. }
+            | Self::DuplicateForecast { field, .. }
+            | Self::NoPreviousSegment { field } => Some(field),
+            _ => None,
+        }
+    }
+}
+
+/// The range of exponents that are allowed, which is used to describe invalid exponents.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ExponentRange {
+    pub minimum: f64,
+    pub maximum: f64,
+    /// Exponents in the range that aren't allowed, e.g., 0 and 1 for hyperbolic segments, which
+    /// are exponential and harmonic segments instead.
+    pub excluded: &'static [f64],
+}
+
+impl fmt::Display for ExponentRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "between {} and {}", self.minimum, self.maximum)?;
+        for (i, excluded) in self.excluded.iter().enumerate() {
+            let separator = if i == 0 { ", other than" } else { " or" };
+            write!(f, "{separator} {excluded}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Describes the range of values allowed for an input, e.g., "greater than 0 and at most 1".
+struct RangeDescription(Bound<f64>, Bound<f64>);
+
+impl fmt::Display for RangeDescription {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let (Bound::Included(minimum), Bound::Included(maximum)) = (self.0, self.1) {
+            return write!(f, "between {minimum} and {maximum}");
+        }
+
+        match self.0 {
+            Bound::Included(minimum) => write!(f, "at least {minimum}")?,
+            Bound::Excluded(minimum) => write!(f, "greater than {minimum}")?,
+            Bound::Unbounded => {}
+        }
+        if !matches!(self.0, Bound::Unbounded) && !matches!(self.1, Bound::Unbounded) {
+            f.write_str(" and ")?;
+        }
+        match self.1 {
+            Bound::Included(maximum) => write!(f, "at most {maximum}"),
+            Bound::Excluded(maximum) => write!(f, "less than {maximum}"),
+            Bound::Unbounded => Ok(()),
+        }
+    }
+}
+
+fn describe_non_finite(value: f64) -> &'static str {
+    if value.is_nan() {
+        "not-a-number"
+    } else {
+        "infinity"
+    }
 }
 
-/// The production rate for a specific time unit.
+/// The production rate for a specific time unit, optionally tagged with a volume unit.
+///
+/// Rates without a volume unit are used by the decline curves, and can be tagged with
+/// [`ProductionRate::with_volume_unit`] so that rates for different streams can't be mixed by
+/// accident.
 #[derive(Debug, Clone, Copy, PartialEq)]
-pub struct ProductionRate<Time: DeclineTimeUnit> {
+pub struct ProductionRate<Time: DeclineTimeUnit, Volume: VolumeUnit = UnspecifiedVolume> {
     value: f64,
     _time: PhantomData<Time>,
+    _volume: PhantomData<Volume>,
 }
 
-impl<Time: DeclineTimeUnit> ProductionRate<Time> {
+impl<Time: DeclineTimeUnit, Volume: VolumeUnit> ProductionRate<Time, Volume> {
     pub const fn new(value: f64) -> Self {
         Self {
             value,
             _time: PhantomData,
+            _volume: PhantomData,
         }
     }
 
@@ -163,18 +537,6 @@ impl<Time: DeclineTimeUnit> ProductionRate<Time> {
     }
 }
 
-impl From<ProductionRate<AverageYearsTime>> for ProductionRate<AverageDaysTime> {
-    fn from(val: ProductionRate<AverageYearsTime>) -> Self {
-        ProductionRate::new(val.value * AverageDaysTime::LENGTH / AverageYearsTime::LENGTH)
-    }
-}
-
-impl From<ProductionRate<AverageDaysTime>> for ProductionRate<AverageYearsTime> {
-    fn from(val: ProductionRate<AverageDaysTime>) -> Self {
-        ProductionRate::new(val.value * AverageYearsTime::LENGTH / AverageDaysTime::LENGTH)
-    }
-}
-
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum DeclineRateSignValidation {
     Continue,
@@ -1,7 +1,11 @@
+#[cfg(not(feature = "std"))]
+use crate::math::FloatMath;
 use crate::{
-    DeclineCurveAnalysisError, DeclineTimeUnit, NominalDeclineRate, ProductionRate, approx_eq,
-    is_effectively_zero, validate_duration, validate_incremental_volume,
-    validate_non_zero_decline_rate, validate_non_zero_positive_rate,
+    DeclineCurveAnalysisError, DeclineTimeUnit, NominalDeclineRate, ProductionRate, RateTruncation,
+    approx_eq, clamp_to_duration, evaluate_at_times, evaluate_at_times_f32,
+    inverse::{rate_at_incremental_volume, time_at_incremental_volume, time_at_rate},
+    is_effectively_zero, kernels, split_duration, truncated_duration, validate_duration,
+    validate_incremental_volume, validate_non_zero_decline_rate, validate_non_zero_positive_rate,
 };
 
 /// A linear decline segment.
@@ -119,11 +123,42 @@ impl<Time: DeclineTimeUnit> LinearParameters<Time> {
         })
     }
 
-    fn incremental_volume_at_time_without_clamping(&self, time: Time) -> f64 {
-        let time_value = time.value();
+    /// The decline rate of a linear decline increases over time, so the final decline rate must
+    /// be at least the initial decline rate.
+    pub fn from_final_decline_rate(
+        initial_rate: ProductionRate<Time>,
+        decline_rate: NominalDeclineRate<Time>,
+        final_decline_rate: NominalDeclineRate<Time>,
+    ) -> Result<Self, DeclineCurveAnalysisError> {
+        validate_non_zero_positive_rate(initial_rate.value, "initial rate")?;
+        validate_non_zero_decline_rate(decline_rate.value(), "decline rate")?;
+        validate_non_zero_decline_rate(final_decline_rate.value(), "final decline rate")?;
 
-        self.initial_rate.value * time_value
-            - 0.5 * self.decline_rate.value() * self.initial_rate.value * time_value.powi(2)
+        let decline_rate_value = decline_rate.value();
+        let final_decline_rate_value = final_decline_rate.value();
+
+        if decline_rate_value.is_sign_positive() != final_decline_rate_value.is_sign_positive() {
+            return Err(DeclineCurveAnalysisError::CannotSolveDecline);
+        }
+
+        // Solve `D_f = D / (1 - D * t)` for the time.
+        let incremental_duration =
+            Time::from(1. / decline_rate_value - 1. / final_decline_rate_value);
+        validate_duration(incremental_duration)?;
+
+        Ok(Self {
+            initial_rate,
+            decline_rate,
+            incremental_duration,
+        })
+    }
+
+    fn incremental_volume_at_time_without_clamping(&self, time: Time) -> f64 {
+        kernels::linear_incremental_volume(
+            self.initial_rate.value,
+            self.decline_rate.value(),
+            time.value(),
+        )
     }
 
     pub fn incremental_volume_at_time(&self, time: Time) -> f64 {
@@ -134,21 +169,159 @@ impl<Time: DeclineTimeUnit> LinearParameters<Time> {
         }
     }
 
+    /// The volume produced between two times, which are clamped to be within the segment. This is
+    /// negative if `end` is before `start`.
+    pub fn incremental_volume_between(&self, start: Time, end: Time) -> f64 {
+        let start = clamp_to_duration(start, self.incremental_duration);
+        let end = clamp_to_duration(end, self.incremental_duration);
+
+        self.incremental_volume_at_time_without_clamping(end)
+            - self.incremental_volume_at_time_without_clamping(start)
+    }
+
+    /// Returns the time at which the cumulative volume reaches the given volume, which is the
+    /// inverse of `incremental_volume_at_time`.
+    ///
+    /// This fails if the volume isn't reached before the end of the segment.
+    pub fn time_at_incremental_volume(
+        &self,
+        incremental_volume: f64,
+    ) -> Result<Time, DeclineCurveAnalysisError> {
+        time_at_incremental_volume(
+            self.incremental_volume(),
+            self.incremental_duration,
+            incremental_volume,
+            || {
+                Ok(Self::from_incremental_volume(
+                    self.initial_rate,
+                    self.decline_rate,
+                    incremental_volume,
+                )?
+                .incremental_duration)
+            },
+        )
+    }
+
+    /// Returns the rate at the time the cumulative volume reaches the given volume, for
+    /// rate-cumulative analysis.
+    ///
+    /// This fails if the volume isn't reached before the end of the segment.
+    pub fn rate_at_incremental_volume(
+        &self,
+        incremental_volume: f64,
+    ) -> Result<ProductionRate<Time>, DeclineCurveAnalysisError> {
+        // The volume is `(q_i^2 - q^2) / (2 * q_i * D)`.
+        rate_at_incremental_volume(self.incremental_volume(), incremental_volume, |volume| {
+            let base = (-2. * self.decline_rate.value() / self.initial_rate.value)
+                .mul_add(volume, 1.)
+                .max(0.);
+            Ok(self.initial_rate.value * base.sqrt())
+        })
+    }
+
     pub fn incremental_volume(&self) -> f64 {
         self.incremental_volume_at_time_without_clamping(self.incremental_duration)
     }
 
     fn rate_at_time_without_clamping(&self, time: Time) -> ProductionRate<Time> {
-        ProductionRate::new(self.initial_rate.value.mul_add(
-            -self.decline_rate.value() * time.value(),
+        ProductionRate::new(kernels::linear_rate(
             self.initial_rate.value,
+            self.decline_rate.value(),
+            time.value(),
         ))
     }
 
+    /// Splits the segment at `time` into two segments that together reproduce the original. The
+    /// second segment starts at the rate at the split, and its decline rate is rescaled so that it
+    /// keeps the same slope.
+    pub fn split_at(&self, time: Time) -> (Self, Self) {
+        let (first_duration, second_duration) = split_duration(time, self.incremental_duration);
+        let split_rate = self.rate_at_time_without_clamping(first_duration);
+        (
+            Self {
+                initial_rate: self.initial_rate,
+                decline_rate: self.decline_rate,
+                incremental_duration: first_duration,
+            },
+            Self {
+                initial_rate: split_rate,
+                decline_rate: NominalDeclineRate::new(
+                    self.initial_rate.value * self.decline_rate.value() / split_rate.value,
+                ),
+                incremental_duration: second_duration,
+            },
+        )
+    }
+
+    /// Returns a copy where every rate is multiplied by `factor`, keeping the same decline.
+    pub(crate) fn scale_rates(&self, factor: f64) -> Self {
+        Self {
+            initial_rate: ProductionRate::new(self.initial_rate.value * factor),
+            ..self.clone()
+        }
+    }
+
+    /// Returns a copy of the parameters that ends at the first time the rate falls to the economic
+    /// limit, along with the volume and duration that were removed.
+    pub fn truncate_at_rate(
+        &self,
+        economic_limit_rate: ProductionRate<Time>,
+    ) -> Result<RateTruncation<Self, Time>, DeclineCurveAnalysisError> {
+        let incremental_duration = truncated_duration(
+            self.initial_rate,
+            self.final_rate(),
+            self.incremental_duration,
+            economic_limit_rate,
+            || {
+                Ok(Self::from_final_rate(
+                    self.initial_rate,
+                    self.decline_rate,
+                    economic_limit_rate,
+                )?
+                .incremental_duration)
+            },
+        )?;
+
+        let parameters = Self::from_incremental_duration(
+            self.initial_rate,
+            self.decline_rate,
+            incremental_duration,
+        )?;
+
+        Ok(RateTruncation {
+            truncated_volume: self.incremental_volume() - parameters.incremental_volume(),
+            truncated_duration: Time::from(
+                self.incremental_duration.value() - parameters.incremental_duration.value(),
+            ),
+            parameters,
+        })
+    }
+
     pub fn final_rate(&self) -> ProductionRate<Time> {
         self.rate_at_time_without_clamping(self.incremental_duration)
     }
 
+    /// Returns the first time at which the rate reaches the given rate.
+    ///
+    /// This fails if the rate isn't reached before the end of the segment.
+    pub fn time_at_rate(
+        &self,
+        rate: ProductionRate<Time>,
+    ) -> Result<Time, DeclineCurveAnalysisError> {
+        time_at_rate(
+            self.initial_rate,
+            self.final_rate(),
+            self.incremental_duration,
+            rate,
+            || {
+                Ok(
+                    Self::from_final_rate(self.initial_rate, self.decline_rate, rate)?
+                        .incremental_duration,
+                )
+            },
+        )
+    }
+
     pub fn rate_at_time(&self, time: Time) -> ProductionRate<Time> {
         if time.value() > self.incremental_duration.value() {
             self.final_rate()
@@ -156,4 +329,69 @@ impl<Time: DeclineTimeUnit> LinearParameters<Time> {
             self.rate_at_time_without_clamping(time)
         }
     }
+
+    /// Evaluates `rate_at_time` for each time, writing the rates to `out`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `times` and `out` have different lengths.
+    pub fn rates_at_times(&self, times: &[Time], out: &mut [f64]) {
+        evaluate_at_times(times, out, self.incremental_duration, |time| {
+            self.rate_at_time_without_clamping(time).value()
+        });
+    }
+
+    /// Evaluates `incremental_volume_at_time` for each time, writing the volumes to `out`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `times` and `out` have different lengths.
+    pub fn incremental_volumes_at_times(&self, times: &[Time], out: &mut [f64]) {
+        evaluate_at_times(times, out, self.incremental_duration, |time| {
+            self.incremental_volume_at_time_without_clamping(time)
+        });
+    }
+
+    /// Like `rates_at_times`, but with `f32` times and rates, where the times are in the segment's
+    /// time unit. See [`Segment::rates_at_times_f32`](crate::Segment::rates_at_times_f32) for the
+    /// precision.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `times` and `out` have different lengths.
+    pub fn rates_at_times_f32(&self, times: &[f32], out: &mut [f32]) {
+        let initial_rate = self.initial_rate.value as f32;
+        let decline_rate = self.decline_rate.value() as f32;
+        evaluate_at_times_f32(times, out, self.incremental_duration, |time| {
+            kernels::linear_rate(initial_rate, decline_rate, time)
+        });
+    }
+
+    /// Like `incremental_volumes_at_times`, but with `f32` times and volumes. See
+    /// [`Segment::rates_at_times_f32`](crate::Segment::rates_at_times_f32) for the precision.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `times` and `out` have different lengths.
+    pub fn incremental_volumes_at_times_f32(&self, times: &[f32], out: &mut [f32]) {
+        let initial_rate = self.initial_rate.value as f32;
+        let decline_rate = self.decline_rate.value() as f32;
+        evaluate_at_times_f32(times, out, self.incremental_duration, |time| {
+            kernels::linear_incremental_volume(initial_rate, decline_rate, time)
+        });
+    }
+
+    /// The instantaneous nominal decline rate at the given time, which is `D / (1 - D * t)`. This
+    /// increases over time because the rate declines by a constant amount. Times past the end of
+    /// the segment are clamped to the end, like `rate_at_time`.
+    pub fn decline_rate_at_time(&self, time: Time) -> NominalDeclineRate<Time> {
+        let time = time.value().min(self.incremental_duration.value());
+        let decline_rate = self.decline_rate.value();
+        NominalDeclineRate::new(decline_rate / (-decline_rate).mul_add(time, 1.))
+    }
+
+    /// The derivative of the rate with respect to time, which is the constant slope `-q_i * D`.
+    pub fn rate_derivative_at_time(&self, _time: Time) -> f64 {
+        -self.initial_rate.value * self.decline_rate.value()
+    }
 }
@@ -0,0 +1,393 @@
+use crate::{
+    DeclineCurveAnalysisError, DeclineTimeUnit, NominalDeclineRate, ProductionRate, RateTruncation,
+    approx_gte, clamp_to_duration, evaluate_at_times, evaluate_at_times_f32,
+    inverse::time_at_incremental_volume, is_effectively_zero, math, max_duration,
+    numerical::find_root, validate_duration, validate_economic_limit_rate,
+    validate_incremental_volume, validate_non_zero_positive_rate, validate_positive,
+};
+
+/// A logistic growth model (LGM) segment, as described by Clark et al. (2011).
+///
+/// The cumulative volume is given by `K * t^n / (a + t^n)`, where `K` is the carrying capacity
+/// (the ultimate recoverable volume), and `a` is the value of `t^n` at which half of the carrying
+/// capacity has been produced. The rate is the derivative, `K * n * a * t^(n - 1) / (a + t^n)^2`.
+///
+/// Note that for exponents less than one, the rate at time zero is infinite.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogisticGrowthParameters<Time: DeclineTimeUnit> {
+    carrying_capacity: f64,
+    a: f64,
+    incremental_duration: Time,
+    exponent: f64,
+}
+
+impl<Time: DeclineTimeUnit> LogisticGrowthParameters<Time> {
+    pub fn carrying_capacity(&self) -> f64 {
+        self.carrying_capacity
+    }
+
+    pub fn a(&self) -> f64 {
+        self.a
+    }
+
+    pub fn incremental_duration(&self) -> Time {
+        self.incremental_duration
+    }
+
+    pub fn exponent(&self) -> f64 {
+        self.exponent
+    }
+
+    fn validate_inputs(
+        carrying_capacity: f64,
+        a: f64,
+        exponent: f64,
+    ) -> Result<(), DeclineCurveAnalysisError> {
+        validate_non_zero_positive_rate(carrying_capacity, "carrying capacity")?;
+        validate_non_zero_positive_rate(a, "a")?;
+        validate_non_zero_positive_rate(exponent, "exponent")
+    }
+
+    pub fn from_incremental_duration(
+        carrying_capacity: f64,
+        a: f64,
+        incremental_duration: Time,
+        exponent: f64,
+    ) -> Result<Self, DeclineCurveAnalysisError> {
+        Self::validate_inputs(carrying_capacity, a, exponent)?;
+        validate_duration(incremental_duration)?;
+
+        Ok(Self {
+            carrying_capacity,
+            a,
+            incremental_duration,
+            exponent,
+        })
+    }
+
+    pub fn from_incremental_volume(
+        carrying_capacity: f64,
+        a: f64,
+        incremental_volume: f64,
+        exponent: f64,
+    ) -> Result<Self, DeclineCurveAnalysisError> {
+        Self::validate_inputs(carrying_capacity, a, exponent)?;
+        validate_incremental_volume(incremental_volume)?;
+
+        // The cumulative volume approaches the carrying capacity as time approaches infinity, so
+        // it can never be reached.
+        if approx_gte(incremental_volume, carrying_capacity) {
+            return Err(DeclineCurveAnalysisError::CannotSolveDecline);
+        }
+
+        let incremental_duration = Time::from(math::powf(
+            (a * incremental_volume) / (carrying_capacity - incremental_volume),
+            1. / exponent,
+        ));
+        validate_duration(incremental_duration)?;
+
+        Ok(Self {
+            carrying_capacity,
+            a,
+            incremental_duration,
+            exponent,
+        })
+    }
+
+    /// Solves for the duration at which the rate reaches the final rate.
+    ///
+    /// For exponents greater than one, the rate rises to a peak before declining, so only the
+    /// declining portion after the peak is considered.
+    pub fn from_final_rate(
+        carrying_capacity: f64,
+        a: f64,
+        final_rate: ProductionRate<Time>,
+        exponent: f64,
+    ) -> Result<Self, DeclineCurveAnalysisError> {
+        Self::validate_inputs(carrying_capacity, a, exponent)?;
+        validate_non_zero_positive_rate(final_rate.value(), "final rate")?;
+
+        let mut result = Self {
+            carrying_capacity,
+            a,
+            incremental_duration: Time::from(0.),
+            exponent,
+        };
+
+        let peak_time = result.peak_time();
+        if final_rate.value() > result.rate_value_at_time(peak_time) {
+            return Err(DeclineCurveAnalysisError::CannotSolveDecline);
+        }
+
+        let incremental_duration = find_root(
+            |time| result.rate_value_at_time(time) - final_rate.value(),
+            peak_time,
+            max_duration::<Time>(),
+        )
+        .ok_or(DeclineCurveAnalysisError::DurationTooLong)?;
+
+        result.incremental_duration = Time::from(incremental_duration);
+        validate_duration(result.incremental_duration)?;
+
+        Ok(result)
+    }
+
+    /// The time at which the rate peaks, which is zero for exponents of one or less.
+    fn peak_time(&self) -> f64 {
+        if self.exponent <= 1. {
+            0.
+        } else {
+            math::powf(
+                self.a * (self.exponent - 1.) / (self.exponent + 1.),
+                1. / self.exponent,
+            )
+        }
+    }
+
+    fn rate_value_at_time(&self, time: f64) -> f64 {
+        let time_to_exponent = math::powf(time, self.exponent);
+        let denominator = self.a + time_to_exponent;
+
+        self.carrying_capacity * self.exponent * self.a * math::powf(time, self.exponent - 1.)
+            / (denominator * denominator)
+    }
+
+    fn incremental_volume_at_time_without_clamping(&self, time: Time) -> f64 {
+        if is_effectively_zero(time.value()) {
+            return 0.;
+        }
+
+        let time_to_exponent = math::powf(time.value(), self.exponent);
+        self.carrying_capacity * time_to_exponent / (self.a + time_to_exponent)
+    }
+
+    pub fn incremental_volume_at_time(&self, time: Time) -> f64 {
+        if time.value() > self.incremental_duration.value() {
+            self.incremental_volume()
+        } else {
+            self.incremental_volume_at_time_without_clamping(time)
+        }
+    }
+
+    /// The volume produced between two times, which are clamped to be within the segment. This is
+    /// negative if `end` is before `start`.
+    pub fn incremental_volume_between(&self, start: Time, end: Time) -> f64 {
+        let start = clamp_to_duration(start, self.incremental_duration);
+        let end = clamp_to_duration(end, self.incremental_duration);
+
+        self.incremental_volume_at_time_without_clamping(end)
+            - self.incremental_volume_at_time_without_clamping(start)
+    }
+
+    /// Returns the time at which the cumulative volume reaches the given volume, which is the
+    /// inverse of `incremental_volume_at_time`.
+    ///
+    /// This fails if the volume isn't reached before the end of the segment.
+    pub fn time_at_incremental_volume(
+        &self,
+        incremental_volume: f64,
+    ) -> Result<Time, DeclineCurveAnalysisError> {
+        time_at_incremental_volume(
+            self.incremental_volume(),
+            self.incremental_duration,
+            incremental_volume,
+            || {
+                Ok(Self::from_incremental_volume(
+                    self.carrying_capacity,
+                    self.a,
+                    incremental_volume,
+                    self.exponent,
+                )?
+                .incremental_duration)
+            },
+        )
+    }
+
+    /// Returns the rate at the time the cumulative volume reaches the given volume, for
+    /// rate-cumulative analysis.
+    ///
+    /// This fails if the volume isn't reached before the end of the segment.
+    ///
+    /// There's no closed form, so the time at which the volume is reached is solved for first.
+    pub fn rate_at_incremental_volume(
+        &self,
+        incremental_volume: f64,
+    ) -> Result<ProductionRate<Time>, DeclineCurveAnalysisError> {
+        Ok(self.rate_at_time(self.time_at_incremental_volume(incremental_volume)?))
+    }
+
+    pub fn incremental_volume(&self) -> f64 {
+        self.incremental_volume_at_time_without_clamping(self.incremental_duration)
+    }
+
+    fn rate_at_time_without_clamping(&self, time: Time) -> ProductionRate<Time> {
+        ProductionRate::new(self.rate_value_at_time(time.value()))
+    }
+
+    /// Returns a copy where every rate is multiplied by `factor`. The carrying capacity is scaled,
+    /// so the peak time is unchanged.
+    pub(crate) fn scale_rates(&self, factor: f64) -> Self {
+        Self {
+            carrying_capacity: self.carrying_capacity * factor,
+            ..self.clone()
+        }
+    }
+
+    /// Returns a copy of the parameters that ends when the rate falls to the economic limit, along
+    /// with the volume and duration that were removed.
+    ///
+    /// Only the declining portion after the peak is considered, so the segment is only truncated to
+    /// zero if the peak rate is below the economic limit.
+    pub fn truncate_at_rate(
+        &self,
+        economic_limit_rate: ProductionRate<Time>,
+    ) -> Result<RateTruncation<Self, Time>, DeclineCurveAnalysisError> {
+        validate_economic_limit_rate(economic_limit_rate)?;
+
+        let incremental_duration = if self.final_rate().value() >= economic_limit_rate.value() {
+            self.incremental_duration
+        } else {
+            match Self::from_final_rate(
+                self.carrying_capacity,
+                self.a,
+                economic_limit_rate,
+                self.exponent,
+            ) {
+                Ok(parameters) => Time::from(
+                    parameters
+                        .incremental_duration
+                        .value()
+                        .min(self.incremental_duration.value()),
+                ),
+                Err(DeclineCurveAnalysisError::CannotSolveDecline) => Time::from(0.),
+                Err(error) => return Err(error),
+            }
+        };
+
+        let parameters = Self::from_incremental_duration(
+            self.carrying_capacity,
+            self.a,
+            incremental_duration,
+            self.exponent,
+        )?;
+
+        Ok(RateTruncation {
+            truncated_volume: self.incremental_volume() - parameters.incremental_volume(),
+            truncated_duration: Time::from(
+                self.incremental_duration.value() - parameters.incremental_duration.value(),
+            ),
+            parameters,
+        })
+    }
+
+    pub fn final_rate(&self) -> ProductionRate<Time> {
+        self.rate_at_time_without_clamping(self.incremental_duration)
+    }
+
+    /// Returns the first time at which the rate reaches the given rate.
+    ///
+    /// For exponents greater than one, the rate rises to a peak before declining, so the rising
+    /// portion is checked before the declining portion. This fails if the rate isn't reached before
+    /// the end of the segment.
+    pub fn time_at_rate(
+        &self,
+        rate: ProductionRate<Time>,
+    ) -> Result<Time, DeclineCurveAnalysisError> {
+        validate_positive(rate.value(), "rate")?;
+
+        let incremental_duration = self.incremental_duration.value();
+        let peak_time = self.peak_time().min(incremental_duration);
+        let difference = |time| self.rate_value_at_time(time) - rate.value();
+
+        find_root(difference, 0., peak_time)
+            .or_else(|| find_root(difference, peak_time, incremental_duration))
+            .map(Time::from)
+            .ok_or(DeclineCurveAnalysisError::CannotSolveDecline)
+    }
+
+    pub fn rate_at_time(&self, time: Time) -> ProductionRate<Time> {
+        if time.value() > self.incremental_duration.value() {
+            self.final_rate()
+        } else {
+            self.rate_at_time_without_clamping(time)
+        }
+    }
+
+    /// Evaluates `rate_at_time` for each time, writing the rates to `out`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `times` and `out` have different lengths.
+    pub fn rates_at_times(&self, times: &[Time], out: &mut [f64]) {
+        evaluate_at_times(times, out, self.incremental_duration, |time| {
+            self.rate_at_time_without_clamping(time).value()
+        });
+    }
+
+    /// Evaluates `incremental_volume_at_time` for each time, writing the volumes to `out`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `times` and `out` have different lengths.
+    pub fn incremental_volumes_at_times(&self, times: &[Time], out: &mut [f64]) {
+        evaluate_at_times(times, out, self.incremental_duration, |time| {
+            self.incremental_volume_at_time_without_clamping(time)
+        });
+    }
+
+    /// Like `rates_at_times`, but with `f32` times and rates, where the times are in the segment's
+    /// time unit. See [`Segment::rates_at_times_f32`](crate::Segment::rates_at_times_f32) for the
+    /// precision.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `times` and `out` have different lengths.
+    pub fn rates_at_times_f32(&self, times: &[f32], out: &mut [f32]) {
+        evaluate_at_times_f32(times, out, self.incremental_duration, |time| {
+            self.rate_at_time_without_clamping(Time::from(time.into()))
+                .value() as f32
+        });
+    }
+
+    /// Like `incremental_volumes_at_times`, but with `f32` times and volumes. See
+    /// [`Segment::rates_at_times_f32`](crate::Segment::rates_at_times_f32) for the precision.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `times` and `out` have different lengths.
+    pub fn incremental_volumes_at_times_f32(&self, times: &[f32], out: &mut [f32]) {
+        evaluate_at_times_f32(times, out, self.incremental_duration, |time| {
+            self.incremental_volume_at_time_without_clamping(Time::from(time.into())) as f32
+        });
+    }
+
+    /// The instantaneous nominal decline rate at the given time, which is
+    /// `2 * n * t^(n - 1) / (a + t^n) - (n - 1) / t`. This is negative while the rate is rising
+    /// before the peak. Times past the end of the segment are clamped to the end, like
+    /// `rate_at_time`.
+    pub fn decline_rate_at_time(&self, time: Time) -> NominalDeclineRate<Time> {
+        let time = time.value().min(self.incremental_duration.value());
+        NominalDeclineRate::new(
+            2. * self.exponent * math::powf(time, self.exponent - 1.)
+                / (self.a + math::powf(time, self.exponent))
+                - (self.exponent - 1.) / time,
+        )
+    }
+
+    /// The derivative of the rate with respect to time, which is
+    /// `K * n * a * t^(n - 2) * ((n - 1) * a - (n + 1) * t^n) / (a + t^n)^3`. This is positive
+    /// while the rate is rising before the peak. Times past the end of the segment are clamped to
+    /// the end, like `decline_rate_at_time`.
+    pub fn rate_derivative_at_time(&self, time: Time) -> f64 {
+        let time = time.value().min(self.incremental_duration.value());
+        let time_to_exponent = math::powf(time, self.exponent);
+        let denominator = self.a + time_to_exponent;
+
+        self.carrying_capacity
+            * self.exponent
+            * self.a
+            * math::powf(time, self.exponent - 2.)
+            * ((self.exponent - 1.) * self.a - (self.exponent + 1.) * time_to_exponent)
+            / (denominator * denominator * denominator)
+    }
+}
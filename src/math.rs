@@ -0,0 +1,93 @@
+//! Floating-point functions that aren't available in `core`.
+//!
+//! Transcendental functions (e.g., `exp` and `powf`) are called through the functions in this
+//! module. Their results depend on the platform's math library, so they use `libm` when the
+//! standard library isn't available or the `deterministic` feature is enabled. `libm` is written in
+//! Rust and only uses basic arithmetic, so it gives bit-identical results on every platform.
+//!
+//! Correctly rounded operations (e.g., `sqrt` and `mul_add`) give the same results everywhere, so
+//! they're called as methods, through [`FloatMath`] in `no_std` builds.
+
+/// Uses `libm` for a function of one argument, or the `f64` method with the same name.
+macro_rules! unary_functions {
+    ($($name:ident => $libm:ident),* $(,)?) => {
+        $(
+            #[inline]
+            pub(crate) fn $name(value: f64) -> f64 {
+                #[cfg(any(not(feature = "std"), feature = "deterministic"))]
+                return libm::$libm(value);
+                #[cfg(all(feature = "std", not(feature = "deterministic")))]
+                return value.$name();
+            }
+        )*
+    };
+}
+
+unary_functions!(
+    cos => cos,
+    exp => exp,
+    exp_m1 => expm1,
+    ln => log,
+    ln_1p => log1p,
+    log10 => log10,
+);
+
+#[inline]
+pub(crate) fn powf(base: f64, exponent: f64) -> f64 {
+    #[cfg(any(not(feature = "std"), feature = "deterministic"))]
+    return libm::pow(base, exponent);
+    #[cfg(all(feature = "std", not(feature = "deterministic")))]
+    return base.powf(exponent);
+}
+
+/// Returns `base ^ exponent`, which uses `pow` in deterministic builds, because the precision of
+/// `powi` isn't specified and may differ between platforms.
+#[inline]
+pub(crate) fn powi(base: f64, exponent: i32) -> f64 {
+    #[cfg(any(not(feature = "std"), feature = "deterministic"))]
+    return libm::pow(base, exponent.into());
+    #[cfg(all(feature = "std", not(feature = "deterministic")))]
+    return base.powi(exponent);
+}
+
+/// Correctly rounded floating-point methods that aren't available in `core`, which use `libm`
+/// when the standard library isn't available.
+///
+/// With the standard library, the inherent `f64` methods take precedence, so this is only
+/// imported in `no_std` builds.
+#[cfg(not(feature = "std"))]
+pub(crate) trait FloatMath {
+    fn ceil(self) -> Self;
+    fn floor(self) -> Self;
+    fn mul_add(self, a: Self, b: Self) -> Self;
+    fn rem_euclid(self, rhs: Self) -> Self;
+    fn sqrt(self) -> Self;
+}
+
+#[cfg(not(feature = "std"))]
+impl FloatMath for f64 {
+    fn ceil(self) -> Self {
+        libm::ceil(self)
+    }
+
+    fn floor(self) -> Self {
+        libm::floor(self)
+    }
+
+    fn mul_add(self, a: Self, b: Self) -> Self {
+        libm::fma(self, a, b)
+    }
+
+    fn rem_euclid(self, rhs: Self) -> Self {
+        let remainder = libm::fmod(self, rhs);
+        if remainder < 0. {
+            remainder + rhs.abs()
+        } else {
+            remainder
+        }
+    }
+
+    fn sqrt(self) -> Self {
+        libm::sqrt(self)
+    }
+}
@@ -0,0 +1,176 @@
+use crate::{DeclineCurveAnalysisError, DeclineTimeUnit, Forecast, Phase, Segment};
+use alloc::{string::String, vec::Vec};
+
+/// How the parameters of a segment or forecast were chosen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum ParameterSource {
+    /// The parameters were fit to production history.
+    Fit,
+    /// The parameters were entered or adjusted by an analyst.
+    Manual,
+}
+
+/// Descriptive information about a segment or forecast, which isn't used in any calculation.
+///
+/// Every field is optional, and fields that aren't set are omitted when serialized.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default, deny_unknown_fields)
+)]
+pub struct Metadata {
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub name: Option<String>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub phase: Option<Phase>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub source: Option<ParameterSource>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub analyst: Option<String>,
+    /// When the segment or forecast was created or last edited, e.g., as an ISO 8601 string.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub timestamp: Option<String>,
+}
+
+/// A value with metadata attached, e.g., a segment with its name and source.
+///
+/// The metadata can be any type, so that applications can attach their own fields.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Labeled<T, Meta = Metadata> {
+    pub value: T,
+    pub metadata: Meta,
+}
+
+impl<T, Meta> Labeled<T, Meta> {
+    pub fn new(value: T, metadata: Meta) -> Self {
+        Self { value, metadata }
+    }
+
+    pub fn map<Mapped>(self, f: impl FnOnce(T) -> Mapped) -> Labeled<Mapped, Meta> {
+        Labeled {
+            value: f(self.value),
+            metadata: self.metadata,
+        }
+    }
+}
+
+/// A forecast with metadata for the whole forecast and for each of its segments.
+///
+/// The segment metadata is kept alongside the forecast, in the order of its segments, so the
+/// forecast is evaluated exactly as if it had no metadata.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LabeledForecast<Time: DeclineTimeUnit, Meta = Metadata> {
+    forecast: Forecast<Time>,
+    metadata: Meta,
+    segment_metadata: Vec<Meta>,
+}
+
+impl<Time: DeclineTimeUnit, Meta> LabeledForecast<Time, Meta> {
+    /// Attaches metadata to a forecast, where there must be one segment metadata for each segment.
+    pub fn new(
+        forecast: Forecast<Time>,
+        metadata: Meta,
+        segment_metadata: Vec<Meta>,
+    ) -> Result<Self, DeclineCurveAnalysisError> {
+        if segment_metadata.len() != forecast.segments().len() {
+            return Err(DeclineCurveAnalysisError::InputLengthMismatch {
+                field: "segment metadata",
+                count: segment_metadata.len(),
+                expected: forecast.segments().len(),
+            });
+        }
+
+        Ok(Self {
+            forecast,
+            metadata,
+            segment_metadata,
+        })
+    }
+
+    /// Attaches metadata to a forecast, where each segment has the default metadata.
+    pub fn from_forecast(forecast: Forecast<Time>, metadata: Meta) -> Self
+    where
+        Meta: Default,
+    {
+        let segment_metadata = forecast
+            .segments()
+            .iter()
+            .map(|_| Meta::default())
+            .collect();
+
+        Self {
+            forecast,
+            metadata,
+            segment_metadata,
+        }
+    }
+
+    /// Creates a forecast from labeled segments.
+    pub fn from_segments(segments: Vec<Labeled<Segment<Time>, Meta>>, metadata: Meta) -> Self {
+        let (segments, segment_metadata) = segments
+            .into_iter()
+            .map(|segment| (segment.value, segment.metadata))
+            .unzip();
+
+        Self {
+            forecast: Forecast::new(segments),
+            metadata,
+            segment_metadata,
+        }
+    }
+
+    pub fn forecast(&self) -> &Forecast<Time> {
+        &self.forecast
+    }
+
+    pub fn into_forecast(self) -> Forecast<Time> {
+        self.forecast
+    }
+
+    pub fn metadata(&self) -> &Meta {
+        &self.metadata
+    }
+
+    pub fn metadata_mut(&mut self) -> &mut Meta {
+        &mut self.metadata
+    }
+
+    /// The metadata of each segment, in the order of the segments.
+    pub fn segment_metadata(&self) -> &[Meta] {
+        &self.segment_metadata
+    }
+
+    pub fn segment_metadata_mut(&mut self) -> &mut [Meta] {
+        &mut self.segment_metadata
+    }
+
+    /// Returns each segment along with its metadata.
+    pub fn labeled_segments(&self) -> impl Iterator<Item = Labeled<&Segment<Time>, &Meta>> + '_ {
+        self.forecast
+            .segments()
+            .iter()
+            .zip(&self.segment_metadata)
+            .map(|(segment, metadata)| Labeled::new(segment, metadata))
+    }
+
+    /// Splits the forecast into its labeled segments and the forecast metadata. The initial
+    /// cumulative volume of the forecast is dropped.
+    pub fn into_labeled_segments(self) -> (Vec<Labeled<Segment<Time>, Meta>>, Meta) {
+        let segments = self
+            .forecast
+            .into_segments()
+            .into_iter()
+            .zip(self.segment_metadata)
+            .map(|(segment, metadata)| Labeled::new(segment, metadata))
+            .collect();
+
+        (segments, self.metadata)
+    }
+}
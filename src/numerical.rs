@@ -0,0 +1,523 @@
+use crate::math;
+#[cfg(not(feature = "std"))]
+use crate::math::FloatMath;
+use alloc::{vec, vec::Vec};
+
+/// Maximum recursion depth for adaptive integration.
+const MAX_INTEGRATION_DEPTH: u32 = 50;
+
+/// Relative tolerance for adaptive integration.
+const INTEGRATION_TOLERANCE: f64 = 1e-12;
+
+/// Maximum number of iterations for root finding.
+const MAX_ROOT_ITERATIONS: u32 = 200;
+
+/// Integrates `f` over `[lower, upper]` using adaptive Simpson's rule.
+///
+/// This is used for segments where the cumulative volume has no closed form.
+pub(crate) fn integrate(f: impl Fn(f64) -> f64, lower: f64, upper: f64) -> f64 {
+    if upper <= lower {
+        return 0.;
+    }
+
+    let f_lower = f(lower);
+    let f_upper = f(upper);
+    let middle = 0.5 * (lower + upper);
+    let f_middle = f(middle);
+    let whole = simpson(lower, upper, f_lower, f_middle, f_upper);
+    let tolerance = INTEGRATION_TOLERANCE * whole.abs().max(1.);
+
+    adaptive_simpson(
+        &f,
+        lower,
+        upper,
+        f_lower,
+        f_middle,
+        f_upper,
+        whole,
+        tolerance,
+        MAX_INTEGRATION_DEPTH,
+    )
+}
+
+fn simpson(lower: f64, upper: f64, f_lower: f64, f_middle: f64, f_upper: f64) -> f64 {
+    (upper - lower) / 6. * (f_lower + 4. * f_middle + f_upper)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn adaptive_simpson(
+    f: &impl Fn(f64) -> f64,
+    lower: f64,
+    upper: f64,
+    f_lower: f64,
+    f_middle: f64,
+    f_upper: f64,
+    whole: f64,
+    tolerance: f64,
+    depth: u32,
+) -> f64 {
+    let middle = 0.5 * (lower + upper);
+    let left_middle = 0.5 * (lower + middle);
+    let right_middle = 0.5 * (middle + upper);
+    let f_left_middle = f(left_middle);
+    let f_right_middle = f(right_middle);
+    let left = simpson(lower, middle, f_lower, f_left_middle, f_middle);
+    let right = simpson(middle, upper, f_middle, f_right_middle, f_upper);
+    if !(whole.is_finite() && left.is_finite() && right.is_finite()) {
+        // A non-finite integrand never meets the tolerance, so don't subdivide it.
+        return left + right;
+    }
+    let delta = left + right - whole;
+
+    if depth == 0 || delta.abs() <= 15. * tolerance {
+        return left + right + delta / 15.;
+    }
+
+    adaptive_simpson(
+        f,
+        lower,
+        middle,
+        f_lower,
+        f_left_middle,
+        f_middle,
+        left,
+        0.5 * tolerance,
+        depth - 1,
+    ) + adaptive_simpson(
+        f,
+        middle,
+        upper,
+        f_middle,
+        f_right_middle,
+        f_upper,
+        right,
+        0.5 * tolerance,
+        depth - 1,
+    )
+}
+
+/// Finds a root of `f` within `[lower, upper]` using bisection.
+///
+/// Returns `None` if the function doesn't change sign over the interval.
+pub(crate) fn find_root(f: impl Fn(f64) -> f64, mut lower: f64, mut upper: f64) -> Option<f64> {
+    let mut f_lower = f(lower);
+    let f_upper = f(upper);
+
+    if f_lower == 0. {
+        return Some(lower);
+    }
+    if f_upper == 0. {
+        return Some(upper);
+    }
+    if f_lower.is_nan() || f_upper.is_nan() || f_lower.signum() == f_upper.signum() {
+        return None;
+    }
+
+    for _ in 0..MAX_ROOT_ITERATIONS {
+        let middle = 0.5 * (lower + upper);
+        if middle <= lower || middle >= upper {
+            break;
+        }
+
+        let f_middle = f(middle);
+        if f_middle == 0. {
+            return Some(middle);
+        }
+
+        if f_middle.signum() == f_lower.signum() {
+            lower = middle;
+            f_lower = f_middle;
+        } else {
+            upper = middle;
+        }
+    }
+
+    Some(0.5 * (lower + upper))
+}
+
+/// Maximum number of iterations for nonlinear least squares.
+const MAX_LEAST_SQUARES_ITERATIONS: usize = 500;
+
+/// Relative change in the cost below which nonlinear least squares is considered converged.
+const LEAST_SQUARES_TOLERANCE: f64 = 1e-14;
+
+/// The solution of a nonlinear least-squares problem.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct LeastSquaresSolution {
+    pub(crate) parameters: Vec<f64>,
+    pub(crate) iterations: usize,
+}
+
+/// Clamps each parameter to its bounds.
+fn clamp_to_bounds(parameters: &mut [f64], lower: &[f64], upper: &[f64]) {
+    for ((parameter, lower), upper) in parameters.iter_mut().zip(lower).zip(upper) {
+        *parameter = parameter.clamp(*lower, *upper);
+    }
+}
+
+fn sum_of_squares(values: &[f64]) -> f64 {
+    values.iter().map(|value| value * value).sum()
+}
+
+/// Approximates the Jacobian of `residuals` with forward differences.
+fn finite_difference_jacobian(
+    residuals: &impl Fn(&[f64]) -> Vec<f64>,
+    parameters: &[f64],
+    current: &[f64],
+    lower: &[f64],
+    upper: &[f64],
+) -> Vec<Vec<f64>> {
+    let mut jacobian = vec![vec![0.; parameters.len()]; current.len()];
+    let mut shifted = parameters.to_vec();
+
+    for column in 0..parameters.len() {
+        let mut step = 1e-7 * parameters[column].abs().max(1e-7);
+
+        // Step backwards if stepping forwards would leave the bounds.
+        if parameters[column] + step > upper[column] && parameters[column] - step >= lower[column] {
+            step = -step;
+        }
+
+        shifted[column] = parameters[column] + step;
+        let perturbed = residuals(&shifted);
+        for (row, jacobian_row) in jacobian.iter_mut().enumerate() {
+            jacobian_row[column] = (perturbed[row] - current[row]) / step;
+        }
+        shifted[column] = parameters[column];
+    }
+
+    jacobian
+}
+
+/// Minimizes the sum of squared residuals using the Levenberg-Marquardt algorithm, keeping the
+/// parameters within the given bounds.
+///
+/// The Jacobian is approximated with finite differences. Returns `None` if the residuals aren't
+/// finite at the initial parameters, or if the solve doesn't converge.
+pub(crate) fn levenberg_marquardt(
+    residuals: impl Fn(&[f64]) -> Vec<f64>,
+    initial: &[f64],
+    lower: &[f64],
+    upper: &[f64],
+) -> Option<LeastSquaresSolution> {
+    levenberg_marquardt_with_jacobian(
+        &residuals,
+        |parameters, current| {
+            finite_difference_jacobian(&residuals, parameters, current, lower, upper)
+        },
+        initial,
+        lower,
+        upper,
+    )
+}
+
+/// Like [`levenberg_marquardt`], but with a Jacobian of the residuals with respect to the
+/// parameters, which is called with the parameters and the residuals at those parameters.
+pub(crate) fn levenberg_marquardt_with_jacobian(
+    residuals: impl Fn(&[f64]) -> Vec<f64>,
+    jacobian: impl Fn(&[f64], &[f64]) -> Vec<Vec<f64>>,
+    initial: &[f64],
+    lower: &[f64],
+    upper: &[f64],
+) -> Option<LeastSquaresSolution> {
+    let mut parameters = initial.to_vec();
+    clamp_to_bounds(&mut parameters, lower, upper);
+
+    let mut current = residuals(&parameters);
+    let mut cost = sum_of_squares(&current);
+    if !cost.is_finite() {
+        return None;
+    }
+
+    let parameter_count = parameters.len();
+    let mut damping = 1e-3;
+
+    for iteration in 1..=MAX_LEAST_SQUARES_ITERATIONS {
+        let jacobian = jacobian(&parameters, &current);
+
+        // Form the normal equations `J^T J` and `J^T r`.
+        let mut normal = vec![vec![0.; parameter_count]; parameter_count];
+        let mut gradient = vec![0.; parameter_count];
+        for (jacobian_row, residual) in jacobian.iter().zip(&current) {
+            for i in 0..parameter_count {
+                gradient[i] += jacobian_row[i] * residual;
+                for j in 0..parameter_count {
+                    normal[i][j] += jacobian_row[i] * jacobian_row[j];
+                }
+            }
+        }
+
+        let mut improved = false;
+        while damping < 1e16 {
+            let mut damped = normal.clone();
+            for (i, row) in damped.iter_mut().enumerate() {
+                row[i] += damping * normal[i][i].max(1e-12);
+            }
+
+            let negative_gradient: Vec<f64> = gradient.iter().map(|value| -value).collect();
+            let Some(step) = solve_linear_system(damped, negative_gradient) else {
+                damping *= 10.;
+                continue;
+            };
+
+            let mut candidate: Vec<f64> =
+                parameters.iter().zip(&step).map(|(p, s)| p + s).collect();
+            clamp_to_bounds(&mut candidate, lower, upper);
+
+            let candidate_residuals = residuals(&candidate);
+            let candidate_cost = sum_of_squares(&candidate_residuals);
+
+            if candidate_cost.is_finite() && candidate_cost <= cost {
+                let relative_change = (cost - candidate_cost) / cost.max(f64::MIN_POSITIVE);
+
+                parameters = candidate;
+                current = candidate_residuals;
+                cost = candidate_cost;
+                damping = (damping / 10.).max(1e-12);
+                improved = true;
+
+                if relative_change <= LEAST_SQUARES_TOLERANCE {
+                    return Some(LeastSquaresSolution {
+                        parameters,
+                        iterations: iteration,
+                    });
+                }
+                break;
+            }
+
+            damping *= 10.;
+        }
+
+        // If no step improves the cost, then we're at a minimum.
+        if !improved {
+            return Some(LeastSquaresSolution {
+                parameters,
+                iterations: iteration,
+            });
+        }
+    }
+
+    None
+}
+
+/// Solves the linear system `a * x = b` using Gaussian elimination with partial pivoting.
+///
+/// Returns `None` if the matrix is singular.
+pub(crate) fn solve_linear_system(mut a: Vec<Vec<f64>>, mut b: Vec<f64>) -> Option<Vec<f64>> {
+    let size = b.len();
+
+    for column in 0..size {
+        let pivot =
+            (column..size).max_by(|&i, &j| a[i][column].abs().total_cmp(&a[j][column].abs()))?;
+        if a[pivot][column].abs() <= f64::MIN_POSITIVE || !a[pivot][column].is_finite() {
+            return None;
+        }
+        a.swap(column, pivot);
+        b.swap(column, pivot);
+
+        let pivot_row = a[column].clone();
+        for row in (column + 1)..size {
+            let factor = a[row][column] / pivot_row[column];
+            for (value, pivot_value) in a[row][column..].iter_mut().zip(&pivot_row[column..]) {
+                *value -= factor * pivot_value;
+            }
+            b[row] -= factor * b[column];
+        }
+    }
+
+    let mut solution = vec![0.; size];
+    for row in (0..size).rev() {
+        let sum: f64 = ((row + 1)..size).map(|k| a[row][k] * solution[k]).sum();
+        solution[row] = (b[row] - sum) / a[row][row];
+    }
+
+    Some(solution)
+}
+
+/// Estimates the parameter covariance of a nonlinear least-squares solution as
+/// `s^2 (J^T J)^-1`, where `s^2` is the residual variance.
+///
+/// The Jacobian of the residuals with respect to the parameters is called with the parameters and
+/// the residuals at those parameters. Returns `None` if there are no residual degrees of freedom,
+/// or if `J^T J` is singular.
+pub(crate) fn least_squares_covariance(
+    residuals: impl Fn(&[f64]) -> Vec<f64>,
+    jacobian: impl Fn(&[f64], &[f64]) -> Vec<Vec<f64>>,
+    parameters: &[f64],
+) -> Option<Vec<Vec<f64>>> {
+    let parameter_count = parameters.len();
+    let current = residuals(parameters);
+    if current.len() <= parameter_count {
+        return None;
+    }
+
+    let jacobian = jacobian(parameters, &current);
+    let mut normal = vec![vec![0.; parameter_count]; parameter_count];
+    for jacobian_row in &jacobian {
+        for i in 0..parameter_count {
+            for j in 0..parameter_count {
+                normal[i][j] += jacobian_row[i] * jacobian_row[j];
+            }
+        }
+    }
+
+    let variance = sum_of_squares(&current) / (current.len() - parameter_count) as f64;
+
+    // Invert `J^T J` one column at a time.
+    let mut covariance = vec![vec![0.; parameter_count]; parameter_count];
+    for column in 0..parameter_count {
+        let mut unit = vec![0.; parameter_count];
+        unit[column] = 1.;
+        let inverse_column = solve_linear_system(normal.clone(), unit)?;
+        for (row, value) in inverse_column.into_iter().enumerate() {
+            covariance[row][column] = variance * value;
+        }
+    }
+
+    covariance
+        .iter()
+        .flatten()
+        .all(|value| value.is_finite())
+        .then_some(covariance)
+}
+
+/// Approximates the quantile function of the standard normal distribution using Acklam's
+/// rational approximation, which has a relative error below 1.15e-9.
+pub(crate) fn standard_normal_quantile(probability: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969683028665376e1,
+        2.209460984245205e2,
+        -2.759285104469687e2,
+        1.38357751867269e2,
+        -3.066479806614716e1,
+        2.506628277459239,
+    ];
+    const B: [f64; 5] = [
+        -5.447609879822406e1,
+        1.615858368580409e2,
+        -1.556989798598866e2,
+        6.680131188771972e1,
+        -1.328068155288572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784894002430293e-3,
+        -3.223964580411365e-1,
+        -2.400758277161838,
+        -2.549732539343734,
+        4.374664141464968,
+        2.938163982698783,
+    ];
+    const D: [f64; 4] = [
+        7.784695709041462e-3,
+        3.224671290700398e-1,
+        2.445134137142996,
+        3.754408661907416,
+    ];
+    const LOW: f64 = 0.02425;
+
+    let tail = |q: f64| {
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.)
+    };
+
+    if probability < LOW {
+        tail((-2. * math::ln(probability)).sqrt())
+    } else if probability > 1. - LOW {
+        -tail((-2. * math::ln(1. - probability)).sqrt())
+    } else {
+        let q = probability - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integrate_polynomial() {
+        insta::assert_snapshot!(integrate(|x| x * x, 0., 3.), @"9");
+    }
+
+    #[test]
+    fn integrate_empty_interval() {
+        insta::assert_snapshot!(integrate(|x| x, 1., 1.), @"0");
+    }
+
+    #[test]
+    fn integrate_non_finite() {
+        let evaluations = core::cell::Cell::new(0);
+        let integral = integrate(
+            |x| {
+                evaluations.set(evaluations.get() + 1);
+                if x > 0.5 { f64::NAN } else { x }
+            },
+            0.,
+            1.,
+        );
+        assert!(integral.is_nan());
+        assert_eq!(evaluations.get(), 5);
+    }
+
+    #[test]
+    fn find_root_sqrt_two() {
+        let root = find_root(|x| x * x - 2., 0., 2.).unwrap();
+        assert!((root - 2_f64.sqrt()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn find_root_no_sign_change() {
+        assert_eq!(find_root(|x| x * x + 1., 0., 2.), None);
+    }
+
+    #[test]
+    fn solve_linear_system_singular() {
+        assert_eq!(
+            solve_linear_system(vec![vec![1., 2.], vec![2., 4.]], vec![1., 2.]),
+            None
+        );
+    }
+
+    #[test]
+    fn levenberg_marquardt_line() {
+        // Fit `y = m * x + c` to points on the line `y = 2x + 1`.
+        let points = [(0., 1.), (1., 3.), (2., 5.), (3., 7.)];
+        let solution = levenberg_marquardt(
+            |p| points.iter().map(|(x, y)| p[0] * x + p[1] - y).collect(),
+            &[0., 0.],
+            &[-10., -10.],
+            &[10., 10.],
+        )
+        .unwrap();
+
+        assert!((solution.parameters[0] - 2.).abs() < 1e-9);
+        assert!((solution.parameters[1] - 1.).abs() < 1e-9);
+    }
+
+    #[test]
+    fn least_squares_covariance_line() {
+        // Fit `y = m * x + c`, where the residuals are known, so the covariance matches the
+        // ordinary least-squares formula.
+        let points = [(0., 1.1), (1., 2.9), (2., 5.2), (3., 6.8)];
+        let covariance = least_squares_covariance(
+            |p| points.iter().map(|(x, y)| p[0] * x + p[1] - y).collect(),
+            |_, _| points.iter().map(|(x, _)| vec![*x, 1.]).collect(),
+            &[1.94, 1.09],
+        )
+        .unwrap();
+
+        // `s^2 = 0.082 / 2` and `(X^T X)^-1 = [[0.2, -0.3], [-0.3, 0.7]]`.
+        assert!((covariance[0][0] - 0.0082).abs() < 1e-6);
+        assert!((covariance[0][1] + 0.0123).abs() < 1e-6);
+        assert!((covariance[1][1] - 0.0287).abs() < 1e-6);
+    }
+
+    #[test]
+    fn standard_normal_quantiles() {
+        insta::assert_snapshot!(standard_normal_quantile(0.5), @"0");
+        insta::assert_snapshot!(standard_normal_quantile(0.975) as f32, @"1.959964");
+        insta::assert_snapshot!(standard_normal_quantile(0.01) as f32, @"-2.3263478");
+    }
+}
@@ -0,0 +1,222 @@
+use crate::{
+    DeclineCurveAnalysisError, DeclineTimeUnit, Segment, validate_finite,
+    validate_non_zero_positive_rate,
+};
+use alloc::vec::Vec;
+
+/// The volume produced over a single period.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PeriodVolume<Time: DeclineTimeUnit> {
+    /// The start of the period, relative to the start of the segment or forecast.
+    pub start: Time,
+    /// The end of the period, relative to the start of the segment or forecast. The last period is
+    /// shortened so that it ends at the end of the segment or forecast.
+    pub end: Time,
+    pub volume: f64,
+}
+
+/// The boundaries of a sequence of consecutive periods, which can be used to evaluate many
+/// forecasts on the same schedule.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeGrid<Time: DeclineTimeUnit> {
+    boundaries: Vec<Time>,
+}
+
+impl<Time: DeclineTimeUnit> TimeGrid<Time> {
+    /// Creates a grid from the boundaries of the periods, where each period spans from one
+    /// boundary to the next. The boundaries must be finite and strictly increasing.
+    pub fn new(boundaries: Vec<Time>) -> Result<Self, DeclineCurveAnalysisError> {
+        for boundary in &boundaries {
+            validate_finite(boundary.value(), "time grid boundary")?;
+        }
+
+        if boundaries
+            .windows(2)
+            .any(|window| window[1].value() <= window[0].value())
+        {
+            return Err(DeclineCurveAnalysisError::NotStrictlyIncreasing {
+                field: "time grid boundaries",
+            });
+        }
+
+        Ok(Self { boundaries })
+    }
+
+    /// Creates a grid of periods with the same length, starting at time zero.
+    pub fn uniform(
+        period_length: Time,
+        period_count: usize,
+    ) -> Result<Self, DeclineCurveAnalysisError> {
+        validate_period_length(period_length)?;
+
+        let boundaries = core::iter::once(0.)
+            .chain(fixed_period_boundaries(period_length.value()))
+            .take(period_count + 1)
+            .map(Time::from)
+            .collect();
+
+        Ok(Self { boundaries })
+    }
+
+    pub fn boundaries(&self) -> &[Time] {
+        &self.boundaries
+    }
+
+    pub fn period_count(&self) -> usize {
+        self.boundaries.len().saturating_sub(1)
+    }
+
+    /// Returns a copy of the grid with `offset` subtracted from every boundary.
+    pub(crate) fn shifted(&self, offset: f64) -> Self {
+        Self {
+            boundaries: self
+                .boundaries
+                .iter()
+                .map(|boundary| Time::from(boundary.value() - offset))
+                .collect(),
+        }
+    }
+}
+
+/// Writes the volume produced by the segments in each period of the grid to `out`. Periods
+/// outside of the segments have zero volume.
+///
+/// # Panics
+///
+/// Panics if `out` doesn't have one value for each period of the grid.
+pub(crate) fn grid_volumes<Time: DeclineTimeUnit>(
+    segments: &[Segment<Time>],
+    grid: &TimeGrid<Time>,
+    out: &mut [f64],
+) {
+    assert_eq!(
+        grid.period_count(),
+        out.len(),
+        "expected the output to have one value for each period"
+    );
+
+    let mut cursor = CumulativeVolumeCursor::new(segments);
+    let mut boundary_volumes = grid
+        .boundaries
+        .iter()
+        .map(|boundary| cursor.incremental_volume_at_time(boundary.value()));
+
+    let Some(mut start_volume) = boundary_volumes.next() else {
+        return;
+    };
+
+    for (out, end_volume) in out.iter_mut().zip(boundary_volumes) {
+        *out = end_volume - start_volume;
+        start_volume = end_volume;
+    }
+}
+
+/// Evaluates the cumulative volume of a sequence of segments at non-decreasing times, which only
+/// requires a single pass over the segments.
+struct CumulativeVolumeCursor<'a, Time: DeclineTimeUnit> {
+    segments: &'a [Segment<Time>],
+    index: usize,
+    segment_start: f64,
+    completed_volume: f64,
+}
+
+impl<'a, Time: DeclineTimeUnit> CumulativeVolumeCursor<'a, Time> {
+    fn new(segments: &'a [Segment<Time>]) -> Self {
+        Self {
+            segments,
+            index: 0,
+            segment_start: 0.,
+            completed_volume: 0.,
+        }
+    }
+
+    fn incremental_volume_at_time(&mut self, time: f64) -> f64 {
+        let time = time.max(0.);
+
+        while let Some(segment) = self.segments.get(self.index) {
+            let segment_end = self.segment_start + segment.incremental_duration().value();
+            if time < segment_end {
+                return self.completed_volume
+                    + segment.incremental_volume_at_time(Time::from(time - self.segment_start));
+            }
+
+            self.completed_volume += segment.incremental_volume();
+            self.segment_start = segment_end;
+            self.index += 1;
+        }
+
+        self.completed_volume
+    }
+}
+
+/// Validates that a period length is positive, finite, and non-zero.
+pub(crate) fn validate_period_length<Time: DeclineTimeUnit>(
+    period_length: Time,
+) -> Result<(), DeclineCurveAnalysisError> {
+    validate_non_zero_positive_rate(period_length.value(), "period length")
+}
+
+/// Returns the boundaries for periods of a fixed length, which are computed by multiplication so
+/// that round-off doesn't accumulate.
+pub(crate) fn fixed_period_boundaries(period_length: f64) -> impl Iterator<Item = f64> {
+    (1..).map(move |index| index as f64 * period_length)
+}
+
+/// Yields the volume between each consecutive pair of boundaries, starting from time zero, until
+/// the end of the duration, where `volume_between` calculates the volume between two times.
+pub(crate) fn period_volumes_between<Time: DeclineTimeUnit>(
+    incremental_duration: f64,
+    boundaries: impl IntoIterator<Item = f64>,
+    mut volume_between: impl FnMut(Time, Time) -> f64,
+) -> impl Iterator<Item = PeriodVolume<Time>> {
+    let mut start = 0.;
+
+    boundaries.into_iter().map_while(move |boundary| {
+        if start >= incremental_duration {
+            return None;
+        }
+
+        let end = boundary.min(incremental_duration);
+        let period = PeriodVolume {
+            start: Time::from(start),
+            end: Time::from(end),
+            volume: volume_between(Time::from(start), Time::from(end)),
+        };
+        start = end;
+
+        Some(period)
+    })
+}
+
+/// Yields the volume between each consecutive pair of boundaries, starting from time zero, until
+/// the end of the segments. The boundaries must be increasing.
+pub(crate) fn period_volumes<'a, Time: DeclineTimeUnit>(
+    segments: &'a [Segment<Time>],
+    boundaries: impl IntoIterator<Item = f64> + 'a,
+) -> impl Iterator<Item = PeriodVolume<Time>> + 'a {
+    let incremental_duration = segments.iter().fold(0., |total, segment| {
+        total + segment.incremental_duration().value()
+    });
+    let mut cursor = CumulativeVolumeCursor::new(segments);
+    let mut start = 0.;
+    let mut start_volume = 0.;
+
+    boundaries.into_iter().map_while(move |boundary| {
+        if start >= incremental_duration {
+            return None;
+        }
+
+        let end = boundary.min(incremental_duration);
+        let end_volume = cursor.incremental_volume_at_time(end);
+        let period = PeriodVolume {
+            start: Time::from(start),
+            end: Time::from(end),
+            volume: end_volume - start_volume,
+        };
+
+        start = end;
+        start_volume = end_volume;
+
+        Some(period)
+    })
+}
@@ -0,0 +1,204 @@
+//! SVG charts of forecasts for quick QC images in reports, which are enabled by the `plot`
+//! feature.
+//!
+//! Each segment of the forecast is drawn as a line in its own color, with history points drawn
+//! over the forecast as black circles.
+
+use alloc::{string::String, vec::Vec};
+use core::ops::Range;
+use plotters::prelude::*;
+
+use crate::{DeclineCurveAnalysisError, DeclineTimeUnit, Forecast, ProductionRate};
+
+/// The number of points that each segment is drawn with.
+const POINTS_PER_SEGMENT: usize = 100;
+
+/// Options for the size and caption of a chart.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlotOptions {
+    /// The width of the image in pixels.
+    pub width: u32,
+    /// The height of the image in pixels.
+    pub height: u32,
+    pub caption: Option<String>,
+}
+
+impl Default for PlotOptions {
+    fn default() -> Self {
+        Self {
+            width: 800,
+            height: 600,
+            caption: None,
+        }
+    }
+}
+
+fn write_failed(error: impl core::fmt::Display) -> DeclineCurveAnalysisError {
+    DeclineCurveAnalysisError::WriteFailed {
+        reason: alloc::format!("{error}"),
+    }
+}
+
+/// The points of each segment of a forecast, where `x` maps a time and the cumulative volume at
+/// that time to the horizontal coordinate.
+fn segment_lines<Time: DeclineTimeUnit>(
+    forecast: &Forecast<Time>,
+    x: impl Fn(f64, f64) -> f64,
+) -> Vec<Vec<(f64, f64)>> {
+    let mut start = 0.;
+    let mut cumulative_volume = forecast.initial_cumulative_volume();
+
+    forecast
+        .segments()
+        .iter()
+        .map(|segment| {
+            let duration = segment.incremental_duration().value();
+            let line = (0..=POINTS_PER_SEGMENT)
+                .map(|index| {
+                    let time = Time::from(duration * index as f64 / POINTS_PER_SEGMENT as f64);
+                    (
+                        x(
+                            start + time.value(),
+                            cumulative_volume + segment.incremental_volume_at_time(time),
+                        ),
+                        segment.rate_at_time(time).value(),
+                    )
+                })
+                .collect();
+
+            start += duration;
+            cumulative_volume += segment.incremental_volume();
+            line
+        })
+        .collect()
+}
+
+/// The range of values from zero to the largest value, which is widened if it's empty.
+fn linear_range(values: impl Iterator<Item = f64>) -> Range<f64> {
+    let end = values.fold(0_f64, f64::max);
+    0.0..if end > 0. { end } else { 1. }
+}
+
+/// The range of the positive values with some padding, since a logarithmic axis can't show zero.
+fn log_range(values: impl Iterator<Item = f64>) -> Range<f64> {
+    let (start, end) = values
+        .filter(|value| *value > 0. && value.is_finite())
+        .fold((f64::INFINITY, 0_f64), |(start, end), value| {
+            (start.min(value), end.max(value))
+        });
+    if start.is_finite() {
+        start / 1.5..end * 1.5
+    } else {
+        1.0..10.
+    }
+}
+
+/// Draws the segment lines and history points on a chart with a logarithmic or linear rate axis.
+fn draw_chart(
+    lines: &[Vec<(f64, f64)>],
+    history: &[(f64, f64)],
+    x_description: &str,
+    log_rate: bool,
+    options: &PlotOptions,
+) -> Result<String, DeclineCurveAnalysisError> {
+    let points = || lines.iter().flatten().chain(history);
+    let x_range = linear_range(points().map(|(x, _)| *x));
+
+    let mut svg = String::new();
+    {
+        let root =
+            SVGBackend::with_string(&mut svg, (options.width, options.height)).into_drawing_area();
+        root.fill(&WHITE).map_err(write_failed)?;
+
+        let mut builder = ChartBuilder::on(&root);
+        builder
+            .margin(10)
+            .x_label_area_size(40)
+            .y_label_area_size(60);
+        if let Some(caption) = &options.caption {
+            builder.caption(caption, ("sans-serif", 20));
+        }
+
+        // The axes have different types for logarithmic and linear rates, so the drawing is
+        // repeated for each.
+        macro_rules! draw {
+            ($y_range:expr) => {{
+                let mut chart = builder
+                    .build_cartesian_2d(x_range, $y_range)
+                    .map_err(write_failed)?;
+                chart
+                    .configure_mesh()
+                    .x_desc(x_description)
+                    .y_desc("Rate")
+                    .draw()
+                    .map_err(write_failed)?;
+
+                for (index, line) in lines.iter().enumerate() {
+                    chart
+                        .draw_series(LineSeries::new(
+                            line.iter()
+                                .copied()
+                                .filter(|(_, rate)| !log_rate || *rate > 0.),
+                            Palette99::pick(index).stroke_width(2),
+                        ))
+                        .map_err(write_failed)?;
+                }
+                chart
+                    .draw_series(
+                        history
+                            .iter()
+                            .filter(|(_, rate)| !log_rate || *rate > 0.)
+                            .map(|point| Circle::new(*point, 3, BLACK.filled())),
+                    )
+                    .map_err(write_failed)?;
+            }};
+        }
+
+        if log_rate {
+            draw!(log_range(points().map(|(_, rate)| *rate)).log_scale());
+        } else {
+            draw!(linear_range(points().map(|(_, rate)| *rate)));
+        }
+
+        root.present().map_err(write_failed)?;
+    }
+
+    Ok(svg)
+}
+
+/// Draws the rate of a forecast against time on a logarithmic rate axis as an SVG image, with
+/// history samples (e.g., from [`ProductionHistory::samples`](crate::ProductionHistory::samples))
+/// drawn over the forecast.
+///
+/// Zero rates can't be drawn on a logarithmic axis, so they're skipped.
+pub fn rate_time_svg<Time: DeclineTimeUnit>(
+    forecast: &Forecast<Time>,
+    history: &[(Time, ProductionRate<Time>)],
+    options: &PlotOptions,
+) -> Result<String, DeclineCurveAnalysisError> {
+    let lines = segment_lines(forecast, |time, _| time);
+    let history: Vec<_> = history
+        .iter()
+        .map(|(time, rate)| (time.value(), rate.value()))
+        .collect();
+
+    draw_chart(&lines, &history, "Time", true, options)
+}
+
+/// Draws the rate of a forecast against its cumulative volume on a linear rate axis as an SVG
+/// image, with history points of cumulative volume and rate drawn over the forecast.
+///
+/// The cumulative volume includes the initial cumulative volume of the forecast.
+pub fn rate_cumulative_svg<Time: DeclineTimeUnit>(
+    forecast: &Forecast<Time>,
+    history: &[(f64, ProductionRate<Time>)],
+    options: &PlotOptions,
+) -> Result<String, DeclineCurveAnalysisError> {
+    let lines = segment_lines(forecast, |_, cumulative_volume| cumulative_volume);
+    let history: Vec<_> = history
+        .iter()
+        .map(|(cumulative_volume, rate)| (*cumulative_volume, rate.value()))
+        .collect();
+
+    draw_chart(&lines, &history, "Cumulative volume", false, options)
+}
@@ -0,0 +1,83 @@
+use crate::{DeclineTimeUnit, Forecast, TimeGrid};
+use alloc::{vec, vec::Vec};
+
+/// The volumes for many forecasts evaluated on the same time grid, stored with one row per
+/// forecast and one column per period.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VolumeMatrix {
+    values: Vec<f64>,
+    period_count: usize,
+}
+
+impl VolumeMatrix {
+    pub fn forecast_count(&self) -> usize {
+        self.values
+            .len()
+            .checked_div(self.period_count)
+            .unwrap_or_default()
+    }
+
+    pub fn period_count(&self) -> usize {
+        self.period_count
+    }
+
+    /// The volumes for each period of a single forecast.
+    pub fn forecast_volumes(&self, forecast_index: usize) -> &[f64] {
+        let start = forecast_index * self.period_count;
+        &self.values[start..start + self.period_count]
+    }
+
+    /// The volumes in row-major order (i.e., all periods of the first forecast, followed by all
+    /// periods of the second forecast, and so on).
+    pub fn values(&self) -> &[f64] {
+        &self.values
+    }
+
+    pub fn into_values(self) -> Vec<f64> {
+        self.values
+    }
+
+    /// The total volume of all forecasts in each period.
+    pub fn period_totals(&self) -> Vec<f64> {
+        let mut totals = vec![0.; self.period_count];
+        for row in self.values.chunks_exact(self.period_count.max(1)) {
+            for (total, value) in totals.iter_mut().zip(row) {
+                *total += value;
+            }
+        }
+        totals
+    }
+}
+
+/// Evaluates the volume of each forecast in each period of the grid.
+///
+/// With the `rayon` feature enabled, the forecasts are evaluated in parallel.
+pub fn evaluate_portfolio<Time: DeclineTimeUnit + Send + Sync>(
+    forecasts: &[Forecast<Time>],
+    grid: &TimeGrid<Time>,
+) -> VolumeMatrix {
+    let period_count = grid.period_count();
+    let mut values = vec![0.; forecasts.len() * period_count];
+
+    if period_count > 0 {
+        #[cfg(feature = "rayon")]
+        {
+            use rayon::prelude::*;
+
+            values
+                .par_chunks_mut(period_count)
+                .zip(forecasts)
+                .for_each(|(row, forecast)| forecast.volumes_on_grid(grid, row));
+        }
+
+        #[cfg(not(feature = "rayon"))]
+        for (row, forecast) in values.chunks_mut(period_count).zip(forecasts) {
+            forecast.volumes_on_grid(grid, row);
+        }
+    }
+
+    VolumeMatrix {
+        values,
+        period_count,
+    }
+}
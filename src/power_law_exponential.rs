@@ -0,0 +1,483 @@
+#[cfg(not(feature = "std"))]
+use crate::math::FloatMath;
+use crate::{
+    DeclineCurveAnalysisError, DeclineRateSignValidation, DeclineTimeUnit, ExponentRange,
+    NominalDeclineRate, ProductionRate, RateTruncation, clamp_to_duration, evaluate_at_times,
+    evaluate_at_times_f32,
+    inverse::{time_at_incremental_volume, time_at_rate},
+    is_effectively_zero, math, max_duration,
+    numerical::{find_root, integrate},
+    truncated_duration, validate_decline_rate_sign, validate_duration, validate_finite,
+    validate_incremental_volume, validate_non_zero_decline_rate, validate_non_zero_positive_rate,
+    validate_positive,
+};
+
+/// Validates that a power-law exponential exponent is valid.
+fn validate_power_law_exponent(exponent: f64) -> Result<(), DeclineCurveAnalysisError> {
+    validate_finite(exponent, "exponent")?;
+    if exponent.is_sign_negative() || is_effectively_zero(exponent) || exponent >= 1. {
+        return Err(DeclineCurveAnalysisError::ExponentOutOfRange {
+            value: exponent,
+            allowed: ExponentRange {
+                minimum: 0.,
+                maximum: 1.,
+                excluded: &[],
+            },
+        });
+    }
+    Ok(())
+}
+
+/// Validates that the power-law decline constant is non-zero and positive.
+fn validate_power_law_decline_rate(
+    initial_decline_rate: f64,
+) -> Result<(), DeclineCurveAnalysisError> {
+    validate_non_zero_decline_rate(initial_decline_rate, "initial decline rate")?;
+    if initial_decline_rate.is_sign_negative() {
+        return Err(DeclineCurveAnalysisError::DeclineRateWrongSign);
+    }
+    Ok(())
+}
+
+/// A power-law exponential (PLE) decline segment, as described by Ilk et al. (2008).
+///
+/// The rate is given by `q_i * exp(-D_inf * t - D_i * t^n)`, where `D_i` is the decline constant
+/// and `D_inf` is the decline rate at infinite time. Since `D_i` has units of `1 / time^n`, it's
+/// stored as a raw value in the segment's time unit rather than as a [`NominalDeclineRate`].
+///
+/// There's no closed form for the cumulative volume, so it's integrated numerically.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PowerLawExponentialParameters<Time: DeclineTimeUnit> {
+    initial_rate: ProductionRate<Time>,
+    initial_decline_rate: f64,
+    infinite_decline_rate: NominalDeclineRate<Time>,
+    incremental_duration: Time,
+    exponent: f64,
+}
+
+impl<Time: DeclineTimeUnit> PowerLawExponentialParameters<Time> {
+    pub fn initial_rate(&self) -> ProductionRate<Time> {
+        self.initial_rate
+    }
+
+    pub fn initial_decline_rate(&self) -> f64 {
+        self.initial_decline_rate
+    }
+
+    pub fn infinite_decline_rate(&self) -> NominalDeclineRate<Time> {
+        self.infinite_decline_rate
+    }
+
+    pub fn incremental_duration(&self) -> Time {
+        self.incremental_duration
+    }
+
+    pub fn exponent(&self) -> f64 {
+        self.exponent
+    }
+
+    fn validate_inputs(
+        initial_rate: ProductionRate<Time>,
+        initial_decline_rate: f64,
+        infinite_decline_rate: NominalDeclineRate<Time>,
+        exponent: f64,
+    ) -> Result<(), DeclineCurveAnalysisError> {
+        validate_non_zero_positive_rate(initial_rate.value, "initial rate")?;
+        validate_power_law_decline_rate(initial_decline_rate)?;
+        validate_positive(infinite_decline_rate.value(), "infinite decline rate")?;
+        validate_power_law_exponent(exponent)
+    }
+
+    pub fn from_incremental_duration(
+        initial_rate: ProductionRate<Time>,
+        initial_decline_rate: f64,
+        infinite_decline_rate: NominalDeclineRate<Time>,
+        incremental_duration: Time,
+        exponent: f64,
+    ) -> Result<Self, DeclineCurveAnalysisError> {
+        Self::validate_inputs(
+            initial_rate,
+            initial_decline_rate,
+            infinite_decline_rate,
+            exponent,
+        )?;
+        validate_duration(incremental_duration)?;
+
+        Ok(Self {
+            initial_rate,
+            initial_decline_rate,
+            infinite_decline_rate,
+            incremental_duration,
+            exponent,
+        })
+    }
+
+    pub fn from_incremental_volume(
+        initial_rate: ProductionRate<Time>,
+        initial_decline_rate: f64,
+        infinite_decline_rate: NominalDeclineRate<Time>,
+        incremental_volume: f64,
+        exponent: f64,
+    ) -> Result<Self, DeclineCurveAnalysisError> {
+        Self::validate_inputs(
+            initial_rate,
+            initial_decline_rate,
+            infinite_decline_rate,
+            exponent,
+        )?;
+        validate_incremental_volume(incremental_volume)?;
+
+        let mut result = Self {
+            initial_rate,
+            initial_decline_rate,
+            infinite_decline_rate,
+            incremental_duration: Time::from(0.),
+            exponent,
+        };
+
+        if is_effectively_zero(incremental_volume) {
+            return Ok(result);
+        }
+
+        // The cumulative volume always approaches a finite limit, but there's no closed form for
+        // it. If the volume isn't reached within the maximum duration, then treat it as
+        // unsolvable.
+        let upper = max_duration::<Time>();
+        let incremental_duration = find_root(
+            |time| result.incremental_volume_at_time_without_clamping(time) - incremental_volume,
+            0.,
+            upper,
+        )
+        .ok_or(DeclineCurveAnalysisError::CannotSolveDecline)?;
+
+        result.incremental_duration = Time::from(incremental_duration);
+        validate_duration(result.incremental_duration)?;
+
+        Ok(result)
+    }
+
+    pub fn from_final_decline_rate(
+        initial_rate: ProductionRate<Time>,
+        initial_decline_rate: f64,
+        infinite_decline_rate: NominalDeclineRate<Time>,
+        final_decline_rate: NominalDeclineRate<Time>,
+        exponent: f64,
+    ) -> Result<Self, DeclineCurveAnalysisError> {
+        Self::validate_inputs(
+            initial_rate,
+            initial_decline_rate,
+            infinite_decline_rate,
+            exponent,
+        )?;
+        validate_non_zero_decline_rate(final_decline_rate.value(), "final decline rate")?;
+
+        // The nominal decline rate is `D_inf + n * D_i * t^(n - 1)`, which starts at infinity and
+        // decreases towards `D_inf`, so the final decline rate must be strictly greater than it.
+        let excess_decline_rate = final_decline_rate.value() - infinite_decline_rate.value();
+        if excess_decline_rate <= 0. || is_effectively_zero(excess_decline_rate) {
+            return Err(DeclineCurveAnalysisError::CannotSolveDecline);
+        }
+
+        let incremental_duration = Time::from(math::powf(
+            excess_decline_rate / (exponent * initial_decline_rate),
+            1. / (exponent - 1.),
+        ));
+        validate_duration(incremental_duration)?;
+
+        Ok(Self {
+            initial_rate,
+            initial_decline_rate,
+            infinite_decline_rate,
+            incremental_duration,
+            exponent,
+        })
+    }
+
+    pub fn from_final_rate(
+        initial_rate: ProductionRate<Time>,
+        initial_decline_rate: f64,
+        infinite_decline_rate: NominalDeclineRate<Time>,
+        final_rate: ProductionRate<Time>,
+        exponent: f64,
+    ) -> Result<Self, DeclineCurveAnalysisError> {
+        Self::validate_inputs(
+            initial_rate,
+            initial_decline_rate,
+            infinite_decline_rate,
+            exponent,
+        )?;
+        validate_non_zero_positive_rate(final_rate.value, "final rate")?;
+
+        match validate_decline_rate_sign(
+            initial_decline_rate,
+            initial_rate.value,
+            final_rate.value,
+        )? {
+            DeclineRateSignValidation::Continue => {}
+            DeclineRateSignValidation::ZeroDuration => {
+                return Ok(Self {
+                    initial_rate,
+                    initial_decline_rate,
+                    infinite_decline_rate,
+                    incremental_duration: Time::from(0.),
+                    exponent,
+                });
+            }
+        }
+
+        // Solve `D_inf * t + D_i * t^n = ln(q_i / q_f)`, which is monotonically increasing in `t`.
+        let log_rate_ratio = math::ln(initial_rate.value / final_rate.value);
+        let incremental_duration = find_root(
+            |time| {
+                infinite_decline_rate
+                    .value()
+                    .mul_add(time, initial_decline_rate * math::powf(time, exponent))
+                    - log_rate_ratio
+            },
+            0.,
+            max_duration::<Time>(),
+        )
+        .ok_or(DeclineCurveAnalysisError::DurationTooLong)?;
+        let incremental_duration = Time::from(incremental_duration);
+        validate_duration(incremental_duration)?;
+
+        Ok(Self {
+            initial_rate,
+            initial_decline_rate,
+            infinite_decline_rate,
+            incremental_duration,
+            exponent,
+        })
+    }
+
+    fn rate_value_at_time(&self, time: f64) -> f64 {
+        self.initial_rate.value
+            * math::exp(-self.infinite_decline_rate.value().mul_add(
+                time,
+                self.initial_decline_rate * math::powf(time, self.exponent),
+            ))
+    }
+
+    fn incremental_volume_at_time_without_clamping(&self, time: f64) -> f64 {
+        integrate(|t| self.rate_value_at_time(t), 0., time)
+    }
+
+    pub fn incremental_volume_at_time(&self, time: Time) -> f64 {
+        if time.value() > self.incremental_duration.value() {
+            self.incremental_volume()
+        } else {
+            self.incremental_volume_at_time_without_clamping(time.value())
+        }
+    }
+
+    /// The volume produced between two times, which are clamped to be within the segment. This is
+    /// negative if `end` is before `start`.
+    pub fn incremental_volume_between(&self, start: Time, end: Time) -> f64 {
+        let start = clamp_to_duration(start, self.incremental_duration);
+        let end = clamp_to_duration(end, self.incremental_duration);
+
+        if end.value() < start.value() {
+            return -self.incremental_volume_between(end, start);
+        }
+
+        integrate(|t| self.rate_value_at_time(t), start.value(), end.value())
+    }
+
+    /// Returns the time at which the cumulative volume reaches the given volume, which is the
+    /// inverse of `incremental_volume_at_time`.
+    ///
+    /// This fails if the volume isn't reached before the end of the segment.
+    pub fn time_at_incremental_volume(
+        &self,
+        incremental_volume: f64,
+    ) -> Result<Time, DeclineCurveAnalysisError> {
+        time_at_incremental_volume(
+            self.incremental_volume(),
+            self.incremental_duration,
+            incremental_volume,
+            || {
+                Ok(Self::from_incremental_volume(
+                    self.initial_rate,
+                    self.initial_decline_rate,
+                    self.infinite_decline_rate,
+                    incremental_volume,
+                    self.exponent,
+                )?
+                .incremental_duration)
+            },
+        )
+    }
+
+    /// Returns the rate at the time the cumulative volume reaches the given volume, for
+    /// rate-cumulative analysis.
+    ///
+    /// This fails if the volume isn't reached before the end of the segment.
+    ///
+    /// There's no closed form, so the time at which the volume is reached is solved for first.
+    pub fn rate_at_incremental_volume(
+        &self,
+        incremental_volume: f64,
+    ) -> Result<ProductionRate<Time>, DeclineCurveAnalysisError> {
+        Ok(self.rate_at_time(self.time_at_incremental_volume(incremental_volume)?))
+    }
+
+    pub fn incremental_volume(&self) -> f64 {
+        self.incremental_volume_at_time_without_clamping(self.incremental_duration.value())
+    }
+
+    fn rate_at_time_without_clamping(&self, time: Time) -> ProductionRate<Time> {
+        ProductionRate::new(self.rate_value_at_time(time.value()))
+    }
+
+    /// Returns a copy where every rate is multiplied by `factor`, keeping the same decline.
+    pub(crate) fn scale_rates(&self, factor: f64) -> Self {
+        Self {
+            initial_rate: ProductionRate::new(self.initial_rate.value * factor),
+            ..self.clone()
+        }
+    }
+
+    /// Returns a copy of the parameters that ends at the first time the rate falls to the economic
+    /// limit, along with the volume and duration that were removed.
+    pub fn truncate_at_rate(
+        &self,
+        economic_limit_rate: ProductionRate<Time>,
+    ) -> Result<RateTruncation<Self, Time>, DeclineCurveAnalysisError> {
+        let incremental_duration = truncated_duration(
+            self.initial_rate,
+            self.final_rate(),
+            self.incremental_duration,
+            economic_limit_rate,
+            || {
+                Ok(Self::from_final_rate(
+                    self.initial_rate,
+                    self.initial_decline_rate,
+                    self.infinite_decline_rate,
+                    economic_limit_rate,
+                    self.exponent,
+                )?
+                .incremental_duration)
+            },
+        )?;
+
+        let parameters = Self::from_incremental_duration(
+            self.initial_rate,
+            self.initial_decline_rate,
+            self.infinite_decline_rate,
+            incremental_duration,
+            self.exponent,
+        )?;
+
+        Ok(RateTruncation {
+            truncated_volume: self.incremental_volume() - parameters.incremental_volume(),
+            truncated_duration: Time::from(
+                self.incremental_duration.value() - parameters.incremental_duration.value(),
+            ),
+            parameters,
+        })
+    }
+
+    pub fn final_rate(&self) -> ProductionRate<Time> {
+        self.rate_at_time_without_clamping(self.incremental_duration)
+    }
+
+    /// Returns the first time at which the rate reaches the given rate.
+    ///
+    /// This fails if the rate isn't reached before the end of the segment.
+    pub fn time_at_rate(
+        &self,
+        rate: ProductionRate<Time>,
+    ) -> Result<Time, DeclineCurveAnalysisError> {
+        time_at_rate(
+            self.initial_rate,
+            self.final_rate(),
+            self.incremental_duration,
+            rate,
+            || {
+                Ok(Self::from_final_rate(
+                    self.initial_rate,
+                    self.initial_decline_rate,
+                    self.infinite_decline_rate,
+                    rate,
+                    self.exponent,
+                )?
+                .incremental_duration)
+            },
+        )
+    }
+
+    pub fn rate_at_time(&self, time: Time) -> ProductionRate<Time> {
+        if time.value() > self.incremental_duration.value() {
+            self.final_rate()
+        } else {
+            self.rate_at_time_without_clamping(time)
+        }
+    }
+
+    /// Evaluates `rate_at_time` for each time, writing the rates to `out`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `times` and `out` have different lengths.
+    pub fn rates_at_times(&self, times: &[Time], out: &mut [f64]) {
+        evaluate_at_times(times, out, self.incremental_duration, |time| {
+            self.rate_at_time_without_clamping(time).value()
+        });
+    }
+
+    /// Evaluates `incremental_volume_at_time` for each time, writing the volumes to `out`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `times` and `out` have different lengths.
+    pub fn incremental_volumes_at_times(&self, times: &[Time], out: &mut [f64]) {
+        evaluate_at_times(times, out, self.incremental_duration, |time| {
+            self.incremental_volume_at_time_without_clamping(time.value())
+        });
+    }
+
+    /// Like `rates_at_times`, but with `f32` times and rates, where the times are in the segment's
+    /// time unit. See [`Segment::rates_at_times_f32`](crate::Segment::rates_at_times_f32) for the
+    /// precision.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `times` and `out` have different lengths.
+    pub fn rates_at_times_f32(&self, times: &[f32], out: &mut [f32]) {
+        evaluate_at_times_f32(times, out, self.incremental_duration, |time| {
+            self.rate_at_time_without_clamping(Time::from(time.into()))
+                .value() as f32
+        });
+    }
+
+    /// Like `incremental_volumes_at_times`, but with `f32` times and volumes. See
+    /// [`Segment::rates_at_times_f32`](crate::Segment::rates_at_times_f32) for the precision.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `times` and `out` have different lengths.
+    pub fn incremental_volumes_at_times_f32(&self, times: &[f32], out: &mut [f32]) {
+        evaluate_at_times_f32(times, out, self.incremental_duration, |time| {
+            self.incremental_volume_at_time_without_clamping(time.into()) as f32
+        });
+    }
+
+    /// The instantaneous nominal decline rate at the given time, which is
+    /// `D_inf + n * D_hat_i * t^(n - 1)`. The decline rate is infinite at time zero. Times past the
+    /// end of the segment are clamped to the end, like `rate_at_time`.
+    pub fn decline_rate_at_time(&self, time: Time) -> NominalDeclineRate<Time> {
+        let time = time.value().min(self.incremental_duration.value());
+        NominalDeclineRate::new((self.exponent * self.initial_decline_rate).mul_add(
+            math::powf(time, self.exponent - 1.),
+            self.infinite_decline_rate.value(),
+        ))
+    }
+
+    /// The derivative of the rate with respect to time, which is
+    /// `-(D_inf + n * D_hat_i * t^(n - 1)) * q(t)`. The derivative is infinite at time zero. Times
+    /// past the end of the segment are clamped to the end, like `decline_rate_at_time`.
+    pub fn rate_derivative_at_time(&self, time: Time) -> f64 {
+        let time = Time::from(time.value().min(self.incremental_duration.value()));
+        -self.decline_rate_at_time(time).value() * self.rate_value_at_time(time.value())
+    }
+}
@@ -0,0 +1,561 @@
+use alloc::{vec, vec::Vec};
+use core::marker::PhantomData;
+
+#[cfg(not(feature = "std"))]
+use crate::math::FloatMath;
+use crate::{
+    DeclineCurveAnalysisError, DeclineTimeUnit, DelayParameters, ExponentialParameters,
+    FlatParameters, Forecast, HarmonicParameters, HyperbolicParameters, LinearParameters,
+    NominalDeclineRate, ProductionRate, Segment, truncation::validate_economic_limit_rate,
+};
+
+mod aggregation;
+mod correlation;
+mod distribution;
+mod rng;
+
+pub use aggregation::{PortfolioAggregation, simulate_portfolio, simulate_portfolio_with_rng};
+pub use correlation::CorrelationMatrix;
+pub use distribution::Distribution;
+pub use rng::{RandomNumberGenerator, SeededRng};
+
+/// Maximum number of consecutive invalid samples before a simulation gives up.
+const MAX_REJECTED_SAMPLES_PER_REALIZATION: usize = 100;
+
+/// A segment with uncertain parameters, where each parameter is given by a distribution.
+///
+/// Rates and decline rates are in the time unit of the [`ForecastDistribution`]. An initial rate
+/// of `None` continues from the final rate of the previous producing segment, which keeps the
+/// sampled forecasts continuous.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SegmentDistribution {
+    Delay {
+        incremental_duration: Distribution,
+    },
+    Flat {
+        rate: Option<Distribution>,
+        incremental_duration: Distribution,
+    },
+    Exponential {
+        initial_rate: Option<Distribution>,
+        decline_rate: Distribution,
+        incremental_duration: Distribution,
+    },
+    Harmonic {
+        initial_rate: Option<Distribution>,
+        initial_decline_rate: Distribution,
+        incremental_duration: Distribution,
+    },
+    Hyperbolic {
+        initial_rate: Option<Distribution>,
+        initial_decline_rate: Distribution,
+        incremental_duration: Distribution,
+        exponent: Distribution,
+    },
+    Linear {
+        initial_rate: Option<Distribution>,
+        decline_rate: Distribution,
+        incremental_duration: Distribution,
+    },
+}
+
+impl SegmentDistribution {
+    fn initial_rate(&self) -> Option<Option<&Distribution>> {
+        match self {
+            Self::Delay { .. } => None,
+            Self::Flat { rate, .. } => Some(rate.as_ref()),
+            Self::Exponential { initial_rate, .. }
+            | Self::Harmonic { initial_rate, .. }
+            | Self::Hyperbolic { initial_rate, .. }
+            | Self::Linear { initial_rate, .. } => Some(initial_rate.as_ref()),
+        }
+    }
+
+    /// The uncertain parameters of the segment, in the order that they're sampled.
+    fn parameters(&self) -> Vec<(SegmentParameter, &Distribution)> {
+        let mut parameters: Vec<_> = self
+            .initial_rate()
+            .flatten()
+            .map(|rate| (SegmentParameter::InitialRate, rate))
+            .into_iter()
+            .collect();
+
+        match self {
+            Self::Delay {
+                incremental_duration,
+            }
+            | Self::Flat {
+                incremental_duration,
+                ..
+            } => parameters.push((SegmentParameter::IncrementalDuration, incremental_duration)),
+            Self::Exponential {
+                decline_rate,
+                incremental_duration,
+                ..
+            }
+            | Self::Linear {
+                decline_rate,
+                incremental_duration,
+                ..
+            }
+            | Self::Harmonic {
+                initial_decline_rate: decline_rate,
+                incremental_duration,
+                ..
+            } => parameters.extend([
+                (SegmentParameter::DeclineRate, decline_rate),
+                (SegmentParameter::IncrementalDuration, incremental_duration),
+            ]),
+            Self::Hyperbolic {
+                initial_decline_rate,
+                incremental_duration,
+                exponent,
+                ..
+            } => parameters.extend([
+                (SegmentParameter::DeclineRate, initial_decline_rate),
+                (SegmentParameter::IncrementalDuration, incremental_duration),
+                (SegmentParameter::Exponent, exponent),
+            ]),
+        }
+
+        parameters
+    }
+
+    /// Samples the segment, where `draw` samples each parameter in the order of
+    /// [`Self::parameters`].
+    fn sample<Time: DeclineTimeUnit>(
+        &self,
+        draw: &mut impl FnMut(&Distribution) -> f64,
+        previous_rate: Option<ProductionRate<Time>>,
+    ) -> Result<Segment<Time>, DeclineCurveAnalysisError> {
+        let mut sample_rate = |rate: &Option<Distribution>| match rate {
+            Some(distribution) => Ok(ProductionRate::new(draw(distribution))),
+            None => previous_rate.ok_or(DeclineCurveAnalysisError::NoPreviousSegment {
+                field: "initial rate",
+            }),
+        };
+
+        Ok(match self {
+            Self::Delay {
+                incremental_duration,
+            } => {
+                DelayParameters::from_incremental_duration(Time::from(draw(incremental_duration)))?
+                    .into()
+            }
+            Self::Flat {
+                rate,
+                incremental_duration,
+            } => {
+                let rate = sample_rate(rate)?;
+                FlatParameters::from_incremental_duration(
+                    rate,
+                    Time::from(draw(incremental_duration)),
+                )?
+                .into()
+            }
+            Self::Exponential {
+                initial_rate,
+                decline_rate,
+                incremental_duration,
+            } => {
+                let initial_rate = sample_rate(initial_rate)?;
+                ExponentialParameters::from_incremental_duration(
+                    initial_rate,
+                    NominalDeclineRate::new(draw(decline_rate)),
+                    Time::from(draw(incremental_duration)),
+                )?
+                .into()
+            }
+            Self::Harmonic {
+                initial_rate,
+                initial_decline_rate,
+                incremental_duration,
+            } => {
+                let initial_rate = sample_rate(initial_rate)?;
+                HarmonicParameters::from_incremental_duration(
+                    initial_rate,
+                    NominalDeclineRate::new(draw(initial_decline_rate)),
+                    Time::from(draw(incremental_duration)),
+                )?
+                .into()
+            }
+            Self::Hyperbolic {
+                initial_rate,
+                initial_decline_rate,
+                incremental_duration,
+                exponent,
+            } => {
+                let initial_rate = sample_rate(initial_rate)?;
+                HyperbolicParameters::from_incremental_duration(
+                    initial_rate,
+                    NominalDeclineRate::new(draw(initial_decline_rate)),
+                    Time::from(draw(incremental_duration)),
+                    draw(exponent),
+                )?
+                .into()
+            }
+            Self::Linear {
+                initial_rate,
+                decline_rate,
+                incremental_duration,
+            } => {
+                let initial_rate = sample_rate(initial_rate)?;
+                LinearParameters::from_incremental_duration(
+                    initial_rate,
+                    NominalDeclineRate::new(draw(decline_rate)),
+                    Time::from(draw(incremental_duration)),
+                )?
+                .into()
+            }
+        })
+    }
+}
+
+/// A parameter of a [`SegmentDistribution`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SegmentParameter {
+    /// The initial rate, or the rate of a flat segment.
+    InitialRate,
+    /// The decline rate, or the initial decline rate of a harmonic or hyperbolic segment.
+    DeclineRate,
+    IncrementalDuration,
+    Exponent,
+}
+
+/// An uncertain parameter of a [`ForecastDistribution`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DistributionParameter {
+    pub segment_index: usize,
+    pub parameter: SegmentParameter,
+}
+
+/// A forecast with uncertain parameters, which can be sampled to produce forecasts.
+///
+/// Parameters are independent unless they're correlated with [`Self::with_correlation`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ForecastDistribution<Time: DeclineTimeUnit> {
+    segments: Vec<SegmentDistribution>,
+    correlation: Option<CorrelationMatrix>,
+    _time: PhantomData<Time>,
+}
+
+impl<Time: DeclineTimeUnit> ForecastDistribution<Time> {
+    pub fn new(segments: Vec<SegmentDistribution>) -> Result<Self, DeclineCurveAnalysisError> {
+        let mut has_previous_rate = false;
+
+        for segment in &segments {
+            for (_, distribution) in segment.parameters() {
+                distribution.validate()?;
+            }
+
+            match segment.initial_rate() {
+                None => {}
+                Some(None) if !has_previous_rate => {
+                    return Err(DeclineCurveAnalysisError::NoPreviousSegment {
+                        field: "initial rate",
+                    });
+                }
+                Some(_) => has_previous_rate = true,
+            }
+        }
+
+        Ok(Self {
+            segments,
+            correlation: None,
+            _time: PhantomData,
+        })
+    }
+
+    /// Correlates the parameters with a matrix whose rows and columns are in the order of
+    /// [`Self::parameters`] (e.g., so that wells with a higher initial rate tend to decline
+    /// faster).
+    pub fn with_correlation(
+        mut self,
+        correlation: CorrelationMatrix,
+    ) -> Result<Self, DeclineCurveAnalysisError> {
+        let parameter_count = self.parameters().len();
+        if correlation.dimension() != parameter_count {
+            return Err(DeclineCurveAnalysisError::InputLengthMismatch {
+                field: "correlation matrix",
+                count: correlation.dimension(),
+                expected: parameter_count,
+            });
+        }
+
+        self.correlation = Some(correlation);
+        Ok(self)
+    }
+
+    pub fn segments(&self) -> &[SegmentDistribution] {
+        &self.segments
+    }
+
+    pub fn correlation(&self) -> Option<&CorrelationMatrix> {
+        self.correlation.as_ref()
+    }
+
+    /// The uncertain parameters of the segments, which includes parameters with a constant
+    /// distribution but not initial rates that continue from the previous segment.
+    pub fn parameters(&self) -> Vec<DistributionParameter> {
+        self.segments
+            .iter()
+            .enumerate()
+            .flat_map(|(segment_index, segment)| {
+                segment
+                    .parameters()
+                    .into_iter()
+                    .map(move |(parameter, _)| DistributionParameter {
+                        segment_index,
+                        parameter,
+                    })
+            })
+            .collect()
+    }
+
+    /// The index of a parameter in [`Self::parameters`], if the segment has it.
+    pub fn parameter_index(
+        &self,
+        segment_index: usize,
+        parameter: SegmentParameter,
+    ) -> Option<usize> {
+        self.parameters().iter().position(|candidate| {
+            *candidate
+                == DistributionParameter {
+                    segment_index,
+                    parameter,
+                }
+        })
+    }
+
+    /// Draws a single forecast. This fails if the sampled parameters are invalid (e.g., a
+    /// negative rate sampled from a normal distribution).
+    pub fn sample<R: RandomNumberGenerator + ?Sized>(
+        &self,
+        rng: &mut R,
+    ) -> Result<Forecast<Time>, DeclineCurveAnalysisError> {
+        let mut segments = Vec::with_capacity(self.segments.len());
+        let mut previous_rate = None;
+        let mut correlated = self
+            .correlation
+            .as_ref()
+            .map(|correlation| correlation.sample_standard_normals(rng).into_iter());
+        let mut draw = |distribution: &Distribution| match &mut correlated {
+            Some(values) => distribution.from_standard_normal(
+                values
+                    .next()
+                    .expect("the correlation matrix has a row for each parameter"),
+            ),
+            None => distribution.sample(rng),
+        };
+
+        for distribution in &self.segments {
+            let segment = distribution.sample(&mut draw, previous_rate)?;
+            if !matches!(segment, Segment::Delay(_)) {
+                previous_rate = Some(segment.final_rate());
+            }
+            segments.push(segment);
+        }
+
+        Ok(Forecast::new(segments))
+    }
+}
+
+/// Options that control a Monte Carlo simulation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MonteCarloOptions<Time: DeclineTimeUnit> {
+    /// The number of forecasts to sample.
+    pub realizations: usize,
+    /// The seed for a [`SeededRng`], so that simulations are reproducible. This is ignored by the
+    /// functions that take a generator (e.g., [`simulate_with_rng`]).
+    pub seed: u64,
+    /// Each sampled forecast is truncated at this rate before calculating its EUR and rates.
+    pub economic_limit_rate: ProductionRate<Time>,
+}
+
+impl<Time: DeclineTimeUnit> Default for MonteCarloOptions<Time> {
+    fn default() -> Self {
+        Self {
+            realizations: 1000,
+            seed: 0,
+            economic_limit_rate: ProductionRate::new(0.),
+        }
+    }
+}
+
+impl<Time: DeclineTimeUnit> MonteCarloOptions<Time> {
+    fn validate(&self) -> Result<(), DeclineCurveAnalysisError> {
+        if self.realizations == 0 {
+            return Err(DeclineCurveAnalysisError::TooFewInputs {
+                field: "realizations",
+                count: 0,
+                minimum: 1,
+            });
+        }
+        validate_economic_limit_rate(self.economic_limit_rate)
+    }
+}
+
+/// Percentiles using the petroleum exceedance convention, where the P90 is the low estimate (90%
+/// probability of being exceeded) and the P10 is the high estimate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Percentiles {
+    pub p90: f64,
+    pub p50: f64,
+    pub p10: f64,
+    pub mean: f64,
+}
+
+impl Percentiles {
+    /// Calculates the percentiles of the values, which are sorted in place.
+    pub fn from_values(values: &mut [f64]) -> Self {
+        values.sort_by(f64::total_cmp);
+
+        Self {
+            p90: quantile(values, 0.1),
+            p50: quantile(values, 0.5),
+            p10: quantile(values, 0.9),
+            mean: if values.is_empty() {
+                0.
+            } else {
+                values.iter().sum::<f64>() / values.len() as f64
+            },
+        }
+    }
+}
+
+/// Returns the linearly interpolated quantile of sorted values.
+fn quantile(sorted: &[f64], fraction: f64) -> f64 {
+    match sorted.len() {
+        0 => 0.,
+        1 => sorted[0],
+        length => {
+            let position = fraction * (length - 1) as f64;
+            let lower = position.floor() as usize;
+            let upper = (lower + 1).min(length - 1);
+            let weight = position - lower as f64;
+            sorted[lower] + weight * (sorted[upper] - sorted[lower])
+        }
+    }
+}
+
+/// The results of a Monte Carlo simulation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonteCarloResult {
+    /// The EUR percentiles across all realizations.
+    pub estimated_ultimate_recovery: Percentiles,
+    /// The rate percentiles at each of the requested times.
+    pub rate_profile: Vec<Percentiles>,
+    /// The EUR of each realization, in the order they were sampled.
+    pub realization_eurs: Vec<f64>,
+    /// The number of samples that were rejected because their parameters were invalid.
+    pub rejected_samples: usize,
+}
+
+/// Samples forecasts from the distribution and calculates the EUR and rate percentiles.
+///
+/// Samples with invalid parameters (e.g., a negative rate sampled from a normal distribution) are
+/// rejected and resampled, which effectively truncates the distributions to valid values. Rates
+/// after the end of a sampled forecast (including after the economic limit) are zero.
+pub fn simulate<Time: DeclineTimeUnit>(
+    distribution: &ForecastDistribution<Time>,
+    times: &[Time],
+    options: &MonteCarloOptions<Time>,
+) -> Result<MonteCarloResult, DeclineCurveAnalysisError> {
+    simulate_with_rng(
+        distribution,
+        times,
+        options,
+        &mut SeededRng::new(options.seed),
+    )
+}
+
+/// Like [`simulate`], but draws from the given generator instead of seeding one from the options.
+pub fn simulate_with_rng<Time: DeclineTimeUnit, R: RandomNumberGenerator + ?Sized>(
+    distribution: &ForecastDistribution<Time>,
+    times: &[Time],
+    options: &MonteCarloOptions<Time>,
+    rng: &mut R,
+) -> Result<MonteCarloResult, DeclineCurveAnalysisError> {
+    options.validate()?;
+
+    let Realizations {
+        eurs: realization_eurs,
+        mut rates,
+        rejected_samples,
+    } = Realizations::sample(distribution, times, options, rng)?;
+
+    Ok(MonteCarloResult {
+        estimated_ultimate_recovery: Percentiles::from_values(&mut realization_eurs.clone()),
+        rate_profile: rates
+            .iter_mut()
+            .map(|rates| Percentiles::from_values(rates))
+            .collect(),
+        realization_eurs,
+        rejected_samples,
+    })
+}
+
+/// The sampled forecasts of a distribution, evaluated at the requested times.
+struct Realizations {
+    /// The EUR of each realization.
+    eurs: Vec<f64>,
+    /// The rate of each realization, with one row per time.
+    rates: Vec<Vec<f64>>,
+    rejected_samples: usize,
+}
+
+impl Realizations {
+    fn sample<Time: DeclineTimeUnit, R: RandomNumberGenerator + ?Sized>(
+        distribution: &ForecastDistribution<Time>,
+        times: &[Time],
+        options: &MonteCarloOptions<Time>,
+        rng: &mut R,
+    ) -> Result<Self, DeclineCurveAnalysisError> {
+        let mut eurs = Vec::with_capacity(options.realizations);
+        let mut rates = vec![Vec::with_capacity(options.realizations); times.len()];
+        let mut rejected_samples = 0;
+
+        for _ in 0..options.realizations {
+            let forecast = sample_valid(distribution, rng, &mut rejected_samples)?
+                .truncate_at_rate(options.economic_limit_rate)?
+                .parameters;
+            let incremental_duration = forecast.incremental_duration().value();
+
+            eurs.push(forecast.incremental_volume());
+            for (time, rates) in times.iter().zip(&mut rates) {
+                rates.push(if time.value() > incremental_duration {
+                    0.
+                } else {
+                    forecast.rate_at_time(*time).value()
+                });
+            }
+        }
+
+        Ok(Self {
+            eurs,
+            rates,
+            rejected_samples,
+        })
+    }
+}
+
+fn sample_valid<Time: DeclineTimeUnit, R: RandomNumberGenerator + ?Sized>(
+    distribution: &ForecastDistribution<Time>,
+    rng: &mut R,
+    rejected_samples: &mut usize,
+) -> Result<Forecast<Time>, DeclineCurveAnalysisError> {
+    let mut attempts = 0;
+
+    loop {
+        match distribution.sample(rng) {
+            Ok(forecast) => return Ok(forecast),
+            Err(error) => {
+                *rejected_samples += 1;
+                attempts += 1;
+                if attempts >= MAX_REJECTED_SAMPLES_PER_REALIZATION {
+                    return Err(error);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,176 @@
+use super::{
+    CorrelationMatrix, ForecastDistribution, MonteCarloOptions, MonteCarloResult, Percentiles,
+    Realizations,
+};
+use crate::{DeclineCurveAnalysisError, DeclineTimeUnit, RandomNumberGenerator, SeededRng};
+use alloc::{vec, vec::Vec};
+use core::ops::Bound;
+
+/// How the forecasts of many wells are combined into portfolio percentiles.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PortfolioAggregation {
+    /// Sums the percentiles of each well, which assumes that every well hits its P90 (or P10) at
+    /// the same time. This overstates the spread of the portfolio unless the wells are perfectly
+    /// correlated, but is a common reporting convention.
+    ///
+    /// There are no portfolio realizations, so the realization EURs of the result are empty.
+    Arithmetic,
+    /// Sums the realizations of the wells, so that the low outcomes of some wells offset the high
+    /// outcomes of others.
+    MonteCarlo {
+        /// The correlation between wells from zero (independent) to one (a low EUR in one well
+        /// always comes with a low EUR in the others, which matches arithmetic summation).
+        ///
+        /// Realizations are paired by the rank of their EUR using a Gaussian copula.
+        correlation: f64,
+    },
+    /// Sums the realizations of the wells like [`Self::MonteCarlo`], with a correlation between
+    /// each pair of wells in the order of the distributions (e.g., wells on the same pad are more
+    /// correlated than wells in different fields).
+    Correlated(CorrelationMatrix),
+}
+
+impl PortfolioAggregation {
+    /// The correlation between the wells, or `None` for arithmetic summation.
+    fn correlation(
+        &self,
+        well_count: usize,
+    ) -> Result<Option<CorrelationMatrix>, DeclineCurveAnalysisError> {
+        match self {
+            Self::Arithmetic => Ok(None),
+            Self::MonteCarlo { correlation } if (0. ..=1.).contains(correlation) => {
+                CorrelationMatrix::uniform(well_count, *correlation).map(Some)
+            }
+            Self::MonteCarlo { correlation } => Err(DeclineCurveAnalysisError::InputOutOfRange {
+                field: "correlation",
+                value: *correlation,
+                minimum: Bound::Included(0.),
+                maximum: Bound::Included(1.),
+            }),
+            Self::Correlated(correlation) if correlation.dimension() == well_count => {
+                Ok(Some(correlation.clone()))
+            }
+            Self::Correlated(correlation) => Err(DeclineCurveAnalysisError::InputLengthMismatch {
+                field: "correlation matrix",
+                count: correlation.dimension(),
+                expected: well_count,
+            }),
+        }
+    }
+}
+
+/// Samples forecasts from the distribution of each well and combines them into portfolio EUR and
+/// rate percentiles, since the P90 of a portfolio isn't the sum of the P90s of its wells.
+///
+/// Each well is sampled as for [`simulate`](super::simulate), drawing from a single generator in
+/// the order of the wells, so every aggregation uses the same realizations. Every well gets the
+/// same number of realizations as the portfolio.
+pub fn simulate_portfolio<Time: DeclineTimeUnit>(
+    distributions: &[ForecastDistribution<Time>],
+    times: &[Time],
+    options: &MonteCarloOptions<Time>,
+    aggregation: PortfolioAggregation,
+) -> Result<MonteCarloResult, DeclineCurveAnalysisError> {
+    simulate_portfolio_with_rng(
+        distributions,
+        times,
+        options,
+        aggregation,
+        &mut SeededRng::new(options.seed),
+    )
+}
+
+/// Like [`simulate_portfolio`], but draws from the given generator instead of seeding one from the
+/// options.
+pub fn simulate_portfolio_with_rng<Time: DeclineTimeUnit, R: RandomNumberGenerator + ?Sized>(
+    distributions: &[ForecastDistribution<Time>],
+    times: &[Time],
+    options: &MonteCarloOptions<Time>,
+    aggregation: PortfolioAggregation,
+    rng: &mut R,
+) -> Result<MonteCarloResult, DeclineCurveAnalysisError> {
+    options.validate()?;
+    if distributions.is_empty() {
+        return Err(DeclineCurveAnalysisError::TooFewInputs {
+            field: "well count",
+            count: 0,
+            minimum: 1,
+        });
+    }
+    let correlation = aggregation.correlation(distributions.len())?;
+
+    let wells = distributions
+        .iter()
+        .map(|distribution| Realizations::sample(distribution, times, options, rng))
+        .collect::<Result<Vec<_>, _>>()?;
+    let rejected_samples = wells.iter().map(|well| well.rejected_samples).sum();
+
+    let Some(correlation) = correlation else {
+        return Ok(MonteCarloResult {
+            estimated_ultimate_recovery: sum_percentiles(
+                wells.iter().map(|well| well.eurs.clone()),
+            ),
+            rate_profile: (0..times.len())
+                .map(|time_index| {
+                    sum_percentiles(wells.iter().map(|well| well.rates[time_index].clone()))
+                })
+                .collect(),
+            realization_eurs: Vec::new(),
+            rejected_samples,
+        });
+    };
+
+    // Each portfolio realization has a correlated score for each well.
+    let realizations = options.realizations;
+    let scores: Vec<Vec<f64>> = (0..realizations)
+        .map(|_| correlation.sample_standard_normals(rng))
+        .collect();
+    let mut realization_eurs = vec![0.; realizations];
+    let mut rates = vec![vec![0.; realizations]; times.len()];
+
+    for (well_index, well) in wells.iter().enumerate() {
+        // The realization with the k-th lowest score gets the k-th lowest EUR of the well.
+        let mut by_score: Vec<usize> = (0..realizations).collect();
+        by_score.sort_by(|a, b| scores[*a][well_index].total_cmp(&scores[*b][well_index]));
+        let mut by_eur: Vec<usize> = (0..realizations).collect();
+        by_eur.sort_by(|a, b| well.eurs[*a].total_cmp(&well.eurs[*b]));
+
+        for (portfolio_index, realization_index) in by_score.into_iter().zip(by_eur) {
+            realization_eurs[portfolio_index] += well.eurs[realization_index];
+            for (rates, well_rates) in rates.iter_mut().zip(&well.rates) {
+                rates[portfolio_index] += well_rates[realization_index];
+            }
+        }
+    }
+
+    Ok(MonteCarloResult {
+        estimated_ultimate_recovery: Percentiles::from_values(&mut realization_eurs.clone()),
+        rate_profile: rates
+            .iter_mut()
+            .map(|rates| Percentiles::from_values(rates))
+            .collect(),
+        realization_eurs,
+        rejected_samples,
+    })
+}
+
+/// Sums the percentiles of each set of values.
+fn sum_percentiles(values: impl Iterator<Item = Vec<f64>>) -> Percentiles {
+    values.fold(
+        Percentiles {
+            p90: 0.,
+            p50: 0.,
+            p10: 0.,
+            mean: 0.,
+        },
+        |total, mut values| {
+            let percentiles = Percentiles::from_values(&mut values);
+            Percentiles {
+                p90: total.p90 + percentiles.p90,
+                p50: total.p50 + percentiles.p50,
+                p10: total.p10 + percentiles.p10,
+                mean: total.mean + percentiles.mean,
+            }
+        },
+    )
+}
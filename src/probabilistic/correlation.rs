@@ -0,0 +1,181 @@
+use super::RandomNumberGenerator;
+#[cfg(not(feature = "std"))]
+use crate::math::FloatMath;
+use crate::{DeclineCurveAnalysisError, validate_finite};
+use alloc::{format, string::ToString, vec, vec::Vec};
+use core::ops::Bound;
+
+/// The tolerance for a correlation matrix that's positive semi-definite, which allows perfectly
+/// correlated variables.
+const SEMI_DEFINITE_TOLERANCE: f64 = 1e-10;
+
+/// A matrix of the correlations between variables, which are sampled as correlated standard normal
+/// values and then transformed to their distributions (i.e., a Gaussian copula).
+///
+/// The correlations are between the normal values, which is close to the rank correlation of the
+/// sampled values.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CorrelationMatrix {
+    rows: Vec<Vec<f64>>,
+    /// The lower triangular Cholesky factor of the matrix.
+    factor: Vec<Vec<f64>>,
+}
+
+impl CorrelationMatrix {
+    /// Creates a matrix from its rows, which must be symmetric with ones on the diagonal and
+    /// positive semi-definite (e.g., if A and B are strongly correlated, A and C can't be strongly
+    /// anti-correlated while B and C are strongly correlated).
+    pub fn new(rows: Vec<Vec<f64>>) -> Result<Self, DeclineCurveAnalysisError> {
+        let dimension = rows.len();
+        for (i, row) in rows.iter().enumerate() {
+            if row.len() != dimension {
+                return Err(DeclineCurveAnalysisError::InputLengthMismatch {
+                    field: "correlation matrix row",
+                    count: row.len(),
+                    expected: dimension,
+                });
+            }
+            for (j, &correlation) in row.iter().enumerate() {
+                validate_finite(correlation, "correlation")?;
+                if i == j && correlation != 1. {
+                    return Err(DeclineCurveAnalysisError::InvalidInput {
+                        reason: format!(
+                            "correlation matrix has {correlation} on the diagonal, but expected one"
+                        ),
+                    });
+                }
+                if !(-1. ..=1.).contains(&correlation) {
+                    return Err(DeclineCurveAnalysisError::InputOutOfRange {
+                        field: "correlation",
+                        value: correlation,
+                        minimum: Bound::Included(-1.),
+                        maximum: Bound::Included(1.),
+                    });
+                }
+                if correlation != rows[j][i] {
+                    return Err(DeclineCurveAnalysisError::InvalidInput {
+                        reason: "correlation matrix isn't symmetric".to_string(),
+                    });
+                }
+            }
+        }
+
+        let factor = cholesky(&rows).ok_or_else(|| DeclineCurveAnalysisError::InvalidInput {
+            reason: "correlation matrix isn't positive semi-definite".to_string(),
+        })?;
+
+        Ok(Self { rows, factor })
+    }
+
+    /// Uncorrelated variables.
+    pub fn identity(dimension: usize) -> Self {
+        let rows: Vec<Vec<f64>> = (0..dimension)
+            .map(|i| {
+                (0..dimension)
+                    .map(|j| if i == j { 1. } else { 0. })
+                    .collect()
+            })
+            .collect();
+
+        Self {
+            factor: rows.clone(),
+            rows,
+        }
+    }
+
+    /// Variables with the same correlation between each pair (e.g., the wells of a pad).
+    pub fn uniform(dimension: usize, correlation: f64) -> Result<Self, DeclineCurveAnalysisError> {
+        Self::new(
+            (0..dimension)
+                .map(|i| {
+                    (0..dimension)
+                        .map(|j| if i == j { 1. } else { correlation })
+                        .collect()
+                })
+                .collect(),
+        )
+    }
+
+    /// Sets the correlation between two variables (e.g., a negative correlation between the initial
+    /// rate and the decline rate).
+    pub fn with_pair(
+        mut self,
+        first: usize,
+        second: usize,
+        correlation: f64,
+    ) -> Result<Self, DeclineCurveAnalysisError> {
+        let dimension = self.dimension();
+        if first >= dimension || second >= dimension {
+            return Err(DeclineCurveAnalysisError::InputOutOfRange {
+                field: "correlation variable",
+                value: first.max(second) as f64,
+                minimum: Bound::Included(0.),
+                maximum: Bound::Included(dimension.saturating_sub(1) as f64),
+            });
+        }
+
+        self.rows[first][second] = correlation;
+        self.rows[second][first] = correlation;
+        Self::new(self.rows)
+    }
+
+    /// The number of variables.
+    pub fn dimension(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn rows(&self) -> &[Vec<f64>] {
+        &self.rows
+    }
+
+    /// Draws a standard normal value for each variable with the correlations of the matrix.
+    pub fn sample_standard_normals<R: RandomNumberGenerator + ?Sized>(
+        &self,
+        rng: &mut R,
+    ) -> Vec<f64> {
+        let independent: Vec<f64> = (0..self.dimension())
+            .map(|_| rng.next_standard_normal())
+            .collect();
+
+        self.factor
+            .iter()
+            .map(|row| row.iter().zip(&independent).map(|(a, b)| a * b).sum())
+            .collect()
+    }
+}
+
+/// Returns the lower triangular Cholesky factor of a positive semi-definite matrix, or `None` if
+/// the matrix isn't positive semi-definite.
+fn cholesky(matrix: &[Vec<f64>]) -> Option<Vec<Vec<f64>>> {
+    let dimension = matrix.len();
+    let mut factor = vec![vec![0.; dimension]; dimension];
+
+    for j in 0..dimension {
+        let diagonal = matrix[j][j]
+            - factor[j][..j]
+                .iter()
+                .map(|value| value * value)
+                .sum::<f64>();
+        if diagonal < -SEMI_DEFINITE_TOLERANCE {
+            return None;
+        }
+        factor[j][j] = diagonal.max(0.).sqrt();
+
+        for i in (j + 1)..dimension {
+            let off_diagonal = matrix[i][j]
+                - factor[i][..j]
+                    .iter()
+                    .zip(&factor[j][..j])
+                    .map(|(a, b)| a * b)
+                    .sum::<f64>();
+            if factor[j][j] > SEMI_DEFINITE_TOLERANCE.sqrt() {
+                factor[i][j] = off_diagonal / factor[j][j];
+            } else if off_diagonal.abs() > SEMI_DEFINITE_TOLERANCE.sqrt() {
+                // A variable that's determined by the previous ones must be consistent with them.
+                return None;
+            }
+        }
+    }
+
+    Some(factor)
+}
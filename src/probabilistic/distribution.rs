@@ -0,0 +1,210 @@
+use super::RandomNumberGenerator;
+#[cfg(not(feature = "std"))]
+use crate::math::FloatMath;
+use crate::{
+    DeclineCurveAnalysisError, math, validate_finite, validate_non_zero_positive_rate,
+    validate_positive,
+};
+
+/// A probability distribution for a single parameter.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Distribution {
+    /// A fixed value, which is useful for parameters without uncertainty.
+    Constant(f64),
+    Normal {
+        mean: f64,
+        standard_deviation: f64,
+    },
+    /// A lognormal distribution, where `mu` and `sigma` are the mean and standard deviation of the
+    /// natural logarithm of the value.
+    LogNormal {
+        mu: f64,
+        sigma: f64,
+    },
+    Triangular {
+        minimum: f64,
+        mode: f64,
+        maximum: f64,
+    },
+    Uniform {
+        minimum: f64,
+        maximum: f64,
+    },
+}
+
+impl Distribution {
+    /// Creates a lognormal distribution from its P90 and P10 values, using the petroleum
+    /// convention where the P90 is the low estimate (90% probability of being exceeded) and the
+    /// P10 is the high estimate.
+    pub fn log_normal_from_p90_p10(p90: f64, p10: f64) -> Result<Self, DeclineCurveAnalysisError> {
+        validate_finite(p90, "P90")?;
+        validate_finite(p10, "P10")?;
+        validate_non_zero_positive_rate(p90, "P90")?;
+        if p10 < p90 {
+            return Err(DeclineCurveAnalysisError::InputGreaterThan {
+                field: "P90",
+                value: p90,
+                other: "P10",
+                limit: p10,
+            });
+        }
+
+        // The 10th and 90th percentiles of a standard normal distribution are ±1.2816.
+        const Z_90: f64 = 1.281_551_565_544_600_4;
+        let mu = 0.5 * (math::ln(p90) + math::ln(p10));
+        let sigma = (math::ln(p10) - math::ln(p90)) / (2. * Z_90);
+
+        Ok(Self::LogNormal { mu, sigma })
+    }
+
+    pub fn validate(&self) -> Result<(), DeclineCurveAnalysisError> {
+        match *self {
+            Self::Constant(value) => validate_finite(value, "constant value"),
+            Self::Normal {
+                mean,
+                standard_deviation,
+            } => {
+                validate_finite(mean, "mean")?;
+                validate_positive(standard_deviation, "standard deviation")
+            }
+            Self::LogNormal { mu, sigma } => {
+                validate_finite(mu, "mu")?;
+                validate_positive(sigma, "sigma")
+            }
+            Self::Triangular {
+                minimum,
+                mode,
+                maximum,
+            } => {
+                validate_finite(minimum, "minimum")?;
+                validate_finite(mode, "mode")?;
+                validate_finite(maximum, "maximum")?;
+                if minimum > mode {
+                    return Err(DeclineCurveAnalysisError::InputGreaterThan {
+                        field: "minimum",
+                        value: minimum,
+                        other: "mode",
+                        limit: mode,
+                    });
+                }
+                if mode > maximum {
+                    return Err(DeclineCurveAnalysisError::InputGreaterThan {
+                        field: "mode",
+                        value: mode,
+                        other: "maximum",
+                        limit: maximum,
+                    });
+                }
+                Ok(())
+            }
+            Self::Uniform { minimum, maximum } => {
+                validate_finite(minimum, "minimum")?;
+                validate_finite(maximum, "maximum")?;
+                if minimum > maximum {
+                    return Err(DeclineCurveAnalysisError::InputGreaterThan {
+                        field: "minimum",
+                        value: minimum,
+                        other: "maximum",
+                        limit: maximum,
+                    });
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Draws a sample from the distribution.
+    pub fn sample<R: RandomNumberGenerator + ?Sized>(&self, rng: &mut R) -> f64 {
+        match *self {
+            Self::Constant(value) => value,
+            Self::Normal {
+                mean,
+                standard_deviation,
+            } => standard_deviation.mul_add(rng.next_standard_normal(), mean),
+            Self::LogNormal { mu, sigma } => {
+                math::exp(sigma.mul_add(rng.next_standard_normal(), mu))
+            }
+            Self::Triangular { .. } | Self::Uniform { .. } => self.quantile(rng.next_f64()),
+        }
+    }
+
+    /// Transforms a standard normal value to a value of the distribution with the same cumulative
+    /// probability, which preserves the rank correlation of correlated normal values (i.e., a
+    /// Gaussian copula).
+    pub fn from_standard_normal(&self, value: f64) -> f64 {
+        match *self {
+            Self::Constant(constant) => constant,
+            Self::Normal {
+                mean,
+                standard_deviation,
+            } => standard_deviation.mul_add(value, mean),
+            Self::LogNormal { mu, sigma } => math::exp(sigma.mul_add(value, mu)),
+            Self::Triangular { .. } | Self::Uniform { .. } => {
+                self.quantile(standard_normal_cdf(value))
+            }
+        }
+    }
+
+    /// Returns the value with the given cumulative probability (inverse transform sampling) for
+    /// distributions with a closed-form inverse.
+    fn quantile(&self, probability: f64) -> f64 {
+        match *self {
+            Self::Triangular {
+                minimum,
+                mode,
+                maximum,
+            } => {
+                let range = maximum - minimum;
+                if range <= 0. {
+                    return minimum;
+                }
+
+                let split = (mode - minimum) / range;
+                if probability < split {
+                    minimum + (probability * range * (mode - minimum)).sqrt()
+                } else {
+                    maximum - ((1. - probability) * range * (maximum - mode)).sqrt()
+                }
+            }
+            Self::Uniform { minimum, maximum } => (maximum - minimum).mul_add(probability, minimum),
+            Self::Constant(_) | Self::Normal { .. } | Self::LogNormal { .. } => {
+                unreachable!("only called for distributions with a closed-form inverse")
+            }
+        }
+    }
+}
+
+/// The cumulative distribution function of a standard normal distribution, using a Chebyshev
+/// approximation of the complementary error function with a relative error below 1.2e-7.
+fn standard_normal_cdf(value: f64) -> f64 {
+    let x = value.abs() / core::f64::consts::SQRT_2;
+    let t = 1. / (1. + 0.5 * x);
+    let polynomial = [
+        -1.265_512_23,
+        1.000_023_68,
+        0.374_091_96,
+        0.096_784_18,
+        -0.186_288_06,
+        0.278_868_07,
+        -1.135_203_98,
+        1.488_515_87,
+        -0.822_152_23,
+        0.170_872_77,
+    ]
+    .iter()
+    .rev()
+    .fold(0., |sum, coefficient| sum * t + coefficient);
+    let erfc = t * math::exp(-x * x + polynomial);
+
+    if value >= 0. {
+        1. - 0.5 * erfc
+    } else {
+        0.5 * erfc
+    }
+}
+
+impl From<f64> for Distribution {
+    fn from(value: f64) -> Self {
+        Self::Constant(value)
+    }
+}
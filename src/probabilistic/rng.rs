@@ -0,0 +1,118 @@
+use crate::math;
+#[cfg(not(feature = "std"))]
+use crate::math::FloatMath;
+
+/// A source of random numbers for stochastic features (e.g., Monte Carlo sampling), which can be
+/// implemented to use another generator.
+///
+/// With the `rand` feature enabled, this is implemented for every generator of the `rand` crate.
+/// The values only depend on the output of [`Self::next_u64`], so a generator with reproducible
+/// output gives reproducible results across runs and platforms.
+pub trait RandomNumberGenerator {
+    fn next_u64(&mut self) -> u64;
+
+    /// Returns a uniformly distributed value in `[0, 1)`.
+    fn next_f64(&mut self) -> f64 {
+        // Use the upper 53 bits, which is the precision of an f64.
+        (self.next_u64() >> 11) as f64 * (1. / (1u64 << 53) as f64)
+    }
+
+    /// Returns a standard normally distributed value using the Box-Muller transform.
+    fn next_standard_normal(&mut self) -> f64 {
+        // Use `1 - u` so that the logarithm is always finite.
+        let radius = (-2. * math::ln(1. - self.next_f64())).sqrt();
+        let angle = core::f64::consts::TAU * self.next_f64();
+        radius * math::cos(angle)
+    }
+}
+
+#[cfg(feature = "rand")]
+impl<R: rand_core::Rng + ?Sized> RandomNumberGenerator for R {
+    fn next_u64(&mut self) -> u64 {
+        rand_core::Rng::next_u64(self)
+    }
+}
+
+/// A small, seedable pseudo-random number generator (xoshiro256**), which is used when a
+/// stochastic feature is given a seed instead of a generator.
+///
+/// The output only depends on the seed, so results are reproducible across runs and platforms.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeededRng {
+    state: [u64; 4],
+}
+
+impl SeededRng {
+    pub fn new(seed: u64) -> Self {
+        // Expand the seed with SplitMix64, which avoids the all-zero state.
+        let mut seed = seed;
+        let mut next = || {
+            seed = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+            let mut z = seed;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+            z ^ (z >> 31)
+        };
+
+        Self {
+            state: [next(), next(), next(), next()],
+        }
+    }
+}
+
+impl RandomNumberGenerator for SeededRng {
+    fn next_u64(&mut self) -> u64 {
+        let result = self.state[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = self.state[1] << 17;
+
+        self.state[2] ^= self.state[0];
+        self.state[3] ^= self.state[1];
+        self.state[1] ^= self.state[2];
+        self.state[0] ^= self.state[3];
+        self.state[2] ^= t;
+        self.state[3] = self.state[3].rotate_left(45);
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn reproducible() {
+        let mut a = SeededRng::new(42);
+        let mut b = SeededRng::new(42);
+        for _ in 0..100 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+        assert_ne!(SeededRng::new(1).next_u64(), SeededRng::new(2).next_u64());
+    }
+
+    #[test]
+    fn uniform_range_and_moments() {
+        let mut rng = SeededRng::new(7);
+        let samples: Vec<f64> = (0..100_000).map(|_| rng.next_f64()).collect();
+        assert!(samples.iter().all(|sample| (0. ..1.).contains(sample)));
+
+        let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+        assert!((mean - 0.5).abs() < 0.01, "mean is {mean}");
+    }
+
+    #[test]
+    fn standard_normal_moments() {
+        let mut rng = SeededRng::new(7);
+        let samples: Vec<f64> = (0..100_000).map(|_| rng.next_standard_normal()).collect();
+        let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+        let variance = samples
+            .iter()
+            .map(|sample| math::powi(sample - mean, 2))
+            .sum::<f64>()
+            / samples.len() as f64;
+
+        assert!(mean.abs() < 0.01, "mean is {mean}");
+        assert!((variance - 1.).abs() < 0.02, "variance is {variance}");
+    }
+}
@@ -0,0 +1,503 @@
+#[cfg(not(feature = "std"))]
+use crate::math::FloatMath;
+use crate::{
+    DeclineCurveAnalysisError, DeclineTimeUnit, ExponentialParameters, FlatParameters, Forecast,
+    HyperbolicParameters, LinearParameters, NominalDeclineRate, PeriodVolume, ProductionRate,
+    Segment, approx_eq, clamp_to_duration, is_effectively_zero, math,
+    numerical::integrate,
+    periods::{fixed_period_boundaries, period_volumes_between, validate_period_length},
+    validate_duration, validate_finite, validate_non_zero_positive_rate, validate_positive,
+};
+use alloc::{string::ToString, vec, vec::Vec};
+
+/// How a ratio changes over the course of a ratio segment.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)
+)]
+pub enum RatioTrend {
+    /// The ratio stays at its initial value.
+    Constant,
+    /// The ratio changes by `slope` per unit of time.
+    Linear { slope: f64 },
+    /// The ratio grows (or shrinks, if negative) continuously at `rate` per unit of time.
+    Exponential { rate: f64 },
+    /// The ratio grows (or shrinks, if negative) at `rate` per unit of time initially, and the
+    /// growth rate slows over time like the decline rate of an Arps hyperbolic decline, i.e.,
+    /// `ratio = initial_ratio * (1 + exponent * rate * t) ^ (1 / exponent)`.
+    Hyperbolic { rate: f64, exponent: f64 },
+}
+
+/// A segment that forecasts a secondary phase as a ratio of a primary phase, such as a GOR
+/// (gas-oil ratio), WOR (water-oil ratio), or yield.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RatioSegment<Time: DeclineTimeUnit> {
+    initial_ratio: f64,
+    trend: RatioTrend,
+    incremental_duration: Time,
+}
+
+impl<Time: DeclineTimeUnit> RatioSegment<Time> {
+    pub fn initial_ratio(&self) -> f64 {
+        self.initial_ratio
+    }
+
+    pub fn trend(&self) -> RatioTrend {
+        self.trend
+    }
+
+    pub fn incremental_duration(&self) -> Time {
+        self.incremental_duration
+    }
+
+    pub fn from_incremental_duration(
+        initial_ratio: f64,
+        trend: RatioTrend,
+        incremental_duration: Time,
+    ) -> Result<Self, DeclineCurveAnalysisError> {
+        validate_positive(initial_ratio, "initial ratio")?;
+        validate_duration(incremental_duration)?;
+
+        match trend {
+            RatioTrend::Constant => {}
+            RatioTrend::Linear { slope } => validate_finite(slope, "ratio slope")?,
+            RatioTrend::Exponential { rate } => validate_finite(rate, "ratio growth rate")?,
+            RatioTrend::Hyperbolic { rate, exponent } => {
+                validate_finite(rate, "ratio growth rate")?;
+                validate_non_zero_positive_rate(exponent, "ratio exponent")?;
+            }
+        }
+
+        let segment = Self {
+            initial_ratio,
+            trend,
+            incremental_duration,
+        };
+
+        let final_ratio = segment.final_ratio();
+        if final_ratio < 0. {
+            return Err(DeclineCurveAnalysisError::InvalidInput {
+                reason: "ratio becomes negative before the end of the segment".to_string(),
+            });
+        }
+        validate_finite(final_ratio, "final ratio")?;
+
+        Ok(segment)
+    }
+
+    fn ratio_at_time_without_clamping(&self, time: f64) -> f64 {
+        match self.trend {
+            RatioTrend::Constant => self.initial_ratio,
+            RatioTrend::Linear { slope } => slope.mul_add(time, self.initial_ratio),
+            RatioTrend::Exponential { rate } => self.initial_ratio * math::exp(rate * time),
+            // A shrinking ratio reaches zero once the base reaches zero, and stays there.
+            RatioTrend::Hyperbolic { rate, exponent } => {
+                self.initial_ratio
+                    * math::powf(
+                        (exponent * rate).mul_add(time, 1.).max(0.),
+                        exponent.recip(),
+                    )
+            }
+        }
+    }
+
+    /// Returns the part of the segment that starts `offset` into the segment and lasts for
+    /// `duration`, which may extend past the end of the segment.
+    fn anchored_at(&self, offset: f64, duration: f64) -> Self {
+        let trend = match self.trend {
+            RatioTrend::Hyperbolic { rate, exponent } => RatioTrend::Hyperbolic {
+                rate: rate / (exponent * rate).mul_add(offset, 1.),
+                exponent,
+            },
+            trend => trend,
+        };
+
+        Self {
+            initial_ratio: self.ratio_at_time_without_clamping(offset),
+            trend,
+            incremental_duration: Time::from(duration),
+        }
+    }
+
+    /// The ratio at the given time, which is clamped to be within the segment.
+    pub fn ratio_at_time(&self, time: Time) -> f64 {
+        self.ratio_at_time_without_clamping(
+            clamp_to_duration(time, self.incremental_duration)
+                .value()
+                .max(0.),
+        )
+    }
+
+    pub fn final_ratio(&self) -> f64 {
+        self.ratio_at_time_without_clamping(self.incremental_duration.value())
+    }
+
+    /// Returns the first time at which the ratio is at least `ratio`, or `None` if it isn't
+    /// reached within the segment.
+    pub fn time_at_ratio(&self, ratio: f64) -> Option<Time> {
+        if self.initial_ratio >= ratio {
+            return Some(Time::from(0.));
+        }
+        if self.final_ratio() < ratio {
+            return None;
+        }
+
+        // The ratio only increases from here, so the trend has a positive slope or growth rate.
+        let time = match self.trend {
+            RatioTrend::Constant => return None,
+            RatioTrend::Linear { slope } => (ratio - self.initial_ratio) / slope,
+            RatioTrend::Exponential { rate } => math::ln(ratio / self.initial_ratio) / rate,
+            RatioTrend::Hyperbolic { rate, exponent } => {
+                (math::powf(ratio / self.initial_ratio, exponent) - 1.) / (exponent * rate)
+            }
+        };
+
+        Some(Time::from(time.min(self.incremental_duration.value())))
+    }
+
+    /// Returns the secondary segment produced alongside `primary` when the ratio starts at the
+    /// start of `primary`, such as the gas segment produced with an oil segment under a GOR trend.
+    /// The secondary segment integrates the product of the ratio and the primary rate exactly, so
+    /// its volumes don't need to be computed numerically.
+    ///
+    /// A constant ratio scales any primary segment. Otherwise, the product only has the shape of
+    /// a supported segment for an exponential ratio with an exponential or flat primary segment,
+    /// and for a linear or hyperbolic ratio with a flat primary segment.
+    pub fn secondary_segment(
+        &self,
+        primary: &Segment<Time>,
+    ) -> Result<Segment<Time>, DeclineCurveAnalysisError> {
+        let duration = primary.incremental_duration();
+        if duration.value() > self.incremental_duration.value()
+            && !approx_eq(duration.value(), self.incremental_duration.value())
+        {
+            return Err(DeclineCurveAnalysisError::InputGreaterThan {
+                field: "primary segment duration",
+                value: duration.value(),
+                other: "ratio segment duration",
+                limit: self.incremental_duration.value(),
+            });
+        }
+
+        let initial_ratio = self.initial_ratio;
+        let no_secondary = matches!(primary, Segment::Delay(_))
+            || matches!(primary, Segment::Flat(parameters) if parameters.rate().value == 0.)
+            || (initial_ratio == 0. && !matches!(self.trend, RatioTrend::Linear { .. }));
+
+        let segment = match (self.trend, primary) {
+            _ if no_secondary => primary.scale_rates(initial_ratio),
+            (RatioTrend::Constant, _) => primary.scale_rates(initial_ratio),
+            (RatioTrend::Exponential { rate }, Segment::Exponential(parameters)) => {
+                exponential_or_flat(
+                    parameters.initial_rate().value * initial_ratio,
+                    parameters.decline_rate().value() - rate,
+                    duration,
+                )?
+            }
+            (RatioTrend::Exponential { rate }, Segment::Flat(parameters)) => {
+                exponential_or_flat(parameters.rate().value * initial_ratio, -rate, duration)?
+            }
+            (RatioTrend::Linear { slope }, Segment::Flat(parameters)) => {
+                let initial_rate = ProductionRate::new(parameters.rate().value * initial_ratio);
+                if is_effectively_zero(slope) {
+                    FlatParameters::from_incremental_duration(initial_rate, duration)?.into()
+                } else {
+                    LinearParameters::from_incremental_duration(
+                        initial_rate,
+                        NominalDeclineRate::new(-slope / initial_ratio),
+                        duration,
+                    )?
+                    .into()
+                }
+            }
+            (RatioTrend::Hyperbolic { rate, exponent }, Segment::Flat(parameters)) => {
+                let initial_rate = ProductionRate::new(parameters.rate().value * initial_ratio);
+                if is_effectively_zero(rate) {
+                    FlatParameters::from_incremental_duration(initial_rate, duration)?.into()
+                } else {
+                    // A hyperbolic ratio is an Arps hyperbolic decline with the signs of its
+                    // decline rate and exponent flipped.
+                    HyperbolicParameters::from_incremental_duration(
+                        initial_rate,
+                        NominalDeclineRate::new(-rate),
+                        duration,
+                        -exponent,
+                    )?
+                    .into()
+                }
+            }
+            _ => {
+                return Err(DeclineCurveAnalysisError::InvalidInput {
+                    reason: "the product of the ratio trend and the primary segment doesn't have \
+                             the shape of a supported segment"
+                        .to_string(),
+                });
+            }
+        };
+
+        Ok(segment)
+    }
+}
+
+/// Returns an exponential segment, or a flat segment if the decline rate is zero.
+fn exponential_or_flat<Time: DeclineTimeUnit>(
+    initial_rate: f64,
+    decline_rate: f64,
+    incremental_duration: Time,
+) -> Result<Segment<Time>, DeclineCurveAnalysisError> {
+    let initial_rate = ProductionRate::new(initial_rate);
+
+    Ok(if is_effectively_zero(decline_rate) {
+        FlatParameters::from_incremental_duration(initial_rate, incremental_duration)?.into()
+    } else {
+        ExponentialParameters::from_incremental_duration(
+            initial_rate,
+            NominalDeclineRate::new(decline_rate),
+            incremental_duration,
+        )?
+        .into()
+    })
+}
+
+/// A sequence of ratio segments that forecasts a secondary phase from a primary forecast, where
+/// each segment starts at the end of the previous one.
+///
+/// The secondary phase shares the time base of the primary forecast, so its volumes end when the
+/// primary forecast ends. The last ratio is held after the end of the ratio segments.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RatioForecast<Time: DeclineTimeUnit> {
+    segments: Vec<RatioSegment<Time>>,
+}
+
+impl<Time: DeclineTimeUnit> RatioForecast<Time> {
+    pub fn new(segments: Vec<RatioSegment<Time>>) -> Self {
+        Self { segments }
+    }
+
+    pub fn segments(&self) -> &[RatioSegment<Time>] {
+        &self.segments
+    }
+
+    pub fn incremental_duration(&self) -> Time {
+        Time::from(self.segments.iter().fold(0., |total, segment| {
+            total + segment.incremental_duration().value()
+        }))
+    }
+
+    /// Returns the segment that is active at the given time along with its start time. Times past
+    /// the end return the last segment.
+    fn segment_at_time(&self, time: f64) -> Option<(f64, &RatioSegment<Time>)> {
+        let mut start = 0.;
+        let last_index = self.segments.len().checked_sub(1)?;
+
+        for (index, segment) in self.segments.iter().enumerate() {
+            let end = start + segment.incremental_duration().value();
+            if time < end || index == last_index {
+                return Some((start, segment));
+            }
+            start = end;
+        }
+
+        None
+    }
+
+    /// The ratio at the given time. An empty ratio forecast has a ratio of zero.
+    pub fn ratio_at_time(&self, time: Time) -> f64 {
+        match self.segment_at_time(time.value()) {
+            Some((start, segment)) => segment.ratio_at_time(Time::from(time.value() - start)),
+            None => 0.,
+        }
+    }
+
+    /// Returns the first time at which the ratio is at least `ratio`, or `None` if it's never
+    /// reached. The last ratio is held after the end of the ratio segments, so the ratio can't be
+    /// reached after that.
+    pub fn time_at_ratio(&self, ratio: f64) -> Result<Option<Time>, DeclineCurveAnalysisError> {
+        validate_finite(ratio, "ratio")?;
+
+        let mut start = 0.;
+        for segment in &self.segments {
+            if let Some(time) = segment.time_at_ratio(ratio) {
+                return Ok(Some(Time::from(start + time.value())));
+            }
+            start += segment.incremental_duration().value();
+        }
+
+        Ok(None)
+    }
+
+    /// Returns the secondary forecast produced alongside `primary`, such as the gas forecast
+    /// produced with an oil forecast under a GOR trend. The primary segments are split wherever
+    /// the ratio changes segment, and each piece is converted with
+    /// [`RatioSegment::secondary_segment`], so this fails if any piece doesn't have the shape of a
+    /// supported segment.
+    pub fn secondary_forecast(
+        &self,
+        primary: &Forecast<Time>,
+    ) -> Result<Forecast<Time>, DeclineCurveAnalysisError> {
+        let mut segments = Vec::with_capacity(primary.segments().len());
+        let mut start = 0.;
+
+        for segment in primary.segments() {
+            let end = start + segment.incremental_duration().value();
+            let mut remaining = segment.clone();
+
+            while let Some((ratio, ratio_end)) = self.remaining_segment_at_time(start) {
+                // Only split the primary segment when the ratio changes segment part way through.
+                let piece = if ratio_end < end && !approx_eq(ratio_end, end) {
+                    let (piece, rest) = remaining.split_at(Time::from(ratio_end - start))?;
+                    remaining = rest;
+                    start = ratio_end;
+                    piece
+                } else {
+                    start = end;
+                    remaining.clone()
+                };
+
+                let ratio = ratio.anchored_at(0., piece.incremental_duration().value());
+                segments.push(ratio.secondary_segment(&piece)?);
+
+                if start >= end {
+                    break;
+                }
+            }
+
+            if self.segments.is_empty() {
+                segments.push(segment.scale_rates(0.));
+            }
+            start = end;
+        }
+
+        Ok(Forecast::new(segments))
+    }
+
+    /// Returns the ratio segment that's active at `time`, re-anchored to start at `time`, along
+    /// with the time it ends. The last ratio is held after the end of the ratio segments, and an
+    /// empty ratio forecast returns `None`.
+    fn remaining_segment_at_time(&self, time: f64) -> Option<(RatioSegment<Time>, f64)> {
+        let (start, segment) = self.segment_at_time(time)?;
+        let end = start + segment.incremental_duration().value();
+
+        if time < end && !approx_eq(time, end) {
+            Some((segment.anchored_at(time - start, end - time), end))
+        } else {
+            let held = RatioSegment {
+                initial_ratio: segment.final_ratio(),
+                trend: RatioTrend::Constant,
+                incremental_duration: Time::from(0.),
+            };
+            Some((held, f64::INFINITY))
+        }
+    }
+
+    /// The secondary rate at the given time, which is the ratio multiplied by the primary rate.
+    pub fn rate_at_time(&self, primary: &Forecast<Time>, time: Time) -> ProductionRate<Time> {
+        ProductionRate::new(self.ratio_at_time(time) * primary.rate_at_time(time).value())
+    }
+
+    /// The secondary volume produced over the whole primary forecast.
+    pub fn incremental_volume(&self, primary: &Forecast<Time>) -> f64 {
+        self.incremental_volume_between(primary, Time::from(0.), primary.incremental_duration())
+    }
+
+    /// The secondary volume produced between two times, which are clamped to be within the
+    /// primary forecast. This is negative if `end` is before `start`.
+    pub fn incremental_volume_between(
+        &self,
+        primary: &Forecast<Time>,
+        start: Time,
+        end: Time,
+    ) -> f64 {
+        let (lower, upper, sign) = if start.value() <= end.value() {
+            (start.value(), end.value(), 1.)
+        } else {
+            (end.value(), start.value(), -1.)
+        };
+        let lower = lower.max(0.);
+        let upper = upper.min(primary.incremental_duration().value());
+
+        // Split the interval wherever the ratio or the primary forecast changes segment, so that
+        // each piece is smooth.
+        let mut breakpoints = vec![lower, upper];
+        for durations in [
+            self.segments
+                .iter()
+                .map(|segment| segment.incremental_duration().value())
+                .collect::<Vec<_>>(),
+            primary
+                .segments()
+                .iter()
+                .map(|segment| segment.incremental_duration().value())
+                .collect(),
+        ] {
+            let mut boundary = 0.;
+            for duration in durations {
+                boundary += duration;
+                if boundary > lower && boundary < upper {
+                    breakpoints.push(boundary);
+                }
+            }
+        }
+        breakpoints.sort_by(f64::total_cmp);
+
+        let volume = breakpoints
+            .windows(2)
+            .filter(|piece| piece[0] < piece[1])
+            .fold(0., |total, piece| {
+                let (piece_start, piece_end) = (piece[0], piece[1]);
+                let midpoint = 0.5 * (piece_start + piece_end);
+
+                // A constant ratio is exact, so it doesn't need numerical integration.
+                let piece_volume = match self.segment_at_time(midpoint) {
+                    Some((_, segment)) if segment.trend() == RatioTrend::Constant => {
+                        segment.initial_ratio()
+                            * primary.incremental_volume_between(
+                                Time::from(piece_start),
+                                Time::from(piece_end),
+                            )
+                    }
+                    Some(_) => integrate(
+                        |time| self.rate_at_time(primary, Time::from(time)).value(),
+                        piece_start,
+                        piece_end,
+                    ),
+                    None => 0.,
+                };
+
+                total + piece_volume
+            });
+
+        sign * volume
+    }
+
+    /// Returns the secondary volume produced in each period of the given length, aligned to the
+    /// periods of the primary forecast.
+    pub fn volumes_by_period<'a>(
+        &'a self,
+        primary: &'a Forecast<Time>,
+        period_length: Time,
+    ) -> Result<impl Iterator<Item = PeriodVolume<Time>> + 'a, DeclineCurveAnalysisError> {
+        validate_period_length(period_length)?;
+
+        Ok(period_volumes_between(
+            primary.incremental_duration().value(),
+            fixed_period_boundaries(period_length.value()),
+            move |start, end| self.incremental_volume_between(primary, start, end),
+        ))
+    }
+}
+
+impl<Time: DeclineTimeUnit> From<Vec<RatioSegment<Time>>> for RatioForecast<Time> {
+    fn from(segments: Vec<RatioSegment<Time>>) -> Self {
+        Self::new(segments)
+    }
+}
+
+impl<Time: DeclineTimeUnit> FromIterator<RatioSegment<Time>> for RatioForecast<Time> {
+    fn from_iter<I: IntoIterator<Item = RatioSegment<Time>>>(iter: I) -> Self {
+        Self::new(iter.into_iter().collect())
+    }
+}
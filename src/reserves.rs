@@ -0,0 +1,111 @@
+use crate::{
+    BindingConstraint, DeclineCurveAnalysisError, DeclineTimeUnit, Forecast, ProductionRate,
+    validate_positive,
+};
+
+/// A summary of the reserves for a forecast at a point in time, after applying an economic limit.
+///
+/// The cumulative production to date is the forecast's initial cumulative volume (see
+/// [`Forecast::with_initial_cumulative_volume`]) plus the forecast volume up to the as-of time, so
+/// the estimated ultimate recovery includes the production before the start of the forecast.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReservesReport<Time: DeclineTimeUnit> {
+    /// The time at which the reserves are evaluated, relative to the start of the forecast.
+    pub as_of_time: Time,
+    /// The volume produced before the start of the forecast, plus the volume produced from the
+    /// start of the forecast to the as-of time.
+    pub cumulative_production: f64,
+    /// The volume remaining to be produced after the as-of time until the economic limit.
+    pub remaining_reserves: f64,
+    /// The total volume produced until the economic limit, which is the cumulative production plus
+    /// the remaining reserves.
+    pub estimated_ultimate_recovery: f64,
+    /// The time at which the forecast reaches the economic limit, or the maximum life if that's
+    /// reached first.
+    pub economic_life: Time,
+    /// The time remaining after the as-of time until the end of the economic life.
+    pub remaining_life: Time,
+    /// The limit that ended the forecast.
+    pub binding_constraint: BindingConstraint,
+}
+
+/// Calculates the estimated ultimate recovery (EUR) of the forecast, which is the total volume
+/// produced until the rate falls to the economic limit.
+pub fn estimated_ultimate_recovery<Time: DeclineTimeUnit>(
+    forecast: &Forecast<Time>,
+    economic_limit_rate: ProductionRate<Time>,
+) -> Result<ReservesReport<Time>, DeclineCurveAnalysisError> {
+    remaining_reserves(forecast, Time::from(0.), economic_limit_rate)
+}
+
+/// Calculates the estimated ultimate recovery (EUR) of the forecast, which is the total volume
+/// produced until the rate falls to the economic limit or the forecast reaches the maximum life,
+/// whichever comes first.
+pub fn estimated_ultimate_recovery_with_maximum_life<Time: DeclineTimeUnit>(
+    forecast: &Forecast<Time>,
+    economic_limit_rate: ProductionRate<Time>,
+    maximum_life: Time,
+) -> Result<ReservesReport<Time>, DeclineCurveAnalysisError> {
+    remaining_reserves_with_maximum_life(
+        forecast,
+        Time::from(0.),
+        economic_limit_rate,
+        maximum_life,
+    )
+}
+
+/// Calculates the reserves remaining after the as-of time until the rate falls to the economic
+/// limit.
+///
+/// If the as-of time is after the economic limit, there are no remaining reserves, and the
+/// cumulative production is the estimated ultimate recovery.
+pub fn remaining_reserves<Time: DeclineTimeUnit>(
+    forecast: &Forecast<Time>,
+    as_of_time: Time,
+    economic_limit_rate: ProductionRate<Time>,
+) -> Result<ReservesReport<Time>, DeclineCurveAnalysisError> {
+    reserves_report(forecast, as_of_time, economic_limit_rate, None)
+}
+
+/// Calculates the reserves remaining after the as-of time until the rate falls to the economic
+/// limit or the forecast reaches the maximum life, whichever comes first.
+pub fn remaining_reserves_with_maximum_life<Time: DeclineTimeUnit>(
+    forecast: &Forecast<Time>,
+    as_of_time: Time,
+    economic_limit_rate: ProductionRate<Time>,
+    maximum_life: Time,
+) -> Result<ReservesReport<Time>, DeclineCurveAnalysisError> {
+    reserves_report(
+        forecast,
+        as_of_time,
+        economic_limit_rate,
+        Some(maximum_life),
+    )
+}
+
+fn reserves_report<Time: DeclineTimeUnit>(
+    forecast: &Forecast<Time>,
+    as_of_time: Time,
+    economic_limit_rate: ProductionRate<Time>,
+    maximum_life: Option<Time>,
+) -> Result<ReservesReport<Time>, DeclineCurveAnalysisError> {
+    validate_positive(as_of_time.value(), "as-of time")?;
+
+    let truncation = forecast.truncate_at_limits(economic_limit_rate, maximum_life)?;
+    let truncated = truncation.forecast;
+    let estimated_ultimate_recovery = truncated.cumulative_volume();
+    let economic_life = truncated.incremental_duration();
+    let cumulative_production = truncated
+        .cumulative_volume_at_time(as_of_time)
+        .min(estimated_ultimate_recovery);
+
+    Ok(ReservesReport {
+        as_of_time,
+        cumulative_production,
+        remaining_reserves: estimated_ultimate_recovery - cumulative_production,
+        estimated_ultimate_recovery,
+        economic_life,
+        remaining_life: Time::from((economic_life.value() - as_of_time.value()).max(0.)),
+        binding_constraint: truncation.binding_constraint,
+    })
+}
@@ -0,0 +1,192 @@
+use crate::{
+    DeclineCurveAnalysisError, DeclineTimeUnit, Forecast, PeriodVolume, ProductionRate,
+    periods::{fixed_period_boundaries, period_volumes_between, validate_period_length},
+    validate_positive,
+};
+use alloc::vec::Vec;
+
+/// How a restimulation combines with the production that came before it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RestimulationKind {
+    /// The earlier production stops at the restimulation, and the new forecast replaces it, e.g.,
+    /// for a refrac that resets the decline.
+    Replace,
+    /// The new forecast is added to the earlier production, which keeps declining, e.g., for a
+    /// workover that adds incremental production.
+    Superpose,
+}
+
+/// A refrac or workover that starts a new forecast at a given time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Restimulation<Time: DeclineTimeUnit> {
+    start: Time,
+    forecast: Forecast<Time>,
+    kind: RestimulationKind,
+}
+
+impl<Time: DeclineTimeUnit> Restimulation<Time> {
+    /// Creates a restimulation where the forecast starts at `start`, relative to the start of the
+    /// base forecast.
+    pub fn new(
+        start: Time,
+        forecast: Forecast<Time>,
+        kind: RestimulationKind,
+    ) -> Result<Self, DeclineCurveAnalysisError> {
+        validate_positive(start.value(), "restimulation start")?;
+
+        Ok(Self {
+            start,
+            forecast,
+            kind,
+        })
+    }
+
+    pub fn start(&self) -> Time {
+        self.start
+    }
+
+    pub fn forecast(&self) -> &Forecast<Time> {
+        &self.forecast
+    }
+
+    pub fn kind(&self) -> RestimulationKind {
+        self.kind
+    }
+}
+
+/// A forecast that's active from `start` (inclusive) to `end` (exclusive).
+struct Component<'a, Time: DeclineTimeUnit> {
+    start: f64,
+    end: f64,
+    forecast: &'a Forecast<Time>,
+}
+
+/// A base forecast with restimulations that start new forecasts part way through its life.
+///
+/// Each forecast runs until it ends or until a later restimulation replaces it, and the rates of
+/// the forecasts that are active at the same time are added together.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RestimulatedForecast<Time: DeclineTimeUnit> {
+    base: Forecast<Time>,
+    restimulations: Vec<Restimulation<Time>>,
+}
+
+impl<Time: DeclineTimeUnit> RestimulatedForecast<Time> {
+    /// Creates a restimulated forecast. The restimulations are ordered by their start time, and
+    /// restimulations that start at the same time keep their given order.
+    pub fn new(base: Forecast<Time>, mut restimulations: Vec<Restimulation<Time>>) -> Self {
+        restimulations.sort_by(|a, b| a.start.value().total_cmp(&b.start.value()));
+
+        Self {
+            base,
+            restimulations,
+        }
+    }
+
+    pub fn base(&self) -> &Forecast<Time> {
+        &self.base
+    }
+
+    pub fn restimulations(&self) -> &[Restimulation<Time>] {
+        &self.restimulations
+    }
+
+    /// Returns the base forecast and each restimulation forecast, along with the times they're
+    /// active for.
+    fn components(&self) -> impl Iterator<Item = Component<'_, Time>> {
+        let base = (0., &self.base);
+        let restimulations = self
+            .restimulations
+            .iter()
+            .map(|restimulation| (restimulation.start.value(), &restimulation.forecast));
+
+        core::iter::once(base)
+            .chain(restimulations)
+            .enumerate()
+            .map(|(index, (start, forecast))| {
+                let replaced_at = self.restimulations[index..]
+                    .iter()
+                    .find(|restimulation| restimulation.kind == RestimulationKind::Replace)
+                    .map_or(f64::INFINITY, |restimulation| restimulation.start.value());
+                let end = (start + forecast.incremental_duration().value()).min(replaced_at);
+
+                Component {
+                    start,
+                    end,
+                    forecast,
+                }
+            })
+    }
+
+    /// The time at which the last forecast ends.
+    pub fn incremental_duration(&self) -> Time {
+        Time::from(
+            self.components()
+                .fold(0., |duration, component| component.end.max(duration)),
+        )
+    }
+
+    /// The sum of the rates of the forecasts that are active at the given time. Rates are zero
+    /// after the end of the last forecast.
+    pub fn rate_at_time(&self, time: Time) -> ProductionRate<Time> {
+        let time = time.value();
+
+        ProductionRate::new(
+            self.components()
+                .filter(|component| time >= component.start && time < component.end)
+                .map(|component| {
+                    component
+                        .forecast
+                        .rate_at_time(Time::from(time - component.start))
+                        .value()
+                })
+                .fold(0., |total, rate| total + rate),
+        )
+    }
+
+    /// The volume produced between two times by every forecast. This is negative if `end` is
+    /// before `start`.
+    pub fn incremental_volume_between(&self, start: Time, end: Time) -> f64 {
+        let (lower, upper, sign) = if start.value() <= end.value() {
+            (start.value(), end.value(), 1.)
+        } else {
+            (end.value(), start.value(), -1.)
+        };
+
+        let volume = self
+            .components()
+            .filter(|component| component.start < upper && component.end > lower)
+            .map(|component| {
+                component.forecast.incremental_volume_between(
+                    Time::from(lower.max(component.start) - component.start),
+                    Time::from(upper.min(component.end) - component.start),
+                )
+            })
+            .fold(0., |total, volume| total + volume);
+
+        sign * volume
+    }
+
+    pub fn incremental_volume_at_time(&self, time: Time) -> f64 {
+        self.incremental_volume_between(Time::from(0.), time)
+    }
+
+    pub fn incremental_volume(&self) -> f64 {
+        self.incremental_volume_between(Time::from(0.), self.incremental_duration())
+    }
+
+    /// Returns the volume produced in each period of the given length until the last forecast
+    /// ends.
+    pub fn volumes_by_period(
+        &self,
+        period_length: Time,
+    ) -> Result<impl Iterator<Item = PeriodVolume<Time>> + '_, DeclineCurveAnalysisError> {
+        validate_period_length(period_length)?;
+
+        Ok(period_volumes_between(
+            self.incremental_duration().value(),
+            fixed_period_boundaries(period_length.value()),
+            move |start, end| self.incremental_volume_between(start, end),
+        ))
+    }
+}
@@ -0,0 +1,281 @@
+//! Basic rate-transient analysis (RTA), which accounts for changing flowing pressures by
+//! normalizing rates by the pressure drop from the initial reservoir pressure.
+//!
+//! Pressures can be in any unit, as long as the initial and flowing pressures use the same one.
+
+#[cfg(not(feature = "std"))]
+use crate::math::FloatMath;
+use crate::{
+    DeclineCurveAnalysisError, DeclineTimeUnit, ExponentialParameters, HarmonicParameters,
+    HyperbolicParameters, NominalDeclineRate, ProductionRate, Segment,
+    diagnostics::cumulative_samples, fit::linear_regression, math, numerical::levenberg_marquardt,
+    validate_finite,
+};
+use alloc::{string::ToString, vec::Vec};
+use core::ops::Bound;
+
+/// The Arps exponents of the decline stems on the Fetkovich type curve, from exponential (zero) to
+/// harmonic (one).
+pub const FETKOVICH_STEMS: [f64; 11] = [0., 0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8, 0.9, 1.];
+
+/// The largest stem exponent, which matches the range of hyperbolic fits.
+const MAX_STEM_EXPONENT: f64 = 2.;
+
+/// A rate along with the flowing (bottomhole) pressure at the same time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RtaSample<Time: DeclineTimeUnit> {
+    pub time: Time,
+    pub rate: ProductionRate<Time>,
+    pub flowing_pressure: f64,
+}
+
+/// A sample normalized by its pressure drop.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NormalizedRatePoint<Time: DeclineTimeUnit> {
+    pub time: Time,
+    /// The initial pressure minus the flowing pressure.
+    pub pressure_drop: f64,
+    /// The rate divided by the pressure drop, i.e., the productivity index.
+    pub normalized_rate: f64,
+    /// The cumulative volume divided by the pressure drop.
+    pub normalized_cumulative_volume: f64,
+    /// The cumulative volume divided by the rate, which converts variable rates to an equivalent
+    /// constant rate time.
+    pub material_balance_time: Time,
+}
+
+/// Normalizes samples by their pressure drops from the initial pressure.
+///
+/// Cumulative volumes integrate the rates in the same way as the diagnostic plots. Samples with a
+/// zero rate are included in the cumulative volume, but have no point, because their material
+/// balance time isn't defined.
+pub fn normalized_rates<Time: DeclineTimeUnit>(
+    samples: &[RtaSample<Time>],
+    initial_pressure: f64,
+) -> Result<Vec<NormalizedRatePoint<Time>>, DeclineCurveAnalysisError> {
+    validate_finite(initial_pressure, "initial pressure")?;
+    for sample in samples {
+        validate_finite(sample.flowing_pressure, "flowing pressure")?;
+        if sample.flowing_pressure >= initial_pressure {
+            return Err(DeclineCurveAnalysisError::InputNotLessThan {
+                field: "flowing pressure",
+                value: sample.flowing_pressure,
+                other: "initial pressure",
+                limit: initial_pressure,
+            });
+        }
+    }
+
+    let rates: Vec<_> = samples
+        .iter()
+        .map(|sample| (sample.time, sample.rate))
+        .collect();
+
+    Ok(cumulative_samples(&rates)?
+        .into_iter()
+        .zip(samples)
+        .filter(|((_, rate, _), _)| *rate > 0.)
+        .map(|((time, rate, cumulative_volume), sample)| {
+            let pressure_drop = initial_pressure - sample.flowing_pressure;
+            NormalizedRatePoint {
+                time: Time::from(time),
+                pressure_drop,
+                normalized_rate: rate / pressure_drop,
+                normalized_cumulative_volume: cumulative_volume / pressure_drop,
+                material_balance_time: Time::from(cumulative_volume / rate),
+            }
+        })
+        .collect())
+}
+
+/// A straight line through the boundary-dominated points of a flowing material balance plot, which
+/// is the normalized rate against the normalized cumulative volume.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FlowingMaterialBalance {
+    /// The normalized rate at zero normalized cumulative volume.
+    pub intercept: f64,
+    /// The change in normalized rate per unit of normalized cumulative volume, which is negative.
+    pub slope: f64,
+    /// The normalized cumulative volume at which the normalized rate reaches zero.
+    ///
+    /// This is proportional to the contacted hydrocarbons in place, e.g., it's the oil in place
+    /// times the total compressibility for an undersaturated oil reservoir.
+    pub normalized_ultimate_volume: f64,
+}
+
+/// Fits a flowing material balance line to the points at or after the start of boundary-dominated
+/// flow, e.g., where the material balance time plots with a slope of -1 on a Blasingame plot.
+pub fn flowing_material_balance<Time: DeclineTimeUnit>(
+    points: &[NormalizedRatePoint<Time>],
+    boundary_dominated_start: Time,
+) -> Result<FlowingMaterialBalance, DeclineCurveAnalysisError> {
+    let boundary_dominated: Vec<_> = points
+        .iter()
+        .filter(|point| point.time.value() >= boundary_dominated_start.value())
+        .map(|point| (point.normalized_cumulative_volume, point.normalized_rate))
+        .collect();
+    if boundary_dominated.len() < 2 {
+        return Err(DeclineCurveAnalysisError::TooFewInputs {
+            field: "boundary-dominated point count",
+            count: boundary_dominated.len(),
+            minimum: 2,
+        });
+    }
+
+    let (slope, intercept) = linear_regression(boundary_dominated.into_iter());
+    if !(slope < 0. && intercept > 0.) {
+        return Err(DeclineCurveAnalysisError::InvalidInput {
+            reason: "normalized rates don't decline with normalized cumulative volume".to_string(),
+        });
+    }
+
+    Ok(FlowingMaterialBalance {
+        intercept,
+        slope,
+        normalized_ultimate_volume: -intercept / slope,
+    })
+}
+
+/// The Arps decline that best matches normalized samples on the Fetkovich type curve.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeCurveMatch<Time: DeclineTimeUnit> {
+    /// The matched decline, which is exponential or harmonic for those stems, and spans the
+    /// samples.
+    pub segment: Segment<Time>,
+    /// The exponent of the matched stem.
+    pub exponent: f64,
+    /// The pressure drop that the rates were normalized to, which is the pressure drop of the last
+    /// sample, so the matched decline continues at the latest flowing pressure.
+    pub reference_pressure_drop: f64,
+    /// The root mean squared error between the natural logarithms of the normalized rates and the
+    /// matched rates.
+    pub root_mean_squared_log_error: f64,
+}
+
+/// Matches samples against the Arps decline stems of the Fetkovich type curve, e.g.,
+/// [`FETKOVICH_STEMS`], and returns the stem with the smallest error.
+///
+/// The rates are normalized to the pressure drop of the last sample to remove the effect of
+/// changing flowing pressures. For each stem, the initial rate and decline rate are fitted on
+/// logarithmic rates, which is how the samples are overlaid on the log-log type curve.
+pub fn match_fetkovich_type_curve<Time: DeclineTimeUnit>(
+    samples: &[RtaSample<Time>],
+    initial_pressure: f64,
+    stems: &[f64],
+) -> Result<TypeCurveMatch<Time>, DeclineCurveAnalysisError> {
+    if stems.is_empty() {
+        return Err(DeclineCurveAnalysisError::TooFewInputs {
+            field: "stem count",
+            count: 0,
+            minimum: 1,
+        });
+    }
+    for exponent in stems {
+        validate_finite(*exponent, "exponent")?;
+        if !(0. ..=MAX_STEM_EXPONENT).contains(exponent) {
+            return Err(DeclineCurveAnalysisError::InputOutOfRange {
+                field: "stem exponent",
+                value: *exponent,
+                minimum: Bound::Included(0.),
+                maximum: Bound::Included(MAX_STEM_EXPONENT),
+            });
+        }
+    }
+
+    let points = normalized_rates(samples, initial_pressure)?;
+    if points.len() < 3 {
+        return Err(DeclineCurveAnalysisError::TooFewInputs {
+            field: "positive rate sample count",
+            count: points.len(),
+            minimum: 3,
+        });
+    }
+
+    let reference_pressure_drop = initial_pressure
+        - samples
+            .last()
+            .map_or(initial_pressure, |sample| sample.flowing_pressure);
+    let log_rates: Vec<(f64, f64)> = points
+        .iter()
+        .map(|point| {
+            (
+                point.time.value(),
+                math::ln(point.normalized_rate * reference_pressure_drop),
+            )
+        })
+        .collect();
+
+    // Start each stem from the exponential that best fits the logarithmic rates.
+    let (slope, intercept) = linear_regression(log_rates.iter().copied());
+    let initial_rate = math::exp(intercept);
+    let initial = [initial_rate, (-slope).max(0.)];
+    let lower = [initial_rate * 1e-6, 0.];
+    let upper = [initial_rate * 1e6, f64::MAX];
+
+    let residuals = |exponent: f64, parameters: &[f64]| -> Vec<f64> {
+        log_rates
+            .iter()
+            .map(|(time, log_rate)| {
+                log_rate - arps_log_rate(parameters[0], parameters[1], exponent, *time)
+            })
+            .collect()
+    };
+
+    let (exponent, parameters, sum_of_squares) = stems
+        .iter()
+        .filter_map(|&exponent| {
+            let solution = levenberg_marquardt(
+                |parameters| residuals(exponent, parameters),
+                &initial,
+                &lower,
+                &upper,
+            )?;
+            let sum_of_squares: f64 = residuals(exponent, &solution.parameters)
+                .iter()
+                .map(|residual| residual * residual)
+                .sum();
+            sum_of_squares
+                .is_finite()
+                .then_some((exponent, solution.parameters, sum_of_squares))
+        })
+        .min_by(|(_, _, a), (_, _, b)| a.total_cmp(b))
+        .ok_or(DeclineCurveAnalysisError::FitDidNotConverge)?;
+
+    let initial_rate = ProductionRate::new(parameters[0]);
+    let decline_rate = NominalDeclineRate::new(parameters[1]);
+    let duration = Time::from(
+        log_rates
+            .iter()
+            .fold(0., |end: f64, (time, _)| end.max(*time)),
+    );
+    let segment = if exponent == 0. {
+        ExponentialParameters::from_incremental_duration(initial_rate, decline_rate, duration)?
+            .into()
+    } else if exponent == 1. {
+        HarmonicParameters::from_incremental_duration(initial_rate, decline_rate, duration)?.into()
+    } else {
+        HyperbolicParameters::from_incremental_duration(
+            initial_rate,
+            decline_rate,
+            duration,
+            exponent,
+        )?
+        .into()
+    };
+
+    Ok(TypeCurveMatch {
+        segment,
+        exponent,
+        reference_pressure_drop,
+        root_mean_squared_log_error: (sum_of_squares / log_rates.len() as f64).sqrt(),
+    })
+}
+
+/// The natural logarithm of the Arps rate, which is exponential for an exponent of zero.
+fn arps_log_rate(initial_rate: f64, decline_rate: f64, exponent: f64, time: f64) -> f64 {
+    if exponent == 0. {
+        math::ln(initial_rate) - decline_rate * time
+    } else {
+        math::ln(initial_rate) - math::ln_1p(exponent * decline_rate * time) / exponent
+    }
+}
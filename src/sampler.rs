@@ -0,0 +1,185 @@
+#[cfg(not(feature = "std"))]
+use crate::math::FloatMath;
+use crate::{
+    DeclineCurveAnalysisError, DeclineTimeUnit, ProductionRate, Segment, approx_eq, fast_math,
+    math, validate_non_zero_positive_rate,
+};
+
+/// The rate and cumulative volume of a segment or forecast at a sampled time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SamplePoint<Time: DeclineTimeUnit> {
+    pub time: Time,
+    pub rate: ProductionRate<Time>,
+    /// The volume produced from the start of the segment or forecast until `time`.
+    pub incremental_volume: f64,
+}
+
+/// Values that are cached for the active segment, so that each step avoids an `exp` or a second
+/// `powf`.
+#[derive(Debug, Clone, Copy)]
+enum StepCache {
+    None,
+    /// The rate is multiplied by `exp(-D * step)` at each step.
+    Exponential {
+        step_factor: f64,
+        previous: Option<(f64, f64)>,
+    },
+    /// The volume is computed from the rate, because `(1 + b * D * t) ^ (1 - 1 / b)` is the base
+    /// multiplied by `q / q_i`.
+    Hyperbolic,
+}
+
+/// Walks a segment or forecast on a fixed step without allocating, yielding a sample at each
+/// multiple of the step and a final sample at the end.
+///
+/// Consecutive samples reuse work where possible, e.g., the rate of an exponential segment is
+/// multiplied by a constant factor at each step instead of evaluating `exp`, which makes daily
+/// sampling of long forecasts cheaper.
+#[derive(Debug, Clone)]
+pub struct SegmentSampler<'a, Time: DeclineTimeUnit> {
+    segments: &'a [Segment<Time>],
+    step: f64,
+    end: f64,
+    sample_index: u64,
+    finished: bool,
+    segment_index: usize,
+    segment_start: f64,
+    completed_volume: f64,
+    cache: StepCache,
+}
+
+impl<'a, Time: DeclineTimeUnit> SegmentSampler<'a, Time> {
+    /// Creates a sampler over segments that are evaluated one after another, like a forecast.
+    pub fn new(
+        segments: &'a [Segment<Time>],
+        step: Time,
+    ) -> Result<Self, DeclineCurveAnalysisError> {
+        validate_non_zero_positive_rate(step.value(), "sampling step")?;
+
+        let end = segments.iter().fold(0., |total, segment| {
+            total + segment.incremental_duration().value()
+        });
+        let mut sampler = Self {
+            segments,
+            step: step.value(),
+            end,
+            sample_index: 0,
+            finished: segments.is_empty(),
+            segment_index: 0,
+            segment_start: 0.,
+            completed_volume: 0.,
+            cache: StepCache::None,
+        };
+        sampler.cache = sampler.step_cache();
+
+        Ok(sampler)
+    }
+
+    fn step_cache(&self) -> StepCache {
+        match self.segments.get(self.segment_index) {
+            Some(Segment::Exponential(parameters)) => StepCache::Exponential {
+                step_factor: math::exp(-parameters.decline_rate().value() * self.step),
+                previous: None,
+            },
+            Some(Segment::Hyperbolic(_)) => StepCache::Hyperbolic,
+            _ => StepCache::None,
+        }
+    }
+
+    /// Evaluates the active segment at the time since its start.
+    fn evaluate(&mut self, segment: &Segment<Time>, time: f64) -> (f64, f64) {
+        match (&mut self.cache, segment) {
+            (
+                StepCache::Exponential {
+                    step_factor,
+                    previous,
+                },
+                Segment::Exponential(parameters),
+            ) => {
+                let initial_rate = parameters.initial_rate().value();
+                let rate = match *previous {
+                    Some((previous_time, previous_rate))
+                        if approx_eq(time - previous_time, self.step) =>
+                    {
+                        previous_rate * *step_factor
+                    }
+                    _ => parameters.rate_at_time(Time::from(time)).value(),
+                };
+                *previous = Some((time, rate));
+
+                let volume = (initial_rate - rate) / parameters.decline_rate().value();
+                (rate, volume)
+            }
+            (StepCache::Hyperbolic, Segment::Hyperbolic(parameters)) => {
+                let initial_rate = parameters.initial_rate().value();
+                let initial_decline_rate = parameters.initial_decline_rate().value();
+                let exponent = parameters.exponent();
+
+                let base = time.mul_add(exponent * initial_decline_rate, 1.);
+                let rate = initial_rate * fast_math::powf(base, -exponent.recip());
+                let volume =
+                    base.mul_add(-rate, initial_rate) / (initial_decline_rate * (1. - exponent));
+                (rate, volume)
+            }
+            _ => (
+                segment.rate_at_time(Time::from(time)).value(),
+                segment.incremental_volume_at_time(Time::from(time)),
+            ),
+        }
+    }
+}
+
+impl<Time: DeclineTimeUnit> Iterator for SegmentSampler<'_, Time> {
+    type Item = SamplePoint<Time>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        // Times are computed by multiplication so that round-off doesn't accumulate.
+        let mut time = self.sample_index as f64 * self.step;
+        if time >= self.end || approx_eq(time, self.end) {
+            time = self.end;
+            self.finished = true;
+        }
+        self.sample_index += 1;
+
+        // Segments are active from their start (inclusive) to their end (exclusive), except that
+        // the end of the last segment is sampled from the last segment.
+        while self.segment_index + 1 < self.segments.len() {
+            let segment = &self.segments[self.segment_index];
+            let segment_end = self.segment_start + segment.incremental_duration().value();
+            if time < segment_end {
+                break;
+            }
+
+            self.completed_volume += segment.incremental_volume();
+            self.segment_start = segment_end;
+            self.segment_index += 1;
+            self.cache = self.step_cache();
+        }
+
+        let segment = &self.segments[self.segment_index];
+        let time_in_segment = (time - self.segment_start)
+            .min(segment.incremental_duration().value())
+            .max(0.);
+        let (rate, volume) = self.evaluate(segment, time_in_segment);
+
+        Some(SamplePoint {
+            time: Time::from(time),
+            rate: ProductionRate::new(rate),
+            incremental_volume: self.completed_volume + volume,
+        })
+    }
+}
+
+impl<Time: DeclineTimeUnit> Segment<Time> {
+    /// Returns a sampler that walks the segment on a fixed step without allocating.
+    pub fn sampler(
+        &self,
+        step: Time,
+    ) -> Result<SegmentSampler<'_, Time>, DeclineCurveAnalysisError> {
+        SegmentSampler::new(core::slice::from_ref(self), step)
+    }
+}
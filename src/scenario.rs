@@ -0,0 +1,157 @@
+use crate::{
+    AggregateProfile, DeclineCurveAnalysisError, DeclineTimeUnit, Forecast, ProductionRate,
+    ProfileAlignment, TimeGrid, aggregate,
+};
+use alloc::{format, vec::Vec};
+
+/// A reserve category, following the Petroleum Resources Management System (PRMS).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum ReserveCategory {
+    /// Proved developed producing (PDP) reserves from existing completions.
+    ProvedDevelopedProducing,
+    /// Proved undeveloped (PUD) reserves, which need new wells or major expenditure.
+    ProvedUndeveloped,
+    /// Probable reserves, which are less likely to be recovered than proved reserves.
+    Probable,
+    /// Possible reserves, which are less likely to be recovered than probable reserves.
+    Possible,
+}
+
+impl ReserveCategory {
+    /// All categories, from most to least certain.
+    pub const ALL: [Self; 4] = [
+        Self::ProvedDevelopedProducing,
+        Self::ProvedUndeveloped,
+        Self::Probable,
+        Self::Possible,
+    ];
+
+    /// The categories included in proved (1P) reserves.
+    pub const PROVED: [Self; 2] = [Self::ProvedDevelopedProducing, Self::ProvedUndeveloped];
+
+    /// The categories included in proved plus probable (2P) reserves.
+    pub const PROVED_PLUS_PROBABLE: [Self; 3] = [
+        Self::ProvedDevelopedProducing,
+        Self::ProvedUndeveloped,
+        Self::Probable,
+    ];
+
+    /// The categories included in proved plus probable plus possible (3P) reserves.
+    pub const PROVED_PLUS_PROBABLE_PLUS_POSSIBLE: [Self; 4] = Self::ALL;
+}
+
+/// The limits used to truncate the forecast of a reserve category, e.g., a shorter maximum life
+/// for proved undeveloped reserves.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CategoryTruncation<Time: DeclineTimeUnit> {
+    pub economic_limit_rate: ProductionRate<Time>,
+    pub maximum_life: Option<Time>,
+}
+
+/// The forecasts of a well for each reserve category, which is how reserve databases typically
+/// organize forecasts.
+///
+/// Each category is forecast at most once, and the forecast for a category is the incremental
+/// production of that category, so the forecasts are summed to get 1P, 2P, or 3P totals.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scenario<Time: DeclineTimeUnit> {
+    forecasts: Vec<(ReserveCategory, Forecast<Time>)>,
+}
+
+impl<Time: DeclineTimeUnit> Scenario<Time> {
+    /// Creates a scenario, where each category can only be forecast once.
+    pub fn new(
+        forecasts: Vec<(ReserveCategory, Forecast<Time>)>,
+    ) -> Result<Self, DeclineCurveAnalysisError> {
+        for (index, (category, _)) in forecasts.iter().enumerate() {
+            if forecasts[..index]
+                .iter()
+                .any(|(previous, _)| previous == category)
+            {
+                return Err(DeclineCurveAnalysisError::DuplicateForecast {
+                    field: "reserve category",
+                    name: format!("{category:?}"),
+                });
+            }
+        }
+
+        Ok(Self { forecasts })
+    }
+
+    pub fn forecasts(&self) -> &[(ReserveCategory, Forecast<Time>)] {
+        &self.forecasts
+    }
+
+    /// The forecast for a category, or `None` if the category isn't forecast.
+    pub fn forecast(&self, category: ReserveCategory) -> Option<&Forecast<Time>> {
+        self.forecasts
+            .iter()
+            .find(|(forecast_category, _)| *forecast_category == category)
+            .map(|(_, forecast)| forecast)
+    }
+
+    /// The forecasts for the given categories, skipping categories that aren't forecast.
+    fn forecasts_in(&self, categories: &[ReserveCategory]) -> Vec<Forecast<Time>> {
+        self.forecasts
+            .iter()
+            .filter(|(category, _)| categories.contains(category))
+            .map(|(_, forecast)| forecast.clone())
+            .collect()
+    }
+
+    /// The total cumulative volume of the given categories, including any volume produced before
+    /// the start of each forecast. Categories that aren't forecast contribute nothing.
+    pub fn cumulative_volume(&self, categories: &[ReserveCategory]) -> f64 {
+        self.forecasts
+            .iter()
+            .filter(|(category, _)| categories.contains(category))
+            .map(|(_, forecast)| forecast.cumulative_volume())
+            .sum()
+    }
+
+    /// Sums the rates and volumes of the given categories on a common time grid, where every
+    /// forecast starts at time zero of the grid.
+    pub fn aggregate(
+        &self,
+        categories: &[ReserveCategory],
+        grid: &TimeGrid<Time>,
+    ) -> Result<AggregateProfile<Time>, DeclineCurveAnalysisError> {
+        aggregate(
+            &self.forecasts_in(categories),
+            grid,
+            &ProfileAlignment::TimeZero,
+        )
+    }
+
+    /// Returns a copy of the scenario where the forecast of each category is truncated at the
+    /// limits returned by `rules`, or left unchanged if `rules` returns `None`.
+    ///
+    /// See [`Forecast::truncate_at_limits`] for details.
+    pub fn truncate_at_limits(
+        &self,
+        rules: impl Fn(ReserveCategory) -> Option<CategoryTruncation<Time>>,
+    ) -> Result<Self, DeclineCurveAnalysisError> {
+        let forecasts = self
+            .forecasts
+            .iter()
+            .map(|(category, forecast)| {
+                let forecast = match rules(*category) {
+                    Some(rule) => {
+                        forecast
+                            .truncate_at_limits(rule.economic_limit_rate, rule.maximum_life)?
+                            .forecast
+                    }
+                    None => forecast.clone(),
+                };
+                Ok((*category, forecast))
+            })
+            .collect::<Result<_, DeclineCurveAnalysisError>>()?;
+
+        Ok(Self { forecasts })
+    }
+}
@@ -0,0 +1,156 @@
+use crate::{
+    CalendarDate, CalendarTime, DeclineCurveAnalysisError, DeclineTimeUnit, DowntimeSchedule,
+    Forecast, ProductionRate,
+};
+use alloc::vec::Vec;
+
+/// How often a production schedule reports volumes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ReportFrequency {
+    Daily,
+    #[default]
+    Monthly,
+    /// Calendar years, which start on January 1.
+    Annual,
+}
+
+impl ReportFrequency {
+    /// The date that the period with the given index starts, where the first period starts at
+    /// `start_date` and later periods start on calendar boundaries.
+    fn period_start(
+        self,
+        start_date: CalendarDate,
+        index: i32,
+    ) -> Result<CalendarDate, DeclineCurveAnalysisError> {
+        if index == 0 {
+            return Ok(start_date);
+        }
+
+        match self {
+            Self::Daily => start_date.add_days(index as i64),
+            Self::Monthly => start_date.start_of_month().add_months(index),
+            Self::Annual => start_date
+                .start_of_month()
+                .add_months(1 - start_date.month() as i32 + 12 * index),
+        }
+    }
+}
+
+/// A single period of a dated production schedule.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScheduleRow<Time: DeclineTimeUnit> {
+    /// The first day of the period, which is the start date of the forecast for the first period.
+    pub date: CalendarDate,
+    /// The start of the period, relative to the start of the forecast.
+    pub start: Time,
+    /// The end of the period, relative to the start of the forecast. The last period is shortened
+    /// so that it ends at the end of the schedule.
+    pub end: Time,
+    /// The number of days that the well produces in the period, which is the length of the period
+    /// weighted by the uptime factor.
+    pub producing_days: f64,
+    pub volume: f64,
+    /// The average rate over the whole period, including downtime.
+    pub rate: ProductionRate<Time>,
+}
+
+/// Builds a dated production schedule from a forecast, e.g., to pass monthly volumes to an
+/// economics model.
+///
+/// The first period starts at the start date and ends at the next calendar boundary, so partial
+/// first and last periods only include the days that the forecast covers.
+#[derive(Debug, Clone)]
+pub struct ScheduleBuilder<'a, Time: DeclineTimeUnit> {
+    forecast: &'a Forecast<Time>,
+    start_date: CalendarDate,
+    frequency: ReportFrequency,
+    downtime: Option<DowntimeSchedule<Time>>,
+    end_date: Option<CalendarDate>,
+}
+
+impl<'a, Time: DeclineTimeUnit> ScheduleBuilder<'a, Time> {
+    /// Creates a monthly schedule of the forecast, starting at `start_date` with no downtime.
+    pub fn new(forecast: &'a Forecast<Time>, start_date: CalendarDate) -> Self {
+        Self {
+            forecast,
+            start_date,
+            frequency: ReportFrequency::default(),
+            downtime: None,
+            end_date: None,
+        }
+    }
+
+    pub fn frequency(mut self, frequency: ReportFrequency) -> Self {
+        self.frequency = frequency;
+        self
+    }
+
+    /// Scales the volumes by the uptime factors of the schedule, relative to the start date.
+    pub fn downtime(mut self, downtime: DowntimeSchedule<Time>) -> Self {
+        self.downtime = Some(downtime);
+        self
+    }
+
+    /// Ends the schedule at the start of `end_date` if the forecast hasn't already ended.
+    pub fn end_date(mut self, end_date: CalendarDate) -> Self {
+        self.end_date = Some(end_date);
+        self
+    }
+
+    pub fn build(&self) -> Result<Vec<ScheduleRow<Time>>, DeclineCurveAnalysisError> {
+        let mut duration = self.forecast.incremental_duration().value();
+        if let Some(end_date) = self.end_date {
+            if end_date <= self.start_date {
+                return Err(DeclineCurveAnalysisError::NotStrictlyIncreasing {
+                    field: "schedule start and end dates",
+                });
+            }
+            duration = duration.min(self.time_at_date(end_date).value());
+        }
+
+        let mut rows = Vec::new();
+        let mut date = self.start_date;
+        let mut start = 0.;
+        let mut index = 0;
+
+        while start < duration {
+            index += 1;
+            let next_date = self.frequency.period_start(self.start_date, index)?;
+            let end = self.time_at_date(next_date).value().min(duration);
+            let (start_time, end_time) = (Time::from(start), Time::from(end));
+
+            let (volume, producing_time) = match &self.downtime {
+                Some(downtime) => (
+                    self.forecast
+                        .incremental_volume_between_with_downtime(start_time, end_time, downtime),
+                    downtime.scale_volume_between(start_time, end_time, |start, end| {
+                        end.value() - start.value()
+                    }),
+                ),
+                None => (
+                    self.forecast
+                        .incremental_volume_between(start_time, end_time),
+                    end - start,
+                ),
+            };
+
+            rows.push(ScheduleRow {
+                date,
+                start: start_time,
+                end: end_time,
+                producing_days: Time::from(producing_time).to_unit::<CalendarTime>().days,
+                volume,
+                rate: ProductionRate::new(volume / (end - start)),
+            });
+
+            date = next_date;
+            start = end;
+        }
+
+        Ok(rows)
+    }
+
+    fn time_at_date(&self, date: CalendarDate) -> Time {
+        CalendarTime::between(self.start_date, date).to_unit()
+    }
+}
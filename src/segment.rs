@@ -0,0 +1,294 @@
+use crate::{
+    DeclineCurveAnalysisError, DeclineTimeUnit, DelayParameters, ExponentPolicy,
+    ExponentialParameters, FlatParameters, HarmonicParameters, HyperbolicParameters,
+    LinearParameters, LogisticGrowthParameters, NominalDeclineRate, PeriodVolume,
+    PowerLawExponentialParameters, ProductionRate, RateTruncation,
+    periods::{fixed_period_boundaries, period_volumes, validate_period_length},
+    within_tolerance,
+};
+use core::mem::discriminant;
+
+/// Any of the supported segment types. This is useful when the segment type is only known at
+/// runtime (e.g., when selecting the best fit).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", rename_all = "snake_case"))]
+pub enum Segment<Time: DeclineTimeUnit> {
+    Delay(DelayParameters<Time>),
+    Exponential(ExponentialParameters<Time>),
+    Flat(FlatParameters<Time>),
+    Harmonic(HarmonicParameters<Time>),
+    Hyperbolic(HyperbolicParameters<Time>),
+    Linear(LinearParameters<Time>),
+    LogisticGrowth(LogisticGrowthParameters<Time>),
+    PowerLawExponential(PowerLawExponentialParameters<Time>),
+}
+
+/// Forwards a method call to the parameters of each segment variant.
+macro_rules! delegate {
+    ($self:ident, $parameters:ident => $call:expr) => {
+        match $self {
+            Segment::Delay($parameters) => $call,
+            Segment::Exponential($parameters) => $call,
+            Segment::Flat($parameters) => $call,
+            Segment::Harmonic($parameters) => $call,
+            Segment::Hyperbolic($parameters) => $call,
+            Segment::Linear($parameters) => $call,
+            Segment::LogisticGrowth($parameters) => $call,
+            Segment::PowerLawExponential($parameters) => $call,
+        }
+    };
+}
+
+impl<Time: DeclineTimeUnit> Segment<Time> {
+    pub fn incremental_duration(&self) -> Time {
+        delegate!(self, parameters => parameters.incremental_duration())
+    }
+
+    pub fn incremental_volume_at_time(&self, time: Time) -> f64 {
+        delegate!(self, parameters => parameters.incremental_volume_at_time(time))
+    }
+
+    pub fn incremental_volume_between(&self, start: Time, end: Time) -> f64 {
+        delegate!(self, parameters => parameters.incremental_volume_between(start, end))
+    }
+
+    pub fn incremental_volume(&self) -> f64 {
+        delegate!(self, parameters => parameters.incremental_volume())
+    }
+
+    pub fn rates_at_times(&self, times: &[Time], out: &mut [f64]) {
+        delegate!(self, parameters => parameters.rates_at_times(times, out))
+    }
+
+    pub fn incremental_volumes_at_times(&self, times: &[Time], out: &mut [f64]) {
+        delegate!(self, parameters => parameters.incremental_volumes_at_times(times, out))
+    }
+
+    /// Like `rates_at_times`, but with `f32` times and rates, where the times are in the segment's
+    /// time unit.
+    ///
+    /// Exponential, harmonic, hyperbolic, and linear segments are evaluated in `f32`, so rates and
+    /// volumes have a relative error of about 1e-7, although hyperbolic volumes can be less precise
+    /// (see [`HyperbolicParameters::incremental_volumes_at_times_f32`]). Other segments are
+    /// evaluated in `f64` and then converted.
+    pub fn rates_at_times_f32(&self, times: &[f32], out: &mut [f32]) {
+        delegate!(self, parameters => parameters.rates_at_times_f32(times, out))
+    }
+
+    /// Like `incremental_volumes_at_times`, but with `f32` times and volumes. See
+    /// [`Self::rates_at_times_f32`] for the precision.
+    pub fn incremental_volumes_at_times_f32(&self, times: &[f32], out: &mut [f32]) {
+        delegate!(self, parameters => parameters.incremental_volumes_at_times_f32(times, out))
+    }
+
+    pub fn decline_rate_at_time(&self, time: Time) -> NominalDeclineRate<Time> {
+        delegate!(self, parameters => parameters.decline_rate_at_time(time))
+    }
+
+    /// The derivative of the rate with respect to time, which is negative while the rate declines.
+    pub fn rate_derivative_at_time(&self, time: Time) -> f64 {
+        delegate!(self, parameters => parameters.rate_derivative_at_time(time))
+    }
+
+    pub fn time_at_incremental_volume(
+        &self,
+        incremental_volume: f64,
+    ) -> Result<Time, DeclineCurveAnalysisError> {
+        delegate!(self, parameters => parameters.time_at_incremental_volume(incremental_volume))
+    }
+
+    /// Returns the rate at the time the cumulative volume reaches the given volume.
+    pub fn rate_at_incremental_volume(
+        &self,
+        incremental_volume: f64,
+    ) -> Result<ProductionRate<Time>, DeclineCurveAnalysisError> {
+        delegate!(self, parameters => parameters.rate_at_incremental_volume(incremental_volume))
+    }
+
+    pub fn time_at_rate(
+        &self,
+        rate: ProductionRate<Time>,
+    ) -> Result<Time, DeclineCurveAnalysisError> {
+        delegate!(self, parameters => parameters.time_at_rate(rate))
+    }
+
+    pub fn final_rate(&self) -> ProductionRate<Time> {
+        delegate!(self, parameters => parameters.final_rate())
+    }
+
+    pub fn rate_at_time(&self, time: Time) -> ProductionRate<Time> {
+        delegate!(self, parameters => parameters.rate_at_time(time))
+    }
+
+    pub(crate) fn scale_rates(&self, factor: f64) -> Self {
+        delegate!(self, parameters => parameters.scale_rates(factor).into())
+    }
+
+    /// Returns the volume produced in each period of the given length over the whole segment.
+    pub fn volumes_by_period(
+        &self,
+        period_length: Time,
+    ) -> Result<impl Iterator<Item = PeriodVolume<Time>> + '_, DeclineCurveAnalysisError> {
+        validate_period_length(period_length)?;
+
+        Ok(period_volumes(
+            core::slice::from_ref(self),
+            fixed_period_boundaries(period_length.value()),
+        ))
+    }
+
+    pub fn truncate_at_rate(
+        &self,
+        economic_limit_rate: ProductionRate<Time>,
+    ) -> Result<RateTruncation<Self, Time>, DeclineCurveAnalysisError> {
+        delegate!(self, parameters => Ok(parameters
+            .truncate_at_rate(economic_limit_rate)?
+            .map_parameters(Segment::from)))
+    }
+
+    /// Validates that the exponent of a hyperbolic segment is allowed by the policy. Other segment
+    /// types are always allowed.
+    pub fn validate_exponent(
+        &self,
+        policy: ExponentPolicy,
+    ) -> Result<(), DeclineCurveAnalysisError> {
+        match self {
+            Segment::Hyperbolic(parameters) => parameters.validate_exponent(policy),
+            _ => Ok(()),
+        }
+    }
+
+    /// Splits the segment at `time` into two segments that together reproduce the original.
+    ///
+    /// Logistic growth and power law exponential segments can't be split, because their rates
+    /// depend on the time since the start of production and can't be restarted part way through,
+    /// so they return [`DeclineCurveAnalysisError::UnsplittableSegment`].
+    pub fn split_at(&self, time: Time) -> Result<(Self, Self), DeclineCurveAnalysisError> {
+        let (first, second) = match self {
+            Segment::Delay(parameters) => split_into_segments(parameters.split_at(time)),
+            Segment::Exponential(parameters) => split_into_segments(parameters.split_at(time)),
+            Segment::Flat(parameters) => split_into_segments(parameters.split_at(time)),
+            Segment::Harmonic(parameters) => split_into_segments(parameters.split_at(time)),
+            Segment::Hyperbolic(parameters) => split_into_segments(parameters.split_at(time)),
+            Segment::Linear(parameters) => split_into_segments(parameters.split_at(time)),
+            Segment::LogisticGrowth(_) => {
+                return Err(DeclineCurveAnalysisError::UnsplittableSegment {
+                    segment: "logistic growth",
+                });
+            }
+            Segment::PowerLawExponential(_) => {
+                return Err(DeclineCurveAnalysisError::UnsplittableSegment {
+                    segment: "power law exponential",
+                });
+            }
+        };
+
+        Ok((first, second))
+    }
+
+    /// Returns true if the other segment has the same type and each of its parameters is within
+    /// `tolerance` of this segment's, otherwise false.
+    ///
+    /// The tolerance is relative to the larger of the two parameters, or absolute when both are
+    /// below one, so rates, decline rates, and durations in any unit can share a tolerance.
+    pub fn approx_eq(&self, other: &Self, tolerance: f64) -> bool {
+        self.parameters_match(other, |a, b| within_tolerance(a, b, tolerance))
+    }
+
+    /// Returns true if the other segment has the same type and `is_match` is true for each pair of
+    /// parameters, otherwise false.
+    pub(crate) fn parameters_match(
+        &self,
+        other: &Self,
+        is_match: impl Fn(f64, f64) -> bool,
+    ) -> bool {
+        discriminant(self) == discriminant(other)
+            && self
+                .parameter_values()
+                .into_iter()
+                .zip(other.parameter_values())
+                .all(|(a, b)| is_match(a, b))
+    }
+
+    /// The parameters that define the segment, padded with zeros. Values that are derived from the
+    /// parameters (e.g., the hyperbolic coefficients) are left out.
+    pub(crate) fn parameter_values(&self) -> [f64; 5] {
+        let duration = self.incremental_duration().value();
+        match self {
+            Segment::Delay(_) => [duration, 0., 0., 0., 0.],
+            Segment::Exponential(parameters) => [
+                parameters.initial_rate().value(),
+                parameters.decline_rate().value(),
+                duration,
+                0.,
+                0.,
+            ],
+            Segment::Flat(parameters) => [parameters.rate().value(), duration, 0., 0., 0.],
+            Segment::Harmonic(parameters) => [
+                parameters.initial_rate().value(),
+                parameters.initial_decline_rate().value(),
+                duration,
+                0.,
+                0.,
+            ],
+            Segment::Hyperbolic(parameters) => [
+                parameters.initial_rate().value(),
+                parameters.initial_decline_rate().value(),
+                duration,
+                parameters.exponent(),
+                0.,
+            ],
+            Segment::Linear(parameters) => [
+                parameters.initial_rate().value(),
+                parameters.decline_rate().value(),
+                duration,
+                0.,
+                0.,
+            ],
+            Segment::LogisticGrowth(parameters) => [
+                parameters.carrying_capacity(),
+                parameters.a(),
+                duration,
+                parameters.exponent(),
+                0.,
+            ],
+            Segment::PowerLawExponential(parameters) => [
+                parameters.initial_rate().value(),
+                parameters.initial_decline_rate(),
+                parameters.infinite_decline_rate().value(),
+                duration,
+                parameters.exponent(),
+            ],
+        }
+    }
+}
+
+fn split_into_segments<Time: DeclineTimeUnit, Parameters: Into<Segment<Time>>>(
+    (first, second): (Parameters, Parameters),
+) -> (Segment<Time>, Segment<Time>) {
+    (first.into(), second.into())
+}
+
+macro_rules! impl_from_parameters {
+    ($($variant:ident($parameters:ident)),* $(,)?) => {
+        $(
+            impl<Time: DeclineTimeUnit> From<$parameters<Time>> for Segment<Time> {
+                fn from(parameters: $parameters<Time>) -> Self {
+                    Self::$variant(parameters)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_parameters!(
+    Delay(DelayParameters),
+    Exponential(ExponentialParameters),
+    Flat(FlatParameters),
+    Harmonic(HarmonicParameters),
+    Hyperbolic(HyperbolicParameters),
+    Linear(LinearParameters),
+    LogisticGrowth(LogisticGrowthParameters),
+    PowerLawExponential(PowerLawExponentialParameters),
+);
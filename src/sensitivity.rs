@@ -0,0 +1,296 @@
+use crate::{
+    DeclineCurveAnalysisError, DeclineTimeUnit, HyperbolicParameters, NominalDeclineRate,
+    ProductionRate, validate_finite, validate_non_zero_positive_rate,
+};
+use alloc::vec::Vec;
+
+/// A parameter of a hyperbolic segment that's varied in a sensitivity analysis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SensitivityParameter {
+    InitialRate,
+    /// The initial nominal decline rate, in the time unit of the segment.
+    InitialDeclineRate,
+    Exponent,
+    /// The final rate, which acts as the economic limit of the segment.
+    FinalRate,
+}
+
+impl SensitivityParameter {
+    /// All parameters that can be varied.
+    pub const ALL: [Self; 4] = [
+        Self::InitialRate,
+        Self::InitialDeclineRate,
+        Self::Exponent,
+        Self::FinalRate,
+    ];
+
+    /// The value of the parameter for a segment.
+    pub fn value<Time: DeclineTimeUnit>(self, segment: &HyperbolicParameters<Time>) -> f64 {
+        match self {
+            Self::InitialRate => segment.initial_rate().value(),
+            Self::InitialDeclineRate => segment.initial_decline_rate().value(),
+            Self::Exponent => segment.exponent(),
+            Self::FinalRate => segment.final_rate().value(),
+        }
+    }
+}
+
+/// The response that's measured in a sensitivity analysis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SensitivityMetric {
+    /// The volume produced until the final rate.
+    EstimatedUltimateRecovery,
+    /// The time until the final rate, in the time unit of the segment.
+    Duration,
+}
+
+impl SensitivityMetric {
+    fn evaluate<Time: DeclineTimeUnit>(self, segment: &HyperbolicParameters<Time>) -> f64 {
+        match self {
+            Self::EstimatedUltimateRecovery => segment.incremental_volume(),
+            Self::Duration => segment.incremental_duration().value(),
+        }
+    }
+}
+
+/// The response of a metric at one value of the varied parameter.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SensitivityPoint {
+    pub value: f64,
+    pub response: f64,
+}
+
+/// The response of a metric as one parameter varies, with every other parameter held at the base
+/// case.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sensitivity {
+    pub parameter: SensitivityParameter,
+    pub metric: SensitivityMetric,
+    /// The value of the parameter in the base case.
+    pub base_value: f64,
+    /// The response in the base case.
+    pub base_response: f64,
+    /// The response at each value of the range, in the order of the range.
+    pub points: Vec<SensitivityPoint>,
+}
+
+impl Sensitivity {
+    /// The lowest and highest responses over the range, including the base case, which are the
+    /// ends of the bar in a tornado chart.
+    pub fn response_range(&self) -> (f64, f64) {
+        self.points.iter().fold(
+            (self.base_response, self.base_response),
+            |(low, high), point| (low.min(point.response), high.max(point.response)),
+        )
+    }
+
+    /// The difference between the highest and lowest responses, which is the length of the bar in
+    /// a tornado chart.
+    pub fn swing(&self) -> f64 {
+        let (low, high) = self.response_range();
+        high - low
+    }
+}
+
+/// Calculates the response of a metric as one parameter of a hyperbolic segment varies over the
+/// values in `range`, with the other parameters held at their values in `segment`.
+///
+/// The segment is rebuilt from its initial rate, initial decline rate, exponent, and final rate,
+/// so the duration changes to keep the final rate fixed unless the final rate is the parameter
+/// that's varied. This fails if any value in the range doesn't produce a valid segment (e.g., a
+/// final rate above the initial rate).
+pub fn sensitivity<Time: DeclineTimeUnit>(
+    segment: &HyperbolicParameters<Time>,
+    parameter: SensitivityParameter,
+    range: &[f64],
+    metric: SensitivityMetric,
+) -> Result<Sensitivity, DeclineCurveAnalysisError> {
+    let points = range
+        .iter()
+        .map(|&value| {
+            validate_finite(value, "sensitivity value")?;
+
+            let varied = |varied_parameter: SensitivityParameter| {
+                if varied_parameter == parameter {
+                    value
+                } else {
+                    varied_parameter.value(segment)
+                }
+            };
+            let varied_segment = HyperbolicParameters::<Time>::from_final_rate(
+                ProductionRate::new(varied(SensitivityParameter::InitialRate)),
+                NominalDeclineRate::new(varied(SensitivityParameter::InitialDeclineRate)),
+                ProductionRate::new(varied(SensitivityParameter::FinalRate)),
+                varied(SensitivityParameter::Exponent),
+            )?;
+
+            Ok(SensitivityPoint {
+                value,
+                response: metric.evaluate(&varied_segment),
+            })
+        })
+        .collect::<Result<_, DeclineCurveAnalysisError>>()?;
+
+    Ok(Sensitivity {
+        parameter,
+        metric,
+        base_value: parameter.value(segment),
+        base_response: metric.evaluate(segment),
+        points,
+    })
+}
+
+/// The low and high values of a parameter for a tornado chart.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ParameterRange {
+    pub parameter: SensitivityParameter,
+    pub low: f64,
+    pub high: f64,
+}
+
+/// One bar of a tornado chart, which is the response at the low and high values of a parameter.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TornadoBar {
+    pub range: ParameterRange,
+    /// The response at the low value of the parameter.
+    pub low_response: f64,
+    /// The response at the high value of the parameter.
+    pub high_response: f64,
+}
+
+impl TornadoBar {
+    /// The difference between the highest and lowest responses of the bar, which is how bars are
+    /// sorted.
+    pub fn swing(&self) -> f64 {
+        (self.high_response - self.low_response).abs()
+    }
+}
+
+/// The data for a tornado chart, where each bar is the response to varying one parameter between
+/// its low and high values.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TornadoChart {
+    pub metric: SensitivityMetric,
+    /// The response in the base case, which is the center line of the chart.
+    pub base_response: f64,
+    /// The bars, sorted from the largest swing to the smallest, which is the order they're drawn
+    /// from the top of the chart.
+    pub bars: Vec<TornadoBar>,
+}
+
+/// Calculates the data for a tornado chart by varying each parameter between its low and high
+/// values, one at a time, with the other parameters held at their values in `segment`.
+///
+/// See [`sensitivity`] for how the segment is rebuilt.
+pub fn tornado<Time: DeclineTimeUnit>(
+    segment: &HyperbolicParameters<Time>,
+    ranges: &[ParameterRange],
+    metric: SensitivityMetric,
+) -> Result<TornadoChart, DeclineCurveAnalysisError> {
+    let mut bars = ranges
+        .iter()
+        .map(|range| {
+            let sensitivity =
+                sensitivity(segment, range.parameter, &[range.low, range.high], metric)?;
+            Ok(TornadoBar {
+                range: *range,
+                low_response: sensitivity.points[0].response,
+                high_response: sensitivity.points[1].response,
+            })
+        })
+        .collect::<Result<Vec<_>, DeclineCurveAnalysisError>>()?;
+    bars.sort_by(|a, b| b.swing().total_cmp(&a.swing()));
+
+    Ok(TornadoChart {
+        metric,
+        base_response: metric.evaluate(segment),
+        bars,
+    })
+}
+
+/// A point on a spider chart line.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpiderPoint {
+    /// The relative change of the parameter from the base case, e.g., -0.2 for 20% lower.
+    pub relative_change: f64,
+    /// The relative change of the response from the base case.
+    pub relative_response: f64,
+}
+
+/// A line of a spider chart, which is the relative response to relative changes of one
+/// parameter.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpiderLine {
+    pub parameter: SensitivityParameter,
+    /// The points in the order of the relative changes.
+    pub points: Vec<SpiderPoint>,
+}
+
+/// The data for a spider chart, where each line is the relative response to the same relative
+/// changes of one parameter.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpiderChart {
+    pub metric: SensitivityMetric,
+    pub base_response: f64,
+    /// The lines, sorted from the largest range of relative responses to the smallest.
+    pub lines: Vec<SpiderLine>,
+}
+
+/// Calculates the data for a spider chart by scaling each parameter by `1 + relative_change` for
+/// each of the relative changes, one parameter at a time.
+///
+/// See [`sensitivity`] for how the segment is rebuilt. This fails if the base response is zero,
+/// since the relative responses are undefined.
+pub fn spider<Time: DeclineTimeUnit>(
+    segment: &HyperbolicParameters<Time>,
+    parameters: &[SensitivityParameter],
+    relative_changes: &[f64],
+    metric: SensitivityMetric,
+) -> Result<SpiderChart, DeclineCurveAnalysisError> {
+    let base_response = metric.evaluate(segment);
+    validate_non_zero_positive_rate(base_response, "base response")?;
+
+    let mut lines = parameters
+        .iter()
+        .map(|&parameter| {
+            let base_value = parameter.value(segment);
+            let range: Vec<f64> = relative_changes
+                .iter()
+                .map(|relative_change| base_value * (1. + relative_change))
+                .collect();
+            let sensitivity = sensitivity(segment, parameter, &range, metric)?;
+
+            Ok(SpiderLine {
+                parameter,
+                points: relative_changes
+                    .iter()
+                    .zip(&sensitivity.points)
+                    .map(|(relative_change, point)| SpiderPoint {
+                        relative_change: *relative_change,
+                        relative_response: point.response / base_response - 1.,
+                    })
+                    .collect(),
+            })
+        })
+        .collect::<Result<Vec<_>, DeclineCurveAnalysisError>>()?;
+
+    let spread = |line: &SpiderLine| {
+        let (low, high) = line
+            .points
+            .iter()
+            .fold((0_f64, 0_f64), |(low, high), point| {
+                (
+                    low.min(point.relative_response),
+                    high.max(point.relative_response),
+                )
+            });
+        high - low
+    };
+    lines.sort_by(|a, b| spread(b).total_cmp(&spread(a)));
+
+    Ok(SpiderChart {
+        metric,
+        base_response,
+        lines,
+    })
+}
@@ -0,0 +1,259 @@
+//! Serde support, which is enabled by the `serde` feature.
+//!
+//! Parameters are serialized using their public accessors, and deserialized through their
+//! `from_incremental_duration` constructors so that invalid parameters are rejected in the same way
+//! as when they're constructed directly.
+
+use alloc::vec::Vec;
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error};
+
+use crate::{
+    CalendarDate, DeclineTimeUnit, DelayParameters, ExponentialParameters, FlatParameters,
+    Forecast, HarmonicParameters, HyperbolicParameters, LabeledForecast, LinearParameters,
+    LogisticGrowthParameters, NominalDeclineRate, PowerLawExponentialParameters, ProductionRate,
+    RatioSegment, RatioTrend, SecantEffectiveDeclineRate, Segment, TangentEffectiveDeclineRate,
+    VolumeUnit,
+};
+
+/// Implements serde for a rate, which is represented as a bare number in its time unit.
+macro_rules! impl_serde_rate {
+    ($($rate:ident),* $(,)?) => {
+        $(
+            impl<Time: DeclineTimeUnit> Serialize for $rate<Time> {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    self.value().serialize(serializer)
+                }
+            }
+
+            impl<'de, Time: DeclineTimeUnit> Deserialize<'de> for $rate<Time> {
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    f64::deserialize(deserializer).map(Self::new)
+                }
+            }
+        )*
+    };
+}
+
+impl_serde_rate!(
+    NominalDeclineRate,
+    SecantEffectiveDeclineRate,
+    TangentEffectiveDeclineRate,
+);
+
+impl<Time: DeclineTimeUnit, Volume: VolumeUnit> Serialize for ProductionRate<Time, Volume> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.value().serialize(serializer)
+    }
+}
+
+impl<'de, Time: DeclineTimeUnit, Volume: VolumeUnit> Deserialize<'de>
+    for ProductionRate<Time, Volume>
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        f64::deserialize(deserializer).map(Self::new)
+    }
+}
+
+/// Implements serde for parameters, where the fields are listed in the same order as the
+/// arguments of `from_incremental_duration`.
+macro_rules! impl_serde_parameters {
+    ($parameters:ident { $($field:ident: $field_type:ty),* $(,)? }) => {
+        impl<Time: DeclineTimeUnit + Serialize> Serialize for $parameters<Time> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                #[derive(Serialize)]
+                #[serde(rename = "Parameters")]
+                struct Fields<Time: DeclineTimeUnit + Serialize> {
+                    $($field: $field_type,)*
+                }
+
+                Fields::<Time> {
+                    $($field: self.$field(),)*
+                }
+                .serialize(serializer)
+            }
+        }
+
+        impl<'de, Time: DeclineTimeUnit + Deserialize<'de>> Deserialize<'de> for $parameters<Time> {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                #[derive(Deserialize)]
+                #[serde(rename = "Parameters", deny_unknown_fields)]
+                #[serde(bound(deserialize = "Time: Deserialize<'de>"))]
+                struct Fields<Time: DeclineTimeUnit> {
+                    $($field: $field_type,)*
+                }
+
+                let fields = Fields::<Time>::deserialize(deserializer)?;
+                Self::from_incremental_duration($(fields.$field),*).map_err(D::Error::custom)
+            }
+        }
+    };
+}
+
+impl_serde_parameters!(DelayParameters {
+    incremental_duration: Time,
+});
+
+impl_serde_parameters!(ExponentialParameters {
+    initial_rate: ProductionRate<Time>,
+    decline_rate: NominalDeclineRate<Time>,
+    incremental_duration: Time,
+});
+
+impl_serde_parameters!(FlatParameters {
+    rate: ProductionRate<Time>,
+    incremental_duration: Time,
+});
+
+impl_serde_parameters!(HarmonicParameters {
+    initial_rate: ProductionRate<Time>,
+    initial_decline_rate: NominalDeclineRate<Time>,
+    incremental_duration: Time,
+});
+
+impl_serde_parameters!(HyperbolicParameters {
+    initial_rate: ProductionRate<Time>,
+    initial_decline_rate: NominalDeclineRate<Time>,
+    incremental_duration: Time,
+    exponent: f64,
+});
+
+impl_serde_parameters!(LinearParameters {
+    initial_rate: ProductionRate<Time>,
+    decline_rate: NominalDeclineRate<Time>,
+    incremental_duration: Time,
+});
+
+impl_serde_parameters!(LogisticGrowthParameters {
+    carrying_capacity: f64,
+    a: f64,
+    incremental_duration: Time,
+    exponent: f64,
+});
+
+impl_serde_parameters!(PowerLawExponentialParameters {
+    initial_rate: ProductionRate<Time>,
+    initial_decline_rate: f64,
+    infinite_decline_rate: NominalDeclineRate<Time>,
+    incremental_duration: Time,
+    exponent: f64,
+});
+
+impl_serde_parameters!(RatioSegment {
+    initial_ratio: f64,
+    trend: RatioTrend,
+    incremental_duration: Time,
+});
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "CalendarDate", deny_unknown_fields)]
+struct CalendarDateFields {
+    year: i32,
+    month: u32,
+    day: u32,
+}
+
+impl Serialize for CalendarDate {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        CalendarDateFields {
+            year: self.year(),
+            month: self.month(),
+            day: self.day(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for CalendarDate {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let fields = CalendarDateFields::deserialize(deserializer)?;
+        CalendarDate::new(fields.year, fields.month, fields.day).map_err(D::Error::custom)
+    }
+}
+
+fn is_zero(value: &f64) -> bool {
+    *value == 0.
+}
+
+/// Forecasts are deserialized through [`Forecast::with_initial_cumulative_volume`], so that the
+/// initial cumulative volume is validated in the same way as when it's set directly.
+impl<Time: DeclineTimeUnit + Serialize> Serialize for Forecast<Time> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[derive(Serialize)]
+        #[serde(rename = "Forecast")]
+        struct Fields<'a, Time: DeclineTimeUnit + Serialize> {
+            segments: &'a [Segment<Time>],
+            #[serde(skip_serializing_if = "is_zero")]
+            initial_cumulative_volume: f64,
+        }
+
+        Fields {
+            segments: self.segments(),
+            initial_cumulative_volume: self.initial_cumulative_volume(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, Time: DeclineTimeUnit + Deserialize<'de>> Deserialize<'de> for Forecast<Time> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(rename = "Forecast")]
+        #[serde(bound(deserialize = "Time: Deserialize<'de>"))]
+        struct Fields<Time: DeclineTimeUnit> {
+            segments: Vec<Segment<Time>>,
+            #[serde(default)]
+            initial_cumulative_volume: f64,
+        }
+
+        let fields = Fields::<Time>::deserialize(deserializer)?;
+        Forecast::new(fields.segments)
+            .with_initial_cumulative_volume(fields.initial_cumulative_volume)
+            .map_err(D::Error::custom)
+    }
+}
+
+/// Labeled forecasts are serialized with the segment metadata next to the forecast, and
+/// deserialized through [`LabeledForecast::new`], so that the metadata must match the segments.
+impl<Time, Meta> Serialize for LabeledForecast<Time, Meta>
+where
+    Time: DeclineTimeUnit + Serialize,
+    Meta: Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[derive(Serialize)]
+        #[serde(rename = "LabeledForecast")]
+        struct Fields<'a, Time: DeclineTimeUnit + Serialize, Meta: Serialize> {
+            forecast: &'a Forecast<Time>,
+            metadata: &'a Meta,
+            segment_metadata: &'a [Meta],
+        }
+
+        Fields {
+            forecast: self.forecast(),
+            metadata: self.metadata(),
+            segment_metadata: self.segment_metadata(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, Time, Meta> Deserialize<'de> for LabeledForecast<Time, Meta>
+where
+    Time: DeclineTimeUnit + Deserialize<'de>,
+    Meta: Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(rename = "LabeledForecast", deny_unknown_fields)]
+        #[serde(bound(deserialize = "Time: Deserialize<'de>, Meta: Deserialize<'de>"))]
+        struct Fields<Time: DeclineTimeUnit, Meta> {
+            forecast: Forecast<Time>,
+            metadata: Meta,
+            segment_metadata: Vec<Meta>,
+        }
+
+        let fields = Fields::<Time, Meta>::deserialize(deserializer)?;
+        Self::new(fields.forecast, fields.metadata, fields.segment_metadata)
+            .map_err(D::Error::custom)
+    }
+}
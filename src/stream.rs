@@ -0,0 +1,99 @@
+use crate::{DeclineCurveAnalysisError, DeclineTimeUnit, Forecast, PeriodVolume, ProductionRate};
+
+/// Whether a stream flows out of a well or into it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum StreamKind {
+    #[default]
+    Production,
+    /// Water or gas injection, which is negative in net volumes.
+    Injection,
+}
+
+impl StreamKind {
+    /// The sign of the stream in net volumes, which is 1 for production and -1 for injection.
+    pub fn sign(self) -> f64 {
+        match self {
+            Self::Production => 1.,
+            Self::Injection => -1.,
+        }
+    }
+}
+
+/// A forecast of a production or injection stream.
+///
+/// Injection profiles often follow decline-like trends, so they're forecast by the magnitude of
+/// their rates with the same segments as production, which require positive rates. The signed
+/// methods apply the sign of the stream, so that injection rates and volumes are negative.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StreamForecast<Time: DeclineTimeUnit> {
+    kind: StreamKind,
+    forecast: Forecast<Time>,
+}
+
+impl<Time: DeclineTimeUnit> StreamForecast<Time> {
+    /// Creates a stream from a forecast of the magnitude of its rates.
+    pub fn new(kind: StreamKind, forecast: Forecast<Time>) -> Self {
+        Self { kind, forecast }
+    }
+
+    /// A production stream.
+    pub fn production(forecast: Forecast<Time>) -> Self {
+        Self::new(StreamKind::Production, forecast)
+    }
+
+    /// An injection stream.
+    pub fn injection(forecast: Forecast<Time>) -> Self {
+        Self::new(StreamKind::Injection, forecast)
+    }
+
+    pub fn kind(&self) -> StreamKind {
+        self.kind
+    }
+
+    /// The forecast of the magnitude of the rates.
+    pub fn forecast(&self) -> &Forecast<Time> {
+        &self.forecast
+    }
+
+    pub fn into_forecast(self) -> Forecast<Time> {
+        self.forecast
+    }
+
+    /// The rate at the given time, which is negative for injection.
+    pub fn signed_rate_at_time(&self, time: Time) -> ProductionRate<Time> {
+        ProductionRate::new(self.kind.sign() * self.forecast.rate_at_time(time).value())
+    }
+
+    /// The volume between two times, which is negative for injection.
+    pub fn signed_incremental_volume_between(&self, start: Time, end: Time) -> f64 {
+        self.kind.sign() * self.forecast.incremental_volume_between(start, end)
+    }
+
+    /// The volume over the whole forecast, which is negative for injection.
+    pub fn signed_incremental_volume(&self) -> f64 {
+        self.kind.sign() * self.forecast.incremental_volume()
+    }
+
+    /// The volume in each period of the given length (see [`Forecast::volumes_by_period`]), which
+    /// is negative for injection.
+    pub fn signed_volumes_by_period(
+        &self,
+        period_length: Time,
+    ) -> Result<impl Iterator<Item = PeriodVolume<Time>> + '_, DeclineCurveAnalysisError> {
+        let sign = self.kind.sign();
+
+        Ok(self
+            .forecast
+            .volumes_by_period(period_length)?
+            .map(move |period| PeriodVolume {
+                volume: sign * period.volume,
+                ..period
+            }))
+    }
+}
@@ -0,0 +1,105 @@
+use crate::{
+    DeclineCurveAnalysisError, DeclineTimeUnit, Forecast, PeriodVolume, ProductionRate,
+    TimeShifted,
+    periods::{fixed_period_boundaries, period_volumes_between, validate_period_length},
+};
+use alloc::vec::Vec;
+
+/// Forecasts that produce at the same time on a shared time axis, whose rates and volumes are
+/// added together, e.g., base production plus the wedges from a workover and a refrac.
+///
+/// Unlike a [`RestimulatedForecast`](crate::RestimulatedForecast), no component stops another, so
+/// the volume of each component is the incremental volume of that project.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SuperposedForecast<Time: DeclineTimeUnit> {
+    components: Vec<TimeShifted<Forecast<Time>, Time>>,
+}
+
+impl<Time: DeclineTimeUnit> Default for SuperposedForecast<Time> {
+    fn default() -> Self {
+        Self::new(Vec::new())
+    }
+}
+
+impl<Time: DeclineTimeUnit> SuperposedForecast<Time> {
+    pub fn new(components: Vec<TimeShifted<Forecast<Time>, Time>>) -> Self {
+        Self { components }
+    }
+
+    /// Adds a forecast that starts at `start_offset` on the shared time axis.
+    pub fn add(
+        &mut self,
+        forecast: Forecast<Time>,
+        start_offset: Time,
+    ) -> Result<(), DeclineCurveAnalysisError> {
+        self.components
+            .push(forecast.with_start_offset(start_offset)?);
+        Ok(())
+    }
+
+    pub fn components(&self) -> &[TimeShifted<Forecast<Time>, Time>] {
+        &self.components
+    }
+
+    /// The time at which the last component ends.
+    pub fn incremental_duration(&self) -> Time {
+        Time::from(self.components.iter().fold(0., |duration: f64, component| {
+            duration.max(component.end_time().value())
+        }))
+    }
+
+    /// The sum of the rates of the components at the given time.
+    pub fn rate_at_time(&self, time: Time) -> ProductionRate<Time> {
+        ProductionRate::new(
+            self.components
+                .iter()
+                .map(|component| component.rate_at_time(time).value())
+                .fold(0., |total, rate| total + rate),
+        )
+    }
+
+    /// The volume produced between two times by every component. This is negative if `end` is
+    /// before `start`.
+    pub fn incremental_volume_between(&self, start: Time, end: Time) -> f64 {
+        self.component_volumes_between(start, end)
+            .fold(0., |total, volume| total + volume)
+    }
+
+    /// The volume produced between two times by each component, in the order of the components,
+    /// e.g., to attribute the volume of a period to each project.
+    pub fn component_volumes_between(
+        &self,
+        start: Time,
+        end: Time,
+    ) -> impl Iterator<Item = f64> + '_ {
+        self.components
+            .iter()
+            .map(move |component| component.incremental_volume_between(start, end))
+    }
+
+    pub fn incremental_volume_at_time(&self, time: Time) -> f64 {
+        self.incremental_volume_between(Time::from(0.), time)
+    }
+
+    pub fn incremental_volume(&self) -> f64 {
+        self.components
+            .iter()
+            .map(|component| component.incremental_volume())
+            .fold(0., |total, volume| total + volume)
+    }
+
+    /// Returns the volume produced in each period of the given length until the last component
+    /// ends.
+    pub fn volumes_by_period(
+        &self,
+        period_length: Time,
+    ) -> Result<impl Iterator<Item = PeriodVolume<Time>> + '_, DeclineCurveAnalysisError> {
+        validate_period_length(period_length)?;
+
+        Ok(period_volumes_between(
+            self.incremental_duration().value(),
+            fixed_period_boundaries(period_length.value()),
+            move |start, end| self.incremental_volume_between(start, end),
+        ))
+    }
+}
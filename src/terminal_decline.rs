@@ -0,0 +1,142 @@
+use crate::{
+    DeclineCurveAnalysisError, DeclineTimeUnit, ExponentialParameters, Forecast,
+    HarmonicParameters, HyperbolicParameters, NominalDeclineRate, Segment,
+    TangentEffectiveDeclineRate, is_effectively_zero, validate_non_zero_positive_rate,
+};
+use alloc::vec;
+
+/// When a decline switches to a terminal exponential decline, since companies and reserves
+/// guidelines enforce different conventions for the minimum decline rate (Dmin).
+///
+/// The minimum decline rates only apply to hyperbolic and harmonic declines, whose decline rates
+/// fall over time. A switch at a fixed time applies to any decline that can be split.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum TerminalDeclinePolicy<Time: DeclineTimeUnit> {
+    /// The decline continues until the end of the forecast.
+    #[default]
+    None,
+    /// Switches once the nominal decline rate falls to this rate, which is the convention of
+    /// [`Forecast::modified_hyperbolic`].
+    Nominal(NominalDeclineRate<Time>),
+    /// Switches once the tangent effective decline rate falls to this rate (e.g., 6% per year).
+    TangentEffective(TangentEffectiveDeclineRate<Time>),
+    /// Switches at this time after the start of the decline, continuing at the decline rate at
+    /// that time.
+    AtTime(Time),
+}
+
+impl<Time: DeclineTimeUnit> TerminalDeclinePolicy<Time> {
+    /// Validates the rate or time of the policy.
+    pub fn validate(&self) -> Result<(), DeclineCurveAnalysisError> {
+        match *self {
+            Self::None => Ok(()),
+            Self::Nominal(decline_rate) => {
+                validate_non_zero_positive_rate(decline_rate.value(), "terminal decline rate")
+            }
+            Self::TangentEffective(decline_rate) => validate_non_zero_positive_rate(
+                decline_rate.to_nominal()?.value(),
+                "terminal decline rate",
+            ),
+            Self::AtTime(time) => validate_non_zero_positive_rate(time.value(), "terminal time"),
+        }
+    }
+
+    /// The minimum nominal decline rate of the policy, if it has one.
+    fn minimum_decline_rate(
+        &self,
+    ) -> Result<Option<NominalDeclineRate<Time>>, DeclineCurveAnalysisError> {
+        Ok(match *self {
+            Self::Nominal(decline_rate) => Some(decline_rate),
+            Self::TangentEffective(decline_rate) => Some(decline_rate.to_nominal()?),
+            Self::None | Self::AtTime(_) => None,
+        })
+    }
+
+    /// The time that the decline switches to the terminal decline, if it does.
+    fn switch_time(
+        &self,
+        decline: &Segment<Time>,
+    ) -> Result<Option<f64>, DeclineCurveAnalysisError> {
+        if let Self::AtTime(time) = *self {
+            return Ok(Some(time.value()));
+        }
+        let Some(minimum_decline_rate) = self.minimum_decline_rate()? else {
+            return Ok(None);
+        };
+
+        let switch_time = match decline {
+            Segment::Harmonic(parameters)
+                if parameters.initial_decline_rate().value() > minimum_decline_rate.value() =>
+            {
+                HarmonicParameters::from_final_decline_rate(
+                    parameters.initial_rate(),
+                    parameters.initial_decline_rate(),
+                    minimum_decline_rate,
+                )?
+                .incremental_duration()
+                .value()
+            }
+            Segment::Hyperbolic(parameters)
+                if parameters.initial_decline_rate().value() > minimum_decline_rate.value() =>
+            {
+                HyperbolicParameters::from_final_decline_rate(
+                    parameters.initial_rate(),
+                    parameters.initial_decline_rate(),
+                    minimum_decline_rate,
+                    parameters.exponent(),
+                )?
+                .incremental_duration()
+                .value()
+            }
+            // The decline rate already starts at or below the minimum.
+            Segment::Harmonic(_) | Segment::Hyperbolic(_) => 0.,
+            _ => return Ok(None),
+        };
+
+        Ok(Some(switch_time))
+    }
+}
+
+impl<Time: DeclineTimeUnit> Forecast<Time> {
+    /// Creates a forecast that follows `decline` until the policy switches it to an exponential
+    /// decline for the rest of the duration of `decline`.
+    ///
+    /// The forecast is just `decline` if the policy doesn't switch before it ends.
+    pub fn with_terminal_decline(
+        decline: impl Into<Segment<Time>>,
+        policy: TerminalDeclinePolicy<Time>,
+    ) -> Result<Self, DeclineCurveAnalysisError> {
+        policy.validate()?;
+
+        let decline = decline.into();
+        let duration = decline.incremental_duration().value();
+        let Some(switch_time) = policy
+            .switch_time(&decline)?
+            .filter(|switch_time| *switch_time < duration)
+        else {
+            return Ok(Self::new(vec![decline]));
+        };
+
+        let terminal_decline_rate = match policy.minimum_decline_rate()? {
+            Some(minimum_decline_rate) if switch_time > 0. => minimum_decline_rate,
+            _ => decline.decline_rate_at_time(Time::from(switch_time)),
+        };
+        let terminal = |initial_rate| {
+            ExponentialParameters::from_incremental_duration(
+                initial_rate,
+                terminal_decline_rate,
+                Time::from(duration - switch_time),
+            )
+        };
+
+        if is_effectively_zero(switch_time) {
+            return Ok(Self::new(vec![
+                terminal(decline.rate_at_time(Time::from(0.)))?.into(),
+            ]));
+        }
+
+        let (decline, _) = decline.split_at(Time::from(switch_time))?;
+        let exponential = terminal(decline.final_rate())?;
+        Ok(Self::new(vec![decline, exponential.into()]))
+    }
+}
@@ -0,0 +1,159 @@
+use crate::{
+    DeclineCurveAnalysisError, DeclineTimeUnit, DelayParameters, ExponentialParameters,
+    FlatParameters, Forecast, HarmonicParameters, HyperbolicParameters, LinearParameters,
+    LogisticGrowthParameters, NominalDeclineRate, PowerLawExponentialParameters, ProductionRate,
+    Segment, validate_positive,
+};
+
+/// A segment or forecast that starts at an offset on an absolute time axis (e.g., project time),
+/// instead of at time zero.
+///
+/// Times passed to its methods and returned from its solves are absolute. Rates and volumes are
+/// zero before the start, and the wrapped segment or forecast is unchanged.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeShifted<S, Time: DeclineTimeUnit> {
+    inner: S,
+    start_offset: Time,
+}
+
+impl<S, Time: DeclineTimeUnit> TimeShifted<S, Time> {
+    /// Starts the segment or forecast at `start_offset`, which must be zero or positive.
+    pub fn new(inner: S, start_offset: Time) -> Result<Self, DeclineCurveAnalysisError> {
+        validate_positive(start_offset.value(), "start offset")?;
+
+        Ok(Self {
+            inner,
+            start_offset,
+        })
+    }
+
+    /// The underlying segment or forecast, which is evaluated on its own time axis.
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+
+    pub fn start_offset(&self) -> Time {
+        self.start_offset
+    }
+
+    /// Converts an absolute time to the time since the start, which is negative before the start.
+    fn local_time(&self, time: Time) -> f64 {
+        time.value() - self.start_offset.value()
+    }
+
+    fn absolute_time(&self, local_time: Time) -> Time {
+        Time::from(local_time.value() + self.start_offset.value())
+    }
+}
+
+macro_rules! impl_time_shifted {
+    ($($inner:ident),* $(,)?) => {
+        $(
+            impl<Time: DeclineTimeUnit> $inner<Time> {
+                /// Wraps this in a [`TimeShifted`] that starts at `start_offset` on an absolute
+                /// time axis.
+                pub fn with_start_offset(
+                    self,
+                    start_offset: Time,
+                ) -> Result<TimeShifted<Self, Time>, DeclineCurveAnalysisError> {
+                    TimeShifted::new(self, start_offset)
+                }
+            }
+
+            impl<Time: DeclineTimeUnit> TimeShifted<$inner<Time>, Time> {
+                pub fn incremental_duration(&self) -> Time {
+                    self.inner.incremental_duration()
+                }
+
+                /// The absolute time at the end.
+                pub fn end_time(&self) -> Time {
+                    self.absolute_time(self.inner.incremental_duration())
+                }
+
+                /// The rate at an absolute time, which is zero before the start.
+                pub fn rate_at_time(&self, time: Time) -> ProductionRate<Time> {
+                    let local_time = self.local_time(time);
+                    if local_time < 0. {
+                        return ProductionRate::new(0.);
+                    }
+
+                    self.inner.rate_at_time(Time::from(local_time))
+                }
+
+                /// The decline rate at an absolute time, which is zero before the start.
+                pub fn decline_rate_at_time(&self, time: Time) -> NominalDeclineRate<Time> {
+                    let local_time = self.local_time(time);
+                    if local_time < 0. {
+                        return NominalDeclineRate::new(0.);
+                    }
+
+                    self.inner.decline_rate_at_time(Time::from(local_time))
+                }
+
+                pub fn final_rate(&self) -> ProductionRate<Time> {
+                    self.inner.final_rate()
+                }
+
+                /// The volume produced from the start until an absolute time, which is zero before
+                /// the start.
+                pub fn incremental_volume_at_time(&self, time: Time) -> f64 {
+                    let local_time = self.local_time(time);
+                    if local_time <= 0. {
+                        return 0.;
+                    }
+
+                    self.inner.incremental_volume_at_time(Time::from(local_time))
+                }
+
+                /// The volume produced between two absolute times, excluding any time before the
+                /// start.
+                pub fn incremental_volume_between(&self, start: Time, end: Time) -> f64 {
+                    self.inner.incremental_volume_between(
+                        Time::from(self.local_time(start).max(0.)),
+                        Time::from(self.local_time(end).max(0.)),
+                    )
+                }
+
+                pub fn incremental_volume(&self) -> f64 {
+                    self.inner.incremental_volume()
+                }
+
+                /// Returns the absolute time at which the rate reaches the given rate.
+                pub fn time_at_rate(
+                    &self,
+                    rate: ProductionRate<Time>,
+                ) -> Result<Time, DeclineCurveAnalysisError> {
+                    Ok(self.absolute_time(self.inner.time_at_rate(rate)?))
+                }
+
+                /// Returns the absolute time at which the volume produced since the start reaches
+                /// the given volume.
+                pub fn time_at_incremental_volume(
+                    &self,
+                    incremental_volume: f64,
+                ) -> Result<Time, DeclineCurveAnalysisError> {
+                    Ok(self.absolute_time(
+                        self.inner.time_at_incremental_volume(incremental_volume)?,
+                    ))
+                }
+            }
+        )*
+    };
+}
+
+impl_time_shifted!(
+    Forecast,
+    Segment,
+    DelayParameters,
+    ExponentialParameters,
+    FlatParameters,
+    HarmonicParameters,
+    HyperbolicParameters,
+    LinearParameters,
+    LogisticGrowthParameters,
+    PowerLawExponentialParameters,
+);
@@ -0,0 +1,97 @@
+use crate::{
+    DeclineCurveAnalysisError, DeclineTimeUnit, Forecast, ProductionRate, validate_positive,
+};
+
+/// The result of truncating parameters at an economic limit rate.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RateTruncation<Parameters, Time: DeclineTimeUnit> {
+    /// The shortened copy of the parameters, which ends when the rate reaches the economic limit.
+    pub parameters: Parameters,
+    /// The volume that was removed by the truncation.
+    pub truncated_volume: f64,
+    /// The duration that was removed by the truncation.
+    pub truncated_duration: Time,
+}
+
+impl<Parameters, Time: DeclineTimeUnit> RateTruncation<Parameters, Time> {
+    /// Returns true if any duration was removed, otherwise false.
+    pub fn is_truncated(&self) -> bool {
+        self.truncated_duration.value() > 0.
+    }
+
+    pub fn map_parameters<Mapped>(
+        self,
+        f: impl FnOnce(Parameters) -> Mapped,
+    ) -> RateTruncation<Mapped, Time> {
+        RateTruncation {
+            parameters: f(self.parameters),
+            truncated_volume: self.truncated_volume,
+            truncated_duration: self.truncated_duration,
+        }
+    }
+}
+
+/// The limit that ended a forecast first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BindingConstraint {
+    /// The rate fell to the economic limit.
+    EconomicLimit,
+    /// The forecast reached the maximum life before the rate fell to the economic limit.
+    MaximumLife,
+    /// The forecast ended before reaching either limit.
+    EndOfForecast,
+}
+
+/// The result of truncating a forecast at an economic limit rate and a maximum life.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LimitTruncation<Time: DeclineTimeUnit> {
+    /// The shortened copy of the forecast, which ends at the first limit that was reached.
+    pub forecast: Forecast<Time>,
+    /// The volume that was removed by the truncation.
+    pub truncated_volume: f64,
+    /// The duration that was removed by the truncation.
+    pub truncated_duration: Time,
+    /// The limit that ended the forecast.
+    pub binding_constraint: BindingConstraint,
+}
+
+impl<Time: DeclineTimeUnit> LimitTruncation<Time> {
+    /// Returns true if any duration was removed, otherwise false.
+    pub fn is_truncated(&self) -> bool {
+        self.truncated_duration.value() > 0.
+    }
+}
+
+/// Returns the duration of a segment with a monotonic rate after truncating it at the first time
+/// the rate falls to the economic limit.
+///
+/// If the initial rate is already below the limit, the duration is zero, and if the final rate is
+/// at or above the limit, the duration is unchanged. Otherwise, `solve` is called to find the time
+/// at which the rate reaches the limit.
+pub(crate) fn truncated_duration<Time: DeclineTimeUnit>(
+    initial_rate: ProductionRate<Time>,
+    final_rate: ProductionRate<Time>,
+    incremental_duration: Time,
+    economic_limit_rate: ProductionRate<Time>,
+    solve: impl FnOnce() -> Result<Time, DeclineCurveAnalysisError>,
+) -> Result<Time, DeclineCurveAnalysisError> {
+    validate_economic_limit_rate(economic_limit_rate)?;
+
+    if initial_rate.value() < economic_limit_rate.value() {
+        return Ok(Time::from(0.));
+    }
+
+    if final_rate.value() >= economic_limit_rate.value() {
+        return Ok(incremental_duration);
+    }
+
+    Ok(Time::from(
+        solve()?.value().min(incremental_duration.value()),
+    ))
+}
+
+pub(crate) fn validate_economic_limit_rate<Time: DeclineTimeUnit>(
+    economic_limit_rate: ProductionRate<Time>,
+) -> Result<(), DeclineCurveAnalysisError> {
+    validate_positive(economic_limit_rate.value(), "economic limit rate")
+}
@@ -0,0 +1,159 @@
+use crate::{
+    CalendarTime, DeclineCurveAnalysisError, DeclineTimeUnit, Forecast, HistoryTimeAxis,
+    ProductionHistory, ProductionRate, Segment, validate_non_zero_positive_rate,
+};
+use alloc::vec::Vec;
+
+/// A normalized forecast that's scaled to forecast specific wells, e.g., a forecast per 1000 ft of
+/// lateral length that's scaled by the lateral length of an undeveloped location.
+///
+/// Scaling multiplies every rate and volume by the same factor, so the decline parameters and
+/// durations are unchanged.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TypeCurve<Time: DeclineTimeUnit> {
+    forecast: Forecast<Time>,
+}
+
+impl<Time: DeclineTimeUnit> TypeCurve<Time> {
+    /// Creates a type curve from a forecast that's already normalized.
+    pub fn new(forecast: Forecast<Time>) -> Self {
+        Self { forecast }
+    }
+
+    /// Creates a type curve by dividing the rates of a forecast by `normalizer`, e.g., the lateral
+    /// length in thousands of feet.
+    pub fn normalize(
+        forecast: &Forecast<Time>,
+        normalizer: f64,
+    ) -> Result<Self, DeclineCurveAnalysisError> {
+        validate_non_zero_positive_rate(normalizer, "normalizer")?;
+
+        Ok(Self::new(scale_forecast(forecast, normalizer.recip())))
+    }
+
+    /// The normalized forecast.
+    pub fn forecast(&self) -> &Forecast<Time> {
+        &self.forecast
+    }
+
+    pub fn into_forecast(self) -> Forecast<Time> {
+        self.forecast
+    }
+
+    /// The initial rate of the first segment that isn't a delay, or `None` if there isn't one.
+    pub fn initial_rate(&self) -> Option<ProductionRate<Time>> {
+        self.forecast
+            .segments()
+            .iter()
+            .find(|segment| !matches!(segment, Segment::Delay(_)))
+            .map(|segment| segment.rate_at_time(Time::from(0.)))
+    }
+
+    /// Returns a forecast where every rate of the type curve is multiplied by `factor`.
+    pub fn scale_by(&self, factor: f64) -> Result<Forecast<Time>, DeclineCurveAnalysisError> {
+        validate_non_zero_positive_rate(factor, "scale factor")?;
+
+        Ok(scale_forecast(&self.forecast, factor))
+    }
+
+    /// Returns a forecast that's scaled so that its initial rate (see `initial_rate`) matches the
+    /// given rate.
+    ///
+    /// This fails if the type curve doesn't have a positive initial rate, e.g., if it starts with
+    /// logistic growth.
+    pub fn scale_to_ip(
+        &self,
+        initial_rate: ProductionRate<Time>,
+    ) -> Result<Forecast<Time>, DeclineCurveAnalysisError> {
+        validate_non_zero_positive_rate(initial_rate.value(), "initial rate")?;
+
+        let type_curve_rate = self.initial_rate().map_or(0., |rate| rate.value());
+        validate_non_zero_positive_rate(type_curve_rate, "type curve initial rate")?;
+
+        self.scale_by(initial_rate.value() / type_curve_rate)
+    }
+}
+
+fn scale_forecast<Time: DeclineTimeUnit>(forecast: &Forecast<Time>, factor: f64) -> Forecast<Time> {
+    forecast
+        .segments()
+        .iter()
+        .map(|segment| segment.scale_rates(factor))
+        .collect()
+}
+
+/// The performance of a well relative to a type curve at the end of a producing period of its
+/// history.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PerformancePoint<Time: DeclineTimeUnit> {
+    /// The end of the period, in producing time since first production.
+    pub time: Time,
+    pub cumulative_volume: f64,
+    /// The cumulative volume of the type curve at the same producing time.
+    pub type_curve_cumulative_volume: f64,
+    /// The ratio of the cumulative volumes, or `None` if the type curve hasn't produced yet.
+    pub cumulative_ratio: Option<f64>,
+    /// The ratio of the volumes over the trailing window of periods, which follows recent
+    /// changes in performance, or `None` if the type curve didn't produce over the window.
+    pub rate_ratio: Option<f64>,
+}
+
+/// Tracks the actual production of a well as a percent of a type curve, with a point at the end
+/// of each producing period of the history (e.g., each month with production).
+///
+/// Time is measured in producing time, so that shut-ins don't count against the well. The rate
+/// ratio compares the volumes of the last `window` periods, e.g., 3 for a three-month trailing
+/// ratio. The remaining volumes of the type curve can be re-scaled with
+/// [`TypeCurve::scale_by`] using the latest ratio.
+pub fn performance_ratio<Time: DeclineTimeUnit>(
+    history: &ProductionHistory,
+    type_curve: &TypeCurve<Time>,
+    window: usize,
+) -> Result<Vec<PerformancePoint<Time>>, DeclineCurveAnalysisError> {
+    if window == 0 {
+        return Err(DeclineCurveAnalysisError::TooFewInputs {
+            field: "window",
+            count: 0,
+            minimum: 1,
+        });
+    }
+
+    let forecast = type_curve.forecast();
+    let to_time = |days: f64| CalendarTime { days }.to_unit::<Time>();
+    let ratio = |actual: f64, reference: f64| (reference > 0.).then(|| actual / reference);
+
+    let mut volumes = Vec::new();
+    let mut cumulative_volume = 0.;
+    let mut type_curve_cumulative_volume = 0.;
+
+    Ok(history
+        .producing_periods(HistoryTimeAxis::Producing)
+        .into_iter()
+        .map(|period| {
+            let start = to_time(period.start_days);
+            let end = to_time(period.start_days + period.days);
+            let volume = period.rate_per_day * period.days;
+            let type_curve_volume = forecast.incremental_volume_between(start, end);
+
+            cumulative_volume += volume;
+            type_curve_cumulative_volume += type_curve_volume;
+            volumes.push((volume, type_curve_volume));
+
+            let (window_volume, window_type_curve_volume) = volumes
+                [volumes.len().saturating_sub(window)..]
+                .iter()
+                .fold((0., 0.), |(a, b), (volume, type_curve_volume)| {
+                    (a + volume, b + type_curve_volume)
+                });
+
+            PerformancePoint {
+                time: end,
+                cumulative_volume,
+                type_curve_cumulative_volume,
+                cumulative_ratio: ratio(cumulative_volume, type_curve_cumulative_volume),
+                rate_ratio: ratio(window_volume, window_type_curve_volume),
+            }
+        })
+        .collect())
+}
@@ -0,0 +1,213 @@
+use crate::{
+    DeclineCurveAnalysisError, DeclineTimeUnit, Forecast, ProductionRate, Segment,
+    is_effectively_zero, validate_non_zero_positive_rate, validate_positive,
+};
+use alloc::vec::Vec;
+
+/// How serious a quality control issue is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// The forecast may be intended, but should be reviewed, e.g., an incline.
+    Warning,
+    /// The forecast breaks a limit that was given, e.g., a maximum well life.
+    Error,
+}
+
+/// A quality control issue in a forecast.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ValidationIssueKind<Time: DeclineTimeUnit> {
+    /// The forecast has no segments.
+    EmptyForecast,
+    /// The initial rate of the segment doesn't match the final rate of the previous segment.
+    RateDiscontinuity {
+        previous_final_rate: ProductionRate<Time>,
+        initial_rate: ProductionRate<Time>,
+    },
+    /// The final rate of the segment is higher than its initial rate.
+    IncreasingRate {
+        initial_rate: ProductionRate<Time>,
+        final_rate: ProductionRate<Time>,
+    },
+    /// The segment has no duration, so it has no effect on the forecast.
+    ZeroDurationSegment,
+    /// The segment ends after the maximum well life.
+    PastMaximumLife { end: Time, maximum_life: Time },
+}
+
+impl<Time: DeclineTimeUnit> ValidationIssueKind<Time> {
+    pub fn severity(&self) -> Severity {
+        match self {
+            Self::PastMaximumLife { .. } => Severity::Error,
+            _ => Severity::Warning,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ValidationIssue<Time: DeclineTimeUnit> {
+    /// The index of the segment with the issue, or `None` for issues with the whole forecast.
+    pub segment_index: Option<usize>,
+    pub kind: ValidationIssueKind<Time>,
+}
+
+impl<Time: DeclineTimeUnit> ValidationIssue<Time> {
+    pub fn severity(&self) -> Severity {
+        self.kind.severity()
+    }
+}
+
+/// The limits that a forecast is checked against.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ValidationOptions<Time: DeclineTimeUnit> {
+    /// The largest relative difference between the rates at a segment boundary that's treated as
+    /// continuous.
+    pub rate_tolerance: f64,
+    /// Whether segments with increasing rates are reported, which should be disabled for
+    /// forecasts that include a ramp up.
+    pub report_increasing_rates: bool,
+    pub maximum_life: Option<Time>,
+}
+
+impl<Time: DeclineTimeUnit> Default for ValidationOptions<Time> {
+    fn default() -> Self {
+        Self {
+            rate_tolerance: 1e-6,
+            report_increasing_rates: true,
+            maximum_life: None,
+        }
+    }
+}
+
+/// The quality control issues in a forecast, in the order of the segments.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationReport<Time: DeclineTimeUnit> {
+    issues: Vec<ValidationIssue<Time>>,
+}
+
+impl<Time: DeclineTimeUnit> ValidationReport<Time> {
+    pub fn issues(&self) -> &[ValidationIssue<Time>] {
+        &self.issues
+    }
+
+    pub fn is_clean(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.issues
+            .iter()
+            .any(|issue| issue.severity() == Severity::Error)
+    }
+
+    pub fn errors(&self) -> impl Iterator<Item = &ValidationIssue<Time>> {
+        self.with_severity(Severity::Error)
+    }
+
+    pub fn warnings(&self) -> impl Iterator<Item = &ValidationIssue<Time>> {
+        self.with_severity(Severity::Warning)
+    }
+
+    fn with_severity(&self, severity: Severity) -> impl Iterator<Item = &ValidationIssue<Time>> {
+        self.issues
+            .iter()
+            .filter(move |issue| issue.severity() == severity)
+    }
+}
+
+impl<Time: DeclineTimeUnit> Forecast<Time> {
+    /// Checks the forecast for common quality control issues with the default options.
+    pub fn validate(&self) -> ValidationReport<Time> {
+        self.validation_report(&ValidationOptions::default())
+    }
+
+    /// Checks the forecast for quality control issues, e.g., to show flags to users.
+    ///
+    /// Delay segments don't produce, so the rates at their boundaries aren't compared.
+    pub fn validate_with(
+        &self,
+        options: &ValidationOptions<Time>,
+    ) -> Result<ValidationReport<Time>, DeclineCurveAnalysisError> {
+        validate_positive(options.rate_tolerance, "rate tolerance")?;
+        if let Some(maximum_life) = options.maximum_life {
+            validate_non_zero_positive_rate(maximum_life.value(), "maximum life")?;
+        }
+
+        Ok(self.validation_report(options))
+    }
+
+    fn validation_report(&self, options: &ValidationOptions<Time>) -> ValidationReport<Time> {
+        let mut issues = Vec::new();
+        if self.segments().is_empty() {
+            issues.push(ValidationIssue {
+                segment_index: None,
+                kind: ValidationIssueKind::EmptyForecast,
+            });
+        }
+
+        let mut previous: Option<&Segment<Time>> = None;
+        let mut end = 0.;
+
+        for (index, segment) in self.segments().iter().enumerate() {
+            let mut report = |kind| {
+                issues.push(ValidationIssue {
+                    segment_index: Some(index),
+                    kind,
+                })
+            };
+
+            let duration = segment.incremental_duration().value();
+            end += duration;
+
+            if is_effectively_zero(duration) {
+                report(ValidationIssueKind::ZeroDurationSegment);
+            }
+
+            if !is_delay(segment) {
+                let initial_rate = segment.rate_at_time(Time::from(0.));
+                let final_rate = segment.final_rate();
+
+                if let Some(previous) = previous.filter(|previous| !is_delay(previous)) {
+                    let previous_final_rate = previous.final_rate();
+                    let difference = (initial_rate.value() - previous_final_rate.value()).abs();
+                    let scale = initial_rate
+                        .value()
+                        .abs()
+                        .max(previous_final_rate.value().abs());
+                    if difference > options.rate_tolerance * scale {
+                        report(ValidationIssueKind::RateDiscontinuity {
+                            previous_final_rate,
+                            initial_rate,
+                        });
+                    }
+                }
+
+                if options.report_increasing_rates
+                    && final_rate.value() > initial_rate.value() * (1. + options.rate_tolerance)
+                {
+                    report(ValidationIssueKind::IncreasingRate {
+                        initial_rate,
+                        final_rate,
+                    });
+                }
+            }
+
+            if let Some(maximum_life) = options.maximum_life
+                && end > maximum_life.value()
+                && !is_effectively_zero(end - maximum_life.value())
+            {
+                report(ValidationIssueKind::PastMaximumLife {
+                    end: Time::from(end),
+                    maximum_life,
+                });
+            }
+
+            previous = Some(segment);
+        }
+
+        ValidationReport { issues }
+    }
+}
+
+fn is_delay<Time: DeclineTimeUnit>(segment: &Segment<Time>) -> bool {
+    matches!(segment, Segment::Delay(_))
+}
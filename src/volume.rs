@@ -0,0 +1,148 @@
+use core::ops::{Add, Mul, Sub};
+
+use crate::{
+    DeclineCurveAnalysisError, DeclineTimeUnit, ProductionRate, validate_non_zero_positive_rate,
+};
+
+/// Cubic meters per barrel.
+const CUBIC_METERS_PER_BARREL: f64 = 0.158_987_294_928;
+
+/// A volume unit for production rates. Volume units are tags that prevent rates for different
+/// streams (e.g., oil and gas) from being mixed by accident.
+pub trait VolumeUnit: Copy + Clone + core::fmt::Debug + PartialEq {}
+
+/// The volume unit of rates that haven't been tagged, which includes the rates used by the decline
+/// curves.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UnspecifiedVolume;
+
+/// Barrels of liquid.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Barrels;
+
+/// Thousands of cubic feet of gas.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Mcf;
+
+/// Cubic meters of liquid.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CubicMeters;
+
+/// Barrels of oil equivalent.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Boe;
+
+impl VolumeUnit for UnspecifiedVolume {}
+impl VolumeUnit for Barrels {}
+impl VolumeUnit for Mcf {}
+impl VolumeUnit for CubicMeters {}
+impl VolumeUnit for Boe {}
+
+/// The conversion from gas volumes to barrels of oil equivalent (BOE).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoeConversion {
+    gas_per_boe: f64,
+}
+
+impl BoeConversion {
+    /// Creates a conversion where `gas_per_boe` Mcf of gas are equivalent to one barrel of oil.
+    pub fn new(gas_per_boe: f64) -> Result<Self, DeclineCurveAnalysisError> {
+        validate_non_zero_positive_rate(gas_per_boe, "gas per BOE")?;
+        Ok(Self { gas_per_boe })
+    }
+
+    pub fn gas_per_boe(&self) -> f64 {
+        self.gas_per_boe
+    }
+
+    /// Converts oil and gas volumes to a total BOE volume.
+    pub fn to_boe(&self, oil_volume: f64, gas_volume: f64) -> f64 {
+        oil_volume + gas_volume / self.gas_per_boe
+    }
+}
+
+impl Default for BoeConversion {
+    /// The common energy-equivalent conversion of 6 Mcf of gas per barrel of oil.
+    fn default() -> Self {
+        Self { gas_per_boe: 6. }
+    }
+}
+
+impl<Time: DeclineTimeUnit> ProductionRate<Time> {
+    /// Tags the rate with a volume unit.
+    pub const fn with_volume_unit<Volume: VolumeUnit>(self) -> ProductionRate<Time, Volume> {
+        ProductionRate::new(self.value())
+    }
+}
+
+impl<Time: DeclineTimeUnit, Volume: VolumeUnit> ProductionRate<Time, Volume> {
+    /// Removes the volume unit tag, so that the rate can be used with the decline curves.
+    pub const fn without_volume_unit(self) -> ProductionRate<Time> {
+        ProductionRate::new(self.value())
+    }
+
+    /// Converts the rate to a different time unit, keeping the volume unit.
+    pub fn to_time_unit<OtherTime: DeclineTimeUnit>(self) -> ProductionRate<OtherTime, Volume> {
+        ProductionRate::new(self.value() * OtherTime::LENGTH / Time::LENGTH)
+    }
+}
+
+impl<Time: DeclineTimeUnit> ProductionRate<Time, Mcf> {
+    /// Converts a gas rate to a BOE rate.
+    pub fn to_boe(self, conversion: BoeConversion) -> ProductionRate<Time, Boe> {
+        ProductionRate::new(self.value() / conversion.gas_per_boe)
+    }
+}
+
+impl<Time: DeclineTimeUnit> From<ProductionRate<Time, Barrels>>
+    for ProductionRate<Time, CubicMeters>
+{
+    fn from(rate: ProductionRate<Time, Barrels>) -> Self {
+        ProductionRate::new(rate.value() * CUBIC_METERS_PER_BARREL)
+    }
+}
+
+impl<Time: DeclineTimeUnit> From<ProductionRate<Time, CubicMeters>>
+    for ProductionRate<Time, Barrels>
+{
+    fn from(rate: ProductionRate<Time, CubicMeters>) -> Self {
+        ProductionRate::new(rate.value() / CUBIC_METERS_PER_BARREL)
+    }
+}
+
+impl<Time: DeclineTimeUnit> From<ProductionRate<Time, Barrels>> for ProductionRate<Time, Boe> {
+    fn from(rate: ProductionRate<Time, Barrels>) -> Self {
+        ProductionRate::new(rate.value())
+    }
+}
+
+impl<Time: DeclineTimeUnit> From<ProductionRate<Time, CubicMeters>> for ProductionRate<Time, Boe> {
+    fn from(rate: ProductionRate<Time, CubicMeters>) -> Self {
+        ProductionRate::<Time, Barrels>::from(rate).into()
+    }
+}
+
+/// Rates can only be added when they have the same time and volume units.
+impl<Time: DeclineTimeUnit, Volume: VolumeUnit> Add for ProductionRate<Time, Volume> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.value() + other.value())
+    }
+}
+
+impl<Time: DeclineTimeUnit, Volume: VolumeUnit> Sub for ProductionRate<Time, Volume> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.value() - other.value())
+    }
+}
+
+impl<Time: DeclineTimeUnit, Volume: VolumeUnit> Mul<f64> for ProductionRate<Time, Volume> {
+    type Output = Self;
+
+    fn mul(self, factor: f64) -> Self {
+        Self::new(self.value() * factor)
+    }
+}
@@ -0,0 +1,207 @@
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    AverageDaysTime, AverageYearsTime, DelayParameters, ExponentialParameters, FlatParameters,
+    Forecast, HarmonicParameters, HyperbolicParameters, LinearParameters, NominalDeclineRate,
+    ProductionRate, SecantEffectiveDeclineRate, Segment, TangentEffectiveDeclineRate,
+};
+
+/// A forecast for JavaScript, which is built one segment at a time.
+///
+/// Times and durations are in days, rates are per day, and decline rates are nominal per year.
+/// Batch methods take and return flat arrays, which map to `Float64Array` in JavaScript.
+#[wasm_bindgen(js_name = Forecast)]
+#[derive(Debug, Clone)]
+pub struct WasmForecast {
+    forecast: Forecast<AverageDaysTime>,
+}
+
+impl Default for WasmForecast {
+    fn default() -> Self {
+        Self {
+            forecast: Forecast::new(Vec::new()),
+        }
+    }
+}
+
+fn days(days: f64) -> AverageDaysTime {
+    AverageDaysTime { days }
+}
+
+fn annual_decline_rate(decline_rate: f64) -> NominalDeclineRate<AverageDaysTime> {
+    NominalDeclineRate::<AverageYearsTime>::new(decline_rate).into()
+}
+
+impl WasmForecast {
+    fn push(&mut self, segment: impl Into<Segment<AverageDaysTime>>) {
+        let mut segments =
+            std::mem::replace(&mut self.forecast, Forecast::new(Vec::new())).into_segments();
+        segments.push(segment.into());
+        self.forecast = Forecast::new(segments);
+    }
+}
+
+#[wasm_bindgen(js_class = Forecast)]
+impl WasmForecast {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[wasm_bindgen(js_name = addDelay)]
+    pub fn add_delay(&mut self, duration: f64) -> Result<(), JsError> {
+        self.push(DelayParameters::from_incremental_duration(days(duration))?);
+        Ok(())
+    }
+
+    #[wasm_bindgen(js_name = addFlat)]
+    pub fn add_flat(&mut self, rate: f64, duration: f64) -> Result<(), JsError> {
+        self.push(FlatParameters::from_incremental_duration(
+            ProductionRate::new(rate),
+            days(duration),
+        )?);
+        Ok(())
+    }
+
+    #[wasm_bindgen(js_name = addExponential)]
+    pub fn add_exponential(
+        &mut self,
+        initial_rate: f64,
+        decline_rate: f64,
+        duration: f64,
+    ) -> Result<(), JsError> {
+        self.push(ExponentialParameters::from_incremental_duration(
+            ProductionRate::new(initial_rate),
+            annual_decline_rate(decline_rate),
+            days(duration),
+        )?);
+        Ok(())
+    }
+
+    #[wasm_bindgen(js_name = addHarmonic)]
+    pub fn add_harmonic(
+        &mut self,
+        initial_rate: f64,
+        decline_rate: f64,
+        duration: f64,
+    ) -> Result<(), JsError> {
+        self.push(HarmonicParameters::from_incremental_duration(
+            ProductionRate::new(initial_rate),
+            annual_decline_rate(decline_rate),
+            days(duration),
+        )?);
+        Ok(())
+    }
+
+    #[wasm_bindgen(js_name = addHyperbolic)]
+    pub fn add_hyperbolic(
+        &mut self,
+        initial_rate: f64,
+        decline_rate: f64,
+        duration: f64,
+        exponent: f64,
+    ) -> Result<(), JsError> {
+        self.push(HyperbolicParameters::from_incremental_duration(
+            ProductionRate::new(initial_rate),
+            annual_decline_rate(decline_rate),
+            days(duration),
+            exponent,
+        )?);
+        Ok(())
+    }
+
+    #[wasm_bindgen(js_name = addLinear)]
+    pub fn add_linear(
+        &mut self,
+        initial_rate: f64,
+        decline_rate: f64,
+        duration: f64,
+    ) -> Result<(), JsError> {
+        self.push(LinearParameters::from_incremental_duration(
+            ProductionRate::new(initial_rate),
+            annual_decline_rate(decline_rate),
+            days(duration),
+        )?);
+        Ok(())
+    }
+
+    #[wasm_bindgen(js_name = segmentCount)]
+    pub fn segment_count(&self) -> usize {
+        self.forecast.segments().len()
+    }
+
+    #[wasm_bindgen(js_name = incrementalDuration)]
+    pub fn incremental_duration(&self) -> f64 {
+        self.forecast.incremental_duration().days
+    }
+
+    #[wasm_bindgen(js_name = incrementalVolume)]
+    pub fn incremental_volume(&self) -> f64 {
+        self.forecast.incremental_volume()
+    }
+
+    /// The rate at each time.
+    #[wasm_bindgen(js_name = ratesAtTimes)]
+    pub fn rates_at_times(&self, times: &[f64]) -> Vec<f64> {
+        let forecast = &self.forecast;
+        times
+            .iter()
+            .map(|time| forecast.rate_at_time(days(*time)).value())
+            .collect()
+    }
+
+    /// The cumulative volume at each time.
+    #[wasm_bindgen(js_name = cumulativeVolumesAtTimes)]
+    pub fn cumulative_volumes_at_times(&self, times: &[f64]) -> Vec<f64> {
+        let forecast = &self.forecast;
+        times
+            .iter()
+            .map(|time| forecast.incremental_volume_at_time(days(*time)))
+            .collect()
+    }
+
+    /// The volume in each average month (30.4375 days) over the whole forecast.
+    #[wasm_bindgen(js_name = monthlyVolumes)]
+    pub fn monthly_volumes(&self) -> Vec<f64> {
+        self.forecast
+            .monthly_volumes()
+            .map(|period| period.volume)
+            .collect()
+    }
+}
+
+/// Converts a nominal decline rate to a secant effective decline rate.
+#[wasm_bindgen(js_name = nominalToSecantEffective)]
+pub fn nominal_to_secant_effective(decline_rate: f64, exponent: f64) -> Result<f64, JsError> {
+    Ok(NominalDeclineRate::<AverageYearsTime>::new(decline_rate)
+        .to_secant_effective(exponent)?
+        .value())
+}
+
+/// Converts a secant effective decline rate to a nominal decline rate.
+#[wasm_bindgen(js_name = secantEffectiveToNominal)]
+pub fn secant_effective_to_nominal(decline_rate: f64, exponent: f64) -> Result<f64, JsError> {
+    Ok(
+        SecantEffectiveDeclineRate::<AverageYearsTime>::new(decline_rate)
+            .to_nominal(exponent)?
+            .value(),
+    )
+}
+
+/// Converts a nominal decline rate to a tangent effective decline rate.
+#[wasm_bindgen(js_name = nominalToTangentEffective)]
+pub fn nominal_to_tangent_effective(decline_rate: f64) -> Result<f64, JsError> {
+    Ok(NominalDeclineRate::<AverageYearsTime>::new(decline_rate)
+        .to_tangent_effective()?
+        .value())
+}
+
+/// Converts a tangent effective decline rate to a nominal decline rate.
+#[wasm_bindgen(js_name = tangentEffectiveToNominal)]
+pub fn tangent_effective_to_nominal(decline_rate: f64) -> Result<f64, JsError> {
+    Ok(
+        TangentEffectiveDeclineRate::<AverageYearsTime>::new(decline_rate)
+            .to_nominal()?
+            .value(),
+    )
+}
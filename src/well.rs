@@ -0,0 +1,292 @@
+use crate::{
+    BoeConversion, DeclineCurveAnalysisError, DeclineTimeUnit, Forecast, PeriodVolume,
+    ProductionRate, RateTruncation, RatioForecast,
+    periods::{fixed_period_boundaries, period_volumes_between, validate_period_length},
+    validate_non_zero_positive_rate,
+};
+use alloc::{format, string::ToString, vec::Vec};
+use core::ops::Bound;
+
+/// A produced fluid phase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum Phase {
+    Oil,
+    Gas,
+    Water,
+}
+
+impl Phase {
+    /// The lowercase name of the phase, which is how it's written to exported tables.
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Self::Oil => "oil",
+            Self::Gas => "gas",
+            Self::Water => "water",
+        }
+    }
+}
+
+/// How a secondary phase of a well is forecast.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum PhaseForecast<Time: DeclineTimeUnit> {
+    /// The phase has its own rate forecast.
+    Rate(Forecast<Time>),
+    /// The phase is forecast as a ratio of the primary phase.
+    Ratio(RatioForecast<Time>),
+}
+
+/// The result of truncating a well forecast when its water cut reaches a limit.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WaterCutTruncation<Time: DeclineTimeUnit> {
+    /// The shortened copy of the well forecast, which ends when the water cut reaches the limit.
+    pub forecast: WellForecast<Time>,
+    /// The time at which the water cut reaches the limit, or `None` if it isn't reached within the
+    /// well's life.
+    pub truncation_time: Option<Time>,
+    /// The volume of each phase produced by the shortened forecast, in the order of `phases`.
+    pub volumes: Vec<(Phase, f64)>,
+}
+
+/// The forecasts for every phase produced by a well.
+///
+/// One phase is the primary phase, which determines the life of the well. The other phases are
+/// either forecast independently or as ratios of the primary phase, and every phase ends when the
+/// primary phase ends, so that truncating the primary phase keeps all phases synchronized.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WellForecast<Time: DeclineTimeUnit> {
+    primary_phase: Phase,
+    primary: Forecast<Time>,
+    secondary: Vec<(Phase, PhaseForecast<Time>)>,
+}
+
+impl<Time: DeclineTimeUnit> WellForecast<Time> {
+    /// Creates a well forecast, where each phase can only be forecast once.
+    pub fn new(
+        primary_phase: Phase,
+        primary: Forecast<Time>,
+        secondary: Vec<(Phase, PhaseForecast<Time>)>,
+    ) -> Result<Self, DeclineCurveAnalysisError> {
+        for (index, (phase, _)) in secondary.iter().enumerate() {
+            if *phase == primary_phase
+                || secondary[..index]
+                    .iter()
+                    .any(|(previous, _)| previous == phase)
+            {
+                return Err(DeclineCurveAnalysisError::DuplicateForecast {
+                    field: "phase",
+                    name: format!("{phase:?}"),
+                });
+            }
+        }
+
+        Ok(Self {
+            primary_phase,
+            primary,
+            secondary,
+        })
+    }
+
+    pub fn primary_phase(&self) -> Phase {
+        self.primary_phase
+    }
+
+    pub fn primary(&self) -> &Forecast<Time> {
+        &self.primary
+    }
+
+    pub fn secondary(&self) -> &[(Phase, PhaseForecast<Time>)] {
+        &self.secondary
+    }
+
+    /// Returns the phases that are forecast, starting with the primary phase.
+    pub fn phases(&self) -> impl Iterator<Item = Phase> + '_ {
+        core::iter::once(self.primary_phase).chain(self.secondary.iter().map(|(phase, _)| *phase))
+    }
+
+    /// The life of the well, which is the duration of the primary forecast.
+    pub fn incremental_duration(&self) -> Time {
+        self.primary.incremental_duration()
+    }
+
+    fn secondary_forecast(&self, phase: Phase) -> Option<&PhaseForecast<Time>> {
+        self.secondary
+            .iter()
+            .find(|(secondary_phase, _)| *secondary_phase == phase)
+            .map(|(_, forecast)| forecast)
+    }
+
+    /// The rate of a phase at the given time. Rates are zero after the end of the well's life,
+    /// and for phases that aren't forecast.
+    pub fn rate_at_time(&self, phase: Phase, time: Time) -> ProductionRate<Time> {
+        if time.value() > self.incremental_duration().value() {
+            return ProductionRate::new(0.);
+        }
+
+        if phase == self.primary_phase {
+            return self.primary.rate_at_time(time);
+        }
+
+        match self.secondary_forecast(phase) {
+            Some(PhaseForecast::Rate(forecast)) => {
+                if time.value() > forecast.incremental_duration().value() {
+                    ProductionRate::new(0.)
+                } else {
+                    forecast.rate_at_time(time)
+                }
+            }
+            Some(PhaseForecast::Ratio(ratios)) => ratios.rate_at_time(&self.primary, time),
+            None => ProductionRate::new(0.),
+        }
+    }
+
+    /// The volume of a phase produced between two times, which are clamped to be within the
+    /// well's life. This is negative if `end` is before `start`, and zero for phases that aren't
+    /// forecast.
+    pub fn incremental_volume_between(&self, phase: Phase, start: Time, end: Time) -> f64 {
+        let incremental_duration = self.incremental_duration().value();
+        let start = Time::from(start.value().min(incremental_duration));
+        let end = Time::from(end.value().min(incremental_duration));
+
+        if phase == self.primary_phase {
+            return self.primary.incremental_volume_between(start, end);
+        }
+
+        match self.secondary_forecast(phase) {
+            Some(PhaseForecast::Rate(forecast)) => forecast.incremental_volume_between(start, end),
+            Some(PhaseForecast::Ratio(ratios)) => {
+                ratios.incremental_volume_between(&self.primary, start, end)
+            }
+            None => 0.,
+        }
+    }
+
+    /// The volume of a phase produced over the well's life.
+    pub fn incremental_volume(&self, phase: Phase) -> f64 {
+        self.incremental_volume_between(phase, Time::from(0.), self.incremental_duration())
+    }
+
+    /// The oil and gas volumes produced between two times, converted to BOE.
+    pub fn boe_volume_between(&self, start: Time, end: Time, conversion: BoeConversion) -> f64 {
+        conversion.to_boe(
+            self.incremental_volume_between(Phase::Oil, start, end),
+            self.incremental_volume_between(Phase::Gas, start, end),
+        )
+    }
+
+    /// The oil and gas volumes produced over the well's life, converted to BOE.
+    pub fn boe_volume(&self, conversion: BoeConversion) -> f64 {
+        self.boe_volume_between(Time::from(0.), self.incremental_duration(), conversion)
+    }
+
+    /// Returns the volume of a phase produced in each period of the given length over the well's
+    /// life.
+    pub fn volumes_by_period(
+        &self,
+        phase: Phase,
+        period_length: Time,
+    ) -> Result<impl Iterator<Item = PeriodVolume<Time>> + '_, DeclineCurveAnalysisError> {
+        validate_period_length(period_length)?;
+
+        Ok(period_volumes_between(
+            self.incremental_duration().value(),
+            fixed_period_boundaries(period_length.value()),
+            move |start, end| self.incremental_volume_between(phase, start, end),
+        ))
+    }
+
+    /// The fraction of the liquid rate that's water at the given time, which is zero when there's
+    /// no liquid production.
+    pub fn water_cut_at_time(&self, time: Time) -> f64 {
+        let water_rate = self.rate_at_time(Phase::Water, time).value();
+        let liquid_rate = water_rate + self.rate_at_time(Phase::Oil, time).value();
+
+        if liquid_rate > 0. {
+            water_rate / liquid_rate
+        } else {
+            0.
+        }
+    }
+
+    /// Returns a copy of the well forecast that ends when the water cut first reaches
+    /// `max_water_cut`, which is a common abandonment criterion, along with the volumes of every
+    /// phase up to that time.
+    ///
+    /// This requires oil to be the primary phase, and water to be forecast as a ratio of oil (a
+    /// WOR), so that the water cut is `WOR / (1 + WOR)`.
+    pub fn truncate_at_water_cut(
+        &self,
+        max_water_cut: f64,
+    ) -> Result<WaterCutTruncation<Time>, DeclineCurveAnalysisError> {
+        validate_non_zero_positive_rate(max_water_cut, "max water cut")?;
+        if max_water_cut >= 1. {
+            return Err(DeclineCurveAnalysisError::InputOutOfRange {
+                field: "max water cut",
+                value: max_water_cut,
+                minimum: Bound::Excluded(0.),
+                maximum: Bound::Excluded(1.),
+            });
+        }
+
+        if self.primary_phase != Phase::Oil {
+            return Err(DeclineCurveAnalysisError::InvalidInput {
+                reason: "water cut truncation requires oil to be the primary phase".to_string(),
+            });
+        }
+        let Some(PhaseForecast::Ratio(water_oil_ratio)) = self.secondary_forecast(Phase::Water)
+        else {
+            return Err(DeclineCurveAnalysisError::InvalidInput {
+                reason: "water cut truncation requires water to be forecast as a ratio of oil"
+                    .to_string(),
+            });
+        };
+
+        let truncation_time = water_oil_ratio
+            .time_at_ratio(max_water_cut / (1. - max_water_cut))?
+            .filter(|time| time.value() < self.incremental_duration().value());
+
+        let forecast = match truncation_time {
+            Some(time) => Self {
+                primary_phase: self.primary_phase,
+                primary: self.primary.truncate_at_time(time)?,
+                secondary: self.secondary.clone(),
+            },
+            None => self.clone(),
+        };
+        let volumes = forecast
+            .phases()
+            .map(|phase| (phase, forecast.incremental_volume(phase)))
+            .collect();
+
+        Ok(WaterCutTruncation {
+            forecast,
+            truncation_time,
+            volumes,
+        })
+    }
+
+    /// Returns a copy of the well forecast where the primary phase is truncated at the economic
+    /// limit, which also ends every other phase. The truncated volume is for the primary phase.
+    pub fn truncate_at_rate(
+        &self,
+        economic_limit_rate: ProductionRate<Time>,
+    ) -> Result<RateTruncation<Self, Time>, DeclineCurveAnalysisError> {
+        Ok(self
+            .primary
+            .truncate_at_rate(economic_limit_rate)?
+            .map_parameters(|primary| Self {
+                primary_phase: self.primary_phase,
+                primary,
+                secondary: self.secondary.clone(),
+            }))
+    }
+}
@@ -0,0 +1,100 @@
+use decline_curve_analysis::{
+    AverageDaysTime, AverageMonthsTime, CalendarDate, ExponentialParameters, FlatParameters,
+    Forecast, NominalDeclineRate, ProductionRate, ProfileAlignment, TimeGrid, aggregate,
+    difference,
+};
+
+fn months(months: f64) -> AverageMonthsTime {
+    AverageMonthsTime { months }
+}
+
+fn flat(rate: f64, duration: f64) -> Forecast<AverageMonthsTime> {
+    Forecast::new(vec![
+        FlatParameters::from_incremental_duration(ProductionRate::new(rate), months(duration))
+            .unwrap()
+            .into(),
+    ])
+}
+
+#[test]
+fn aggregate_from_time_zero() {
+    let forecasts = [flat(100., 3.), flat(50., 5.)];
+    let grid = TimeGrid::uniform(months(1.), 6).unwrap();
+
+    let profile = aggregate(&forecasts, &grid, &ProfileAlignment::TimeZero).unwrap();
+
+    insta::assert_snapshot!(format!("{:?}", profile.rates()), @"[150.0, 150.0, 150.0, 50.0, 50.0, 0.0, 0.0]");
+    insta::assert_snapshot!(format!("{:?}", profile.volumes()), @"[150.0, 150.0, 150.0, 50.0, 50.0, 0.0]");
+    insta::assert_snapshot!(format!("{:?}", profile.cumulative_volumes()), @"[150.0, 300.0, 450.0, 500.0, 550.0, 550.0]");
+}
+
+#[test]
+fn aggregate_with_start_times() {
+    let forecasts = [flat(100., 3.), flat(50., 5.)];
+    let grid = TimeGrid::uniform(months(1.), 6).unwrap();
+    let alignment = ProfileAlignment::StartTimes(vec![months(0.), months(2.5)]);
+
+    let profile = aggregate(&forecasts, &grid, &alignment).unwrap();
+
+    insta::assert_snapshot!(format!("{:?}", profile.rates()), @"[100.0, 100.0, 100.0, 50.0, 50.0, 50.0, 50.0]");
+    insta::assert_snapshot!(format!("{:?}", profile.volumes()), @"[100.0, 100.0, 125.0, 50.0, 50.0, 50.0]");
+}
+
+#[test]
+fn aggregate_by_calendar_date() {
+    let decline = ExponentialParameters::from_incremental_duration(
+        ProductionRate::new(100.),
+        NominalDeclineRate::new(0.01),
+        AverageDaysTime { days: 365. },
+    )
+    .unwrap();
+    let forecasts = [
+        Forecast::new(vec![decline.clone().into()]),
+        Forecast::new(vec![decline.into()]),
+    ];
+    let grid_start = CalendarDate::new(2024, 1, 1).unwrap();
+    let alignment = ProfileAlignment::from_start_dates(
+        grid_start,
+        &[grid_start, CalendarDate::new(2024, 3, 1).unwrap()],
+    );
+    let grid = TimeGrid::uniform(AverageDaysTime { days: 30. }, 4).unwrap();
+
+    let profile = aggregate(&forecasts, &grid, &alignment).unwrap();
+
+    // The second well starts 60 days into the grid, since 2024 is a leap year.
+    insta::assert_snapshot!(format!("{alignment:?}"), @"StartTimes([AverageDaysTime { days: 0.0 }, AverageDaysTime { days: 60.0 }])");
+    insta::assert_snapshot!(profile.rates()[2] - forecasts[0].rate_at_time(AverageDaysTime { days: 60. }).value(), @"100");
+    insta::assert_snapshot!(profile.volumes()[2] - forecasts[0].incremental_volume_between(AverageDaysTime { days: 60. }, AverageDaysTime { days: 90. }), @"2591.817793182821");
+}
+
+#[test]
+fn mismatched_start_times() {
+    let forecasts = [flat(100., 3.), flat(50., 5.)];
+    let grid = TimeGrid::uniform(months(1.), 6).unwrap();
+
+    insta::assert_snapshot!(
+        aggregate(&forecasts, &grid, &ProfileAlignment::StartTimes(vec![months(0.)])).unwrap_err(),
+        @"start times has a length of 1, but expected 2"
+    );
+    insta::assert_snapshot!(
+        aggregate(&forecasts, &grid, &ProfileAlignment::StartTimes(vec![months(0.), months(f64::NAN)])).unwrap_err(),
+        @"start time is not-a-number, but expected a finite number"
+    );
+}
+
+#[test]
+fn incremental_profile_of_a_workover() {
+    let baseline = flat(100., 5.);
+    let mut segments = flat(100., 2.).into_segments();
+    segments.extend(flat(150., 6.).into_segments());
+    let with_workover = Forecast::new(segments);
+    let grid = TimeGrid::uniform(months(1.), 6).unwrap();
+
+    let incremental = difference(&with_workover, &baseline, &grid);
+
+    insta::assert_snapshot!(format!("{:?}", incremental.profile().rates()), @"[0.0, 0.0, 50.0, 50.0, 50.0, 150.0, 150.0]");
+    insta::assert_snapshot!(format!("{:?}", incremental.profile().volumes()), @"[0.0, 0.0, 50.0, 50.0, 50.0, 150.0]");
+    insta::assert_snapshot!(format!("{:?}", incremental.profile().cumulative_volumes()), @"[0.0, 0.0, 50.0, 100.0, 150.0, 300.0]");
+    // The grid ends before the workover does, but the EUR includes its whole life.
+    insta::assert_snapshot!(incremental.incremental_eur(), @"600");
+}
@@ -0,0 +1,178 @@
+use decline_curve_analysis::{
+    AverageDaysTime, AverageYearsTime, ExponentialParameters, FlatParameters, Forecast,
+    HyperbolicParameters, NominalDeclineRate, ProductionRate, Segment,
+};
+
+#[test]
+fn segment_approx_eq() {
+    let hyperbolic = |initial_rate, days| -> Segment<AverageDaysTime> {
+        HyperbolicParameters::from_incremental_duration(
+            ProductionRate::new(initial_rate),
+            NominalDeclineRate::new(0.002),
+            AverageDaysTime { days },
+            0.9,
+        )
+        .unwrap()
+        .into()
+    };
+    let segment = hyperbolic(1000., 3650.);
+
+    assert!(segment.approx_eq(&segment, 0.));
+    assert!(segment.approx_eq(&hyperbolic(1000. + 1e-7, 3650.), 1e-9));
+    assert!(!segment.approx_eq(&hyperbolic(1000. + 1e-5, 3650.), 1e-9));
+    assert!(segment.approx_eq(&hyperbolic(1000., 3650.0001), 1e-6));
+    assert!(!segment.approx_eq(&hyperbolic(1000., 3651.), 1e-6));
+
+    // Segments of different types are never approximately equal, even if their rates match.
+    let exponential: Segment<AverageDaysTime> = ExponentialParameters::from_incremental_duration(
+        ProductionRate::new(1000.),
+        NominalDeclineRate::new(0.002),
+        AverageDaysTime { days: 3650. },
+    )
+    .unwrap()
+    .into();
+    assert!(!segment.approx_eq(&exponential, 1.));
+}
+
+#[test]
+fn segment_approx_eq_across_time_units() {
+    let segment = |decline_rate| -> Segment<AverageDaysTime> {
+        ExponentialParameters::from_incremental_duration(
+            ProductionRate::new(1000.),
+            decline_rate,
+            AverageDaysTime { days: 3650. },
+        )
+        .unwrap()
+        .into()
+    };
+
+    let converted = segment(NominalDeclineRate::<AverageYearsTime>::new(0.3).into());
+    let direct = segment(NominalDeclineRate::new(0.3 / 365.25));
+    assert!(converted.approx_eq(&direct, 1e-12));
+}
+
+#[test]
+fn forecast_approx_eq() {
+    let with_initial_rate = |initial_rate| {
+        Forecast::new(vec![
+            FlatParameters::from_incremental_duration(
+                ProductionRate::new(initial_rate),
+                AverageDaysTime { days: 30. },
+            )
+            .unwrap()
+            .into(),
+            HyperbolicParameters::from_incremental_duration(
+                ProductionRate::new(initial_rate),
+                NominalDeclineRate::new(0.002),
+                AverageDaysTime { days: 3650. },
+                0.9,
+            )
+            .unwrap()
+            .into(),
+        ])
+    };
+    let forecast = with_initial_rate(1000.);
+
+    assert!(forecast.approx_eq(&forecast, 0.));
+    assert!(forecast.approx_eq(&with_initial_rate(1000. + 1e-7), 1e-9));
+    assert!(!forecast.approx_eq(&with_initial_rate(1001.), 1e-9));
+
+    let with_initial_cumulative_volume = forecast
+        .clone()
+        .with_initial_cumulative_volume(1e-3)
+        .unwrap();
+    assert!(forecast.approx_eq(&with_initial_cumulative_volume, 1e-2));
+    assert!(!forecast.approx_eq(&with_initial_cumulative_volume, 1e-4));
+
+    let truncated = Forecast::new(forecast.segments()[..1].to_vec());
+    assert!(!forecast.approx_eq(&truncated, 1.));
+}
+
+#[cfg(feature = "approx")]
+#[test]
+fn approx_traits() {
+    let hyperbolic = |initial_rate, days| -> Segment<AverageDaysTime> {
+        HyperbolicParameters::from_incremental_duration(
+            ProductionRate::new(initial_rate),
+            NominalDeclineRate::new(0.002),
+            AverageDaysTime { days },
+            0.9,
+        )
+        .unwrap()
+        .into()
+    };
+    let forecast = |initial_rate| {
+        Forecast::new(vec![
+            FlatParameters::from_incremental_duration(
+                ProductionRate::new(initial_rate),
+                AverageDaysTime { days: 30. },
+            )
+            .unwrap()
+            .into(),
+            HyperbolicParameters::from_incremental_duration(
+                ProductionRate::new(initial_rate),
+                NominalDeclineRate::new(0.002),
+                AverageDaysTime { days: 3650. },
+                0.9,
+            )
+            .unwrap()
+            .into(),
+        ])
+    };
+    let segment = hyperbolic(1000., 3650.);
+    let nearby = hyperbolic(1000. + 1e-9, 3650.);
+
+    approx::assert_relative_eq!(segment, nearby, max_relative = 1e-10);
+    approx::assert_relative_ne!(segment, hyperbolic(1001., 3650.), max_relative = 1e-10);
+    approx::assert_abs_diff_eq!(forecast(1000.), forecast(1000.5), epsilon = 1.);
+    approx::assert_abs_diff_ne!(forecast(1000.), forecast(1002.), epsilon = 1.);
+    approx::assert_ulps_eq!(segment, segment.clone());
+}
+
+#[cfg(feature = "float-cmp")]
+#[test]
+fn float_cmp_traits() {
+    use float_cmp::{ApproxEq, F64Margin};
+
+    let hyperbolic = |initial_rate, days| -> Segment<AverageDaysTime> {
+        HyperbolicParameters::from_incremental_duration(
+            ProductionRate::new(initial_rate),
+            NominalDeclineRate::new(0.002),
+            AverageDaysTime { days },
+            0.9,
+        )
+        .unwrap()
+        .into()
+    };
+    let segment = hyperbolic(1000., 3650.);
+    let margin = F64Margin {
+        epsilon: 1e-6,
+        ulps: 2,
+    };
+
+    // The inherent `approx_eq` methods take precedence over the trait method.
+    assert!(ApproxEq::approx_eq(
+        &segment,
+        &hyperbolic(1000. + 1e-7, 3650.),
+        margin
+    ));
+    assert!(!ApproxEq::approx_eq(
+        &segment,
+        &hyperbolic(1000. + 1e-5, 3650.),
+        margin
+    ));
+    let forecast = Forecast::new(vec![
+        FlatParameters::from_incremental_duration(
+            ProductionRate::<AverageDaysTime>::new(1000.),
+            AverageDaysTime { days: 30. },
+        )
+        .unwrap()
+        .into(),
+        segment,
+    ]);
+    assert!(ApproxEq::approx_eq(
+        &forecast,
+        &forecast.clone(),
+        F64Margin::default()
+    ));
+}
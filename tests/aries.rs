@@ -0,0 +1,78 @@
+use decline_curve_analysis::{
+    AverageDaysTime, AverageYearsTime, Phase, Segment,
+    interop::aries::{AriesRateUnit, format_forecast, parse_forecasts},
+};
+
+const DECK: &str = "
+START 1/2024
+OIL 0 X B/D 3 MOS FLAT 0
+OIL 1000 X B/D 2 YRS B/1.2 70
+OIL X 50 B/D X YRS EXP 8
+GAS 3000 X M/M 30 YRS HAR 40
+";
+
+#[test]
+#[cfg_attr(feature = "fast-math", ignore = "exact values differ with fast-math")]
+fn parse_deck() {
+    let forecasts = parse_forecasts::<AverageDaysTime>(DECK).unwrap();
+    assert_eq!(
+        forecasts
+            .iter()
+            .map(|(phase, _)| *phase)
+            .collect::<Vec<_>>(),
+        [Phase::Oil, Phase::Gas]
+    );
+
+    let oil = &forecasts[0].1;
+    assert!(matches!(oil.segments()[0], Segment::Delay(_)));
+    insta::assert_snapshot!(oil.segments()[0].incremental_duration().days, @"91.3125");
+    insta::assert_snapshot!(oil.segments()[1].rate_at_time(AverageDaysTime { days: 0. }).value(), @"1000");
+    // The exponential continues from the end of the hyperbolic.
+    insta::assert_snapshot!(oil.segments()[2].rate_at_time(AverageDaysTime { days: 0. }).value(), @"186.92394141303154");
+    insta::assert_snapshot!(oil.final_rate().value(), @"50.00000000000001");
+    // A secant effective decline of 70% per year for the first year.
+    insta::assert_snapshot!(
+        oil.segments()[1].rate_at_time(AverageDaysTime { days: 365.25 }).value(),
+        @"300"
+    );
+
+    let gas = &forecasts[1].1;
+    // 3000 mcf per month in mcf per day.
+    insta::assert_snapshot!(gas.rate_at_time(AverageDaysTime { days: 0. }).value(), @"98.56262833675565");
+}
+
+#[test]
+#[cfg_attr(feature = "fast-math", ignore = "exact values differ with fast-math")]
+fn round_trip() {
+    let forecasts = parse_forecasts::<AverageYearsTime>(DECK).unwrap();
+    let (phase, oil) = &forecasts[0];
+
+    let formatted = format_forecast(*phase, oil, AriesRateUnit::PerDay).unwrap();
+    insta::assert_snapshot!(formatted, @"
+    OIL 0 X B/D 0.25 YRS FLAT 0
+    OIL 1000 X B/D 2 YRS B/1.2 70
+    OIL 186.92394141303154 X B/D 15.814983844237188 YRS EXP 7.9999999999999964
+    ");
+
+    let parsed = parse_forecasts::<AverageYearsTime>(&formatted).unwrap();
+    let reparsed = &parsed[0].1;
+    for years in [0.5, 1., 2.5, 10.] {
+        let time = AverageYearsTime { years };
+        let relative_error =
+            (reparsed.rate_at_time(time).value() / oil.rate_at_time(time).value() - 1.).abs();
+        assert!(relative_error < 1e-12, "{relative_error}");
+    }
+    assert!(
+        (reparsed.incremental_duration().years - oil.incremental_duration().years).abs() < 1e-9
+    );
+}
+
+#[test]
+fn invalid_lines() {
+    insta::assert_snapshot!(parse_forecasts::<AverageDaysTime>("OIL 1000 X B/D 2 YRS").unwrap_err(), @"line 1: expected 8 values, but got 6");
+    insta::assert_snapshot!(parse_forecasts::<AverageDaysTime>("OIL X X B/D 2 YRS EXP 8").unwrap_err(), @"line 1: initial rate is `X`, but there's no previous line");
+    insta::assert_snapshot!(parse_forecasts::<AverageDaysTime>("OIL 1000 X B/D X YRS EXP 8").unwrap_err(), @"line 1: expected exactly one of the final rate and life to be `X`");
+    insta::assert_snapshot!(parse_forecasts::<AverageDaysTime>("\nGAS 1000 X B/Y 2 YRS EXP 8").unwrap_err(), @"line 2: unsupported rate units `B/Y`");
+    insta::assert_snapshot!(parse_forecasts::<AverageDaysTime>("WTR 1000 X B/D 2 YRS LIN 8").unwrap_err(), @"line 1: unsupported method `LIN`");
+    insta::assert_snapshot!(parse_forecasts::<AverageDaysTime>("OIL 1000 X B/D 2 YRS EXP 100").unwrap_err(), @"line 1: decline rate too high");
+}
@@ -0,0 +1,116 @@
+use decline_curve_analysis::{
+    AutoForecastOptions, AverageDaysTime, CalendarDate, DeclineTimeUnit, HistoryValue,
+    HyperbolicParameters, NominalDeclineRate, ProductionHistory, ProductionRate, ProductionRecord,
+    TerminalDeclinePolicy, auto_forecast,
+};
+
+/// Three years of monthly volumes with two months of ramp-up, then a hyperbolic decline with a
+/// repeating pattern of noise and a shut-in month.
+fn history() -> ProductionHistory {
+    let decline = HyperbolicParameters::from_incremental_duration(
+        ProductionRate::<AverageDaysTime>::new(900.),
+        NominalDeclineRate::new(0.003),
+        AverageDaysTime { days: 3000. },
+        0.9,
+    )
+    .unwrap();
+
+    let records = (0..36_i32)
+        .map(|month| {
+            let date = CalendarDate::new(2022 + month / 12, (month % 12 + 1) as u32, 1).unwrap();
+            let rate = match month {
+                0 => 300.,
+                1 => 650.,
+                14 => 0.,
+                _ => {
+                    let noise = [1.02, 0.99, 0.97, 1.01, 1.03, 0.98][month as usize % 6];
+                    let time = AverageDaysTime {
+                        days: (month - 2) as f64 * 30.4375,
+                    };
+                    decline.rate_at_time(time).value() * noise
+                }
+            };
+            ProductionRecord {
+                date,
+                value: rate * 30.4375,
+            }
+        })
+        .collect();
+
+    ProductionHistory::new(HistoryValue::MonthlyVolume, records).unwrap()
+}
+
+#[test]
+fn auto_forecast_hyperbolic_history() {
+    let options = AutoForecastOptions {
+        terminal_decline: TerminalDeclinePolicy::Nominal(NominalDeclineRate::new(0.0002)),
+        ..AutoForecastOptions::new(ProductionRate::<AverageDaysTime>::new(20.))
+    };
+    let result = auto_forecast(&history(), &options).unwrap();
+    let report = &result.report;
+
+    insta::assert_snapshot!(report.sample_count, @"35");
+    insta::assert_debug_snapshot!(report.changepoints, @r"
+    [
+        6,
+    ]
+    ");
+    insta::assert_snapshot!(report.window_start_index, @"6");
+    insta::assert_snapshot!(report.start.value(), @"196.5");
+    insta::assert_debug_snapshot!(report.fit.ranking[0].model, @"Harmonic");
+    insta::assert_debug_snapshot!(report.binding_constraint, @"EconomicLimit");
+    insta::assert_snapshot!(report.validation.is_clean(), @"true");
+
+    // The forecast switches to the terminal decline, then ends at the economic limit.
+    let forecast = &result.forecast;
+    insta::assert_snapshot!(forecast.segments().len(), @"2");
+    insta::assert_snapshot!(forecast.final_rate().value() as f32, @"20");
+    insta::assert_snapshot!(forecast.incremental_volume() as f32, @"822848.06");
+}
+
+#[test]
+fn auto_forecast_skips_ramp_up() {
+    let options = AutoForecastOptions {
+        segmentation: None,
+        ..AutoForecastOptions::new(ProductionRate::<AverageDaysTime>::new(20.))
+    };
+    let report = auto_forecast(&history(), &options).unwrap().report;
+
+    insta::assert_snapshot!(report.window_start_index, @"2");
+    insta::assert_snapshot!(report.start.value(), @"74.5");
+}
+
+#[test]
+fn auto_forecast_validation() {
+    let empty = ProductionHistory::new(HistoryValue::MonthlyVolume, Vec::new()).unwrap();
+    insta::assert_debug_snapshot!(
+        auto_forecast(
+            &empty,
+            &AutoForecastOptions::new(ProductionRate::<AverageDaysTime>::new(20.))
+        ),
+        @r#"
+    Err(
+        TooFewInputs {
+            field: "production record count",
+            count: 0,
+            minimum: 1,
+        },
+    )
+    "#
+    );
+
+    insta::assert_debug_snapshot!(
+        auto_forecast(
+            &history(),
+            &AutoForecastOptions::new(ProductionRate::<AverageDaysTime>::new(0.))
+        ),
+        @r#"
+    Err(
+        NonPositiveInput {
+            field: "economic limit rate",
+            value: 0.0,
+        },
+    )
+    "#
+    );
+}
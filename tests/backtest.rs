@@ -0,0 +1,127 @@
+use decline_curve_analysis::{
+    AutoForecastOptions, AverageDaysTime, CalendarDate, ExponentialParameters, HistoryValue,
+    NominalDeclineRate, ProductionHistory, ProductionRate, ProductionRecord, backtest,
+};
+
+/// Three years of monthly volumes from an exponential decline, with a repeating pattern of noise.
+fn history() -> ProductionHistory {
+    let decline = ExponentialParameters::from_incremental_duration(
+        ProductionRate::<AverageDaysTime>::new(600.),
+        NominalDeclineRate::new(0.002),
+        AverageDaysTime { days: 2000. },
+    )
+    .unwrap();
+
+    let records = (0..36_i32)
+        .map(|month| {
+            let date = CalendarDate::new(2022 + month / 12, (month % 12 + 1) as u32, 1).unwrap();
+            let noise = [1.02, 0.99, 0.97, 1.01, 1.03, 0.98][month as usize % 6];
+            let time = AverageDaysTime {
+                days: (month as f64 + 0.5) * 30.4375,
+            };
+            ProductionRecord {
+                date,
+                value: decline.rate_at_time(time).value() * noise * 30.4375,
+            }
+        })
+        .collect();
+
+    ProductionHistory::new(HistoryValue::MonthlyVolume, records).unwrap()
+}
+
+#[test]
+fn backtest_exponential_history() {
+    let options = AutoForecastOptions {
+        segmentation: None,
+        ..AutoForecastOptions::new(ProductionRate::<AverageDaysTime>::new(5.))
+    };
+    let result = backtest(&history(), 12, &options).unwrap();
+
+    insta::assert_debug_snapshot!(result.holdout_start, @r"
+    CalendarDate {
+        year: 2024,
+        month: 1,
+        day: 1,
+    }
+    ");
+    insta::assert_snapshot!(result.holdout.len(), @"12");
+    insta::assert_debug_snapshot!(result.fit.report.fit.ranking[0].model, @"Exponential");
+
+    let metrics = result.metrics;
+    insta::assert_snapshot!(metrics.mean_absolute_percentage_error as f32, @"2.6513987");
+    insta::assert_snapshot!(metrics.bias as f32, @"-1.8165456");
+    insta::assert_snapshot!(metrics.actual_volume as f32, @"36078.81");
+    insta::assert_snapshot!(metrics.forecast_volume as f32, @"35406.74");
+    insta::assert_snapshot!(metrics.cumulative_error as f32, @"-1.8627814");
+}
+
+#[test]
+fn backtest_validation() {
+    let options = AutoForecastOptions::new(ProductionRate::<AverageDaysTime>::new(5.));
+
+    insta::assert_debug_snapshot!(backtest(&history(), 0, &options), @r#"
+    Err(
+        TooFewInputs {
+            field: "holdout months",
+            count: 0,
+            minimum: 1,
+        },
+    )
+    "#);
+    insta::assert_debug_snapshot!(backtest(&history(), 1 << 31, &options), @r#"
+    Err(
+        InputOutOfRange {
+            field: "holdout months",
+            value: 2147483648.0,
+            minimum: Included(
+                1.0,
+            ),
+            maximum: Included(
+                2147483647.0,
+            ),
+        },
+    )
+    "#);
+    insta::assert_debug_snapshot!(backtest(&history(), u32::MAX, &options), @r#"
+    Err(
+        InputOutOfRange {
+            field: "holdout months",
+            value: 4294967295.0,
+            minimum: Included(
+                1.0,
+            ),
+            maximum: Included(
+                2147483647.0,
+            ),
+        },
+    )
+    "#);
+    insta::assert_debug_snapshot!(backtest(&history(), 36, &options), @r#"
+    Err(
+        TooFewInputs {
+            field: "production record count",
+            count: 0,
+            minimum: 1,
+        },
+    )
+    "#);
+}
+
+#[test]
+fn backtest_past_the_end_of_the_forecast() {
+    // The forecast reaches the economic limit before the holdout starts.
+    let options = AutoForecastOptions {
+        segmentation: None,
+        ..AutoForecastOptions::new(ProductionRate::<AverageDaysTime>::new(300.))
+    };
+    let result = backtest(&history(), 12, &options).unwrap();
+
+    assert!(
+        result
+            .holdout
+            .iter()
+            .all(|sample| sample.forecast_rate.value() == 0.)
+    );
+    insta::assert_snapshot!(result.metrics.forecast_volume, @"0");
+    insta::assert_snapshot!(result.metrics.mean_absolute_percentage_error, @"100");
+}
@@ -0,0 +1,110 @@
+use decline_curve_analysis::{
+    AverageYearsTime, DelayParameters, ExponentialParameters, FlatParameters, HarmonicParameters,
+    HyperbolicParameters, LinearParameters, LogisticGrowthParameters, NominalDeclineRate,
+    PowerLawExponentialParameters, ProductionRate, Segment,
+};
+
+fn segments() -> Vec<Segment<AverageYearsTime>> {
+    let rate = ProductionRate::new(1000.);
+    let decline_rate = NominalDeclineRate::new(0.3);
+    let duration = AverageYearsTime { years: 10. };
+
+    vec![
+        DelayParameters::from_incremental_duration(duration)
+            .unwrap()
+            .into(),
+        ExponentialParameters::from_incremental_duration(rate, decline_rate, duration)
+            .unwrap()
+            .into(),
+        FlatParameters::from_incremental_duration(rate, duration)
+            .unwrap()
+            .into(),
+        HarmonicParameters::from_incremental_duration(rate, decline_rate, duration)
+            .unwrap()
+            .into(),
+        HyperbolicParameters::from_incremental_duration(rate, decline_rate, duration, 0.5)
+            .unwrap()
+            .into(),
+        LinearParameters::from_incremental_duration(rate, NominalDeclineRate::new(0.05), duration)
+            .unwrap()
+            .into(),
+        LogisticGrowthParameters::from_incremental_duration(10_000., 5., duration, 1.5)
+            .unwrap()
+            .into(),
+        PowerLawExponentialParameters::from_incremental_duration(
+            rate,
+            0.3,
+            NominalDeclineRate::new(0.01),
+            duration,
+            0.5,
+        )
+        .unwrap()
+        .into(),
+    ]
+}
+
+#[test]
+fn batch_matches_single_evaluation() {
+    // Include times past the end to check clamping.
+    let times: Vec<_> = (0..=60)
+        .map(|index| AverageYearsTime {
+            years: index as f64 * 0.25,
+        })
+        .collect();
+    let mut rates = vec![0.; times.len()];
+    let mut volumes = vec![0.; times.len()];
+
+    for segment in segments() {
+        segment.rates_at_times(&times, &mut rates);
+        segment.incremental_volumes_at_times(&times, &mut volumes);
+
+        for ((time, rate), volume) in times.iter().zip(&rates).zip(&volumes) {
+            assert_eq!(*rate, segment.rate_at_time(*time).value(), "{segment:?}");
+            assert_eq!(
+                *volume,
+                segment.incremental_volume_at_time(*time),
+                "{segment:?}"
+            );
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "expected the times and output to have the same length")]
+fn batch_length_mismatch() {
+    let segment = &segments()[1];
+    segment.rates_at_times(&[AverageYearsTime { years: 1. }], &mut [0.; 2]);
+}
+
+#[test]
+fn f32_batch_matches_f64_batch() {
+    let times: Vec<_> = (1..=60).map(|index| index as f32 * 0.25).collect();
+    let f64_times: Vec<_> = times
+        .iter()
+        .map(|years| AverageYearsTime {
+            years: f64::from(*years),
+        })
+        .collect();
+    let mut rates = vec![0.; times.len()];
+    let mut volumes = vec![0.; times.len()];
+    let mut expected_rates = vec![0.; times.len()];
+    let mut expected_volumes = vec![0.; times.len()];
+
+    for segment in segments() {
+        segment.rates_at_times_f32(&times, &mut rates);
+        segment.incremental_volumes_at_times_f32(&times, &mut volumes);
+        segment.rates_at_times(&f64_times, &mut expected_rates);
+        segment.incremental_volumes_at_times(&f64_times, &mut expected_volumes);
+
+        for (actual, expected) in rates
+            .iter()
+            .zip(&expected_rates)
+            .chain(volumes.iter().zip(&expected_volumes))
+        {
+            assert!(
+                (f64::from(*actual) - expected).abs() <= 1e-5 * expected.abs(),
+                "{segment:?}: expected {expected}, got {actual}"
+            );
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use decline_curve_analysis::{
+    AverageDaysTime, BootstrapOptions, FitModel, FitSpace, HyperbolicParameters,
+    NominalDeclineRate, ProductionRate, SeededRng, bootstrap_eur, bootstrap_eur_with_rng,
+};
+
+/// Samples a hyperbolic decline monthly for three years with deterministic multiplicative noise.
+fn noisy_samples() -> Vec<(AverageDaysTime, ProductionRate<AverageDaysTime>)> {
+    let decline = HyperbolicParameters::from_incremental_duration(
+        ProductionRate::new(500.),
+        NominalDeclineRate::new(0.004),
+        AverageDaysTime { days: 1500. },
+        0.8,
+    )
+    .unwrap();
+
+    (0..36)
+        .map(|month| {
+            let time = AverageDaysTime {
+                days: month as f64 * 30.4375,
+            };
+            let noise = 1. + 0.1 * (month as f64 * 2.3).sin();
+            (
+                time,
+                ProductionRate::new(decline.rate_at_time(time).value() * noise),
+            )
+        })
+        .collect()
+}
+
+fn options() -> BootstrapOptions<AverageDaysTime> {
+    BootstrapOptions {
+        resamples: 200,
+        ..BootstrapOptions::new(FitModel::Hyperbolic, ProductionRate::new(5.))
+    }
+}
+
+#[test]
+fn bootstrap_brackets_fitted_eur() {
+    let result = bootstrap_eur(&noisy_samples(), &options()).unwrap();
+
+    assert_eq!(result.realization_eurs.len() + result.failed_resamples, 200);
+    let eur = result.estimated_ultimate_recovery;
+    assert!(eur.p90 < result.fitted_eur && result.fitted_eur < eur.p10);
+    assert!(eur.p90 < eur.p50 && eur.p50 < eur.p10);
+    // Noise of 10% shouldn't move the EUR much.
+    assert!((eur.p50 / result.fitted_eur - 1.).abs() < 0.05);
+
+    assert_eq!(result, bootstrap_eur(&noisy_samples(), &options()).unwrap());
+    assert_eq!(
+        result,
+        bootstrap_eur_with_rng(&noisy_samples(), &options(), &mut SeededRng::new(0)).unwrap()
+    );
+}
+
+#[test]
+fn bootstrap_in_log_space() {
+    let mut options = options();
+    options.model = FitModel::Exponential;
+    options.fit.space = FitSpace::LogRate;
+    let result = bootstrap_eur(&noisy_samples(), &options).unwrap();
+
+    let eur = result.estimated_ultimate_recovery;
+    assert!(eur.p90 < result.fitted_eur && result.fitted_eur < eur.p10);
+    assert_eq!(result.failed_resamples, 0);
+}
+
+#[test]
+fn exact_samples_have_no_spread() {
+    let decline = HyperbolicParameters::from_incremental_duration(
+        ProductionRate::new(500.),
+        NominalDeclineRate::new(0.004),
+        AverageDaysTime { days: 1500. },
+        0.8,
+    )
+    .unwrap();
+    let samples: Vec<_> = (0..36)
+        .map(|month| {
+            let time = AverageDaysTime {
+                days: month as f64 * 30.4375,
+            };
+            (time, decline.rate_at_time(time))
+        })
+        .collect();
+
+    let mut options = options();
+    options.resamples = 10;
+    let result = bootstrap_eur(&samples, &options).unwrap();
+    let eur = result.estimated_ultimate_recovery;
+    assert!((eur.p10 - eur.p90).abs() < 1e-6 * result.fitted_eur);
+}
+
+#[test]
+fn invalid_bootstrap_options() {
+    let mut options = options();
+    options.resamples = 0;
+    let result = bootstrap_eur(&noisy_samples(), &options);
+    insta::assert_snapshot!(result.unwrap_err(), @"resamples is 0, but expected at least 1");
+
+    let mut options = self::options();
+    options.economic_limit_rate = ProductionRate::new(0.);
+    let result = bootstrap_eur(&noisy_samples(), &options);
+    insta::assert_snapshot!(result.unwrap_err(), @"economic limit rate is negative or zero, but expected a positive number");
+}
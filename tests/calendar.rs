@@ -0,0 +1,118 @@
+use decline_curve_analysis::{
+    AverageDaysTime, AverageMonthsTime, AverageYearsTime, CalendarDate, CalendarTime,
+    NominalDeclineRate, ProductionRate, days_in_month, is_leap_year,
+};
+
+#[test]
+fn leap_years() {
+    assert!(is_leap_year(2024));
+    assert!(!is_leap_year(2023));
+    assert!(!is_leap_year(1900));
+    assert!(is_leap_year(2000));
+
+    assert_eq!(days_in_month(2024, 2), 29);
+    assert_eq!(days_in_month(2023, 2), 28);
+    assert_eq!(days_in_month(2023, 4), 30);
+    assert_eq!(days_in_month(2023, 12), 31);
+}
+
+#[test]
+fn invalid_dates() {
+    let result = CalendarDate::new(2023, 13, 1);
+    insta::assert_snapshot!(result.unwrap_err(), @"month is 13, but expected it to be between 1 and 12");
+
+    let result = CalendarDate::new(2023, 2, 29);
+    insta::assert_snapshot!(result.unwrap_err(), @"day is 29, but expected it to be between 1 and 28");
+}
+
+#[test]
+fn days_since_epoch_round_trip() {
+    let epoch = CalendarDate::new(1970, 1, 1).unwrap();
+    assert_eq!(epoch.days_since_epoch(), 0);
+
+    let date = CalendarDate::new(2024, 2, 29).unwrap();
+    insta::assert_snapshot!(date.days_since_epoch(), @"19782");
+    assert_eq!(CalendarDate::from_days_since_epoch(19782).unwrap(), date);
+
+    let date = CalendarDate::new(1969, 12, 31).unwrap();
+    assert_eq!(date.days_since_epoch(), -1);
+    assert_eq!(CalendarDate::from_days_since_epoch(-1).unwrap(), date);
+}
+
+#[test]
+fn add_months_clamps_day() {
+    let date = CalendarDate::new(2023, 1, 31).unwrap();
+    assert_eq!(
+        date.add_months(1).unwrap(),
+        CalendarDate::new(2023, 2, 28).unwrap()
+    );
+    assert_eq!(
+        date.add_months(13).unwrap(),
+        CalendarDate::new(2024, 2, 29).unwrap()
+    );
+    assert_eq!(
+        date.add_months(-2).unwrap(),
+        CalendarDate::new(2022, 11, 30).unwrap()
+    );
+}
+
+#[test]
+fn add_months_out_of_range() {
+    let date = CalendarDate::new(i32::MAX, 12, 1).unwrap();
+    insta::assert_snapshot!(date.add_months(1).unwrap_err(), @"months is 1, which moves the date out of range");
+    assert!(
+        CalendarDate::new(2023, 1, 1)
+            .unwrap()
+            .add_months(i32::MIN)
+            .is_ok()
+    );
+}
+
+#[test]
+fn add_days_out_of_range() {
+    let date = CalendarDate::new(2023, 1, 1).unwrap();
+    assert_eq!(
+        date.add_days(365).unwrap(),
+        CalendarDate::new(2024, 1, 1).unwrap()
+    );
+    insta::assert_snapshot!(date.add_days(i64::MAX).unwrap_err(), @"days is 9223372036854775807, which moves the date out of range");
+    insta::assert_snapshot!(date.add_days(1 << 40).unwrap_err(), @"days since epoch is 1099511647134, which moves the date out of range");
+    insta::assert_snapshot!(CalendarDate::from_days_since_epoch(i64::MIN).unwrap_err(), @"days since epoch is -9223372036854775808, which moves the date out of range");
+}
+
+#[test]
+fn between_dates() {
+    let start = CalendarDate::new(2023, 1, 1).unwrap();
+    let end = CalendarDate::new(2024, 1, 1).unwrap();
+    assert_eq!(CalendarTime::between(start, end).days, 365.);
+    assert_eq!(CalendarTime::between(end, start).days, -365.);
+}
+
+#[test]
+fn month_boundaries() {
+    let start = CalendarDate::new(2024, 1, 15).unwrap();
+    let boundaries: Vec<f64> = CalendarTime::month_boundaries(start)
+        .take(4)
+        .map(|time| time.days)
+        .collect();
+
+    // The first month is partial.
+    assert_eq!(boundaries, [17., 46., 77., 107.]);
+}
+
+#[test]
+fn average_months_conversions() {
+    let rate = ProductionRate::<AverageMonthsTime>::new(3043.75);
+    let daily: ProductionRate<AverageDaysTime> = rate.into();
+    insta::assert_snapshot!(daily.value(), @"100");
+
+    let yearly: ProductionRate<AverageYearsTime> = rate.into();
+    insta::assert_snapshot!(yearly.value(), @"36525");
+
+    let decline = NominalDeclineRate::<AverageYearsTime>::new(1.2);
+    let monthly: NominalDeclineRate<AverageMonthsTime> = decline.into();
+    insta::assert_snapshot!(monthly.value(), @"0.09999999999999999");
+
+    let calendar: CalendarTime = AverageDaysTime { days: 12. }.into();
+    assert_eq!(calendar.days, 12.);
+}
@@ -0,0 +1,111 @@
+#![cfg(feature = "arrow")]
+
+use arrow::array::{Array, Date32Array, Float64Array, StringArray};
+use decline_curve_analysis::{
+    AverageDaysTime, CalendarDate, FlatParameters, Forecast, Phase, PhaseForecast, ProductionRate,
+    RatioForecast, RatioSegment, RatioTrend, ScheduleWell, WellForecast,
+    monthly_schedule_record_batch, write_monthly_schedule_parquet,
+};
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+fn well() -> WellForecast<AverageDaysTime> {
+    let oil = Forecast::new(vec![
+        FlatParameters::from_incremental_duration(
+            ProductionRate::new(100.),
+            AverageDaysTime { days: 60. },
+        )
+        .unwrap()
+        .into(),
+    ]);
+    let gas = RatioForecast::new(vec![
+        RatioSegment::from_incremental_duration(
+            2.,
+            RatioTrend::Constant,
+            AverageDaysTime { days: 60. },
+        )
+        .unwrap(),
+    ]);
+
+    WellForecast::new(
+        Phase::Oil,
+        oil,
+        vec![(Phase::Gas, PhaseForecast::Ratio(gas))],
+    )
+    .unwrap()
+}
+
+#[test]
+fn monthly_schedule() {
+    let well = well();
+    let wells = [ScheduleWell {
+        name: "A-1",
+        start_date: CalendarDate::new(2024, 1, 16).unwrap(),
+        forecast: &well,
+    }];
+
+    let batch = monthly_schedule_record_batch(&wells).unwrap();
+    let column = |name: &str| batch.column_by_name(name).unwrap().clone();
+
+    let wells = column("well");
+    let phases = column("phase");
+    let dates = column("date");
+    let rates = column("rate");
+    let volumes = column("volume");
+    let wells = wells.as_any().downcast_ref::<StringArray>().unwrap();
+    let phases = phases.as_any().downcast_ref::<StringArray>().unwrap();
+    let dates = dates.as_any().downcast_ref::<Date32Array>().unwrap();
+    let rates = rates.as_any().downcast_ref::<Float64Array>().unwrap();
+    let volumes = volumes.as_any().downcast_ref::<Float64Array>().unwrap();
+
+    let rows = (0..batch.num_rows())
+        .map(|row| {
+            format!(
+                "{} {} {} {} {}",
+                wells.value(row),
+                phases.value(row),
+                dates.value_as_date(row).unwrap(),
+                rates.value(row),
+                volumes.value(row),
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    insta::assert_snapshot!(rows, @"
+    A-1 oil 2024-01-16 100 1600
+    A-1 oil 2024-02-01 100 2900
+    A-1 oil 2024-03-01 100 1500
+    A-1 gas 2024-01-16 200 3200
+    A-1 gas 2024-02-01 200 5800
+    A-1 gas 2024-03-01 200 3000
+    ");
+}
+
+#[test]
+fn parquet_round_trip() {
+    let well = well();
+    let wells = [
+        ScheduleWell {
+            name: "A-1",
+            start_date: CalendarDate::new(2024, 1, 1).unwrap(),
+            forecast: &well,
+        },
+        ScheduleWell {
+            name: "A-2",
+            start_date: CalendarDate::new(2024, 3, 1).unwrap(),
+            forecast: &well,
+        },
+    ];
+    let batch = monthly_schedule_record_batch(&wells).unwrap();
+
+    let path = std::env::temp_dir().join("decline-curve-analysis-schedule.parquet");
+    write_monthly_schedule_parquet(&wells, std::fs::File::create(&path).unwrap()).unwrap();
+
+    let reader = ParquetRecordBatchReaderBuilder::try_new(std::fs::File::open(&path).unwrap())
+        .unwrap()
+        .build()
+        .unwrap();
+    let batches = reader.collect::<Result<Vec<_>, _>>().unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(batches, [batch]);
+}
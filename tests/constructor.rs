@@ -0,0 +1,281 @@
+use decline_curve_analysis::{
+    AverageYearsTime, DeclineCurveAnalysisError, DelayParameters, ExponentialParameters,
+    FlatParameters, HarmonicParameters, HyperbolicParameters, LinearParameters,
+    LogisticGrowthParameters, NominalDeclineRate, PowerLawExponentialParameters, ProductionRate,
+    Segment, SegmentConstructor, SegmentEnd, SegmentEndKind,
+};
+
+/// Constructs a segment of any type from each kind of end.
+fn from_each_end<Parameters>(
+    shape: Parameters::Shape,
+) -> Vec<Result<Segment<AverageYearsTime>, DeclineCurveAnalysisError>>
+where
+    Parameters: SegmentConstructor<AverageYearsTime> + Into<Segment<AverageYearsTime>>,
+    Parameters::Shape: Clone,
+{
+    [
+        SegmentEnd::IncrementalDuration(AverageYearsTime { years: 5. }),
+        SegmentEnd::IncrementalVolume(1000.),
+        SegmentEnd::FinalRate(ProductionRate::new(100.)),
+        SegmentEnd::FinalDeclineRate(NominalDeclineRate::new(0.2)),
+    ]
+    .into_iter()
+    .map(|end| Parameters::from_end(shape.clone(), end).map(Into::into))
+    .collect()
+}
+
+fn describe(results: Vec<Result<Segment<AverageYearsTime>, DeclineCurveAnalysisError>>) -> String {
+    results
+        .into_iter()
+        .map(|result| match result {
+            Ok(segment) => segment.to_string(),
+            Err(error) => error.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn construct_from_each_end() {
+    let initial_rate = ProductionRate::new(1000.);
+    let decline_rate = NominalDeclineRate::new(0.5);
+
+    insta::assert_snapshot!(describe(from_each_end::<DelayParameters<_>>(())), @r"
+    Delay: t=1826 d
+    delay segments can't be constructed from an incremental volume
+    delay segments can't be constructed from a final rate
+    delay segments can't be constructed from a final decline rate
+    ");
+    insta::assert_snapshot!(describe(from_each_end::<FlatParameters<_>>(initial_rate)), @r"
+    Flat: q=2.738/d, t=1826 d
+    Flat: q=2.738/d, t=365.2 d
+    flat segments can't be constructed from a final rate
+    flat segments can't be constructed from a final decline rate
+    ");
+    insta::assert_snapshot!(
+        describe(from_each_end::<ExponentialParameters<_>>((initial_rate, decline_rate))),
+        @r"
+    Exponential: qi=2.738/d, D=50%/yr nominal, t=1826 d, qf=0.2247/d
+    Exponential: qi=2.738/d, D=50%/yr nominal, t=506.3 d, qf=1.369/d
+    Exponential: qi=2.738/d, D=50%/yr nominal, t=1682 d, qf=0.2738/d
+    cannot solve decline: no finite solution exists for the given parameters
+    "
+    );
+    insta::assert_snapshot!(
+        describe(from_each_end::<HarmonicParameters<_>>((initial_rate, decline_rate))),
+        @r"
+    Harmonic: qi=2.738/d, Di=50%/yr nominal, t=1826 d, qf=0.7822/d
+    Harmonic: qi=2.738/d, Di=50%/yr nominal, t=473.9 d, qf=1.661/d
+    Harmonic: qi=2.738/d, Di=50%/yr nominal, t=6574 d, qf=0.2738/d
+    Harmonic: qi=2.738/d, Di=50%/yr nominal, t=1096 d, qf=1.095/d
+    "
+    );
+    insta::assert_snapshot!(
+        describe(from_each_end::<HyperbolicParameters<_>>((initial_rate, decline_rate, 0.5))),
+        @r"
+    Hyperbolic: qi=2.738/d, Di=50%/yr nominal, b=0.5, t=1826 d, qf=0.5408/d
+    Hyperbolic: qi=2.738/d, Di=50%/yr nominal, b=0.5, t=487 d, qf=1.54/d
+    Hyperbolic: qi=2.738/d, Di=50%/yr nominal, b=0.5, t=3159 d, qf=0.2738/d
+    Hyperbolic: qi=2.738/d, Di=50%/yr nominal, b=0.5, t=2192 d, qf=0.4381/d
+    "
+    );
+    insta::assert_snapshot!(
+        describe(from_each_end::<LinearParameters<_>>((
+            initial_rate,
+            NominalDeclineRate::new(0.1)
+        ))),
+        @r"
+    Linear: qi=2.738/d, D=10%/yr, t=1826 d, qf=1.369/d
+    Linear: qi=2.738/d, D=10%/yr, t=385.6 d, qf=2.449/d
+    Linear: qi=2.738/d, D=10%/yr, t=3287 d, qf=0.2738/d
+    Linear: qi=2.738/d, D=10%/yr, t=1826 d, qf=1.369/d
+    "
+    );
+    insta::assert_snapshot!(
+        describe(from_each_end::<LogisticGrowthParameters<_>>((100_000., 5., 1.5))),
+        @r"
+    Logistic growth: K=100000, a=5, n=1.5, t=1826 d, qf=17.54/d
+    Logistic growth: K=100000, a=5, n=1.5, t=49.91 d, qf=29.76/d
+    Logistic growth: K=100000, a=5, n=1.5, t=12713 d, qf=0.2738/d
+    logistic growth segments can't be constructed from a final decline rate
+    "
+    );
+    insta::assert_snapshot!(
+        describe(from_each_end::<PowerLawExponentialParameters<_>>((
+            initial_rate,
+            0.5,
+            NominalDeclineRate::new(0.05),
+            0.5
+        ))),
+        @r"
+    Power law exponential: qi=2.738/d, Di=0.5, Dinf=5%/yr nominal, n=0.5, t=1826 d, qf=0.6971/d
+    Power law exponential: qi=2.738/d, Di=0.5, Dinf=5%/yr nominal, n=0.5, t=566.8 d, qf=1.359/d
+    Power law exponential: qi=2.738/d, Di=0.5, Dinf=5%/yr nominal, n=0.5, t=4295 d, qf=0.2738/d
+    Power law exponential: qi=2.738/d, Di=0.5, Dinf=5%/yr nominal, n=0.5, t=1015 d, qf=1.036/d
+    "
+    );
+}
+
+#[test]
+fn constructor_matches_inherent_constructor() {
+    let initial_rate = ProductionRate::<AverageYearsTime>::new(1000.);
+    let decline_rate = NominalDeclineRate::new(0.5);
+    let final_decline_rate = NominalDeclineRate::new(0.2);
+
+    assert_eq!(
+        HyperbolicParameters::from_end(
+            (initial_rate, decline_rate, 0.5),
+            SegmentEnd::FinalDeclineRate(final_decline_rate),
+        ),
+        HyperbolicParameters::from_final_decline_rate(
+            initial_rate,
+            decline_rate,
+            final_decline_rate,
+            0.5
+        ),
+    );
+}
+
+#[test]
+fn supported_solves() {
+    let segments: Vec<Segment<AverageYearsTime>> = vec![
+        DelayParameters::from_incremental_duration(AverageYearsTime { years: 1. })
+            .unwrap()
+            .into(),
+        FlatParameters::from_incremental_duration(
+            ProductionRate::new(100.),
+            AverageYearsTime { years: 1. },
+        )
+        .unwrap()
+        .into(),
+        LinearParameters::from_incremental_duration(
+            ProductionRate::new(100.),
+            NominalDeclineRate::new(0.1),
+            AverageYearsTime { years: 1. },
+        )
+        .unwrap()
+        .into(),
+    ];
+
+    insta::assert_debug_snapshot!(
+        segments.iter().map(Segment::supported_solves).collect::<Vec<_>>(),
+        @r"
+    [
+        [
+            IncrementalDuration,
+        ],
+        [
+            IncrementalDuration,
+            IncrementalVolume,
+        ],
+        [
+            IncrementalDuration,
+            IncrementalVolume,
+            FinalRate,
+            FinalDeclineRate,
+        ],
+    ]
+    "
+    );
+    assert_eq!(
+        SegmentEnd::FinalRate(ProductionRate::<AverageYearsTime>::new(1.)).kind(),
+        SegmentEndKind::FinalRate
+    );
+}
+
+/// Reconstructs a segment from each of its supported ends and returns the relative error of the
+/// incremental duration.
+fn round_trip_errors<Parameters>(parameters: &Parameters) -> Vec<f64>
+where
+    Parameters: SegmentConstructor<AverageYearsTime> + Clone + Into<Segment<AverageYearsTime>>,
+{
+    let duration = parameters.to_incremental_duration_spec().1.years;
+    Parameters::SUPPORTED_ENDS
+        .iter()
+        .map(|&kind| {
+            let (shape, end) = parameters.to_spec(kind).unwrap();
+            let segment: Segment<_> = Parameters::from_end(shape, end).unwrap().into();
+            (segment.incremental_duration().years - duration).abs() / duration
+        })
+        .collect()
+}
+
+#[test]
+fn round_trip_through_each_end() {
+    let initial_rate = ProductionRate::new(1000.);
+    let decline_rate = NominalDeclineRate::new(0.5);
+    let duration = AverageYearsTime { years: 5. };
+
+    let errors = [
+        round_trip_errors(
+            &HyperbolicParameters::from_incremental_duration(
+                initial_rate,
+                decline_rate,
+                duration,
+                0.9,
+            )
+            .unwrap(),
+        ),
+        round_trip_errors(
+            &HarmonicParameters::from_incremental_duration(initial_rate, decline_rate, duration)
+                .unwrap(),
+        ),
+        round_trip_errors(
+            &LinearParameters::from_incremental_duration(
+                initial_rate,
+                NominalDeclineRate::new(0.1),
+                duration,
+            )
+            .unwrap(),
+        ),
+        round_trip_errors(
+            &PowerLawExponentialParameters::from_incremental_duration(
+                initial_rate,
+                0.5,
+                NominalDeclineRate::new(0.05),
+                duration,
+                0.5,
+            )
+            .unwrap(),
+        ),
+        round_trip_errors(
+            &LogisticGrowthParameters::from_incremental_duration(100_000., 5., duration, 1.5)
+                .unwrap(),
+        ),
+        round_trip_errors(
+            &FlatParameters::from_incremental_duration(initial_rate, duration).unwrap(),
+        ),
+    ];
+
+    for error in errors.iter().flatten() {
+        assert!(*error < 1e-9, "{errors:?}");
+    }
+}
+
+#[test]
+fn specs_of_each_end() {
+    let parameters = HyperbolicParameters::<AverageYearsTime>::from_incremental_volume(
+        ProductionRate::new(1000.),
+        NominalDeclineRate::new(0.5),
+        2000.,
+        0.5,
+    )
+    .unwrap();
+
+    insta::assert_debug_snapshot!(parameters.to_incremental_volume_spec().unwrap().1, @"2000.0");
+    insta::assert_debug_snapshot!(parameters.to_final_rate_spec().unwrap().1.value(), @"250.0");
+    insta::assert_debug_snapshot!(parameters.to_final_decline_rate_spec().unwrap().1.value(), @"0.25");
+
+    let flat = FlatParameters::<AverageYearsTime>::from_incremental_duration(
+        ProductionRate::new(100.),
+        AverageYearsTime { years: 1. },
+    )
+    .unwrap();
+    let (rate, volume) = flat.to_incremental_volume_spec().unwrap();
+    assert_eq!((rate.value(), volume), (100., 100.));
+    insta::assert_snapshot!(flat.to_final_rate_spec().unwrap_err(), @"flat segments can't be constructed from a final rate");
+    insta::assert_snapshot!(
+        Segment::from(flat).to_end(SegmentEndKind::FinalDeclineRate).unwrap_err(),
+        @"flat segments can't be constructed from a final decline rate"
+    );
+}
@@ -0,0 +1,71 @@
+use decline_curve_analysis::{
+    AverageYearsTime, CurtailedSegment, HyperbolicParameters, NominalDeclineRate, ProductionRate,
+    Segment,
+};
+
+fn hyperbolic() -> HyperbolicParameters<AverageYearsTime> {
+    HyperbolicParameters::from_incremental_duration(
+        ProductionRate::new(1000.),
+        NominalDeclineRate::new(0.5),
+        AverageYearsTime { years: 10. },
+        0.8,
+    )
+    .unwrap()
+}
+
+#[test]
+#[cfg_attr(feature = "fast-math", ignore = "exact values differ with fast-math")]
+fn curtailed_rates_and_volumes() {
+    let segment = hyperbolic();
+    let curtailed = CurtailedSegment::new(segment.clone(), 0.92).unwrap();
+    let time = AverageYearsTime { years: 2.5 };
+
+    insta::assert_snapshot!(curtailed.rate_at_time(time).value(), @"386.81235101670876");
+    insta::assert_snapshot!(0.92 * segment.rate_at_time(time).value(), @"386.81235101670876");
+    insta::assert_snapshot!(curtailed.incremental_volume(), @"3047.5891942169183");
+    insta::assert_snapshot!(0.92 * segment.incremental_volume(), @"3047.5891942169183");
+    insta::assert_snapshot!(curtailed.decline_rate_at_time(time).value(), @"0.25");
+    insta::assert_snapshot!(curtailed.incremental_duration().years, @"10");
+    assert_eq!(curtailed.segment(), &segment);
+}
+
+#[test]
+fn curtailed_inverses() {
+    let curtailed = CurtailedSegment::new(hyperbolic(), 0.92).unwrap();
+    let time = AverageYearsTime { years: 2.5 };
+
+    let rate = curtailed.rate_at_time(time);
+    let volume = curtailed.incremental_volume_at_time(time);
+
+    assert!((curtailed.time_at_rate(rate).unwrap().years - 2.5).abs() < 1e-9);
+    assert!((curtailed.time_at_incremental_volume(volume).unwrap().years - 2.5).abs() < 1e-9);
+}
+
+#[test]
+#[cfg_attr(feature = "fast-math", ignore = "exact values differ with fast-math")]
+fn curtailed_truncation() {
+    let segment: Segment<AverageYearsTime> = hyperbolic().into();
+    let curtailed = CurtailedSegment::new(segment, 0.5).unwrap();
+
+    let truncation = curtailed
+        .truncate_at_rate(ProductionRate::new(80.))
+        .unwrap();
+
+    // The curtailed rate reaches 80 when the underlying rate reaches 160.
+    insta::assert_snapshot!(truncation.parameters.final_rate().value(), @"79.99999999999999");
+    insta::assert_snapshot!(truncation.parameters.segment().final_rate().value(), @"159.99999999999997");
+    insta::assert_snapshot!(truncation.parameters.availability_factor(), @"0.5");
+    insta::assert_snapshot!(truncation.truncated_volume, @"122.02269089362176");
+    insta::assert_snapshot!(
+        truncation.parameters.incremental_volume() + truncation.truncated_volume
+            - curtailed.incremental_volume(),
+        @"0"
+    );
+}
+
+#[test]
+fn invalid_availability_factor() {
+    insta::assert_snapshot!(CurtailedSegment::new(hyperbolic(), 0.).unwrap_err(), @"availability factor is negative or zero, but expected a positive number");
+    insta::assert_snapshot!(CurtailedSegment::new(hyperbolic(), 1.2).unwrap_err(), @"availability factor is greater than one, but expected a fraction");
+    insta::assert_snapshot!(CurtailedSegment::new(hyperbolic(), f64::NAN).unwrap_err(), @"availability factor is not-a-number, but expected a finite number");
+}
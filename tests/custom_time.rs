@@ -0,0 +1,76 @@
+use decline_curve_analysis::{
+    AverageDaysTime, AverageMonthsTime, AverageQuartersTime, AverageYearsTime, Barrels, CustomTime,
+    DeclineTimeUnit, ExponentialParameters, Months30Time, NominalDeclineRate, ProductionRate,
+    Years365Time,
+};
+
+#[test]
+fn custom_time_lengths() {
+    insta::assert_snapshot!(Years365Time::LENGTH, @"365");
+    insta::assert_snapshot!(Months30Time::LENGTH, @"30");
+    insta::assert_snapshot!(CustomTime::<700>::LENGTH, @"7");
+
+    let years: Years365Time = AverageDaysTime { days: 730. }.to_unit();
+    insta::assert_snapshot!(years.value, @"2");
+    let months: Months30Time = years.to_unit();
+    insta::assert_snapshot!(months.value, @"24.333333333333332");
+    let average_years: AverageYearsTime = years.to_unit();
+    insta::assert_snapshot!(average_years.years as f32, @"1.9986311");
+}
+
+#[test]
+fn custom_rate_conversions() {
+    let rate = ProductionRate::<Years365Time>::new(36_500.);
+    insta::assert_snapshot!(rate.to_time_unit::<AverageDaysTime>().value(), @"100");
+
+    let decline_rate = NominalDeclineRate::<Years365Time>::new(0.365);
+    insta::assert_snapshot!(decline_rate.to_time_unit::<AverageDaysTime>().value(), @"0.001");
+}
+
+#[test]
+fn segments_with_custom_time_match_days() {
+    let custom = ExponentialParameters::from_incremental_duration(
+        ProductionRate::<Years365Time>::new(36_500.),
+        NominalDeclineRate::new(0.365),
+        Years365Time { value: 2. },
+    )
+    .unwrap();
+    let days = ExponentialParameters::from_incremental_duration(
+        ProductionRate::<AverageDaysTime>::new(100.),
+        NominalDeclineRate::new(0.001),
+        AverageDaysTime { days: 730. },
+    )
+    .unwrap();
+
+    assert!((custom.incremental_volume() - days.incremental_volume()).abs() < 1e-9);
+    assert!(
+        (custom
+            .rate_at_time(Years365Time { value: 1. })
+            .to_time_unit::<AverageDaysTime>()
+            .value()
+            - days.rate_at_time(AverageDaysTime { days: 365. }).value())
+        .abs()
+            < 1e-9
+    );
+}
+
+#[test]
+fn from_conversions() {
+    let days = AverageDaysTime::from(AverageYearsTime { years: 1. });
+    insta::assert_snapshot!(days.days, @"365.25");
+    let quarters: AverageQuartersTime = days.into();
+    insta::assert_snapshot!(quarters.quarters, @"4");
+    let months: AverageMonthsTime = quarters.into();
+    insta::assert_snapshot!(months.months, @"12");
+    let custom: Years365Time = months.into();
+    insta::assert_snapshot!(custom.value as f32, @"1.000685");
+    let years: AverageYearsTime = custom.into();
+    insta::assert_snapshot!(years.years, @"1");
+
+    let rate: ProductionRate<AverageMonthsTime, Barrels> =
+        ProductionRate::<Months30Time, Barrels>::new(3000.).into();
+    insta::assert_snapshot!(rate.value(), @"3043.75");
+    let decline_rate: NominalDeclineRate<Years365Time> =
+        NominalDeclineRate::<AverageQuartersTime>::new(0.1).into();
+    insta::assert_snapshot!(decline_rate.value() as f32, @"0.3997262");
+}
@@ -0,0 +1,73 @@
+#![cfg(feature = "chrono")]
+
+use chrono::NaiveDate;
+use decline_curve_analysis::{
+    AverageDaysTime, AverageYearsTime, CalendarDate, DatedForecast, FlatParameters, Forecast,
+    ProductionRate,
+};
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+#[test]
+fn volume_between_dates_uses_actual_month_lengths() {
+    let forecast = Forecast::new(vec![
+        FlatParameters::from_incremental_duration(
+            ProductionRate::<AverageDaysTime>::new(100.),
+            AverageDaysTime { days: 3650. },
+        )
+        .unwrap()
+        .into(),
+    ]);
+    let dated = DatedForecast::new(date(2024, 1, 1), forecast);
+
+    // February has 29 days in a leap year.
+    insta::assert_snapshot!(dated.volume_between_dates(date(2024, 2, 1), date(2024, 3, 1)), @"2900");
+    insta::assert_snapshot!(dated.volume_between_dates(date(2025, 2, 1), date(2025, 3, 1)), @"2800");
+    insta::assert_snapshot!(dated.rate_at_date(date(2024, 6, 1)).value(), @"100");
+}
+
+#[test]
+fn dated_forecast_in_years() {
+    let forecast = Forecast::new(vec![
+        FlatParameters::from_incremental_duration(
+            ProductionRate::<AverageYearsTime>::new(36525.),
+            AverageYearsTime { years: 10. },
+        )
+        .unwrap()
+        .into(),
+    ]);
+    let dated = DatedForecast::new(date(2024, 1, 1), forecast);
+
+    insta::assert_snapshot!(dated.time_at_date(date(2025, 1, 1)).years, @"1.002053388090349");
+    insta::assert_snapshot!(dated.volume_between_dates(date(2024, 1, 1), date(2025, 1, 1)), @"36600");
+}
+
+#[test]
+fn calendar_date_conversions() {
+    let calendar = CalendarDate::from(date(2024, 2, 29));
+    assert_eq!(calendar, CalendarDate::new(2024, 2, 29).unwrap());
+    assert_eq!(NaiveDate::from(calendar), date(2024, 2, 29));
+}
+
+#[test]
+fn calendar_monthly_volumes() {
+    let forecast = Forecast::new(vec![
+        FlatParameters::from_incremental_duration(
+            ProductionRate::<AverageDaysTime>::new(100.),
+            AverageDaysTime { days: 60. },
+        )
+        .unwrap()
+        .into(),
+    ]);
+    let dated = DatedForecast::new(date(2024, 1, 15), forecast);
+
+    let volumes: Vec<f64> = dated
+        .monthly_volumes()
+        .map(|period| period.volume)
+        .collect();
+
+    // The first month is partial, and February has 29 days.
+    assert_eq!(volumes, [1700., 2900., 1400.]);
+}
@@ -0,0 +1,77 @@
+use decline_curve_analysis::{AverageYearsTime, DeclinationTable, NominalDeclineRate};
+
+#[test]
+fn declination_table() {
+    let exponents = [0., 0.5, 1.];
+    let table = DeclinationTable::new(
+        [10., 50., 100.].map(|percent| NominalDeclineRate::<AverageYearsTime>::new(percent / 100.)),
+        &exponents,
+    )
+    .unwrap();
+
+    assert_eq!(table.exponents(), exponents);
+    insta::assert_debug_snapshot!(
+        table
+            .rows()
+            .iter()
+            .map(|row| (
+                row.nominal.to_percent().0,
+                row.tangent_effective.to_percent().0 as f32,
+                row.secant_effective
+                    .iter()
+                    .map(|rate| rate.to_percent().0 as f32)
+                    .collect::<Vec<_>>(),
+            ))
+            .collect::<Vec<_>>(),
+        @r"
+    [
+        (
+            10.0,
+            9.516258,
+            [
+                9.516258,
+                9.297052,
+                9.090909,
+            ],
+        ),
+        (
+            50.0,
+            39.346935,
+            [
+                39.346935,
+                36.0,
+                33.333332,
+            ],
+        ),
+        (
+            100.0,
+            63.212055,
+            [
+                63.212055,
+                55.555557,
+                50.0,
+            ],
+        ),
+    ]
+    "
+    );
+
+    for row in table.rows() {
+        for (exponent, secant_effective) in exponents.iter().zip(&row.secant_effective) {
+            assert_eq!(
+                *secant_effective,
+                row.nominal.to_secant_effective(*exponent).unwrap()
+            );
+        }
+    }
+}
+
+#[test]
+fn invalid_exponent() {
+    let result = DeclinationTable::new(
+        [NominalDeclineRate::<AverageYearsTime>::new(0.1)],
+        &[f64::NAN],
+    );
+
+    insta::assert_snapshot!(result.unwrap_err(), @"exponent is not-a-number, but expected a finite number");
+}
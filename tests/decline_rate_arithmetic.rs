@@ -0,0 +1,64 @@
+use decline_curve_analysis::{
+    AverageYearsTime, NominalDeclineRate, Percent, SecantEffectiveDeclineRate,
+    TangentEffectiveDeclineRate,
+};
+
+type Nominal = NominalDeclineRate<AverageYearsTime>;
+
+#[test]
+fn nominal_arithmetic() {
+    let a = Nominal::new(0.3);
+    let b = Nominal::new(0.1);
+
+    insta::assert_debug_snapshot!(
+        [(a + b).value(), (a - b).value(), (a * 2.).value(), (a / 3.).value(), (-a).value()],
+        @r"
+    [
+        0.4,
+        0.19999999999999998,
+        0.6,
+        0.09999999999999999,
+        -0.3,
+    ]
+    "
+    );
+}
+
+#[test]
+fn comparisons() {
+    assert!(Nominal::new(0.3) > Nominal::new(0.1));
+    assert!(-Nominal::new(0.3) < Nominal::new(0.));
+    assert!(
+        SecantEffectiveDeclineRate::<AverageYearsTime>::new(0.2)
+            < SecantEffectiveDeclineRate::new(0.25)
+    );
+    assert_eq!(Nominal::new(f64::NAN).partial_cmp(&Nominal::new(0.1)), None);
+
+    let mut rates = [0.5, 0.1, 0.3].map(TangentEffectiveDeclineRate::<AverageYearsTime>::new);
+    rates.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    insta::assert_debug_snapshot!(rates.map(|rate| rate.value()), @r"
+    [
+        0.1,
+        0.3,
+        0.5,
+    ]
+    ");
+}
+
+#[test]
+fn percent_conversions() {
+    let rate = SecantEffectiveDeclineRate::<AverageYearsTime>::from_percent(Percent(65.));
+
+    insta::assert_debug_snapshot!(rate.value(), @"0.65");
+    insta::assert_debug_snapshot!(rate.to_percent(), @r"
+    Percent(
+        65.0,
+    )
+    ");
+    insta::assert_debug_snapshot!(Nominal::new(0.125).to_percent(), @r"
+    Percent(
+        12.5,
+    )
+    ");
+    insta::assert_debug_snapshot!(Percent::from_fraction(0.4).to_fraction(), @"0.4");
+}
@@ -0,0 +1,171 @@
+use decline_curve_analysis::{
+    AverageDaysTime, AverageYearsTime, ExponentialParameters, FlatParameters, Forecast,
+    HarmonicParameters, HyperbolicParameters, LinearParameters, LogisticGrowthParameters,
+    NominalDeclineRate, PowerLawExponentialParameters, ProductionRate, Segment,
+};
+
+fn segments() -> Vec<Segment<AverageYearsTime>> {
+    let rate = ProductionRate::new(1000.);
+    let decline_rate = NominalDeclineRate::new(0.3);
+    let duration = AverageYearsTime { years: 10. };
+
+    vec![
+        ExponentialParameters::from_incremental_duration(rate, decline_rate, duration)
+            .unwrap()
+            .into(),
+        FlatParameters::from_incremental_duration(rate, duration)
+            .unwrap()
+            .into(),
+        HarmonicParameters::from_incremental_duration(rate, decline_rate, duration)
+            .unwrap()
+            .into(),
+        HyperbolicParameters::from_incremental_duration(rate, decline_rate, duration, 0.5)
+            .unwrap()
+            .into(),
+        LinearParameters::from_incremental_duration(rate, NominalDeclineRate::new(0.05), duration)
+            .unwrap()
+            .into(),
+        LogisticGrowthParameters::from_incremental_duration(10_000., 5., duration, 1.5)
+            .unwrap()
+            .into(),
+        PowerLawExponentialParameters::from_incremental_duration(
+            rate,
+            0.3,
+            NominalDeclineRate::new(0.01),
+            duration,
+            0.5,
+        )
+        .unwrap()
+        .into(),
+    ]
+}
+
+#[test]
+fn matches_finite_difference() {
+    let step = 1e-6;
+
+    for segment in segments() {
+        for years in [0.5, 2., 7.5] {
+            let rate = |years| segment.rate_at_time(AverageYearsTime { years }).value();
+            let expected = -(rate(years + step) - rate(years - step)) / (2. * step) / rate(years);
+            let actual = segment
+                .decline_rate_at_time(AverageYearsTime { years })
+                .value();
+
+            assert!(
+                (actual - expected).abs() < 1e-6,
+                "{segment:?} at {years}: expected {expected}, got {actual}"
+            );
+        }
+    }
+}
+
+#[test]
+fn hyperbolic_decline_rate_at_time() {
+    let params = HyperbolicParameters::from_incremental_duration(
+        ProductionRate::<AverageYearsTime>::new(1000.),
+        NominalDeclineRate::new(0.8),
+        AverageYearsTime { years: 10. },
+        1.2,
+    )
+    .unwrap();
+
+    insta::assert_snapshot!(params.decline_rate_at_time(AverageYearsTime { years: 0. }).value(), @"0.8");
+    insta::assert_snapshot!(params.decline_rate_at_time(AverageYearsTime { years: 5. }).value(), @"0.13793103448275862");
+
+    // Times past the end are clamped to the end.
+    assert_eq!(
+        params.decline_rate_at_time(AverageYearsTime { years: 20. }),
+        params.decline_rate_at_time(AverageYearsTime { years: 10. })
+    );
+}
+
+#[test]
+fn forecast_decline_rate_at_time() {
+    let forecast = Forecast::new(vec![
+        Segment::from(
+            FlatParameters::from_incremental_duration(
+                ProductionRate::<AverageYearsTime>::new(1000.),
+                AverageYearsTime { years: 1. },
+            )
+            .unwrap(),
+        ),
+        HarmonicParameters::from_incremental_duration(
+            ProductionRate::new(1000.),
+            NominalDeclineRate::new(0.5),
+            AverageYearsTime { years: 10. },
+        )
+        .unwrap()
+        .into(),
+    ]);
+
+    insta::assert_snapshot!(forecast.decline_rate_at_time(AverageYearsTime { years: 0.5 }).value(), @"0");
+    insta::assert_snapshot!(forecast.decline_rate_at_time(AverageYearsTime { years: 1. }).value(), @"0.5");
+    insta::assert_snapshot!(forecast.decline_rate_at_time(AverageYearsTime { years: 3. }).value(), @"0.25");
+}
+
+#[test]
+fn time_at_nominal_decline() {
+    let hyperbolic = HyperbolicParameters::from_incremental_duration(
+        ProductionRate::<AverageYearsTime>::new(1000.),
+        NominalDeclineRate::new(1.),
+        AverageYearsTime { years: 30. },
+        0.5,
+    )
+    .unwrap();
+    let switch_time = hyperbolic
+        .time_at_nominal_decline(NominalDeclineRate::new(0.1))
+        .unwrap();
+    insta::assert_snapshot!(switch_time.years, @"18");
+    assert!((hyperbolic.decline_rate_at_time(switch_time).value() - 0.1).abs() < 1e-12);
+
+    let harmonic = HarmonicParameters::from_incremental_duration(
+        ProductionRate::<AverageYearsTime>::new(1000.),
+        NominalDeclineRate::new(1.),
+        AverageYearsTime { years: 30. },
+    )
+    .unwrap();
+    insta::assert_snapshot!(
+        harmonic
+            .time_at_nominal_decline(NominalDeclineRate::new(0.5))
+            .unwrap()
+            .years,
+        @"1"
+    );
+    assert_eq!(
+        harmonic
+            .time_at_nominal_decline(NominalDeclineRate::new(1.))
+            .unwrap()
+            .years,
+        0.
+    );
+
+    // The decline rate never reaches a terminal decline that's higher than the initial decline,
+    // or lower than the final decline.
+    let too_high = hyperbolic.time_at_nominal_decline(NominalDeclineRate::new(2.));
+    insta::assert_snapshot!(too_high.unwrap_err(), @"cannot solve decline: no finite solution exists for the given parameters");
+    let too_low = harmonic.time_at_nominal_decline(NominalDeclineRate::new(0.01));
+    insta::assert_snapshot!(too_low.unwrap_err(), @"cannot solve decline: no finite solution exists for the given parameters");
+}
+
+#[test]
+fn time_at_nominal_decline_round_trip() {
+    let hyperbolic = HyperbolicParameters::from_incremental_duration(
+        ProductionRate::<AverageDaysTime>::new(500.),
+        NominalDeclineRate::new(0.004),
+        AverageDaysTime { days: 5000. },
+        1.4,
+    )
+    .unwrap();
+
+    for days in [0., 10., 365., 2000., 5000.] {
+        let time = AverageDaysTime { days };
+        let decline_rate = hyperbolic.decline_rate_at_time(time);
+        let solved = hyperbolic.time_at_nominal_decline(decline_rate).unwrap();
+        assert!(
+            (solved.days - days).abs() < 1e-6,
+            "{days} != {}",
+            solved.days
+        );
+    }
+}
@@ -1,5 +1,7 @@
 use decline_curve_analysis::{
-    AverageDaysTime, AverageYearsTime, NominalDeclineRate, SecantEffectiveDeclineRate,
+    AverageDaysTime, AverageMonthsTime, AverageQuartersTime, AverageYearsTime,
+    ExponentialParameters, HarmonicParameters, HyperbolicParameters, NominalDeclineRate,
+    ProductionRate, SecantEffectiveDeclineRate, TangentEffectiveDeclineRate,
 };
 
 macro_rules! assert_approx_eq {
@@ -521,3 +523,159 @@ fn secant_to_nominal_daily() {
     let nominal_daily: NominalDeclineRate<AverageDaysTime> = nominal_yearly.into();
     assert_approx_eq!(nominal_daily.value(), 0.6485188 / 365.25, 1e-6);
 }
+
+#[test]
+fn segments_from_effective_decline_rates() {
+    let initial_rate = ProductionRate::<AverageYearsTime>::new(1000.);
+    let duration = AverageYearsTime { years: 5. };
+    let secant = SecantEffectiveDeclineRate::new(0.6);
+    let tangent = TangentEffectiveDeclineRate::new(0.6);
+
+    // Each segment converts with its own exponent.
+    let hyperbolic = HyperbolicParameters::from_secant_effective_incremental_duration(
+        initial_rate,
+        secant,
+        duration,
+        1.5,
+    )
+    .unwrap();
+    assert_eq!(
+        hyperbolic.initial_decline_rate(),
+        secant.to_nominal(1.5).unwrap()
+    );
+    insta::assert_snapshot!(hyperbolic.initial_decline_rate().value() as f32, @"1.9685647");
+
+    let harmonic = HarmonicParameters::from_secant_effective_incremental_duration(
+        initial_rate,
+        secant,
+        duration,
+    )
+    .unwrap();
+    assert_eq!(
+        harmonic.initial_decline_rate(),
+        secant.to_nominal(1.).unwrap()
+    );
+    insta::assert_snapshot!(harmonic.initial_decline_rate().value(), @"1.5");
+
+    let exponential = ExponentialParameters::from_tangent_effective_incremental_duration(
+        initial_rate,
+        tangent,
+        duration,
+    )
+    .unwrap();
+    insta::assert_snapshot!(exponential.decline_rate().value() as f32, @"0.91629076");
+    assert_eq!(
+        ExponentialParameters::from_secant_effective_incremental_duration(
+            initial_rate,
+            secant,
+            duration
+        )
+        .unwrap(),
+        exponential
+    );
+
+    // A secant effective decline of 60% means the rate falls by 60% over the first time unit.
+    let hyperbolic = HyperbolicParameters::from_secant_effective_final_rate(
+        initial_rate,
+        secant,
+        ProductionRate::new(400.),
+        1.5,
+    )
+    .unwrap();
+    insta::assert_snapshot!(hyperbolic.incremental_duration().years as f32, @"1");
+
+    // The same tangent effective decline is a lower nominal decline, so it takes longer.
+    let tangent_hyperbolic = HyperbolicParameters::from_tangent_effective_final_rate(
+        initial_rate,
+        tangent,
+        ProductionRate::new(400.),
+        1.5,
+    )
+    .unwrap();
+    assert!(
+        tangent_hyperbolic.incremental_duration().years > hyperbolic.incremental_duration().years
+    );
+
+    let harmonic = HarmonicParameters::from_tangent_effective_final_rate(
+        initial_rate,
+        tangent,
+        ProductionRate::new(400.),
+    )
+    .unwrap();
+    insta::assert_snapshot!(harmonic.incremental_duration().years as f32, @"1.637035");
+
+    let exponential = ExponentialParameters::from_secant_effective_final_rate(
+        initial_rate,
+        secant,
+        ProductionRate::new(400.),
+    )
+    .unwrap();
+    insta::assert_snapshot!(exponential.incremental_duration().years as f32, @"1");
+
+    let too_high = HyperbolicParameters::from_secant_effective_incremental_duration(
+        initial_rate,
+        SecantEffectiveDeclineRate::new(1.),
+        duration,
+        0.5,
+    );
+    insta::assert_snapshot!(too_high.unwrap_err(), @"decline rate too high");
+}
+
+#[test]
+fn non_finite_conversions() {
+    let nominal = NominalDeclineRate::<AverageYearsTime>::new(f64::NAN);
+    insta::assert_snapshot!(nominal.to_tangent_effective().unwrap_err(), @"decline rate is not-a-number, but expected a finite number");
+    insta::assert_snapshot!(nominal.to_secant_effective(0.5).unwrap_err(), @"decline rate is not-a-number, but expected a finite number");
+
+    let secant = SecantEffectiveDeclineRate::<AverageYearsTime>::new(f64::NEG_INFINITY);
+    insta::assert_snapshot!(secant.to_nominal(0.5).unwrap_err(), @"decline rate is infinity, but expected a finite number");
+    insta::assert_snapshot!(secant.to_tangent_effective(0.).unwrap_err(), @"decline rate is infinity, but expected a finite number");
+
+    let tangent = TangentEffectiveDeclineRate::<AverageYearsTime>::new(0.3);
+    insta::assert_snapshot!(tangent.to_secant_effective(f64::NAN).unwrap_err(), @"exponent is not-a-number, but expected a finite number");
+
+    let segment = HyperbolicParameters::from_secant_effective_incremental_duration(
+        ProductionRate::<AverageYearsTime>::new(1000.),
+        SecantEffectiveDeclineRate::new(f64::NAN),
+        AverageYearsTime { years: 5. },
+        0.5,
+    );
+    insta::assert_snapshot!(segment.unwrap_err(), @"decline rate is not-a-number, but expected a finite number");
+}
+
+#[test]
+fn conversions_without_a_finite_result() {
+    // A steep incline has no equivalent secant effective decline rate for the exponent.
+    let nominal = NominalDeclineRate::<AverageYearsTime>::new(-2.);
+    insta::assert_snapshot!(nominal.to_secant_effective(0.9).unwrap_err(), @"cannot solve decline: no finite solution exists for the given parameters");
+
+    let nominal = NominalDeclineRate::<AverageYearsTime>::new(-1000.);
+    insta::assert_snapshot!(nominal.to_tangent_effective().unwrap_err(), @"cannot solve decline: no finite solution exists for the given parameters");
+}
+
+#[test]
+fn effective_for_period() {
+    let nominal = NominalDeclineRate::<AverageYearsTime>::new(0.6);
+
+    let monthly = nominal
+        .to_effective_for_period::<AverageMonthsTime>()
+        .unwrap();
+    insta::assert_snapshot!(monthly.value(), @"0.048770575499285984");
+    let quarterly = nominal
+        .to_effective_for_period::<AverageQuartersTime>()
+        .unwrap();
+    insta::assert_snapshot!(quarterly.value(), @"0.1392920235749422");
+
+    // Compounding the effective decline over a year gives the annual effective decline.
+    let annual = nominal
+        .to_effective_for_period::<AverageYearsTime>()
+        .unwrap();
+    assert_eq!(annual, nominal.to_tangent_effective().unwrap());
+    assert!(((1. - monthly.value()).powi(12) - (1. - annual.value())).abs() < 1e-12);
+    assert!(((1. - quarterly.value()).powi(4) - (1. - annual.value())).abs() < 1e-12);
+
+    // The conversion is reversible.
+    let round_trip: NominalDeclineRate<AverageYearsTime> =
+        monthly.to_nominal().unwrap().to_time_unit();
+    assert!((round_trip.value() - nominal.value()).abs() < 1e-12);
+}
@@ -0,0 +1,73 @@
+#![cfg(all(feature = "deterministic", not(feature = "fast-math")))]
+
+//! These snapshots are exact bit patterns, which must match on every platform.
+
+use decline_curve_analysis::{
+    AverageDaysTime, AverageYearsTime, ExponentialParameters, HarmonicParameters,
+    HyperbolicParameters, NominalDeclineRate, ProductionRate, SecantEffectiveDeclineRate,
+};
+
+fn bits(value: f64) -> String {
+    format!("{:#018x}", value.to_bits())
+}
+
+#[test]
+fn bit_identical_evaluation() {
+    let time = AverageDaysTime { days: 2700. };
+    let hyperbolic = HyperbolicParameters::from_incremental_duration(
+        ProductionRate::new(1000.),
+        NominalDeclineRate::new(0.002),
+        AverageDaysTime { days: 18250. },
+        0.9,
+    )
+    .unwrap();
+    let harmonic = HarmonicParameters::from_incremental_duration(
+        ProductionRate::new(1000.),
+        NominalDeclineRate::new(0.002),
+        AverageDaysTime { days: 18250. },
+    )
+    .unwrap();
+    let exponential = ExponentialParameters::from_incremental_duration(
+        ProductionRate::new(1000.),
+        NominalDeclineRate::new(0.002),
+        AverageDaysTime { days: 18250. },
+    )
+    .unwrap();
+
+    insta::assert_debug_snapshot!(
+        [
+            hyperbolic.rate_at_time(time).value(),
+            hyperbolic.incremental_volume_at_time(time),
+            harmonic.incremental_volume_at_time(time),
+            exponential.incremental_volume_at_time(time),
+        ]
+        .map(bits),
+        @r#"
+    [
+        "0x406186bc1f149605",
+        "0x412b3772ceabfdda",
+        "0x412c5329fd889a08",
+        "0x411e6136d68eb09b",
+    ]
+    "#
+    );
+}
+
+#[test]
+fn bit_identical_decline_rate_conversions() {
+    let secant = SecantEffectiveDeclineRate::<AverageYearsTime>::new(0.65);
+
+    insta::assert_debug_snapshot!(
+        [
+            secant.to_nominal(0.5).unwrap().value(),
+            secant.to_nominal(0.).unwrap().value(),
+        ]
+        .map(bits),
+        @r#"
+    [
+        "0x3ff61701df089742",
+        "0x3ff0cc1248b56ccc",
+    ]
+    "#
+    );
+}
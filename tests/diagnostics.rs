@@ -0,0 +1,351 @@
+use decline_curve_analysis::{
+    AverageDaysTime, AverageYearsTime, ExponentialParameters, HarmonicParameters,
+    NominalDeclineRate, ProductionRate, bourdet_derivative, fetkovich_normalized,
+    log_rate_vs_log_material_balance_time, log_rate_vs_log_time, log_rate_vs_time,
+    rate_vs_cumulative_volume,
+};
+
+fn samples() -> Vec<(AverageDaysTime, ProductionRate<AverageDaysTime>)> {
+    [(0., 100.), (10., 90.), (20., 80.), (30., 0.), (40., 70.)]
+        .into_iter()
+        .map(|(days, rate)| (AverageDaysTime { days }, ProductionRate::new(rate)))
+        .collect()
+}
+
+#[test]
+fn diagnostic_series() {
+    let samples = samples();
+
+    #[cfg(all(feature = "std", not(feature = "deterministic")))]
+    insta::assert_debug_snapshot!(log_rate_vs_time(&samples).unwrap(), @r"
+    [
+        (
+            0.0,
+            2.0,
+        ),
+        (
+            10.0,
+            1.9542425094393248,
+        ),
+        (
+            20.0,
+            1.9030899869919435,
+        ),
+        (
+            40.0,
+            1.845098040014257,
+        ),
+    ]
+    ");
+    #[cfg(any(not(feature = "std"), feature = "deterministic"))]
+    insta::assert_debug_snapshot!(log_rate_vs_time(&samples).unwrap(), @r"
+    [
+        (
+            0.0,
+            2.0,
+        ),
+        (
+            10.0,
+            1.954242509439325,
+        ),
+        (
+            20.0,
+            1.9030899869919435,
+        ),
+        (
+            40.0,
+            1.845098040014257,
+        ),
+    ]
+    ");
+    insta::assert_debug_snapshot!(rate_vs_cumulative_volume(&samples).unwrap(), @r"
+    [
+        (
+            0.0,
+            100.0,
+        ),
+        (
+            950.0,
+            90.0,
+        ),
+        (
+            1800.0,
+            80.0,
+        ),
+        (
+            2200.0,
+            0.0,
+        ),
+        (
+            2550.0,
+            70.0,
+        ),
+    ]
+    ");
+    #[cfg(all(feature = "std", not(feature = "deterministic")))]
+    insta::assert_debug_snapshot!(log_rate_vs_log_time(&samples).unwrap(), @r"
+    [
+        (
+            1.0,
+            1.9542425094393248,
+        ),
+        (
+            1.3010299956639813,
+            1.9030899869919435,
+        ),
+        (
+            1.6020599913279625,
+            1.845098040014257,
+        ),
+    ]
+    ");
+    #[cfg(any(not(feature = "std"), feature = "deterministic"))]
+    insta::assert_debug_snapshot!(log_rate_vs_log_time(&samples).unwrap(), @r"
+    [
+        (
+            1.0,
+            1.954242509439325,
+        ),
+        (
+            1.3010299956639813,
+            1.9030899869919435,
+        ),
+        (
+            1.6020599913279623,
+            1.845098040014257,
+        ),
+    ]
+    ");
+    #[cfg(all(feature = "std", not(feature = "deterministic")))]
+    insta::assert_debug_snapshot!(log_rate_vs_log_material_balance_time(&samples).unwrap(), @r"
+    [
+        (
+            1.0234810958495228,
+            1.9542425094393248,
+        ),
+        (
+            1.3521825181113625,
+            1.9030899869919435,
+        ),
+        (
+            1.5614421404196983,
+            1.845098040014257,
+        ),
+    ]
+    ");
+    #[cfg(any(not(feature = "std"), feature = "deterministic"))]
+    insta::assert_debug_snapshot!(log_rate_vs_log_material_balance_time(&samples).unwrap(), @r"
+    [
+        (
+            1.0234810958495228,
+            1.954242509439325,
+        ),
+        (
+            1.3521825181113625,
+            1.9030899869919435,
+        ),
+        (
+            1.5614421404196983,
+            1.845098040014257,
+        ),
+    ]
+    ");
+    insta::assert_debug_snapshot!(
+        fetkovich_normalized(&samples, ProductionRate::new(100.), NominalDeclineRate::new(0.01))
+            .unwrap(),
+        @r"
+    [
+        (
+            -1.0,
+            -0.045757490560675115,
+        ),
+        (
+            -0.6989700043360187,
+            -0.09691001300805639,
+        ),
+        (
+            -0.3979400086720376,
+            -0.1549019599857432,
+        ),
+    ]
+    "
+    );
+}
+
+#[test]
+fn exponential_rate_vs_cumulative_volume_is_linear() {
+    let params = ExponentialParameters::from_incremental_duration(
+        ProductionRate::<AverageYearsTime>::new(1000.),
+        NominalDeclineRate::new(0.3),
+        AverageYearsTime { years: 10. },
+    )
+    .unwrap();
+    let samples = (0..=1000)
+        .map(|index| {
+            let time = AverageYearsTime {
+                years: f64::from(index) / 100.,
+            };
+            (time, params.rate_at_time(time))
+        })
+        .collect::<Vec<_>>();
+
+    // The slope of rate against cumulative volume is the decline rate.
+    let series = rate_vs_cumulative_volume(&samples).unwrap();
+    let (first, last) = (series[0], series[series.len() - 1]);
+    let slope = (last.1 - first.1) / (last.0 - first.0);
+    assert!((slope + 0.3).abs() < 1e-4, "slope {slope}");
+}
+
+#[test]
+fn harmonic_fetkovich_normalized() {
+    let params = HarmonicParameters::from_incremental_duration(
+        ProductionRate::<AverageYearsTime>::new(1000.),
+        NominalDeclineRate::new(0.5),
+        AverageYearsTime { years: 100. },
+    )
+    .unwrap();
+    let samples = [1., 10., 100.].map(|years| {
+        let time = AverageYearsTime { years };
+        (time, params.rate_at_time(time))
+    });
+
+    // A harmonic decline follows `q / qi = 1 / (1 + Di * t)`.
+    let series = fetkovich_normalized(
+        &samples,
+        ProductionRate::new(1000.),
+        NominalDeclineRate::new(0.5),
+    )
+    .unwrap();
+    for (normalized_time, normalized_rate) in series {
+        let expected = -(1. + 10_f64.powf(normalized_time)).log10();
+        assert!((normalized_rate - expected).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn invalid_samples() {
+    let samples = [
+        (AverageDaysTime { days: 10. }, ProductionRate::new(100.)),
+        (AverageDaysTime { days: 10. }, ProductionRate::new(90.)),
+    ];
+    insta::assert_snapshot!(log_rate_vs_time(&samples).unwrap_err(), @"sample times are not strictly increasing");
+
+    let samples = [(AverageDaysTime { days: 10. }, ProductionRate::new(-1.))];
+    insta::assert_snapshot!(rate_vs_cumulative_volume(&samples).unwrap_err(), @"sample rate is negative, but expected a positive number");
+
+    let result = fetkovich_normalized(
+        &samples,
+        ProductionRate::new(100.),
+        NominalDeclineRate::new(-0.1),
+    );
+    insta::assert_snapshot!(result.unwrap_err(), @"initial decline rate is negative or zero, but expected a positive number");
+}
+
+#[test]
+fn bourdet_derivative_of_power_laws() {
+    // `y = x^(1/2)` has a logarithmic derivative of `x^(1/2) / 2`, and `y = ln(x)` has a constant
+    // derivative of one, for any smoothing.
+    let square_roots: Vec<_> = (0..=20)
+        .map(|step| {
+            let x = 10_f64.powf(f64::from(step) / 10.);
+            (x, x.sqrt())
+        })
+        .collect();
+    let logarithms: Vec<_> = square_roots.iter().map(|&(x, _)| (x, x.ln())).collect();
+
+    for smoothing in [0., 0.2, 0.5] {
+        for (x, derivative) in bourdet_derivative(&logarithms, smoothing).unwrap() {
+            assert!((derivative - 1.).abs() < 1e-12, "{x}: {derivative}");
+        }
+    }
+
+    let derivative = bourdet_derivative(&square_roots, 0.).unwrap();
+    assert_eq!(derivative.len(), square_roots.len() - 2);
+    for (x, derivative) in derivative {
+        let relative_error = (derivative / (0.5 * x.sqrt()) - 1.).abs();
+        assert!(relative_error < 1e-2, "{x}: {derivative}");
+    }
+}
+
+#[test]
+fn bourdet_derivative_smoothing() {
+    let points = [
+        (0., 5.),
+        (1., 10.),
+        (2., 8.),
+        (3., 12.),
+        (4., 9.),
+        (8., 11.),
+    ];
+
+    #[cfg(all(feature = "std", not(feature = "deterministic")))]
+    insta::assert_debug_snapshot!(bourdet_derivative(&points, 0.).unwrap(), @r"
+    [
+        (
+            2.0,
+            5.159345314474122,
+        ),
+        (
+            3.0,
+            -2.0056596817298664,
+        ),
+        (
+            4.0,
+            -6.523242960745432,
+        ),
+    ]
+    ");
+    #[cfg(any(not(feature = "std"), feature = "deterministic"))]
+    insta::assert_debug_snapshot!(bourdet_derivative(&points, 0.).unwrap(), @r"
+    [
+        (
+            2.0,
+            5.159345314474127,
+        ),
+        (
+            3.0,
+            -2.005659681729852,
+        ),
+        (
+            4.0,
+            -6.523242960745424,
+        ),
+    ]
+    ");
+    #[cfg(all(feature = "std", not(feature = "deterministic")))]
+    insta::assert_debug_snapshot!(bourdet_derivative(&points, 1.).unwrap(), @r"
+    [
+        (
+            2.0,
+            -1.202245867407469,
+        ),
+        (
+            3.0,
+            0.3200346584674204,
+        ),
+        (
+            4.0,
+            1.6831442143704574,
+        ),
+    ]
+    ");
+    #[cfg(any(not(feature = "std"), feature = "deterministic"))]
+    insta::assert_debug_snapshot!(bourdet_derivative(&points, 1.).unwrap(), @r"
+    [
+        (
+            2.0,
+            -1.202245867407469,
+        ),
+        (
+            3.0,
+            0.32003465846742096,
+        ),
+        (
+            4.0,
+            1.6831442143704574,
+        ),
+    ]
+    ");
+    insta::assert_snapshot!(bourdet_derivative(&[(1., 1.), (1., 2.)], 0.).unwrap_err(), @"x values are not strictly increasing");
+    insta::assert_snapshot!(bourdet_derivative(&points, -0.1).unwrap_err(), @"smoothing is negative, but expected a positive number");
+}
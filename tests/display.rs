@@ -0,0 +1,132 @@
+use decline_curve_analysis::{
+    AverageDaysTime, AverageMonthsTime, AverageYearsTime, DeclineRateConvention, DelayParameters,
+    DisplayOptions, ExponentialParameters, FlatParameters, HarmonicParameters,
+    HyperbolicParameters, LinearParameters, LogisticGrowthParameters, NominalDeclineRate,
+    PowerLawExponentialParameters, ProductionRate, SecantEffectiveDeclineRate, Segment,
+    TangentEffectiveDeclineRate, Years365Time,
+};
+
+fn hyperbolic() -> HyperbolicParameters<AverageYearsTime> {
+    HyperbolicParameters::from_final_rate(
+        ProductionRate::<AverageDaysTime>::new(50.).into(),
+        NominalDeclineRate::new(0.5),
+        ProductionRate::<AverageDaysTime>::new(10.).into(),
+        0.9,
+    )
+    .unwrap()
+}
+
+#[test]
+fn hyperbolic_display() {
+    let parameters = hyperbolic();
+
+    insta::assert_snapshot!(parameters, @"Hyperbolic: qi=50/d, Di=50%/yr nominal, b=0.9, t=2643 d, qf=10/d");
+    insta::assert_snapshot!(
+        parameters.display_with(DisplayOptions {
+            decline_rate_convention: DeclineRateConvention::SecantEffective,
+            volume_unit: "bbl",
+        }),
+        @"Hyperbolic: qi=50 bbl/d, Di=33.82%/yr secant effective, b=0.9, t=2643 d, qf=10 bbl/d"
+    );
+    insta::assert_snapshot!(
+        parameters.display_with(DisplayOptions {
+            decline_rate_convention: DeclineRateConvention::TangentEffective,
+            volume_unit: "bbl",
+        }),
+        @"Hyperbolic: qi=50 bbl/d, Di=39.35%/yr tangent effective, b=0.9, t=2643 d, qf=10 bbl/d"
+    );
+}
+
+#[test]
+fn display_converts_time_units() {
+    let parameters = ExponentialParameters::from_incremental_duration(
+        ProductionRate::<AverageMonthsTime>::new(3043.75),
+        NominalDeclineRate::<AverageYearsTime>::new(0.2).to_time_unit(),
+        AverageMonthsTime { months: 12. },
+    )
+    .unwrap();
+
+    insta::assert_snapshot!(parameters, @"Exponential: qi=100/d, D=20%/yr nominal, t=365.2 d, qf=81.87/d");
+    insta::assert_snapshot!(
+        parameters.display_with(DisplayOptions {
+            decline_rate_convention: DeclineRateConvention::SecantEffective,
+            volume_unit: "Mcf",
+        }),
+        @"Exponential: qi=100 Mcf/d, D=18.13%/yr secant effective, t=365.2 d, qf=81.87 Mcf/d"
+    );
+}
+
+#[test]
+fn segment_display() {
+    let segments: Vec<Segment<AverageDaysTime>> = vec![
+        DelayParameters::from_incremental_duration(AverageDaysTime { days: 30. })
+            .unwrap()
+            .into(),
+        FlatParameters::from_incremental_duration(
+            ProductionRate::new(500.),
+            AverageDaysTime { days: 90. },
+        )
+        .unwrap()
+        .into(),
+        HarmonicParameters::from_incremental_duration(
+            ProductionRate::new(500.),
+            NominalDeclineRate::<AverageYearsTime>::new(0.8).to_time_unit(),
+            AverageDaysTime { days: 365.25 },
+        )
+        .unwrap()
+        .into(),
+        LinearParameters::from_incremental_duration(
+            ProductionRate::new(300.),
+            NominalDeclineRate::<AverageYearsTime>::new(0.1).to_time_unit(),
+            AverageDaysTime { days: 730.5 },
+        )
+        .unwrap()
+        .into(),
+        LogisticGrowthParameters::from_incremental_duration(
+            500_000.,
+            1000.,
+            AverageDaysTime { days: 3652.5 },
+            0.9,
+        )
+        .unwrap()
+        .into(),
+        PowerLawExponentialParameters::from_incremental_duration(
+            ProductionRate::new(800.),
+            0.05,
+            NominalDeclineRate::<AverageYearsTime>::new(0.05).to_time_unit(),
+            AverageDaysTime { days: 1826.25 },
+            0.5,
+        )
+        .unwrap()
+        .into(),
+    ];
+
+    let displayed = segments
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    insta::assert_snapshot!(displayed, @r"
+    Delay: t=30 d
+    Flat: q=500/d, t=90 d
+    Harmonic: qi=500/d, Di=80%/yr nominal, t=365.2 d, qf=277.8/d
+    Linear: qi=300/d, D=10%/yr, t=730.5 d, qf=240/d
+    Logistic growth: K=500000, a=1000, n=0.9, t=3652 d, qf=29.13/d
+    Power law exponential: qi=800/d, Di=0.05, Dinf=5%/yr nominal, n=0.5, t=1826 d, qf=73.54/d
+    ");
+}
+
+#[test]
+fn decline_rate_display() {
+    insta::assert_snapshot!(NominalDeclineRate::<AverageYearsTime>::new(0.5), @"50%/yr nominal");
+    insta::assert_snapshot!(
+        SecantEffectiveDeclineRate::<AverageMonthsTime>::new(0.0125),
+        @"1.25%/mo secant effective"
+    );
+    insta::assert_snapshot!(
+        TangentEffectiveDeclineRate::<AverageDaysTime>::new(0.000_123_4),
+        @"0.01234%/d tangent effective"
+    );
+    insta::assert_snapshot!(NominalDeclineRate::<Years365Time>::new(0.3), @"30%/(365 d) nominal");
+}
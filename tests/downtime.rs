@@ -0,0 +1,132 @@
+use decline_curve_analysis::{
+    AverageDaysTime, DowntimeInterval, DowntimeSchedule, ExponentialParameters, FlatParameters,
+    Forecast, NominalDeclineRate, ProductionRate, Segment,
+};
+
+fn days(days: f64) -> AverageDaysTime {
+    AverageDaysTime { days }
+}
+
+#[test]
+fn constant_uptime_factor() {
+    let schedule = DowntimeSchedule::new(0.95, vec![]).unwrap();
+    let forecast = Forecast::new(vec![
+        FlatParameters::from_incremental_duration(
+            ProductionRate::new(100.),
+            AverageDaysTime { days: 100. },
+        )
+        .unwrap()
+        .into(),
+        ExponentialParameters::from_incremental_duration(
+            ProductionRate::new(100.),
+            NominalDeclineRate::new(0.001),
+            AverageDaysTime { days: 1000. },
+        )
+        .unwrap()
+        .into(),
+    ]);
+
+    insta::assert_snapshot!(forecast.rate_at_time_with_downtime(days(50.), &schedule).value(), @"95");
+    insta::assert_snapshot!(forecast.incremental_volume_with_downtime(&schedule), @"69551.45308871297");
+    insta::assert_snapshot!(0.95 * forecast.incremental_volume(), @"69551.45308871297");
+}
+
+#[test]
+fn explicit_shut_in() {
+    let schedule = DowntimeSchedule::new(
+        0.95,
+        vec![DowntimeInterval::new(days(10.), days(20.), 0.).unwrap()],
+    )
+    .unwrap();
+    let segment: Segment<AverageDaysTime> = FlatParameters::from_incremental_duration(
+        ProductionRate::new(100.),
+        AverageDaysTime { days: 100. },
+    )
+    .unwrap()
+    .into();
+
+    insta::assert_snapshot!(schedule.uptime_factor_at_time(days(5.)), @"0.95");
+    insta::assert_snapshot!(schedule.uptime_factor_at_time(days(10.)), @"0");
+    insta::assert_snapshot!(schedule.uptime_factor_at_time(days(20.)), @"0.95");
+    insta::assert_snapshot!(segment.rate_at_time_with_downtime(days(15.), &schedule).value(), @"0");
+    // 90 days at 95 and 10 days shut in.
+    insta::assert_snapshot!(segment.incremental_volume_with_downtime(&schedule), @"8550");
+    insta::assert_snapshot!(segment.incremental_volume_between_with_downtime(days(5.), days(15.), &schedule), @"475");
+}
+
+#[test]
+fn recurring_downtime() {
+    let schedule = DowntimeSchedule::new(
+        1.,
+        vec![
+            DowntimeInterval::recurring(days(28.), days(30.), 0., days(30.)).unwrap(),
+            DowntimeInterval::new(days(29.), days(40.), 0.5).unwrap(),
+        ],
+    )
+    .unwrap();
+    let segment: Segment<AverageDaysTime> = FlatParameters::from_incremental_duration(
+        ProductionRate::new(100.),
+        AverageDaysTime { days: 100. },
+    )
+    .unwrap()
+    .into();
+
+    insta::assert_snapshot!(schedule.uptime_factor_at_time(days(58.5)), @"0");
+    insta::assert_snapshot!(schedule.uptime_factor_at_time(days(60.)), @"1");
+    insta::assert_snapshot!(schedule.uptime_factor_at_time(days(29.5)), @"0");
+    insta::assert_snapshot!(schedule.uptime_factor_at_time(days(35.)), @"0.5");
+    // Shut in for days 28-30, 58-60, and 88-90, and half rate for days 30-40.
+    insta::assert_snapshot!(segment.incremental_volume_with_downtime(&schedule), @"8900");
+}
+
+#[test]
+fn downtime_matches_integrated_rate() {
+    let schedule = DowntimeSchedule::new(
+        0.9,
+        vec![
+            DowntimeInterval::recurring(days(3.), days(5.5), 0.2, days(17.)).unwrap(),
+            DowntimeInterval::new(days(250.), days(400.), 0.).unwrap(),
+        ],
+    )
+    .unwrap();
+    let forecast = Forecast::new(vec![
+        FlatParameters::from_incremental_duration(
+            ProductionRate::new(100.),
+            AverageDaysTime { days: 100. },
+        )
+        .unwrap()
+        .into(),
+        ExponentialParameters::from_incremental_duration(
+            ProductionRate::new(100.),
+            NominalDeclineRate::new(0.001),
+            AverageDaysTime { days: 1000. },
+        )
+        .unwrap()
+        .into(),
+    ]);
+
+    let steps = 1_100_000;
+    let step = forecast.incremental_duration().days / f64::from(steps);
+    let integrated: f64 = (0..steps)
+        .map(|index| {
+            let time = days((f64::from(index) + 0.5) * step);
+            forecast.rate_at_time_with_downtime(time, &schedule).value() * step
+        })
+        .sum();
+
+    let volume = forecast.incremental_volume_with_downtime(&schedule);
+    assert!(
+        (volume - integrated).abs() < 1e-3,
+        "{volume} != {integrated}"
+    );
+}
+
+#[test]
+fn invalid_downtime() {
+    insta::assert_snapshot!(DowntimeSchedule::<AverageDaysTime>::new(1.5, vec![]).unwrap_err(), @"uptime factor is greater than one, but expected a fraction");
+    insta::assert_snapshot!(DowntimeSchedule::<AverageDaysTime>::new(-0.5, vec![]).unwrap_err(), @"uptime factor is negative, but expected a positive number");
+    insta::assert_snapshot!(DowntimeInterval::new(days(10.), days(10.), 0.).unwrap_err(), @"downtime start is 10, but expected it to be less than the downtime end of 10");
+    insta::assert_snapshot!(DowntimeInterval::new(days(f64::NAN), days(10.), 0.).unwrap_err(), @"downtime start is not-a-number, but expected a finite number");
+    insta::assert_snapshot!(DowntimeInterval::recurring(days(0.), days(10.), 0., days(10.)).unwrap_err(), @"downtime duration is 10, but expected it to be less than the downtime period of 10");
+    insta::assert_snapshot!(DowntimeInterval::recurring(days(0.), days(10.), 0., days(0.)).unwrap_err(), @"downtime period is negative or zero, but expected a positive number");
+}
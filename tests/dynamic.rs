@@ -0,0 +1,68 @@
+use decline_curve_analysis::{
+    AverageDaysTime, AverageMonthsTime, AverageYearsTime, DynNominalDeclineRate, DynProductionRate,
+    DynTime, DynTimeUnit, ExponentialParameters, NominalDeclineRate, ProductionRate, Years365Time,
+};
+
+#[test]
+fn parse_time_units() {
+    let days: DynTimeUnit = "days".parse().unwrap();
+    assert_eq!(days, DynTimeUnit::of::<AverageDaysTime>());
+    assert_eq!(
+        " Month ".parse::<DynTimeUnit>().unwrap(),
+        DynTimeUnit::of::<AverageMonthsTime>()
+    );
+    insta::assert_snapshot!("years".parse::<DynTimeUnit>().unwrap().length_days(), @"365.25");
+    insta::assert_snapshot!("fortnights".parse::<DynTimeUnit>().unwrap_err(), @r#"time unit "fortnights" is unknown, but expected days, months, or years"#);
+    insta::assert_snapshot!(DynTimeUnit::new(0.).unwrap_err(), @"time unit length is negative or zero, but expected a positive number");
+}
+
+#[test]
+fn convert_into_typed_units() {
+    let years: DynTimeUnit = "years".parse().unwrap();
+
+    let time = DynTime::new(2., years);
+    let days: AverageDaysTime = time.to_typed().unwrap();
+    insta::assert_snapshot!(days.days, @"730.5");
+    insta::assert_snapshot!(time.to_unit(DynTimeUnit::of::<Years365Time>()).value(), @"2.0013698630136987");
+    assert_eq!(
+        DynTime::from_typed(days)
+            .to_typed::<AverageYearsTime>()
+            .unwrap()
+            .years,
+        2.
+    );
+
+    let rate = ProductionRate::<AverageDaysTime>::try_from(DynProductionRate::new(36_525., years))
+        .unwrap();
+    insta::assert_snapshot!(rate.value(), @"100");
+
+    let decline_rate =
+        NominalDeclineRate::<AverageMonthsTime>::try_from(DynNominalDeclineRate::new(1.2, years))
+            .unwrap();
+    insta::assert_snapshot!(decline_rate.value() as f32, @"0.1");
+
+    let invalid =
+        ProductionRate::<AverageDaysTime>::try_from(DynProductionRate::new(f64::NAN, years));
+    insta::assert_snapshot!(invalid.unwrap_err(), @"rate is not-a-number, but expected a finite number");
+}
+
+#[test]
+fn runtime_units_build_typed_segments() {
+    // Parameters read from a file in monthly units are used with a daily forecast.
+    let months: DynTimeUnit = "months".parse().unwrap();
+    let segment = ExponentialParameters::<AverageDaysTime>::from_incremental_duration(
+        DynProductionRate::new(3043.75, months).try_into().unwrap(),
+        DynNominalDeclineRate::new(0.0304375, months)
+            .try_into()
+            .unwrap(),
+        DynTime::new(12., months).to_typed().unwrap(),
+    )
+    .unwrap();
+
+    assert!((segment.initial_rate().value() - 100.).abs() < 1e-9);
+    assert!((segment.decline_rate().value() - 0.001).abs() < 1e-12);
+    assert!((segment.incremental_duration().days - 365.25).abs() < 1e-9);
+
+    let rate = DynProductionRate::from(segment.initial_rate()).to_unit(months);
+    assert!((rate.value() - 3043.75).abs() < 1e-9);
+}
@@ -0,0 +1,132 @@
+use decline_curve_analysis::{
+    AverageDaysTime, AverageYearsTime, CalendarDate, CurtailedSegment, DeclineCurveAnalysisError,
+    DowntimeInterval, ExponentialParameters, HyperbolicParameters, NominalDeclineRate,
+    PowerLawExponentialParameters, ProductionRate,
+};
+
+fn exponential(
+    initial_rate: f64,
+    decline_rate: f64,
+    years: f64,
+) -> Result<ExponentialParameters<AverageYearsTime>, DeclineCurveAnalysisError> {
+    ExponentialParameters::from_incremental_duration(
+        ProductionRate::new(initial_rate),
+        NominalDeclineRate::new(decline_rate),
+        AverageYearsTime { years },
+    )
+}
+
+#[test]
+fn errors_carry_the_invalid_input() {
+    let error = exponential(-100., 0.1, 1.).unwrap_err();
+    insta::assert_debug_snapshot!(error, @r#"
+    NonPositiveInput {
+        field: "initial rate",
+        value: -100.0,
+    }
+    "#);
+    insta::assert_debug_snapshot!(error.field(), @r#"
+    Some(
+        "initial rate",
+    )
+    "#);
+
+    let error = exponential(100., f64::INFINITY, 1.).unwrap_err();
+    insta::assert_debug_snapshot!(error, @r#"
+    NonFiniteInput {
+        field: "decline rate",
+        value: inf,
+    }
+    "#);
+    insta::assert_snapshot!(error, @"decline rate is infinity, but expected a finite number");
+
+    let error = exponential(100., 0.1, -1.).unwrap_err();
+    insta::assert_debug_snapshot!(error, @r#"
+    NegativeInput {
+        field: "duration",
+        value: -1.0,
+    }
+    "#);
+}
+
+#[test]
+fn exponent_out_of_range() {
+    let error = HyperbolicParameters::<AverageYearsTime>::from_incremental_duration(
+        ProductionRate::new(100.),
+        NominalDeclineRate::new(0.5),
+        AverageYearsTime { years: 1. },
+        101.,
+    )
+    .unwrap_err();
+    insta::assert_snapshot!(error, @"exponent is 101, but expected it to be between -100 and 100");
+    insta::assert_debug_snapshot!(error.field(), @r#"
+    Some(
+        "exponent",
+    )
+    "#);
+
+    let error = PowerLawExponentialParameters::<AverageYearsTime>::from_incremental_duration(
+        ProductionRate::new(100.),
+        0.5,
+        NominalDeclineRate::new(0.05),
+        AverageYearsTime { years: 1. },
+        1.5,
+    )
+    .unwrap_err();
+    insta::assert_debug_snapshot!(error, @r"
+    ExponentOutOfRange {
+        value: 1.5,
+        allowed: ExponentRange {
+            minimum: 0.0,
+            maximum: 1.0,
+            excluded: [],
+        },
+    }
+    ");
+}
+
+#[test]
+fn fraction_out_of_range() {
+    let error = CurtailedSegment::new(exponential(100., 0.1, 1.).unwrap(), 1.5).unwrap_err();
+    insta::assert_debug_snapshot!(error, @r#"
+    InputGreaterThanOne {
+        field: "availability factor",
+        value: 1.5,
+    }
+    "#);
+}
+
+#[test]
+fn validation_errors_name_the_input() {
+    let error = CalendarDate::new(2024, 13, 1).unwrap_err();
+    insta::assert_debug_snapshot!(error, @r#"
+    InputOutOfRange {
+        field: "month",
+        value: 13.0,
+        minimum: Included(
+            1.0,
+        ),
+        maximum: Included(
+            12.0,
+        ),
+    }
+    "#);
+    insta::assert_snapshot!(error, @"month is 13, but expected it to be between 1 and 12");
+
+    let error = DowntimeInterval::new(
+        AverageDaysTime { days: 20. },
+        AverageDaysTime { days: 10. },
+        0.,
+    )
+    .unwrap_err();
+    insta::assert_debug_snapshot!(error.field(), @r#"
+    Some(
+        "downtime start",
+    )
+    "#);
+}
+
+#[test]
+fn errors_without_a_field() {
+    insta::assert_debug_snapshot!(DeclineCurveAnalysisError::CannotSolveDecline.field(), @"None");
+}
@@ -54,6 +54,27 @@ fn exponential_from_final_rate() {
     insta::assert_snapshot!(calculated_duration, @"1175.6943950331104");
 }
 
+#[test]
+fn exponential_from_final_decline_rate() {
+    let initial_rate = ProductionRate::<AverageDaysTime>::new(50.);
+    let decline_rate = NominalDeclineRate::<AverageYearsTime>::new(0.5).into();
+
+    let calculated_duration =
+        ExponentialParameters::from_final_decline_rate(initial_rate, decline_rate, decline_rate)
+            .unwrap()
+            .incremental_duration()
+            .days;
+    insta::assert_snapshot!(calculated_duration, @"0");
+
+    // The decline rate of an exponential never changes.
+    let result = ExponentialParameters::from_final_decline_rate(
+        initial_rate,
+        decline_rate,
+        NominalDeclineRate::<AverageYearsTime>::new(0.1).into(),
+    );
+    insta::assert_snapshot!(result.unwrap_err(), @"cannot solve decline: no finite solution exists for the given parameters");
+}
+
 #[test]
 fn exponential_incremental_volume_at_time() {
     let initial_rate = ProductionRate::<AverageDaysTime>::new(50.);
@@ -365,3 +386,15 @@ proptest! {
         }
     }
 }
+
+#[test]
+fn tangent_effective_accessor() {
+    let parameters = ExponentialParameters::from_incremental_duration(
+        ProductionRate::<AverageYearsTime>::new(1000.),
+        NominalDeclineRate::new(0.5),
+        AverageYearsTime { years: 10. },
+    )
+    .unwrap();
+
+    insta::assert_snapshot!(parameters.tangent_effective().unwrap().value(), @"0.3934693402873666");
+}
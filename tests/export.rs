@@ -0,0 +1,72 @@
+use decline_curve_analysis::{
+    AverageDaysTime, CalendarDate, ExponentialParameters, FlatParameters, Forecast,
+    NominalDeclineRate, Phase, ProductionRate, ReportFrequency,
+    export::{FlatTableOptions, flat_table, to_csv},
+};
+
+#[test]
+fn monthly_flat_table() {
+    let forecast = Forecast::new(vec![
+        FlatParameters::from_incremental_duration(
+            ProductionRate::new(100.),
+            AverageDaysTime { days: 45. },
+        )
+        .unwrap()
+        .into(),
+        ExponentialParameters::from_incremental_duration(
+            ProductionRate::new(100.),
+            NominalDeclineRate::new(0.01),
+            AverageDaysTime { days: 60. },
+        )
+        .unwrap()
+        .into(),
+    ])
+    .with_initial_cumulative_volume(1000.)
+    .unwrap();
+    let options = FlatTableOptions::new(CalendarDate::new(2024, 1, 20).unwrap(), Phase::Oil);
+    let rows = flat_table(&forecast, &options).unwrap();
+
+    insta::assert_snapshot!(to_csv(&rows), @r"
+    date,days,phase,rate,volume,cumulative_volume,segment_index
+    2024-01-20,12,oil,100,1200,2200,0
+    2024-02-01,29,oil,100,2900,5100,0
+    2024-03-01,31,oil,89.23242118166027,2766.2050566314683,7866.205056631468,0
+    2024-04-01,30,oil,65.95135187910537,1978.540556373161,9844.74561300463,1
+    2024-05-01,3,oil,55.71267535170227,167.13802605510682,10011.883639059737,1
+    ");
+    let last = rows.last().unwrap();
+    assert!((last.cumulative_volume - forecast.cumulative_volume()).abs() < 1e-9);
+}
+
+#[test]
+fn annual_flat_table_with_end_date() {
+    let forecast = Forecast::new(vec![
+        FlatParameters::from_incremental_duration(
+            ProductionRate::new(100.),
+            AverageDaysTime { days: 45. },
+        )
+        .unwrap()
+        .into(),
+        ExponentialParameters::from_incremental_duration(
+            ProductionRate::new(100.),
+            NominalDeclineRate::new(0.01),
+            AverageDaysTime { days: 60. },
+        )
+        .unwrap()
+        .into(),
+    ])
+    .with_initial_cumulative_volume(1000.)
+    .unwrap();
+
+    let options = FlatTableOptions {
+        frequency: ReportFrequency::Annual,
+        end_date: Some(CalendarDate::new(2024, 3, 1).unwrap()),
+        ..FlatTableOptions::new(CalendarDate::new(2024, 1, 20).unwrap(), Phase::Gas)
+    };
+    let rows = flat_table(&forecast, &options).unwrap();
+
+    insta::assert_snapshot!(to_csv(&rows), @r"
+    date,days,phase,rate,volume,cumulative_volume,segment_index
+    2024-01-20,41,gas,100,4100,5100,0
+    ");
+}
@@ -0,0 +1,61 @@
+#![cfg(feature = "fast-math")]
+
+use decline_curve_analysis::{
+    AverageDaysTime, HyperbolicParameters, NominalDeclineRate, ProductionRate, Segment,
+};
+
+fn assert_relative_eq(actual: f64, expected: f64) {
+    let error = ((actual - expected) / expected).abs();
+    assert!(
+        error <= 1e-7,
+        "{actual} differs from {expected} by a relative error of {error}"
+    );
+}
+
+#[test]
+fn hyperbolic_matches_exact_evaluation() {
+    let initial_rate = 1000.;
+    for exponent in [0.01, 0.3, 0.5, 0.9, 1.5, 2.5] {
+        for decline_rate in [0.0001, 0.002, 0.05] {
+            let parameters = HyperbolicParameters::from_incremental_duration(
+                ProductionRate::new(initial_rate),
+                NominalDeclineRate::new(decline_rate),
+                AverageDaysTime { days: 18250. },
+                exponent,
+            )
+            .unwrap();
+
+            for days in [0.5, 1., 30.4, 365.25, 1000., 5000., 18250.] {
+                let base = 1. + exponent * decline_rate * days;
+                let rate = initial_rate * base.powf(-1. / exponent);
+                let volume = initial_rate / (decline_rate * (1. - exponent))
+                    * (1. - base.powf(1. - 1. / exponent));
+
+                let time = AverageDaysTime { days };
+                assert_relative_eq(parameters.rate_at_time(time).value(), rate);
+                assert_relative_eq(parameters.incremental_volume_at_time(time), volume);
+            }
+        }
+    }
+}
+
+#[test]
+fn sampler_matches_exact_evaluation() {
+    let segment: Segment<AverageDaysTime> = HyperbolicParameters::from_incremental_duration(
+        ProductionRate::new(500.),
+        NominalDeclineRate::new(0.003),
+        AverageDaysTime { days: 3650. },
+        1.1,
+    )
+    .unwrap()
+    .into();
+
+    for point in segment
+        .sampler(AverageDaysTime { days: 1. })
+        .unwrap()
+        .skip(1)
+    {
+        let base = 1. + 1.1 * 0.003 * point.time.days;
+        assert_relative_eq(point.rate.value(), 500. * base.powf(-1. / 1.1));
+    }
+}
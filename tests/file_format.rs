@@ -0,0 +1,112 @@
+#![cfg(feature = "json")]
+
+use decline_curve_analysis::{
+    AverageDaysTime, AverageYearsTime, ExponentialParameters, FlatParameters, Forecast,
+    NominalDeclineRate, ProductionRate,
+};
+
+#[test]
+fn file_round_trip() {
+    let forecast = Forecast::new(vec![
+        FlatParameters::from_incremental_duration(
+            ProductionRate::new(1000.),
+            AverageYearsTime { years: 1. },
+        )
+        .unwrap()
+        .into(),
+        ExponentialParameters::from_incremental_duration(
+            ProductionRate::new(1000.),
+            NominalDeclineRate::new(0.1),
+            AverageYearsTime { years: 10. },
+        )
+        .unwrap()
+        .into(),
+    ]);
+    let contents = forecast.to_file_string().unwrap();
+    insta::assert_snapshot!(contents, @r#"
+    {
+      "format": "decline-curve-analysis/forecast",
+      "version": 1,
+      "time_unit_days": 365.25,
+      "forecast": {
+        "segments": [
+          {
+            "type": "flat",
+            "rate": 1000.0,
+            "incremental_duration": 1.0
+          },
+          {
+            "type": "exponential",
+            "initial_rate": 1000.0,
+            "decline_rate": 0.1,
+            "incremental_duration": 10.0
+          }
+        ]
+      }
+    }
+    "#);
+    assert_eq!(Forecast::from_file_str(&contents).unwrap(), forecast);
+
+    let path = std::env::temp_dir().join("decline-curve-analysis-file-round-trip.json");
+    forecast.to_file(&path).unwrap();
+    assert_eq!(Forecast::from_file(&path).unwrap(), forecast);
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn migrate_bare_forecast() {
+    let forecast = Forecast::new(vec![
+        FlatParameters::from_incremental_duration(
+            ProductionRate::new(1000.),
+            AverageYearsTime { years: 1. },
+        )
+        .unwrap()
+        .into(),
+        ExponentialParameters::from_incremental_duration(
+            ProductionRate::new(1000.),
+            NominalDeclineRate::new(0.1),
+            AverageYearsTime { years: 10. },
+        )
+        .unwrap()
+        .into(),
+    ]);
+
+    // Forecasts serialized directly are version 0 of the file format.
+    let contents = serde_json::to_string(&forecast).unwrap();
+    assert_eq!(Forecast::from_file_str(&contents).unwrap(), forecast);
+}
+
+#[test]
+fn unsupported_files() {
+    let forecast = Forecast::new(vec![
+        FlatParameters::from_incremental_duration(
+            ProductionRate::new(1000.),
+            AverageYearsTime { years: 1. },
+        )
+        .unwrap()
+        .into(),
+        ExponentialParameters::from_incremental_duration(
+            ProductionRate::new(1000.),
+            NominalDeclineRate::new(0.1),
+            AverageYearsTime { years: 10. },
+        )
+        .unwrap()
+        .into(),
+    ]);
+
+    let contents = forecast.to_file_string().unwrap();
+
+    let result = Forecast::<AverageDaysTime>::from_file_str(&contents);
+    insta::assert_snapshot!(result.unwrap_err(), @"forecast file has a time unit of 365.25 days, but expected 1 days");
+
+    let newer = contents.replace("\"version\": 1", "\"version\": 2");
+    let result = Forecast::<AverageYearsTime>::from_file_str(&newer);
+    insta::assert_snapshot!(result.unwrap_err(), @"failed to read input: forecast file version 2 is newer than the supported version 1");
+
+    // Unknown fields are ignored.
+    let extended = contents.replace("\"version\": 1", "\"version\": 1, \"author\": \"engineer\"");
+    assert_eq!(Forecast::from_file_str(&extended).unwrap(), forecast);
+
+    let result = Forecast::<AverageYearsTime>::from_file_str("{\"format\": \"other\"");
+    insta::assert_snapshot!(result.unwrap_err(), @"failed to read input: EOF while parsing an object at line 1 column 18");
+}
@@ -0,0 +1,628 @@
+use decline_curve_analysis::{
+    AverageDaysTime, ExponentialParameters, FitLoss, FitModel, FitOptions, FitSpace,
+    HarmonicParameters, HyperbolicParameters, LinearParameters, LowessSmoothing,
+    ModelSelectionCriterion, NominalDeclineRate, ProductionRate, Segment, SigmaClipping,
+    estimate_exponent, fit_best, fit_exponential, fit_harmonic, fit_hyperbolic, fit_linear,
+};
+
+/// Samples a rate function monthly for three years.
+fn samples(
+    rate_at_time: impl Fn(AverageDaysTime) -> ProductionRate<AverageDaysTime>,
+) -> Vec<(AverageDaysTime, ProductionRate<AverageDaysTime>)> {
+    (0..36)
+        .map(|month| {
+            let time = AverageDaysTime {
+                days: month as f64 * 30.4375,
+            };
+            (time, rate_at_time(time))
+        })
+        .collect()
+}
+
+#[test]
+fn fit_exponential_recovers_parameters() {
+    let expected = ExponentialParameters::from_incremental_duration(
+        ProductionRate::new(500.),
+        NominalDeclineRate::new(0.002),
+        AverageDaysTime { days: 1500. },
+    )
+    .unwrap();
+
+    let fit = fit_exponential(
+        &samples(|time| expected.rate_at_time(time)),
+        &FitOptions::default(),
+    )
+    .unwrap();
+
+    // Use `f32` so the snapshots are less sensitive to solver round-off.
+    insta::assert_snapshot!(fit.parameters.initial_rate().value() as f32, @"500");
+    insta::assert_snapshot!(fit.parameters.decline_rate().value() as f32, @"0.002");
+    insta::assert_snapshot!(fit.parameters.incremental_duration().days, @"1065.3125");
+    insta::assert_snapshot!(fit.statistics.r_squared as f32, @"1");
+}
+
+#[test]
+fn fit_harmonic_recovers_parameters() {
+    let expected = HarmonicParameters::from_incremental_duration(
+        ProductionRate::new(500.),
+        NominalDeclineRate::new(0.004),
+        AverageDaysTime { days: 1500. },
+    )
+    .unwrap();
+
+    let fit = fit_harmonic(
+        &samples(|time| expected.rate_at_time(time)),
+        &FitOptions::default(),
+    )
+    .unwrap();
+
+    insta::assert_snapshot!(fit.parameters.initial_rate().value() as f32, @"500");
+    insta::assert_snapshot!(fit.parameters.initial_decline_rate().value() as f32, @"0.004");
+}
+
+#[test]
+fn fit_hyperbolic_recovers_parameters() {
+    let expected = HyperbolicParameters::from_incremental_duration(
+        ProductionRate::new(500.),
+        NominalDeclineRate::new(0.004),
+        AverageDaysTime { days: 1500. },
+        0.8,
+    )
+    .unwrap();
+
+    let fit = fit_hyperbolic(
+        &samples(|time| expected.rate_at_time(time)),
+        &FitOptions::default(),
+    )
+    .unwrap();
+
+    insta::assert_snapshot!(fit.parameters.initial_rate().value() as f32, @"500");
+    insta::assert_snapshot!(fit.parameters.initial_decline_rate().value() as f32, @"0.004");
+    insta::assert_snapshot!(fit.parameters.exponent() as f32, @"0.8");
+    insta::assert_snapshot!(fit.statistics.root_mean_squared_error < 1e-6, @"true");
+}
+
+#[test]
+fn fit_linear_recovers_parameters() {
+    let expected = LinearParameters::from_incremental_duration(
+        ProductionRate::new(500.),
+        NominalDeclineRate::new(0.0005),
+        AverageDaysTime { days: 1500. },
+    )
+    .unwrap();
+
+    let fit = fit_linear(
+        &samples(|time| expected.rate_at_time(time)),
+        &FitOptions::default(),
+    )
+    .unwrap();
+
+    insta::assert_snapshot!(fit.parameters.initial_rate().value() as f32, @"500");
+    insta::assert_snapshot!(fit.parameters.decline_rate().value() as f32, @"0.0005");
+}
+
+#[test]
+fn fit_noisy_samples() {
+    let expected = HyperbolicParameters::from_incremental_duration(
+        ProductionRate::new(500.),
+        NominalDeclineRate::new(0.004),
+        AverageDaysTime { days: 1500. },
+        0.8,
+    )
+    .unwrap();
+
+    // Alternate the noise so it's deterministic.
+    let noisy: Vec<_> = samples(|time| expected.rate_at_time(time))
+        .into_iter()
+        .enumerate()
+        .map(|(i, (time, rate))| {
+            let noise = if i % 2 == 0 { 1.05 } else { 0.95 };
+            (time, ProductionRate::new(rate.value() * noise))
+        })
+        .collect();
+
+    let fit = fit_hyperbolic(&noisy, &FitOptions::default()).unwrap();
+
+    insta::assert_snapshot!(fit.parameters.exponent() as f32, @"0.85298914");
+    insta::assert_snapshot!(fit.statistics.sample_count, @"36");
+    insta::assert_snapshot!(fit.statistics.r_squared as f32, @"0.99098825");
+    insta::assert_snapshot!(fit.residuals.len(), @"36");
+}
+
+#[test]
+fn not_enough_samples() {
+    let samples = [
+        (AverageDaysTime { days: 0. }, ProductionRate::new(100.)),
+        (AverageDaysTime { days: 30. }, ProductionRate::new(90.)),
+    ];
+
+    let result = fit_exponential(&samples, &FitOptions::default());
+    insta::assert_snapshot!(result.unwrap_err(), @"sample count is 2, but expected at least 3");
+
+    let result = fit_hyperbolic(&samples, &FitOptions::default());
+    insta::assert_snapshot!(result.unwrap_err(), @"sample count is 2, but expected at least 4");
+}
+
+#[test]
+fn invalid_samples() {
+    let samples = [
+        (AverageDaysTime { days: 0. }, ProductionRate::new(100.)),
+        (AverageDaysTime { days: 30. }, ProductionRate::new(f64::NAN)),
+        (AverageDaysTime { days: 60. }, ProductionRate::new(80.)),
+    ];
+    let result = fit_exponential(&samples, &FitOptions::default());
+    insta::assert_snapshot!(result.unwrap_err(), @"sample rate is not-a-number, but expected a finite number");
+
+    let samples = [
+        (AverageDaysTime { days: 0. }, ProductionRate::new(100.)),
+        (AverageDaysTime { days: -30. }, ProductionRate::new(90.)),
+        (AverageDaysTime { days: 60. }, ProductionRate::new(80.)),
+    ];
+    let result = fit_exponential(&samples, &FitOptions::default());
+    insta::assert_snapshot!(result.unwrap_err(), @"sample time is negative, but expected a positive number");
+}
+
+#[test]
+fn fit_best_selects_hyperbolic() {
+    let expected = HyperbolicParameters::from_incremental_duration(
+        ProductionRate::new(500.),
+        NominalDeclineRate::new(0.004),
+        AverageDaysTime { days: 1500. },
+        0.8,
+    )
+    .unwrap();
+
+    let result = fit_best(
+        &samples(|time| expected.rate_at_time(time)),
+        ModelSelectionCriterion::Aic,
+        &FitOptions::default(),
+    )
+    .unwrap();
+
+    let Segment::Hyperbolic(parameters) = &result.best.parameters else {
+        panic!(
+            "expected a hyperbolic fit, got {:?}",
+            result.best.parameters
+        );
+    };
+    insta::assert_snapshot!(parameters.exponent() as f32, @"0.8");
+
+    let ranking: Vec<_> = result.ranking.iter().map(|score| score.model).collect();
+    insta::assert_debug_snapshot!(ranking, @"
+    [
+        Hyperbolic,
+        Harmonic,
+        Exponential,
+        Linear,
+    ]
+    ");
+}
+
+#[test]
+fn fit_best_selects_linear() {
+    let expected = LinearParameters::from_incremental_duration(
+        ProductionRate::new(500.),
+        NominalDeclineRate::new(0.0005),
+        AverageDaysTime { days: 1500. },
+    )
+    .unwrap();
+
+    for criterion in [
+        ModelSelectionCriterion::Aic,
+        ModelSelectionCriterion::Bic,
+        ModelSelectionCriterion::Rmse,
+    ] {
+        let result = fit_best(
+            &samples(|time| expected.rate_at_time(time)),
+            criterion,
+            &FitOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(result.ranking[0].model, FitModel::Linear);
+        assert!(matches!(result.best.parameters, Segment::Linear(_)));
+    }
+}
+
+#[test]
+fn fit_best_not_enough_samples() {
+    let samples = [(AverageDaysTime { days: 0. }, ProductionRate::new(100.))];
+
+    let result = fit_best(
+        &samples,
+        ModelSelectionCriterion::Aic,
+        &FitOptions::default(),
+    );
+    insta::assert_snapshot!(result.unwrap_err(), @"sample count is 1, but expected at least 3");
+}
+
+/// Samples a hyperbolic decline with downtime spikes every sixth month.
+fn samples_with_outliers() -> Vec<(AverageDaysTime, ProductionRate<AverageDaysTime>)> {
+    let expected = HyperbolicParameters::from_incremental_duration(
+        ProductionRate::new(500.),
+        NominalDeclineRate::new(0.004),
+        AverageDaysTime { days: 1500. },
+        0.8,
+    )
+    .unwrap();
+
+    samples(|time| expected.rate_at_time(time))
+        .into_iter()
+        .enumerate()
+        .map(|(i, (time, rate))| {
+            let factor = if i % 6 == 5 { 0.2 } else { 1. };
+            (time, ProductionRate::new(rate.value() * factor))
+        })
+        .collect()
+}
+
+#[test]
+fn fit_with_outliers() {
+    let fit = fit_hyperbolic(&samples_with_outliers(), &FitOptions::default()).unwrap();
+
+    // Without any robust options, the outliers skew the fit.
+    insta::assert_snapshot!(fit.parameters.exponent() as f32, @"0.988552");
+    insta::assert_snapshot!(fit.parameters.initial_decline_rate().value() as f32, @"0.005953028");
+}
+
+#[test]
+fn fit_with_huber_loss() {
+    let options = FitOptions {
+        loss: FitLoss::Huber { threshold: 1.345 },
+        ..FitOptions::default()
+    };
+    let fit = fit_hyperbolic(&samples_with_outliers(), &options).unwrap();
+
+    insta::assert_snapshot!(fit.parameters.exponent() as f32, @"0.8");
+    insta::assert_snapshot!(fit.parameters.initial_decline_rate().value() as f32, @"0.004");
+}
+
+#[test]
+fn fit_with_sigma_clipping() {
+    let options = FitOptions {
+        sigma_clipping: Some(SigmaClipping {
+            sigma: 2.,
+            max_iterations: 10,
+        }),
+        ..FitOptions::default()
+    };
+    let fit = fit_hyperbolic(&samples_with_outliers(), &options).unwrap();
+
+    insta::assert_snapshot!(fit.parameters.exponent() as f32, @"0.8");
+    insta::assert_snapshot!(fit.parameters.initial_decline_rate().value() as f32, @"0.004");
+    insta::assert_debug_snapshot!(fit.rejected_samples, @"
+    [
+        5,
+        11,
+        17,
+        23,
+        29,
+        35,
+    ]
+    ");
+    insta::assert_snapshot!(fit.statistics.sample_count, @"30");
+}
+
+#[test]
+fn fit_with_lowess() {
+    let options = FitOptions {
+        lowess: Some(LowessSmoothing { fraction: 0.3 }),
+        ..FitOptions::default()
+    };
+    let fit = fit_hyperbolic(&samples_with_outliers(), &options).unwrap();
+
+    insta::assert_snapshot!(fit.parameters.exponent() as f32, @"0.80981725");
+    insta::assert_snapshot!(fit.residuals.len(), @"36");
+}
+
+#[test]
+fn invalid_fit_options() {
+    let options = FitOptions {
+        loss: FitLoss::Huber { threshold: -1. },
+        ..FitOptions::default()
+    };
+    let result = fit_hyperbolic(&samples_with_outliers(), &options);
+    insta::assert_snapshot!(result.unwrap_err(), @"Huber threshold is negative or zero, but expected a positive number");
+
+    let options = FitOptions {
+        lowess: Some(LowessSmoothing { fraction: 0. }),
+        ..FitOptions::default()
+    };
+    let result = fit_hyperbolic(&samples_with_outliers(), &options);
+    insta::assert_snapshot!(result.unwrap_err(), @"LOWESS fraction is 0, but expected it to be greater than 0 and at most 1");
+
+    let options = FitOptions {
+        initial_exponent: Some(3.),
+        ..FitOptions::default()
+    };
+    let result = fit_hyperbolic(&samples_with_outliers(), &options);
+    insta::assert_snapshot!(result.unwrap_err(), @"exponent is 3, but expected it to be between 0.001 and 2");
+}
+
+#[test]
+fn fit_with_zero_weight_outliers() {
+    let samples = samples_with_outliers();
+    let weights = (0..samples.len())
+        .map(|i| if i % 6 == 5 { 0. } else { 1. })
+        .collect();
+    let options = FitOptions {
+        weights: Some(weights),
+        ..FitOptions::default()
+    };
+    let fit = fit_hyperbolic(&samples, &options).unwrap();
+
+    insta::assert_snapshot!(fit.parameters.exponent() as f32, @"0.8");
+    insta::assert_snapshot!(fit.parameters.initial_decline_rate().value() as f32, @"0.004");
+    insta::assert_snapshot!(fit.statistics.sample_count, @"30");
+    insta::assert_debug_snapshot!(fit.rejected_samples, @"[]");
+}
+
+#[test]
+fn fit_with_recent_weights() {
+    // Weight recent samples more heavily, which pulls the fit towards the later rates.
+    let samples = samples_with_outliers();
+    let weights = (0..samples.len()).map(|i| 1. + i as f64).collect();
+    let options = FitOptions {
+        weights: Some(weights),
+        ..FitOptions::default()
+    };
+    let fit = fit_hyperbolic(&samples, &options).unwrap();
+
+    insta::assert_snapshot!(fit.parameters.exponent() as f32, @"0.90379995");
+}
+
+#[test]
+fn fit_in_log_rate_space() {
+    let expected = HyperbolicParameters::from_incremental_duration(
+        ProductionRate::new(500.),
+        NominalDeclineRate::new(0.004),
+        AverageDaysTime { days: 1500. },
+        0.8,
+    )
+    .unwrap();
+
+    // Include a zero-rate shut-in month, which is excluded in log space.
+    let mut samples = samples(|time| expected.rate_at_time(time));
+    samples[10].1 = ProductionRate::new(0.);
+
+    let options = FitOptions {
+        space: FitSpace::LogRate,
+        ..FitOptions::default()
+    };
+    let fit = fit_hyperbolic(&samples, &options).unwrap();
+
+    insta::assert_snapshot!(fit.parameters.initial_rate().value() as f32, @"500");
+    insta::assert_snapshot!(fit.parameters.exponent() as f32, @"0.8");
+    insta::assert_snapshot!(fit.statistics.sample_count, @"35");
+}
+
+#[test]
+fn invalid_weights() {
+    let options = FitOptions {
+        weights: Some(vec![1.; 3]),
+        ..FitOptions::default()
+    };
+    let result = fit_hyperbolic(&samples_with_outliers(), &options);
+    insta::assert_snapshot!(result.unwrap_err(), @"weights has a length of 3, but expected 36");
+
+    let mut weights = vec![1.; 36];
+    weights[3] = -1.;
+    let options = FitOptions {
+        weights: Some(weights),
+        ..FitOptions::default()
+    };
+    let result = fit_hyperbolic(&samples_with_outliers(), &options);
+    insta::assert_snapshot!(result.unwrap_err(), @"weight is negative, but expected a positive number");
+
+    let options = FitOptions {
+        weights: Some(vec![0.; 36]),
+        ..FitOptions::default()
+    };
+    let result = fit_hyperbolic(&samples_with_outliers(), &options);
+    insta::assert_snapshot!(result.unwrap_err(), @"active sample count is 0, but expected at least 4");
+}
+
+/// Adds deterministic, alternating noise to samples.
+fn with_noise(
+    samples: Vec<(AverageDaysTime, ProductionRate<AverageDaysTime>)>,
+) -> Vec<(AverageDaysTime, ProductionRate<AverageDaysTime>)> {
+    samples
+        .into_iter()
+        .enumerate()
+        .map(|(i, (time, rate))| {
+            let noise = [0.04, -0.03, -0.05, 0.02, 0.03][i % 5];
+            (time, ProductionRate::new(rate.value() * (1. + noise)))
+        })
+        .collect()
+}
+
+#[test]
+fn fit_covariance_and_confidence_band() {
+    let expected = HyperbolicParameters::from_incremental_duration(
+        ProductionRate::new(500.),
+        NominalDeclineRate::new(0.004),
+        AverageDaysTime { days: 1500. },
+        0.8,
+    )
+    .unwrap();
+    let samples = with_noise(samples(|time| expected.rate_at_time(time)));
+
+    let fit = fit_hyperbolic(&samples, &FitOptions::default()).unwrap();
+    let covariance = fit.covariance.as_ref().unwrap();
+    assert_eq!(covariance.model(), FitModel::Hyperbolic);
+    assert_eq!(covariance.matrix().len(), 3);
+    let standard_errors: Vec<_> = covariance
+        .standard_errors()
+        .iter()
+        .map(|error| *error as f32)
+        .collect();
+    insta::assert_debug_snapshot!(standard_errors, @"
+    [
+        6.376509,
+        0.00021269612,
+        0.054319445,
+    ]
+    ");
+
+    let time = AverageDaysTime { days: 2000. };
+    let narrow = fit.confidence_band(time, 0.5).unwrap();
+    let wide = fit.confidence_band(time, 0.95).unwrap();
+    assert!(
+        wide.lower.value() < narrow.lower.value() && narrow.lower.value() < narrow.rate.value()
+    );
+    assert!(
+        narrow.rate.value() < narrow.upper.value() && narrow.upper.value() < wide.upper.value()
+    );
+    insta::assert_snapshot!(
+        format!("{} {} {}", wide.lower.value() as f32, wide.rate.value() as f32, wide.upper.value() as f32),
+        @"38.094135 42.574387 47.054638"
+    );
+
+    // Bands widen as the forecast is extrapolated further from the samples.
+    let near = fit
+        .confidence_band(AverageDaysTime { days: 500. }, 0.95)
+        .unwrap();
+    assert!(near.upper.value() - near.lower.value() < wide.upper.value() - wide.lower.value());
+
+    insta::assert_snapshot!(fit.confidence_band(time, 1.).unwrap_err(), @"confidence level is 1, but expected it to be greater than 0 and less than 1");
+}
+
+#[test]
+fn log_rate_confidence_band_is_positive() {
+    let expected = ExponentialParameters::from_incremental_duration(
+        ProductionRate::new(500.),
+        NominalDeclineRate::new(0.002),
+        AverageDaysTime { days: 1500. },
+    )
+    .unwrap();
+    let samples = with_noise(samples(|time| expected.rate_at_time(time)));
+
+    let fit = fit_exponential(
+        &samples,
+        &FitOptions {
+            space: FitSpace::LogRate,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    let band = fit
+        .confidence_band(AverageDaysTime { days: 10000. }, 0.99)
+        .unwrap();
+    assert!(band.lower.value() > 0.);
+    assert!(band.lower.value() < band.rate.value() && band.rate.value() < band.upper.value());
+}
+
+#[test]
+fn exact_fit_has_zero_width_band() {
+    let expected = ExponentialParameters::from_incremental_duration(
+        ProductionRate::new(500.),
+        NominalDeclineRate::new(0.002),
+        AverageDaysTime { days: 1500. },
+    )
+    .unwrap();
+
+    let fit = fit_exponential(
+        &samples(|time| expected.rate_at_time(time)),
+        &FitOptions::default(),
+    )
+    .unwrap();
+    let band = fit
+        .confidence_band(AverageDaysTime { days: 100. }, 0.95)
+        .unwrap();
+    assert!((band.upper.value() - band.lower.value()) < 1e-6);
+}
+
+#[test]
+fn rate_jacobian_matches_finite_difference() {
+    let cases: [(FitModel, &[f64]); 5] = [
+        (FitModel::Exponential, &[500., 0.002]),
+        (FitModel::Harmonic, &[500., 0.002]),
+        (FitModel::Hyperbolic, &[500., 0.002, 0.8]),
+        (FitModel::Hyperbolic, &[500., 0.002, 0.1]),
+        (FitModel::Linear, &[500., 0.0002]),
+    ];
+
+    for (model, parameters) in cases {
+        for time in [0., 100., 1000.] {
+            let jacobian = model.rate_jacobian(parameters, time);
+            assert_eq!(jacobian.len(), model.parameter_count());
+
+            for (i, actual) in jacobian.into_iter().enumerate() {
+                let step = 1e-6 * parameters[i];
+                let mut shifted = parameters.to_vec();
+                shifted[i] = parameters[i] + step;
+                let upper = model.rate(&shifted, time);
+                shifted[i] = parameters[i] - step;
+                let lower = model.rate(&shifted, time);
+                let expected = (upper - lower) / (2. * step);
+
+                assert!(
+                    (actual - expected).abs() <= 1e-6 * expected.abs().max(1.),
+                    "{model:?} parameter {i} at {time}: expected {expected}, got {actual}"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn estimate_hyperbolic_exponent() {
+    let expected = HyperbolicParameters::from_incremental_duration(
+        ProductionRate::new(500.),
+        NominalDeclineRate::new(0.004),
+        AverageDaysTime { days: 1500. },
+        0.8,
+    )
+    .unwrap();
+    let samples = samples(|time| expected.rate_at_time(time));
+
+    let estimate = estimate_exponent(&samples, LowessSmoothing { fraction: 0.3 }).unwrap();
+    insta::assert_snapshot!(estimate.exponent as f32, @"0.75642157");
+    insta::assert_snapshot!(estimate.r_squared as f32, @"0.9933316");
+    insta::assert_snapshot!(estimate.sample_count, @"36");
+    assert_eq!(estimate.exponents.len(), estimate.sample_count);
+
+    // The estimate seeds a fit of noisy samples.
+    let fit = fit_hyperbolic(
+        &with_noise(samples),
+        &FitOptions {
+            initial_exponent: Some(estimate.exponent),
+            ..FitOptions::default()
+        },
+    )
+    .unwrap();
+    insta::assert_snapshot!(fit.parameters.exponent() as f32, @"0.84154576");
+}
+
+#[test]
+fn estimate_exponential_exponent() {
+    let expected = ExponentialParameters::from_incremental_duration(
+        ProductionRate::new(500.),
+        NominalDeclineRate::new(0.002),
+        AverageDaysTime { days: 1500. },
+    )
+    .unwrap();
+
+    // An exponential decline has a constant loss ratio, so the exponent is at the lower bound.
+    let estimate = estimate_exponent(
+        &samples(|time| expected.rate_at_time(time)),
+        LowessSmoothing { fraction: 0.3 },
+    )
+    .unwrap();
+    insta::assert_snapshot!(estimate.exponent, @"0.001");
+    insta::assert_snapshot!(estimate.standard_deviation < 1e-6, @"true");
+}
+
+#[test]
+fn estimate_exponent_invalid_samples() {
+    let samples = [100., 110., 120., 130., 140.].map(|rate| {
+        (
+            AverageDaysTime { days: rate },
+            ProductionRate::<AverageDaysTime>::new(rate),
+        )
+    });
+    let result = estimate_exponent(&samples, LowessSmoothing { fraction: 0.5 });
+    insta::assert_snapshot!(result.unwrap_err(), @"declining sample count is 0, but expected at least 4");
+
+    let result = estimate_exponent(&samples, LowessSmoothing { fraction: 2. });
+    insta::assert_snapshot!(result.unwrap_err(), @"LOWESS fraction is 2, but expected it to be greater than 0 and at most 1");
+}
@@ -0,0 +1,106 @@
+use decline_curve_analysis::{
+    AverageYearsTime, ExponentialParameters, FixedForecast, FlatParameters, Forecast,
+    HyperbolicParameters, NominalDeclineRate, ProductionRate, Segment,
+};
+
+fn years(years: f64) -> AverageYearsTime {
+    AverageYearsTime { years }
+}
+
+fn segments() -> [Segment<AverageYearsTime>; 3] {
+    [
+        FlatParameters::from_incremental_duration(ProductionRate::new(1000.), years(0.5))
+            .unwrap()
+            .into(),
+        HyperbolicParameters::from_incremental_duration(
+            ProductionRate::new(1000.),
+            NominalDeclineRate::new(0.8),
+            years(4.5),
+            1.1,
+        )
+        .unwrap()
+        .into(),
+        ExponentialParameters::from_incremental_duration(
+            ProductionRate::new(250.),
+            NominalDeclineRate::new(0.08),
+            years(25.),
+        )
+        .unwrap()
+        .into(),
+    ]
+}
+
+#[test]
+#[cfg_attr(feature = "fast-math", ignore = "exact values differ with fast-math")]
+fn matches_heap_backed_forecast() {
+    let fixed = FixedForecast::new(segments())
+        .with_initial_cumulative_volume(5000.)
+        .unwrap();
+    let forecast = Forecast::from(fixed.clone());
+    assert_eq!(forecast.segments(), fixed.segments());
+
+    assert_eq!(
+        fixed.incremental_duration(),
+        forecast.incremental_duration()
+    );
+    assert_eq!(fixed.incremental_volume(), forecast.incremental_volume());
+    assert_eq!(fixed.cumulative_volume(), forecast.cumulative_volume());
+    assert_eq!(fixed.final_rate(), forecast.final_rate());
+    for time in [0., 0.25, 0.5, 3., 12., 40.].map(years) {
+        assert_eq!(fixed.rate_at_time(time), forecast.rate_at_time(time));
+        assert_eq!(
+            fixed.decline_rate_at_time(time),
+            forecast.decline_rate_at_time(time)
+        );
+        assert_eq!(
+            fixed.cumulative_volume_at_time(time),
+            forecast.cumulative_volume_at_time(time)
+        );
+        assert_eq!(
+            fixed.incremental_volume_between(years(1.), time),
+            forecast.incremental_volume_between(years(1.), time)
+        );
+    }
+
+    assert_eq!(
+        fixed.time_at_rate(ProductionRate::new(100.)).unwrap(),
+        forecast.time_at_rate(ProductionRate::new(100.)).unwrap()
+    );
+    assert_eq!(
+        fixed.time_at_incremental_volume(1000.).unwrap(),
+        forecast.time_at_incremental_volume(1000.).unwrap()
+    );
+    assert!(
+        fixed
+            .volumes_by_period(years(1.))
+            .unwrap()
+            .eq(forecast.volumes_by_period(years(1.)).unwrap())
+    );
+    insta::assert_snapshot!(fixed.incremental_volume(), @"5160.98942094692");
+}
+
+#[test]
+fn conversion_requires_matching_segment_count() {
+    let forecast = Forecast::new(segments().to_vec());
+    let fixed = FixedForecast::<_, 3>::try_from(forecast.clone()).unwrap();
+    assert_eq!(fixed.into_segments(), segments());
+
+    let error = FixedForecast::<_, 2>::try_from(forecast).unwrap_err();
+    insta::assert_snapshot!(error, @"segments has a length of 3, but expected 2");
+}
+
+#[test]
+fn empty_fixed_forecast() {
+    let empty = FixedForecast::<AverageYearsTime, 0>::new([]);
+    assert_eq!(empty.rate_at_time(years(1.)).value(), 0.);
+    assert_eq!(empty.incremental_volume(), 0.);
+}
+
+#[test]
+fn fixed_forecast_fingerprint() {
+    let fixed = FixedForecast::new(segments());
+    assert_eq!(
+        fixed.fingerprint(),
+        Forecast::from(fixed.clone()).fingerprint()
+    );
+}
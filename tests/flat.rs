@@ -1,4 +1,6 @@
-use decline_curve_analysis::{AverageDaysTime, FlatParameters, ProductionRate};
+use decline_curve_analysis::{
+    AverageDaysTime, FlatParameters, Forecast, ProductionRate, TypeCurve,
+};
 use proptest::prelude::*;
 
 #[test]
@@ -60,7 +62,29 @@ fn flat_zero_rate_from_volume_errors() {
     let incremental_volume = 1000.;
 
     let result = FlatParameters::from_incremental_volume(rate, incremental_volume);
-    insta::assert_snapshot!(result.unwrap_err(), @"cannot solve decline: no finite solution exists for the given parameters");
+    insta::assert_snapshot!(result.unwrap_err(), @"a zero rate can't produce an incremental volume of 1000");
+}
+
+#[test]
+fn flat_zero() {
+    let params = FlatParameters::zero(AverageDaysTime { days: 30. }).unwrap();
+
+    insta::assert_snapshot!(params.rate().value(), @"0");
+    insta::assert_snapshot!(params.incremental_duration().days, @"30");
+    insta::assert_snapshot!(params.incremental_volume(), @"0");
+
+    // Unlike a delay, a zero-rate flat segment is part of the producing profile.
+    let type_curve = TypeCurve::new(Forecast::new(vec![params.into()]));
+    insta::assert_debug_snapshot!(type_curve.initial_rate().map(|rate| rate.value()), @r"
+    Some(
+        0.0,
+    )
+    ");
+
+    insta::assert_snapshot!(
+        FlatParameters::zero(AverageDaysTime { days: -1. }).unwrap_err(),
+        @"duration is negative, but expected a positive number"
+    );
 }
 
 #[test]
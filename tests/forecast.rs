@@ -0,0 +1,379 @@
+use decline_curve_analysis::{
+    AverageDaysTime, ExponentialParameters, FlatParameters, Forecast, HyperbolicParameters,
+    NominalDeclineRate, ProductionRate,
+};
+
+#[test]
+fn forecast_totals() {
+    let forecast = Forecast::new(vec![
+        FlatParameters::from_incremental_duration(
+            ProductionRate::new(100.),
+            AverageDaysTime { days: 30. },
+        )
+        .unwrap()
+        .into(),
+        ExponentialParameters::from_incremental_duration(
+            ProductionRate::new(100.),
+            NominalDeclineRate::new(0.001),
+            AverageDaysTime { days: 1000. },
+        )
+        .unwrap()
+        .into(),
+    ]);
+
+    insta::assert_snapshot!(forecast.incremental_duration().days, @"1030");
+    insta::assert_snapshot!(forecast.incremental_volume(), @"66212.05588285576");
+    insta::assert_snapshot!(forecast.final_rate().value(), @"36.787944117144235");
+}
+
+#[test]
+fn forecast_rate_at_time() {
+    let forecast = Forecast::new(vec![
+        FlatParameters::from_incremental_duration(
+            ProductionRate::new(100.),
+            AverageDaysTime { days: 30. },
+        )
+        .unwrap()
+        .into(),
+        ExponentialParameters::from_incremental_duration(
+            ProductionRate::new(100.),
+            NominalDeclineRate::new(0.001),
+            AverageDaysTime { days: 1000. },
+        )
+        .unwrap()
+        .into(),
+    ]);
+
+    insta::assert_snapshot!(forecast.rate_at_time(AverageDaysTime { days: -1. }).value(), @"100");
+    insta::assert_snapshot!(forecast.rate_at_time(AverageDaysTime { days: 30. }).value(), @"100");
+    insta::assert_snapshot!(forecast.rate_at_time(AverageDaysTime { days: 530. }).value(), @"60.653065971263345");
+    insta::assert_snapshot!(forecast.rate_at_time(AverageDaysTime { days: 5000. }).value(), @"36.787944117144235");
+}
+
+#[test]
+fn forecast_incremental_volume_at_time() {
+    let forecast = Forecast::new(vec![
+        FlatParameters::from_incremental_duration(
+            ProductionRate::new(100.),
+            AverageDaysTime { days: 30. },
+        )
+        .unwrap()
+        .into(),
+        ExponentialParameters::from_incremental_duration(
+            ProductionRate::new(100.),
+            NominalDeclineRate::new(0.001),
+            AverageDaysTime { days: 1000. },
+        )
+        .unwrap()
+        .into(),
+    ]);
+
+    insta::assert_snapshot!(forecast.incremental_volume_at_time(AverageDaysTime { days: 0. }), @"0");
+    insta::assert_snapshot!(forecast.incremental_volume_at_time(AverageDaysTime { days: 15. }), @"1500");
+    insta::assert_snapshot!(forecast.incremental_volume_at_time(AverageDaysTime { days: 530. }), @"42346.93402873666");
+    assert_eq!(
+        forecast.incremental_volume_at_time(AverageDaysTime { days: 5000. }),
+        forecast.incremental_volume()
+    );
+}
+
+#[test]
+fn empty_forecast() {
+    let forecast = Forecast::<AverageDaysTime>::new(vec![]);
+
+    insta::assert_snapshot!(forecast.incremental_duration().days, @"0");
+    insta::assert_snapshot!(forecast.incremental_volume(), @"0");
+    insta::assert_snapshot!(forecast.rate_at_time(AverageDaysTime { days: 10. }).value(), @"0");
+}
+
+#[test]
+fn forecast_advance_to() {
+    let original = Forecast::new(vec![
+        FlatParameters::from_incremental_duration(
+            ProductionRate::new(100.),
+            AverageDaysTime { days: 30. },
+        )
+        .unwrap()
+        .into(),
+        ExponentialParameters::from_incremental_duration(
+            ProductionRate::new(100.),
+            NominalDeclineRate::new(0.001),
+            AverageDaysTime { days: 1000. },
+        )
+        .unwrap()
+        .into(),
+    ]);
+    let mut forecast = original.clone();
+
+    insta::assert_snapshot!(forecast.advance_to(AverageDaysTime { days: 10. }).unwrap(), @"1000");
+    insta::assert_snapshot!(forecast.segments().len(), @"2");
+    insta::assert_snapshot!(forecast.incremental_duration().days, @"1020");
+
+    insta::assert_snapshot!(forecast.advance_to(AverageDaysTime { days: 520. }).unwrap(), @"41346.93402873666");
+    insta::assert_snapshot!(forecast.segments().len(), @"1");
+    insta::assert_snapshot!(forecast.incremental_duration().days, @"500");
+    insta::assert_snapshot!(forecast.rate_at_time(AverageDaysTime { days: 0. }).value(), @"60.653065971263345");
+
+    insta::assert_snapshot!(forecast.advance_to(AverageDaysTime { days: 5000. }).unwrap(), @"23865.12185411911");
+    insta::assert_snapshot!(forecast.segments().len(), @"0");
+}
+
+#[test]
+fn forecast_advance_to_preserves_volume() {
+    let original = Forecast::new(vec![
+        FlatParameters::from_incremental_duration(
+            ProductionRate::new(100.),
+            AverageDaysTime { days: 30. },
+        )
+        .unwrap()
+        .into(),
+        ExponentialParameters::from_incremental_duration(
+            ProductionRate::new(100.),
+            NominalDeclineRate::new(0.001),
+            AverageDaysTime { days: 1000. },
+        )
+        .unwrap()
+        .into(),
+    ]);
+
+    for days in [0., 30., 100., 1030.] {
+        let mut forecast = original.clone();
+        let produced_volume = forecast.advance_to(AverageDaysTime { days }).unwrap();
+
+        assert!(
+            (produced_volume + forecast.incremental_volume() - original.incremental_volume()).abs()
+                < 1e-9
+        );
+        assert!(
+            (produced_volume - original.incremental_volume_at_time(AverageDaysTime { days })).abs()
+                < 1e-9
+        );
+    }
+}
+
+#[test]
+fn forecast_advance_to_segment_boundary() {
+    let mut forecast = Forecast::new(vec![
+        FlatParameters::from_incremental_duration(
+            ProductionRate::new(100.),
+            AverageDaysTime { days: 30. },
+        )
+        .unwrap()
+        .into(),
+        ExponentialParameters::from_incremental_duration(
+            ProductionRate::new(100.),
+            NominalDeclineRate::new(0.001),
+            AverageDaysTime { days: 1000. },
+        )
+        .unwrap()
+        .into(),
+    ]);
+
+    insta::assert_snapshot!(forecast.advance_to(AverageDaysTime { days: 30. }).unwrap(), @"3000");
+    insta::assert_snapshot!(forecast.segments().len(), @"1");
+    insta::assert_snapshot!(forecast.incremental_duration().days, @"1000");
+    insta::assert_snapshot!(forecast.rate_at_time(AverageDaysTime { days: 0. }).value(), @"100");
+}
+
+#[test]
+fn forecast_advance_to_invalid_time() {
+    let original = Forecast::new(vec![
+        FlatParameters::from_incremental_duration(
+            ProductionRate::new(100.),
+            AverageDaysTime { days: 30. },
+        )
+        .unwrap()
+        .into(),
+        ExponentialParameters::from_incremental_duration(
+            ProductionRate::new(100.),
+            NominalDeclineRate::new(0.001),
+            AverageDaysTime { days: 1000. },
+        )
+        .unwrap()
+        .into(),
+    ]);
+    let mut forecast = original.clone();
+
+    insta::assert_debug_snapshot!(forecast.advance_to(AverageDaysTime { days: -1. }), @r#"
+    Err(
+        NegativeInput {
+            field: "time",
+            value: -1.0,
+        },
+    )
+    "#);
+    insta::assert_debug_snapshot!(forecast.advance_to(AverageDaysTime { days: f64::NAN }), @r#"
+    Err(
+        NonFiniteInput {
+            field: "time",
+            value: NaN,
+        },
+    )
+    "#);
+    assert_eq!(forecast, original);
+}
+
+#[test]
+fn forecast_initial_cumulative_volume() {
+    let forecast = Forecast::new(vec![
+        FlatParameters::from_incremental_duration(
+            ProductionRate::new(100.),
+            AverageDaysTime { days: 30. },
+        )
+        .unwrap()
+        .into(),
+        ExponentialParameters::from_incremental_duration(
+            ProductionRate::new(100.),
+            NominalDeclineRate::new(0.001),
+            AverageDaysTime { days: 1000. },
+        )
+        .unwrap()
+        .into(),
+    ])
+    .with_initial_cumulative_volume(50_000.)
+    .unwrap();
+
+    insta::assert_snapshot!(forecast.initial_cumulative_volume(), @"50000");
+    insta::assert_snapshot!(forecast.cumulative_volume_at_time(AverageDaysTime { days: 15. }), @"51500");
+    insta::assert_snapshot!(forecast.cumulative_volume(), @"116212.05588285576");
+    insta::assert_snapshot!(forecast.incremental_volume(), @"66212.05588285576");
+
+    insta::assert_snapshot!(forecast.clone().with_initial_cumulative_volume(-1.).unwrap_err(), @"initial cumulative volume is negative, but expected a positive number");
+}
+
+#[test]
+fn forecast_advance_to_accumulates_volume() {
+    let original = Forecast::new(vec![
+        FlatParameters::from_incremental_duration(
+            ProductionRate::new(100.),
+            AverageDaysTime { days: 30. },
+        )
+        .unwrap()
+        .into(),
+        ExponentialParameters::from_incremental_duration(
+            ProductionRate::new(100.),
+            NominalDeclineRate::new(0.001),
+            AverageDaysTime { days: 1000. },
+        )
+        .unwrap()
+        .into(),
+    ])
+    .with_initial_cumulative_volume(50_000.)
+    .unwrap();
+    let mut forecast = original.clone();
+
+    let produced_volume = forecast.advance_to(AverageDaysTime { days: 100. }).unwrap();
+    insta::assert_snapshot!(forecast.initial_cumulative_volume(), @"59760.61800940518");
+    assert_eq!(
+        forecast.initial_cumulative_volume(),
+        50_000. + produced_volume
+    );
+    assert!((forecast.cumulative_volume() - original.cumulative_volume()).abs() < 1e-9);
+}
+
+#[test]
+fn ramp_up_then_decline() {
+    let decline = HyperbolicParameters::from_incremental_duration(
+        ProductionRate::<AverageDaysTime>::new(1000.),
+        NominalDeclineRate::new(0.003),
+        AverageDaysTime { days: 3000. },
+        0.9,
+    )
+    .unwrap();
+    let forecast = Forecast::ramp_up_then_decline(
+        ProductionRate::new(200.),
+        AverageDaysTime { days: 60. },
+        decline.clone(),
+    )
+    .unwrap();
+
+    insta::assert_snapshot!(forecast.segments().len(), @"2");
+    insta::assert_snapshot!(forecast.rate_at_time(AverageDaysTime { days: 0. }).value(), @"200");
+    insta::assert_snapshot!(forecast.rate_at_time(AverageDaysTime { days: 30. }).value(), @"600");
+    insta::assert_snapshot!(forecast.rate_at_time(AverageDaysTime { days: 60. }).value(), @"1000");
+    insta::assert_snapshot!(forecast.incremental_duration().days, @"3060");
+
+    let result = Forecast::ramp_up_then_decline(
+        ProductionRate::new(1000.),
+        AverageDaysTime { days: 60. },
+        decline.clone(),
+    );
+    insta::assert_snapshot!(result.unwrap_err(), @"initial rate is 1000, but expected it to be less than the peak rate of 1000");
+
+    let result = Forecast::ramp_up_then_decline(
+        ProductionRate::new(200.),
+        AverageDaysTime { days: 0. },
+        decline,
+    );
+    insta::assert_snapshot!(result.unwrap_err(), @"ramp duration is negative or zero, but expected a positive number");
+}
+
+#[test]
+fn plateau_then_decline() {
+    let decline = ExponentialParameters::from_incremental_duration(
+        ProductionRate::<AverageDaysTime>::new(500.),
+        NominalDeclineRate::new(0.001),
+        AverageDaysTime { days: 1000. },
+    )
+    .unwrap();
+    let forecast = Forecast::plateau_then_decline(AverageDaysTime { days: 365. }, decline).unwrap();
+
+    insta::assert_snapshot!(forecast.rate_at_time(AverageDaysTime { days: 200. }).value(), @"500");
+    insta::assert_snapshot!(forecast.incremental_volume(), @"498560.27941427886");
+    insta::assert_snapshot!(forecast.incremental_duration().days, @"1365");
+}
+
+#[test]
+fn forecast_fingerprint() {
+    let forecast = Forecast::new(vec![
+        FlatParameters::from_incremental_duration(
+            ProductionRate::new(100.),
+            AverageDaysTime { days: 30. },
+        )
+        .unwrap()
+        .into(),
+        ExponentialParameters::from_incremental_duration(
+            ProductionRate::new(100.),
+            NominalDeclineRate::new(0.001),
+            AverageDaysTime { days: 1000. },
+        )
+        .unwrap()
+        .into(),
+    ]);
+    let fingerprint = forecast.fingerprint();
+
+    // The fingerprint is stable across platforms and releases, so it can be stored.
+    insta::assert_snapshot!(format!("{fingerprint:016x}"), @"e79c21352cfca22d");
+    assert_eq!(forecast.clone().fingerprint(), fingerprint);
+
+    // Round-off doesn't change the fingerprint, but a meaningful change does.
+    let with_decline_rate = |decline_rate| {
+        let mut segments = forecast.segments().to_vec();
+        segments[1] = ExponentialParameters::from_incremental_duration(
+            ProductionRate::new(100.),
+            NominalDeclineRate::new(decline_rate),
+            AverageDaysTime { days: 1000. },
+        )
+        .unwrap()
+        .into();
+        Forecast::new(segments)
+    };
+    assert_eq!(
+        with_decline_rate(0.001 * (1. + 1e-13)).fingerprint(),
+        fingerprint
+    );
+    assert_ne!(with_decline_rate(0.0011).fingerprint(), fingerprint);
+
+    assert_ne!(
+        forecast
+            .clone()
+            .with_initial_cumulative_volume(1000.)
+            .unwrap()
+            .fingerprint(),
+        fingerprint
+    );
+    assert_ne!(
+        Forecast::new(forecast.segments()[..1].to_vec()).fingerprint(),
+        fingerprint
+    );
+}
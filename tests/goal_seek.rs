@@ -0,0 +1,109 @@
+use decline_curve_analysis::{
+    AverageYearsTime, Forecast, NominalDeclineRate, ProductionRate, Segment,
+    solve_initial_decline_for_volume, solve_modified_hyperbolic_initial_decline_for_volume,
+};
+
+#[test]
+#[cfg_attr(feature = "fast-math", ignore = "exact values differ with fast-math")]
+fn solve_hyperbolic_initial_decline() {
+    let params = solve_initial_decline_for_volume(
+        ProductionRate::<AverageYearsTime>::new(100_000.),
+        400_000.,
+        0.9,
+        AverageYearsTime { years: 30. },
+    )
+    .unwrap();
+
+    #[cfg(all(feature = "std", not(feature = "deterministic")))]
+    insta::assert_snapshot!(params.initial_decline_rate().value(), @"0.7093275990523938");
+    #[cfg(any(not(feature = "std"), feature = "deterministic"))]
+    insta::assert_snapshot!(params.initial_decline_rate().value(), @"0.7093275990523942");
+    #[cfg(all(feature = "std", not(feature = "deterministic")))]
+    insta::assert_snapshot!(params.incremental_volume(), @"400000");
+    #[cfg(any(not(feature = "std"), feature = "deterministic"))]
+    insta::assert_snapshot!(params.incremental_volume(), @"400000.00000000006");
+}
+
+#[test]
+#[cfg_attr(feature = "fast-math", ignore = "exact values differ with fast-math")]
+fn solve_modified_hyperbolic_initial_decline() {
+    let forecast = solve_modified_hyperbolic_initial_decline_for_volume(
+        ProductionRate::<AverageYearsTime>::new(100_000.),
+        400_000.,
+        1.2,
+        NominalDeclineRate::new(0.06),
+        AverageYearsTime { years: 30. },
+    )
+    .unwrap();
+
+    insta::assert_snapshot!(forecast.incremental_volume(), @"400000");
+    insta::assert_snapshot!(forecast.incremental_duration().years, @"30");
+    let Segment::Hyperbolic(hyperbolic) = &forecast.segments()[0] else {
+        panic!("expected a hyperbolic segment, got {forecast:?}");
+    };
+    insta::assert_snapshot!(hyperbolic.initial_decline_rate().value(), @"1.0044223849111866");
+    insta::assert_snapshot!(forecast.decline_rate_at_time(AverageYearsTime { years: 29. }).value(), @"0.06");
+}
+
+#[test]
+fn modified_hyperbolic() {
+    let forecast = Forecast::modified_hyperbolic(
+        ProductionRate::<AverageYearsTime>::new(1000.),
+        NominalDeclineRate::new(1.),
+        1.5,
+        NominalDeclineRate::new(0.1),
+        AverageYearsTime { years: 30. },
+    )
+    .unwrap();
+    insta::assert_snapshot!(forecast.segments().len(), @"2");
+    insta::assert_snapshot!(forecast.segments()[0].incremental_duration().years, @"6");
+
+    // The decline never reaches the terminal decline rate.
+    let forecast = Forecast::modified_hyperbolic(
+        ProductionRate::<AverageYearsTime>::new(1000.),
+        NominalDeclineRate::new(1.),
+        1.5,
+        NominalDeclineRate::new(0.01),
+        AverageYearsTime { years: 30. },
+    )
+    .unwrap();
+    insta::assert_snapshot!(forecast.segments().len(), @"1");
+
+    // The decline starts below the terminal decline rate.
+    let forecast = Forecast::modified_hyperbolic(
+        ProductionRate::<AverageYearsTime>::new(1000.),
+        NominalDeclineRate::new(0.05),
+        1.5,
+        NominalDeclineRate::new(0.1),
+        AverageYearsTime { years: 30. },
+    )
+    .unwrap();
+    assert!(matches!(forecast.segments(), [Segment::Exponential(_)]));
+}
+
+#[test]
+fn unreachable_target_volume() {
+    let result = solve_initial_decline_for_volume(
+        ProductionRate::<AverageYearsTime>::new(1000.),
+        30_000.,
+        0.9,
+        AverageYearsTime { years: 30. },
+    );
+    insta::assert_snapshot!(result.unwrap_err(), @"cannot solve decline: no finite solution exists for the given parameters");
+
+    let result = solve_initial_decline_for_volume(
+        ProductionRate::<AverageYearsTime>::new(1000.),
+        0.,
+        0.9,
+        AverageYearsTime { years: 30. },
+    );
+    insta::assert_snapshot!(result.unwrap_err(), @"target volume is negative or zero, but expected a positive number");
+
+    let result = solve_initial_decline_for_volume(
+        ProductionRate::<AverageYearsTime>::new(1000.),
+        10_000.,
+        1.,
+        AverageYearsTime { years: 30. },
+    );
+    insta::assert_snapshot!(result.unwrap_err(), @"exponent is 1, but expected it to be between -100 and 100, other than 0 or 1");
+}
@@ -0,0 +1,91 @@
+use decline_curve_analysis::{
+    AverageDaysTime, AverageMonthsTime, CalendarDate, CalendarTime, FitOptions, HistoryTimeAxis,
+    HistoryValue, ProductionHistory, fit_exponential,
+};
+
+const MONTHLY: &str = "
+date,oil
+2023-12,0
+2024-01,3100
+2024-02,2610
+2024-03,0
+2024-04,2400
+2024-06,2170
+";
+
+#[test]
+fn monthly_volumes_to_samples() {
+    let history = ProductionHistory::parse_csv(MONTHLY, HistoryValue::MonthlyVolume).unwrap();
+    insta::assert_snapshot!(history.records().len(), @"6");
+    assert_eq!(
+        history.first_production_date(),
+        Some(CalendarDate::new(2024, 1, 1).unwrap())
+    );
+
+    let samples = history.samples::<AverageDaysTime>(HistoryTimeAxis::Calendar);
+    insta::assert_snapshot!(
+        format!("{:?}", samples.iter().map(|(time, rate)| (time.days, rate.value())).collect::<Vec<_>>()),
+        @"[(15.5, 100.0), (45.5, 90.0), (106.0, 80.0), (167.0, 72.33333333333333)]"
+    );
+
+    // Shut-ins and missing months are removed from the producing time.
+    let samples = history.samples::<AverageDaysTime>(HistoryTimeAxis::Producing);
+    insta::assert_snapshot!(
+        format!("{:?}", samples.iter().map(|(time, _)| time.days).collect::<Vec<_>>()),
+        @"[15.5, 45.5, 75.0, 105.0]"
+    );
+}
+
+#[test]
+fn daily_rates_to_samples() {
+    let history = ProductionHistory::parse_csv(
+        "2024-01-01,100\n2024-01-11,0\n2024-01-21,80\n2024-01-31,70",
+        HistoryValue::DailyRate,
+    )
+    .unwrap();
+
+    let samples = history.samples::<AverageMonthsTime>(HistoryTimeAxis::Producing);
+    insta::assert_snapshot!(
+        format!("{:?}", samples.iter().map(|(time, rate)| (time.months, rate.value())).collect::<Vec<_>>()),
+        @"[(0.0, 3043.75), (0.32854209445585214, 2435.0), (0.6570841889117043, 2130.625)]"
+    );
+}
+
+#[test]
+fn fit_parsed_history() {
+    // Monthly volumes of an exponential decline from 1000 per day at 0.1% per day.
+    let first_production_date = CalendarDate::new(2022, 1, 1).unwrap();
+    let csv = (0..24)
+        .map(|month| {
+            let start = first_production_date.add_months(month).unwrap();
+            let volume_at = |date: CalendarDate| {
+                let days = CalendarTime::between(first_production_date, date).days;
+                1000. * (1. - (-0.001 * days).exp()) / 0.001
+            };
+            let volume = volume_at(start.add_months(1).unwrap()) - volume_at(start);
+            format!("{}-{:02},{volume}\n", start.year(), start.month())
+        })
+        .collect::<String>();
+    let history = ProductionHistory::parse_csv(&csv, HistoryValue::MonthlyVolume).unwrap();
+
+    let samples = history.samples::<AverageDaysTime>(HistoryTimeAxis::Calendar);
+    let fit = fit_exponential(&samples, &FitOptions::default()).unwrap();
+
+    #[cfg(all(feature = "std", not(feature = "deterministic")))]
+    insta::assert_snapshot!(fit.parameters.decline_rate().value(), @"0.0009999984954102662");
+    #[cfg(any(not(feature = "std"), feature = "deterministic"))]
+    insta::assert_snapshot!(fit.parameters.decline_rate().value(), @"0.0009999984954100886");
+    #[cfg(all(feature = "std", not(feature = "deterministic")))]
+    insta::assert_snapshot!(fit.parameters.initial_rate().value(), @"1000.0380420741446");
+    #[cfg(any(not(feature = "std"), feature = "deterministic"))]
+    insta::assert_snapshot!(fit.parameters.initial_rate().value(), @"1000.0380420740837");
+}
+
+#[test]
+fn invalid_history() {
+    insta::assert_snapshot!(ProductionHistory::parse_csv("2024-01,1,2", HistoryValue::MonthlyVolume).unwrap_err(), @"line 1: expected 2 columns, but got 3");
+    insta::assert_snapshot!(ProductionHistory::parse_csv("date,oil\n2024-13,1", HistoryValue::MonthlyVolume).unwrap_err(), @"line 2: `2024-13` is not a date");
+    insta::assert_snapshot!(ProductionHistory::parse_csv("2024-01,abc", HistoryValue::MonthlyVolume).unwrap_err(), @"line 1: `abc` is not a number");
+    insta::assert_snapshot!(ProductionHistory::parse_csv("2024-01,-1", HistoryValue::MonthlyVolume).unwrap_err(), @"production value is negative, but expected a positive number");
+    insta::assert_snapshot!(ProductionHistory::parse_csv("2024-01-05,1\n2024-01-20,1", HistoryValue::MonthlyVolume).unwrap_err(), @"production dates are not strictly increasing");
+}
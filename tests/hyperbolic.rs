@@ -1,5 +1,7 @@
 use decline_curve_analysis::{
-    AverageDaysTime, AverageYearsTime, HyperbolicParameters, NominalDeclineRate, ProductionRate,
+    AverageDaysTime, AverageYearsTime, DeclineCurveAnalysisError, ExponentPolicy,
+    ExponentialParameters, Forecast, HyperbolicExponent, HyperbolicParameters, NominalDeclineRate,
+    ProductionRate, SecantEffectiveDeclineRate,
 };
 use proptest::prelude::*;
 
@@ -84,6 +86,7 @@ fn hyperbolic_from_final_rate() {
 }
 
 #[test]
+#[cfg_attr(feature = "fast-math", ignore = "exact values differ with fast-math")]
 fn hyperbolic_incremental_volume_at_time() {
     let initial_rate = ProductionRate::<AverageDaysTime>::new(50.);
     let initial_decline_rate = NominalDeclineRate::<AverageYearsTime>::new(0.5).into();
@@ -99,13 +102,17 @@ fn hyperbolic_incremental_volume_at_time() {
     .unwrap();
 
     // Calculate past the end to check the total.
+    #[cfg(all(feature = "std", not(feature = "deterministic")))]
     insta::assert_snapshot!(parameters.incremental_volume_at_time(AverageDaysTime { days: 2700. }), @"54298.10011031419");
+    #[cfg(any(not(feature = "std"), feature = "deterministic"))]
+    insta::assert_snapshot!(parameters.incremental_volume_at_time(AverageDaysTime { days: 2700. }), @"54298.100110314226");
 
     // Check a point somewhere in the middle.
     insta::assert_snapshot!(parameters.incremental_volume_at_time(AverageDaysTime { days: 0.5 * 2700. }), @"37666.26214690978");
 }
 
 #[test]
+#[cfg_attr(feature = "fast-math", ignore = "exact values differ with fast-math")]
 fn hyperbolic_final_rate() {
     let initial_rate = ProductionRate::<AverageDaysTime>::new(50.);
     let initial_decline_rate = NominalDeclineRate::<AverageYearsTime>::new(0.5).into();
@@ -124,6 +131,7 @@ fn hyperbolic_final_rate() {
 }
 
 #[test]
+#[cfg_attr(feature = "fast-math", ignore = "exact values differ with fast-math")]
 fn hyperbolic_incline() {
     let initial_rate = ProductionRate::<AverageDaysTime>::new(50.);
     let initial_decline_rate = NominalDeclineRate::<AverageYearsTime>::new(-0.005).into();
@@ -263,7 +271,10 @@ fn negative_exponent() {
         exponent,
     )
     .unwrap();
+    #[cfg(all(feature = "std", not(feature = "deterministic")))]
     insta::assert_snapshot!(params.incremental_duration().years, @"30.396841995794926");
+    #[cfg(any(not(feature = "std"), feature = "deterministic"))]
+    insta::assert_snapshot!(params.incremental_duration().years, @"30.39684199579492");
 }
 
 #[test]
@@ -342,7 +353,7 @@ fn exponent_range() {
         500.,
         0.,
     );
-    insta::assert_snapshot!(result.unwrap_err(), @"exponent was approximately zero, so an exponential should be used instead");
+    insta::assert_snapshot!(result.unwrap_err(), @"exponent is 0, but expected it to be between -100 and 100, other than 0 or 1");
 
     let result = HyperbolicParameters::<AverageYearsTime>::from_incremental_volume(
         ProductionRate::new(100.),
@@ -350,7 +361,7 @@ fn exponent_range() {
         500.,
         1.,
     );
-    insta::assert_snapshot!(result.unwrap_err(), @"exponent was approximately one, so a harmonic should be used instead");
+    insta::assert_snapshot!(result.unwrap_err(), @"exponent is 1, but expected it to be between -100 and 100, other than 0 or 1");
 
     let result = HyperbolicParameters::<AverageYearsTime>::from_incremental_volume(
         ProductionRate::new(100.),
@@ -358,7 +369,7 @@ fn exponent_range() {
         500.,
         150.,
     );
-    insta::assert_snapshot!(result.unwrap_err(), @"exponent too large");
+    insta::assert_snapshot!(result.unwrap_err(), @"exponent is 150, but expected it to be between -100 and 100");
 
     let result = HyperbolicParameters::<AverageYearsTime>::from_incremental_volume(
         ProductionRate::new(100.),
@@ -366,7 +377,7 @@ fn exponent_range() {
         500.,
         -150.,
     );
-    insta::assert_snapshot!(result.unwrap_err(), @"exponent too large");
+    insta::assert_snapshot!(result.unwrap_err(), @"exponent is -150, but expected it to be between -100 and 100");
 }
 
 #[test]
@@ -398,6 +409,7 @@ fn zero_volume() {
 }
 
 #[test]
+#[cfg_attr(feature = "fast-math", ignore = "exact values differ with fast-math")]
 fn final_rate_roundtrip() {
     let initial_rate = ProductionRate::<AverageDaysTime>::new(100.);
     let decline_rate = NominalDeclineRate::<AverageYearsTime>::new(0.5).into();
@@ -509,3 +521,125 @@ proptest! {
         }
     }
 }
+
+#[test]
+fn exponent_policy() {
+    let parameters = HyperbolicParameters::<AverageYearsTime>::from_incremental_duration(
+        ProductionRate::new(1000.),
+        NominalDeclineRate::new(2.),
+        AverageYearsTime { years: 5. },
+        1.6,
+    )
+    .unwrap();
+
+    assert!(
+        parameters
+            .validate_exponent(ExponentPolicy::Permissive)
+            .is_ok()
+    );
+    assert!(
+        parameters
+            .validate_exponent(ExponentPolicy::Strict { maximum: 2. })
+            .is_ok()
+    );
+    insta::assert_snapshot!(parameters.validate_exponent(ExponentPolicy::Strict { maximum: 1. }).unwrap_err(), @"exponent is 1.6, but expected it to be between 0 and 1");
+
+    let incline = HyperbolicParameters::<AverageYearsTime>::from_incremental_duration(
+        ProductionRate::new(1000.),
+        NominalDeclineRate::new(-0.5),
+        AverageYearsTime { years: 5. },
+        -0.5,
+    )
+    .unwrap();
+    insta::assert_snapshot!(incline.validate_exponent(ExponentPolicy::Strict { maximum: 1. }).unwrap_err(), @"exponent is -0.5, but expected it to be between 0 and 1");
+
+    insta::assert_snapshot!(ExponentPolicy::Strict { maximum: 0. }.validate(0.5).unwrap_err(), @"maximum exponent is negative or zero, but expected a positive number");
+    insta::assert_snapshot!(ExponentPolicy::Permissive.validate(f64::NAN).unwrap_err(), @"exponent is not-a-number, but expected a finite number");
+
+    let forecast = Forecast::new(vec![
+        parameters.into(),
+        ExponentialParameters::from_incremental_duration(
+            ProductionRate::new(100.),
+            NominalDeclineRate::new(0.08),
+            AverageYearsTime { years: 10. },
+        )
+        .unwrap()
+        .into(),
+    ]);
+    assert!(
+        forecast
+            .validate_exponents(ExponentPolicy::Strict { maximum: 2. })
+            .is_ok()
+    );
+    insta::assert_debug_snapshot!(forecast.validate_exponents(ExponentPolicy::Strict { maximum: 1.5 }), @r"
+    Err(
+        ExponentOutOfRange {
+            value: 1.6,
+            allowed: ExponentRange {
+                minimum: 0.0,
+                maximum: 1.5,
+                excluded: [],
+            },
+        },
+    )
+    ");
+}
+
+#[test]
+fn hyperbolic_exponent() {
+    insta::assert_snapshot!(HyperbolicExponent::new(0.).unwrap_err(), @"exponent is 0, but expected it to be between -100 and 100, other than 0 or 1");
+    insta::assert_snapshot!(HyperbolicExponent::try_from(1.).unwrap_err(), @"exponent is 1, but expected it to be between -100 and 100, other than 0 or 1");
+    assert!(matches!(
+        HyperbolicExponent::new(1e-12),
+        Err(DeclineCurveAnalysisError::ExponentOutOfRange { value: 1e-12, .. })
+    ));
+    insta::assert_snapshot!(HyperbolicExponent::new(f64::NAN).unwrap_err(), @"exponent is not-a-number, but expected a finite number");
+    insta::assert_snapshot!(
+        HyperbolicExponent::with_policy(1.6, ExponentPolicy::Strict { maximum: 1.5 }).unwrap_err(),
+        @"exponent is 1.6, but expected it to be between 0 and 1.5"
+    );
+
+    let exponent =
+        HyperbolicExponent::with_policy(0.8, ExponentPolicy::Strict { maximum: 1. }).unwrap();
+    let decline_rate = SecantEffectiveDeclineRate::<AverageYearsTime>::new(0.6);
+    let from_exponent = HyperbolicParameters::from_secant_effective_incremental_duration(
+        ProductionRate::new(1000.),
+        decline_rate,
+        AverageYearsTime { years: 10. },
+        exponent,
+    )
+    .unwrap();
+    let from_f64 = HyperbolicParameters::from_secant_effective_incremental_duration(
+        ProductionRate::new(1000.),
+        decline_rate,
+        AverageYearsTime { years: 10. },
+        0.8,
+    )
+    .unwrap();
+
+    assert_eq!(from_exponent, from_f64);
+    assert_eq!(
+        decline_rate.to_nominal(exponent).unwrap(),
+        decline_rate.to_nominal(0.8).unwrap()
+    );
+    assert_eq!(f64::from(exponent), from_exponent.exponent());
+}
+
+#[test]
+fn effective_decline_accessors() {
+    let parameters = HyperbolicParameters::from_secant_effective_incremental_duration(
+        ProductionRate::<AverageYearsTime>::new(1000.),
+        SecantEffectiveDeclineRate::new(0.65),
+        AverageYearsTime { years: 10. },
+        0.9,
+    )
+    .unwrap();
+
+    insta::assert_snapshot!(parameters.initial_secant_effective().unwrap().value(), @"0.6500000000000001");
+    #[cfg(all(feature = "std", not(feature = "deterministic")))]
+    insta::assert_snapshot!(parameters.final_secant_effective().unwrap().value(), @"0.09502132652859097");
+    #[cfg(any(not(feature = "std"), feature = "deterministic"))]
+    insta::assert_snapshot!(parameters.final_secant_effective().unwrap().value(), @"0.09502132652859108");
+    insta::assert_snapshot!(parameters.initial_tangent_effective().unwrap().value(), @"0.8257236143695019");
+    insta::assert_snapshot!(parameters.final_tangent_effective().unwrap().value(), @"0.09919575151032112");
+}
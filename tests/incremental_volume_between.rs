@@ -0,0 +1,115 @@
+use decline_curve_analysis::{
+    AverageYearsTime, DelayParameters, ExponentialParameters, FlatParameters, Forecast,
+    HarmonicParameters, HyperbolicParameters, LinearParameters, LogisticGrowthParameters,
+    NominalDeclineRate, PowerLawExponentialParameters, ProductionRate, Segment,
+};
+
+fn segments() -> Vec<Segment<AverageYearsTime>> {
+    let rate = ProductionRate::new(1000.);
+    let decline_rate = NominalDeclineRate::new(0.3);
+    let duration = AverageYearsTime { years: 10. };
+
+    vec![
+        DelayParameters::from_incremental_duration(duration)
+            .unwrap()
+            .into(),
+        ExponentialParameters::from_incremental_duration(rate, decline_rate, duration)
+            .unwrap()
+            .into(),
+        FlatParameters::from_incremental_duration(rate, duration)
+            .unwrap()
+            .into(),
+        HarmonicParameters::from_incremental_duration(rate, decline_rate, duration)
+            .unwrap()
+            .into(),
+        HyperbolicParameters::from_incremental_duration(rate, decline_rate, duration, 0.5)
+            .unwrap()
+            .into(),
+        LinearParameters::from_incremental_duration(rate, NominalDeclineRate::new(0.05), duration)
+            .unwrap()
+            .into(),
+        LogisticGrowthParameters::from_incremental_duration(10_000., 5., duration, 1.5)
+            .unwrap()
+            .into(),
+        PowerLawExponentialParameters::from_incremental_duration(
+            rate,
+            0.3,
+            NominalDeclineRate::new(0.01),
+            duration,
+            0.5,
+        )
+        .unwrap()
+        .into(),
+    ]
+}
+
+#[test]
+fn matches_difference_of_cumulative_volumes() {
+    for segment in segments() {
+        let start = AverageYearsTime { years: 2. };
+        let end = AverageYearsTime { years: 7. };
+        let expected =
+            segment.incremental_volume_at_time(end) - segment.incremental_volume_at_time(start);
+        let actual = segment.incremental_volume_between(start, end);
+
+        assert!(
+            (actual - expected).abs() < 1e-6,
+            "{segment:?}: expected {expected}, got {actual}"
+        );
+        assert_eq!(segment.incremental_volume_between(end, start), -actual);
+    }
+}
+
+#[test]
+fn clamps_to_segment() {
+    for segment in segments() {
+        let volume = segment.incremental_volume_between(
+            AverageYearsTime { years: -5. },
+            AverageYearsTime { years: 50. },
+        );
+        assert!(
+            (volume - segment.incremental_volume()).abs() < 1e-6,
+            "{segment:?}: expected {}, got {volume}",
+            segment.incremental_volume()
+        );
+    }
+}
+
+#[test]
+fn forecast_incremental_volume_between() {
+    let forecast = Forecast::new(vec![
+        Segment::from(
+            FlatParameters::from_incremental_duration(
+                ProductionRate::<AverageYearsTime>::new(1000.),
+                AverageYearsTime { years: 1. },
+            )
+            .unwrap(),
+        ),
+        ExponentialParameters::from_incremental_duration(
+            ProductionRate::new(1000.),
+            NominalDeclineRate::new(0.5),
+            AverageYearsTime { years: 10. },
+        )
+        .unwrap()
+        .into(),
+    ]);
+
+    let start = AverageYearsTime { years: 0.5 };
+    let end = AverageYearsTime { years: 3. };
+    insta::assert_snapshot!(forecast.incremental_volume_between(start, end), @"1764.2411176571154");
+    insta::assert_snapshot!(
+        forecast.incremental_volume_at_time(end) - forecast.incremental_volume_at_time(start),
+        @"1764.2411176571154"
+    );
+    assert_eq!(
+        forecast.incremental_volume_between(end, start),
+        -forecast.incremental_volume_between(start, end)
+    );
+    insta::assert_snapshot!(
+        forecast.incremental_volume_between(
+            AverageYearsTime { years: -1. },
+            AverageYearsTime { years: 100. },
+        ),
+        @"2986.524106001829"
+    );
+}
@@ -0,0 +1,362 @@
+use decline_curve_analysis::{
+    AverageDaysTime, AverageYearsTime, DelayParameters, ExponentialParameters, FlatParameters,
+    Forecast, HarmonicParameters, HyperbolicParameters, LinearParameters, LogisticGrowthParameters,
+    NominalDeclineRate, PowerLawExponentialParameters, ProductionRate, Segment,
+};
+use proptest::prelude::*;
+
+fn segments() -> Vec<Segment<AverageYearsTime>> {
+    let rate = ProductionRate::new(1000.);
+    let decline_rate = NominalDeclineRate::new(0.3);
+    let duration = AverageYearsTime { years: 10. };
+
+    vec![
+        ExponentialParameters::from_incremental_duration(rate, decline_rate, duration)
+            .unwrap()
+            .into(),
+        FlatParameters::from_incremental_duration(rate, duration)
+            .unwrap()
+            .into(),
+        HarmonicParameters::from_incremental_duration(rate, decline_rate, duration)
+            .unwrap()
+            .into(),
+        HyperbolicParameters::from_incremental_duration(rate, decline_rate, duration, 0.5)
+            .unwrap()
+            .into(),
+        LinearParameters::from_incremental_duration(rate, NominalDeclineRate::new(0.05), duration)
+            .unwrap()
+            .into(),
+        LogisticGrowthParameters::from_incremental_duration(10_000., 5., duration, 1.5)
+            .unwrap()
+            .into(),
+        PowerLawExponentialParameters::from_incremental_duration(
+            rate,
+            0.3,
+            NominalDeclineRate::new(0.01),
+            duration,
+            0.5,
+        )
+        .unwrap()
+        .into(),
+    ]
+}
+
+#[test]
+fn time_at_incremental_volume_round_trip() {
+    for segment in segments() {
+        let time = AverageYearsTime { years: 4. };
+        let volume = segment.incremental_volume_at_time(time);
+        let solved = segment.time_at_incremental_volume(volume).unwrap();
+        assert!(
+            (solved.years - time.years).abs() < 1e-6,
+            "{segment:?} solved {solved:?}"
+        );
+    }
+}
+
+#[test]
+fn time_at_incremental_volume_past_end() {
+    let params = ExponentialParameters::from_incremental_duration(
+        ProductionRate::<AverageYearsTime>::new(1000.),
+        NominalDeclineRate::new(0.3),
+        AverageYearsTime { years: 10. },
+    )
+    .unwrap();
+
+    insta::assert_snapshot!(params.time_at_incremental_volume(1000.).unwrap().years, @"1.1889164797957745");
+    assert_eq!(
+        params
+            .time_at_incremental_volume(params.incremental_volume())
+            .unwrap(),
+        params.incremental_duration()
+    );
+
+    let result = params.time_at_incremental_volume(params.incremental_volume() + 1.);
+    insta::assert_snapshot!(result.unwrap_err(), @"cannot solve decline: no finite solution exists for the given parameters");
+
+    let result = params.time_at_incremental_volume(-1.);
+    insta::assert_snapshot!(result.unwrap_err(), @"incremental volume is negative, but expected a positive number");
+}
+
+#[test]
+fn delay_time_at_incremental_volume() {
+    let params = DelayParameters::from_incremental_duration(AverageDaysTime { days: 10. }).unwrap();
+
+    insta::assert_snapshot!(params.time_at_incremental_volume(0.).unwrap().days, @"0");
+    let result = params.time_at_incremental_volume(1.);
+    insta::assert_snapshot!(result.unwrap_err(), @"cannot solve decline: no finite solution exists for the given parameters");
+}
+
+#[test]
+fn forecast_time_at_incremental_volume() {
+    let forecast = Forecast::new(vec![
+        Segment::from(
+            DelayParameters::from_incremental_duration(AverageYearsTime { years: 1. }).unwrap(),
+        ),
+        FlatParameters::from_incremental_duration(
+            ProductionRate::new(1000.),
+            AverageYearsTime { years: 1. },
+        )
+        .unwrap()
+        .into(),
+        ExponentialParameters::from_incremental_duration(
+            ProductionRate::new(1000.),
+            NominalDeclineRate::new(0.5),
+            AverageYearsTime { years: 10. },
+        )
+        .unwrap()
+        .into(),
+    ]);
+
+    insta::assert_snapshot!(forecast.time_at_incremental_volume(0.).unwrap().years, @"0");
+    insta::assert_snapshot!(forecast.time_at_incremental_volume(500.).unwrap().years, @"1.5");
+    insta::assert_snapshot!(forecast.time_at_incremental_volume(1000.).unwrap().years, @"2");
+    insta::assert_snapshot!(forecast.time_at_incremental_volume(1500.).unwrap().years, @"2.5753641449035616");
+
+    let result = forecast.time_at_incremental_volume(forecast.incremental_volume() + 1.);
+    insta::assert_snapshot!(result.unwrap_err(), @"cannot solve decline: no finite solution exists for the given parameters");
+}
+
+#[test]
+fn time_at_rate_round_trip() {
+    for segment in segments() {
+        // Flat rates are reached immediately, and the logistic growth rate is also reached before
+        // the peak.
+        if matches!(segment, Segment::Flat(_) | Segment::LogisticGrowth(_)) {
+            continue;
+        }
+
+        let time = AverageYearsTime { years: 4. };
+        let rate = segment.rate_at_time(time);
+        let solved = segment.time_at_rate(rate).unwrap();
+        assert!(
+            (solved.years - time.years).abs() < 1e-6,
+            "{segment:?} solved {solved:?}"
+        );
+    }
+}
+
+#[test]
+fn time_at_rate_outside_segment() {
+    let params = HyperbolicParameters::from_incremental_duration(
+        ProductionRate::<AverageYearsTime>::new(1000.),
+        NominalDeclineRate::new(0.3),
+        AverageYearsTime { years: 10. },
+        0.5,
+    )
+    .unwrap();
+
+    insta::assert_snapshot!(params.time_at_rate(ProductionRate::new(1000.)).unwrap().years, @"0");
+    assert_eq!(
+        params.time_at_rate(params.final_rate()).unwrap(),
+        params.incremental_duration()
+    );
+
+    let result = params.time_at_rate(ProductionRate::new(1001.));
+    insta::assert_snapshot!(result.unwrap_err(), @"cannot solve decline: no finite solution exists for the given parameters");
+
+    // Rates past the end of the segment aren't reached, which is consistent with `rate_at_time`.
+    let result = params.time_at_rate(ProductionRate::new(1.));
+    insta::assert_snapshot!(result.unwrap_err(), @"cannot solve decline: no finite solution exists for the given parameters");
+
+    let result = params.time_at_rate(ProductionRate::new(-1.));
+    insta::assert_snapshot!(result.unwrap_err(), @"rate is negative, but expected a positive number");
+}
+
+#[test]
+fn logistic_growth_time_at_rate() {
+    let params = LogisticGrowthParameters::from_incremental_duration(
+        100_000.,
+        5.,
+        AverageYearsTime { years: 30. },
+        1.5,
+    )
+    .unwrap();
+
+    // The rate is reached on the rising portion before the peak.
+    insta::assert_snapshot!(params.time_at_rate(ProductionRate::new(1000.)).unwrap().years, @"0.0011111440347509266");
+
+    let result = params.time_at_rate(ProductionRate::new(1_000_000.));
+    insta::assert_snapshot!(result.unwrap_err(), @"cannot solve decline: no finite solution exists for the given parameters");
+}
+
+#[test]
+fn forecast_time_at_rate() {
+    let forecast = Forecast::new(vec![
+        Segment::from(
+            FlatParameters::from_incremental_duration(
+                ProductionRate::<AverageYearsTime>::new(1000.),
+                AverageYearsTime { years: 1. },
+            )
+            .unwrap(),
+        ),
+        ExponentialParameters::from_incremental_duration(
+            ProductionRate::new(1000.),
+            NominalDeclineRate::new(0.5),
+            AverageYearsTime { years: 10. },
+        )
+        .unwrap()
+        .into(),
+    ]);
+
+    insta::assert_snapshot!(forecast.time_at_rate(ProductionRate::new(1000.)).unwrap().years, @"0");
+    insta::assert_snapshot!(forecast.time_at_rate(ProductionRate::new(100.)).unwrap().years, @"5.605170185988092");
+
+    let result = forecast.time_at_rate(ProductionRate::new(1.));
+    insta::assert_snapshot!(result.unwrap_err(), @"cannot solve decline: no finite solution exists for the given parameters");
+}
+
+#[test]
+fn rate_at_incremental_volume_matches_rate_at_time() {
+    for segment in segments() {
+        let time = AverageYearsTime { years: 4. };
+        let volume = segment.incremental_volume_at_time(time);
+        let rate = segment.rate_at_incremental_volume(volume).unwrap();
+        let expected = segment.rate_at_time(time);
+        assert!(
+            (rate.value() - expected.value()).abs() < 1e-6 * expected.value(),
+            "{segment:?} rate {rate:?}"
+        );
+    }
+}
+
+#[test]
+#[cfg_attr(feature = "fast-math", ignore = "exact values differ with fast-math")]
+fn rate_at_incremental_volume_outside_segment() {
+    let params = HyperbolicParameters::from_incremental_duration(
+        ProductionRate::<AverageYearsTime>::new(1000.),
+        NominalDeclineRate::new(0.3),
+        AverageYearsTime { years: 10. },
+        0.5,
+    )
+    .unwrap();
+
+    insta::assert_snapshot!(params.rate_at_incremental_volume(0.).unwrap().value(), @"1000");
+    insta::assert_snapshot!(
+        params.rate_at_incremental_volume(params.incremental_volume()).unwrap().value(),
+        @"160.00000000000006"
+    );
+    insta::assert_snapshot!(params.final_rate().value(), @"160");
+
+    let result = params.rate_at_incremental_volume(params.incremental_volume() + 1.);
+    insta::assert_snapshot!(result.unwrap_err(), @"cannot solve decline: no finite solution exists for the given parameters");
+
+    let result = params.rate_at_incremental_volume(-1.);
+    insta::assert_snapshot!(result.unwrap_err(), @"incremental volume is negative, but expected a positive number");
+
+    let delay = DelayParameters::from_incremental_duration(AverageDaysTime { days: 10. }).unwrap();
+    insta::assert_snapshot!(delay.rate_at_incremental_volume(0.).unwrap().value(), @"0");
+    let result = delay.rate_at_incremental_volume(1.);
+    insta::assert_snapshot!(result.unwrap_err(), @"cannot solve decline: no finite solution exists for the given parameters");
+}
+
+#[test]
+fn forecast_rate_at_cumulative_volume() {
+    let forecast = Forecast::new(vec![
+        Segment::from(
+            DelayParameters::from_incremental_duration(AverageYearsTime { years: 1. }).unwrap(),
+        ),
+        FlatParameters::from_incremental_duration(
+            ProductionRate::new(1000.),
+            AverageYearsTime { years: 1. },
+        )
+        .unwrap()
+        .into(),
+        ExponentialParameters::from_incremental_duration(
+            ProductionRate::new(1000.),
+            NominalDeclineRate::new(0.5),
+            AverageYearsTime { years: 10. },
+        )
+        .unwrap()
+        .into(),
+    ])
+    .with_initial_cumulative_volume(5000.)
+    .unwrap();
+
+    insta::assert_snapshot!(forecast.rate_at_cumulative_volume(5000.).unwrap().value(), @"0");
+    insta::assert_snapshot!(forecast.rate_at_cumulative_volume(5500.).unwrap().value(), @"1000");
+    insta::assert_snapshot!(forecast.rate_at_cumulative_volume(6500.).unwrap().value(), @"750");
+    insta::assert_snapshot!(
+        forecast.rate_at_cumulative_volume(forecast.cumulative_volume()).unwrap().value(),
+        @"6.73794699908558"
+    );
+
+    let result = forecast.rate_at_cumulative_volume(4000.);
+    insta::assert_snapshot!(result.unwrap_err(), @"cannot solve decline: no finite solution exists for the given parameters");
+    let result = forecast.rate_at_cumulative_volume(forecast.cumulative_volume() + 1.);
+    insta::assert_snapshot!(result.unwrap_err(), @"cannot solve decline: no finite solution exists for the given parameters");
+}
+
+#[test]
+fn forecast_rate_cumulative_points() {
+    let forecast = Forecast::new(vec![
+        ExponentialParameters::from_incremental_duration(
+            ProductionRate::new(1000.),
+            NominalDeclineRate::new(0.5),
+            AverageYearsTime { years: 2.5 },
+        )
+        .unwrap()
+        .into(),
+    ])
+    .with_initial_cumulative_volume(5000.)
+    .unwrap();
+
+    let points = forecast
+        .rate_cumulative_points(AverageYearsTime { years: 1. })
+        .unwrap()
+        .map(|point| {
+            (
+                point.time.years,
+                point.cumulative_volume,
+                point.rate.value(),
+            )
+        })
+        .collect::<Vec<_>>();
+    insta::assert_debug_snapshot!(points, @r"
+    [
+        (
+            0.0,
+            5000.0,
+            1000.0,
+        ),
+        (
+            1.0,
+            5786.938680574733,
+            606.5306597126335,
+        ),
+        (
+            2.0,
+            6264.241117657115,
+            367.87944117144235,
+        ),
+        (
+            2.5,
+            6426.9904062796195,
+            286.5047968601901,
+        ),
+    ]
+    ");
+
+    let result = forecast.rate_cumulative_points(AverageYearsTime { years: 0. });
+    assert!(result.is_err());
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(100))]
+
+    #[test]
+    fn time_at_incremental_volume_is_within_duration(
+        fraction in 0.0..=1.0f64,
+    ) {
+        for segment in segments() {
+            let volume = segment.incremental_volume() * fraction;
+            let time = segment.time_at_incremental_volume(volume).unwrap();
+            prop_assert!(time.years >= 0., "Time should be non-negative, got {}", time.years);
+            prop_assert!(
+                time.years <= segment.incremental_duration().years,
+                "Time should be within the segment, got {}",
+                time.years
+            );
+        }
+    }
+}
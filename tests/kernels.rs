@@ -0,0 +1,240 @@
+use core::ops::{Add, Div, Mul, Neg, Sub};
+
+use decline_curve_analysis::{
+    AverageDaysTime, ExponentialParameters, FitModel, HarmonicParameters, HyperbolicParameters,
+    LinearParameters, NominalDeclineRate, ProductionRate,
+    kernels::{self, Float},
+};
+
+/// A dual number `value + derivative * e`, where `e^2 = 0`, for forward-mode automatic
+/// differentiation.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+struct Dual {
+    value: f64,
+    derivative: f64,
+}
+
+impl Dual {
+    fn constant(value: f64) -> Self {
+        Self {
+            value,
+            derivative: 0.,
+        }
+    }
+
+    fn variable(value: f64) -> Self {
+        Self {
+            value,
+            derivative: 1.,
+        }
+    }
+}
+
+impl Add for Dual {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self {
+            value: self.value + rhs.value,
+            derivative: self.derivative + rhs.derivative,
+        }
+    }
+}
+
+impl Sub for Dual {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self + -rhs
+    }
+}
+
+impl Mul for Dual {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Self {
+            value: self.value * rhs.value,
+            derivative: self.derivative * rhs.value + self.value * rhs.derivative,
+        }
+    }
+}
+
+impl Div for Dual {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self {
+        Self {
+            value: self.value / rhs.value,
+            derivative: (self.derivative * rhs.value - self.value * rhs.derivative)
+                / (rhs.value * rhs.value),
+        }
+    }
+}
+
+impl Neg for Dual {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            value: -self.value,
+            derivative: -self.derivative,
+        }
+    }
+}
+
+impl Float for Dual {
+    fn from_f64(value: f64) -> Self {
+        Self::constant(value)
+    }
+
+    fn exp(self) -> Self {
+        let value = self.value.exp();
+        Self {
+            value,
+            derivative: self.derivative * value,
+        }
+    }
+
+    fn exp_m1(self) -> Self {
+        Self {
+            value: self.value.exp_m1(),
+            derivative: self.derivative * self.value.exp(),
+        }
+    }
+
+    fn ln(self) -> Self {
+        Self {
+            value: self.value.ln(),
+            derivative: self.derivative / self.value,
+        }
+    }
+
+    fn ln_1p(self) -> Self {
+        Self {
+            value: self.value.ln_1p(),
+            derivative: self.derivative / (1. + self.value),
+        }
+    }
+
+    fn powf(self, exponent: Self) -> Self {
+        (exponent * self.ln()).exp()
+    }
+
+    fn mul_add(self, a: Self, b: Self) -> Self {
+        self * a + b
+    }
+}
+
+#[test]
+fn kernels_match_segments() {
+    let initial_rate = ProductionRate::<AverageDaysTime>::new(500.);
+    let decline_rate = NominalDeclineRate::new(0.002);
+    let duration = AverageDaysTime { days: 2000. };
+    let time = AverageDaysTime { days: 750. };
+
+    let exponential =
+        ExponentialParameters::from_incremental_duration(initial_rate, decline_rate, duration)
+            .unwrap();
+    assert_eq!(
+        kernels::exponential_rate(500., 0.002, 750.),
+        exponential.rate_at_time(time).value()
+    );
+    assert_eq!(
+        kernels::exponential_incremental_volume(500., 0.002, 750.),
+        exponential.incremental_volume_at_time(time)
+    );
+
+    let harmonic =
+        HarmonicParameters::from_incremental_duration(initial_rate, decline_rate, duration)
+            .unwrap();
+    assert_eq!(
+        kernels::harmonic_rate(500., 0.002, 750.),
+        harmonic.rate_at_time(time).value()
+    );
+    assert_eq!(
+        kernels::harmonic_incremental_volume(500., 0.002, 750.),
+        harmonic.incremental_volume_at_time(time)
+    );
+
+    let hyperbolic =
+        HyperbolicParameters::from_incremental_duration(initial_rate, decline_rate, duration, 0.8)
+            .unwrap();
+    assert_eq!(
+        kernels::hyperbolic_rate(500., 0.002, 0.8, 750.),
+        hyperbolic.rate_at_time(time).value()
+    );
+    assert_eq!(
+        kernels::hyperbolic_incremental_volume(500., 0.002, 0.8, 750.),
+        hyperbolic.incremental_volume_at_time(time)
+    );
+
+    let linear = LinearParameters::from_incremental_duration(
+        initial_rate,
+        NominalDeclineRate::new(0.0002),
+        duration,
+    )
+    .unwrap();
+    assert_eq!(
+        kernels::linear_rate(500., 0.0002, 750.),
+        linear.rate_at_time(time).value()
+    );
+    assert_eq!(
+        kernels::linear_incremental_volume(500., 0.0002, 750.),
+        linear.incremental_volume_at_time(time)
+    );
+}
+
+#[test]
+fn kernels_in_f32() {
+    let rate = kernels::hyperbolic_rate(500_f32, 0.002, 0.8, 750.);
+    let expected = kernels::hyperbolic_rate(500_f64, 0.002, 0.8, 750.);
+    assert!((f64::from(rate) - expected).abs() < 1e-5 * expected);
+}
+
+#[test]
+fn dual_numbers_match_rate_jacobian() {
+    let time = 750.;
+    let initial_rate = 500.;
+    let decline_rate = 0.002;
+    let exponent = 0.8;
+
+    let jacobian =
+        FitModel::Hyperbolic.rate_jacobian(&[initial_rate, decline_rate, exponent], time);
+    let derivatives = [
+        kernels::hyperbolic_rate(
+            Dual::variable(initial_rate),
+            Dual::constant(decline_rate),
+            Dual::constant(exponent),
+            Dual::constant(time),
+        ),
+        kernels::hyperbolic_rate(
+            Dual::constant(initial_rate),
+            Dual::variable(decline_rate),
+            Dual::constant(exponent),
+            Dual::constant(time),
+        ),
+        kernels::hyperbolic_rate(
+            Dual::constant(initial_rate),
+            Dual::constant(decline_rate),
+            Dual::variable(exponent),
+            Dual::constant(time),
+        ),
+    ]
+    .map(|rate| rate.derivative);
+
+    for (expected, actual) in jacobian.into_iter().zip(derivatives) {
+        assert!(
+            (actual - expected).abs() <= 1e-10 * expected.abs(),
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    // The derivative with respect to time is the decline of the rate.
+    let rate = kernels::exponential_rate(
+        Dual::constant(initial_rate),
+        Dual::constant(decline_rate),
+        Dual::variable(time),
+    );
+    assert!((rate.derivative + decline_rate * rate.value).abs() < 1e-12);
+}
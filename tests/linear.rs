@@ -65,6 +65,33 @@ fn linear_from_final_rate() {
     insta::assert_snapshot!(calculated_duration, @"1461");
 }
 
+#[test]
+fn linear_from_final_decline_rate() {
+    let initial_rate = ProductionRate::<AverageDaysTime>::new(50.);
+    let decline_rate = NominalDeclineRate::<AverageYearsTime>::new(0.2).into();
+    let final_decline_rate = NominalDeclineRate::<AverageYearsTime>::new(1.).into();
+
+    let parameters =
+        LinearParameters::from_final_decline_rate(initial_rate, decline_rate, final_decline_rate)
+            .unwrap();
+    insta::assert_snapshot!(parameters.incremental_duration().days, @"1461");
+    insta::assert_snapshot!(
+        NominalDeclineRate::<AverageYearsTime>::from(
+            parameters.decline_rate_at_time(parameters.incremental_duration())
+        )
+        .value(),
+        @"1"
+    );
+
+    // The decline rate of a linear decline only increases.
+    let result = LinearParameters::from_final_decline_rate(
+        initial_rate,
+        decline_rate,
+        NominalDeclineRate::<AverageYearsTime>::new(0.1).into(),
+    );
+    insta::assert_snapshot!(result.unwrap_err(), @"duration is negative, but expected a positive number");
+}
+
 #[test]
 fn linear_incremental_volume_at_time() {
     let initial_rate = ProductionRate::<AverageDaysTime>::new(50.);
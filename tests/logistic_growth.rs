@@ -0,0 +1,191 @@
+use decline_curve_analysis::{
+    AverageDaysTime, AverageYearsTime, LogisticGrowthParameters, ProductionRate,
+};
+use proptest::prelude::*;
+
+#[test]
+fn logistic_growth_from_incremental_duration() {
+    let params = LogisticGrowthParameters::from_incremental_duration(
+        100_000.,
+        5.,
+        AverageYearsTime { years: 10. },
+        0.9,
+    )
+    .unwrap();
+
+    insta::assert_snapshot!(params.incremental_duration().years, @"10");
+    insta::assert_snapshot!(params.final_rate().value(), @"2133.6523758275534");
+}
+
+#[test]
+fn logistic_growth_incremental_volume_at_time() {
+    let params = LogisticGrowthParameters::from_incremental_duration(
+        100_000.,
+        5.,
+        AverageYearsTime { years: 10. },
+        0.9,
+    )
+    .unwrap();
+
+    // Calculate past the end to check the total.
+    insta::assert_snapshot!(params.incremental_volume_at_time(AverageYearsTime { years: 20. }), @"61369.92251378104");
+
+    // Check a point somewhere in the middle.
+    insta::assert_snapshot!(params.incremental_volume_at_time(AverageYearsTime { years: 5. }), @"45985.0680128801");
+}
+
+#[test]
+fn logistic_growth_from_incremental_volume() {
+    let params = LogisticGrowthParameters::<AverageYearsTime>::from_incremental_volume(
+        100_000., 5., 50_000., 1.,
+    )
+    .unwrap();
+
+    // Half of the carrying capacity is produced when `t^n = a`.
+    insta::assert_snapshot!(params.incremental_duration().years, @"5");
+}
+
+#[test]
+fn logistic_growth_from_final_rate() {
+    let params = LogisticGrowthParameters::from_final_rate(
+        100_000.,
+        5.,
+        ProductionRate::<AverageYearsTime>::new(1000.),
+        0.9,
+    )
+    .unwrap();
+
+    insta::assert_snapshot!(params.incremental_duration().years, @"17.83051353958541");
+    #[cfg(all(feature = "std", not(feature = "deterministic")))]
+    insta::assert_snapshot!(params.final_rate().value(), @"999.9999999999995");
+    #[cfg(any(not(feature = "std"), feature = "deterministic"))]
+    insta::assert_snapshot!(params.final_rate().value(), @"999.9999999999999");
+}
+
+#[test]
+fn logistic_growth_from_final_rate_after_peak() {
+    // With an exponent greater than one, the rate peaks before declining.
+    let params = LogisticGrowthParameters::from_final_rate(
+        100_000.,
+        5.,
+        ProductionRate::<AverageYearsTime>::new(1000.),
+        1.5,
+    )
+    .unwrap();
+
+    insta::assert_snapshot!(params.incremental_duration().years, @"13.029328987949963");
+    insta::assert_snapshot!(params.final_rate().value(), @"1000.0000000000001");
+
+    // The final rate is above the peak rate, so it can't be reached.
+    let result = LogisticGrowthParameters::from_final_rate(
+        100_000.,
+        5.,
+        ProductionRate::<AverageYearsTime>::new(1_000_000.),
+        1.5,
+    );
+    insta::assert_snapshot!(result.unwrap_err(), @"cannot solve decline: no finite solution exists for the given parameters");
+}
+
+#[test]
+fn volume_range() {
+    // The cumulative volume approaches the carrying capacity but never reaches it.
+    let result = LogisticGrowthParameters::<AverageYearsTime>::from_incremental_volume(
+        100_000., 5., 100_000., 0.9,
+    );
+    insta::assert_snapshot!(result.unwrap_err(), @"cannot solve decline: no finite solution exists for the given parameters");
+}
+
+#[test]
+fn invalid_parameters() {
+    let result = LogisticGrowthParameters::from_incremental_duration(
+        0.,
+        5.,
+        AverageYearsTime { years: 10. },
+        0.9,
+    );
+    insta::assert_snapshot!(result.unwrap_err(), @"carrying capacity is negative or zero, but expected a positive number");
+
+    let result = LogisticGrowthParameters::from_incremental_duration(
+        100_000.,
+        f64::NAN,
+        AverageYearsTime { years: 10. },
+        0.9,
+    );
+    insta::assert_snapshot!(result.unwrap_err(), @"a is not-a-number, but expected a finite number");
+
+    let result = LogisticGrowthParameters::from_incremental_duration(
+        100_000.,
+        5.,
+        AverageYearsTime { years: 10. },
+        -0.9,
+    );
+    insta::assert_snapshot!(result.unwrap_err(), @"exponent is negative or zero, but expected a positive number");
+}
+
+#[test]
+fn zero_volume() {
+    let params = LogisticGrowthParameters::<AverageYearsTime>::from_incremental_volume(
+        100_000., 5., 0., 0.9,
+    )
+    .unwrap();
+    insta::assert_snapshot!(params.incremental_duration().years, @"0");
+    insta::assert_snapshot!(params.incremental_volume(), @"0");
+}
+
+#[test]
+fn duration_range() {
+    let result = LogisticGrowthParameters::from_incremental_duration(
+        100_000.,
+        5.,
+        AverageYearsTime { years: 10_000. },
+        0.9,
+    );
+    insta::assert_snapshot!(result.unwrap_err(), @"duration too long");
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(1000))]
+
+    #[test]
+    fn from_incremental_volume(
+        carrying_capacity in prop::num::f64::ANY,
+        a in prop::num::f64::ANY,
+        volume in prop::num::f64::ANY,
+        exponent in prop::num::f64::ANY,
+    ) {
+        let result = LogisticGrowthParameters::<AverageDaysTime>::from_incremental_volume(
+            carrying_capacity,
+            a,
+            volume,
+            exponent,
+        );
+
+        if let Ok(params) = result {
+            let duration = params.incremental_duration().days;
+            prop_assert!(duration >= 0., "Duration should be non-negative, got {}", duration);
+            prop_assert!(duration.is_finite(), "Duration should be finite, got {}", duration);
+        }
+    }
+
+    #[test]
+    fn from_final_rate(
+        carrying_capacity in prop::num::f64::ANY,
+        a in prop::num::f64::ANY,
+        final_rate in prop::num::f64::ANY,
+        exponent in prop::num::f64::ANY,
+    ) {
+        let final_rate = ProductionRate::<AverageDaysTime>::new(final_rate);
+        let result = LogisticGrowthParameters::from_final_rate(
+            carrying_capacity,
+            a,
+            final_rate,
+            exponent,
+        );
+
+        if let Ok(params) = result {
+            let duration = params.incremental_duration().days;
+            prop_assert!(duration >= 0., "Duration should be non-negative, got {}", duration);
+            prop_assert!(duration.is_finite(), "Duration should be finite, got {}", duration);
+        }
+    }
+}
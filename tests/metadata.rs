@@ -0,0 +1,104 @@
+use decline_curve_analysis::{
+    AverageYearsTime, ExponentialParameters, FlatParameters, Forecast, Labeled, LabeledForecast,
+    Metadata, NominalDeclineRate, ParameterSource, Phase, ProductionRate, Segment,
+};
+
+fn segments() -> Vec<Segment<AverageYearsTime>> {
+    vec![
+        FlatParameters::from_incremental_duration(
+            ProductionRate::new(1000.),
+            AverageYearsTime { years: 1. },
+        )
+        .unwrap()
+        .into(),
+        ExponentialParameters::from_incremental_duration(
+            ProductionRate::new(1000.),
+            NominalDeclineRate::new(0.5),
+            AverageYearsTime { years: 10. },
+        )
+        .unwrap()
+        .into(),
+    ]
+}
+
+#[test]
+fn labeled_segments() {
+    let [plateau, decline] = segments().try_into().unwrap();
+    let forecast = LabeledForecast::from_segments(
+        vec![
+            Labeled::new(plateau, "plateau"),
+            Labeled::new(decline, "decline"),
+        ],
+        "type well",
+    );
+
+    assert_eq!(forecast.forecast(), &Forecast::new(segments()));
+    assert_eq!(*forecast.metadata(), "type well");
+    insta::assert_debug_snapshot!(
+        forecast
+            .labeled_segments()
+            .map(|segment| (*segment.metadata, segment.value.incremental_volume()))
+            .collect::<Vec<_>>(),
+        @r#"
+    [
+        (
+            "plateau",
+            1000.0,
+        ),
+        (
+            "decline",
+            1986.524106001829,
+        ),
+    ]
+    "#
+    );
+
+    let (segments, metadata) = forecast.into_labeled_segments();
+    assert_eq!(metadata, "type well");
+    assert_eq!(segments[1].metadata, "decline");
+}
+
+#[test]
+fn default_segment_metadata() {
+    let mut forecast = LabeledForecast::from_forecast(
+        Forecast::new(segments()),
+        Metadata {
+            name: Some("Smith 1H".into()),
+            phase: Some(Phase::Oil),
+            ..Metadata::default()
+        },
+    );
+    forecast.segment_metadata_mut()[1].source = Some(ParameterSource::Fit);
+
+    insta::assert_debug_snapshot!(forecast.segment_metadata(), @r"
+    [
+        Metadata {
+            name: None,
+            phase: None,
+            source: None,
+            analyst: None,
+            timestamp: None,
+        },
+        Metadata {
+            name: None,
+            phase: None,
+            source: Some(
+                Fit,
+            ),
+            analyst: None,
+            timestamp: None,
+        },
+    ]
+    ");
+}
+
+#[test]
+fn mismatched_segment_metadata() {
+    let result = LabeledForecast::new(
+        Forecast::new(segments()),
+        Metadata::default(),
+        vec![Metadata::default()],
+    );
+
+    insta::assert_snapshot!(result.unwrap_err(), @"segment metadata has a length of 1, but expected 2");
+}
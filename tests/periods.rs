@@ -0,0 +1,114 @@
+use decline_curve_analysis::{
+    AverageDaysTime, AverageYearsTime, ExponentialParameters, FlatParameters, Forecast,
+    NominalDeclineRate, ProductionRate, Segment,
+};
+
+#[test]
+fn yearly_volumes() {
+    let forecast = Forecast::new(vec![
+        FlatParameters::from_incremental_duration(
+            ProductionRate::new(1000.),
+            AverageYearsTime { years: 1.5 },
+        )
+        .unwrap()
+        .into(),
+        ExponentialParameters::from_incremental_duration(
+            ProductionRate::new(1000.),
+            NominalDeclineRate::new(0.5),
+            AverageYearsTime { years: 2. },
+        )
+        .unwrap()
+        .into(),
+    ]);
+    let volumes: Vec<_> = forecast
+        .yearly_volumes()
+        .map(|period| (period.start.years, period.end.years, period.volume as f32))
+        .collect();
+
+    insta::assert_debug_snapshot!(volumes, @"
+    [
+        (
+            0.0,
+            1.0,
+            1000.0,
+        ),
+        (
+            1.0,
+            2.0,
+            942.39844,
+        ),
+        (
+            2.0,
+            3.0,
+            612.86847,
+        ),
+        (
+            3.0,
+            3.5,
+            208.97423,
+        ),
+    ]
+    ");
+}
+
+#[test]
+fn period_volumes_sum_to_total() {
+    let forecast = Forecast::new(vec![
+        FlatParameters::from_incremental_duration(
+            ProductionRate::new(1000.),
+            AverageYearsTime { years: 1.5 },
+        )
+        .unwrap()
+        .into(),
+        ExponentialParameters::from_incremental_duration(
+            ProductionRate::new(1000.),
+            NominalDeclineRate::new(0.5),
+            AverageYearsTime { years: 2. },
+        )
+        .unwrap()
+        .into(),
+    ]);
+
+    for volumes in [
+        forecast.daily_volumes().collect::<Vec<_>>(),
+        forecast.monthly_volumes().collect(),
+        forecast
+            .volumes_by_period(AverageYearsTime { years: 0.3 })
+            .unwrap()
+            .collect(),
+    ] {
+        let total: f64 = volumes.iter().map(|period| period.volume).sum();
+        assert!((total - forecast.incremental_volume()).abs() < 1e-6);
+        assert_eq!(volumes.last().unwrap().end, forecast.incremental_duration());
+    }
+
+    insta::assert_snapshot!(forecast.monthly_volumes().count(), @"42");
+    insta::assert_snapshot!(forecast.daily_volumes().count(), @"1279");
+}
+
+#[test]
+fn segment_volumes_by_period() {
+    let segment = Segment::from(
+        FlatParameters::from_incremental_duration(
+            ProductionRate::<AverageDaysTime>::new(10.),
+            AverageDaysTime { days: 25. },
+        )
+        .unwrap(),
+    );
+
+    let volumes: Vec<_> = segment
+        .volumes_by_period(AverageDaysTime { days: 10. })
+        .unwrap()
+        .map(|period| period.volume)
+        .collect();
+    assert_eq!(volumes, [100., 100., 50.]);
+
+    let result = segment.volumes_by_period(AverageDaysTime { days: 0. });
+    insta::assert_snapshot!(result.err().unwrap(), @"period length is negative or zero, but expected a positive number");
+}
+
+#[test]
+fn empty_forecast_has_no_periods() {
+    let forecast = Forecast::<AverageDaysTime>::new(vec![]);
+    assert_eq!(forecast.monthly_volumes().count(), 0);
+}
@@ -0,0 +1,69 @@
+#![cfg(feature = "plot")]
+
+use decline_curve_analysis::{
+    AverageDaysTime, ExponentialParameters, FlatParameters, Forecast, NominalDeclineRate,
+    ProductionRate,
+    plot::{PlotOptions, rate_cumulative_svg, rate_time_svg},
+};
+
+#[test]
+fn rate_time_chart() {
+    let forecast = Forecast::new(vec![
+        FlatParameters::from_incremental_duration(
+            ProductionRate::new(100.),
+            AverageDaysTime { days: 30. },
+        )
+        .unwrap()
+        .into(),
+        ExponentialParameters::from_incremental_duration(
+            ProductionRate::new(100.),
+            NominalDeclineRate::new(0.01),
+            AverageDaysTime { days: 300. },
+        )
+        .unwrap()
+        .into(),
+    ]);
+
+    let history: Vec<_> = [(10., 98.), (40., 92.), (100., 48.), (200., 0.)]
+        .map(|(days, rate)| (AverageDaysTime { days }, ProductionRate::new(rate)))
+        .to_vec();
+    let options = PlotOptions {
+        caption: Some("Well 1".to_string()),
+        ..PlotOptions::default()
+    };
+    let svg = rate_time_svg(&forecast, &history, &options).unwrap();
+
+    assert!(svg.starts_with("<svg"));
+    assert!(svg.contains(r#"width="800" height="600""#));
+    assert!(svg.contains("Well 1"));
+    // The zero rate isn't drawn on the logarithmic axis.
+    insta::assert_snapshot!(svg.matches("<circle").count(), @"3");
+}
+
+#[test]
+fn rate_cumulative_chart() {
+    let forecast = Forecast::new(vec![
+        FlatParameters::from_incremental_duration(
+            ProductionRate::new(100.),
+            AverageDaysTime { days: 30. },
+        )
+        .unwrap()
+        .into(),
+        ExponentialParameters::from_incremental_duration(
+            ProductionRate::new(100.),
+            NominalDeclineRate::new(0.01),
+            AverageDaysTime { days: 300. },
+        )
+        .unwrap()
+        .into(),
+    ]);
+
+    let history = [
+        (1000., ProductionRate::new(100.)),
+        (5000., ProductionRate::new(60.)),
+    ];
+    let svg = rate_cumulative_svg(&forecast, &history, &PlotOptions::default()).unwrap();
+
+    assert!(svg.contains("Cumulative volume"));
+    insta::assert_snapshot!(svg.matches("<circle").count(), @"2");
+}
@@ -0,0 +1,97 @@
+use decline_curve_analysis::{
+    AverageYearsTime, ExponentialParameters, FlatParameters, Forecast, NominalDeclineRate,
+    ProductionRate, TimeGrid, evaluate_portfolio,
+};
+
+fn forecasts() -> Vec<Forecast<AverageYearsTime>> {
+    (1..=100)
+        .map(|index| {
+            Forecast::new(vec![
+                ExponentialParameters::from_incremental_duration(
+                    ProductionRate::new(100. * index as f64),
+                    NominalDeclineRate::new(0.5),
+                    AverageYearsTime { years: 2.5 },
+                )
+                .unwrap()
+                .into(),
+            ])
+        })
+        .collect()
+}
+
+#[test]
+fn portfolio_matches_single_forecasts() {
+    let forecasts = forecasts();
+    let grid = TimeGrid::uniform(AverageYearsTime { years: 1. }, 4).unwrap();
+    let matrix = evaluate_portfolio(&forecasts, &grid);
+
+    assert_eq!(matrix.forecast_count(), 100);
+    assert_eq!(matrix.period_count(), 4);
+
+    for (index, forecast) in forecasts.iter().enumerate() {
+        let expected: Vec<_> = forecast
+            .yearly_volumes()
+            .map(|period| period.volume)
+            .chain([0.])
+            .collect();
+        assert_eq!(matrix.forecast_volumes(index), expected);
+    }
+
+    let totals: Vec<_> = matrix
+        .period_totals()
+        .into_iter()
+        .map(|total| total as f32)
+        .collect();
+    insta::assert_debug_snapshot!(totals, @"
+    [
+        397404.03,
+        241037.73,
+        82188.39,
+        0.0,
+    ]
+    ");
+}
+
+#[test]
+fn grid_outside_forecast() {
+    let forecast = Forecast::new(vec![
+        FlatParameters::from_incremental_duration(
+            ProductionRate::new(100.),
+            AverageYearsTime { years: 2. },
+        )
+        .unwrap()
+        .into(),
+    ]);
+    let grid = TimeGrid::new(vec![
+        AverageYearsTime { years: -1. },
+        AverageYearsTime { years: 0.5 },
+        AverageYearsTime { years: 1.5 },
+        AverageYearsTime { years: 3. },
+    ])
+    .unwrap();
+
+    let mut out = [0.; 3];
+    forecast.volumes_on_grid(&grid, &mut out);
+    assert_eq!(out, [50., 100., 50.]);
+}
+
+#[test]
+fn invalid_grid() {
+    let result = TimeGrid::new(vec![
+        AverageYearsTime { years: 1. },
+        AverageYearsTime { years: 1. },
+    ]);
+    insta::assert_snapshot!(result.unwrap_err(), @"time grid boundaries are not strictly increasing");
+
+    let result = TimeGrid::new(vec![AverageYearsTime { years: f64::NAN }]);
+    insta::assert_snapshot!(result.unwrap_err(), @"time grid boundary is not-a-number, but expected a finite number");
+}
+
+#[test]
+fn empty_grid() {
+    let grid = TimeGrid::<AverageYearsTime>::new(vec![]).unwrap();
+    let matrix = evaluate_portfolio(&forecasts(), &grid);
+    assert_eq!(matrix.period_count(), 0);
+    assert_eq!(matrix.forecast_count(), 0);
+    assert!(matrix.values().is_empty());
+}
@@ -0,0 +1,262 @@
+use decline_curve_analysis::{
+    AverageDaysTime, AverageYearsTime, NominalDeclineRate, PowerLawExponentialParameters,
+    ProductionRate,
+};
+use proptest::prelude::*;
+
+fn parameters() -> PowerLawExponentialParameters<AverageYearsTime> {
+    PowerLawExponentialParameters::from_incremental_duration(
+        ProductionRate::new(1000.),
+        0.8,
+        NominalDeclineRate::new(0.05),
+        AverageYearsTime { years: 10. },
+        0.4,
+    )
+    .unwrap()
+}
+
+#[test]
+fn power_law_exponential_from_incremental_duration() {
+    let params = parameters();
+
+    insta::assert_snapshot!(params.incremental_duration().years, @"10");
+    insta::assert_snapshot!(params.final_rate().value(), @"81.30813993751072");
+}
+
+#[test]
+fn power_law_exponential_incremental_volume_at_time() {
+    let params = parameters();
+
+    // Calculate past the end to check the total.
+    insta::assert_snapshot!(params.incremental_volume_at_time(AverageYearsTime { years: 20. }), @"2206.615646027514");
+
+    // Check a point somewhere in the middle.
+    insta::assert_snapshot!(params.incremental_volume_at_time(AverageYearsTime { years: 5. }), @"1615.6689603969794");
+}
+
+#[test]
+fn power_law_exponential_from_incremental_volume() {
+    let expected = parameters();
+
+    let params = PowerLawExponentialParameters::from_incremental_volume(
+        expected.initial_rate(),
+        expected.initial_decline_rate(),
+        expected.infinite_decline_rate(),
+        expected.incremental_volume(),
+        expected.exponent(),
+    )
+    .unwrap();
+
+    insta::assert_snapshot!(params.incremental_duration().years, @"10");
+}
+
+#[test]
+fn power_law_exponential_from_final_rate() {
+    let expected = parameters();
+
+    let params = PowerLawExponentialParameters::from_final_rate(
+        expected.initial_rate(),
+        expected.initial_decline_rate(),
+        expected.infinite_decline_rate(),
+        expected.final_rate(),
+        expected.exponent(),
+    )
+    .unwrap();
+
+    insta::assert_snapshot!(params.incremental_duration().years, @"10");
+}
+
+#[test]
+fn power_law_exponential_from_final_decline_rate() {
+    let params = PowerLawExponentialParameters::from_final_decline_rate(
+        ProductionRate::<AverageYearsTime>::new(1000.),
+        0.8,
+        NominalDeclineRate::new(0.05),
+        NominalDeclineRate::new(0.15),
+        0.4,
+    )
+    .unwrap();
+
+    insta::assert_snapshot!(params.incremental_duration().years, @"6.948909098482965");
+}
+
+#[test]
+fn power_law_exponential_final_decline_rate_impossible() {
+    // The decline rate approaches the infinite decline rate, so it can never be reached.
+    let result = PowerLawExponentialParameters::from_final_decline_rate(
+        ProductionRate::<AverageYearsTime>::new(1000.),
+        0.8,
+        NominalDeclineRate::new(0.05),
+        NominalDeclineRate::new(0.05),
+        0.4,
+    );
+    insta::assert_snapshot!(result.unwrap_err(), @"cannot solve decline: no finite solution exists for the given parameters");
+}
+
+#[test]
+fn volume_range() {
+    // The volume approaches a finite limit, so a huge volume can't be reached.
+    let result = PowerLawExponentialParameters::from_incremental_volume(
+        ProductionRate::<AverageYearsTime>::new(1000.),
+        0.8,
+        NominalDeclineRate::new(0.05),
+        1_000_000_000.,
+        0.4,
+    );
+    insta::assert_snapshot!(result.unwrap_err(), @"cannot solve decline: no finite solution exists for the given parameters");
+}
+
+#[test]
+fn decline_rate_wrong_sign() {
+    let result = PowerLawExponentialParameters::from_incremental_duration(
+        ProductionRate::<AverageYearsTime>::new(1000.),
+        -0.8,
+        NominalDeclineRate::new(0.05),
+        AverageYearsTime { years: 10. },
+        0.4,
+    );
+    insta::assert_snapshot!(result.unwrap_err(), @"decline rate has wrong sign");
+
+    let result = PowerLawExponentialParameters::from_final_rate(
+        ProductionRate::<AverageYearsTime>::new(1000.),
+        0.8,
+        NominalDeclineRate::new(0.05),
+        ProductionRate::new(2000.),
+        0.4,
+    );
+    insta::assert_snapshot!(result.unwrap_err(), @"decline rate has wrong sign");
+}
+
+#[test]
+fn exponent_range() {
+    for exponent in [0., -0.5, 1., 1.5, f64::NAN] {
+        let result = PowerLawExponentialParameters::from_incremental_duration(
+            ProductionRate::<AverageYearsTime>::new(1000.),
+            0.8,
+            NominalDeclineRate::new(0.05),
+            AverageYearsTime { years: 10. },
+            exponent,
+        );
+        assert!(result.is_err(), "exponent {exponent} should be rejected");
+    }
+}
+
+#[test]
+fn negative_infinite_decline_rate() {
+    let result = PowerLawExponentialParameters::from_incremental_duration(
+        ProductionRate::<AverageYearsTime>::new(1000.),
+        0.8,
+        NominalDeclineRate::new(-0.05),
+        AverageYearsTime { years: 10. },
+        0.4,
+    );
+    insta::assert_snapshot!(result.unwrap_err(), @"infinite decline rate is negative, but expected a positive number");
+}
+
+#[test]
+fn zero_volume() {
+    let params = PowerLawExponentialParameters::from_incremental_volume(
+        ProductionRate::<AverageYearsTime>::new(1000.),
+        0.8,
+        NominalDeclineRate::new(0.05),
+        0.,
+        0.4,
+    )
+    .unwrap();
+    insta::assert_snapshot!(params.incremental_duration().years, @"0");
+}
+
+#[test]
+fn zero_duration() {
+    let params = PowerLawExponentialParameters::from_incremental_duration(
+        ProductionRate::<AverageYearsTime>::new(1000.),
+        0.8,
+        NominalDeclineRate::new(0.05),
+        AverageYearsTime { years: 0. },
+        0.4,
+    )
+    .unwrap();
+    insta::assert_snapshot!(params.incremental_volume(), @"0");
+    insta::assert_snapshot!(params.final_rate().value(), @"1000");
+}
+
+#[test]
+fn final_rate_roundtrip() {
+    let params = PowerLawExponentialParameters::from_final_rate(
+        ProductionRate::<AverageYearsTime>::new(1000.),
+        0.8,
+        NominalDeclineRate::new(0.05),
+        ProductionRate::new(100.),
+        0.4,
+    )
+    .unwrap();
+    insta::assert_snapshot!(params.final_rate().value(), @"99.99999999999997");
+}
+
+#[test]
+fn duration_range() {
+    let result = PowerLawExponentialParameters::from_incremental_duration(
+        ProductionRate::<AverageYearsTime>::new(1000.),
+        0.8,
+        NominalDeclineRate::new(0.05),
+        AverageYearsTime { years: 10_000. },
+        0.4,
+    );
+    insta::assert_snapshot!(result.unwrap_err(), @"duration too long");
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(200))]
+
+    #[test]
+    fn from_incremental_duration(
+        rate in prop::num::f64::ANY,
+        decline in prop::num::f64::ANY,
+        infinite_decline in prop::num::f64::ANY,
+        duration in prop::num::f64::ANY,
+        exponent in prop::num::f64::ANY,
+    ) {
+        let initial_rate = ProductionRate::<AverageDaysTime>::new(rate);
+        let infinite_decline_rate = NominalDeclineRate::<AverageDaysTime>::new(infinite_decline);
+        let duration = AverageDaysTime { days: duration };
+        let result = PowerLawExponentialParameters::from_incremental_duration(
+            initial_rate,
+            decline,
+            infinite_decline_rate,
+            duration,
+            exponent,
+        );
+
+        if let Ok(params) = result {
+            let computed_volume = params.incremental_volume();
+            prop_assert!(computed_volume >= 0. || computed_volume.is_nan() || computed_volume.is_infinite(),
+                "Computed volume should be non-negative, got {}", computed_volume);
+        }
+    }
+
+    #[test]
+    fn from_final_rate(
+        rate in 1_f64..10_000.,
+        decline in 0.01_f64..5.,
+        infinite_decline in 0_f64..1.,
+        final_fraction in 0.01_f64..1.,
+        exponent in 0.05_f64..0.95,
+    ) {
+        let initial_rate = ProductionRate::<AverageYearsTime>::new(rate);
+        let infinite_decline_rate = NominalDeclineRate::<AverageYearsTime>::new(infinite_decline);
+        let final_rate = ProductionRate::<AverageYearsTime>::new(rate * final_fraction);
+        let result = PowerLawExponentialParameters::from_final_rate(
+            initial_rate,
+            decline,
+            infinite_decline_rate,
+            final_rate,
+            exponent,
+        );
+
+        if let Ok(params) = result {
+            let duration = params.incremental_duration().years;
+            prop_assert!(duration >= 0., "Duration should be non-negative, got {}", duration);
+            prop_assert!(duration.is_finite(), "Duration should be finite, got {}", duration);
+        }
+    }
+}
@@ -0,0 +1,422 @@
+use decline_curve_analysis::{
+    AverageYearsTime, CorrelationMatrix, Distribution, DistributionParameter, ForecastDistribution,
+    MonteCarloOptions, PortfolioAggregation, ProductionRate, RandomNumberGenerator, SeededRng,
+    SegmentDistribution, SegmentParameter, simulate, simulate_portfolio,
+    simulate_portfolio_with_rng, simulate_with_rng,
+};
+
+fn distribution() -> ForecastDistribution<AverageYearsTime> {
+    ForecastDistribution::new(vec![
+        SegmentDistribution::Flat {
+            rate: Some(Distribution::Triangular {
+                minimum: 800.,
+                mode: 1000.,
+                maximum: 1400.,
+            }),
+            incremental_duration: Distribution::Uniform {
+                minimum: 0.5,
+                maximum: 1.,
+            },
+        },
+        SegmentDistribution::Hyperbolic {
+            initial_rate: None,
+            initial_decline_rate: Distribution::LogNormal {
+                mu: 0.5f64.ln(),
+                sigma: 0.3,
+            },
+            incremental_duration: 30.0.into(),
+            exponent: Distribution::Normal {
+                mean: 0.9,
+                standard_deviation: 0.2,
+            },
+        },
+    ])
+    .unwrap()
+}
+
+fn times() -> Vec<AverageYearsTime> {
+    [0., 1., 5., 10.]
+        .into_iter()
+        .map(|years| AverageYearsTime { years })
+        .collect()
+}
+
+#[test]
+fn simulation_is_reproducible() {
+    let options = MonteCarloOptions {
+        realizations: 200,
+        seed: 42,
+        economic_limit_rate: ProductionRate::new(10.),
+    };
+
+    let first = simulate(&distribution(), &times(), &options).unwrap();
+    let second = simulate(&distribution(), &times(), &options).unwrap();
+    assert_eq!(first, second);
+
+    let other_seed = simulate(
+        &distribution(),
+        &times(),
+        &MonteCarloOptions { seed: 7, ..options },
+    )
+    .unwrap();
+    assert_ne!(first.realization_eurs, other_seed.realization_eurs);
+}
+
+#[test]
+fn percentiles_are_ordered() {
+    let result = simulate(
+        &distribution(),
+        &times(),
+        &MonteCarloOptions {
+            realizations: 500,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    assert_eq!(result.realization_eurs.len(), 500);
+    assert_eq!(result.rate_profile.len(), 4);
+    for percentiles in
+        std::iter::once(&result.estimated_ultimate_recovery).chain(&result.rate_profile)
+    {
+        assert!(percentiles.p90 <= percentiles.p50);
+        assert!(percentiles.p50 <= percentiles.p10);
+    }
+
+    let initial_rate = result.rate_profile[0];
+    assert!(initial_rate.p90 >= 800. && initial_rate.p10 <= 1400.);
+}
+
+#[test]
+fn constant_distributions_are_deterministic() {
+    let distribution = ForecastDistribution::<AverageYearsTime>::new(vec![
+        SegmentDistribution::Delay {
+            incremental_duration: 1.0.into(),
+        },
+        SegmentDistribution::Exponential {
+            initial_rate: Some(1000.0.into()),
+            decline_rate: 0.5.into(),
+            incremental_duration: 2.0.into(),
+        },
+    ])
+    .unwrap();
+
+    let result = simulate(
+        &distribution,
+        &[
+            AverageYearsTime { years: 0.5 },
+            AverageYearsTime { years: 4. },
+        ],
+        &MonteCarloOptions {
+            realizations: 10,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    let eur = result.estimated_ultimate_recovery;
+    assert_eq!(eur.p90, eur.p10);
+    insta::assert_snapshot!(eur.p50, @"1264.2411176571154");
+    assert_eq!(result.rate_profile[0].p50, 0.);
+    assert_eq!(result.rate_profile[1].p50, 0.);
+    assert_eq!(result.rejected_samples, 0);
+}
+
+#[test]
+fn sampling_continues_from_previous_rate() {
+    let mut rng = SeededRng::new(1);
+    let forecast = distribution().sample(&mut rng).unwrap();
+    let segments = forecast.segments();
+    assert_eq!(
+        segments[0].final_rate(),
+        segments[1].rate_at_time(AverageYearsTime { years: 0. })
+    );
+}
+
+#[test]
+fn invalid_distributions() {
+    let missing_rate =
+        ForecastDistribution::<AverageYearsTime>::new(vec![SegmentDistribution::Exponential {
+            initial_rate: None,
+            decline_rate: 0.5.into(),
+            incremental_duration: 2.0.into(),
+        }]);
+    insta::assert_snapshot!(missing_rate.unwrap_err(), @"initial rate is missing, but there's no previous segment to continue from");
+
+    let invalid = ForecastDistribution::<AverageYearsTime>::new(vec![SegmentDistribution::Flat {
+        rate: Some(Distribution::Uniform {
+            minimum: 10.,
+            maximum: 5.,
+        }),
+        incremental_duration: 1.0.into(),
+    }]);
+    insta::assert_snapshot!(invalid.unwrap_err(), @"minimum is 10, but expected it to be at most the maximum of 5");
+
+    let always_invalid =
+        ForecastDistribution::<AverageYearsTime>::new(vec![SegmentDistribution::Flat {
+            rate: Some((-1.).into()),
+            incremental_duration: 1.0.into(),
+        }])
+        .unwrap();
+    let result = simulate(&always_invalid, &[], &MonteCarloOptions::default());
+    insta::assert_snapshot!(result.unwrap_err(), @"rate is negative, but expected a positive number");
+
+    let result = simulate(
+        &distribution(),
+        &[],
+        &MonteCarloOptions {
+            realizations: 0,
+            ..Default::default()
+        },
+    );
+    insta::assert_snapshot!(result.unwrap_err(), @"realizations is 0, but expected at least 1");
+}
+
+#[test]
+fn portfolio_aggregation() {
+    let wells = vec![distribution(); 5];
+    let options = MonteCarloOptions {
+        realizations: 500,
+        seed: 3,
+        economic_limit_rate: ProductionRate::new(10.),
+    };
+    let portfolio = |aggregation| simulate_portfolio(&wells, &times(), &options, aggregation);
+
+    let arithmetic = portfolio(PortfolioAggregation::Arithmetic).unwrap();
+    let correlated = portfolio(PortfolioAggregation::MonteCarlo { correlation: 1. }).unwrap();
+    let independent = portfolio(PortfolioAggregation::MonteCarlo { correlation: 0. }).unwrap();
+    assert!(arithmetic.realization_eurs.is_empty());
+    assert_eq!(independent.realization_eurs.len(), 500);
+    assert_eq!(independent.rate_profile.len(), 4);
+
+    // Perfectly correlated wells add their percentiles.
+    let (eur, correlated_eur) = (
+        arithmetic.estimated_ultimate_recovery,
+        correlated.estimated_ultimate_recovery,
+    );
+    for (a, b) in [
+        (eur.p90, correlated_eur.p90),
+        (eur.p50, correlated_eur.p50),
+        (eur.p10, correlated_eur.p10),
+        (eur.mean, correlated_eur.mean),
+    ] {
+        assert!((a - b).abs() < 1e-6 * a, "{a} != {b}");
+    }
+
+    // Independent wells offset each other, which narrows the range with the same mean.
+    let independent_eur = independent.estimated_ultimate_recovery;
+    assert!(independent_eur.p90 > eur.p90);
+    assert!(independent_eur.p10 < eur.p10);
+    assert!((independent_eur.mean - eur.mean).abs() < 1e-6 * eur.mean);
+    assert_eq!(
+        independent,
+        portfolio(PortfolioAggregation::MonteCarlo { correlation: 0. }).unwrap()
+    );
+
+    let invalid = portfolio(PortfolioAggregation::MonteCarlo { correlation: 1.5 });
+    insta::assert_snapshot!(invalid.unwrap_err(), @"correlation is 1.5, but expected it to be between 0 and 1");
+
+    let empty = simulate_portfolio(&[], &times(), &options, PortfolioAggregation::Arithmetic);
+    insta::assert_snapshot!(empty.unwrap_err(), @"well count is 0, but expected at least 1");
+}
+
+#[test]
+fn correlated_parameters() {
+    let distribution =
+        ForecastDistribution::<AverageYearsTime>::new(vec![SegmentDistribution::Exponential {
+            initial_rate: Some(Distribution::Uniform {
+                minimum: 500.,
+                maximum: 1500.,
+            }),
+            decline_rate: Distribution::LogNormal {
+                mu: 0.5f64.ln(),
+                sigma: 0.3,
+            },
+            incremental_duration: 10.0.into(),
+        }])
+        .unwrap();
+    assert_eq!(
+        distribution.parameters()[1],
+        DistributionParameter {
+            segment_index: 0,
+            parameter: SegmentParameter::DeclineRate,
+        }
+    );
+
+    let initial_rate = distribution
+        .parameter_index(0, SegmentParameter::InitialRate)
+        .unwrap();
+    let decline_rate = distribution
+        .parameter_index(0, SegmentParameter::DeclineRate)
+        .unwrap();
+    assert_eq!(
+        distribution.parameter_index(0, SegmentParameter::Exponent),
+        None
+    );
+    let correlation = CorrelationMatrix::identity(3)
+        .with_pair(initial_rate, decline_rate, -0.9)
+        .unwrap();
+    let distribution = distribution.with_correlation(correlation).unwrap();
+
+    let mut rng = SeededRng::new(5);
+    let samples: Vec<(f64, f64)> = (0..2000)
+        .map(|_| {
+            let forecast = distribution.sample(&mut rng).unwrap();
+            let segment = &forecast.segments()[0];
+            let initial_rate = segment.rate_at_time(AverageYearsTime { years: 0. });
+            let decline_rate = segment.decline_rate_at_time(AverageYearsTime { years: 0. });
+            (initial_rate.value(), decline_rate.value())
+        })
+        .collect();
+    assert!(
+        samples
+            .iter()
+            .all(|(rate, _)| (500. ..=1500.).contains(rate))
+    );
+
+    let mean = |values: &dyn Fn(&(f64, f64)) -> f64| {
+        samples.iter().map(values).sum::<f64>() / samples.len() as f64
+    };
+    let (rate_mean, decline_mean) = (mean(&|sample| sample.0), mean(&|sample| sample.1));
+    let covariance = mean(&|sample| (sample.0 - rate_mean) * (sample.1 - decline_mean));
+    let rate_variance = mean(&|sample| (sample.0 - rate_mean).powi(2));
+    let decline_variance = mean(&|sample| (sample.1 - decline_mean).powi(2));
+    let sample_correlation = covariance / (rate_variance * decline_variance).sqrt();
+    assert!(
+        (-0.95..-0.8).contains(&sample_correlation),
+        "correlation is {sample_correlation}"
+    );
+
+    let wrong_size = distribution.with_correlation(CorrelationMatrix::identity(2));
+    insta::assert_snapshot!(wrong_size.unwrap_err(), @"correlation matrix has a length of 2, but expected 3");
+}
+
+#[test]
+fn correlated_wells() {
+    let wells = vec![distribution(); 4];
+    let options = MonteCarloOptions {
+        realizations: 500,
+        seed: 3,
+        economic_limit_rate: ProductionRate::new(10.),
+    };
+
+    // Two pads of two wells, which are correlated within each pad.
+    let pads = CorrelationMatrix::identity(4)
+        .with_pair(0, 1, 0.8)
+        .unwrap()
+        .with_pair(2, 3, 0.8)
+        .unwrap();
+    let pad_result = simulate_portfolio(
+        &wells,
+        &times(),
+        &options,
+        PortfolioAggregation::Correlated(pads),
+    )
+    .unwrap();
+    let independent = simulate_portfolio(
+        &wells,
+        &times(),
+        &options,
+        PortfolioAggregation::MonteCarlo { correlation: 0. },
+    )
+    .unwrap();
+    let arithmetic =
+        simulate_portfolio(&wells, &times(), &options, PortfolioAggregation::Arithmetic).unwrap();
+
+    let spread = |result: &decline_curve_analysis::MonteCarloResult| {
+        result.estimated_ultimate_recovery.p10 - result.estimated_ultimate_recovery.p90
+    };
+    assert!(spread(&independent) < spread(&pad_result));
+    assert!(spread(&pad_result) < spread(&arithmetic));
+
+    let wrong_size = simulate_portfolio(
+        &wells,
+        &times(),
+        &options,
+        PortfolioAggregation::Correlated(CorrelationMatrix::identity(3)),
+    );
+    insta::assert_snapshot!(wrong_size.unwrap_err(), @"correlation matrix has a length of 3, but expected 4");
+}
+
+#[test]
+fn invalid_correlation_matrices() {
+    let not_square = CorrelationMatrix::new(vec![vec![1., 0.5], vec![0.5]]);
+    insta::assert_snapshot!(not_square.unwrap_err(), @"correlation matrix row has a length of 1, but expected 2");
+
+    let diagonal = CorrelationMatrix::new(vec![vec![1., 0.5], vec![0.5, 0.9]]);
+    insta::assert_snapshot!(diagonal.unwrap_err(), @"correlation matrix has 0.9 on the diagonal, but expected one");
+
+    let asymmetric = CorrelationMatrix::new(vec![vec![1., 0.5], vec![0.4, 1.]]);
+    insta::assert_snapshot!(asymmetric.unwrap_err(), @"correlation matrix isn't symmetric");
+
+    let inconsistent = CorrelationMatrix::new(vec![
+        vec![1., 0.9, -0.9],
+        vec![0.9, 1., 0.9],
+        vec![-0.9, 0.9, 1.],
+    ]);
+    insta::assert_snapshot!(inconsistent.unwrap_err(), @"correlation matrix isn't positive semi-definite");
+
+    let out_of_range = CorrelationMatrix::identity(2).with_pair(0, 2, 0.5);
+    insta::assert_snapshot!(out_of_range.unwrap_err(), @"correlation variable is 2, but expected it to be between 0 and 1");
+
+    // Perfectly correlated variables are allowed.
+    assert!(CorrelationMatrix::uniform(3, 1.).is_ok());
+}
+
+/// A linear congruential generator, which stands in for a generator from another library.
+struct Lcg(u64);
+
+impl RandomNumberGenerator for Lcg {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self
+            .0
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        self.0
+    }
+}
+
+#[test]
+fn custom_generators() {
+    let options = MonteCarloOptions {
+        realizations: 200,
+        seed: 42,
+        economic_limit_rate: ProductionRate::new(10.),
+    };
+
+    // Seeding from the options is the same as passing a seeded generator.
+    let seeded = simulate(&distribution(), &times(), &options).unwrap();
+    let with_rng =
+        simulate_with_rng(&distribution(), &times(), &options, &mut SeededRng::new(42)).unwrap();
+    assert_eq!(seeded, with_rng);
+
+    let first = simulate_with_rng(&distribution(), &times(), &options, &mut Lcg(1)).unwrap();
+    let second = simulate_with_rng(&distribution(), &times(), &options, &mut Lcg(1)).unwrap();
+    assert_eq!(first, second);
+    assert_ne!(first.realization_eurs, seeded.realization_eurs);
+
+    let wells = vec![distribution(); 3];
+    let aggregation = PortfolioAggregation::MonteCarlo { correlation: 0.5 };
+    let dynamic: &mut dyn RandomNumberGenerator = &mut Lcg(2);
+    assert_eq!(
+        simulate_portfolio_with_rng(&wells, &times(), &options, aggregation.clone(), dynamic)
+            .unwrap(),
+        simulate_portfolio_with_rng(&wells, &times(), &options, aggregation, &mut Lcg(2)).unwrap()
+    );
+}
+
+#[cfg(feature = "rand")]
+#[test]
+fn rand_generators() {
+    use rand_core::SeedableRng;
+
+    let options = MonteCarloOptions {
+        realizations: 200,
+        ..Default::default()
+    };
+    let mut rng = rand_xorshift::XorShiftRng::seed_from_u64(3);
+    let first = simulate_with_rng(&distribution(), &times(), &options, &mut rng).unwrap();
+    let mut rng = rand_xorshift::XorShiftRng::seed_from_u64(3);
+    let second = simulate_with_rng(&distribution(), &times(), &options, &mut rng).unwrap();
+    assert_eq!(first, second);
+}
@@ -0,0 +1,217 @@
+use decline_curve_analysis::{
+    AutoForecastOptions, AverageDaysTime, CalendarDate, DeclineCurveAnalysisError, FitOptions,
+    HistoryValue, HyperbolicParameters, ModelSelectionCriterion, NominalDeclineRate,
+    ProductionHistory, ProductionRate, ProductionRecord, QualityGate, QualityGates, auto_forecast,
+    fit_best_with_gates,
+};
+
+/// Samples a hyperbolic decline with an exponent of 0.8, monthly for two years.
+fn samples() -> Vec<(AverageDaysTime, ProductionRate<AverageDaysTime>)> {
+    let decline = HyperbolicParameters::from_incremental_duration(
+        ProductionRate::new(1000.),
+        NominalDeclineRate::new(0.004),
+        AverageDaysTime { days: 3000. },
+        0.8,
+    )
+    .unwrap();
+
+    (0..24)
+        .map(|month| {
+            let time = AverageDaysTime {
+                days: month as f64 * 30.4375,
+            };
+            (time, decline.rate_at_time(time))
+        })
+        .collect()
+}
+
+fn fit_with_gates(
+    samples: &[(AverageDaysTime, ProductionRate<AverageDaysTime>)],
+    gates: QualityGates<AverageDaysTime>,
+) -> Result<(), DeclineCurveAnalysisError> {
+    fit_best_with_gates(
+        samples,
+        ModelSelectionCriterion::Aic,
+        &FitOptions::default(),
+        &gates,
+    )
+    .map(|_| ())
+}
+
+#[test]
+fn passing_gates() {
+    let gates = QualityGates {
+        min_duration: Some(AverageDaysTime { days: 365. }),
+        min_samples: Some(12),
+        min_observed_decline: Some(0.5),
+        max_initial_decline_rate: Some(NominalDeclineRate::new(0.01)),
+        max_exponent: Some(1.),
+        min_r_squared: Some(0.99),
+    };
+    let fit = fit_best_with_gates(
+        &samples(),
+        ModelSelectionCriterion::Aic,
+        &FitOptions::default(),
+        &gates,
+    )
+    .unwrap();
+
+    assert!(fit.best.statistics.r_squared > 0.99);
+    assert!(
+        QualityGates::<AverageDaysTime>::default()
+            .check_samples(&[])
+            .is_ok()
+    );
+}
+
+#[test]
+fn insufficient_data() {
+    let samples = samples();
+
+    let error = fit_with_gates(
+        &samples[..6],
+        QualityGates {
+            min_samples: Some(12),
+            ..QualityGates::default()
+        },
+    )
+    .unwrap_err();
+    insta::assert_snapshot!(error, @"insufficient data: sample count is 6, but expected at least 12");
+    assert_eq!(
+        error,
+        DeclineCurveAnalysisError::InsufficientData {
+            gate: QualityGate::SampleCount,
+            value: 6.,
+            minimum: 12.,
+        }
+    );
+
+    let error = fit_with_gates(
+        &samples[..6],
+        QualityGates {
+            min_duration: Some(AverageDaysTime { days: 365. }),
+            ..QualityGates::default()
+        },
+    )
+    .unwrap_err();
+    insta::assert_snapshot!(error, @"insufficient data: duration is 152.1875, but expected at least 365");
+
+    // A flat well hasn't started declining.
+    let flat: Vec<_> = samples
+        .iter()
+        .map(|(time, _)| (*time, ProductionRate::new(500.)))
+        .collect();
+    let error = fit_with_gates(
+        &flat,
+        QualityGates {
+            min_observed_decline: Some(0.1),
+            ..QualityGates::default()
+        },
+    )
+    .unwrap_err();
+    insta::assert_snapshot!(error, @"insufficient data: observed decline is 0, but expected at least 0.1");
+}
+
+#[test]
+fn quality_gate_failed() {
+    let samples = samples();
+
+    let error = fit_with_gates(
+        &samples,
+        QualityGates {
+            max_exponent: Some(0.5),
+            ..QualityGates::default()
+        },
+    )
+    .unwrap_err();
+    let DeclineCurveAnalysisError::QualityGateFailed { gate, value, limit } = error else {
+        panic!("expected a failed quality gate, got {error:?}");
+    };
+    assert_eq!(gate, QualityGate::Exponent);
+    insta::assert_snapshot!(format!("{value:.2}"), @"0.80");
+    assert_eq!(limit, 0.5);
+    insta::assert_snapshot!(
+        DeclineCurveAnalysisError::QualityGateFailed { gate, value: 0.8, limit },
+        @"quality gate failed: exponent is 0.8, but expected at most 0.5"
+    );
+
+    let error = fit_with_gates(
+        &samples,
+        QualityGates {
+            max_initial_decline_rate: Some(NominalDeclineRate::new(0.001)),
+            ..QualityGates::default()
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(
+        error,
+        DeclineCurveAnalysisError::QualityGateFailed {
+            gate: QualityGate::InitialDeclineRate,
+            limit: 0.001,
+            ..
+        }
+    ));
+
+    // Noise that the fit can't explain.
+    let noisy: Vec<_> = samples
+        .iter()
+        .enumerate()
+        .map(|(i, (time, rate))| {
+            let noise = [1.4, 0.6, 1.3, 0.7][i % 4];
+            (*time, ProductionRate::new(rate.value() * noise))
+        })
+        .collect();
+    let error = fit_with_gates(
+        &noisy,
+        QualityGates {
+            min_r_squared: Some(0.9),
+            ..QualityGates::default()
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(
+        error,
+        DeclineCurveAnalysisError::QualityGateFailed {
+            gate: QualityGate::RSquared,
+            limit: 0.9,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn auto_forecast_quality_gates() {
+    // Six months of history is too short to forecast automatically.
+    let records = samples()[..6]
+        .iter()
+        .enumerate()
+        .map(|(month, (_, rate))| ProductionRecord {
+            date: CalendarDate::new(2024, month as u32 + 1, 1).unwrap(),
+            value: rate.value() * 30.4375,
+        })
+        .collect();
+    let history = ProductionHistory::new(HistoryValue::MonthlyVolume, records).unwrap();
+
+    let options = AutoForecastOptions {
+        segmentation: None,
+        quality_gates: QualityGates {
+            min_duration: Some(AverageDaysTime { days: 365. }),
+            ..QualityGates::default()
+        },
+        ..AutoForecastOptions::new(ProductionRate::<AverageDaysTime>::new(20.))
+    };
+    let error = auto_forecast(&history, &options).unwrap_err();
+    assert!(matches!(
+        error,
+        DeclineCurveAnalysisError::InsufficientData {
+            gate: QualityGate::Duration,
+            ..
+        }
+    ));
+
+    let options = AutoForecastOptions {
+        quality_gates: QualityGates::default(),
+        ..options
+    };
+    assert!(auto_forecast(&history, &options).is_ok());
+}
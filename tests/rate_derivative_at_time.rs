@@ -0,0 +1,119 @@
+use decline_curve_analysis::{
+    AverageYearsTime, DelayParameters, ExponentialParameters, FlatParameters, Forecast,
+    HarmonicParameters, HyperbolicParameters, LinearParameters, LogisticGrowthParameters,
+    NominalDeclineRate, PowerLawExponentialParameters, ProductionRate, Segment,
+};
+
+fn segments() -> Vec<Segment<AverageYearsTime>> {
+    let rate = ProductionRate::new(1000.);
+    let decline_rate = NominalDeclineRate::new(0.3);
+    let duration = AverageYearsTime { years: 10. };
+
+    vec![
+        DelayParameters::from_incremental_duration(duration)
+            .unwrap()
+            .into(),
+        ExponentialParameters::from_incremental_duration(rate, decline_rate, duration)
+            .unwrap()
+            .into(),
+        FlatParameters::from_incremental_duration(rate, duration)
+            .unwrap()
+            .into(),
+        HarmonicParameters::from_incremental_duration(rate, decline_rate, duration)
+            .unwrap()
+            .into(),
+        HyperbolicParameters::from_incremental_duration(rate, decline_rate, duration, 0.5)
+            .unwrap()
+            .into(),
+        LinearParameters::from_incremental_duration(rate, NominalDeclineRate::new(0.05), duration)
+            .unwrap()
+            .into(),
+        LogisticGrowthParameters::from_incremental_duration(10_000., 5., duration, 1.5)
+            .unwrap()
+            .into(),
+        PowerLawExponentialParameters::from_incremental_duration(
+            rate,
+            0.3,
+            NominalDeclineRate::new(0.01),
+            duration,
+            0.5,
+        )
+        .unwrap()
+        .into(),
+    ]
+}
+
+#[test]
+fn matches_finite_difference() {
+    let step = 1e-6;
+
+    for segment in segments() {
+        for years in [0.5, 2., 7.5] {
+            let rate = |years| segment.rate_at_time(AverageYearsTime { years }).value();
+            let expected = (rate(years + step) - rate(years - step)) / (2. * step);
+            let actual = segment.rate_derivative_at_time(AverageYearsTime { years });
+
+            assert!(
+                (actual - expected).abs() < 1e-4,
+                "{segment:?} at {years}: expected {expected}, got {actual}"
+            );
+        }
+    }
+}
+
+#[test]
+#[cfg_attr(feature = "fast-math", ignore = "exact values differ with fast-math")]
+fn rate_derivative_at_end() {
+    let hyperbolic = HyperbolicParameters::from_incremental_duration(
+        ProductionRate::<AverageYearsTime>::new(1000.),
+        NominalDeclineRate::new(0.8),
+        AverageYearsTime { years: 10. },
+        1.2,
+    )
+    .unwrap();
+
+    insta::assert_snapshot!(hyperbolic.rate_derivative_at_time(AverageYearsTime { years: 0. }), @"-800");
+    insta::assert_snapshot!(hyperbolic.rate_derivative_at_time(AverageYearsTime { years: 10. }), @"-10.552675105526255");
+
+    // Times past the end are clamped to the end, like the decline rate.
+    assert_eq!(
+        hyperbolic.rate_derivative_at_time(AverageYearsTime { years: 20. }),
+        hyperbolic.rate_derivative_at_time(AverageYearsTime { years: 10. })
+    );
+
+    // Logistic growth rises before its peak and declines after it.
+    let logistic_growth = LogisticGrowthParameters::from_incremental_duration(
+        10_000.,
+        5.,
+        AverageYearsTime { years: 10. },
+        1.5,
+    )
+    .unwrap();
+    assert!(logistic_growth.rate_derivative_at_time(AverageYearsTime { years: 0.5 }) > 0.);
+    assert!(logistic_growth.rate_derivative_at_time(AverageYearsTime { years: 5. }) < 0.);
+}
+
+#[test]
+fn forecast_rate_derivative_at_time() {
+    let forecast = Forecast::new(vec![
+        Segment::from(
+            FlatParameters::from_incremental_duration(
+                ProductionRate::<AverageYearsTime>::new(1000.),
+                AverageYearsTime { years: 1. },
+            )
+            .unwrap(),
+        ),
+        HarmonicParameters::from_incremental_duration(
+            ProductionRate::new(1000.),
+            NominalDeclineRate::new(0.5),
+            AverageYearsTime { years: 10. },
+        )
+        .unwrap()
+        .into(),
+    ]);
+
+    insta::assert_snapshot!(forecast.rate_derivative_at_time(AverageYearsTime { years: 0.5 }), @"0");
+    insta::assert_snapshot!(forecast.rate_derivative_at_time(AverageYearsTime { years: 1. }), @"-500");
+    insta::assert_snapshot!(forecast.rate_derivative_at_time(AverageYearsTime { years: 3. }), @"-125");
+    insta::assert_snapshot!(Forecast::<AverageYearsTime>::new(vec![]).rate_derivative_at_time(AverageYearsTime { years: 3. }), @"0");
+}
@@ -0,0 +1,244 @@
+use decline_curve_analysis::{
+    AverageDaysTime, AverageYearsTime, ExponentialParameters, FlatParameters, Forecast,
+    NominalDeclineRate, ProductionRate, RatioForecast, RatioSegment, RatioTrend, Segment,
+};
+
+fn primary() -> Forecast<AverageYearsTime> {
+    Forecast::new(vec![
+        FlatParameters::from_incremental_duration(
+            ProductionRate::new(1000.),
+            AverageYearsTime { years: 1. },
+        )
+        .unwrap()
+        .into(),
+        ExponentialParameters::from_incremental_duration(
+            ProductionRate::new(1000.),
+            NominalDeclineRate::new(0.5),
+            AverageYearsTime { years: 3. },
+        )
+        .unwrap()
+        .into(),
+    ])
+}
+
+fn ratio(initial_ratio: f64, trend: RatioTrend, years: f64) -> RatioSegment<AverageYearsTime> {
+    RatioSegment::from_incremental_duration(initial_ratio, trend, AverageYearsTime { years })
+        .unwrap()
+}
+
+#[test]
+fn constant_ratio_scales_primary() {
+    let primary = primary();
+    let ratios = RatioForecast::new(vec![ratio(2.5, RatioTrend::Constant, 10.)]);
+
+    assert_eq!(
+        ratios.incremental_volume(&primary),
+        2.5 * primary.incremental_volume()
+    );
+    assert_eq!(
+        ratios
+            .rate_at_time(&primary, AverageYearsTime { years: 2. })
+            .value(),
+        2.5 * primary.rate_at_time(AverageYearsTime { years: 2. }).value()
+    );
+}
+
+#[test]
+fn trending_ratios() {
+    let linear = ratio(1., RatioTrend::Linear { slope: 0.5 }, 2.);
+    insta::assert_snapshot!(linear.ratio_at_time(AverageYearsTime { years: 1. }), @"1.5");
+    insta::assert_snapshot!(linear.final_ratio(), @"2");
+    // Times past the end of the segment hold the final ratio.
+    insta::assert_snapshot!(linear.ratio_at_time(AverageYearsTime { years: 5. }), @"2");
+
+    let exponential = ratio(2., RatioTrend::Exponential { rate: -0.1 }, 4.);
+    insta::assert_snapshot!(exponential.final_ratio() as f32, @"1.3406401");
+
+    // A linearly increasing GOR over a flat primary has a trapezoidal volume.
+    let primary = Forecast::new(vec![
+        FlatParameters::from_incremental_duration(
+            ProductionRate::new(100.),
+            AverageYearsTime { years: 2. },
+        )
+        .unwrap()
+        .into(),
+    ]);
+    let ratios = RatioForecast::new(vec![linear]);
+    let volume = ratios.incremental_volume(&primary);
+    assert!((volume - 300.).abs() < 1e-9);
+}
+
+#[test]
+fn volumes_follow_primary_time_base() {
+    let primary = primary();
+    let ratios = RatioForecast::new(vec![
+        ratio(1., RatioTrend::Linear { slope: 0.2 }, 1.5),
+        ratio(1.3, RatioTrend::Exponential { rate: 0.1 }, 1.),
+    ]);
+
+    let volumes: Vec<_> = ratios
+        .volumes_by_period(&primary, AverageYearsTime { years: 1. })
+        .unwrap()
+        .collect();
+    assert_eq!(volumes.len(), 4);
+    assert_eq!(volumes.last().unwrap().end, primary.incremental_duration());
+
+    let total = volumes.iter().map(|period| period.volume).sum::<f64>();
+    assert!((total - ratios.incremental_volume(&primary)).abs() < 1e-6);
+
+    let volumes: Vec<_> = volumes.iter().map(|period| period.volume as f32).collect();
+    insta::assert_debug_snapshot!(volumes, @"
+    [
+        1100.0,
+        1010.88837,
+        675.8806,
+        415.92902,
+    ]
+    ");
+
+    // Reversed intervals are negative, and times outside of the primary forecast are clamped.
+    let forward = ratios.incremental_volume_between(
+        &primary,
+        AverageYearsTime { years: 0.5 },
+        AverageYearsTime { years: 10. },
+    );
+    let backward = ratios.incremental_volume_between(
+        &primary,
+        AverageYearsTime { years: 4. },
+        AverageYearsTime { years: 0.5 },
+    );
+    assert_eq!(forward, -backward);
+}
+
+#[test]
+fn hyperbolic_ratio() {
+    let hyperbolic = ratio(
+        500.,
+        RatioTrend::Hyperbolic {
+            rate: 0.8,
+            exponent: 0.5,
+        },
+        5.,
+    );
+    // The initial growth rate of 80% slows to 0.8 / (1 + 0.5 * 0.8 * t).
+    insta::assert_snapshot!(hyperbolic.ratio_at_time(AverageYearsTime { years: 1. }) as f32, @"980");
+    insta::assert_snapshot!(hyperbolic.final_ratio(), @"4500");
+
+    let time = hyperbolic.time_at_ratio(2000.).unwrap();
+    assert!((hyperbolic.ratio_at_time(time) - 2000.).abs() < 1e-9);
+
+    // A hyperbolic GOR over a flat oil segment is a hyperbolic incline.
+    let oil: Segment<AverageYearsTime> = FlatParameters::from_incremental_duration(
+        ProductionRate::new(100.),
+        AverageYearsTime { years: 5. },
+    )
+    .unwrap()
+    .into();
+    let gas = hyperbolic.secondary_segment(&oil).unwrap();
+    insta::assert_snapshot!(gas, @"Hyperbolic: qi=136.9/d, Di=-80%/yr nominal, b=-0.5, t=1826 d, qf=1232/d");
+
+    let numerical =
+        RatioForecast::new(vec![hyperbolic]).incremental_volume(&Forecast::new(vec![oil]));
+    assert!((gas.incremental_volume() - numerical).abs() < 1e-9 * numerical);
+}
+
+#[test]
+fn secondary_segments_integrate_exactly() {
+    let oil: Segment<AverageYearsTime> = ExponentialParameters::from_incremental_duration(
+        ProductionRate::new(1000.),
+        NominalDeclineRate::new(0.5),
+        AverageYearsTime { years: 3. },
+    )
+    .unwrap()
+    .into();
+
+    // A growing exponential GOR slows the decline of the gas.
+    let gor = ratio(2., RatioTrend::Exponential { rate: 0.1 }, 3.);
+    let gas = gor.secondary_segment(&oil).unwrap();
+    insta::assert_snapshot!(gas, @"Exponential: qi=5.476/d, D=40%/yr nominal, t=1096 d, qf=1.649/d");
+    let numerical =
+        RatioForecast::new(vec![gor]).incremental_volume(&Forecast::new(vec![oil.clone()]));
+    assert!((gas.incremental_volume() - numerical).abs() < 1e-9 * numerical);
+
+    // A GOR that grows as fast as the oil declines gives flat gas.
+    let gor = ratio(2., RatioTrend::Exponential { rate: 0.5 }, 3.);
+    insta::assert_snapshot!(gor.secondary_segment(&oil).unwrap(), @"Flat: q=5.476/d, t=1096 d");
+
+    let linear = ratio(2., RatioTrend::Linear { slope: 0.5 }, 3.);
+    insta::assert_snapshot!(linear.secondary_segment(&oil).unwrap_err(), @"the product of the ratio trend and the primary segment doesn't have the shape of a supported segment");
+    let short = ratio(2., RatioTrend::Constant, 1.);
+    insta::assert_snapshot!(short.secondary_segment(&oil).unwrap_err(), @"primary segment duration is 3, but expected it to be at most the ratio segment duration of 1");
+}
+
+#[test]
+fn secondary_forecast_splits_at_ratio_changes() {
+    let primary = primary();
+    let ratios = RatioForecast::new(vec![
+        ratio(2., RatioTrend::Constant, 1.5),
+        ratio(2., RatioTrend::Exponential { rate: 0.1 }, 1.),
+    ]);
+
+    let secondary = ratios.secondary_forecast(&primary).unwrap();
+    let segments = secondary
+        .segments()
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("\n");
+    // The exponential primary segment is split where the ratio starts growing, and again where
+    // the final ratio is held.
+    insta::assert_snapshot!(segments, @r"
+    Flat: q=5.476/d, t=365.2 d
+    Exponential: qi=5.476/d, D=50%/yr nominal, t=182.6 d, qf=4.264/d
+    Exponential: qi=4.264/d, D=40%/yr nominal, t=365.2 d, qf=2.859/d
+    Exponential: qi=2.859/d, D=50%/yr nominal, t=547.9 d, qf=1.35/d
+    ");
+
+    assert_eq!(
+        secondary.incremental_duration(),
+        primary.incremental_duration()
+    );
+    let numerical = ratios.incremental_volume(&primary);
+    assert!((secondary.incremental_volume() - numerical).abs() < 1e-9 * numerical);
+    for years in [0.5, 1.25, 2., 3.5] {
+        let time = AverageYearsTime { years };
+        let expected = ratios.rate_at_time(&primary, time).value();
+        assert!((secondary.rate_at_time(time).value() - expected).abs() < 1e-9 * expected);
+    }
+
+    let empty = RatioForecast::new(vec![])
+        .secondary_forecast(&primary)
+        .unwrap();
+    assert_eq!(empty.incremental_volume(), 0.);
+}
+
+#[test]
+fn empty_ratio_forecast_has_no_secondary_volume() {
+    let ratios = RatioForecast::<AverageYearsTime>::new(vec![]);
+    assert_eq!(ratios.incremental_volume(&primary()), 0.);
+    assert_eq!(ratios.ratio_at_time(AverageYearsTime { years: 1. }), 0.);
+}
+
+#[test]
+fn invalid_ratio_segments() {
+    let negative_ratio = RatioSegment::from_incremental_duration(
+        -1.,
+        RatioTrend::Constant,
+        AverageDaysTime { days: 10. },
+    );
+    insta::assert_snapshot!(negative_ratio.unwrap_err(), @"initial ratio is negative, but expected a positive number");
+
+    let becomes_negative = RatioSegment::from_incremental_duration(
+        1.,
+        RatioTrend::Linear { slope: -0.2 },
+        AverageDaysTime { days: 10. },
+    );
+    insta::assert_snapshot!(becomes_negative.unwrap_err(), @"ratio becomes negative before the end of the segment");
+
+    let overflows = RatioSegment::from_incremental_duration(
+        1.,
+        RatioTrend::Exponential { rate: 1000. },
+        AverageDaysTime { days: 10. },
+    );
+    insta::assert_snapshot!(overflows.unwrap_err(), @"final ratio is infinity, but expected a finite number");
+}
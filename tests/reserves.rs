@@ -0,0 +1,221 @@
+use decline_curve_analysis::{
+    AverageYearsTime, BindingConstraint, ExponentialParameters, FlatParameters, Forecast,
+    NominalDeclineRate, PowerLawExponentialParameters, ProductionRate, estimated_ultimate_recovery,
+    estimated_ultimate_recovery_with_maximum_life, remaining_reserves,
+    remaining_reserves_with_maximum_life,
+};
+
+#[test]
+fn eur() {
+    let forecast = Forecast::new(vec![
+        FlatParameters::from_incremental_duration(
+            ProductionRate::new(1000.),
+            AverageYearsTime { years: 1. },
+        )
+        .unwrap()
+        .into(),
+        ExponentialParameters::from_incremental_duration(
+            ProductionRate::new(1000.),
+            NominalDeclineRate::new(0.5),
+            AverageYearsTime { years: 10. },
+        )
+        .unwrap()
+        .into(),
+    ]);
+
+    let report = estimated_ultimate_recovery(&forecast, ProductionRate::new(100.)).unwrap();
+
+    insta::assert_snapshot!(report.estimated_ultimate_recovery, @"2800");
+    insta::assert_snapshot!(report.cumulative_production, @"0");
+    insta::assert_snapshot!(report.remaining_reserves, @"2800");
+    insta::assert_snapshot!(report.economic_life.years, @"5.605170185988092");
+    assert_eq!(report.remaining_life, report.economic_life);
+    assert_eq!(report.binding_constraint, BindingConstraint::EconomicLimit);
+}
+
+#[test]
+fn remaining_reserves_as_of_time() {
+    let forecast = Forecast::new(vec![
+        FlatParameters::from_incremental_duration(
+            ProductionRate::new(1000.),
+            AverageYearsTime { years: 1. },
+        )
+        .unwrap()
+        .into(),
+        ExponentialParameters::from_incremental_duration(
+            ProductionRate::new(1000.),
+            NominalDeclineRate::new(0.5),
+            AverageYearsTime { years: 10. },
+        )
+        .unwrap()
+        .into(),
+    ]);
+
+    let report = remaining_reserves(
+        &forecast,
+        AverageYearsTime { years: 2. },
+        ProductionRate::new(100.),
+    )
+    .unwrap();
+
+    insta::assert_snapshot!(report.cumulative_production, @"1786.938680574733");
+    insta::assert_snapshot!(report.remaining_reserves, @"1013.0613194252669");
+    insta::assert_snapshot!(report.estimated_ultimate_recovery, @"2800");
+    insta::assert_snapshot!(report.remaining_life.years, @"3.605170185988092");
+}
+
+#[test]
+fn remaining_reserves_after_economic_limit() {
+    let forecast = Forecast::new(vec![
+        FlatParameters::from_incremental_duration(
+            ProductionRate::new(1000.),
+            AverageYearsTime { years: 1. },
+        )
+        .unwrap()
+        .into(),
+        ExponentialParameters::from_incremental_duration(
+            ProductionRate::new(1000.),
+            NominalDeclineRate::new(0.5),
+            AverageYearsTime { years: 10. },
+        )
+        .unwrap()
+        .into(),
+    ]);
+
+    let report = remaining_reserves(
+        &forecast,
+        AverageYearsTime { years: 20. },
+        ProductionRate::new(100.),
+    )
+    .unwrap();
+
+    insta::assert_snapshot!(report.cumulative_production, @"2800");
+    insta::assert_snapshot!(report.remaining_reserves, @"0");
+    insta::assert_snapshot!(report.remaining_life.years, @"0");
+}
+
+#[test]
+fn invalid_as_of_time() {
+    let forecast = Forecast::new(vec![
+        FlatParameters::from_incremental_duration(
+            ProductionRate::new(1000.),
+            AverageYearsTime { years: 1. },
+        )
+        .unwrap()
+        .into(),
+        ExponentialParameters::from_incremental_duration(
+            ProductionRate::new(1000.),
+            NominalDeclineRate::new(0.5),
+            AverageYearsTime { years: 10. },
+        )
+        .unwrap()
+        .into(),
+    ]);
+
+    let result = remaining_reserves(
+        &forecast,
+        AverageYearsTime { years: -1. },
+        ProductionRate::new(100.),
+    );
+    insta::assert_snapshot!(result.unwrap_err(), @"as-of time is negative, but expected a positive number");
+}
+
+#[test]
+fn eur_includes_initial_cumulative_volume() {
+    let forecast = Forecast::new(vec![
+        FlatParameters::from_incremental_duration(
+            ProductionRate::new(1000.),
+            AverageYearsTime { years: 1. },
+        )
+        .unwrap()
+        .into(),
+        ExponentialParameters::from_incremental_duration(
+            ProductionRate::new(1000.),
+            NominalDeclineRate::new(0.5),
+            AverageYearsTime { years: 10. },
+        )
+        .unwrap()
+        .into(),
+    ])
+    .with_initial_cumulative_volume(5000.)
+    .unwrap();
+    let report = remaining_reserves(
+        &forecast,
+        AverageYearsTime { years: 2. },
+        ProductionRate::new(100.),
+    )
+    .unwrap();
+
+    insta::assert_snapshot!(report.cumulative_production, @"6786.938680574733");
+    insta::assert_snapshot!(report.remaining_reserves, @"1013.0613194252674");
+    insta::assert_snapshot!(report.estimated_ultimate_recovery, @"7800");
+}
+
+#[test]
+fn eur_with_maximum_life() {
+    let forecast = Forecast::new(vec![
+        FlatParameters::from_incremental_duration(
+            ProductionRate::new(1000.),
+            AverageYearsTime { years: 1. },
+        )
+        .unwrap()
+        .into(),
+        ExponentialParameters::from_incremental_duration(
+            ProductionRate::new(1000.),
+            NominalDeclineRate::new(0.5),
+            AverageYearsTime { years: 10. },
+        )
+        .unwrap()
+        .into(),
+    ]);
+
+    let report = estimated_ultimate_recovery_with_maximum_life(
+        &forecast,
+        ProductionRate::new(100.),
+        AverageYearsTime { years: 3. },
+    )
+    .unwrap();
+
+    insta::assert_snapshot!(report.estimated_ultimate_recovery, @"2264.2411176571154");
+    insta::assert_snapshot!(report.economic_life.years, @"3");
+    assert_eq!(report.binding_constraint, BindingConstraint::MaximumLife);
+
+    // The economic limit is reached before a longer maximum life.
+    let report = remaining_reserves_with_maximum_life(
+        &forecast,
+        AverageYearsTime { years: 2. },
+        ProductionRate::new(100.),
+        AverageYearsTime { years: 50. },
+    )
+    .unwrap();
+
+    insta::assert_snapshot!(report.remaining_reserves, @"1013.0613194252669");
+    assert_eq!(report.binding_constraint, BindingConstraint::EconomicLimit);
+}
+
+#[test]
+fn time_dependent_reserves_with_maximum_life() {
+    let forecast = Forecast::new(vec![
+        PowerLawExponentialParameters::from_incremental_duration(
+            ProductionRate::new(1000.),
+            0.3,
+            NominalDeclineRate::new(0.01),
+            AverageYearsTime { years: 30. },
+            0.5,
+        )
+        .unwrap()
+        .into(),
+    ]);
+
+    let report = remaining_reserves_with_maximum_life(
+        &forecast,
+        AverageYearsTime { years: 2. },
+        ProductionRate::new(1.),
+        AverageYearsTime { years: 10. },
+    )
+    .unwrap();
+
+    insta::assert_snapshot!(report.economic_life.years, @"10");
+    insta::assert_snapshot!(report.estimated_ultimate_recovery, @"5222.899365299905");
+    assert_eq!(report.binding_constraint, BindingConstraint::MaximumLife);
+}
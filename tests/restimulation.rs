@@ -0,0 +1,102 @@
+use decline_curve_analysis::{
+    AverageYearsTime, ExponentialParameters, FlatParameters, Forecast, NominalDeclineRate,
+    ProductionRate, RestimulatedForecast, Restimulation, RestimulationKind,
+};
+
+fn years(years: f64) -> AverageYearsTime {
+    AverageYearsTime { years }
+}
+
+fn flat(rate: f64, duration: f64) -> Forecast<AverageYearsTime> {
+    Forecast::new(vec![
+        FlatParameters::from_incremental_duration(ProductionRate::new(rate), years(duration))
+            .unwrap()
+            .into(),
+    ])
+}
+
+fn exponential(rate: f64, duration: f64) -> Forecast<AverageYearsTime> {
+    Forecast::new(vec![
+        ExponentialParameters::from_incremental_duration(
+            ProductionRate::new(rate),
+            NominalDeclineRate::new(0.3),
+            years(duration),
+        )
+        .unwrap()
+        .into(),
+    ])
+}
+
+#[test]
+fn refrac_replaces_base() {
+    let base = exponential(1000., 20.);
+    let refrac = exponential(800., 15.);
+    let forecast = RestimulatedForecast::new(
+        base.clone(),
+        vec![Restimulation::new(years(5.), refrac.clone(), RestimulationKind::Replace).unwrap()],
+    );
+
+    insta::assert_snapshot!(forecast.incremental_duration().years, @"20");
+    insta::assert_snapshot!(forecast.rate_at_time(years(4.9)).value(), @"229.92548518672385");
+    insta::assert_snapshot!(forecast.rate_at_time(years(5.)).value(), @"800");
+    insta::assert_snapshot!(forecast.rate_at_time(years(20.)).value(), @"0");
+    assert!(
+        (forecast.incremental_volume()
+            - base.incremental_volume_at_time(years(5.))
+            - refrac.incremental_volume())
+        .abs()
+            < 1e-9
+    );
+}
+
+#[test]
+fn workover_superposes_on_base() {
+    let base = flat(100., 10.);
+    let workover = flat(50., 2.);
+    let forecast = RestimulatedForecast::new(
+        base,
+        vec![Restimulation::new(years(3.), workover, RestimulationKind::Superpose).unwrap()],
+    );
+
+    insta::assert_snapshot!(forecast.incremental_duration().years, @"10");
+    insta::assert_snapshot!(forecast.rate_at_time(years(2.)).value(), @"100");
+    insta::assert_snapshot!(forecast.rate_at_time(years(4.)).value(), @"150");
+    insta::assert_snapshot!(forecast.rate_at_time(years(6.)).value(), @"100");
+    insta::assert_snapshot!(forecast.incremental_volume(), @"1100");
+    insta::assert_snapshot!(forecast.incremental_volume_between(years(4.), years(2.)), @"-250");
+    insta::assert_snapshot!(
+        format!("{:?}", forecast
+            .volumes_by_period(years(2.))
+            .unwrap()
+            .map(|period| period.volume)
+            .collect::<Vec<_>>()),
+        @"[200.0, 250.0, 250.0, 200.0, 200.0]"
+    );
+}
+
+#[test]
+fn replace_ends_superposed_production() {
+    // The refrac is given first, but is ordered after the workover.
+    let forecast = RestimulatedForecast::new(
+        flat(100., 10.),
+        vec![
+            Restimulation::new(years(6.), flat(300., 10.), RestimulationKind::Replace).unwrap(),
+            Restimulation::new(years(4.), flat(50., 5.), RestimulationKind::Superpose).unwrap(),
+        ],
+    );
+
+    insta::assert_snapshot!(forecast.restimulations()[0].start().years, @"4");
+    insta::assert_snapshot!(forecast.rate_at_time(years(5.)).value(), @"150");
+    insta::assert_snapshot!(forecast.rate_at_time(years(7.)).value(), @"300");
+    insta::assert_snapshot!(forecast.incremental_duration().years, @"16");
+    // 6 years of base, 2 years of workover, and 10 years of refrac.
+    insta::assert_snapshot!(forecast.incremental_volume(), @"3700");
+}
+
+#[test]
+fn invalid_restimulation_start() {
+    insta::assert_snapshot!(
+        Restimulation::new(years(-1.), flat(100., 1.), RestimulationKind::Replace).unwrap_err(),
+        @"restimulation start is negative, but expected a positive number"
+    );
+}
@@ -0,0 +1,139 @@
+use decline_curve_analysis::{
+    AverageDaysTime, FETKOVICH_STEMS, HyperbolicParameters, NominalDeclineRate, ProductionRate,
+    RtaSample, Segment, flowing_material_balance, match_fetkovich_type_curve, normalized_rates,
+};
+
+const INITIAL_PRESSURE: f64 = 5000.;
+
+fn days(days: f64) -> AverageDaysTime {
+    AverageDaysTime { days }
+}
+
+#[test]
+fn normalized_rate_points() {
+    let samples = [
+        (0., 100., 1000.),
+        (10., 90., 2000.),
+        (20., 0., 2000.),
+        (30., 80., 3000.),
+    ]
+    .map(|(time, rate, flowing_pressure)| RtaSample {
+        time: days(time),
+        rate: ProductionRate::new(rate),
+        flowing_pressure,
+    });
+
+    insta::assert_debug_snapshot!(
+        normalized_rates(&samples, INITIAL_PRESSURE)
+            .unwrap()
+            .iter()
+            .map(|point| (
+                point.time.days,
+                point.pressure_drop,
+                point.normalized_rate,
+                point.normalized_cumulative_volume,
+                point.material_balance_time.days,
+            ))
+            .collect::<Vec<_>>(),
+        @r"
+    [
+        (
+            0.0,
+            4000.0,
+            0.025,
+            0.0,
+            0.0,
+        ),
+        (
+            10.0,
+            3000.0,
+            0.03,
+            0.31666666666666665,
+            10.555555555555555,
+        ),
+        (
+            30.0,
+            2000.0,
+            0.04,
+            0.9,
+            22.5,
+        ),
+    ]
+    "
+    );
+
+    let mut invalid = samples;
+    invalid[1].flowing_pressure = INITIAL_PRESSURE;
+    insta::assert_snapshot!(normalized_rates(&invalid, INITIAL_PRESSURE).unwrap_err(), @"flowing pressure is 5000, but expected it to be less than the initial pressure of 5000");
+}
+
+#[test]
+fn flowing_material_balance_of_exponential_decline() {
+    // An exponential decline at a constant pressure drop is a straight line, which reaches zero at
+    // `q_i / (D * drop) = 1000 / (0.001 * 2000) = 500`.
+    let samples: Vec<_> = (0..=100)
+        .map(|step| {
+            let time = 10. * f64::from(step);
+            RtaSample {
+                time: days(time),
+                rate: ProductionRate::new(1000. * (-0.001 * time).exp()),
+                flowing_pressure: 3000.,
+            }
+        })
+        .collect();
+
+    let points = normalized_rates(&samples, INITIAL_PRESSURE).unwrap();
+    let balance = flowing_material_balance(&points, days(100.)).unwrap();
+
+    insta::assert_snapshot!(balance.normalized_ultimate_volume as f32, @"500.00418");
+    insta::assert_snapshot!(balance.intercept as f32, @"0.5");
+
+    insta::assert_snapshot!(flowing_material_balance(&points, days(1000.)).unwrap_err(), @"boundary-dominated point count is 1, but expected at least 2");
+}
+
+#[test]
+fn fetkovich_type_curve_match() {
+    let decline = HyperbolicParameters::from_incremental_duration(
+        ProductionRate::new(1000.),
+        NominalDeclineRate::new(0.01),
+        days(720.),
+        0.5,
+    )
+    .unwrap();
+
+    // The flowing pressure falls over time, so the rates decline faster than the decline at the
+    // final pressure drop.
+    let samples: Vec<_> = (0..=24)
+        .map(|month| {
+            let time = days(30. * f64::from(month));
+            let flowing_pressure = 2000. - 40. * f64::from(month);
+            let pressure_drop = INITIAL_PRESSURE - flowing_pressure;
+            RtaSample {
+                time,
+                rate: ProductionRate::new(
+                    decline.rate_at_time(time).value() * pressure_drop / 3960.,
+                ),
+                flowing_pressure,
+            }
+        })
+        .collect();
+
+    let matched = match_fetkovich_type_curve(&samples, INITIAL_PRESSURE, &FETKOVICH_STEMS).unwrap();
+    assert_eq!(matched.exponent, 0.5);
+    assert_eq!(matched.reference_pressure_drop, 3960.);
+    assert!(matched.root_mean_squared_log_error < 1e-6);
+    let Segment::Hyperbolic(parameters) = matched.segment else {
+        panic!("expected a hyperbolic segment");
+    };
+    insta::assert_snapshot!(parameters.initial_rate().value() as f32, @"1000");
+    insta::assert_snapshot!(parameters.initial_decline_rate().value() as f32, @"0.01");
+
+    let exponential = match_fetkovich_type_curve(&samples, INITIAL_PRESSURE, &[0.]).unwrap();
+    assert!(matches!(exponential.segment, Segment::Exponential(_)));
+    assert!(exponential.root_mean_squared_log_error > matched.root_mean_squared_log_error);
+
+    insta::assert_snapshot!(
+        match_fetkovich_type_curve(&samples, INITIAL_PRESSURE, &[2.5]).unwrap_err(),
+        @"stem exponent is 2.5, but expected it to be between 0 and 2"
+    );
+}
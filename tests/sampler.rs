@@ -0,0 +1,115 @@
+use decline_curve_analysis::{
+    AverageDaysTime, ExponentialParameters, FixedForecast, FlatParameters, Forecast,
+    HarmonicParameters, HyperbolicParameters, NominalDeclineRate, ProductionRate, Segment,
+};
+
+fn days(days: f64) -> AverageDaysTime {
+    AverageDaysTime { days }
+}
+
+fn segments() -> [Segment<AverageDaysTime>; 4] {
+    [
+        FlatParameters::from_incremental_duration(ProductionRate::new(1000.), days(45.5))
+            .unwrap()
+            .into(),
+        HyperbolicParameters::from_incremental_duration(
+            ProductionRate::new(1000.),
+            NominalDeclineRate::new(0.004),
+            days(1000.),
+            1.2,
+        )
+        .unwrap()
+        .into(),
+        HarmonicParameters::from_incremental_duration(
+            ProductionRate::new(300.),
+            NominalDeclineRate::new(0.0005),
+            days(500.25),
+        )
+        .unwrap()
+        .into(),
+        ExponentialParameters::from_incremental_duration(
+            ProductionRate::new(250.),
+            NominalDeclineRate::new(0.0002),
+            days(9000.),
+        )
+        .unwrap()
+        .into(),
+    ]
+}
+
+#[test]
+#[cfg_attr(feature = "fast-math", ignore = "exact values differ with fast-math")]
+fn daily_samples_match_forecast() {
+    let forecast = Forecast::new(segments().to_vec());
+    let samples = forecast.sampler(days(1.)).unwrap();
+
+    let mut count = 0;
+    let mut last = None;
+    for sample in samples {
+        let rate = forecast.rate_at_time(sample.time).value();
+        let volume = forecast.incremental_volume_at_time(sample.time);
+        assert!(
+            (sample.rate.value() - rate).abs() <= 1e-9 * rate,
+            "rate at {:?}",
+            sample.time
+        );
+        assert!(
+            (sample.incremental_volume - volume).abs() <= 1e-9 * volume.max(1.),
+            "volume at {:?}",
+            sample.time
+        );
+
+        count += 1;
+        last = Some(sample);
+    }
+
+    // One sample at each whole day, plus the end of the forecast at 10545.75 days.
+    assert_eq!(count, 10547);
+    let last = last.unwrap();
+    assert_eq!(last.time, forecast.incremental_duration());
+    assert_eq!(last.rate, forecast.final_rate());
+    assert!((last.incremental_volume - forecast.incremental_volume()).abs() < 1e-6);
+}
+
+#[test]
+fn segment_and_fixed_forecast_samplers() {
+    let [_, hyperbolic, ..] = segments();
+    let samples: Vec<_> = hyperbolic
+        .sampler(days(400.))
+        .unwrap()
+        .map(|sample| (sample.time.days, sample.rate.value() as f32))
+        .collect();
+    insta::assert_debug_snapshot!(samples, @r"
+    [
+        (
+            0.0,
+            1000.0,
+        ),
+        (
+            400.0,
+            409.4312,
+        ),
+        (
+            800.0,
+            268.71725,
+        ),
+        (
+            1000.0,
+            231.10535,
+        ),
+    ]
+    ");
+
+    let fixed = FixedForecast::new(segments());
+    let forecast = Forecast::from(fixed.clone());
+    assert!(
+        fixed
+            .sampler(days(30.))
+            .unwrap()
+            .eq(forecast.sampler(days(30.)).unwrap())
+    );
+
+    let empty = Forecast::<AverageDaysTime>::new(vec![]);
+    assert_eq!(empty.sampler(days(1.)).unwrap().count(), 0);
+    insta::assert_snapshot!(forecast.sampler(days(0.)).unwrap_err(), @"sampling step is negative or zero, but expected a positive number");
+}
@@ -0,0 +1,95 @@
+use decline_curve_analysis::{
+    AverageYearsTime, CategoryTruncation, ExponentialParameters, FlatParameters, Forecast,
+    NominalDeclineRate, ProductionRate, ReserveCategory, Scenario, TimeGrid,
+};
+
+fn flat(rate: f64, years: f64) -> Forecast<AverageYearsTime> {
+    Forecast::new(vec![
+        FlatParameters::from_incremental_duration(
+            ProductionRate::new(rate),
+            AverageYearsTime { years },
+        )
+        .unwrap()
+        .into(),
+    ])
+}
+
+fn scenario() -> Scenario<AverageYearsTime> {
+    Scenario::new(vec![
+        (
+            ReserveCategory::ProvedDevelopedProducing,
+            Forecast::new(vec![
+                ExponentialParameters::from_incremental_duration(
+                    ProductionRate::new(1000.),
+                    NominalDeclineRate::new(0.5),
+                    AverageYearsTime { years: 10. },
+                )
+                .unwrap()
+                .into(),
+            ]),
+        ),
+        (ReserveCategory::ProvedUndeveloped, flat(200., 10.)),
+        (ReserveCategory::Probable, flat(100., 10.)),
+    ])
+    .unwrap()
+}
+
+#[test]
+fn sum_categories() {
+    let scenario = scenario();
+
+    insta::assert_snapshot!(scenario.cumulative_volume(&[ReserveCategory::ProvedDevelopedProducing]), @"1986.524106001829");
+    insta::assert_snapshot!(scenario.cumulative_volume(&ReserveCategory::PROVED), @"3986.524106001829");
+    insta::assert_snapshot!(scenario.cumulative_volume(&ReserveCategory::PROVED_PLUS_PROBABLE), @"4986.524106001829");
+    // Possible reserves aren't forecast, so 3P is the same as 2P.
+    insta::assert_snapshot!(scenario.cumulative_volume(&ReserveCategory::PROVED_PLUS_PROBABLE_PLUS_POSSIBLE), @"4986.524106001829");
+
+    assert!(scenario.forecast(ReserveCategory::Possible).is_none());
+
+    let grid = TimeGrid::uniform(AverageYearsTime { years: 5. }, 2).unwrap();
+    let profile = scenario.aggregate(&ReserveCategory::PROVED, &grid).unwrap();
+    insta::assert_snapshot!(format!("{:?}", profile.rates()), @"[1200.0, 282.0849986238988, 0.0]");
+    let total: f64 = profile.volumes().iter().sum();
+    assert!((total - scenario.cumulative_volume(&ReserveCategory::PROVED)).abs() < 1e-9);
+}
+
+#[test]
+fn category_truncation() {
+    let truncated = scenario()
+        .truncate_at_limits(|category| match category {
+            ReserveCategory::ProvedDevelopedProducing => Some(CategoryTruncation {
+                economic_limit_rate: ProductionRate::new(100.),
+                maximum_life: None,
+            }),
+            // Undeveloped and unproved reserves are limited to five years.
+            _ => Some(CategoryTruncation {
+                economic_limit_rate: ProductionRate::new(50.),
+                maximum_life: Some(AverageYearsTime { years: 5. }),
+            }),
+        })
+        .unwrap();
+
+    let duration = |category| {
+        truncated
+            .forecast(category)
+            .unwrap()
+            .incremental_duration()
+            .years
+    };
+    insta::assert_snapshot!(duration(ReserveCategory::ProvedDevelopedProducing), @"4.605170185988092");
+    insta::assert_snapshot!(duration(ReserveCategory::ProvedUndeveloped), @"5");
+    insta::assert_snapshot!(truncated.cumulative_volume(&ReserveCategory::PROVED_PLUS_PROBABLE), @"3300");
+
+    // Categories without a rule are unchanged.
+    let unchanged = scenario().truncate_at_limits(|_| None).unwrap();
+    assert_eq!(unchanged, scenario());
+}
+
+#[test]
+fn duplicate_category() {
+    let result = Scenario::new(vec![
+        (ReserveCategory::Probable, flat(100., 1.)),
+        (ReserveCategory::Probable, flat(200., 1.)),
+    ]);
+    insta::assert_snapshot!(result.unwrap_err(), @"Probable is forecast more than once");
+}
@@ -0,0 +1,128 @@
+use decline_curve_analysis::{
+    AverageDaysTime, CalendarDate, DowntimeInterval, DowntimeSchedule, ExponentialParameters,
+    Forecast, NominalDeclineRate, ProductionRate, ReportFrequency, ScheduleBuilder, ScheduleRow,
+};
+
+fn days(days: f64) -> AverageDaysTime {
+    AverageDaysTime { days }
+}
+
+fn forecast() -> Forecast<AverageDaysTime> {
+    Forecast::new(vec![
+        ExponentialParameters::from_incremental_duration(
+            ProductionRate::new(100.),
+            NominalDeclineRate::new(0.001),
+            days(400.5),
+        )
+        .unwrap()
+        .into(),
+    ])
+}
+
+fn start_date() -> CalendarDate {
+    CalendarDate::new(2024, 1, 20).unwrap()
+}
+
+fn describe(rows: &[ScheduleRow<AverageDaysTime>]) -> Vec<String> {
+    rows.iter()
+        .map(|row| {
+            format!(
+                "{}-{:02}-{:02}: {}..{} d, {:.2} days on, {:.1} volume, {:.2}/d",
+                row.date.year(),
+                row.date.month(),
+                row.date.day(),
+                row.start.days,
+                row.end.days,
+                row.producing_days,
+                row.volume,
+                row.rate.value(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn partial_first_and_last_periods() {
+    let forecast = forecast();
+    let rows = ScheduleBuilder::new(&forecast, start_date())
+        .build()
+        .unwrap();
+
+    assert_eq!(rows.len(), 14);
+    let total = rows.iter().map(|row| row.volume).sum::<f64>();
+    assert!((total - forecast.incremental_volume()).abs() < 1e-9);
+
+    // January starts on the 20th, and the last month ends half way through the 23rd of February.
+    let rows = describe(&rows);
+    insta::assert_debug_snapshot!((&rows[..2], &rows[12..]), @r#"
+    (
+        [
+            "2024-01-20: 0..12 d, 12.00 days on, 1192.8 volume, 99.40/d",
+            "2024-02-01: 12..41 d, 29.00 days on, 2824.3 volume, 97.39/d",
+        ],
+        [
+            "2025-01-01: 347..378 d, 31.00 days on, 2157.5 volume, 69.60/d",
+            "2025-02-01: 378..400.5 d, 22.50 days on, 1524.6 volume, 67.76/d",
+        ],
+    )
+    "#);
+}
+
+#[test]
+fn daily_and_annual_frequencies() {
+    let forecast = forecast();
+
+    let daily = ScheduleBuilder::new(&forecast, start_date())
+        .frequency(ReportFrequency::Daily)
+        .build()
+        .unwrap();
+    assert_eq!(daily.len(), 401);
+    assert_eq!(daily[31].date, CalendarDate::new(2024, 2, 20).unwrap());
+    assert_eq!(daily.last().unwrap().producing_days, 0.5);
+
+    let annual = ScheduleBuilder::new(&forecast, start_date())
+        .frequency(ReportFrequency::Annual)
+        .build()
+        .unwrap();
+    insta::assert_debug_snapshot!(describe(&annual), @r#"
+    [
+        "2024-01-20: 0..347 d, 347.00 days on, 29319.5 volume, 84.49/d",
+        "2025-01-01: 347..400.5 d, 53.50 days on, 3682.0 volume, 68.82/d",
+    ]
+    "#);
+}
+
+#[test]
+fn downtime_and_end_date() {
+    let forecast = forecast();
+    // A 90% base uptime with a five day shut-in at the start of the second month.
+    let downtime = DowntimeSchedule::new(
+        0.9,
+        vec![DowntimeInterval::new(days(12.), days(17.), 0.).unwrap()],
+    )
+    .unwrap();
+
+    let rows = ScheduleBuilder::new(&forecast, start_date())
+        .downtime(downtime.clone())
+        .end_date(CalendarDate::new(2024, 4, 1).unwrap())
+        .build()
+        .unwrap();
+    insta::assert_debug_snapshot!(describe(&rows), @r#"
+    [
+        "2024-01-20: 0..12 d, 10.80 days on, 1073.5 volume, 89.46/d",
+        "2024-02-01: 12..41 d, 21.60 days on, 2098.3 volume, 72.36/d",
+        "2024-03-01: 41..72 d, 27.90 days on, 2636.8 volume, 85.06/d",
+    ]
+    "#);
+
+    let total = rows.iter().map(|row| row.volume).sum::<f64>();
+    let expected =
+        forecast.incremental_volume_between_with_downtime(days(0.), days(72.), &downtime);
+    assert!((total - expected).abs() < 1e-9);
+
+    let error = ScheduleBuilder::new(&forecast, start_date())
+        .end_date(start_date())
+        .build()
+        .unwrap_err();
+    insta::assert_snapshot!(error, @"schedule start and end dates are not strictly increasing");
+}
@@ -0,0 +1,64 @@
+use decline_curve_analysis::{
+    AverageDaysTime, AverageYearsTime, DelayParameters, ExponentialParameters, FlatParameters,
+    HyperbolicParameters, NominalDeclineRate, ProductionRate, Segment,
+};
+
+#[test]
+fn segment_delegates_to_parameters() {
+    let parameters = HyperbolicParameters::from_incremental_duration(
+        ProductionRate::<AverageDaysTime>::new(50.),
+        NominalDeclineRate::<AverageYearsTime>::new(0.5).into(),
+        AverageDaysTime { days: 3650. },
+        0.9,
+    )
+    .unwrap();
+    let segment = Segment::from(parameters.clone());
+
+    let time = AverageDaysTime { days: 1000. };
+    assert_eq!(
+        segment.incremental_duration(),
+        parameters.incremental_duration()
+    );
+    assert_eq!(
+        segment.incremental_volume(),
+        parameters.incremental_volume()
+    );
+    assert_eq!(
+        segment.incremental_volume_at_time(time),
+        parameters.incremental_volume_at_time(time)
+    );
+    assert_eq!(segment.rate_at_time(time), parameters.rate_at_time(time));
+    assert_eq!(segment.final_rate(), parameters.final_rate());
+}
+
+#[test]
+fn segment_variants() {
+    let duration = AverageDaysTime { days: 100. };
+    let segments: Vec<Segment<AverageDaysTime>> = vec![
+        DelayParameters::from_incremental_duration(duration)
+            .unwrap()
+            .into(),
+        FlatParameters::from_incremental_duration(ProductionRate::new(10.), duration)
+            .unwrap()
+            .into(),
+        ExponentialParameters::from_incremental_duration(
+            ProductionRate::new(10.),
+            NominalDeclineRate::new(0.01),
+            duration,
+        )
+        .unwrap()
+        .into(),
+    ];
+
+    let volumes: Vec<f64> = segments
+        .iter()
+        .map(|segment| segment.incremental_volume())
+        .collect();
+    insta::assert_debug_snapshot!(volumes, @"
+    [
+        0.0,
+        1000.0,
+        632.1205588285577,
+    ]
+    ");
+}
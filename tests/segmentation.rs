@@ -0,0 +1,160 @@
+use decline_curve_analysis::{
+    AverageDaysTime, DeclineTimeUnit, ExponentialParameters, HyperbolicParameters,
+    NominalDeclineRate, ProductionRate, SegmentationOptions, detect_changepoints, fit_segmented,
+};
+
+/// Samples monthly for four years, with a refrac after two years that restores the rate and
+/// decline of a new well.
+fn refrac_samples() -> Vec<(AverageDaysTime, ProductionRate<AverageDaysTime>)> {
+    let decline = HyperbolicParameters::from_incremental_duration(
+        ProductionRate::new(800.),
+        NominalDeclineRate::new(0.004),
+        AverageDaysTime { days: 3000. },
+        0.8,
+    )
+    .unwrap();
+    let refrac_month = 24;
+
+    (0..48)
+        .map(|month| {
+            let time = AverageDaysTime {
+                days: month as f64 * 30.4375,
+            };
+            let local_time = if month < refrac_month {
+                time
+            } else {
+                AverageDaysTime {
+                    days: (month - refrac_month) as f64 * 30.4375,
+                }
+            };
+            // Add a repeating pattern of noise so that the regimes aren't fitted exactly.
+            let noise = [1.02, 0.99, 0.97, 1.01, 1.03, 0.98][month % 6];
+            (
+                time,
+                ProductionRate::new(decline.rate_at_time(local_time).value() * noise),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn detect_changepoints_finds_refrac() {
+    let changepoints =
+        detect_changepoints(&refrac_samples(), &SegmentationOptions::default()).unwrap();
+
+    insta::assert_debug_snapshot!(changepoints, @r"
+    [
+        24,
+    ]
+    ");
+}
+
+#[test]
+fn detect_changepoints_without_change() {
+    let decline = ExponentialParameters::from_incremental_duration(
+        ProductionRate::new(500.),
+        NominalDeclineRate::new(0.002),
+        AverageDaysTime { days: 2000. },
+    )
+    .unwrap();
+    let samples: Vec<_> = (0..48)
+        .map(|month| {
+            let time = AverageDaysTime {
+                days: month as f64 * 30.4375,
+            };
+            let noise = [1.02, 0.99, 0.97, 1.01, 1.03, 0.98][month % 6];
+            (
+                time,
+                ProductionRate::new(decline.rate_at_time(time).value() * noise),
+            )
+        })
+        .collect();
+
+    let changepoints = detect_changepoints(&samples, &SegmentationOptions::default()).unwrap();
+
+    insta::assert_debug_snapshot!(changepoints, @"[]");
+}
+
+#[test]
+fn fit_segmented_refrac() {
+    let fit = fit_segmented(&refrac_samples(), &SegmentationOptions::default()).unwrap();
+
+    let regimes: Vec<_> = fit
+        .regimes
+        .iter()
+        .map(|regime| {
+            (
+                regime.start_index,
+                regime.end_index,
+                regime.start.value(),
+                regime.fit.ranking[0].model,
+            )
+        })
+        .collect();
+    insta::assert_debug_snapshot!(regimes, @r"
+    [
+        (
+            0,
+            24,
+            0.0,
+            Hyperbolic,
+        ),
+        (
+            24,
+            48,
+            730.5,
+            Hyperbolic,
+        ),
+    ]
+    ");
+
+    let segments = fit.forecast.segments();
+    insta::assert_snapshot!(segments.len(), @"2");
+    insta::assert_snapshot!(segments[0].incremental_duration().days, @"730.5");
+    insta::assert_snapshot!(segments[1].rate_at_time(AverageDaysTime { days: 0. }).value() as f32, @"805.3819");
+}
+
+#[test]
+fn segmentation_validation() {
+    let samples = refrac_samples();
+
+    let options = SegmentationOptions {
+        min_regime_samples: 3,
+        ..SegmentationOptions::default()
+    };
+    insta::assert_debug_snapshot!(detect_changepoints(&samples, &options), @r#"
+    Err(
+        TooFewInputs {
+            field: "minimum regime samples",
+            count: 3,
+            minimum: 4,
+        },
+    )
+    "#);
+
+    let options = SegmentationOptions {
+        penalty: Some(-1.),
+        ..SegmentationOptions::default()
+    };
+    insta::assert_debug_snapshot!(detect_changepoints(&samples, &options), @r#"
+    Err(
+        NegativeInput {
+            field: "penalty",
+            value: -1.0,
+        },
+    )
+    "#);
+
+    let mut unordered = samples.clone();
+    unordered.swap(3, 4);
+    insta::assert_debug_snapshot!(
+        detect_changepoints(&unordered, &SegmentationOptions::default()),
+        @r#"
+    Err(
+        NotStrictlyIncreasing {
+            field: "sample times",
+        },
+    )
+    "#
+    );
+}
@@ -0,0 +1,156 @@
+use decline_curve_analysis::{
+    AverageYearsTime, HyperbolicParameters, NominalDeclineRate, ParameterRange, ProductionRate,
+    SensitivityMetric, SensitivityParameter, sensitivity, spider, tornado,
+};
+
+fn base_case() -> HyperbolicParameters<AverageYearsTime> {
+    HyperbolicParameters::from_final_rate(
+        ProductionRate::new(1000.),
+        NominalDeclineRate::new(0.8),
+        ProductionRate::new(50.),
+        1.2,
+    )
+    .unwrap()
+}
+
+#[test]
+#[cfg_attr(feature = "fast-math", ignore = "exact values differ with fast-math")]
+fn eur_sensitivity() {
+    let base_case = base_case();
+    let result = sensitivity(
+        &base_case,
+        SensitivityParameter::Exponent,
+        &[0.5, 0.9, 1.2, 1.5],
+        SensitivityMetric::EstimatedUltimateRecovery,
+    )
+    .unwrap();
+
+    insta::assert_snapshot!(result.base_value, @"1.2");
+    assert_eq!(result.base_response, base_case.incremental_volume());
+    // The base value reproduces the base response.
+    assert!((result.points[2].response - result.base_response).abs() < 1e-9);
+    // A higher exponent flattens the decline, so the EUR increases.
+    assert!(
+        result
+            .points
+            .windows(2)
+            .all(|pair| pair[0].response < pair[1].response)
+    );
+
+    let (low, high) = result.response_range();
+    assert_eq!(low, result.points[0].response);
+    assert_eq!(high, result.points[3].response);
+    assert_eq!(result.swing(), high - low);
+}
+
+#[test]
+fn duration_sensitivity() {
+    let result = sensitivity(
+        &base_case(),
+        SensitivityParameter::FinalRate,
+        &[25., 100.],
+        SensitivityMetric::Duration,
+    )
+    .unwrap();
+
+    insta::assert_snapshot!(result.base_response, @"36.88675422971");
+    insta::assert_snapshot!(result.points[0].response, @"86.09496271593943");
+    insta::assert_snapshot!(result.points[1].response, @"15.46763742146993");
+}
+
+#[test]
+fn invalid_sensitivity_range() {
+    let result = sensitivity(
+        &base_case(),
+        SensitivityParameter::FinalRate,
+        &[2000.],
+        SensitivityMetric::Duration,
+    );
+    insta::assert_snapshot!(result.unwrap_err(), @"decline rate has wrong sign");
+}
+
+#[test]
+#[cfg_attr(feature = "fast-math", ignore = "exact values differ with fast-math")]
+fn tornado_chart() {
+    let chart = tornado(
+        &base_case(),
+        &[
+            ParameterRange {
+                parameter: SensitivityParameter::InitialDeclineRate,
+                low: 0.6,
+                high: 1.,
+            },
+            ParameterRange {
+                parameter: SensitivityParameter::InitialRate,
+                low: 800.,
+                high: 1200.,
+            },
+            ParameterRange {
+                parameter: SensitivityParameter::FinalRate,
+                low: 25.,
+                high: 100.,
+            },
+        ],
+        SensitivityMetric::EstimatedUltimateRecovery,
+    )
+    .unwrap();
+
+    insta::assert_snapshot!(chart.base_response, @"5128.526268913");
+    let bars: Vec<_> = chart
+        .bars
+        .iter()
+        .map(|bar| (bar.range.parameter, bar.low_response, bar.high_response))
+        .collect();
+    insta::assert_debug_snapshot!(bars, @r"
+    [
+        (
+            FinalRate,
+            6820.494407390913,
+            3655.582452881959,
+        ),
+        (
+            InitialRate,
+            3705.5056329612407,
+            6661.312669423529,
+        ),
+        (
+            InitialDeclineRate,
+            6838.035025217336,
+            4102.821015130401,
+        ),
+    ]
+    ");
+    assert!(
+        chart
+            .bars
+            .windows(2)
+            .all(|pair| pair[0].swing() >= pair[1].swing())
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "fast-math", ignore = "exact values differ with fast-math")]
+fn spider_chart() {
+    let chart = spider(
+        &base_case(),
+        &SensitivityParameter::ALL,
+        &[-0.2, 0., 0.2],
+        SensitivityMetric::Duration,
+    )
+    .unwrap();
+
+    let order: Vec<_> = chart.lines.iter().map(|line| line.parameter).collect();
+    insta::assert_debug_snapshot!(order, @r"
+    [
+        Exponent,
+        FinalRate,
+        InitialRate,
+        InitialDeclineRate,
+    ]
+    ");
+    for line in &chart.lines {
+        // No change to the parameter reproduces the base case.
+        assert!(line.points[1].relative_response.abs() < 1e-12);
+    }
+    insta::assert_snapshot!(chart.lines[0].points[0].relative_response, @"-0.4090359350126128");
+}
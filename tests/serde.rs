@@ -0,0 +1,186 @@
+#![cfg(feature = "serde")]
+
+use decline_curve_analysis::{
+    AverageDaysTime, AverageYearsTime, CalendarDate, DeclinationTable, DelayParameters,
+    ExponentialParameters, FlatParameters, Forecast, HarmonicParameters, HyperbolicParameters,
+    LabeledForecast, LinearParameters, LogisticGrowthParameters, Mcf, Metadata, NominalDeclineRate,
+    ParameterSource, Phase, PowerLawExponentialParameters, ProductionRate, RatioForecast,
+    RatioSegment, RatioTrend, SecantEffectiveDeclineRate, Segment,
+};
+
+fn round_trip<T>(value: &T) -> String
+where
+    T: serde::Serialize + serde::de::DeserializeOwned + PartialEq + std::fmt::Debug,
+{
+    let json = serde_json::to_string(value).unwrap();
+    let deserialized: T = serde_json::from_str(&json).unwrap();
+    assert_eq!(&deserialized, value);
+    json
+}
+
+#[test]
+fn rates_and_time_units() {
+    insta::assert_snapshot!(round_trip(&ProductionRate::<AverageDaysTime>::new(100.)), @"100.0");
+    insta::assert_snapshot!(round_trip(&ProductionRate::<AverageDaysTime, Mcf>::new(250.)), @"250.0");
+    insta::assert_snapshot!(round_trip(&NominalDeclineRate::<AverageYearsTime>::new(0.5)), @"0.5");
+    insta::assert_snapshot!(round_trip(&SecantEffectiveDeclineRate::<AverageYearsTime>::new(0.3)), @"0.3");
+    insta::assert_snapshot!(round_trip(&AverageYearsTime { years: 2. }), @"2.0");
+    insta::assert_snapshot!(round_trip(&CalendarDate::new(2024, 2, 29).unwrap()), @r#"{"year":2024,"month":2,"day":29}"#);
+}
+
+#[test]
+fn segments_round_trip() {
+    let duration = AverageDaysTime { days: 100. };
+    let rate = ProductionRate::new(50.);
+    let decline_rate = NominalDeclineRate::new(0.001);
+    let segments: Vec<Segment<AverageDaysTime>> = vec![
+        DelayParameters::from_incremental_duration(duration)
+            .unwrap()
+            .into(),
+        ExponentialParameters::from_incremental_duration(rate, decline_rate, duration)
+            .unwrap()
+            .into(),
+        FlatParameters::from_incremental_duration(rate, duration)
+            .unwrap()
+            .into(),
+        HarmonicParameters::from_incremental_duration(rate, decline_rate, duration)
+            .unwrap()
+            .into(),
+        HyperbolicParameters::from_incremental_duration(rate, decline_rate, duration, 0.5)
+            .unwrap()
+            .into(),
+        LinearParameters::from_incremental_duration(rate, decline_rate, duration)
+            .unwrap()
+            .into(),
+        LogisticGrowthParameters::from_incremental_duration(10_000., 5., duration, 0.9)
+            .unwrap()
+            .into(),
+        PowerLawExponentialParameters::from_incremental_duration(
+            rate,
+            0.01,
+            decline_rate,
+            duration,
+            0.5,
+        )
+        .unwrap()
+        .into(),
+    ];
+
+    for segment in &segments {
+        round_trip(segment);
+    }
+
+    insta::assert_snapshot!(round_trip(&segments[4]), @r#"{"type":"hyperbolic","initial_rate":50.0,"initial_decline_rate":0.001,"incremental_duration":100.0,"exponent":0.5}"#);
+}
+
+#[test]
+fn forecast_round_trip() {
+    let forecast = Forecast::new(vec![
+        FlatParameters::from_incremental_duration(
+            ProductionRate::<AverageYearsTime>::new(1000.),
+            AverageYearsTime { years: 1. },
+        )
+        .unwrap()
+        .into(),
+        ExponentialParameters::from_incremental_duration(
+            ProductionRate::new(1000.),
+            NominalDeclineRate::new(0.1),
+            AverageYearsTime { years: 10. },
+        )
+        .unwrap()
+        .into(),
+    ]);
+
+    insta::assert_snapshot!(round_trip(&forecast), @r#"{"segments":[{"type":"flat","rate":1000.0,"incremental_duration":1.0},{"type":"exponential","initial_rate":1000.0,"decline_rate":0.1,"incremental_duration":10.0}]}"#);
+
+    let forecast = forecast.with_initial_cumulative_volume(5000.).unwrap();
+    insta::assert_snapshot!(round_trip(&forecast), @r#"{"segments":[{"type":"flat","rate":1000.0,"incremental_duration":1.0},{"type":"exponential","initial_rate":1000.0,"decline_rate":0.1,"incremental_duration":10.0}],"initial_cumulative_volume":5000.0}"#);
+}
+
+#[test]
+fn invalid_parameters_are_rejected() {
+    let result = serde_json::from_str::<Segment<AverageDaysTime>>(
+        r#"{"type":"exponential","initial_rate":-50.0,"decline_rate":0.001,"incremental_duration":100.0}"#,
+    );
+    insta::assert_snapshot!(result.unwrap_err(), @"initial rate is negative or zero, but expected a positive number");
+
+    let result = serde_json::from_str::<FlatParameters<AverageDaysTime>>(
+        r#"{"rate":50.0,"incremental_duration":100.0,"exponent":0.5}"#,
+    );
+    insta::assert_snapshot!(result.unwrap_err(), @"unknown field `exponent`, expected `rate` or `incremental_duration` at line 1 column 52");
+
+    let result = serde_json::from_str::<CalendarDate>(r#"{"year":2023,"month":2,"day":29}"#);
+    insta::assert_snapshot!(result.unwrap_err(), @"day is 29, but expected it to be between 1 and 28");
+
+    let result = serde_json::from_str::<Forecast<AverageDaysTime>>(
+        r#"{"segments":[],"initial_cumulative_volume":-1.0}"#,
+    );
+    insta::assert_snapshot!(result.unwrap_err(), @"initial cumulative volume is negative, but expected a positive number");
+}
+
+#[test]
+fn ratio_forecast_round_trip() {
+    let ratios = RatioForecast::new(vec![
+        RatioSegment::from_incremental_duration(
+            1.5,
+            RatioTrend::Linear { slope: 0.01 },
+            AverageDaysTime { days: 30. },
+        )
+        .unwrap(),
+        RatioSegment::from_incremental_duration(
+            1.8,
+            RatioTrend::Constant,
+            AverageDaysTime { days: 60. },
+        )
+        .unwrap(),
+    ]);
+
+    insta::assert_snapshot!(round_trip(&ratios), @r#"{"segments":[{"initial_ratio":1.5,"trend":{"type":"linear","slope":0.01},"incremental_duration":30.0},{"initial_ratio":1.8,"trend":{"type":"constant"},"incremental_duration":60.0}]}"#);
+
+    let negative = serde_json::from_str::<RatioSegment<AverageDaysTime>>(
+        r#"{"initial_ratio":1.0,"trend":{"type":"linear","slope":-1.0},"incremental_duration":10.0}"#,
+    );
+    insta::assert_snapshot!(negative.unwrap_err(), @"ratio becomes negative before the end of the segment");
+}
+
+#[test]
+fn labeled_forecast_round_trip() {
+    let forecast = Forecast::new(vec![
+        FlatParameters::from_incremental_duration(
+            ProductionRate::<AverageYearsTime>::new(100.),
+            AverageYearsTime { years: 1. },
+        )
+        .unwrap()
+        .into(),
+    ]);
+    let labeled = LabeledForecast::new(
+        forecast,
+        Metadata {
+            name: Some("Smith 1H".into()),
+            phase: Some(Phase::Gas),
+            analyst: Some("jdoe".into()),
+            timestamp: Some("2024-03-01T12:00:00Z".into()),
+            ..Metadata::default()
+        },
+        vec![Metadata {
+            source: Some(ParameterSource::Manual),
+            ..Metadata::default()
+        }],
+    )
+    .unwrap();
+
+    insta::assert_snapshot!(round_trip(&labeled), @r#"{"forecast":{"segments":[{"type":"flat","rate":100.0,"incremental_duration":1.0}]},"metadata":{"name":"Smith 1H","phase":"gas","analyst":"jdoe","timestamp":"2024-03-01T12:00:00Z"},"segment_metadata":[{"source":"manual"}]}"#);
+
+    let result = serde_json::from_str::<LabeledForecast<AverageYearsTime>>(
+        r#"{"forecast":{"segments":[]},"metadata":{},"segment_metadata":[{}]}"#,
+    );
+    insta::assert_snapshot!(result.unwrap_err(), @"segment metadata has a length of 1, but expected 0");
+}
+
+#[test]
+fn declination_table_round_trip() {
+    let table =
+        DeclinationTable::new([NominalDeclineRate::<AverageYearsTime>::new(0.5)], &[0.5]).unwrap();
+
+    insta::assert_snapshot!(round_trip(&table), @r#"{"exponents":[0.5],"rows":[{"nominal":0.5,"tangent_effective":0.3934693402873666,"secant_effective":[0.36]}]}"#);
+}
@@ -0,0 +1,170 @@
+use decline_curve_analysis::{
+    AverageYearsTime, DelayParameters, ExponentialParameters, FlatParameters, HarmonicParameters,
+    HyperbolicParameters, LinearParameters, LogisticGrowthParameters, NominalDeclineRate,
+    PowerLawExponentialParameters, ProductionRate, Segment,
+};
+
+fn splittable_segments() -> Vec<Segment<AverageYearsTime>> {
+    let rate = ProductionRate::new(1000.);
+    let decline_rate = NominalDeclineRate::new(0.3);
+    let duration = AverageYearsTime { years: 10. };
+
+    vec![
+        DelayParameters::from_incremental_duration(duration)
+            .unwrap()
+            .into(),
+        ExponentialParameters::from_incremental_duration(rate, decline_rate, duration)
+            .unwrap()
+            .into(),
+        FlatParameters::from_incremental_duration(rate, duration)
+            .unwrap()
+            .into(),
+        HarmonicParameters::from_incremental_duration(rate, decline_rate, duration)
+            .unwrap()
+            .into(),
+        HarmonicParameters::from_incremental_duration(
+            rate,
+            NominalDeclineRate::new(-0.05),
+            duration,
+        )
+        .unwrap()
+        .into(),
+        HyperbolicParameters::from_incremental_duration(rate, decline_rate, duration, 0.5)
+            .unwrap()
+            .into(),
+        LinearParameters::from_incremental_duration(rate, NominalDeclineRate::new(0.05), duration)
+            .unwrap()
+            .into(),
+    ]
+}
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!(
+        (actual - expected).abs() <= 1e-9 * expected.abs().max(1.),
+        "{actual} != {expected}"
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "fast-math", ignore = "exact values differ with fast-math")]
+fn split_segments_reproduce_the_original() {
+    let split_time = AverageYearsTime { years: 3.7 };
+
+    for segment in splittable_segments() {
+        let (first, second) = segment.split_at(split_time).unwrap();
+
+        assert_close(first.incremental_duration().years, 3.7);
+        assert_close(second.incremental_duration().years, 6.3);
+        assert_close(
+            first.incremental_volume() + second.incremental_volume(),
+            segment.incremental_volume(),
+        );
+        assert_close(
+            second.rate_at_time(AverageYearsTime { years: 0. }).value(),
+            segment.rate_at_time(split_time).value(),
+        );
+        assert_close(
+            second
+                .decline_rate_at_time(AverageYearsTime { years: 0. })
+                .value(),
+            segment.decline_rate_at_time(split_time).value(),
+        );
+        assert_close(second.final_rate().value(), segment.final_rate().value());
+
+        for years in [0., 1., 2.5, 5., 6.3] {
+            assert_close(
+                second.rate_at_time(AverageYearsTime { years }).value(),
+                segment
+                    .rate_at_time(AverageYearsTime { years: 3.7 + years })
+                    .value(),
+            );
+        }
+    }
+}
+
+#[test]
+fn split_times_are_clamped_to_the_segment() {
+    for segment in splittable_segments() {
+        let (first, second) = segment.split_at(AverageYearsTime { years: -1. }).unwrap();
+        assert_eq!(first.incremental_duration().years, 0.);
+        assert_eq!(second, segment);
+
+        let (first, second) = segment
+            .split_at(AverageYearsTime { years: f64::NAN })
+            .unwrap();
+        assert_eq!(first.incremental_duration().years, 0.);
+        assert_eq!(second, segment);
+
+        let (first, second) = segment.split_at(AverageYearsTime { years: 20. }).unwrap();
+        assert_eq!(first, segment);
+        assert_eq!(second.incremental_duration().years, 0.);
+        assert_eq!(second.incremental_volume(), 0.);
+    }
+}
+
+#[test]
+fn split_hyperbolic() {
+    let parameters = HyperbolicParameters::<AverageYearsTime>::from_incremental_duration(
+        ProductionRate::new(1000.),
+        NominalDeclineRate::new(0.5),
+        AverageYearsTime { years: 10. },
+        0.8,
+    )
+    .unwrap();
+
+    let (first, second) = parameters.split_at(AverageYearsTime { years: 2.5 });
+
+    // q = 1000 / (1 + 0.8 * 0.5 * 2.5)^(1 / 0.8) = 1000 / 2^1.25, D = 0.5 / 2.
+    assert_close(second.initial_rate().value(), 1000. / 2_f64.powf(1.25));
+    assert_close(second.initial_decline_rate().value(), 0.25);
+    assert_eq!(second.exponent(), 0.8);
+    assert_eq!(first.initial_rate().value(), 1000.);
+    assert_eq!(first.incremental_duration().years, 2.5);
+}
+
+#[test]
+fn split_linear() {
+    let parameters = LinearParameters::<AverageYearsTime>::from_incremental_duration(
+        ProductionRate::new(1000.),
+        NominalDeclineRate::new(0.05),
+        AverageYearsTime { years: 10. },
+    )
+    .unwrap();
+
+    let (_, second) = parameters.split_at(AverageYearsTime { years: 4. });
+
+    // The slope of 50 per year is kept, relative to the new initial rate of 800.
+    assert_close(second.initial_rate().value(), 800.);
+    assert_close(second.decline_rate().value(), 0.0625);
+}
+
+#[test]
+fn split_time_dependent_segments() {
+    let duration = AverageYearsTime { years: 10. };
+    let logistic_growth: Segment<AverageYearsTime> =
+        LogisticGrowthParameters::from_incremental_duration(10_000., 5., duration, 1.5)
+            .unwrap()
+            .into();
+    let power_law_exponential: Segment<AverageYearsTime> =
+        PowerLawExponentialParameters::from_incremental_duration(
+            ProductionRate::new(1000.),
+            0.3,
+            NominalDeclineRate::new(0.01),
+            duration,
+            0.5,
+        )
+        .unwrap()
+        .into();
+
+    insta::assert_debug_snapshot!(logistic_growth.split_at(AverageYearsTime { years: 1. }), @r#"
+    Err(
+        UnsplittableSegment {
+            segment: "logistic growth",
+        },
+    )
+    "#);
+    let error = power_law_exponential
+        .split_at(AverageYearsTime { years: 1. })
+        .unwrap_err();
+    insta::assert_snapshot!(error, @"power law exponential segments can't be split because their rates depend on the time since the start of production");
+}
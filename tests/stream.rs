@@ -0,0 +1,81 @@
+use decline_curve_analysis::{
+    AverageMonthsTime, ExponentialParameters, FlatParameters, Forecast, NominalDeclineRate,
+    ProductionRate, ProfileAlignment, StreamForecast, StreamKind, TimeGrid, aggregate_streams,
+};
+
+fn months(months: f64) -> AverageMonthsTime {
+    AverageMonthsTime { months }
+}
+
+fn flat(rate: f64, duration: f64) -> Forecast<AverageMonthsTime> {
+    Forecast::new(vec![
+        FlatParameters::from_incremental_duration(ProductionRate::new(rate), months(duration))
+            .unwrap()
+            .into(),
+    ])
+}
+
+/// A declining water injection profile.
+fn injection() -> StreamForecast<AverageMonthsTime> {
+    StreamForecast::injection(Forecast::new(vec![
+        ExponentialParameters::from_incremental_duration(
+            ProductionRate::new(100.),
+            NominalDeclineRate::new(0.1),
+            months(6.),
+        )
+        .unwrap()
+        .into(),
+    ]))
+}
+
+#[test]
+fn injection_is_negative() {
+    let stream = injection();
+
+    insta::assert_debug_snapshot!(stream.kind(), @"Injection");
+    insta::assert_snapshot!(stream.signed_rate_at_time(months(0.)).value(), @"-100");
+    insta::assert_snapshot!(stream.forecast().rate_at_time(months(0.)).value(), @"100");
+    insta::assert_snapshot!(stream.signed_incremental_volume() as f32, @"-451.18835");
+    insta::assert_snapshot!(
+        stream.signed_incremental_volume_between(months(0.), months(1.)) as f32,
+        @"-95.16258"
+    );
+    insta::assert_snapshot!(
+        format!(
+            "{:?}",
+            stream
+                .signed_volumes_by_period(months(2.))
+                .unwrap()
+                .map(|period| period.volume as f32)
+                .collect::<Vec<_>>()
+        ),
+        @"[-181.26924, -148.4107, -121.50841]"
+    );
+
+    let production = StreamForecast::production(flat(50., 3.));
+    insta::assert_snapshot!(production.signed_incremental_volume(), @"150");
+    insta::assert_debug_snapshot!(StreamKind::default(), @"Production");
+}
+
+#[test]
+fn net_profile_of_streams() {
+    let streams = [StreamForecast::production(flat(150., 4.)), injection()];
+    let grid = TimeGrid::uniform(months(1.), 6).unwrap();
+
+    let profile = aggregate_streams(&streams, &grid, &ProfileAlignment::TimeZero).unwrap();
+
+    insta::assert_snapshot!(
+        format!("{:?}", profile.rates().iter().map(|rate| *rate as f32).collect::<Vec<_>>()),
+        @"[50.0, 59.51626, 68.12692, 75.918175, -67.032005, -60.653065, 0.0]"
+    );
+    insta::assert_snapshot!(
+        format!("{:?}", profile.cumulative_volumes().iter().map(|volume| *volume as f32).collect::<Vec<_>>()),
+        @"[54.837418, 118.73075, 190.81822, 270.32004, 206.53065, 148.81163]"
+    );
+
+    insta::assert_snapshot!(
+        aggregate_streams(&streams, &grid, &ProfileAlignment::StartTimes(vec![months(0.)]))
+            .unwrap_err(),
+        @"start times has a length of 1, but expected 2"
+    );
+}
@@ -0,0 +1,83 @@
+use decline_curve_analysis::{
+    AverageYearsTime, ExponentialParameters, FlatParameters, Forecast, NominalDeclineRate,
+    ProductionRate, SuperposedForecast,
+};
+
+fn years(years: f64) -> AverageYearsTime {
+    AverageYearsTime { years }
+}
+
+fn flat(rate: f64, duration: f64) -> Forecast<AverageYearsTime> {
+    Forecast::new(vec![
+        FlatParameters::from_incremental_duration(ProductionRate::new(rate), years(duration))
+            .unwrap()
+            .into(),
+    ])
+}
+
+#[test]
+fn base_with_wedges() {
+    let mut forecast = SuperposedForecast::default();
+    forecast.add(flat(100., 10.), years(0.)).unwrap();
+    forecast.add(flat(50., 2.), years(3.)).unwrap();
+    forecast
+        .add(
+            Forecast::new(vec![
+                ExponentialParameters::from_incremental_duration(
+                    ProductionRate::new(80.),
+                    NominalDeclineRate::new(0.3),
+                    years(6.),
+                )
+                .unwrap()
+                .into(),
+            ]),
+            years(8.),
+        )
+        .unwrap();
+
+    insta::assert_snapshot!(forecast.incremental_duration().years, @"14");
+    insta::assert_snapshot!(forecast.rate_at_time(years(2.)).value(), @"100");
+    insta::assert_snapshot!(forecast.rate_at_time(years(4.)).value(), @"150");
+    insta::assert_snapshot!(forecast.rate_at_time(years(9.)).value(), @"209.26545765453744");
+    insta::assert_snapshot!(forecast.incremental_volume(), @"1322.5869631409103");
+    insta::assert_snapshot!(forecast.incremental_volume_between(years(4.), years(2.)), @"-250");
+    insta::assert_debug_snapshot!(
+        forecast
+            .component_volumes_between(years(0.), years(9.))
+            .collect::<Vec<_>>(),
+        @r"
+    [
+        900.0,
+        100.0,
+        69.1151411515419,
+    ]
+    "
+    );
+    insta::assert_debug_snapshot!(
+        forecast
+            .volumes_by_period(years(5.))
+            .unwrap()
+            .map(|period| period.volume)
+            .collect::<Vec<_>>(),
+        @r"
+    [
+        600.0,
+        620.3168970415929,
+        102.27006609931729,
+    ]
+    "
+    );
+    assert!(
+        (forecast.incremental_volume_at_time(years(14.)) - forecast.incremental_volume()).abs()
+            < 1e-9
+    );
+}
+
+#[test]
+fn empty_superposition() {
+    let forecast = SuperposedForecast::<AverageYearsTime>::new(vec![]);
+
+    assert_eq!(forecast.incremental_duration().years, 0.);
+    assert_eq!(forecast.rate_at_time(years(1.)).value(), 0.);
+    assert_eq!(forecast.incremental_volume(), 0.);
+}
@@ -0,0 +1,225 @@
+use decline_curve_analysis::{
+    AverageYearsTime, ExponentialParameters, Forecast, HarmonicParameters, HyperbolicParameters,
+    NominalDeclineRate, ProductionRate, Segment, TangentEffectiveDeclineRate,
+    TerminalDeclinePolicy,
+};
+
+fn hyperbolic() -> HyperbolicParameters<AverageYearsTime> {
+    HyperbolicParameters::from_incremental_duration(
+        ProductionRate::new(10_000.),
+        NominalDeclineRate::new(1.2),
+        AverageYearsTime { years: 30. },
+        1.2,
+    )
+    .unwrap()
+}
+
+/// Summarizes each segment as its type, duration, and initial decline rate.
+fn summary(forecast: &Forecast<AverageYearsTime>) -> Vec<(&'static str, f32, f32)> {
+    forecast
+        .segments()
+        .iter()
+        .map(|segment| {
+            let kind = match segment {
+                Segment::Exponential(_) => "exponential",
+                Segment::Harmonic(_) => "harmonic",
+                Segment::Hyperbolic(_) => "hyperbolic",
+                _ => "other",
+            };
+            (
+                kind,
+                segment.incremental_duration().years as f32,
+                segment
+                    .decline_rate_at_time(AverageYearsTime { years: 0. })
+                    .value() as f32,
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn nominal_matches_modified_hyperbolic() {
+    let forecast = Forecast::with_terminal_decline(
+        hyperbolic(),
+        TerminalDeclinePolicy::Nominal(NominalDeclineRate::new(0.06)),
+    )
+    .unwrap();
+    let modified = Forecast::modified_hyperbolic(
+        ProductionRate::new(10_000.),
+        NominalDeclineRate::new(1.2),
+        1.2,
+        NominalDeclineRate::new(0.06),
+        AverageYearsTime { years: 30. },
+    )
+    .unwrap();
+
+    assert!(forecast.approx_eq(&modified, 1e-9));
+    insta::assert_debug_snapshot!(summary(&forecast), @r#"
+    [
+        (
+            "hyperbolic",
+            13.194445,
+            1.2,
+        ),
+        (
+            "exponential",
+            16.805555,
+            0.06,
+        ),
+    ]
+    "#);
+}
+
+#[test]
+fn tangent_effective_policy() {
+    let forecast = Forecast::with_terminal_decline(
+        hyperbolic(),
+        TerminalDeclinePolicy::TangentEffective(TangentEffectiveDeclineRate::new(0.06)),
+    )
+    .unwrap();
+
+    // The nominal terminal decline rate is -ln(1 - 0.06).
+    insta::assert_debug_snapshot!(summary(&forecast), @r#"
+    [
+        (
+            "hyperbolic",
+            12.773481,
+            1.2,
+        ),
+        (
+            "exponential",
+            17.226519,
+            0.061875403,
+        ),
+    ]
+    "#);
+    insta::assert_snapshot!(forecast.incremental_volume() as f32, @"35584.77");
+}
+
+#[test]
+fn at_time_policy() {
+    let forecast = Forecast::with_terminal_decline(
+        hyperbolic(),
+        TerminalDeclinePolicy::AtTime(AverageYearsTime { years: 5. }),
+    )
+    .unwrap();
+
+    // The terminal decline continues at the decline rate at the switch.
+    insta::assert_debug_snapshot!(summary(&forecast), @r#"
+    [
+        (
+            "hyperbolic",
+            5.0,
+            1.2,
+        ),
+        (
+            "exponential",
+            25.0,
+            0.14634146,
+        ),
+    ]
+    "#);
+    assert_eq!(
+        forecast.rate_at_time(AverageYearsTime { years: 5. }),
+        hyperbolic().rate_at_time(AverageYearsTime { years: 5. })
+    );
+}
+
+#[test]
+fn harmonic_and_exponential_declines() {
+    let harmonic = HarmonicParameters::from_incremental_duration(
+        ProductionRate::new(10_000.),
+        NominalDeclineRate::new(0.5),
+        AverageYearsTime { years: 30. },
+    )
+    .unwrap();
+    let forecast = Forecast::with_terminal_decline(
+        harmonic,
+        TerminalDeclinePolicy::Nominal(NominalDeclineRate::new(0.1)),
+    )
+    .unwrap();
+    insta::assert_debug_snapshot!(summary(&forecast), @r#"
+    [
+        (
+            "harmonic",
+            8.0,
+            0.5,
+        ),
+        (
+            "exponential",
+            22.0,
+            0.1,
+        ),
+    ]
+    "#);
+
+    // Exponential declines don't have a falling decline rate, so they're unchanged.
+    let exponential = ExponentialParameters::from_incremental_duration(
+        ProductionRate::new(10_000.),
+        NominalDeclineRate::new(0.5),
+        AverageYearsTime { years: 30. },
+    )
+    .unwrap();
+    let forecast = Forecast::with_terminal_decline(
+        exponential,
+        TerminalDeclinePolicy::Nominal(NominalDeclineRate::new(0.1)),
+    )
+    .unwrap();
+    insta::assert_debug_snapshot!(summary(&forecast), @r#"
+    [
+        (
+            "exponential",
+            30.0,
+            0.5,
+        ),
+    ]
+    "#);
+
+    // A decline that starts below the minimum is exponential throughout.
+    let forecast = Forecast::with_terminal_decline(
+        hyperbolic(),
+        TerminalDeclinePolicy::Nominal(NominalDeclineRate::new(2.)),
+    )
+    .unwrap();
+    insta::assert_debug_snapshot!(summary(&forecast), @r#"
+    [
+        (
+            "exponential",
+            30.0,
+            1.2,
+        ),
+    ]
+    "#);
+}
+
+#[test]
+fn terminal_decline_policy_validation() {
+    let policies = [
+        TerminalDeclinePolicy::Nominal(NominalDeclineRate::new(0.)),
+        TerminalDeclinePolicy::TangentEffective(TangentEffectiveDeclineRate::new(1.)),
+        TerminalDeclinePolicy::AtTime(AverageYearsTime { years: -1. }),
+    ];
+
+    insta::assert_debug_snapshot!(
+        policies.map(|policy| Forecast::with_terminal_decline(hyperbolic(), policy)),
+        @r#"
+    [
+        Err(
+            NonPositiveInput {
+                field: "terminal decline rate",
+                value: 0.0,
+            },
+        ),
+        Err(
+            DeclineRateTooHigh,
+        ),
+        Err(
+            NonPositiveInput {
+                field: "terminal time",
+                value: -1.0,
+            },
+        ),
+    ]
+    "#
+    );
+}
@@ -0,0 +1,75 @@
+use decline_curve_analysis::{
+    AverageDaysTime, AverageYearsTime, CalendarTime, DeclineTimeUnit, DynTime, DynTimeUnit,
+    Years365Time,
+};
+use std::ops::Add;
+
+#[test]
+fn time_arithmetic() {
+    let a = AverageYearsTime { years: 1.5 };
+    let b = AverageYearsTime { years: 0.5 };
+
+    insta::assert_debug_snapshot!([a + b, a - b, a * 2., a / 3.], @r"
+    [
+        AverageYearsTime {
+            years: 2.0,
+        },
+        AverageYearsTime {
+            years: 1.0,
+        },
+        AverageYearsTime {
+            years: 3.0,
+        },
+        AverageYearsTime {
+            years: 0.5,
+        },
+    ]
+    ");
+    assert_eq!(
+        Years365Time { value: 1. } + Years365Time { value: 2. },
+        Years365Time { value: 3. }
+    );
+    assert_eq!(
+        CalendarTime { days: 31. } - CalendarTime { days: 1. },
+        CalendarTime { days: 30. }
+    );
+
+    // Runtime times are converted to the unit of the left-hand side.
+    let years = DynTimeUnit::of::<AverageYearsTime>();
+    let days = DynTimeUnit::of::<AverageDaysTime>();
+    let time = DynTime::new(1., years) + DynTime::new(365.25, days);
+    assert_eq!(time, DynTime::new(2., years));
+    assert_eq!(
+        (DynTime::new(730.5, days) - DynTime::new(1., years)) * 2. / 4.,
+        DynTime::new(182.625, days)
+    );
+}
+
+#[test]
+fn time_comparisons() {
+    assert!(AverageDaysTime { days: 30. } < AverageDaysTime { days: 31. });
+    assert!(Years365Time { value: 2. } >= Years365Time { value: 2. });
+    assert_eq!(
+        AverageYearsTime { years: f64::NAN }.partial_cmp(&AverageYearsTime { years: 1. }),
+        None
+    );
+}
+
+#[test]
+fn generic_timeline() {
+    // Times can be composed in generic code that asks for the operators it needs.
+    fn end_of_timeline<Time: DeclineTimeUnit + Add<Output = Time>>(
+        start: Time,
+        durations: &[Time],
+    ) -> Time {
+        durations
+            .iter()
+            .fold(start, |end, duration| end + *duration)
+    }
+
+    let end = end_of_timeline(
+        AverageDaysTime { days: 10. },
+        &[AverageDaysTime { days: 30. }, AverageDaysTime { days: 60. }],
+    );
+    insta::assert_snapshot!(end.days, @"100");
+}
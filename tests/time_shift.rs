@@ -0,0 +1,79 @@
+use decline_curve_analysis::{
+    AverageYearsTime, ExponentialParameters, FlatParameters, Forecast, NominalDeclineRate,
+    ProductionRate, Segment,
+};
+
+fn years(years: f64) -> AverageYearsTime {
+    AverageYearsTime { years }
+}
+
+fn exponential() -> ExponentialParameters<AverageYearsTime> {
+    ExponentialParameters::from_incremental_duration(
+        ProductionRate::new(1000.),
+        NominalDeclineRate::new(0.2),
+        years(10.),
+    )
+    .unwrap()
+}
+
+#[test]
+fn segment_on_an_absolute_axis() {
+    let local = exponential();
+    let shifted = local.clone().with_start_offset(years(2.5)).unwrap();
+
+    assert_eq!(shifted.rate_at_time(years(1.)).value(), 0.);
+    assert_eq!(shifted.decline_rate_at_time(years(1.)).value(), 0.);
+    assert_eq!(shifted.incremental_volume_at_time(years(2.5)), 0.);
+    assert_eq!(
+        shifted.rate_at_time(years(6.)),
+        local.rate_at_time(years(3.5))
+    );
+    assert_eq!(
+        shifted.incremental_volume_at_time(years(6.)),
+        local.incremental_volume_at_time(years(3.5))
+    );
+    assert_eq!(
+        shifted.incremental_volume_between(years(0.), years(4.)),
+        local.incremental_volume_between(years(0.), years(1.5))
+    );
+    assert_eq!(shifted.incremental_volume(), local.incremental_volume());
+
+    insta::assert_debug_snapshot!(shifted.end_time().years, @"12.5");
+    insta::assert_debug_snapshot!(
+        shifted.time_at_rate(ProductionRate::new(500.)).unwrap().years,
+        @"5.965735902799726"
+    );
+    insta::assert_debug_snapshot!(
+        shifted.time_at_incremental_volume(1000.).unwrap().years,
+        @"3.6157177565710485"
+    );
+}
+
+#[test]
+fn forecast_on_an_absolute_axis() {
+    let forecast = Forecast::new(vec![
+        FlatParameters::from_incremental_duration(ProductionRate::new(1000.), years(1.))
+            .unwrap()
+            .into(),
+        Segment::from(exponential()),
+    ]);
+    let shifted = forecast.clone().with_start_offset(years(1.)).unwrap();
+
+    // An unshifted forecast uses the initial rate before its start, but a shifted one is zero.
+    assert_eq!(forecast.rate_at_time(years(-0.5)).value(), 1000.);
+    assert_eq!(shifted.rate_at_time(years(0.5)).value(), 0.);
+    assert_eq!(
+        shifted.rate_at_time(years(3.)),
+        forecast.rate_at_time(years(2.))
+    );
+    assert_eq!(shifted.end_time().years, 12.);
+    assert_eq!(shifted.inner(), &forecast);
+}
+
+#[test]
+fn negative_offset() {
+    insta::assert_snapshot!(
+        exponential().with_start_offset(years(-1.)).unwrap_err(),
+        @"start offset is negative, but expected a positive number"
+    );
+}
@@ -0,0 +1,107 @@
+use decline_curve_analysis::{
+    AverageDaysTime, ExponentRange, FitOptions, FitSpace, HyperbolicParameters, NominalDeclineRate,
+    ProductionRate, Segment, TransientFitOptions, fit_hyperbolic, fit_transient_to_boundary,
+};
+
+/// Samples a transient decline with an exponent of 1.8 that switches to boundary-dominated flow
+/// with an exponent of 0.3 after a year, monthly for five years.
+fn samples() -> Vec<(AverageDaysTime, ProductionRate<AverageDaysTime>)> {
+    let switch_time = AverageDaysTime { days: 365. };
+    let transient = HyperbolicParameters::from_incremental_duration(
+        ProductionRate::new(1000.),
+        NominalDeclineRate::new(0.01),
+        switch_time,
+        1.8,
+    )
+    .unwrap();
+    let boundary = HyperbolicParameters::from_incremental_duration(
+        transient.final_rate(),
+        transient.decline_rate_at_time(switch_time),
+        AverageDaysTime { days: 1500. },
+        0.3,
+    )
+    .unwrap();
+
+    (0..60)
+        .map(|month| {
+            let time = AverageDaysTime {
+                days: month as f64 * 30.4375,
+            };
+            let rate = if time <= switch_time {
+                transient.rate_at_time(time)
+            } else {
+                boundary.rate_at_time(time - switch_time)
+            };
+            (time, rate)
+        })
+        .collect()
+}
+
+#[test]
+fn recovers_switch_time() {
+    let fit = fit_transient_to_boundary(&samples(), &TransientFitOptions::default()).unwrap();
+
+    assert!(
+        (fit.switch_time.days - 365.).abs() < 10.,
+        "switch time is {}",
+        fit.switch_time.days
+    );
+    let [
+        Segment::Hyperbolic(transient),
+        Segment::Hyperbolic(boundary),
+    ] = fit.forecast.segments()
+    else {
+        panic!("expected two hyperbolic segments");
+    };
+    insta::assert_snapshot!(format!("{:.2}", transient.exponent()), @"1.80");
+    insta::assert_snapshot!(format!("{:.2}", boundary.exponent()), @"0.30");
+    insta::assert_snapshot!(format!("{:.1}", transient.initial_rate().value()), @"1000.0");
+    assert_eq!(transient.final_rate(), boundary.initial_rate());
+    assert!(fit.statistics.r_squared > 0.9999);
+    assert_eq!(fit.residuals.len(), 60);
+
+    // A single hyperbolic doesn't fit the change in exponent as well.
+    let single = fit_hyperbolic(&samples(), &FitOptions::default()).unwrap();
+    assert!(fit.statistics.root_mean_squared_error < single.statistics.root_mean_squared_error);
+}
+
+#[test]
+fn fits_in_log_space() {
+    let fit = fit_transient_to_boundary(
+        &samples(),
+        &TransientFitOptions {
+            space: FitSpace::LogRate,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    assert!((fit.switch_time.days - 365.).abs() < 10.);
+}
+
+#[test]
+fn invalid_transient_options() {
+    let result = fit_transient_to_boundary(&samples()[..7], &TransientFitOptions::default());
+    insta::assert_snapshot!(result.unwrap_err(), @"sample count is 7, but expected at least 8");
+
+    let result = fit_transient_to_boundary(
+        &samples(),
+        &TransientFitOptions {
+            min_segment_samples: 1,
+            ..Default::default()
+        },
+    );
+    insta::assert_snapshot!(result.unwrap_err(), @"minimum segment samples is 1, but expected at least 2");
+
+    let result = fit_transient_to_boundary(
+        &samples(),
+        &TransientFitOptions {
+            boundary_exponent: ExponentRange {
+                minimum: 0.,
+                maximum: 0.5,
+                excluded: &[],
+            },
+            ..Default::default()
+        },
+    );
+    insta::assert_snapshot!(result.unwrap_err(), @"exponent is 0, but expected it to be between 0.001 and 2");
+}
@@ -0,0 +1,263 @@
+use decline_curve_analysis::{
+    AverageDaysTime, AverageYearsTime, BindingConstraint, DelayParameters, ExponentialParameters,
+    FlatParameters, Forecast, HyperbolicParameters, LogisticGrowthParameters, NominalDeclineRate,
+    PowerLawExponentialParameters, ProductionRate, Segment,
+};
+
+#[test]
+fn exponential_truncation() {
+    let params = ExponentialParameters::from_incremental_duration(
+        ProductionRate::<AverageYearsTime>::new(1000.),
+        NominalDeclineRate::new(0.5),
+        AverageYearsTime { years: 10. },
+    )
+    .unwrap();
+
+    let truncation = params.truncate_at_rate(ProductionRate::new(100.)).unwrap();
+    assert!(truncation.is_truncated());
+    insta::assert_snapshot!(truncation.parameters.incremental_duration().years, @"4.605170185988092");
+    insta::assert_snapshot!(truncation.parameters.final_rate().value(), @"99.99999999999997");
+    insta::assert_snapshot!(truncation.truncated_duration.years, @"5.394829814011908");
+    insta::assert_snapshot!(truncation.truncated_volume, @"186.52410600182907");
+    insta::assert_snapshot!(
+        truncation.parameters.incremental_volume() + truncation.truncated_volume,
+        @"1986.524106001829"
+    );
+}
+
+#[test]
+fn no_truncation_above_limit() {
+    let params = HyperbolicParameters::from_incremental_duration(
+        ProductionRate::<AverageYearsTime>::new(1000.),
+        NominalDeclineRate::new(0.5),
+        AverageYearsTime { years: 1. },
+        0.9,
+    )
+    .unwrap();
+
+    let truncation = params.truncate_at_rate(ProductionRate::new(10.)).unwrap();
+    assert!(!truncation.is_truncated());
+    assert_eq!(truncation.parameters, params);
+    assert_eq!(truncation.truncated_volume, 0.);
+}
+
+#[test]
+fn initial_rate_below_limit() {
+    let params = ExponentialParameters::from_incremental_duration(
+        ProductionRate::<AverageYearsTime>::new(50.),
+        NominalDeclineRate::new(-0.1),
+        AverageYearsTime { years: 2. },
+    )
+    .unwrap();
+
+    // Inclines that start below the limit are truncated entirely.
+    let truncation = params.truncate_at_rate(ProductionRate::new(100.)).unwrap();
+    insta::assert_snapshot!(truncation.parameters.incremental_duration().years, @"0");
+    assert_eq!(truncation.truncated_volume, params.incremental_volume());
+
+    // Inclines that start above the limit are never truncated.
+    let truncation = params.truncate_at_rate(ProductionRate::new(10.)).unwrap();
+    assert!(!truncation.is_truncated());
+}
+
+#[test]
+fn flat_and_delay_truncation() {
+    let flat = FlatParameters::from_incremental_duration(
+        ProductionRate::<AverageDaysTime>::new(50.),
+        AverageDaysTime { days: 100. },
+    )
+    .unwrap();
+    let truncation = flat.truncate_at_rate(ProductionRate::new(50.)).unwrap();
+    assert!(!truncation.is_truncated());
+    let truncation = flat.truncate_at_rate(ProductionRate::new(51.)).unwrap();
+    insta::assert_snapshot!(truncation.truncated_volume, @"5000");
+
+    let delay = DelayParameters::from_incremental_duration(AverageDaysTime { days: 100. }).unwrap();
+    let truncation = delay.truncate_at_rate(ProductionRate::new(51.)).unwrap();
+    assert!(!truncation.is_truncated());
+}
+
+#[test]
+fn logistic_growth_truncation() {
+    let params = LogisticGrowthParameters::from_incremental_duration(
+        100_000.,
+        5.,
+        AverageYearsTime { years: 30. },
+        1.5,
+    )
+    .unwrap();
+
+    let truncation = params.truncate_at_rate(ProductionRate::new(1000.)).unwrap();
+    insta::assert_snapshot!(truncation.parameters.incremental_duration().years, @"13.029328987949963");
+
+    // The peak rate is below the limit.
+    let truncation = params
+        .truncate_at_rate(ProductionRate::new(1_000_000.))
+        .unwrap();
+    insta::assert_snapshot!(truncation.parameters.incremental_duration().years, @"0");
+}
+
+#[test]
+fn forecast_truncation() {
+    let forecast = Forecast::new(vec![
+        Segment::from(
+            FlatParameters::from_incremental_duration(
+                ProductionRate::<AverageYearsTime>::new(1000.),
+                AverageYearsTime { years: 1. },
+            )
+            .unwrap(),
+        ),
+        ExponentialParameters::from_incremental_duration(
+            ProductionRate::new(1000.),
+            NominalDeclineRate::new(0.5),
+            AverageYearsTime { years: 10. },
+        )
+        .unwrap()
+        .into(),
+        FlatParameters::from_incremental_duration(
+            ProductionRate::new(5.),
+            AverageYearsTime { years: 10. },
+        )
+        .unwrap()
+        .into(),
+    ]);
+
+    let truncation = forecast
+        .truncate_at_rate(ProductionRate::new(100.))
+        .unwrap();
+    insta::assert_snapshot!(truncation.parameters.segments().len(), @"2");
+    insta::assert_snapshot!(truncation.parameters.incremental_duration().years, @"5.605170185988092");
+    insta::assert_snapshot!(truncation.truncated_duration.years, @"15.394829814011908");
+    insta::assert_snapshot!(
+        truncation.parameters.incremental_volume() + truncation.truncated_volume,
+        @"3036.524106001829"
+    );
+    insta::assert_snapshot!(forecast.incremental_volume(), @"3036.524106001829");
+}
+
+#[test]
+fn invalid_economic_limit() {
+    let params = FlatParameters::from_incremental_duration(
+        ProductionRate::<AverageDaysTime>::new(50.),
+        AverageDaysTime { days: 100. },
+    )
+    .unwrap();
+
+    let result = params.truncate_at_rate(ProductionRate::new(-1.));
+    insta::assert_snapshot!(result.unwrap_err(), @"economic limit rate is negative, but expected a positive number");
+
+    let result = params.truncate_at_rate(ProductionRate::new(f64::NAN));
+    insta::assert_snapshot!(result.unwrap_err(), @"economic limit rate is not-a-number, but expected a finite number");
+}
+
+#[test]
+fn forecast_truncation_at_limits() {
+    let forecast = Forecast::new(vec![
+        FlatParameters::from_incremental_duration(
+            ProductionRate::<AverageYearsTime>::new(1000.),
+            AverageYearsTime { years: 1. },
+        )
+        .unwrap()
+        .into(),
+        ExponentialParameters::from_incremental_duration(
+            ProductionRate::new(1000.),
+            NominalDeclineRate::new(0.1),
+            AverageYearsTime { years: 60. },
+        )
+        .unwrap()
+        .into(),
+    ]);
+
+    let truncation = forecast
+        .truncate_at_limits(
+            ProductionRate::new(5.),
+            Some(AverageYearsTime { years: 50. }),
+        )
+        .unwrap();
+    assert_eq!(
+        truncation.binding_constraint,
+        BindingConstraint::MaximumLife
+    );
+    insta::assert_snapshot!(truncation.forecast.incremental_duration().years, @"50");
+    insta::assert_snapshot!(truncation.truncated_duration.years, @"11");
+    insta::assert_snapshot!(
+        truncation.forecast.incremental_volume() + truncation.truncated_volume,
+        @"10975.212478233336"
+    );
+
+    let truncation = forecast
+        .truncate_at_limits(
+            ProductionRate::new(100.),
+            Some(AverageYearsTime { years: 50. }),
+        )
+        .unwrap();
+    assert_eq!(
+        truncation.binding_constraint,
+        BindingConstraint::EconomicLimit
+    );
+    assert_eq!(
+        truncation.forecast,
+        forecast
+            .truncate_at_rate(ProductionRate::new(100.))
+            .unwrap()
+            .parameters
+    );
+
+    let truncation = forecast
+        .truncate_at_limits(ProductionRate::new(1.), None)
+        .unwrap();
+    assert_eq!(
+        truncation.binding_constraint,
+        BindingConstraint::EndOfForecast
+    );
+    assert!(!truncation.is_truncated());
+
+    insta::assert_snapshot!(
+        forecast
+            .truncate_at_limits(ProductionRate::new(10.), Some(AverageYearsTime { years: 0. }))
+            .unwrap_err(),
+        @"maximum life is negative or zero, but expected a positive number"
+    );
+}
+
+#[test]
+fn time_dependent_truncation_at_maximum_life() {
+    let duration = AverageYearsTime { years: 30. };
+    let maximum_life = AverageYearsTime { years: 10. };
+    let forecasts = [
+        Forecast::new(vec![
+            PowerLawExponentialParameters::from_incremental_duration(
+                ProductionRate::<AverageYearsTime>::new(1000.),
+                0.3,
+                NominalDeclineRate::new(0.01),
+                duration,
+                0.5,
+            )
+            .unwrap()
+            .into(),
+        ]),
+        Forecast::new(vec![
+            LogisticGrowthParameters::from_incremental_duration(10_000., 5., duration, 1.5)
+                .unwrap()
+                .into(),
+        ]),
+    ];
+
+    for forecast in forecasts {
+        let truncation = forecast
+            .truncate_at_limits(ProductionRate::new(1e-6), Some(maximum_life))
+            .unwrap();
+        assert_eq!(
+            truncation.binding_constraint,
+            BindingConstraint::MaximumLife
+        );
+        assert_eq!(truncation.forecast.incremental_duration(), maximum_life);
+        assert!(
+            (truncation.forecast.incremental_volume()
+                - forecast
+                    .incremental_volume_between(AverageYearsTime { years: 0. }, maximum_life))
+            .abs()
+                < 1e-6
+        );
+    }
+}
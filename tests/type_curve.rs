@@ -0,0 +1,206 @@
+use decline_curve_analysis::{
+    AverageDaysTime, AverageYearsTime, CalendarDate, DelayParameters, ExponentialParameters,
+    Forecast, HistoryValue, HyperbolicParameters, LinearParameters, LogisticGrowthParameters,
+    NominalDeclineRate, ProductionHistory, ProductionRate, ProductionRecord, TypeCurve,
+    days_in_month, performance_ratio,
+};
+
+fn years(years: f64) -> AverageYearsTime {
+    AverageYearsTime { years }
+}
+
+fn well() -> Forecast<AverageYearsTime> {
+    Forecast::new(vec![
+        DelayParameters::from_incremental_duration(years(0.25))
+            .unwrap()
+            .into(),
+        HyperbolicParameters::from_incremental_duration(
+            ProductionRate::new(1000.),
+            NominalDeclineRate::new(1.5),
+            years(5.),
+            1.1,
+        )
+        .unwrap()
+        .into(),
+        LinearParameters::from_incremental_duration(
+            ProductionRate::new(200.),
+            NominalDeclineRate::new(0.05),
+            years(10.),
+        )
+        .unwrap()
+        .into(),
+    ])
+}
+
+#[test]
+#[cfg_attr(feature = "fast-math", ignore = "exact values differ with fast-math")]
+fn normalize_and_scale() {
+    let well = well();
+    // Normalize a 10,000 ft lateral to 1000 ft.
+    let type_curve = TypeCurve::normalize(&well, 10.).unwrap();
+
+    insta::assert_snapshot!(type_curve.initial_rate().unwrap().value(), @"100");
+    insta::assert_snapshot!(type_curve.forecast().incremental_duration().years, @"15.25");
+
+    // A 7,500 ft lateral.
+    let scaled = type_curve.scale_by(7.5).unwrap();
+    insta::assert_snapshot!(scaled.incremental_duration().years, @"15.25");
+    insta::assert_snapshot!(scaled.rate_at_time(years(3.)).value() / well.rate_at_time(years(3.)).value(), @"0.75");
+    insta::assert_snapshot!(scaled.rate_at_time(years(10.)).value() / well.rate_at_time(years(10.)).value(), @"0.75");
+    assert!((scaled.incremental_volume() - 0.75 * well.incremental_volume()).abs() < 1e-9);
+    assert_eq!(
+        scaled.decline_rate_at_time(years(3.)),
+        well.decline_rate_at_time(years(3.))
+    );
+}
+
+#[test]
+fn scale_to_ip() {
+    let type_curve = TypeCurve::new(well());
+    let scaled = type_curve.scale_to_ip(ProductionRate::new(1500.)).unwrap();
+
+    insta::assert_snapshot!(scaled.rate_at_time(years(0.25)).value(), @"1500");
+    insta::assert_snapshot!(scaled.final_rate().value() / type_curve.forecast().final_rate().value(), @"1.5");
+}
+
+#[test]
+fn scale_logistic_growth() {
+    let type_curve = TypeCurve::new(Forecast::new(vec![
+        LogisticGrowthParameters::from_incremental_duration(1000., 5., years(20.), 1.2)
+            .unwrap()
+            .into(),
+    ]));
+
+    let scaled = type_curve.scale_by(2.).unwrap();
+    insta::assert_snapshot!(scaled.rate_at_time(years(4.)).value() / type_curve.forecast().rate_at_time(years(4.)).value(), @"2");
+
+    insta::assert_snapshot!(
+        type_curve.scale_to_ip(ProductionRate::new(100.)).unwrap_err(),
+        @"type curve initial rate is negative or zero, but expected a positive number"
+    );
+}
+
+#[test]
+fn invalid_scale_factor() {
+    let type_curve = TypeCurve::new(well());
+
+    insta::assert_snapshot!(type_curve.scale_by(0.).unwrap_err(), @"scale factor is negative or zero, but expected a positive number");
+    insta::assert_snapshot!(TypeCurve::normalize(&well(), -1.).unwrap_err(), @"normalizer is negative or zero, but expected a positive number");
+    insta::assert_snapshot!(TypeCurve::<AverageYearsTime>::new(Forecast::new(vec![])).scale_to_ip(ProductionRate::new(100.)).unwrap_err(), @"type curve initial rate is negative or zero, but expected a positive number");
+}
+
+#[test]
+fn performance_ratio_tracks_actuals() {
+    let type_curve = TypeCurve::new(Forecast::new(vec![
+        ExponentialParameters::from_incremental_duration(
+            ProductionRate::<AverageDaysTime>::new(100.),
+            NominalDeclineRate::new(0.002),
+            AverageDaysTime { days: 3000. },
+        )
+        .unwrap()
+        .into(),
+    ]));
+
+    // The well produces 20% above the type curve for three months, then 10% below it, with a
+    // shut-in in the fourth month that doesn't count against it.
+    let mut producing_days = 0.;
+    let records = (1..=7)
+        .map(|month| {
+            let date = CalendarDate::new(2024, month, 1).unwrap();
+            let days = f64::from(days_in_month(2024, month));
+            let value = match month {
+                4 => 0.,
+                _ => {
+                    let factor = if month <= 3 { 1.2 } else { 0.9 };
+                    let volume = type_curve.forecast().incremental_volume_between(
+                        AverageDaysTime {
+                            days: producing_days,
+                        },
+                        AverageDaysTime {
+                            days: producing_days + days,
+                        },
+                    );
+                    producing_days += days;
+                    factor * volume
+                }
+            };
+            ProductionRecord { date, value }
+        })
+        .collect();
+    let history = ProductionHistory::new(HistoryValue::MonthlyVolume, records).unwrap();
+
+    let points = performance_ratio(&history, &type_curve, 2).unwrap();
+    let summary: Vec<_> = points
+        .iter()
+        .map(|point| {
+            (
+                point.time.days,
+                point.cumulative_ratio.map(|ratio| ratio as f32),
+                point.rate_ratio.map(|ratio| ratio as f32),
+            )
+        })
+        .collect();
+    insta::assert_debug_snapshot!(summary, @r"
+    [
+        (
+            31.0,
+            Some(
+                1.2,
+            ),
+            Some(
+                1.2,
+            ),
+        ),
+        (
+            60.0,
+            Some(
+                1.2,
+            ),
+            Some(
+                1.2,
+            ),
+        ),
+        (
+            91.0,
+            Some(
+                1.2,
+            ),
+            Some(
+                1.2,
+            ),
+        ),
+        (
+            122.0,
+            Some(
+                1.1305623,
+            ),
+            Some(
+                1.0546485,
+            ),
+        ),
+        (
+            152.0,
+            Some(
+                1.0904317,
+            ),
+            Some(
+                0.9,
+            ),
+        ),
+        (
+            183.0,
+            Some(
+                1.0628713,
+            ),
+            Some(
+                0.9,
+            ),
+        ),
+    ]
+    ");
+
+    insta::assert_snapshot!(
+        performance_ratio(&history, &type_curve, 0).unwrap_err(),
+        @"window is 0, but expected at least 1"
+    );
+}
@@ -0,0 +1,189 @@
+use decline_curve_analysis::{
+    AverageYearsTime, DelayParameters, ExponentialParameters, FlatParameters, Forecast,
+    LinearParameters, NominalDeclineRate, ProductionRate, Segment, ValidationIssueKind,
+    ValidationOptions,
+};
+
+fn years(years: f64) -> AverageYearsTime {
+    AverageYearsTime { years }
+}
+
+fn flat(rate: f64, duration: f64) -> Segment<AverageYearsTime> {
+    FlatParameters::from_incremental_duration(ProductionRate::new(rate), years(duration))
+        .unwrap()
+        .into()
+}
+
+fn exponential(rate: f64, duration: f64) -> Segment<AverageYearsTime> {
+    ExponentialParameters::from_incremental_duration(
+        ProductionRate::new(rate),
+        NominalDeclineRate::new(0.2),
+        years(duration),
+    )
+    .unwrap()
+    .into()
+}
+
+#[test]
+fn continuous_forecast_is_clean() {
+    let forecast = Forecast::new(vec![
+        DelayParameters::from_incremental_duration(years(0.5))
+            .unwrap()
+            .into(),
+        flat(1000., 1.),
+        exponential(1000., 10.),
+    ]);
+
+    assert!(forecast.validate().is_clean());
+}
+
+#[test]
+fn quality_control_issues() {
+    let forecast = Forecast::new(vec![
+        flat(1000., 1.),
+        exponential(900., 10.),
+        LinearParameters::from_incremental_duration(
+            ProductionRate::new(100.),
+            NominalDeclineRate::new(-0.1),
+            years(2.),
+        )
+        .unwrap()
+        .into(),
+        flat(120., 0.),
+    ]);
+
+    let report = forecast
+        .validate_with(&ValidationOptions {
+            maximum_life: Some(years(12.)),
+            ..ValidationOptions::default()
+        })
+        .unwrap();
+
+    insta::assert_debug_snapshot!(
+        report
+            .issues()
+            .iter()
+            .map(|issue| {
+                let kind = match issue.kind {
+                    ValidationIssueKind::RateDiscontinuity {
+                        previous_final_rate,
+                        initial_rate,
+                    } => format!(
+                        "RateDiscontinuity({} -> {})",
+                        previous_final_rate.value(),
+                        initial_rate.value()
+                    ),
+                    ValidationIssueKind::IncreasingRate {
+                        initial_rate,
+                        final_rate,
+                    } => format!(
+                        "IncreasingRate({} -> {})",
+                        initial_rate.value(),
+                        final_rate.value()
+                    ),
+                    ValidationIssueKind::PastMaximumLife { end, maximum_life } => {
+                        format!("PastMaximumLife({} > {})", end.years, maximum_life.years)
+                    }
+                    kind => format!("{kind:?}"),
+                };
+                (issue.segment_index, issue.severity(), kind)
+            })
+            .collect::<Vec<_>>(),
+        @r#"
+    [
+        (
+            Some(
+                1,
+            ),
+            Warning,
+            "RateDiscontinuity(1000 -> 900)",
+        ),
+        (
+            Some(
+                2,
+            ),
+            Warning,
+            "RateDiscontinuity(121.80175491295144 -> 100)",
+        ),
+        (
+            Some(
+                2,
+            ),
+            Warning,
+            "IncreasingRate(100 -> 120)",
+        ),
+        (
+            Some(
+                2,
+            ),
+            Error,
+            "PastMaximumLife(13 > 12)",
+        ),
+        (
+            Some(
+                3,
+            ),
+            Warning,
+            "ZeroDurationSegment",
+        ),
+        (
+            Some(
+                3,
+            ),
+            Error,
+            "PastMaximumLife(13 > 12)",
+        ),
+    ]
+    "#
+    );
+    assert!(report.has_errors());
+    assert_eq!(report.errors().count(), 2);
+    assert_eq!(report.warnings().count(), report.issues().len() - 2);
+}
+
+#[test]
+fn increasing_rates_can_be_allowed() {
+    let forecast = Forecast::new(vec![
+        LinearParameters::from_incremental_duration(
+            ProductionRate::new(100.),
+            NominalDeclineRate::new(-0.1),
+            years(2.),
+        )
+        .unwrap()
+        .into(),
+    ]);
+
+    assert!(!forecast.validate().is_clean());
+    assert!(
+        forecast
+            .validate_with(&ValidationOptions {
+                report_increasing_rates: false,
+                ..ValidationOptions::default()
+            })
+            .unwrap()
+            .is_clean()
+    );
+}
+
+#[test]
+fn empty_forecast_and_invalid_options() {
+    let forecast = Forecast::<AverageYearsTime>::new(vec![]);
+
+    insta::assert_debug_snapshot!(forecast.validate().issues(), @r"
+    [
+        ValidationIssue {
+            segment_index: None,
+            kind: EmptyForecast,
+        },
+    ]
+    ");
+    insta::assert_snapshot!(
+        forecast
+            .validate_with(&ValidationOptions {
+                maximum_life: Some(years(0.)),
+                ..ValidationOptions::default()
+            })
+            .unwrap_err(),
+        @"maximum life is negative or zero, but expected a positive number"
+    );
+}
@@ -0,0 +1,61 @@
+use decline_curve_analysis::{
+    AverageDaysTime, AverageYearsTime, Barrels, Boe, BoeConversion, CubicMeters,
+    ExponentialParameters, Mcf, NominalDeclineRate, ProductionRate,
+};
+
+#[test]
+fn tagged_rates_from_decline_curves() {
+    let oil = ExponentialParameters::from_incremental_duration(
+        ProductionRate::<AverageDaysTime>::new(100.),
+        NominalDeclineRate::new(0.001),
+        AverageDaysTime { days: 365. },
+    )
+    .unwrap();
+
+    let rate = oil
+        .rate_at_time(AverageDaysTime { days: 0. })
+        .with_volume_unit::<Barrels>();
+    let doubled = rate + rate;
+    insta::assert_snapshot!(doubled.value(), @"200");
+    insta::assert_snapshot!((doubled - rate * 0.5).value(), @"150");
+
+    // Tags can be removed to build new segments from tagged rates.
+    let restarted = ExponentialParameters::from_incremental_duration(
+        doubled.without_volume_unit(),
+        NominalDeclineRate::new(0.001),
+        AverageDaysTime { days: 365. },
+    )
+    .unwrap();
+    assert_eq!(restarted.initial_rate().value(), 200.);
+}
+
+#[test]
+fn volume_unit_conversions() {
+    let oil = ProductionRate::<AverageDaysTime, Barrels>::new(100.);
+    let cubic_meters = ProductionRate::<AverageDaysTime, CubicMeters>::from(oil);
+    insta::assert_snapshot!(cubic_meters.value(), @"15.898729492800001");
+    let round_trip = ProductionRate::<AverageDaysTime, Barrels>::from(cubic_meters);
+    assert!((round_trip.value() - 100.).abs() < 1e-12);
+
+    let gas = ProductionRate::<AverageDaysTime, Mcf>::new(600.);
+    let total: ProductionRate<AverageDaysTime, Boe> =
+        ProductionRate::from(oil) + gas.to_boe(BoeConversion::default());
+    insta::assert_snapshot!(total.value(), @"200");
+    let total = ProductionRate::from(cubic_meters) + gas.to_boe(BoeConversion::new(5.).unwrap());
+    insta::assert_snapshot!(total.value() as f32, @"220");
+}
+
+#[test]
+fn time_unit_conversions_keep_volume_unit() {
+    let gas = ProductionRate::<AverageDaysTime, Mcf>::new(1000.);
+    let yearly: ProductionRate<AverageYearsTime, Mcf> = gas.to_time_unit();
+    insta::assert_snapshot!(yearly.value(), @"365250");
+}
+
+#[test]
+fn boe_conversion_volumes() {
+    let conversion = BoeConversion::new(5.8).unwrap();
+    assert_eq!(conversion.gas_per_boe(), 5.8);
+    insta::assert_snapshot!(conversion.to_boe(1000., 5800.), @"2000");
+    insta::assert_snapshot!(BoeConversion::new(f64::NAN).unwrap_err(), @"gas per BOE is not-a-number, but expected a finite number");
+}
@@ -0,0 +1,34 @@
+#![cfg(feature = "wasm")]
+
+use decline_curve_analysis::{
+    WasmForecast, nominal_to_secant_effective, nominal_to_tangent_effective,
+    secant_effective_to_nominal, tangent_effective_to_nominal,
+};
+
+#[test]
+fn evaluate_forecast() {
+    let mut forecast = WasmForecast::new();
+    forecast.add_delay(30.).unwrap();
+    forecast.add_hyperbolic(1000., 1.5, 365.25, 1.1).unwrap();
+    forecast.add_exponential(200., 0.1, 3652.5).unwrap();
+
+    insta::assert_snapshot!(forecast.segment_count(), @"3");
+    insta::assert_snapshot!(forecast.incremental_duration(), @"4047.75");
+    insta::assert_snapshot!(format!("{:?}", forecast.rates_at_times(&[0., 30., 395.25])), @"[0.0, 1000.0, 200.0]");
+    insta::assert_snapshot!(
+        format!("{:?}", forecast.cumulative_volumes_at_times(&[30., 60.])),
+        @"[0.0, 28297.120608202848]"
+    );
+
+    let monthly_volumes = forecast.monthly_volumes();
+    insta::assert_snapshot!(monthly_volumes.len(), @"133");
+    assert!((monthly_volumes.iter().sum::<f64>() - forecast.incremental_volume()).abs() < 1e-6);
+}
+
+#[test]
+fn decline_rate_conversions() {
+    insta::assert_snapshot!(nominal_to_secant_effective(1.5, 1.1).unwrap(), @"0.5876832066047593");
+    insta::assert_snapshot!(secant_effective_to_nominal(0.6, 1.1).unwrap(), @"1.5817232417845848");
+    insta::assert_snapshot!(nominal_to_tangent_effective(0.1).unwrap(), @"0.09516258196404048");
+    insta::assert_snapshot!(tangent_effective_to_nominal(0.1).unwrap(), @"0.10536051565782631");
+}
@@ -0,0 +1,237 @@
+use decline_curve_analysis::{
+    AverageYearsTime, BoeConversion, ExponentialParameters, Forecast, HyperbolicParameters,
+    NominalDeclineRate, Phase, PhaseForecast, PowerLawExponentialParameters, ProductionRate,
+    RatioForecast, RatioSegment, RatioTrend, WellForecast,
+};
+
+fn years(years: f64) -> AverageYearsTime {
+    AverageYearsTime { years }
+}
+
+fn well() -> WellForecast<AverageYearsTime> {
+    let oil = Forecast::new(vec![
+        HyperbolicParameters::from_incremental_duration(
+            ProductionRate::new(100_000.),
+            NominalDeclineRate::new(1.),
+            years(30.),
+            1.2,
+        )
+        .unwrap()
+        .into(),
+    ]);
+    let gas = RatioForecast::new(vec![
+        RatioSegment::from_incremental_duration(
+            2.,
+            RatioTrend::Exponential { rate: 0.05 },
+            years(30.),
+        )
+        .unwrap(),
+    ]);
+    let water = Forecast::new(vec![
+        ExponentialParameters::from_incremental_duration(
+            ProductionRate::new(50_000.),
+            NominalDeclineRate::new(0.1),
+            years(50.),
+        )
+        .unwrap()
+        .into(),
+    ]);
+
+    WellForecast::new(
+        Phase::Oil,
+        oil,
+        vec![
+            (Phase::Gas, PhaseForecast::Ratio(gas)),
+            (Phase::Water, PhaseForecast::Rate(water)),
+        ],
+    )
+    .unwrap()
+}
+
+#[test]
+fn per_phase_and_boe_volumes() {
+    let well = well();
+    assert_eq!(
+        well.phases().collect::<Vec<_>>(),
+        [Phase::Oil, Phase::Gas, Phase::Water]
+    );
+
+    let oil = well.incremental_volume(Phase::Oil);
+    let gas = well.incremental_volume(Phase::Gas);
+    insta::assert_snapshot!(oil as f32, @"412718.72");
+    insta::assert_snapshot!(gas as f32, @"1409603.8");
+    // The water forecast is longer than the oil forecast, so it's cut off at the end of the
+    // well's life.
+    insta::assert_snapshot!(well.incremental_volume(Phase::Water) as f32, @"475106.47");
+
+    let boe = well.boe_volume(BoeConversion::default());
+    assert!((boe - (oil + gas / 6.)).abs() < 1e-6);
+    let boe = well.boe_volume(BoeConversion::new(5.8).unwrap());
+    assert!((boe - (oil + gas / 5.8)).abs() < 1e-6);
+
+    let yearly = well
+        .volumes_by_period(Phase::Gas, years(1.))
+        .unwrap()
+        .map(|period| period.volume)
+        .sum::<f64>();
+    assert!((yearly - gas).abs() < 1e-6 * gas);
+}
+
+#[test]
+fn truncation_keeps_phases_synchronized() {
+    let well = well();
+    let truncation = well.truncate_at_rate(ProductionRate::new(10_000.)).unwrap();
+    assert!(truncation.is_truncated());
+
+    let truncated = truncation.parameters;
+    let life = truncated.incremental_duration();
+    insta::assert_snapshot!(life.years as f32, @"12.37411");
+
+    for phase in [Phase::Oil, Phase::Gas, Phase::Water] {
+        assert!(truncated.incremental_volume(phase) < well.incremental_volume(phase));
+        assert_eq!(
+            truncated.incremental_volume(phase),
+            well.incremental_volume_between(phase, years(0.), life)
+        );
+        assert_eq!(
+            truncated
+                .rate_at_time(phase, years(life.years + 1.))
+                .value(),
+            0.
+        );
+    }
+}
+
+#[test]
+fn missing_and_duplicate_phases() {
+    let well = well();
+    let oil = well.primary().clone();
+
+    let oil_only = WellForecast::new(Phase::Oil, oil.clone(), vec![]).unwrap();
+    assert_eq!(oil_only.incremental_volume(Phase::Gas), 0.);
+    assert_eq!(oil_only.rate_at_time(Phase::Water, years(1.)).value(), 0.);
+
+    let duplicate = WellForecast::new(
+        Phase::Oil,
+        oil.clone(),
+        vec![(Phase::Oil, PhaseForecast::Rate(oil))],
+    );
+    insta::assert_snapshot!(duplicate.unwrap_err(), @"Oil is forecast more than once");
+
+    insta::assert_snapshot!(BoeConversion::new(0.).unwrap_err(), @"gas per BOE is negative or zero, but expected a positive number");
+}
+
+fn waterflood_well() -> WellForecast<AverageYearsTime> {
+    let oil = well().primary().clone();
+    let water = RatioForecast::new(vec![
+        RatioSegment::from_incremental_duration(0.5, RatioTrend::Constant, years(2.)).unwrap(),
+        RatioSegment::from_incremental_duration(0.5, RatioTrend::Linear { slope: 1. }, years(28.))
+            .unwrap(),
+    ]);
+
+    WellForecast::new(
+        Phase::Oil,
+        oil,
+        vec![(Phase::Water, PhaseForecast::Ratio(water))],
+    )
+    .unwrap()
+}
+
+#[test]
+#[cfg_attr(feature = "fast-math", ignore = "exact values differ with fast-math")]
+fn truncation_at_water_cut() {
+    let well = waterflood_well();
+    let truncation = well.truncate_at_water_cut(0.9).unwrap();
+
+    // A 90% water cut is a WOR of 9, which the linear trend reaches 8.5 years into its segment.
+    let time = truncation.truncation_time.unwrap();
+    assert!((time.years - 10.5).abs() < 1e-9);
+    assert!((truncation.forecast.incremental_duration().years - 10.5).abs() < 1e-9);
+    assert!((well.water_cut_at_time(time) - 0.9).abs() < 1e-9);
+    insta::assert_snapshot!(well.water_cut_at_time(years(1.)) as f32, @"0.33333334");
+
+    for (phase, volume) in &truncation.volumes {
+        assert!((volume - well.incremental_volume_between(*phase, years(0.), time)).abs() < 1e-6);
+    }
+    insta::assert_debug_snapshot!(truncation.volumes, @r"
+    [
+        (
+            Oil,
+            272490.51512861205,
+        ),
+        (
+            Water,
+            687241.6576195956,
+        ),
+    ]
+    ");
+
+    // The water cut limit isn't reached when it's above the final WOR of 28.5.
+    let untruncated = well.truncate_at_water_cut(0.99).unwrap();
+    assert_eq!(untruncated.truncation_time, None);
+    assert_eq!(untruncated.forecast, well);
+
+    // A limit that's already exceeded ends the well immediately.
+    let immediate = well.truncate_at_water_cut(0.2).unwrap();
+    assert_eq!(immediate.truncation_time, Some(years(0.)));
+    assert!(immediate.volumes.iter().all(|(_, volume)| *volume == 0.));
+}
+
+#[test]
+fn truncation_at_water_cut_of_time_dependent_oil() {
+    // Power law exponential rates depend on the time since the start of production, so the
+    // truncated oil forecast must keep its original shape.
+    let oil = Forecast::new(vec![
+        PowerLawExponentialParameters::from_incremental_duration(
+            ProductionRate::new(1000.),
+            0.3,
+            NominalDeclineRate::new(0.01),
+            years(30.),
+            0.5,
+        )
+        .unwrap()
+        .into(),
+    ]);
+    let water = RatioForecast::new(vec![
+        RatioSegment::from_incremental_duration(0.5, RatioTrend::Constant, years(2.)).unwrap(),
+        RatioSegment::from_incremental_duration(0.5, RatioTrend::Linear { slope: 1. }, years(28.))
+            .unwrap(),
+    ]);
+    let well = WellForecast::new(
+        Phase::Oil,
+        oil.clone(),
+        vec![(Phase::Water, PhaseForecast::Ratio(water))],
+    )
+    .unwrap();
+
+    let truncation = well.truncate_at_water_cut(0.9).unwrap();
+    let time = truncation.truncation_time.unwrap();
+    assert!((time.years - 10.5).abs() < 1e-9);
+    let truncated_oil = truncation.forecast.primary();
+    assert!((truncated_oil.incremental_duration().years - 10.5).abs() < 1e-9);
+    assert!(
+        (truncated_oil.rate_at_time(years(5.)).value() - oil.rate_at_time(years(5.)).value()).abs()
+            < 1e-9
+    );
+    assert!(
+        (truncated_oil.incremental_volume() - oil.incremental_volume_between(years(0.), time))
+            .abs()
+            < 1e-6
+    );
+}
+
+#[test]
+fn water_cut_truncation_errors() {
+    insta::assert_snapshot!(
+        well().truncate_at_water_cut(0.9).unwrap_err(),
+        @"water cut truncation requires water to be forecast as a ratio of oil"
+    );
+    insta::assert_snapshot!(
+        waterflood_well().truncate_at_water_cut(1.).unwrap_err(),
+        @"max water cut is 1, but expected it to be greater than 0 and less than 1"
+    );
+    insta::assert_snapshot!(
+        waterflood_well().truncate_at_water_cut(0.).unwrap_err(),
+        @"max water cut is negative or zero, but expected a positive number"
+    );
+}